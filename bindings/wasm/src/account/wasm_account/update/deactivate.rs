@@ -0,0 +1,37 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use js_sys::Promise;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::future_to_promise;
+
+use crate::account::wasm_account::account::AccountRc;
+use crate::account::wasm_account::WasmAccount;
+use crate::common::PromiseVoid;
+use crate::error::WasmResult;
+
+#[wasm_bindgen(js_class = Account)]
+impl WasmAccount {
+  /// Deactivates the DID by publishing a final integration message that removes all verification
+  /// methods and services from the document. No further updates are possible afterwards.
+  #[wasm_bindgen(js_name = deactivate)]
+  pub fn deactivate(&mut self) -> PromiseVoid {
+    let account: Rc<RefCell<AccountRc>> = Rc::clone(&self.0);
+
+    let promise: Promise = future_to_promise(async move {
+      account
+        .borrow_mut()
+        .update_identity()
+        .deactivate()
+        .apply()
+        .await
+        .wasm_result()
+        .map(|_| JsValue::undefined())
+    });
+    promise.unchecked_into::<PromiseVoid>()
+  }
+}
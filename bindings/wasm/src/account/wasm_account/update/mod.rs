@@ -4,6 +4,7 @@
 mod attach_method_relationships;
 mod create_method;
 mod create_service;
+mod deactivate;
 mod delete_method;
 mod delete_service;
 mod detach_method_relationships;
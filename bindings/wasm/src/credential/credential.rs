@@ -191,7 +191,7 @@ impl WasmCredential {
   /// Returns a copy of the proof used to verify the `Credential`.
   #[wasm_bindgen]
   pub fn proof(&self) -> Option<WasmProof> {
-    self.0.proof.clone().map(WasmProof)
+    self.0.proof().cloned().map(WasmProof)
   }
 
   /// Returns a copy of the miscellaneous properties on the `Credential`.
@@ -13,6 +13,7 @@ pub use self::types::*;
 pub use self::validation_options::WasmCredentialValidationOptions;
 pub use self::validation_options::WasmFailFast;
 pub use self::validation_options::WasmPresentationValidationOptions;
+pub use self::validation_options::WasmProofSetPolicy;
 pub use self::validation_options::WasmSubjectHolderRelationship;
 
 mod credential;
@@ -4,6 +4,7 @@
 use identity_iota::credential::CredentialValidationOptions;
 use identity_iota::credential::FailFast;
 use identity_iota::credential::PresentationValidationOptions;
+use identity_iota::credential::ProofSetPolicy;
 use identity_iota::credential::StatusCheck;
 use identity_iota::credential::SubjectHolderRelationship;
 use serde_repr::Deserialize_repr;
@@ -117,6 +118,29 @@ impl From<WasmStatusCheck> for StatusCheck {
   }
 }
 
+/// Declares which proofs of a [proof set](https://www.w3.org/TR/vc-data-integrity/#proof-sets) must be valid
+/// for the signature of a credential or presentation to be considered valid.
+#[wasm_bindgen(js_name = ProofSetPolicy)]
+#[derive(Debug, Clone, Copy, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum WasmProofSetPolicy {
+  /// Every proof must be valid.
+  ///
+  /// This is the default.
+  All = 0,
+  /// At least one proof must be valid.
+  Any = 1,
+}
+
+impl From<WasmProofSetPolicy> for ProofSetPolicy {
+  fn from(policy: WasmProofSetPolicy) -> Self {
+    match policy {
+      WasmProofSetPolicy::All => Self::All,
+      WasmProofSetPolicy::Any => Self::Any,
+    }
+  }
+}
+
 /// Declares how credential subjects must relate to the presentation holder during validation.
 /// See `PresentationValidationOptions::subject_holder_relationship`.
 ///
@@ -175,6 +199,11 @@ interface ICredentialValidationOptions {
     /** Options which affect the verification of the signature on the credential. */
     readonly verifierOptions?: VerifierOptions;
 
+    /** Declares which proofs of a credential secured by a proof set must be valid.
+     *
+     * Default: `ProofSetPolicy.All`. */
+    readonly proofSetPolicy?: ProofSetPolicy;
+
 }"#;
 
 #[wasm_bindgen(typescript_custom_section)]
@@ -193,6 +222,12 @@ interface IPresentationValidationOptions {
      */
     readonly subjectHolderRelationship?: SubjectHolderRelationship;
 
+    /** Declare which proofs of the presentation must be valid if it is secured by a proof set.
+     *
+     * Default: ProofSetPolicy.All
+     */
+    readonly presentationProofSetPolicy?: ProofSetPolicy;
+
 }"#;
 
 /// Declares when validation should return if an error occurs.
@@ -70,4 +70,10 @@ interface IProofOptions {
 
     /** Purpose for which the proof was generated. */
     readonly purpose?: ProofPurpose;
+
+    /** Identifier of the proof, allowing later proofs of a proof chain to reference it. */
+    readonly id?: string;
+
+    /** Identifier of the proof secured by the proof, forming a proof chain. */
+    readonly previousProof?: string;
 }"#;
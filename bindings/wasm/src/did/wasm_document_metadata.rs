@@ -37,6 +37,12 @@ impl WasmDocumentMetadata {
     self.0.previous_message_id.to_string()
   }
 
+  /// Returns whether the DID document has been deactivated.
+  #[wasm_bindgen]
+  pub fn deactivated(&self) -> bool {
+    self.0.deactivated.unwrap_or(false)
+  }
+
   /// Returns a copy of the custom metadata properties.
   #[wasm_bindgen]
   pub fn properties(&self) -> Result<MapStringAny> {
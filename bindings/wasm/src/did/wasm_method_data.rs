@@ -1,8 +1,10 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use identity_iota::did::Jwk;
 use identity_iota::did::MethodData;
 use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
 
 use crate::error::Result;
 use crate::error::WasmResult;
@@ -25,6 +27,27 @@ impl WasmMethodData {
     Self(MethodData::new_multibase(data))
   }
 
+  /// Creates a new `MethodData` variant containing a public JSON Web Key.
+  ///
+  /// ### Errors
+  /// Fails if the JWK is invalid or contains private key material.
+  #[wasm_bindgen(js_name = newJwk)]
+  pub fn new_jwk(jwk: IJwk) -> Result<WasmMethodData> {
+    let jwk: Jwk = jwk.into_serde().wasm_result()?;
+    MethodData::new_jwk(jwk).map(Self).wasm_result()
+  }
+
+  /// Returns the public JSON Web Key if this is a `PublicKeyJwk` variant.
+  #[wasm_bindgen(js_name = tryPublicKeyJwk)]
+  pub fn try_public_key_jwk(&self) -> Result<Option<IJwk>> {
+    self
+      .0
+      .public_key_jwk()
+      .map(|jwk| JsValue::from_serde(jwk).map(JsCast::unchecked_into::<IJwk>))
+      .transpose()
+      .wasm_result()
+  }
+
   /// Returns a `Uint8Array` containing the decoded bytes of the `MethodData`.
   ///
   /// This is generally a public key identified by a `MethodData` value.
@@ -38,6 +61,12 @@ impl WasmMethodData {
   }
 }
 
+#[wasm_bindgen]
+extern "C" {
+  #[wasm_bindgen(typescript_type = "Record<string, any>")]
+  pub type IJwk;
+}
+
 impl_wasm_json!(WasmMethodData, MethodData);
 impl_wasm_clone!(WasmMethodData, MethodData);
 
@@ -20,6 +20,11 @@ impl WasmMethodType {
     WasmMethodType(MethodType::X25519KeyAgreementKey2019)
   }
 
+  #[wasm_bindgen(js_name = JsonWebKey2020)]
+  pub fn json_web_key_2020() -> WasmMethodType {
+    WasmMethodType(MethodType::JsonWebKey2020)
+  }
+
   /// Returns the `MethodType` as a string.
   #[allow(clippy::inherent_to_string)]
   #[wasm_bindgen(js_name = toString)]
@@ -7,17 +7,26 @@ use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
+use identity_account_storage::crypto::KeyDerivation;
 use identity_account_storage::crypto::RemoteEd25519;
 use identity_account_storage::crypto::RemoteKey;
 use identity_account_storage::identity::ChainState;
 use identity_account_storage::storage::Storage;
 use identity_account_storage::types::KeyLocation;
+use identity_core::common::Timestamp;
 use identity_core::convert::FromJson;
 use identity_core::convert::ToJson;
+use identity_core::crypto::Ed25519;
+use identity_core::crypto::JcsEd25519;
+use identity_core::crypto::KeyPair;
 use identity_core::crypto::KeyType;
+use identity_core::crypto::PrivateKey;
 use identity_core::crypto::ProofOptions;
 use identity_core::crypto::SetSignature;
+use identity_core::crypto::Signer;
+use identity_core::diff::ThreeWayMerge;
 use identity_iota_client::chain::DocumentChain;
+use identity_iota_client::chain::IntegrationChain;
 use identity_iota_client::document::ResolvedIotaDocument;
 use identity_iota_client::tangle::Client;
 use identity_iota_client::tangle::PublishType;
@@ -25,6 +34,7 @@ use identity_iota_client::tangle::SharedPtr;
 use identity_iota_core::did::IotaDID;
 use identity_iota_core::did::IotaDIDUrl;
 use identity_iota_core::diff::DiffMessage;
+use identity_iota_core::document::IotaCoreDocument;
 use identity_iota_core::document::IotaDocument;
 use identity_iota_core::document::IotaVerificationMethod;
 use identity_iota_core::tangle::MessageId;
@@ -33,10 +43,16 @@ use serde::Serialize;
 
 use crate::account::AccountBuilder;
 use crate::account::PublishOptions;
+use crate::account::SyncStatus;
+use crate::account::SyncStrategy;
 use crate::types::IdentitySetup;
 use crate::types::IdentityState;
 use crate::types::IdentityUpdater;
+use crate::types::PublicationKind;
+use crate::types::UpdateFailure;
+use crate::types::UpdatePreview;
 use crate::updates::create_identity;
+use crate::updates::ensure_key_derivation;
 use crate::updates::Update;
 use crate::Error;
 use crate::Result;
@@ -117,16 +133,7 @@ where
   /// Callers are expected **not** to load the same [`IotaDID`] into more than one account,
   /// as that would cause race conditions when updating the identity.
   pub(crate) async fn load_identity(setup: AccountSetup<C>, did: IotaDID) -> Result<Self> {
-    // Ensure the DID matches the client network.
-    if did.network_str() != setup.client.network().name_str() {
-      return Err(Error::IotaClientError(
-        identity_iota_client::Error::IncompatibleNetwork(format!(
-          "DID network {} does not match account network {}",
-          did.network_str(),
-          setup.client.network().name_str()
-        )),
-      ));
-    }
+    ensure_network(&did, setup.client.deref())?;
 
     // Ensure the identity exists in storage
     let identity_state_bytes: Vec<u8> = setup.storage.blob_get(&did).await?.ok_or(Error::IdentityNotFound)?;
@@ -141,6 +148,84 @@ where
     Self::with_setup(setup, chain_state, document).await
   }
 
+  /// Recovers an existing identity from its latest document on the Tangle and returns an [`Account`] to
+  /// manage it.
+  ///
+  /// The keys of all methods whose keys were derived from `key_derivation` are derived again and inserted
+  /// into the [`Storage`]. Keys that were not derived from it cannot be recovered.
+  pub(crate) async fn recover_identity(
+    setup: AccountSetup<C>,
+    did: IotaDID,
+    key_derivation: KeyDerivation,
+  ) -> Result<Self> {
+    ensure_network(&did, setup.client.deref())?;
+
+    let resolved: ResolvedIotaDocument = setup.client.read_document(&did).await?;
+
+    Self::recover_resolved(setup, resolved, key_derivation).await
+  }
+
+  /// Recovers the identity of the `resolved` document, see [`Account::recover_identity`].
+  pub(crate) async fn recover_resolved(
+    setup: AccountSetup<C>,
+    resolved: ResolvedIotaDocument,
+    key_derivation: KeyDerivation,
+  ) -> Result<Self> {
+    let did: IotaDID = resolved.document.id().to_owned();
+    ensure_key_derivation(&did, &key_derivation)?;
+
+    // Registers the identity in the storage and fails if it exists already.
+    let root: KeyPair = key_derivation.derive_root()?;
+    setup
+      .storage
+      .did_create(
+        setup.client.network().name(),
+        IotaDocument::DEFAULT_METHOD_FRAGMENT,
+        Some(root.private().clone()),
+      )
+      .await?;
+
+    let mut account: Self = Self::with_setup(setup, ChainState::new(), resolved.document.clone()).await?;
+
+    if let Err(error) = account.recover_state(&resolved, &key_derivation).await {
+      if let Err(purge_error) = account.storage.did_purge(&did).await {
+        log::warn!("[recover_identity] failed to remove {}: {}", did, purge_error);
+      }
+
+      return Err(error);
+    }
+
+    Ok(account)
+  }
+
+  /// Inserts the recoverable keys of the document into the storage and stores its published state.
+  async fn recover_state(&mut self, resolved: &ResolvedIotaDocument, key_derivation: &KeyDerivation) -> Result<()> {
+    for method in self.document.methods() {
+      // Methods without an Ed25519 or X25519 key cannot have been derived from the seed.
+      let location: KeyLocation = match KeyLocation::from_verification_method(method) {
+        Ok(location) => location,
+        Err(error) => {
+          log::warn!("[recover_identity] skipping {}: {}", method.id(), error);
+          continue;
+        }
+      };
+      let public_key: Vec<u8> = method.data().try_decode()?;
+
+      match key_derivation.find(location.key_type, location.fragment(), &public_key)? {
+        Some((_, keypair)) => {
+          let private_key: PrivateKey = keypair.private().clone();
+          self.storage.key_insert(self.did(), &location, private_key).await?;
+        }
+        None => log::warn!("[recover_identity] cannot derive the key of {}", method.id()),
+      }
+    }
+
+    self.set_published_state(resolved).await?;
+    self.save(false).await?;
+
+    Ok(())
+  }
+
   // ===========================================================================
   // Getters & Setters
   // ===========================================================================
@@ -238,6 +323,9 @@ where
   /// Removes the identity from the local storage entirely.
   ///
   /// Note: This will remove all associated document updates and key material - recovery is NOT POSSIBLE!
+  ///
+  /// This does not affect the DID on the Tangle, see [`IdentityUpdater::deactivate`] to
+  /// deactivate it before deleting the local state.
   pub async fn delete_identity(self) -> Result<()> {
     // Remove all associated keys and events
     self.storage().did_purge(self.did()).await?;
@@ -282,10 +370,24 @@ where
     Ok(())
   }
 
+  /// Returns whether the document has changes that were not published yet, i.e. whether [`Account::publish`]
+  /// would publish an update.
+  pub async fn has_unpublished_changes(&self) -> Result<bool> {
+    if self.chain_state().is_new_identity() {
+      return Ok(true);
+    }
+
+    let old_doc: IotaDocument = self.load_document().await?;
+    let deactivation: bool = self.document.is_deactivated() && !old_doc.is_deactivated();
+
+    Ok(deactivation || PublishType::new(&old_doc, &self.document).is_some())
+  }
+
   /// Fetches the latest document from the tangle and **overwrites** the local document.
   ///
   /// If a DID is managed from distributed accounts, this should be called before making changes
-  /// to the identity, to avoid publishing updates that would be ignored.
+  /// to the identity, to avoid publishing updates that would be ignored. See [`Account::sync`] to keep
+  /// unpublished changes.
   pub async fn fetch_document(&mut self) -> Result<()> {
     let iota_did: &IotaDID = self.did();
     let mut document_chain: DocumentChain = self.client.read_document_chain(iota_did).await?;
@@ -313,6 +415,156 @@ where
     Ok(())
   }
 
+  /// Rebases the unpublished local changes onto `resolved`, a newer version of the document
+  /// resolved from the Tangle, e.g. after an update was published from another account.
+  ///
+  /// The changes made locally since the last publication and the changes contained in `resolved`
+  /// are combined with a [`ThreeWayMerge`] of the core documents, while the metadata is taken from
+  /// `resolved`. Afterwards `resolved` is considered the last published state, so the next publication
+  /// only contains the local changes.
+  ///
+  /// The local state is unmodified if the changes cannot be combined.
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`Error::MergeConflict`] if the same values were changed differently and if the combined
+  /// document is invalid, e.g. if a verification relationship references a method removed by the other side.
+  pub async fn rebase_document(&mut self, resolved: ResolvedIotaDocument) -> Result<()> {
+    if resolved.document.id() != self.did() {
+      return Err(Error::InvalidIdentityState(
+        "cannot rebase onto the document of a different identity".to_owned(),
+      ));
+    }
+
+    if self.chain_state().is_new_identity() {
+      return Err(Error::InvalidIdentityState(
+        "cannot rebase an unpublished identity".to_owned(),
+      ));
+    }
+
+    let base: IotaDocument = self.load_document().await?;
+
+    self.rebase_onto(base, resolved).await
+  }
+
+  /// Compares the local state with `chain`, the [`DocumentChain`] of the identity resolved from the Tangle.
+  ///
+  /// See [`SyncStatus`].
+  pub async fn sync_status(&self, chain: &DocumentChain) -> Result<SyncStatus> {
+    if chain.id() != self.did() {
+      return Err(Error::InvalidIdentityState(
+        "cannot sync with the document chain of a different identity".to_owned(),
+      ));
+    }
+
+    let integration_message_id: &MessageId = self.chain_state.last_integration_message_id();
+    let diff_message_id: &MessageId = self.chain_state.last_diff_message_id();
+
+    // Whether diffs were published on top of the last integration document.
+    let has_diffs: bool = !diff_message_id.is_null() && diff_message_id != integration_message_id;
+
+    // Whether the last publication is the latest state on the Tangle, or `None` if it is not part of it.
+    let is_latest: Option<bool> = if integration_message_id.is_null() {
+      None
+    } else if integration_message_id == chain.integration_message_id() {
+      let diff_message_ids: Vec<&MessageId> = chain.diff().iter().map(DiffMessage::message_id).collect();
+
+      if has_diffs {
+        diff_message_ids
+          .iter()
+          .position(|message_id| *message_id == diff_message_id)
+          .map(|index| index + 1 == diff_message_ids.len())
+      } else {
+        Some(diff_message_ids.is_empty())
+      }
+    } else if has_diffs {
+      // The diffs of superseded integration documents are not resolved, so they cannot be related to the history.
+      None
+    } else {
+      integration_document(chain, integration_message_id).map(|_| false)
+    };
+
+    let status: SyncStatus = match (is_latest, self.has_unpublished_changes().await?) {
+      (Some(true), false) => SyncStatus::UpToDate,
+      (Some(true), true) => SyncStatus::LocalAhead,
+      (Some(false), false) => SyncStatus::RemoteNewer,
+      (Some(false), true) => SyncStatus::Diverged,
+      (None, _) => SyncStatus::Forked,
+    };
+
+    Ok(status)
+  }
+
+  /// Resolves the identity from the Tangle and reconciles the local state with it according to `strategy`.
+  ///
+  /// See [`Account::sync_with`].
+  pub async fn sync(&mut self, strategy: SyncStrategy) -> Result<SyncStatus> {
+    let chain: DocumentChain = self.client.read_document_chain(self.did()).await?;
+
+    self.sync_with(&chain, strategy).await
+  }
+
+  /// Reconciles the local state with `chain`, the [`DocumentChain`] of the identity resolved from the Tangle,
+  /// according to `strategy` and returns the [`SyncStatus`] prior to the reconciliation.
+  ///
+  /// The local state is only modified if the Tangle contains updates the account is not aware of,
+  /// see [`SyncStatus::is_behind`]. Unpublished changes on top of the latest state on the Tangle are kept.
+  ///
+  /// # Errors
+  ///
+  /// When rebasing, fails with [`Error::MergeConflict`] if the same values were changed differently and with
+  /// [`Error::UnresolvableFork`] if no document of the local history is part of the history on the Tangle.
+  /// Rebasing onto or publishing on top of a deactivated document fails as well.
+  pub async fn sync_with(&mut self, chain: &DocumentChain, strategy: SyncStrategy) -> Result<SyncStatus> {
+    let status: SyncStatus = self.sync_status(chain).await?;
+
+    if !status.is_behind() {
+      return Ok(status);
+    }
+
+    let resolved: ResolvedIotaDocument = chain.current().clone();
+
+    match strategy {
+      SyncStrategy::AdoptRemote => {
+        self.set_published_state(&resolved).await?;
+        self.document = resolved.document;
+
+        self.increment_actions();
+        self.save(false).await?;
+      }
+      SyncStrategy::Rebase => {
+        let base: IotaDocument = self.common_document(chain, status).await?;
+
+        self.rebase_onto(base, resolved).await?;
+      }
+      SyncStrategy::ForcePublish => {
+        if resolved.document.is_deactivated() {
+          return Err(identity_iota_core::Error::DocumentDeactivated.into());
+        }
+
+        // Publish the local document as an update of the latest document on the Tangle. The latter is only
+        // stored as the published state once the publication succeeded.
+        let chain_state: ChainState = self.chain_state.clone();
+        self
+          .chain_state
+          .set_last_integration_message_id(resolved.integration_message_id);
+        self.chain_state.set_last_diff_message_id(resolved.diff_message_id);
+
+        if let Err(error) = self
+          .publish_changes(&resolved.document, &PublishOptions::default())
+          .await
+        {
+          self.chain_state = chain_state;
+          return Err(error);
+        }
+
+        self.store_state().await?;
+      }
+    }
+
+    Ok(status)
+  }
+
   // ===========================================================================
   // Misc. Private
   // ===========================================================================
@@ -334,9 +586,92 @@ where
       .ok_or_else(|| Error::InvalidIdentityState("document not found".to_owned()))
   }
 
+  /// Combines the changes made locally to `base` with the changes made to it on the Tangle, which resulted
+  /// in `resolved`, and considers `resolved` the last published state.
+  async fn rebase_onto(&mut self, base: IotaDocument, resolved: ResolvedIotaDocument) -> Result<()> {
+    if resolved.document.is_deactivated() {
+      return Err(identity_iota_core::Error::DocumentDeactivated.into());
+    }
+
+    let merge: ThreeWayMerge = ThreeWayMerge::new(
+      base.core_document(),
+      self.document.core_document(),
+      resolved.document.core_document(),
+    )
+    .map_err(identity_iota_core::Error::from)?;
+
+    if !merge.is_clean() {
+      return Err(Error::MergeConflict(
+        merge.conflicts().iter().map(|conflict| conflict.path.clone()).collect(),
+      ));
+    }
+
+    let core_document: IotaCoreDocument = merge.into_merged().map_err(identity_iota_core::Error::from)?;
+    let mut document: IotaDocument = resolved.document.clone();
+
+    if &core_document != document.core_document() {
+      *document.core_document_mut() = core_document;
+      document.metadata.updated = Some(Timestamp::now_utc());
+      document.proof = None;
+    }
+
+    // Changes that are valid on their own can still be invalid combined, e.g. a reference to a removed method.
+    document.check_methods()?;
+
+    // Store the resolved document as the last published state the local changes are based on.
+    self.set_published_state(&resolved).await?;
+    self.document = document;
+
+    self.increment_actions();
+    self.save(false).await?;
+
+    Ok(())
+  }
+
+  /// Returns the latest document that is part of both the local history and the history in `chain`.
+  async fn common_document(&self, chain: &DocumentChain, status: SyncStatus) -> Result<IotaDocument> {
+    if status != SyncStatus::Forked {
+      return self.load_document().await;
+    }
+
+    if self.chain_state.is_new_identity() {
+      return Err(Error::UnresolvableFork);
+    }
+
+    // Forked diffs are based on the integration document they were published on, whereas a forked
+    // integration document is based on the document it references.
+    let base: Option<&ResolvedIotaDocument> =
+      match integration_document(chain, self.chain_state.last_integration_message_id()) {
+        Some(resolved) => Some(resolved),
+        None => integration_document(chain, &self.load_document().await?.metadata.previous_message_id),
+      };
+
+    base
+      .map(|resolved| resolved.document.clone())
+      .ok_or(Error::UnresolvableFork)
+  }
+
+  /// Stores `resolved` as the last published state and updates the chain state accordingly.
+  async fn set_published_state(&mut self, resolved: &ResolvedIotaDocument) -> Result<()> {
+    let mut chain_state: ChainState = self.chain_state.clone();
+    chain_state.set_last_integration_message_id(resolved.integration_message_id);
+    chain_state.set_last_diff_message_id(resolved.diff_message_id);
+
+    let identity_state: IdentityState = IdentityState::new(Some(&resolved.document), Some(&chain_state))?;
+    self.storage.blob_set(self.did(), identity_state.to_json_vec()?).await?;
+
+    self.chain_state = chain_state;
+
+    Ok(())
+  }
+
   pub(crate) async fn process_update(&mut self, update: Update) -> Result<()> {
+    self.check_update(&update)?;
+
     let did = self.did().to_owned();
-    update.process(&did, &mut self.document, self.storage.deref()).await?;
+    update
+      .process(&did, &mut self.document, self.storage.deref(), &mut Vec::new())
+      .await?;
 
     self.increment_actions();
 
@@ -345,6 +680,186 @@ where
     Ok(())
   }
 
+  /// Applies `updates` to the document and publishes the result once, or restores the previous state
+  /// and removes the keys created by `updates` if any update or the publication fails.
+  pub(crate) async fn commit_updates(&mut self, updates: Vec<Update>) -> Result<()> {
+    if updates.is_empty() {
+      return Ok(());
+    }
+
+    // Validate the updates before creating any keys.
+    let preview: UpdatePreview = self.preview_updates(&updates).await?;
+
+    if let Some(failure) = preview.failures.into_iter().next() {
+      return Err(failure.error);
+    }
+
+    let document: IotaDocument = self.document.clone();
+    let chain_state: ChainState = self.chain_state.clone();
+    let mut created_keys: Vec<KeyLocation> = Vec::new();
+
+    let result: Result<()> = self.apply_updates(updates, &mut created_keys).await;
+
+    // The published document references the created keys, so they must be kept once published.
+    if result.is_err() && self.chain_state == chain_state {
+      self.document = document;
+
+      for location in created_keys.iter() {
+        if let Err(error) = self.storage.key_delete(self.did(), location).await {
+          log::warn!("[commit_updates] failed to remove key {}: {}", location, error);
+        }
+      }
+    }
+
+    result
+  }
+
+  async fn apply_updates(&mut self, updates: Vec<Update>, created_keys: &mut Vec<KeyLocation>) -> Result<()> {
+    let did: IotaDID = self.did().to_owned();
+
+    for update in updates {
+      update
+        .process(&did, &mut self.document, self.storage.deref(), created_keys)
+        .await?;
+    }
+
+    self.increment_actions();
+
+    self.publish_internal(false, PublishOptions::default()).await
+  }
+
+  /// Applies `updates` to a copy of the document and previews its publication, without modifying the
+  /// storage or accessing the network.
+  pub(crate) async fn preview_updates(&self, updates: &[Update]) -> Result<UpdatePreview> {
+    let old_doc: &IotaDocument = self.document();
+    let mut document: IotaDocument = old_doc.clone();
+    let mut failures: Vec<UpdateFailure> = Vec::new();
+
+    for (index, update) in updates.iter().enumerate() {
+      // Apply each update to a copy, so a failed update does not leave partial changes behind.
+      let mut updated: IotaDocument = document.clone();
+
+      let result: Result<()> = match self.check_update(update) {
+        Ok(()) => {
+          update
+            .clone()
+            .preview(self.did(), &mut updated, self.storage.deref())
+            .await
+        }
+        Err(error) => Err(error),
+      };
+
+      match result {
+        Ok(()) => document = updated,
+        Err(error) => failures.push(UpdateFailure {
+          index: Some(index),
+          error,
+        }),
+      }
+    }
+
+    // Updates that are valid on their own can still result in an invalid document, e.g. if the only
+    // signing method is replaced by a method that cannot sign.
+    if let Err(error) = document.check_methods() {
+      failures.push(UpdateFailure {
+        index: None,
+        error: error.into(),
+      });
+    }
+
+    let publish_type: Option<PublishType> = if self.chain_state().is_new_identity() {
+      Some(PublishType::Integration)
+    } else {
+      self.publish_type(old_doc, &document, &PublishOptions::default())
+    };
+
+    let mut diff_message: Option<DiffMessage> = None;
+    let mut signature: Result<()> = Ok(());
+
+    let message_size: usize = match publish_type {
+      Some(PublishType::Integration) => {
+        let signing_doc: &IotaDocument = if self.chain_state().is_new_identity() {
+          &document
+        } else {
+          old_doc
+        };
+
+        let mut message: IotaDocument = document.clone();
+        message.metadata.previous_message_id = *self.chain_state().last_integration_message_id();
+        signature = sign_ephemeral(signing_doc, &mut message);
+
+        self.client.pack_message(&message)?.len()
+      }
+      Some(PublishType::Diff) => {
+        let previous_message_id: MessageId = match self.chain_state().last_diff_message_id() {
+          message_id if message_id.is_null() => *self.chain_state().last_integration_message_id(),
+          message_id => *message_id,
+        };
+
+        let mut message: DiffMessage = DiffMessage::new(old_doc, &document, previous_message_id)?;
+        diff_message = Some(message.clone());
+        signature = sign_ephemeral(old_doc, &mut message);
+
+        self.client.pack_message(&message)?.len()
+      }
+      None => 0,
+    };
+
+    if let Err(error) = signature {
+      failures.push(UpdateFailure { index: None, error });
+    }
+
+    let publication_kind: Option<PublicationKind> = publish_type.map(|publish_type| match publish_type {
+      PublishType::Integration => PublicationKind::Integration,
+      PublishType::Diff => PublicationKind::Diff,
+    });
+
+    Ok(UpdatePreview {
+      document,
+      publication_kind,
+      diff_message,
+      message_size,
+      failures,
+    })
+  }
+
+  /// Checks whether `update` can be processed in the current state of the identity.
+  fn check_update(&self, update: &Update) -> Result<()> {
+    // The deactivation must be signed by a method of the previously published document.
+    if matches!(update, Update::Deactivate {}) && self.chain_state().is_new_identity() {
+      return Err(Error::InvalidIdentityState(
+        "cannot deactivate an unpublished identity".to_owned(),
+      ));
+    }
+
+    Ok(())
+  }
+
+  /// Returns how the changes from `old_doc` to `new_doc` are published, or `None` if there are none.
+  fn publish_type(
+    &self,
+    old_doc: &IotaDocument,
+    new_doc: &IotaDocument,
+    options: &PublishOptions,
+  ) -> Option<PublishType> {
+    // NOTE: always publish an integration update (if needed); diff chain slated for removal.
+    // NOTE: deactivation is only valid as an integration update.
+    let deactivation: bool = new_doc.is_deactivated() && !old_doc.is_deactivated();
+
+    if options.force_integration_update || deactivation {
+      Some(PublishType::Integration)
+    } else if let Some(publish_type) = PublishType::new(old_doc, new_doc) {
+      if self.config.testmode {
+        // Allow tests to pass as normal.
+        Some(publish_type)
+      } else {
+        Some(PublishType::Integration)
+      }
+    } else {
+      None
+    }
+  }
+
   async fn sign_self(
     &self,
     old_doc: &IotaDocument,
@@ -382,35 +897,10 @@ where
     } else {
       // Existing identity
       let old_doc: IotaDocument = self.load_document().await?;
-      let new_doc: &IotaDocument = self.document();
 
-      // NOTE: always publish an integration update (if needed); diff chain slated for removal.
-      let publish_type: Option<PublishType> = if options.force_integration_update {
-        Some(PublishType::Integration)
-      } else if let Some(publish_type) = PublishType::new(&old_doc, new_doc) {
-        if self.config.testmode {
-          // Allow tests to pass as normal.
-          Some(publish_type)
-        } else {
-          Some(PublishType::Integration)
-        }
-      } else {
-        None
-      };
-
-      match publish_type {
-        Some(PublishType::Integration) => {
-          self
-            .publish_integration_change(Some(&old_doc), &options.sign_with)
-            .await?;
-        }
-        Some(PublishType::Diff) => {
-          self.publish_diff_change(&old_doc, &options.sign_with).await?;
-        }
-        None => {
-          // Can return early, as there is nothing new to publish or store.
-          return Ok(());
-        }
+      if !self.publish_changes(&old_doc, &options).await? {
+        // Can return early, as there is nothing new to publish or store.
+        return Ok(());
       }
     }
 
@@ -419,6 +909,27 @@ where
     Ok(())
   }
 
+  /// Publishes the changes from `old_doc` to the current document, without storing the new state.
+  ///
+  /// Returns `false` if there is nothing to publish.
+  async fn publish_changes(&mut self, old_doc: &IotaDocument, options: &PublishOptions) -> Result<bool> {
+    let publish_type: Option<PublishType> = self.publish_type(old_doc, self.document(), options);
+
+    match publish_type {
+      Some(PublishType::Integration) => {
+        self
+          .publish_integration_change(Some(old_doc), &options.sign_with)
+          .await?;
+      }
+      Some(PublishType::Diff) => {
+        self.publish_diff_change(old_doc, &options.sign_with).await?;
+      }
+      None => return Ok(false),
+    }
+
+    Ok(true)
+  }
+
   async fn store_state(&self) -> Result<()> {
     let identity_state: IdentityState = IdentityState::new(Some(&self.document), Some(&self.chain_state))?;
     self.storage.blob_set(self.did(), identity_state.to_json_vec()?).await?;
@@ -561,6 +1072,60 @@ where
   }
 }
 
+/// Ensures the network of `did` is the network of the `client`.
+fn ensure_network(did: &IotaDID, client: &Client) -> Result<()> {
+  if did.network_str() != client.network().name_str() {
+    return Err(Error::IotaClientError(
+      identity_iota_client::Error::IncompatibleNetwork(format!(
+        "DID network {} does not match account network {}",
+        did.network_str(),
+        client.network().name_str()
+      )),
+    ));
+  }
+
+  Ok(())
+}
+
+/// Signs `data` with the default signing method of `signing_doc` using an ephemeral key, which results in
+/// a proof of the same size as a valid one.
+fn sign_ephemeral<D>(signing_doc: &IotaDocument, data: &mut D) -> Result<()>
+where
+  D: Serialize + SetSignature,
+{
+  let method: &IotaVerificationMethod = signing_doc.default_signing_method()?;
+
+  match KeyLocation::from_verification_method(method)?.key_type {
+    KeyType::Ed25519 => {
+      let keypair: KeyPair = KeyPair::new(KeyType::Ed25519)?;
+      JcsEd25519::<Ed25519>::create_signature(
+        data,
+        method.id().to_string(),
+        keypair.private().as_ref(),
+        ProofOptions::default(),
+      )?;
+    }
+    KeyType::X25519 => return Err(identity_did::Error::InvalidMethodType.into()),
+  }
+
+  Ok(())
+}
+
+/// Returns the document of the integration chain of `chain` published in the message with `message_id`.
+fn integration_document<'chain>(
+  chain: &'chain DocumentChain,
+  message_id: &MessageId,
+) -> Option<&'chain ResolvedIotaDocument> {
+  let integration_chain: &IntegrationChain = chain.integration_chain();
+
+  integration_chain
+    .history()
+    .unwrap_or_default()
+    .iter()
+    .chain(std::iter::once(integration_chain.current()))
+    .find(|resolved| &resolved.integration_message_id == message_id)
+}
+
 #[cfg(feature = "revocation-bitmap")]
 mod account_revocation {
   use super::Account;
@@ -672,3 +1237,103 @@ mod account_encryption {
     }
   }
 }
+
+#[cfg(feature = "oidc")]
+mod account_oidc {
+  use std::ops::Deref;
+  use std::time::Duration;
+
+  use identity_account_storage::crypto::RemoteKey;
+  use identity_account_storage::types::KeyLocation;
+  use identity_core::common::Object;
+  use identity_core::crypto::ProofOptions;
+  use identity_credential::oidc::AuthorizationRequest;
+  use identity_credential::oidc::AuthorizationResponse;
+  use identity_credential::oidc::OidcError;
+  use identity_credential::oidc::PresentationSubmission;
+  use identity_credential::oidc::SelfIssuedIdToken;
+  use identity_credential::presentation::Presentation;
+  use identity_did::did::DID;
+  use identity_iota_client::tangle::Client;
+  use identity_iota_client::tangle::SharedPtr;
+  use identity_iota_core::document::IotaVerificationMethod;
+  use libjose::jws::Encoder;
+  use libjose::jws::JwsHeader;
+  use libjose::jws::Recipient;
+  use libjose::jwt::JwtClaims;
+
+  use super::Account;
+  use crate::Error;
+  use crate::Result;
+
+  /// Validity of the ID tokens in authorization responses.
+  const ID_TOKEN_EXPIRES_IN: Duration = Duration::from_secs(600);
+
+  impl<C> Account<C>
+  where
+    C: SharedPtr<Client>,
+  {
+    /// Creates a Self-Issued ID Token in response to `request`, signed with the Ed25519 key
+    /// of the verification method identified by `fragment` and valid for `expires_in`.
+    pub async fn create_id_token(
+      &self,
+      fragment: &str,
+      request: &AuthorizationRequest,
+      expires_in: Duration,
+    ) -> Result<String> {
+      let method: &IotaVerificationMethod = self
+        .document()
+        .resolve_method(fragment, None)
+        .ok_or(Error::DIDError(identity_did::Error::MethodNotFound))?;
+
+      let location: KeyLocation = KeyLocation::from_verification_method(method)?;
+      let private: RemoteKey<'_> = RemoteKey::new(self.did(), &location, self.storage().deref());
+
+      let header: JwsHeader = SelfIssuedIdToken::header(&method.id().to_string());
+      let claims: JwtClaims<Object> = SelfIssuedIdToken::claims(self.did().as_str(), request, expires_in);
+
+      Encoder::new()
+        .recipient(Recipient::new_with_async_signer(&private).protected(&header))
+        .encode_serde_async(&claims)
+        .await
+        .map_err(Into::into)
+    }
+
+    /// Creates an [`AuthorizationResponse`] to `request` signed with the verification method
+    /// identified by `fragment`.
+    ///
+    /// Includes a Self-Issued ID Token valid for 10 minutes if requested. If a `vp_token` is requested, `presentation`
+    /// is signed with the `nonce` of the request as challenge and its `client_id` as domain,
+    /// and returned with `submission`.
+    pub async fn create_authorization_response(
+      &self,
+      fragment: &str,
+      request: &AuthorizationRequest,
+      presentation: Option<Presentation>,
+      submission: Option<PresentationSubmission>,
+    ) -> Result<AuthorizationResponse> {
+      request.check_structure()?;
+
+      let mut response: AuthorizationResponse = AuthorizationResponse::new(request);
+
+      if request.requests_id_token() {
+        response.id_token = Some(self.create_id_token(fragment, request, ID_TOKEN_EXPIRES_IN).await?);
+      }
+
+      if request.requests_vp_token() {
+        let mut presentation: Presentation = presentation.ok_or(OidcError::InvalidResponse("missing vp_token"))?;
+        let options: ProofOptions = ProofOptions::new()
+          .challenge(request.nonce.clone())
+          .domain(request.client_id.clone());
+
+        self.sign(fragment, &mut presentation, options).await?;
+
+        response.vp_token = Some(presentation);
+        response.presentation_submission =
+          Some(submission.ok_or(OidcError::InvalidResponse("missing presentation_submission"))?);
+      }
+
+      Ok(response)
+    }
+  }
+}
@@ -3,6 +3,7 @@
 
 use std::sync::Arc;
 
+use identity_account_storage::crypto::KeyDerivation;
 use identity_account_storage::storage::MemStore;
 use identity_account_storage::storage::Storage;
 use identity_iota_client::tangle::Client;
@@ -11,6 +12,7 @@ use identity_iota_client::tangle::SharedPtr;
 use identity_iota_core::did::IotaDID;
 
 use crate::account::Account;
+use crate::account::AccountManager;
 use crate::error::Result;
 use crate::types::IdentitySetup;
 
@@ -172,6 +174,29 @@ where
     let setup: AccountSetup<C> = self.build_setup().await?;
     Account::load_identity(setup, did).await
   }
+
+  /// Recovers an existing identity with the specified `did` from its latest document on the Tangle,
+  /// stores it in the configured [`Storage`] and returns an [`Account`] to manage it.
+  ///
+  /// The keys of the methods derived from `key_derivation` are recovered, i.e. the key of the DID if the
+  /// identity was created with [`IdentitySetup::key_derivation`] and the keys of methods created with
+  /// [`MethodContent::DeriveEd25519`](crate::types::MethodContent::DeriveEd25519) or
+  /// [`MethodContent::DeriveX25519`](crate::types::MethodContent::DeriveX25519).
+  /// Fails if the DID was not derived from `key_derivation` or if the identity exists in the [`Storage`].
+  pub async fn recover_identity(&mut self, did: IotaDID, key_derivation: KeyDerivation) -> Result<Account<C>> {
+    let setup: AccountSetup<C> = self.build_setup().await?;
+    Account::recover_identity(setup, did, key_derivation).await
+  }
+
+  /// Creates an [`AccountManager`] for all identities in the configured [`Storage`], using the
+  /// current builder configuration.
+  ///
+  /// The manager shares the [`Storage`] and [`Client`] with the accounts created by this builder,
+  /// so it should be used instead of [`AccountBuilder::load_identity`] to access the identities it manages.
+  pub async fn build_manager(&mut self) -> Result<AccountManager<C>> {
+    let setup: AccountSetup<C> = self.build_setup().await?;
+    Ok(AccountManager::new(setup))
+  }
 }
 
 impl<C> Default for AccountBuilder<C>
@@ -182,3 +207,97 @@ where
     Self::new()
   }
 }
+
+/// An [`AccountManager`] builder for easy configuration of the managed accounts.
+///
+/// All accounts loaded by the built manager share its [`Storage`] and [`Client`] and are configured
+/// alike. See [`AccountBuilder::build_manager`] to also create accounts outside of the manager.
+#[derive(Debug)]
+pub struct AccountManagerBuilder<C = Arc<Client>>
+where
+  C: SharedPtr<Client>,
+{
+  builder: AccountBuilder<C>,
+}
+
+impl<C> AccountManagerBuilder<C>
+where
+  C: SharedPtr<Client>,
+{
+  /// Creates a new `AccountManagerBuilder`.
+  pub fn new() -> Self {
+    Self {
+      builder: AccountBuilder::new(),
+    }
+  }
+
+  /// Sets the auto-save behaviour of the managed accounts.
+  #[must_use]
+  pub fn autosave(mut self, value: AutoSave) -> Self {
+    self.builder = self.builder.autosave(value);
+    self
+  }
+
+  /// Sets the auto-publish behaviour of the managed accounts.
+  #[must_use]
+  pub fn autopublish(mut self, value: bool) -> Self {
+    self.builder = self.builder.autopublish(value);
+    self
+  }
+
+  /// Set whether the managed accounts are in testmode or not.
+  /// In testmode, the accounts skip publishing to the tangle.
+  #[cfg(test)]
+  #[must_use]
+  pub(crate) fn testmode(mut self, value: bool) -> Self {
+    self.builder = self.builder.testmode(value);
+    self
+  }
+
+  /// Sets the storage adapter of the managed identities.
+  #[must_use]
+  pub fn storage<S: Storage + 'static>(mut self, value: S) -> Self {
+    self.builder = self.builder.storage(value);
+    self
+  }
+
+  /// Sets the storage adapter of the managed identities from a shared pointer.
+  #[must_use]
+  pub fn storage_shared(mut self, value: Arc<dyn Storage>) -> Self {
+    self.builder = self.builder.storage_shared(value);
+    self
+  }
+
+  /// Sets the IOTA Tangle [`Client`] shared by the managed accounts.
+  ///
+  /// NOTE: this overwrites any [`ClientBuilder`] previously set by
+  /// [`AccountManagerBuilder::client_builder`].
+  #[must_use]
+  pub fn client(mut self, client: C) -> Self {
+    self.builder = self.builder.client(client);
+    self
+  }
+
+  /// Sets the IOTA Tangle [`Client`] shared by the managed accounts from a [`ClientBuilder`].
+  ///
+  /// NOTE: this overwrites any [`Client`] previously set by [`AccountManagerBuilder::client`].
+  #[must_use]
+  pub fn client_builder(mut self, client_builder: ClientBuilder) -> Self {
+    self.builder = self.builder.client_builder(client_builder);
+    self
+  }
+
+  /// Creates an [`AccountManager`] for all identities in the configured [`Storage`].
+  pub async fn build(mut self) -> Result<AccountManager<C>> {
+    self.builder.build_manager().await
+  }
+}
+
+impl<C> Default for AccountManagerBuilder<C>
+where
+  C: SharedPtr<Client>,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
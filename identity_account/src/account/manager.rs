@@ -0,0 +1,249 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::RwLockWriteGuard;
+
+use identity_account_storage::crypto::KeyDerivation;
+use identity_account_storage::storage::Storage;
+use identity_account_storage::utils::Shared;
+use identity_iota_client::tangle::Client;
+use identity_iota_client::tangle::SharedPtr;
+use identity_iota_core::did::IotaDID;
+use tokio::sync::Mutex;
+use tokio::sync::MutexGuard;
+
+use crate::account::Account;
+use crate::account::AccountManagerBuilder;
+use crate::account::SyncStatus;
+use crate::account::SyncStrategy;
+use crate::types::IdentitySetup;
+use crate::Error;
+use crate::Result;
+
+use super::config::AccountSetup;
+
+/// An [`Account`] managed by an [`AccountManager`].
+///
+/// The lock must be held for the duration of an update, which serialises concurrent updates
+/// to the same identity.
+pub type SharedAccount<C = Arc<Client>> = Arc<Mutex<Account<C>>>;
+
+/// Manages all identities in a [`Storage`].
+///
+/// Accounts are loaded lazily on first access and cached afterwards, so each identity is managed
+/// by a single [`Account`]. All accounts share the [`Storage`] and the [`Client`] of the manager,
+/// as well as the configuration of the [`AccountBuilder`] it was built from.
+#[derive(Debug)]
+pub struct AccountManager<C = Arc<Client>>
+where
+  C: SharedPtr<Client>,
+{
+  setup: AccountSetup<C>,
+  accounts: Shared<HashMap<IotaDID, SharedAccount<C>>>,
+}
+
+impl<C> AccountManager<C>
+where
+  C: SharedPtr<Client>,
+{
+  /// Creates a new [`AccountManagerBuilder`].
+  pub fn builder() -> AccountManagerBuilder<C> {
+    AccountManagerBuilder::new()
+  }
+
+  pub(crate) fn new(setup: AccountSetup<C>) -> Self {
+    Self {
+      setup,
+      accounts: Shared::new(HashMap::new()),
+    }
+  }
+
+  /// Returns a reference counter to the [Storage] implementation.
+  pub fn storage(&self) -> &Arc<dyn Storage> {
+    &self.setup.storage
+  }
+
+  /// Returns the [`Client`] shared by all accounts.
+  pub fn client(&self) -> &C {
+    &self.setup.client
+  }
+
+  /// Returns the DIDs of all identities in the [`Storage`].
+  pub async fn identities(&self) -> Result<Vec<IotaDID>> {
+    self.setup.storage.did_list().await.map_err(Into::into)
+  }
+
+  /// Returns the DIDs of the identities whose accounts are currently loaded.
+  pub fn loaded_identities(&self) -> Result<Vec<IotaDID>> {
+    Ok(self.accounts.read()?.keys().cloned().collect())
+  }
+
+  /// Creates a new identity and returns the [`SharedAccount`] managing it.
+  ///
+  /// See [`IdentitySetup`] to customize the identity creation.
+  pub async fn create_identity(&self, input: IdentitySetup) -> Result<SharedAccount<C>> {
+    let account: Account<C> = Account::create_identity(self.setup.clone(), input).await?;
+    let did: IotaDID = account.did().to_owned();
+
+    self.insert(did, account)
+  }
+
+  /// Recovers an existing identity with the specified `did` from the Tangle and returns the
+  /// [`SharedAccount`] managing it.
+  ///
+  /// See [`AccountBuilder::recover_identity`].
+  pub async fn recover_identity(&self, did: IotaDID, key_derivation: KeyDerivation) -> Result<SharedAccount<C>> {
+    let account: Account<C> = Account::recover_identity(self.setup.clone(), did.clone(), key_derivation).await?;
+
+    self.insert(did, account)
+  }
+
+  /// Returns the [`SharedAccount`] managing the identity with the specified `did`, loading it from
+  /// the [`Storage`] if it is not loaded yet.
+  pub async fn load_identity(&self, did: &IotaDID) -> Result<SharedAccount<C>> {
+    if let Some(account) = self.accounts.read()?.get(did) {
+      return Ok(Arc::clone(account));
+    }
+
+    let account: Account<C> = Account::load_identity(self.setup.clone(), did.to_owned()).await?;
+
+    self.insert(did.to_owned(), account)
+  }
+
+  /// Removes the account managing the identity with the specified `did` from the cache.
+  ///
+  /// Returns `false` if the account is not loaded, still in use or has unpublished changes, in which
+  /// case it stays loaded. Unpublished changes are only held by the account, so they must be published
+  /// first, e.g. with [`AccountManager::publish_all`].
+  pub async fn unload_identity(&self, did: &IotaDID) -> Result<bool> {
+    let account: SharedAccount<C> = match self.accounts.read()?.get(did) {
+      Some(account) => Arc::clone(account),
+      None => return Ok(false),
+    };
+
+    // Hold the lock, so the account cannot be updated until it is removed.
+    let guard: MutexGuard<'_, Account<C>> = account.lock().await;
+
+    if guard.has_unpublished_changes().await? {
+      return Ok(false);
+    }
+
+    let mut accounts: RwLockWriteGuard<'_, _> = self.accounts.write()?;
+
+    // The account is only referenced by the cache and `account`.
+    match accounts.get(did) {
+      Some(loaded) if Arc::ptr_eq(loaded, &account) && Arc::strong_count(loaded) == 2 => {
+        Ok(accounts.remove(did).is_some())
+      }
+      _ => Ok(false),
+    }
+  }
+
+  /// Publishes the unpublished changes of all loaded accounts.
+  ///
+  /// The identities without unpublished changes are skipped.
+  pub async fn publish_all(&self) -> Result<BulkReport> {
+    let mut report: BulkReport = BulkReport::default();
+
+    for (did, account) in self.loaded_accounts()? {
+      let mut account: MutexGuard<'_, Account<C>> = account.lock().await;
+
+      let result: Result<bool> = match account.has_unpublished_changes().await {
+        Ok(true) => account.publish().await.map(|_| true),
+        other => other,
+      };
+
+      report.push(did, result);
+    }
+
+    Ok(report)
+  }
+
+  /// Fetches the latest document of all identities in the [`Storage`] from the Tangle, loading
+  /// their accounts if necessary.
+  ///
+  /// Like [`Account::fetch_document`], this **overwrites** unpublished changes. See
+  /// [`AccountManager::sync_all`] to keep them.
+  pub async fn fetch_all(&self) -> Result<BulkReport> {
+    let mut report: BulkReport = BulkReport::default();
+
+    for did in self.identities().await? {
+      let result: Result<bool> = match self.load_identity(&did).await {
+        Ok(account) => account.lock().await.fetch_document().await.map(|_| true),
+        Err(error) => Err(error),
+      };
+
+      report.push(did, result);
+    }
+
+    Ok(report)
+  }
+
+  /// Synchronizes all identities in the [`Storage`] with the Tangle according to `strategy`, loading
+  /// their accounts if necessary.
+  ///
+  /// The identities the Tangle contains no unknown updates for are skipped. See [`Account::sync`].
+  pub async fn sync_all(&self, strategy: SyncStrategy) -> Result<BulkReport> {
+    let mut report: BulkReport = BulkReport::default();
+
+    for did in self.identities().await? {
+      let result: Result<bool> = match self.load_identity(&did).await {
+        Ok(account) => account.lock().await.sync(strategy).await.map(SyncStatus::is_behind),
+        Err(error) => Err(error),
+      };
+
+      report.push(did, result);
+    }
+
+    Ok(report)
+  }
+
+  fn insert(&self, did: IotaDID, account: Account<C>) -> Result<SharedAccount<C>> {
+    // Keep the account inserted first if the identity was loaded concurrently.
+    let mut accounts: RwLockWriteGuard<'_, _> = self.accounts.write()?;
+    let account: &SharedAccount<C> = accounts.entry(did).or_insert_with(|| Arc::new(Mutex::new(account)));
+
+    Ok(Arc::clone(account))
+  }
+
+  fn loaded_accounts(&self) -> Result<Vec<(IotaDID, SharedAccount<C>)>> {
+    Ok(
+      self
+        .accounts
+        .read()?
+        .iter()
+        .map(|(did, account)| (did.clone(), Arc::clone(account)))
+        .collect(),
+    )
+  }
+}
+
+/// The outcome of an operation applied to multiple identities by an [`AccountManager`].
+///
+/// A failure for one identity does not prevent the operation from being applied to the others.
+#[derive(Debug, Default)]
+pub struct BulkReport {
+  /// The identities the operation was applied to.
+  pub completed: Vec<IotaDID>,
+  /// The identities the operation was not necessary for.
+  pub skipped: Vec<IotaDID>,
+  /// The identities the operation failed for.
+  pub failed: Vec<(IotaDID, Error)>,
+}
+
+impl BulkReport {
+  /// Returns `true` if the operation did not fail for any identity.
+  pub fn is_success(&self) -> bool {
+    self.failed.is_empty()
+  }
+
+  fn push(&mut self, did: IotaDID, result: Result<bool>) {
+    match result {
+      Ok(true) => self.completed.push(did),
+      Ok(false) => self.skipped.push(did),
+      Err(error) => self.failed.push((did, error)),
+    }
+  }
+}
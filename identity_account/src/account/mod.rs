@@ -6,9 +6,13 @@
 mod account;
 mod builder;
 mod config;
+mod manager;
 mod publish_options;
+mod sync;
 
 pub use self::account::*;
 pub use self::builder::*;
 pub use self::config::*;
+pub use self::manager::*;
 pub use self::publish_options::*;
+pub use self::sync::*;
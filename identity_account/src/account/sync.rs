@@ -0,0 +1,46 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+/// The state of an [`Account`](crate::account::Account) compared to the state of its identity on the Tangle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SyncStatus {
+  /// The last publication of the account is the latest state on the Tangle and there are no
+  /// unpublished changes.
+  UpToDate,
+  /// The last publication of the account is the latest state on the Tangle and there are
+  /// unpublished changes.
+  LocalAhead,
+  /// The Tangle contains updates published after the last publication of the account, e.g. by
+  /// another account managing the same identity, and there are no unpublished changes.
+  RemoteNewer,
+  /// The Tangle contains updates published after the last publication of the account and there are
+  /// unpublished changes.
+  Diverged,
+  /// The last publication of the account is not part of the history on the Tangle, e.g. because
+  /// it was published on top of an outdated state and ignored during resolution.
+  Forked,
+}
+
+impl SyncStatus {
+  /// Returns `true` if the Tangle contains updates the account is not aware of.
+  pub fn is_behind(self) -> bool {
+    matches!(self, Self::RemoteNewer | Self::Diverged | Self::Forked)
+  }
+}
+
+/// Determines how an [`Account`](crate::account::Account) is reconciled with its identity on the Tangle,
+/// if the Tangle contains updates the account is not aware of.
+///
+/// See [`Account::sync`](crate::account::Account::sync).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SyncStrategy {
+  /// Overwrites the local document with the latest document on the Tangle, discarding all local
+  /// changes that are not part of it.
+  AdoptRemote,
+  /// Combines the local changes with the changes published on the Tangle and keeps the result as
+  /// unpublished changes. Fails if the same values were changed differently.
+  Rebase,
+  /// Publishes the local document on top of the latest document on the Tangle, discarding all
+  /// changes published on the Tangle that are not part of the local document.
+  ForcePublish,
+}
@@ -27,6 +27,14 @@ pub enum Error {
   /// Caused by errors from the [identity_iota_core] crate.
   #[error(transparent)]
   IotaCoreError(#[from] identity_iota_core::Error),
+  /// Caused by errors from the [libjose] crate.
+  #[cfg(feature = "oidc")]
+  #[error(transparent)]
+  JoseError(#[from] libjose::Error),
+  /// Caused by errors from the [identity_credential::oidc] module.
+  #[cfg(feature = "oidc")]
+  #[error(transparent)]
+  OidcError(#[from] identity_credential::oidc::OidcError),
   /// Caused by attempting to find an identity that does not exist.
   #[error("Identity not found")]
   IdentityNotFound,
@@ -39,6 +47,15 @@ pub enum Error {
   /// Caused by reaching an invalid state for the identity.
   #[error("invalid identity state: {0}")]
   InvalidIdentityState(String),
+  /// Caused by local and remote changes to the same values of a document, contains the conflicting paths.
+  #[error("conflicting document changes at {}", .0.join(", "))]
+  MergeConflict(Vec<String>),
+  /// Caused by local publications that are not part of the history on the Tangle and cannot be related to it.
+  #[error("the local history cannot be related to the history on the Tangle")]
+  UnresolvableFork,
+  /// Caused by deriving keys for an identity from a key derivation whose root key does not match its DID.
+  #[error("the key derivation does not belong to the identity")]
+  KeyDerivationMismatch,
 }
 
 impl From<identity_did::did::DIDError> for Error {
@@ -6,23 +6,37 @@ use std::sync::Arc;
 
 use futures::Future;
 
+use identity_account_storage::crypto::KeyDerivation;
 use identity_account_storage::identity::ChainState;
 use identity_account_storage::storage::MemStore;
 use identity_account_storage::storage::Stronghold;
+use identity_account_storage::types::KeyLocation;
+use identity_core::common::Object;
 use identity_core::common::Timestamp;
 use identity_core::common::Url;
+use identity_core::crypto::KeyPair;
+use identity_core::crypto::KeyType;
 use identity_core::crypto::ProofOptions;
+use identity_did::did::DID;
+use identity_did::service::ServiceEndpoint;
 use identity_did::utils::Queryable;
+use identity_did::verification::MethodRelationship;
 use identity_did::verification::MethodScope;
 use identity_iota_client::chain::DocumentChain;
+use identity_iota_client::chain::IntegrationChain;
+use identity_iota_client::document::ResolvedIotaDocument;
 use identity_iota_client::tangle::Client;
 use identity_iota_client::tangle::ClientBuilder;
 use identity_iota_core::did::IotaDID;
+use identity_iota_core::did::IotaDIDUrl;
 use identity_iota_core::diff::DiffMessage;
 use identity_iota_core::document::IotaDocument;
+use identity_iota_core::document::IotaService;
+use identity_iota_core::document::IotaVerificationMethod;
 use identity_iota_core::tangle::MessageId;
 use identity_iota_core::tangle::MessageIdExt;
 use identity_iota_core::tangle::Network;
+use identity_iota_core::tangle::NetworkName;
 
 use crate::account::Account;
 use crate::account::AccountBuilder;
@@ -30,6 +44,8 @@ use crate::account::AccountConfig;
 use crate::account::AccountSetup;
 use crate::account::AutoSave;
 use crate::account::PublishOptions;
+use crate::account::SyncStatus;
+use crate::account::SyncStrategy;
 use crate::types::IdentitySetup;
 use crate::types::MethodContent;
 use crate::Error;
@@ -562,6 +578,416 @@ async fn test_account_sync_diff_msg_update() {
   .unwrap();
 }
 
+#[tokio::test]
+async fn test_account_rebase_document() {
+  let config = AccountConfig::default().autopublish(false).testmode(true);
+  let client = ClientBuilder::new().node_sync_disabled().build().await.unwrap();
+  let account_setup = AccountSetup::new(Arc::new(MemStore::new()), Arc::new(client), config);
+
+  let mut account = Account::create_identity(account_setup, IdentitySetup::new())
+    .await
+    .unwrap();
+  account.publish().await.unwrap();
+
+  let service = |document: &IotaDocument, fragment: &str, endpoint: &str| -> IotaService {
+    IotaService::builder(Object::default())
+      .id(document.id().to_url().join(fragment).unwrap())
+      .type_("LinkedDomains")
+      .service_endpoint(ServiceEndpoint::One(Url::parse(endpoint).unwrap()))
+      .build()
+      .unwrap()
+  };
+
+  // Simulate an update published from another account.
+  let mut remote: IotaDocument = account.document().clone();
+  remote.properties_mut().insert("foo".into(), 123u32.into());
+  assert!(remote.insert_service(service(&remote, "#remote-service", "https://example.org")));
+  let resolved: ResolvedIotaDocument = ResolvedIotaDocument {
+    document: remote,
+    integration_message_id: MessageId::new([1; 32]),
+    diff_message_id: MessageId::null(),
+  };
+
+  // Prepare an unpublished local update.
+  account
+    .update_identity()
+    .create_method()
+    .content(MethodContent::GenerateEd25519)
+    .fragment("local-method")
+    .apply()
+    .await
+    .unwrap();
+
+  account.rebase_document(resolved.clone()).await.unwrap();
+
+  let document: &IotaDocument = account.document();
+  assert!(document.properties().contains_key("foo"));
+  assert!(document.service().query("#remote-service").is_some());
+  assert!(document.resolve_method("local-method", None).is_some());
+  assert_eq!(
+    account.chain_state().last_integration_message_id(),
+    &resolved.integration_message_id
+  );
+  assert_eq!(account.load_document().await.unwrap(), resolved.document);
+
+  // Only the local changes remain to be published.
+  account.publish().await.unwrap();
+  assert!(account.document().resolve_method("local-method", None).is_some());
+
+  // Changing the same values locally and remotely results in a conflict.
+  let mut remote: IotaDocument = account.document().clone();
+  assert!(remote.insert_service(service(&remote, "#service", "https://example.org")));
+  let resolved: ResolvedIotaDocument = ResolvedIotaDocument {
+    document: remote,
+    integration_message_id: MessageId::new([2; 32]),
+    diff_message_id: MessageId::null(),
+  };
+
+  account
+    .update_identity()
+    .create_service()
+    .fragment("service")
+    .type_("LinkedDomains")
+    .endpoint(Url::parse("https://iota.org").unwrap())
+    .apply()
+    .await
+    .unwrap();
+
+  let chain_state: ChainState = account.chain_state().clone();
+  let document: IotaDocument = account.document().clone();
+
+  assert!(matches!(
+    account.rebase_document(resolved).await.unwrap_err(),
+    Error::MergeConflict(paths) if paths.len() == 1 && paths[0].ends_with("#service/serviceEndpoint")
+  ));
+  assert_eq!(account.chain_state(), &chain_state);
+  assert_eq!(account.document(), &document);
+}
+
+#[tokio::test]
+async fn test_account_rebase_document_removed_method() {
+  let config = AccountConfig::default().autopublish(false).testmode(true);
+  let client = ClientBuilder::new().node_sync_disabled().build().await.unwrap();
+  let account_setup = AccountSetup::new(Arc::new(MemStore::new()), Arc::new(client), config);
+
+  let mut account = Account::create_identity(account_setup, IdentitySetup::new())
+    .await
+    .unwrap();
+  account
+    .update_identity()
+    .create_method()
+    .content(MethodContent::GenerateEd25519)
+    .fragment("key-1")
+    .apply()
+    .await
+    .unwrap();
+  account.publish().await.unwrap();
+
+  // Simulate an update removing the method, published from another account.
+  let mut remote: IotaDocument = account.document().clone();
+  let method_url: IotaDIDUrl = remote.id().to_url().join("#key-1").unwrap();
+  remote.remove_method(&method_url).unwrap();
+  let resolved: ResolvedIotaDocument = ResolvedIotaDocument {
+    document: remote,
+    integration_message_id: MessageId::new([1; 32]),
+    diff_message_id: MessageId::null(),
+  };
+
+  // Reference the method locally.
+  account
+    .update_identity()
+    .attach_method_relationship()
+    .fragment("key-1")
+    .relationship(MethodRelationship::Authentication)
+    .apply()
+    .await
+    .unwrap();
+
+  let chain_state: ChainState = account.chain_state().clone();
+  let document: IotaDocument = account.document().clone();
+
+  // Both changes merge cleanly, but the result references a method that no longer exists.
+  assert!(matches!(
+    account.rebase_document(resolved).await.unwrap_err(),
+    Error::IotaCoreError(identity_iota_core::Error::InvalidDoc(
+      identity_did::Error::MethodNotFound
+    ))
+  ));
+  assert_eq!(account.chain_state(), &chain_state);
+  assert_eq!(account.document(), &document);
+}
+
+#[tokio::test]
+async fn test_account_sync() {
+  let config = AccountConfig::default().autopublish(false).testmode(true);
+  let client = ClientBuilder::new().node_sync_disabled().build().await.unwrap();
+  let account_setup = AccountSetup::new(Arc::new(MemStore::new()), Arc::new(client), config);
+
+  let mut account = Account::create_identity(account_setup, IdentitySetup::new())
+    .await
+    .unwrap();
+  account.publish().await.unwrap();
+
+  let root: ResolvedIotaDocument = ResolvedIotaDocument {
+    document: account.document().clone(),
+    integration_message_id: *account.chain_state().last_integration_message_id(),
+    diff_message_id: MessageId::null(),
+  };
+  let mut chain: DocumentChain = DocumentChain::new(IntegrationChain::new(root).unwrap());
+
+  assert_eq!(account.sync_status(&chain).await.unwrap(), SyncStatus::UpToDate);
+
+  // Unpublished changes are kept if the Tangle contains no unknown updates.
+  account
+    .update_identity()
+    .create_method()
+    .content(MethodContent::GenerateEd25519)
+    .fragment("local-method")
+    .apply()
+    .await
+    .unwrap();
+
+  assert_eq!(
+    account.sync_with(&chain, SyncStrategy::AdoptRemote).await.unwrap(),
+    SyncStatus::LocalAhead
+  );
+  assert!(account.document().resolve_method("local-method", None).is_some());
+
+  // Local changes are rebased onto updates published from another account.
+  push_remote_update(&account, &mut chain, 1, |document| {
+    document.properties_mut().insert("foo".into(), 123u32.into());
+  })
+  .await;
+
+  assert_eq!(
+    account.sync_with(&chain, SyncStrategy::Rebase).await.unwrap(),
+    SyncStatus::Diverged
+  );
+  assert!(account.document().properties().contains_key("foo"));
+  assert!(account.document().resolve_method("local-method", None).is_some());
+  assert_eq!(account.sync_status(&chain).await.unwrap(), SyncStatus::LocalAhead);
+
+  // Publishing on top of an outdated state forks the history.
+  push_remote_update(&account, &mut chain, 2, |document| {
+    document.properties_mut().insert("bar".into(), 456u32.into());
+  })
+  .await;
+  account.publish().await.unwrap();
+
+  assert_eq!(account.sync_status(&chain).await.unwrap(), SyncStatus::Forked);
+  assert_eq!(
+    account.sync_with(&chain, SyncStrategy::Rebase).await.unwrap(),
+    SyncStatus::Forked
+  );
+  assert!(account.document().properties().contains_key("bar"));
+  assert!(account.document().resolve_method("local-method", None).is_some());
+  assert_eq!(
+    account.chain_state().last_integration_message_id(),
+    chain.integration_message_id()
+  );
+  assert_eq!(account.sync_status(&chain).await.unwrap(), SyncStatus::LocalAhead);
+
+  // Force-publishing overwrites the changes published from another account.
+  push_remote_update(&account, &mut chain, 3, |document| {
+    document.properties_mut().remove("foo");
+  })
+  .await;
+
+  assert_eq!(
+    account.sync_with(&chain, SyncStrategy::ForcePublish).await.unwrap(),
+    SyncStatus::Diverged
+  );
+  assert!(account.document().properties().contains_key("foo"));
+  assert!(!account.has_unpublished_changes().await.unwrap());
+  assert_eq!(account.load_document().await.unwrap(), *account.document());
+
+  // Adopting the remote state discards all local changes.
+  assert_eq!(
+    account.sync_with(&chain, SyncStrategy::AdoptRemote).await.unwrap(),
+    SyncStatus::Forked
+  );
+  assert_eq!(account.document(), &chain.current().document);
+  assert_eq!(
+    account.chain_state().last_integration_message_id(),
+    chain.integration_message_id()
+  );
+  assert_eq!(account.sync_status(&chain).await.unwrap(), SyncStatus::UpToDate);
+
+  // A failed force-publication does not store the remote state as the published state.
+  account
+    .update_identity()
+    .create_method()
+    .content(MethodContent::GenerateEd25519)
+    .fragment("other-method")
+    .apply()
+    .await
+    .unwrap();
+
+  push_remote_update(&account, &mut chain, 4, |document| {
+    // Replace the signing method with one whose key is not in the local storage.
+    let keypair: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+    let method: IotaVerificationMethod =
+      IotaVerificationMethod::new(document.id().clone(), keypair.type_(), keypair.public(), "remote-key").unwrap();
+    let signing_method: IotaDIDUrl = document.default_signing_method().unwrap().id().clone();
+    document
+      .insert_method(method, MethodScope::capability_invocation())
+      .unwrap();
+    document.remove_method(&signing_method).unwrap();
+  })
+  .await;
+
+  let chain_state: ChainState = account.chain_state().clone();
+  let published: IotaDocument = account.load_document().await.unwrap();
+
+  assert!(account.sync_with(&chain, SyncStrategy::ForcePublish).await.is_err());
+  assert_eq!(account.chain_state(), &chain_state);
+  assert_eq!(account.load_document().await.unwrap(), published);
+  assert!(account.document().resolve_method("other-method", None).is_some());
+}
+
+#[tokio::test]
+async fn test_account_recover_identity() {
+  let config = AccountConfig::default().autopublish(false).testmode(true);
+  let client = Arc::new(ClientBuilder::new().node_sync_disabled().build().await.unwrap());
+  let account_setup = AccountSetup::new(Arc::new(MemStore::new()), Arc::clone(&client), config.clone());
+
+  let mnemonic: String = KeyDerivation::generate_mnemonic().unwrap();
+  let key_derivation: KeyDerivation = KeyDerivation::from_mnemonic(&mnemonic, "", 0).unwrap();
+
+  let mut account = Account::create_identity(
+    account_setup,
+    IdentitySetup::new().key_derivation(key_derivation.clone()),
+  )
+  .await
+  .unwrap();
+
+  let network: NetworkName = account.did().network().unwrap().name();
+  assert_eq!(&key_derivation.did(network).unwrap(), account.did());
+
+  // Keys of another identity cannot be derived for the account.
+  let other_derivation: KeyDerivation = KeyDerivation::from_mnemonic(&mnemonic, "", 1).unwrap();
+  assert!(matches!(
+    account
+      .update_identity()
+      .create_method()
+      .content(MethodContent::DeriveEd25519(other_derivation.clone()))
+      .fragment("derived-ed25519")
+      .apply()
+      .await
+      .unwrap_err(),
+    Error::KeyDerivationMismatch
+  ));
+
+  let methods: [(&str, MethodContent); 3] = [
+    ("derived-ed25519", MethodContent::DeriveEd25519(key_derivation.clone())),
+    ("derived-x25519", MethodContent::DeriveX25519(key_derivation.clone())),
+    ("generated", MethodContent::GenerateEd25519),
+  ];
+
+  for (fragment, content) in methods {
+    account
+      .update_identity()
+      .create_method()
+      .content(content)
+      .fragment(fragment)
+      .apply()
+      .await
+      .unwrap();
+  }
+
+  // Re-creating a method derives a key of the next generation.
+  let old_method: IotaVerificationMethod = account
+    .document()
+    .resolve_method("derived-ed25519", None)
+    .unwrap()
+    .clone();
+  account
+    .update_identity()
+    .delete_method()
+    .fragment("derived-ed25519")
+    .apply()
+    .await
+    .unwrap();
+  account
+    .update_identity()
+    .create_method()
+    .content(MethodContent::DeriveEd25519(key_derivation.clone()))
+    .fragment("derived-ed25519")
+    .apply()
+    .await
+    .unwrap();
+  let new_method: &IotaVerificationMethod = account.document().resolve_method("derived-ed25519", None).unwrap();
+  assert_ne!(old_method.data(), new_method.data());
+
+  account.publish().await.unwrap();
+
+  let resolved: ResolvedIotaDocument = ResolvedIotaDocument {
+    document: account.document().clone(),
+    integration_message_id: *account.chain_state().last_integration_message_id(),
+    diff_message_id: MessageId::null(),
+  };
+  let recovery_setup = AccountSetup::new(Arc::new(MemStore::new()), Arc::clone(&client), config);
+
+  // The DID must be derived from the key derivation.
+  assert!(matches!(
+    Account::recover_resolved(recovery_setup.clone(), resolved.clone(), other_derivation)
+      .await
+      .unwrap_err(),
+    Error::KeyDerivationMismatch
+  ));
+
+  let recovered = Account::recover_resolved(recovery_setup.clone(), resolved.clone(), key_derivation.clone())
+    .await
+    .unwrap();
+
+  assert_eq!(recovered.document(), account.document());
+  assert_eq!(recovered.chain_state(), account.chain_state());
+  assert_eq!(recovered.load_document().await.unwrap(), *account.document());
+  assert!(!recovered.has_unpublished_changes().await.unwrap());
+
+  for method in recovered.document().methods() {
+    let location: KeyLocation = KeyLocation::from_verification_method(method).unwrap();
+    let exists: bool = recovered
+      .storage()
+      .key_exists(recovered.did(), &location)
+      .await
+      .unwrap();
+    assert_eq!(exists, method.id().fragment() != Some("generated"));
+  }
+
+  // An identity that exists in the storage is neither recovered nor removed.
+  assert!(Account::recover_resolved(recovery_setup, resolved, key_derivation)
+    .await
+    .is_err());
+  assert!(recovered.storage().did_exists(recovered.did()).await.unwrap());
+}
+
+// Simulates an update of the latest document in `chain` published from another account.
+async fn push_remote_update(account: &Account, chain: &mut DocumentChain, seed: u8, f: impl FnOnce(&mut IotaDocument)) {
+  let previous: &IotaDocument = &chain.current().document;
+  let method: IotaVerificationMethod = previous.default_signing_method().unwrap().clone();
+
+  let mut document: IotaDocument = previous.clone();
+  f(&mut document);
+  document.metadata.previous_message_id = *chain.integration_message_id();
+  document.metadata.updated = Some(Timestamp::now_utc());
+  document.proof = None;
+
+  let did: IotaDID = document.id().clone();
+  account
+    .remote_sign_data(&did, &method, &mut document, ProofOptions::default())
+    .await
+    .unwrap();
+
+  chain
+    .try_push_integration(ResolvedIotaDocument {
+      document,
+      integration_message_id: MessageId::new([seed; 32]),
+      diff_message_id: MessageId::null(),
+    })
+    .unwrap();
+}
+
 async fn create_account(network: Network) -> Account {
   Account::builder()
     .storage(
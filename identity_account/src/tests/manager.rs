@@ -0,0 +1,129 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use identity_core::common::Url;
+use identity_did::utils::Queryable;
+use identity_iota_core::did::IotaDID;
+
+use crate::account::AccountManager;
+use crate::account::BulkReport;
+use crate::account::SharedAccount;
+use crate::types::IdentitySetup;
+use crate::Result;
+
+async fn create_service(account: &SharedAccount, fragment: &str) -> Result<()> {
+  account
+    .lock()
+    .await
+    .update_identity()
+    .create_service()
+    .fragment(fragment)
+    .type_("LinkedDomains")
+    .endpoint(Url::parse("https://example.com").unwrap())
+    .apply()
+    .await
+}
+
+#[tokio::test]
+async fn test_manager_load_identity() -> Result<()> {
+  let manager: AccountManager = AccountManager::builder().testmode(true).build().await?;
+
+  let account: SharedAccount = manager.create_identity(IdentitySetup::default()).await?;
+  let did: IotaDID = account.lock().await.did().to_owned();
+
+  // Created identities are listed and stay loaded.
+  assert_eq!(manager.identities().await?, vec![did.clone()]);
+  assert!(Arc::ptr_eq(&account, &manager.load_identity(&did).await?));
+
+  // Accounts in use are not unloaded.
+  assert!(!manager.unload_identity(&did).await?);
+  drop(account);
+  assert!(manager.unload_identity(&did).await?);
+  assert!(manager.loaded_identities()?.is_empty());
+
+  // Identities are loaded lazily from the storage.
+  let account: SharedAccount = manager.load_identity(&did).await?;
+  assert_eq!(account.lock().await.did(), &did);
+  assert_eq!(manager.loaded_identities()?, vec![did]);
+
+  Ok(())
+}
+
+#[tokio::test]
+async fn test_manager_concurrent_updates() -> Result<()> {
+  let manager: Arc<AccountManager> = Arc::new(AccountManager::builder().testmode(true).build().await?);
+
+  let did: IotaDID = manager
+    .create_identity(IdentitySetup::default())
+    .await?
+    .lock()
+    .await
+    .did()
+    .to_owned();
+  assert!(manager.unload_identity(&did).await?);
+
+  // Each task loads the account from the manager.
+  let update = |fragment: &'static str| {
+    let manager: Arc<AccountManager> = Arc::clone(&manager);
+    let did: IotaDID = did.clone();
+
+    tokio::spawn(async move {
+      let account: SharedAccount = manager.load_identity(&did).await?;
+      create_service(&account, fragment).await
+    })
+  };
+
+  let (result1, result2) = futures::join!(update("service-1"), update("service-2"));
+  result1.unwrap()?;
+  result2.unwrap()?;
+
+  // Both updates are applied to the same account.
+  let account: SharedAccount = manager.load_identity(&did).await?;
+  let account = account.lock().await;
+  assert!(account.document().service().query("#service-1").is_some());
+  assert!(account.document().service().query("#service-2").is_some());
+
+  Ok(())
+}
+
+#[tokio::test]
+async fn test_manager_publish_all() -> Result<()> {
+  let manager: AccountManager = AccountManager::builder()
+    .testmode(true)
+    .autopublish(false)
+    .build()
+    .await?;
+
+  let account1: SharedAccount = manager.create_identity(IdentitySetup::default()).await?;
+  let account2: SharedAccount = manager.create_identity(IdentitySetup::default()).await?;
+  let did1: IotaDID = account1.lock().await.did().to_owned();
+  let did2: IotaDID = account2.lock().await.did().to_owned();
+
+  // New identities are published.
+  let report: BulkReport = manager.publish_all().await?;
+  assert!(report.is_success());
+  assert_eq!(report.completed.len(), 2);
+  assert!(!account1.lock().await.chain_state().is_new_identity());
+
+  // Only identities with unpublished changes are published.
+  create_service(&account2, "service").await?;
+  assert!(account2.lock().await.has_unpublished_changes().await?);
+
+  let report: BulkReport = manager.publish_all().await?;
+  assert_eq!(report.completed, vec![did2.clone()]);
+  assert_eq!(report.skipped, vec![did1.clone()]);
+  assert!(!account2.lock().await.has_unpublished_changes().await?);
+
+  // Accounts with unpublished changes are not unloaded.
+  create_service(&account1, "service").await?;
+  drop(account1);
+  assert!(!manager.unload_identity(&did1).await?);
+  assert!(manager.loaded_identities()?.contains(&did1));
+
+  manager.publish_all().await?;
+  assert!(manager.unload_identity(&did1).await?);
+
+  Ok(())
+}
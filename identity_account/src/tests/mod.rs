@@ -2,5 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod account;
+mod manager;
+#[cfg(feature = "oidc")]
+mod oidc;
 mod updates;
 mod util;
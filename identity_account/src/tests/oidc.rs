@@ -0,0 +1,157 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use identity_core::common::Timestamp;
+use identity_core::common::Url;
+use identity_core::convert::FromJson;
+use identity_core::crypto::ProofOptions;
+use identity_core::json;
+use identity_credential::credential::Credential;
+use identity_credential::credential::CredentialBuilder;
+use identity_credential::credential::Subject;
+use identity_credential::oidc::AuthorizationRequest;
+use identity_credential::oidc::AuthorizationResponse;
+use identity_credential::oidc::InputDescriptor;
+use identity_credential::oidc::OidcError;
+use identity_credential::oidc::PresentationDefinition;
+use identity_credential::oidc::PresentationSubmission;
+use identity_credential::oidc::ResponseValidator;
+use identity_credential::presentation::Presentation;
+use identity_credential::presentation::PresentationBuilder;
+use identity_credential::validator::FailFast;
+use identity_credential::validator::PresentationValidationOptions;
+use identity_did::did::DID;
+use identity_did::verification::MethodScope;
+use identity_iota_core::document::IotaDocument;
+use identity_iota_core::tangle::Network;
+
+use crate::account::Account;
+use crate::types::IdentitySetup;
+use crate::types::MethodContent;
+use crate::Error;
+
+use super::util::*;
+
+/// The fragment of the authentication method signing ID tokens and presentations.
+const AUTHENTICATION_FRAGMENT: &str = "authentication-1";
+
+async fn account() -> Account {
+  let mut account: Account = Account::create_identity(account_setup(Network::Mainnet).await, IdentitySetup::default())
+    .await
+    .unwrap();
+
+  // Self-Issued ID Tokens must be signed with an authentication method.
+  account
+    .update_identity()
+    .create_method()
+    .content(MethodContent::GenerateEd25519)
+    .fragment(AUTHENTICATION_FRAGMENT)
+    .scope(MethodScope::authentication())
+    .apply()
+    .await
+    .unwrap();
+
+  account
+}
+
+fn request() -> AuthorizationRequest {
+  let mut request: AuthorizationRequest =
+    AuthorizationRequest::new("vp_token id_token", "https://verifier.example.com/cb", "n-0S6_WzA2Mj");
+  request.redirect_uri = Some(Url::parse("https://verifier.example.com/cb").unwrap());
+  request.state = Some("af0ifjsldkj".to_owned());
+  request.presentation_definition = Some(PresentationDefinition::new(
+    "degree",
+    vec![InputDescriptor::new("bachelor_degree")],
+  ));
+  request
+}
+
+async fn presentation(account: &Account) -> Presentation {
+  let subject: Subject = Subject::from_json_value(json!({
+    "id": account.did().as_str(),
+    "degree": "Bachelor of Science and Arts",
+  }))
+  .unwrap();
+
+  let mut credential: Credential = CredentialBuilder::default()
+    .issuer(Url::parse(account.did().as_str()).unwrap())
+    .type_("UniversityDegreeCredential")
+    .subject(subject)
+    .issuance_date(Timestamp::parse("2020-01-01T00:00:00Z").unwrap())
+    .build()
+    .unwrap();
+
+  account
+    .sign(
+      IotaDocument::DEFAULT_METHOD_FRAGMENT,
+      &mut credential,
+      ProofOptions::default(),
+    )
+    .await
+    .unwrap();
+
+  PresentationBuilder::default()
+    .holder(Url::parse(account.did().as_str()).unwrap())
+    .credential(credential)
+    .build()
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_account_authorization_response() {
+  let account: Account = account().await;
+
+  let request: AuthorizationRequest = request();
+  let mut submission: PresentationSubmission =
+    PresentationSubmission::new("submission", request.presentation_definition.as_ref().unwrap());
+  submission.map_credential("bachelor_degree", 0);
+
+  let response: AuthorizationResponse = account
+    .create_authorization_response(
+      AUTHENTICATION_FRAGMENT,
+      &request,
+      Some(presentation(&account).await),
+      Some(submission),
+    )
+    .await
+    .unwrap();
+
+  let response: AuthorizationResponse =
+    AuthorizationResponse::from_form_urlencoded(&response.to_form_urlencoded().unwrap()).unwrap();
+
+  ResponseValidator::validate(
+    &response,
+    &request,
+    account.document(),
+    &[account.document()],
+    &PresentationValidationOptions::default(),
+    FailFast::FirstError,
+  )
+  .unwrap();
+}
+
+#[tokio::test]
+async fn test_account_id_token_expired() {
+  let account: Account = account().await;
+
+  let mut request: AuthorizationRequest = request();
+  request.response_type = AuthorizationRequest::ID_TOKEN.to_owned();
+
+  let id_token: String = account
+    .create_id_token(AUTHENTICATION_FRAGMENT, &request, Duration::ZERO)
+    .await
+    .unwrap();
+
+  assert!(ResponseValidator::validate_id_token(&id_token, &request, account.document()).is_err());
+
+  // A vp_token cannot be created without a presentation.
+  request.response_type = AuthorizationRequest::VP_TOKEN.to_owned();
+  assert!(matches!(
+    account
+      .create_authorization_response(AUTHENTICATION_FRAGMENT, &request, None, None)
+      .await,
+    Err(Error::OidcError(OidcError::InvalidResponse("missing vp_token")))
+  ));
+}
@@ -3,8 +3,17 @@
 
 use std::sync::Arc;
 
+use identity_account_storage::identity::ChainState;
 use identity_account_storage::storage::MemStore;
+use identity_account_storage::storage::Storage;
+#[cfg(feature = "encryption")]
+use identity_account_storage::types::CekAlgorithm;
+#[cfg(feature = "encryption")]
+use identity_account_storage::types::EncryptedData;
+#[cfg(feature = "encryption")]
+use identity_account_storage::types::EncryptionAlgorithm;
 use identity_account_storage::types::KeyLocation;
+use identity_account_storage::types::Signature;
 use identity_core::common::OneOrSet;
 use identity_core::common::OrderedSet;
 use identity_core::common::Timestamp;
@@ -22,9 +31,11 @@ use identity_did::verification::MethodScope;
 use identity_did::verification::MethodType;
 use identity_iota_client::tangle::ClientBuilder;
 use identity_iota_core::did::IotaDID;
+use identity_iota_core::diff::DiffMessage;
 use identity_iota_core::document::IotaDocument;
 use identity_iota_core::document::IotaVerificationMethod;
 use identity_iota_core::tangle::Network;
+use identity_iota_core::tangle::NetworkName;
 
 use crate::account::Account;
 use crate::account::AccountConfig;
@@ -34,6 +45,8 @@ use crate::error::Result;
 use crate::types::IdentitySetup;
 use crate::types::IdentityState;
 use crate::types::MethodContent;
+use crate::types::PublicationKind;
+use crate::types::UpdatePreview;
 use crate::updates::Update;
 use crate::updates::UpdateError;
 
@@ -367,6 +380,31 @@ async fn test_create_method_from_private_key() {
   }
 }
 
+#[tokio::test]
+async fn test_create_method_from_pkcs8_pem() -> Result<()> {
+  let mut account = Account::create_identity(account_setup(Network::Mainnet).await, IdentitySetup::default()).await?;
+
+  let keypair: KeyPair = KeyPair::new(KeyType::X25519).unwrap();
+  let fragment = "key-1".to_owned();
+
+  let update: Update = Update::CreateMethod {
+    scope: MethodScope::key_agreement(),
+    content: MethodContent::from_pkcs8_pem(&keypair.to_pkcs8_pem()).unwrap(),
+    fragment: fragment.clone(),
+  };
+
+  account.process_update(update).await?;
+
+  let method: &IotaVerificationMethod = account.document().resolve_method(&fragment, None).unwrap();
+  assert_eq!(method.type_(), MethodType::X25519KeyAgreementKey2019);
+  assert_eq!(method.data().try_decode()?, keypair.public().as_ref());
+
+  let location: KeyLocation = KeyLocation::from_verification_method(method)?;
+  assert!(account.storage().key_exists(account.did(), &location).await?);
+
+  Ok(())
+}
+
 #[tokio::test]
 async fn test_create_method_from_invalid_private_key() -> Result<()> {
   let mut account = Account::create_identity(account_setup(Network::Mainnet).await, IdentitySetup::default()).await?;
@@ -732,3 +770,427 @@ async fn test_set_also_known_as() -> Result<()> {
 
   Ok(())
 }
+
+#[tokio::test]
+async fn test_deactivate() -> Result<()> {
+  let mut account = Account::create_identity(account_setup(Network::Mainnet).await, IdentitySetup::default()).await?;
+  let previous_message_id = *account.chain_state().last_integration_message_id();
+
+  let update: Update = Update::CreateService {
+    fragment: "#service-42".to_owned(),
+    types: vec!["LinkedDomains".to_owned()],
+    endpoint: ServiceEndpoint::One(Url::parse("https://iota.org").unwrap()),
+    properties: None,
+  };
+  account.process_update(update).await.unwrap();
+
+  account.process_update(Update::Deactivate {}).await.unwrap();
+
+  // The tombstone was published as a signed integration update.
+  let document: &IotaDocument = account.document();
+  assert!(document.is_deactivated());
+  assert_eq!(document.methods().count(), 0);
+  assert_eq!(document.service().len(), 0);
+  assert!(document.proof.is_some());
+  assert_ne!(
+    account.chain_state().last_integration_message_id(),
+    &previous_message_id
+  );
+
+  // No further updates are accepted.
+  let update: Update = Update::SetAlsoKnownAs {
+    urls: OrderedSet::from_iter(vec![Url::parse("did:iota:xyz").unwrap()]),
+  };
+  let err = account.process_update(update).await.unwrap_err();
+  assert!(matches!(err, Error::UpdateError(UpdateError::DocumentDeactivated)));
+
+  let err = account.process_update(Update::Deactivate {}).await.unwrap_err();
+  assert!(matches!(err, Error::UpdateError(UpdateError::DocumentDeactivated)));
+
+  Ok(())
+}
+
+#[tokio::test]
+async fn test_deactivate_unpublished() -> Result<()> {
+  let mut account_setup = account_setup(Network::Mainnet).await;
+  account_setup.config = account_setup.config.testmode(true).autopublish(false);
+
+  let mut account = Account::create_identity(account_setup, IdentitySetup::default()).await?;
+
+  let err = account.process_update(Update::Deactivate {}).await.unwrap_err();
+  assert!(matches!(err, Error::InvalidIdentityState(_)));
+  assert!(!account.document().is_deactivated());
+
+  Ok(())
+}
+
+#[tokio::test]
+async fn test_update_batch_dry_run() -> Result<()> {
+  let mut account = Account::create_identity(account_setup(Network::Mainnet).await, IdentitySetup::default()).await?;
+  let document: IotaDocument = account.document().clone();
+
+  let mut batch = account.update_identity().batch();
+  batch
+    .create_method()
+    .content(MethodContent::GenerateEd25519)
+    .fragment("new-method")
+    .apply()
+    .await?;
+  batch
+    .create_service()
+    .fragment("service")
+    .type_("LinkedDomains")
+    .endpoint(Url::parse("https://iota.org").unwrap())
+    .apply()
+    .await?;
+  batch.delete_service().fragment("missing-service").apply().await?;
+  assert_eq!(batch.len(), 3);
+
+  let preview: UpdatePreview = batch.dry_run().await?;
+
+  // The failed update is reported and skipped.
+  assert!(!preview.is_valid());
+  assert_eq!(preview.failures().len(), 1);
+  assert_eq!(preview.failures()[0].index, Some(2));
+  assert!(matches!(
+    preview.failures()[0].error,
+    Error::UpdateError(UpdateError::ServiceNotFound)
+  ));
+
+  let method: &IotaVerificationMethod = preview.document().resolve_method("new-method", None).unwrap();
+  assert!(preview.document().service().query("#service").is_some());
+
+  assert_eq!(preview.publication_kind(), Some(PublicationKind::Diff));
+  let diff: &DiffMessage = preview.diff_message().unwrap();
+  assert!(diff.proof().is_none());
+  assert!(diff.merge(&document)?.resolve_method("new-method", None).is_some());
+  assert!(preview.message_size() > 0);
+
+  // Neither the document nor the storage was modified.
+  let location: KeyLocation = KeyLocation::from_verification_method(method)?;
+  assert!(!account.storage().key_exists(account.did(), &location).await?);
+  assert_eq!(account.document(), &document);
+  assert_eq!(account.load_document().await?, document);
+
+  // Changing the signing methods requires an integration update.
+  let mut batch = account.update_identity().batch();
+  batch
+    .create_method()
+    .content(MethodContent::GenerateEd25519)
+    .scope(MethodScope::capability_invocation())
+    .fragment("signing-method")
+    .apply()
+    .await?;
+
+  let preview: UpdatePreview = batch.dry_run().await?;
+  assert!(preview.is_valid());
+  assert_eq!(preview.publication_kind(), Some(PublicationKind::Integration));
+  assert!(preview.diff_message().is_none());
+  assert!(preview.message_size() > 0);
+
+  // An empty batch publishes nothing.
+  let preview: UpdatePreview = account.update_identity().batch().dry_run().await?;
+  assert!(preview.is_valid());
+  assert_eq!(preview.publication_kind(), None);
+  assert_eq!(preview.message_size(), 0);
+  assert_eq!(preview.document(), &document);
+
+  // Updates that are valid on their own can result in a document that cannot be signed.
+  let signing_fragment: String = document.default_signing_method()?.id().fragment().unwrap().to_owned();
+
+  let mut batch = account.update_identity().batch();
+  batch
+    .create_method()
+    .content(MethodContent::GenerateX25519)
+    .fragment("key-agreement")
+    .apply()
+    .await?;
+  batch
+    .attach_method_relationship()
+    .fragment("key-agreement")
+    .relationship(MethodRelationship::CapabilityInvocation)
+    .apply()
+    .await?;
+  batch.delete_method().fragment(signing_fragment).apply().await?;
+
+  let preview: UpdatePreview = batch.dry_run().await?;
+  assert_eq!(preview.failures().len(), 1);
+  assert_eq!(preview.failures()[0].index, None);
+  assert!(matches!(
+    preview.failures()[0].error,
+    Error::IotaCoreError(identity_iota_core::Error::InvalidDocumentSigningMethodType)
+  ));
+
+  Ok(())
+}
+
+#[tokio::test]
+async fn test_update_batch_commit() -> Result<()> {
+  let mut account = Account::create_identity(account_setup(Network::Mainnet).await, IdentitySetup::default()).await?;
+  let did: IotaDID = account.did().to_owned();
+
+  let private_key = |fragment: &str| -> (PrivateKey, KeyLocation) {
+    let keypair: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+    let location: KeyLocation = KeyLocation::new(KeyType::Ed25519, fragment.to_owned(), keypair.public().as_ref());
+    (keypair.private().clone(), location)
+  };
+
+  // All updates are applied and published at once.
+  let actions: usize = account.actions();
+  let chain_state: ChainState = account.chain_state().clone();
+
+  let mut batch = account.update_identity().batch();
+  batch
+    .create_method()
+    .content(MethodContent::GenerateEd25519)
+    .fragment("key-1")
+    .apply()
+    .await?;
+  batch
+    .attach_method_relationship()
+    .fragment("key-1")
+    .relationship(MethodRelationship::Authentication)
+    .apply()
+    .await?;
+  batch
+    .create_service()
+    .fragment("service")
+    .type_("LinkedDomains")
+    .endpoint(Url::parse("https://iota.org").unwrap())
+    .apply()
+    .await?;
+  batch.commit().await?;
+
+  let document: IotaDocument = account.document().clone();
+  assert!(document
+    .resolve_method("key-1", Some(MethodScope::authentication()))
+    .is_some());
+  assert!(document.service().query("#service").is_some());
+  assert_eq!(account.actions(), actions + 1);
+  assert_ne!(account.chain_state(), &chain_state);
+  assert_eq!(account.load_document().await?, document);
+
+  // Invalid updates are rejected before any key is created.
+  let (key_2, location_2): (PrivateKey, KeyLocation) = private_key("key-2");
+
+  let mut batch = account.update_identity().batch();
+  batch
+    .create_method()
+    .content(MethodContent::PrivateEd25519(key_2))
+    .fragment("key-2")
+    .apply()
+    .await?;
+  batch.delete_service().fragment("missing-service").apply().await?;
+
+  let error: Error = batch.commit().await.unwrap_err();
+  assert!(matches!(error, Error::UpdateError(UpdateError::ServiceNotFound)));
+  assert!(!account.storage().key_exists(&did, &location_2).await?);
+  assert_eq!(account.document(), &document);
+
+  // Keys created before a failing update are removed.
+  let (key_3, location_3): (PrivateKey, KeyLocation) = private_key("key-3");
+  let (key_4, location_4): (PrivateKey, KeyLocation) = private_key("key-4");
+  account.storage().key_insert(&did, &location_4, key_4.clone()).await?;
+
+  let mut batch = account.update_identity().batch();
+  batch
+    .create_method()
+    .content(MethodContent::PrivateEd25519(key_3))
+    .fragment("key-3")
+    .apply()
+    .await?;
+  batch
+    .create_method()
+    .content(MethodContent::PrivateEd25519(key_4))
+    .fragment("key-4")
+    .apply()
+    .await?;
+
+  let error: Error = batch.commit().await.unwrap_err();
+  assert!(matches!(
+    error,
+    Error::UpdateError(UpdateError::DuplicateKeyLocation(_))
+  ));
+  assert!(!account.storage().key_exists(&did, &location_3).await?);
+  assert!(account.storage().key_exists(&did, &location_4).await?);
+  assert_eq!(account.document(), &document);
+  assert_eq!(account.load_document().await?, document);
+
+  // An empty batch is neither counted as an action nor published.
+  let actions: usize = account.actions();
+  let chain_state: ChainState = account.chain_state().clone();
+
+  account.update_identity().batch().commit().await?;
+  assert_eq!(account.actions(), actions);
+  assert_eq!(account.chain_state(), &chain_state);
+
+  Ok(())
+}
+
+/// A storage that fails to return the public key of a key at the fragment `"key-public-fails"`.
+#[derive(Debug)]
+struct FailingKeyPublicStorage(MemStore);
+
+impl FailingKeyPublicStorage {
+  const FRAGMENT: &'static str = "key-public-fails";
+}
+
+#[cfg_attr(not(feature = "send-sync-storage"), async_trait::async_trait(?Send))]
+#[cfg_attr(feature = "send-sync-storage", async_trait::async_trait)]
+impl Storage for FailingKeyPublicStorage {
+  async fn did_create(
+    &self,
+    network: NetworkName,
+    fragment: &str,
+    private_key: Option<PrivateKey>,
+  ) -> identity_account_storage::Result<(IotaDID, KeyLocation)> {
+    self.0.did_create(network, fragment, private_key).await
+  }
+
+  async fn did_purge(&self, did: &IotaDID) -> identity_account_storage::Result<bool> {
+    self.0.did_purge(did).await
+  }
+
+  async fn did_exists(&self, did: &IotaDID) -> identity_account_storage::Result<bool> {
+    self.0.did_exists(did).await
+  }
+
+  async fn did_list(&self) -> identity_account_storage::Result<Vec<IotaDID>> {
+    self.0.did_list().await
+  }
+
+  async fn key_generate(
+    &self,
+    did: &IotaDID,
+    key_type: KeyType,
+    fragment: &str,
+  ) -> identity_account_storage::Result<KeyLocation> {
+    self.0.key_generate(did, key_type, fragment).await
+  }
+
+  async fn key_insert(
+    &self,
+    did: &IotaDID,
+    location: &KeyLocation,
+    private_key: PrivateKey,
+  ) -> identity_account_storage::Result<()> {
+    self.0.key_insert(did, location, private_key).await
+  }
+
+  async fn key_public(&self, did: &IotaDID, location: &KeyLocation) -> identity_account_storage::Result<PublicKey> {
+    if location.fragment() == Self::FRAGMENT {
+      return Err(identity_account_storage::Error::KeyNotFound);
+    }
+
+    self.0.key_public(did, location).await
+  }
+
+  async fn key_delete(&self, did: &IotaDID, location: &KeyLocation) -> identity_account_storage::Result<bool> {
+    self.0.key_delete(did, location).await
+  }
+
+  async fn key_sign(
+    &self,
+    did: &IotaDID,
+    location: &KeyLocation,
+    data: Vec<u8>,
+  ) -> identity_account_storage::Result<Signature> {
+    self.0.key_sign(did, location, data).await
+  }
+
+  async fn key_exists(&self, did: &IotaDID, location: &KeyLocation) -> identity_account_storage::Result<bool> {
+    self.0.key_exists(did, location).await
+  }
+
+  #[cfg(feature = "encryption")]
+  async fn data_encrypt(
+    &self,
+    did: &IotaDID,
+    plaintext: Vec<u8>,
+    associated_data: Vec<u8>,
+    encryption_algorithm: &EncryptionAlgorithm,
+    cek_algorithm: &CekAlgorithm,
+    public_key: PublicKey,
+  ) -> identity_account_storage::Result<EncryptedData> {
+    self
+      .0
+      .data_encrypt(
+        did,
+        plaintext,
+        associated_data,
+        encryption_algorithm,
+        cek_algorithm,
+        public_key,
+      )
+      .await
+  }
+
+  #[cfg(feature = "encryption")]
+  async fn data_decrypt(
+    &self,
+    did: &IotaDID,
+    data: EncryptedData,
+    encryption_algorithm: &EncryptionAlgorithm,
+    cek_algorithm: &CekAlgorithm,
+    private_key: &KeyLocation,
+  ) -> identity_account_storage::Result<Vec<u8>> {
+    self
+      .0
+      .data_decrypt(did, data, encryption_algorithm, cek_algorithm, private_key)
+      .await
+  }
+
+  async fn blob_set(&self, did: &IotaDID, blob: Vec<u8>) -> identity_account_storage::Result<()> {
+    self.0.blob_set(did, blob).await
+  }
+
+  async fn blob_get(&self, did: &IotaDID) -> identity_account_storage::Result<Option<Vec<u8>>> {
+    self.0.blob_get(did).await
+  }
+
+  async fn flush_changes(&self) -> identity_account_storage::Result<()> {
+    self.0.flush_changes().await
+  }
+}
+
+#[tokio::test]
+async fn test_update_batch_commit_removes_keys_of_failed_update() -> Result<()> {
+  let storage: Arc<FailingKeyPublicStorage> = Arc::new(FailingKeyPublicStorage(MemStore::new()));
+  let mut account: Account = Account::create_identity(
+    account_setup_storage(storage.clone(), Network::Mainnet).await,
+    IdentitySetup::default(),
+  )
+  .await?;
+  let did: IotaDID = account.did().to_owned();
+  let document: IotaDocument = account.document().clone();
+
+  // The key is generated, but the update fails before its method is added to the document.
+  let mut batch = account.update_identity().batch();
+  batch
+    .create_method()
+    .content(MethodContent::GenerateEd25519)
+    .fragment("key-1")
+    .apply()
+    .await?;
+  batch
+    .create_method()
+    .content(MethodContent::GenerateEd25519)
+    .fragment(FailingKeyPublicStorage::FRAGMENT)
+    .apply()
+    .await?;
+
+  let error: Error = batch.commit().await.unwrap_err();
+  assert!(matches!(
+    error,
+    Error::AccountCoreError(identity_account_storage::Error::KeyNotFound)
+  ));
+  assert_eq!(account.document(), &document);
+
+  let locations: Vec<KeyLocation> = storage.0.key_list(&did).await?;
+  assert!(locations.iter().all(|location| location.fragment() != "key-1"));
+  assert!(locations
+    .iter()
+    .all(|location| location.fragment() != FailingKeyPublicStorage::FRAGMENT));
+
+  Ok(())
+}
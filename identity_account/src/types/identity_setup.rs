@@ -1,6 +1,7 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use identity_account_storage::crypto::KeyDerivation;
 use identity_core::crypto::PrivateKey;
 
 /// Configuration used to create a new Identity.
@@ -8,12 +9,17 @@ use identity_core::crypto::PrivateKey;
 pub struct IdentitySetup {
   /// Use a pre-generated Ed25519 private key for the DID.
   pub(crate) private_key: Option<PrivateKey>,
+  /// Derive the Ed25519 private key for the DID.
+  pub(crate) key_derivation: Option<KeyDerivation>,
 }
 
 impl IdentitySetup {
   /// Creates a new `IdentitySetup` instance.
   pub const fn new() -> Self {
-    Self { private_key: None }
+    Self {
+      private_key: None,
+      key_derivation: None,
+    }
   }
 
   /// Sets the Ed25519 private key to use for Identity creation.
@@ -22,6 +28,17 @@ impl IdentitySetup {
     self.private_key = Some(value);
     self
   }
+
+  /// Sets the [`KeyDerivation`] from which the Ed25519 private key for Identity creation is derived.
+  ///
+  /// The identity can be recovered from the same derivation, see
+  /// [`AccountBuilder::recover_identity`](crate::account::AccountBuilder::recover_identity).
+  /// Cannot be combined with [`IdentitySetup::private_key`].
+  #[must_use]
+  pub fn key_derivation(mut self, value: KeyDerivation) -> Self {
+    self.key_derivation = Some(value);
+    self
+  }
 }
 
 impl Default for IdentitySetup {
@@ -5,6 +5,7 @@ use identity_iota_client::tangle::Client;
 use identity_iota_client::tangle::SharedPtr;
 
 use crate::account::Account;
+use crate::types::UpdateBatch;
 
 /// A struct created by the [`Account::update_identity`] method, that
 /// allows executing various updates on the identity it was created on.
@@ -23,4 +24,11 @@ where
   pub(crate) fn new(account: &'account mut Account<C>) -> Self {
     Self { account }
   }
+
+  /// Creates a batch of updates, which can be previewed and applied atomically.
+  ///
+  /// See [`UpdateBatch`].
+  pub fn batch(self) -> UpdateBatch<'account, C> {
+    UpdateBatch::new(self.account)
+  }
 }
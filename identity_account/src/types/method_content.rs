@@ -1,6 +1,8 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use identity_account_storage::crypto::KeyDerivation;
+use identity_core::crypto::KeyPair;
 use identity_core::crypto::KeyType;
 use identity_core::crypto::PrivateKey;
 use identity_core::crypto::PublicKey;
@@ -23,6 +25,14 @@ pub enum MethodContent {
   ///
   /// NOTE: the method will be unable to be used to sign anything without a private key.
   PublicEd25519(PublicKey),
+  /// Derive and store an Ed25519 keypair from a [`KeyDerivation`] for a new
+  /// [`Ed25519VerificationKey2018`](identity_did::verification::MethodType::Ed25519VerificationKey2018)
+  /// method.
+  ///
+  /// The derivation must belong to the identity, i.e. the identity must have been created or recovered
+  /// from it. The key can be recovered with
+  /// [`AccountBuilder::recover_identity`](crate::account::AccountBuilder::recover_identity).
+  DeriveEd25519(KeyDerivation),
   /// Generate and store a new X25519 keypair for a new
   /// [`X25519KeyAgreementKey2019`](identity_did::verification::MethodType::X25519KeyAgreementKey2019)
   /// method.
@@ -37,9 +47,35 @@ pub enum MethodContent {
   ///
   /// NOTE: the method will be unable to be used for key exchange without a private key.
   PublicX25519(PublicKey),
+  /// Derive and store an X25519 keypair from a [`KeyDerivation`] for a new
+  /// [`X25519KeyAgreementKey2019`](identity_did::verification::MethodType::X25519KeyAgreementKey2019)
+  /// method.
+  ///
+  /// See [`MethodContent::DeriveEd25519`].
+  DeriveX25519(KeyDerivation),
 }
 
 impl MethodContent {
+  /// Creates a [`MethodContent`] from a PEM-encoded PKCS#8 Ed25519 or X25519 private key.
+  ///
+  /// The private key is inserted into storage when the method is created.
+  pub fn from_pkcs8_pem(pem: &str) -> identity_core::Result<Self> {
+    let keypair: KeyPair = KeyPair::try_from_pkcs8_pem(pem)?;
+
+    match keypair.type_() {
+      KeyType::Ed25519 => Ok(Self::PrivateEd25519(keypair.private().clone())),
+      KeyType::X25519 => Ok(Self::PrivateX25519(keypair.private().clone())),
+    }
+  }
+
+  /// Creates a [`MethodContent`] from a PEM-encoded Ed25519 or X25519 `SubjectPublicKeyInfo`.
+  pub fn from_spki_pem(pem: &str) -> identity_core::Result<Self> {
+    match PublicKey::try_from_spki_pem(pem)? {
+      (KeyType::Ed25519, public_key) => Ok(Self::PublicEd25519(public_key)),
+      (KeyType::X25519, public_key) => Ok(Self::PublicX25519(public_key)),
+    }
+  }
+
   /// Returns the [`MethodType`](identity_did::verification::MethodType) associated with the `MethodContent` variant.
   #[cfg(test)]
   pub(crate) fn method_type(&self) -> identity_did::verification::MethodType {
@@ -47,9 +83,11 @@ impl MethodContent {
       MethodContent::GenerateEd25519 => identity_did::verification::MethodType::Ed25519VerificationKey2018,
       MethodContent::PrivateEd25519(_) => identity_did::verification::MethodType::Ed25519VerificationKey2018,
       MethodContent::PublicEd25519(_) => identity_did::verification::MethodType::Ed25519VerificationKey2018,
+      MethodContent::DeriveEd25519(_) => identity_did::verification::MethodType::Ed25519VerificationKey2018,
       MethodContent::GenerateX25519 => identity_did::verification::MethodType::X25519KeyAgreementKey2019,
       MethodContent::PrivateX25519(_) => identity_did::verification::MethodType::X25519KeyAgreementKey2019,
       MethodContent::PublicX25519(_) => identity_did::verification::MethodType::X25519KeyAgreementKey2019,
+      MethodContent::DeriveX25519(_) => identity_did::verification::MethodType::X25519KeyAgreementKey2019,
     }
   }
 
@@ -59,9 +97,11 @@ impl MethodContent {
       MethodContent::GenerateEd25519 => KeyType::Ed25519,
       MethodContent::PrivateEd25519(_) => KeyType::Ed25519,
       MethodContent::PublicEd25519(_) => KeyType::Ed25519,
+      MethodContent::DeriveEd25519(_) => KeyType::Ed25519,
       MethodContent::GenerateX25519 => KeyType::X25519,
       MethodContent::PrivateX25519(_) => KeyType::X25519,
       MethodContent::PublicX25519(_) => KeyType::X25519,
+      MethodContent::DeriveX25519(_) => KeyType::X25519,
     }
   }
 }
@@ -5,8 +5,12 @@ pub use self::identity_setup::*;
 pub(crate) use self::identity_state::IdentityState;
 pub use self::identity_updater::*;
 pub use self::method_content::*;
+pub use self::update_batch::*;
+pub use self::update_preview::*;
 
 mod identity_setup;
 mod identity_state;
 mod identity_updater;
 mod method_content;
+mod update_batch;
+mod update_preview;
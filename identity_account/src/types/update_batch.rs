@@ -0,0 +1,92 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_iota_client::tangle::Client;
+use identity_iota_client::tangle::SharedPtr;
+
+use crate::account::Account;
+use crate::types::UpdatePreview;
+use crate::updates::Update;
+use crate::Result;
+
+/// A batch of updates to an identity, created by the [`IdentityUpdater::batch`](crate::types::IdentityUpdater::batch)
+/// method.
+///
+/// Updates are added with the same builders the [`IdentityUpdater`](crate::types::IdentityUpdater) provides,
+/// but calling `apply` on a builder only adds the update to the batch. The batch can be previewed with
+/// [`UpdateBatch::dry_run`] and applied atomically with [`UpdateBatch::commit`].
+#[derive(Debug)]
+pub struct UpdateBatch<'account, C>
+where
+  C: SharedPtr<Client>,
+{
+  account: &'account mut Account<C>,
+  pub(crate) updates: Vec<Update>,
+}
+
+impl<'account, C> UpdateBatch<'account, C>
+where
+  C: SharedPtr<Client>,
+{
+  pub(crate) fn new(account: &'account mut Account<C>) -> Self {
+    Self {
+      account,
+      updates: Vec::new(),
+    }
+  }
+
+  /// Returns the number of updates in the batch.
+  pub fn len(&self) -> usize {
+    self.updates.len()
+  }
+
+  /// Returns `true` if the batch contains no updates.
+  pub fn is_empty(&self) -> bool {
+    self.updates.is_empty()
+  }
+
+  /// Applies the updates to a copy of the document and previews the resulting publication, without
+  /// modifying the storage or accessing the network. The account is not modified.
+  ///
+  /// See [`UpdatePreview`].
+  pub async fn dry_run(&self) -> Result<UpdatePreview> {
+    self.account.preview_updates(&self.updates).await
+  }
+
+  /// Applies all updates to the document at once and publishes the result according to the
+  /// autopublish configuration.
+  ///
+  /// The updates are validated with a dry-run before any key is generated. If an update or the
+  /// publication fails, the document is restored and the keys created by the batch are removed
+  /// from the storage. Committing an empty batch has no effect.
+  pub async fn commit(self) -> Result<()> {
+    self.account.commit_updates(self.updates).await
+  }
+}
+
+/// The recipient of the update built by an update builder.
+#[derive(Debug)]
+pub(crate) enum UpdateTarget<'account, C>
+where
+  C: SharedPtr<Client>,
+{
+  /// Processes the update immediately.
+  Account(&'account mut Account<C>),
+  /// Adds the update to an [`UpdateBatch`].
+  Batch(&'account mut Vec<Update>),
+}
+
+impl<'account, C> UpdateTarget<'account, C>
+where
+  C: SharedPtr<Client>,
+{
+  pub(crate) async fn apply(self, update: Update) -> Result<()> {
+    match self {
+      Self::Account(account) => account.process_update(update).await,
+      Self::Batch(updates) => {
+        updates.push(update);
+        Ok(())
+      }
+    }
+  }
+}
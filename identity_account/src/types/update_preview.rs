@@ -0,0 +1,80 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_iota_core::diff::DiffMessage;
+use identity_iota_core::document::IotaDocument;
+
+use crate::Error;
+
+/// The outcome of a dry-run of an [`UpdateBatch`](crate::types::UpdateBatch).
+///
+/// The publication is previewed as if the account published the updated document right away. Since the
+/// storage is not modified and the network is not accessed, generated keys are ephemeral and the messages are
+/// signed with an ephemeral key, which results in the size of a valid signature. Derived keys are the keys
+/// the updates would store.
+#[derive(Debug)]
+pub struct UpdatePreview {
+  pub(crate) document: IotaDocument,
+  pub(crate) publication_kind: Option<PublicationKind>,
+  pub(crate) diff_message: Option<DiffMessage>,
+  pub(crate) message_size: usize,
+  pub(crate) failures: Vec<UpdateFailure>,
+}
+
+impl UpdatePreview {
+  /// Returns the document resulting from the updates that could be applied.
+  pub fn document(&self) -> &IotaDocument {
+    &self.document
+  }
+
+  /// Consumes the preview and returns the resulting document.
+  pub fn into_document(self) -> IotaDocument {
+    self.document
+  }
+
+  /// Returns how the resulting document would be published, or `None` if there is nothing to publish.
+  pub fn publication_kind(&self) -> Option<PublicationKind> {
+    self.publication_kind
+  }
+
+  /// Returns the unsigned [`DiffMessage`] that would be published, if the document would be published
+  /// as a diff.
+  pub fn diff_message(&self) -> Option<&DiffMessage> {
+    self.diff_message.as_ref()
+  }
+
+  /// Returns the size in bytes of the data of the message that would be published, using the
+  /// encoding of the client, or `0` if there is nothing to publish.
+  pub fn message_size(&self) -> usize {
+    self.message_size
+  }
+
+  /// Returns the updates that could not be applied and the reasons the publication would fail.
+  pub fn failures(&self) -> &[UpdateFailure] {
+    &self.failures
+  }
+
+  /// Returns `true` if all updates could be applied and the resulting document could be published.
+  pub fn is_valid(&self) -> bool {
+    self.failures.is_empty()
+  }
+}
+
+/// How the document resulting from an [`UpdateBatch`](crate::types::UpdateBatch) would be published.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PublicationKind {
+  /// The document would be published as an integration message.
+  Integration,
+  /// The changes would be published as a diff message.
+  Diff,
+}
+
+/// An update of an [`UpdateBatch`](crate::types::UpdateBatch) that could not be applied, or a reason the
+/// publication of the resulting document would fail.
+#[derive(Debug)]
+pub struct UpdateFailure {
+  /// The position of the update in the batch, or `None` if the publication would fail.
+  pub index: Option<usize>,
+  /// The error that occurred.
+  pub error: Error,
+}
@@ -23,4 +23,6 @@ pub enum UpdateError {
   DuplicateKeyLocation(KeyLocation),
   #[error("duplicate service fragment - {0}")]
   DuplicateServiceFragment(String),
+  #[error("document is deactivated")]
+  DocumentDeactivated,
 }
@@ -38,7 +38,7 @@ macro_rules! impl_update_builder {
       where
         C: identity_iota_client::tangle::SharedPtr<Client>,
       {
-        account: &'account mut Account<C>,
+        target: $crate::types::UpdateTarget<'account, C>,
         $(
           $field: Option<$ty>,
         )*
@@ -60,8 +60,12 @@ macro_rules! impl_update_builder {
       where
         C: identity_iota_client::tangle::SharedPtr<Client>,
         {
+          Self::with_target($crate::types::UpdateTarget::Account(account))
+        }
+
+        fn with_target(target: $crate::types::UpdateTarget<'account, C>) -> [<$ident Builder>]<'account, C> {
           [<$ident Builder>] {
-            account,
+            target,
             $(
               $field: None,
             )*
@@ -75,7 +79,7 @@ macro_rules! impl_update_builder {
             )*
           };
 
-          self.account.process_update(update).await
+          self.target.apply(update).await
         }
       }
 
@@ -88,6 +92,16 @@ macro_rules! impl_update_builder {
           [<$ident Builder>]::new(self.account)
         }
       }
+
+      impl<'account, C> $crate::types::UpdateBatch<'account, C>
+      where
+        C: identity_iota_client::tangle::SharedPtr<Client>,
+      {
+        /// Creates a new builder to add an update to the batch. See the documentation of the return type for details.
+        pub fn [<$ident:snake>](&mut self) -> [<$ident Builder>]<'_, C> {
+          [<$ident Builder>]::with_target($crate::types::UpdateTarget::Batch(&mut self.updates))
+        }
+      }
     }
   };
 }
@@ -4,6 +4,7 @@
 use log::debug;
 use log::trace;
 
+use identity_account_storage::crypto::KeyDerivation;
 use identity_account_storage::storage::Storage;
 use identity_account_storage::types::KeyLocation;
 use identity_core::common::Fragment;
@@ -50,7 +51,18 @@ pub(crate) async fn create_identity(
       .map_err(|err| UpdateError::InvalidMethodContent(err.to_string()))?;
   };
 
-  let (did, location) = store.did_create(network.clone(), fragment, setup.private_key).await?;
+  let private_key: Option<PrivateKey> = match (setup.private_key, setup.key_derivation) {
+    (Some(_), Some(_)) => {
+      return Err(
+        UpdateError::InvalidMethodContent("cannot use both a private key and a key derivation".to_owned()).into(),
+      );
+    }
+    (Some(private_key), None) => Some(private_key),
+    (None, Some(key_derivation)) => Some(key_derivation.derive_root()?.private().clone()),
+    (None, None) => None,
+  };
+
+  let (did, location) = store.did_create(network.clone(), fragment, private_key).await?;
 
   let public_key: PublicKey = store.key_public(&did, &location).await?;
 
@@ -95,14 +107,47 @@ pub(crate) enum Update {
   SetAlsoKnownAs {
     urls: OrderedSet<Url>,
   },
+  Deactivate {},
 }
 
 impl Update {
-  pub(crate) async fn process(self, did: &IotaDID, document: &mut IotaDocument, storage: &dyn Storage) -> Result<()> {
+  /// Applies the update to `document`, adding the locations of the keys it creates in the `storage` to
+  /// `created_keys` as soon as they are created, so they can be removed if a later step fails.
+  pub(crate) async fn process(
+    self,
+    did: &IotaDID,
+    document: &mut IotaDocument,
+    storage: &dyn Storage,
+    created_keys: &mut Vec<KeyLocation>,
+  ) -> Result<()> {
     debug!("[Update::process] Update = {:?}", self);
     trace!("[Update::process] Document = {:?}", document);
     trace!("[Update::process] Store = {:?}", storage);
 
+    self.apply(did, document, storage, false, created_keys).await
+  }
+
+  /// Applies the update to `document` without modifying the storage.
+  ///
+  /// Generated keys are ephemeral and private keys are only used to derive the public keys.
+  pub(crate) async fn preview(self, did: &IotaDID, document: &mut IotaDocument, storage: &dyn Storage) -> Result<()> {
+    debug!("[Update::preview] Update = {:?}", self);
+    trace!("[Update::preview] Document = {:?}", document);
+
+    self.apply(did, document, storage, true, &mut Vec::new()).await
+  }
+
+  async fn apply(
+    self,
+    did: &IotaDID,
+    document: &mut IotaDocument,
+    storage: &dyn Storage,
+    preview: bool,
+    created_keys: &mut Vec<KeyLocation>,
+  ) -> Result<()> {
+    // A deactivated identity cannot be updated anymore.
+    ensure!(!document.is_deactivated(), UpdateError::DocumentDeactivated);
+
     match self {
       Self::CreateMethod {
         scope,
@@ -120,18 +165,40 @@ impl Update {
         // Generate or extract the private key and/or retrieve the public key.
         let key_type: KeyType = content.key_type();
 
-        let public: PublicKey = match content {
-          MethodContent::GenerateEd25519 | MethodContent::GenerateX25519 => {
+        let public: PublicKey = match (content, preview) {
+          (MethodContent::GenerateEd25519 | MethodContent::GenerateX25519, false) => {
             let location: KeyLocation = storage.key_generate(did, key_type, fragment.name()).await?;
+            created_keys.push(location.clone());
             storage.key_public(did, &location).await?
           }
-          MethodContent::PrivateEd25519(private_key) | MethodContent::PrivateX25519(private_key) => {
+          (MethodContent::PrivateEd25519(private_key) | MethodContent::PrivateX25519(private_key), false) => {
             let location: KeyLocation =
               insert_method_secret(storage, did, key_type, fragment.name(), private_key).await?;
+            created_keys.push(location.clone());
+            storage.key_public(did, &location).await?
+          }
+          (MethodContent::DeriveEd25519(key_derivation) | MethodContent::DeriveX25519(key_derivation), false) => {
+            let location: KeyLocation =
+              insert_derived_secret(storage, did, key_type, fragment.name(), &key_derivation).await?;
+            created_keys.push(location.clone());
             storage.key_public(did, &location).await?
           }
-          MethodContent::PublicEd25519(public_key) => public_key,
-          MethodContent::PublicX25519(public_key) => public_key,
+          (MethodContent::GenerateEd25519 | MethodContent::GenerateX25519, true) => {
+            KeyPair::new(key_type)?.public().clone()
+          }
+          (MethodContent::PrivateEd25519(private_key) | MethodContent::PrivateX25519(private_key), true) => {
+            KeyPair::try_from_private_key_bytes(key_type, private_key.as_ref())
+              .map_err(|err| UpdateError::InvalidMethodContent(err.to_string()))?
+              .public()
+              .clone()
+          }
+          (MethodContent::DeriveEd25519(key_derivation) | MethodContent::DeriveX25519(key_derivation), true) => {
+            let (keypair, _): (KeyPair, KeyLocation) =
+              next_derived_key(storage, did, key_type, fragment.name(), &key_derivation).await?;
+            keypair.public().clone()
+          }
+          (MethodContent::PublicEd25519(public_key), _) => public_key,
+          (MethodContent::PublicX25519(public_key), _) => public_key,
         };
 
         // Insert a new method.
@@ -238,6 +305,9 @@ impl Update {
       Self::SetAlsoKnownAs { urls } => {
         *document.also_known_as_mut() = urls;
       }
+      Self::Deactivate {} => {
+        document.deactivate()?;
+      }
     }
 
     document.metadata.updated = Some(Timestamp::now_utc());
@@ -269,6 +339,67 @@ async fn insert_method_secret(
   Ok(location)
 }
 
+/// Derives the key of a method from `key_derivation` and inserts it into the storage.
+///
+/// See [`next_derived_key`].
+async fn insert_derived_secret(
+  store: &dyn Storage,
+  did: &IotaDID,
+  key_type: KeyType,
+  fragment: &str,
+  key_derivation: &KeyDerivation,
+) -> Result<KeyLocation> {
+  let (keypair, location): (KeyPair, KeyLocation) =
+    next_derived_key(store, did, key_type, fragment, key_derivation).await?;
+
+  store.key_insert(did, &location, keypair.private().clone()).await?;
+
+  Ok(location)
+}
+
+/// Derives the key of a method from `key_derivation` and returns it with its storage location.
+///
+/// Uses the first generation whose key is not in the storage yet, so re-creating a method with the
+/// same fragment does not reuse a previous key.
+async fn next_derived_key(
+  store: &dyn Storage,
+  did: &IotaDID,
+  key_type: KeyType,
+  fragment: &str,
+  key_derivation: &KeyDerivation,
+) -> Result<(KeyPair, KeyLocation)> {
+  ensure_key_derivation(did, key_derivation)?;
+
+  for generation in 0..KeyDerivation::GENERATION_LIMIT {
+    let keypair: KeyPair = key_derivation.derive(key_type, fragment, generation)?;
+    let location: KeyLocation = KeyLocation::new(key_type, fragment.to_owned(), keypair.public().as_ref());
+
+    if !store.key_exists(did, &location).await? {
+      return Ok((keypair, location));
+    }
+  }
+
+  Err(
+    UpdateError::InvalidMethodContent(format!(
+      "all {} derived keys of the fragment are in use",
+      KeyDerivation::GENERATION_LIMIT
+    ))
+    .into(),
+  )
+}
+
+/// Ensures the DID derived from `key_derivation` is `did`, so the keys derived for the identity can be
+/// recovered.
+pub(crate) fn ensure_key_derivation(did: &IotaDID, key_derivation: &KeyDerivation) -> Result<()> {
+  let network: NetworkName = NetworkName::try_from(did.network_str().to_owned())?;
+
+  if &key_derivation.did(network)? != did {
+    return Err(crate::Error::KeyDerivationMismatch);
+  }
+
+  Ok(())
+}
+
 // =============================================================================
 
 // =============================================================================
@@ -383,3 +514,11 @@ impl_update_builder!(
 SetAlsoKnownAs {
     @required urls OrderedSet<Url>,
 });
+
+impl_update_builder!(
+/// Deactivate an identity by publishing a final integration message that removes all
+/// verification methods and services from the document.
+///
+/// The deactivation is permanent: the DID can still be resolved, but no further updates
+/// will be accepted. The keys of the identity are kept in storage.
+Deactivate {});
@@ -0,0 +1,336 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Debug;
+use core::fmt::Display;
+use core::fmt::Formatter;
+
+use crypto::hashes::sha::SHA256;
+use crypto::hashes::sha::SHA256_LEN;
+use crypto::keys::bip39;
+use crypto::keys::bip39::wordlist;
+use crypto::keys::slip10::Chain;
+use crypto::keys::slip10::Curve;
+use crypto::keys::slip10::Key;
+use crypto::keys::slip10::Seed;
+use identity_core::crypto::KeyPair;
+use identity_core::crypto::KeyType;
+use identity_iota_core::did::IotaDID;
+use identity_iota_core::document::IotaDocument;
+use identity_iota_core::tangle::NetworkName;
+use zeroize::Zeroize;
+
+use crate::error::Error;
+use crate::error::Result;
+
+/// The purpose of derivation paths, as defined by BIP-44.
+const PURPOSE: u32 = 44;
+
+/// The SLIP-44 coin type of IOTA.
+const COIN_TYPE: u32 = 4218;
+
+/// The number of bytes of entropy encoded by a generated mnemonic, resulting in 24 words.
+const MNEMONIC_ENTROPY_LEN: usize = 32;
+
+/// Derives the keys of an identity from a seed, following SLIP-10 for the Ed25519 curve.
+///
+/// Keys are derived along the hardened path
+///
+/// `m/44'/4218'/{identity}'/{key_type}'/{fragment}'/{generation}'`
+///
+/// where `identity` is the index of the identity derived from the seed, `key_type` is `0` for
+/// [`Ed25519`](KeyType::Ed25519) and `1` for [`X25519`](KeyType::X25519) keys, `fragment` is derived from
+/// the hash of the method fragment and `generation` distinguishes keys created for the same fragment.
+///
+/// The DID of the identity is derived from its root key, which is the first generation of the Ed25519 key
+/// with the [default method fragment](IotaDocument::DEFAULT_METHOD_FRAGMENT). Hence every identity
+/// index of a seed corresponds to exactly one DID, and all keys of that identity can be recovered
+/// from the seed and the index.
+#[derive(Clone)]
+pub struct KeyDerivation {
+  seed: Vec<u8>,
+  identity: u32,
+}
+
+impl KeyDerivation {
+  /// The number of generations derived for each fragment. Keys of later generations are not found when
+  /// recovering an identity.
+  pub const GENERATION_LIMIT: u32 = 100;
+
+  /// Creates a new `KeyDerivation` for the identity with the index `identity` from the given `seed`.
+  pub fn new(seed: &[u8], identity: u32) -> Self {
+    Self {
+      seed: seed.to_vec(),
+      identity,
+    }
+  }
+
+  /// Creates a new `KeyDerivation` for the identity with the index `identity` from the seed of an
+  /// English BIP-39 `mnemonic` and an optional `passphrase`, which may be empty.
+  pub fn from_mnemonic(mnemonic: &str, passphrase: &str, identity: u32) -> Result<Self> {
+    wordlist::verify(mnemonic, &wordlist::ENGLISH).map_err(|err| Error::InvalidMnemonic(format!("{:?}", err)))?;
+
+    let mut seed: [u8; 64] = [0; 64];
+    bip39::mnemonic_to_seed(mnemonic, passphrase, &mut seed);
+
+    let derivation: Self = Self::new(&seed, identity);
+    seed.zeroize();
+
+    Ok(derivation)
+  }
+
+  /// Generates a new random English BIP-39 mnemonic of 24 words.
+  pub fn generate_mnemonic() -> Result<String> {
+    let mut entropy: [u8; MNEMONIC_ENTROPY_LEN] = [0; MNEMONIC_ENTROPY_LEN];
+    crypto::utils::rand::fill(&mut entropy).map_err(Error::KeyDerivationFailure)?;
+
+    let mnemonic: Result<String> =
+      wordlist::encode(&entropy, &wordlist::ENGLISH).map_err(|err| Error::InvalidMnemonic(format!("{:?}", err)));
+    entropy.zeroize();
+
+    mnemonic
+  }
+
+  /// Returns the index of the identity.
+  pub fn identity(&self) -> u32 {
+    self.identity
+  }
+
+  /// Returns the derivation path of the key with the given `key_type`, `fragment` and `generation`.
+  pub fn path(&self, key_type: KeyType, fragment: &str, generation: u32) -> DerivationPath {
+    DerivationPath::new(self.identity, key_type, fragment, generation)
+  }
+
+  /// Derives the key pair with the given `key_type`, `fragment` and `generation`.
+  pub fn derive(&self, key_type: KeyType, fragment: &str, generation: u32) -> Result<KeyPair> {
+    let chain: Chain = Chain::from_u32_hardened(self.path(key_type, fragment, generation).indices());
+
+    let mut key: Key = Seed::from_bytes(&self.seed)
+      .derive(Curve::Ed25519, &chain)
+      .map_err(Error::KeyDerivationFailure)?;
+    let keypair: Result<KeyPair> = Self::keypair(&key, key_type);
+    key.zeroize();
+
+    keypair
+  }
+
+  /// Searches the first [`GENERATION_LIMIT`](Self::GENERATION_LIMIT) generations of the keys with the given
+  /// `key_type` and `fragment` for the key pair with the given `public_key`.
+  ///
+  /// The key of the fragment is derived only once, each generation then requires a single derivation step.
+  pub fn find(&self, key_type: KeyType, fragment: &str, public_key: &[u8]) -> Result<Option<(u32, KeyPair)>> {
+    let indices: [u32; 6] = self.path(key_type, fragment, 0).indices();
+    let chain: Chain = Chain::from_u32_hardened(indices[..indices.len() - 1].iter().copied());
+
+    let mut parent: Key = Seed::from_bytes(&self.seed)
+      .derive(Curve::Ed25519, &chain)
+      .map_err(Error::KeyDerivationFailure)?;
+    let mut found: Result<Option<(u32, KeyPair)>> = Ok(None);
+
+    for generation in 0..Self::GENERATION_LIMIT {
+      let keypair: Result<KeyPair> = parent
+        .derive(&Chain::from_u32_hardened([generation]))
+        .map_err(Error::KeyDerivationFailure)
+        .and_then(|mut key| {
+          let keypair: Result<KeyPair> = Self::keypair(&key, key_type);
+          key.zeroize();
+          keypair
+        });
+
+      match keypair {
+        Ok(keypair) if keypair.public().as_ref() == public_key => {
+          found = Ok(Some((generation, keypair)));
+          break;
+        }
+        Ok(_) => {}
+        Err(error) => {
+          found = Err(error);
+          break;
+        }
+      }
+    }
+
+    parent.zeroize();
+
+    found
+  }
+
+  /// Derives the root key pair of the identity, from which its DID is derived.
+  pub fn derive_root(&self) -> Result<KeyPair> {
+    self.derive(KeyType::Ed25519, IotaDocument::DEFAULT_METHOD_FRAGMENT, 0)
+  }
+
+  /// Returns the DID of the identity on the given `network`.
+  pub fn did(&self, network: NetworkName) -> Result<IotaDID> {
+    let root: KeyPair = self.derive_root()?;
+
+    IotaDID::new_with_network(root.public().as_ref(), network).map_err(|err| Error::DIDCreationError(err.to_string()))
+  }
+
+  /// Creates a key pair of the given `key_type` from the secret of a derived `key`.
+  fn keypair(key: &Key, key_type: KeyType) -> Result<KeyPair> {
+    let mut secret: [u8; 32] = key.secret_key().to_bytes();
+
+    // The derived secret is a valid private key of either key type.
+    let keypair: Result<KeyPair> =
+      KeyPair::try_from_private_key_bytes(key_type, &secret).map_err(|err| Error::InvalidPrivateKey(err.to_string()));
+    secret.zeroize();
+
+    keypair
+  }
+}
+
+impl Debug for KeyDerivation {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    f.debug_struct("KeyDerivation")
+      .field("seed", &"[redacted]")
+      .field("identity", &self.identity)
+      .finish()
+  }
+}
+
+impl Drop for KeyDerivation {
+  fn drop(&mut self) {
+    self.seed.zeroize();
+  }
+}
+
+/// The SLIP-10 derivation path of a key, see [`KeyDerivation`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DerivationPath {
+  identity: u32,
+  key_type: u32,
+  fragment: u32,
+  generation: u32,
+}
+
+impl DerivationPath {
+  /// Creates the derivation path of the key with the given `key_type`, `fragment` and `generation`
+  /// of the identity with the index `identity`.
+  pub fn new(identity: u32, key_type: KeyType, fragment: &str, generation: u32) -> Self {
+    let key_type: u32 = match key_type {
+      KeyType::Ed25519 => 0,
+      KeyType::X25519 => 1,
+    };
+
+    Self {
+      identity,
+      key_type,
+      fragment: fragment_index(fragment),
+      generation,
+    }
+  }
+
+  /// Returns the indices of the path, which are all hardened.
+  pub fn indices(&self) -> [u32; 6] {
+    [
+      PURPOSE,
+      COIN_TYPE,
+      self.identity,
+      self.key_type,
+      self.fragment,
+      self.generation,
+    ]
+  }
+}
+
+impl Display for DerivationPath {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    f.write_str("m")?;
+
+    for index in self.indices() {
+      write!(f, "/{}'", index)?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Maps a method fragment to an index of a derivation path, using the first 31 bits of its SHA-256 hash.
+fn fragment_index(fragment: &str) -> u32 {
+  let mut digest: [u8; SHA256_LEN] = [0; SHA256_LEN];
+  SHA256(fragment.as_bytes(), &mut digest);
+
+  u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]) >> 1
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::crypto::KeyPair;
+  use identity_core::crypto::KeyType;
+
+  use super::DerivationPath;
+  use super::KeyDerivation;
+
+  const MNEMONIC: &str = "veteran provide abstract express quick another fee dragon trend extend cotton tail dog \
+                          truly angle napkin lunch dinosaur shrimp odor gain bag media mountain";
+
+  fn public_key(derivation: &KeyDerivation, key_type: KeyType, fragment: &str, generation: u32) -> Vec<u8> {
+    derivation
+      .derive(key_type, fragment, generation)
+      .unwrap()
+      .public()
+      .as_ref()
+      .to_vec()
+  }
+
+  #[test]
+  fn test_derivation_is_deterministic() {
+    let derivation: KeyDerivation = KeyDerivation::from_mnemonic(MNEMONIC, "", 0).unwrap();
+    let other: KeyDerivation = KeyDerivation::from_mnemonic(MNEMONIC, "", 0).unwrap();
+
+    for key_type in [KeyType::Ed25519, KeyType::X25519] {
+      let keypair: KeyPair = derivation.derive(key_type, "key-1", 0).unwrap();
+      assert_eq!(keypair.type_(), key_type);
+      assert_eq!(keypair.public().as_ref(), public_key(&other, key_type, "key-1", 0));
+    }
+  }
+
+  #[test]
+  fn test_derivation_paths_are_distinct() {
+    let derivation: KeyDerivation = KeyDerivation::from_mnemonic(MNEMONIC, "", 0).unwrap();
+    let public: Vec<u8> = public_key(&derivation, KeyType::Ed25519, "key-1", 0);
+
+    assert_ne!(public, public_key(&derivation, KeyType::Ed25519, "key-2", 0));
+    assert_ne!(public, public_key(&derivation, KeyType::Ed25519, "key-1", 1));
+
+    let other_identity: KeyDerivation = KeyDerivation::from_mnemonic(MNEMONIC, "", 1).unwrap();
+    assert_ne!(public, public_key(&other_identity, KeyType::Ed25519, "key-1", 0));
+
+    let other_passphrase: KeyDerivation = KeyDerivation::from_mnemonic(MNEMONIC, "passphrase", 0).unwrap();
+    assert_ne!(public, public_key(&other_passphrase, KeyType::Ed25519, "key-1", 0));
+  }
+
+  #[test]
+  fn test_find_generation() {
+    let derivation: KeyDerivation = KeyDerivation::from_mnemonic(MNEMONIC, "", 0).unwrap();
+
+    for key_type in [KeyType::Ed25519, KeyType::X25519] {
+      let public: Vec<u8> = public_key(&derivation, key_type, "key-1", 7);
+      let (generation, keypair): (u32, KeyPair) = derivation.find(key_type, "key-1", &public).unwrap().unwrap();
+
+      assert_eq!(generation, 7);
+      assert_eq!(keypair.public().as_ref(), public);
+      assert!(derivation.find(key_type, "key-2", &public).unwrap().is_none());
+    }
+  }
+
+  #[test]
+  fn test_derivation_path_display() {
+    let path: DerivationPath = DerivationPath::new(3, KeyType::X25519, "key-1", 2);
+    let indices: [u32; 6] = path.indices();
+
+    assert!(indices[4] < 1 << 31);
+    assert_eq!(path.to_string(), format!("m/44'/4218'/3'/1'/{}'/2'", indices[4]));
+  }
+
+  #[test]
+  fn test_mnemonic() {
+    let mnemonic: String = KeyDerivation::generate_mnemonic().unwrap();
+    assert_eq!(mnemonic.split(' ').count(), 24);
+    assert!(KeyDerivation::from_mnemonic(&mnemonic, "", 0).is_ok());
+
+    assert!(KeyDerivation::from_mnemonic("veteran provide abstract", "", 0).is_err());
+    assert!(KeyDerivation::from_mnemonic(&MNEMONIC.replace("veteran", "abandon"), "", 0).is_err());
+  }
+}
@@ -1,6 +1,8 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+mod derivation;
 mod remote;
 
+pub use self::derivation::*;
 pub use self::remote::*;
@@ -16,6 +16,23 @@ use identity_core::error::Error;
 use identity_core::error::Result;
 use identity_core::utils::BaseEncoding;
 
+#[cfg(feature = "jose")]
+use identity_core::crypto::KeyType;
+#[cfg(feature = "jose")]
+use identity_core::crypto::PublicKey;
+#[cfg(feature = "jose")]
+use libjose::jwe::AsyncJweKeyAgreement;
+#[cfg(feature = "jose")]
+use libjose::jwk::EcdhCurve;
+#[cfg(feature = "jose")]
+use libjose::jwk::EcxCurve;
+#[cfg(feature = "jose")]
+use libjose::jws::AsyncJwsSigner;
+#[cfg(feature = "jose")]
+use libjose::jws::JwsAlgorithm;
+#[cfg(feature = "jose")]
+use libjose::utils::Secret;
+
 use crate::storage::Storage;
 use crate::types::KeyLocation;
 use crate::types::Signature as StorageSignature;
@@ -94,3 +111,135 @@ impl<'a> RemoteSign<'a> {
       .map_err(|_| Error::InvalidProofValue("remote sign"))
   }
 }
+
+// =============================================================================
+// JWS
+// =============================================================================
+
+/// Signs JWS tokens with the key in storage, see
+/// [`Recipient::new_with_async_signer`](libjose::jws::Recipient::new_with_async_signer).
+///
+/// Only `EdDSA` with Ed25519 keys is supported.
+#[cfg(feature = "jose")]
+#[async_trait::async_trait(?Send)]
+impl AsyncJwsSigner for RemoteKey<'_> {
+  async fn sign(&self, algorithm: JwsAlgorithm, message: &[u8]) -> libjose::Result<Vec<u8>> {
+    if algorithm != JwsAlgorithm::EdDSA || self.location.key_type != KeyType::Ed25519 {
+      return Err(libjose::Error::AlgError(algorithm.name()));
+    }
+
+    RemoteSign::sign(message, self)
+      .await
+      .map(Into::into)
+      .map_err(|_| libjose::Error::SigError("remote sign"))
+  }
+}
+
+// =============================================================================
+// JWE
+// =============================================================================
+
+/// Performs the sender's static key agreement of ECDH-1PU JWE tokens with the key in storage, see
+/// [`Encoder::async_key_agreement`](libjose::jwe::Encoder::async_key_agreement).
+///
+/// Only X25519 keys are supported, and the storage must implement [`Storage::key_exchange`].
+#[cfg(feature = "jose")]
+#[async_trait::async_trait(?Send)]
+impl AsyncJweKeyAgreement for RemoteKey<'_> {
+  async fn diffie_hellman(&self, curve: EcdhCurve, public: Secret<'_>) -> libjose::Result<Vec<u8>> {
+    if curve != EcdhCurve::Ecx(EcxCurve::X25519) || self.location.key_type != KeyType::X25519 {
+      return Err(libjose::Error::AlgError(curve.name()));
+    }
+
+    let public_key: PublicKey = public.to_x25519_public()?.to_bytes().to_vec().into();
+
+    self
+      .store
+      .key_exchange(self.did, self.location, public_key)
+      .await
+      .map_err(|_| libjose::Error::EncError("remote key exchange"))
+  }
+}
+
+#[cfg(all(test, feature = "jose"))]
+mod tests {
+  use identity_core::crypto::KeyPair;
+  use identity_core::crypto::KeyType;
+  use identity_core::crypto::PublicKey;
+  use identity_iota_core::did::IotaDID;
+  use identity_iota_core::tangle::Network;
+  use libjose::jwe;
+  use libjose::jwe::JweAlgorithm;
+  use libjose::jwe::JweEncryption;
+  use libjose::jwe::JweHeader;
+  use libjose::jws::Decoder;
+  use libjose::jws::Encoder;
+  use libjose::jws::JwsAlgorithm;
+  use libjose::jws::JwsHeader;
+  use libjose::jws::Recipient;
+  use libjose::jws::Token;
+
+  use crate::storage::MemStore;
+  use crate::storage::Storage;
+  use crate::types::KeyLocation;
+
+  use super::RemoteKey;
+
+  #[tokio::test]
+  async fn test_remote_key_jws() {
+    let store: MemStore = MemStore::new();
+    let (did, location): (IotaDID, KeyLocation) =
+      store.did_create(Network::Mainnet.name(), "key-1", None).await.unwrap();
+    let public_key: PublicKey = store.key_public(&did, &location).await.unwrap();
+
+    let remote_key: RemoteKey<'_> = RemoteKey::new(&did, &location, &store);
+    let header: JwsHeader = JwsHeader::new(JwsAlgorithm::EdDSA);
+    let recipient: Recipient<'_> = Recipient::new_with_async_signer(&remote_key).protected(&header);
+    let encoder: Encoder<'_> = Encoder::new().recipient(recipient);
+
+    let encoded: String = encoder.encode_async(b"claims").await.unwrap();
+    let decoded: Token<'_> = Decoder::new(public_key.as_ref()).decode(encoded.as_bytes()).unwrap();
+    assert_eq!(decoded.claims.as_ref(), b"claims");
+
+    // Keys unable to sign are rejected.
+    let location: KeyLocation = store.key_generate(&did, KeyType::X25519, "key-2").await.unwrap();
+    let remote_key: RemoteKey<'_> = RemoteKey::new(&did, &location, &store);
+    let recipient: Recipient<'_> = Recipient::new_with_async_signer(&remote_key).protected(&header);
+    let encoder: Encoder<'_> = Encoder::new().recipient(recipient);
+    assert!(encoder.encode_async(b"claims").await.is_err());
+  }
+
+  #[tokio::test]
+  async fn test_remote_key_jwe() {
+    let store: MemStore = MemStore::new();
+    let (did, _): (IotaDID, KeyLocation) = store.did_create(Network::Mainnet.name(), "key-1", None).await.unwrap();
+    let location: KeyLocation = store.key_generate(&did, KeyType::X25519, "kex-1").await.unwrap();
+    let sender_public: PublicKey = store.key_public(&did, &location).await.unwrap();
+    let recipient: KeyPair = KeyPair::new(KeyType::X25519).unwrap();
+
+    let remote_key: RemoteKey<'_> = RemoteKey::new(&did, &location, &store);
+    let decoder: jwe::Decoder<'_> = jwe::Decoder::new(recipient.private().as_ref()).public(sender_public.as_ref());
+
+    for algorithm in [JweAlgorithm::ECDH_1PU, JweAlgorithm::ECDH_1PU_A256KW] {
+      let header: JweHeader = JweHeader::new(algorithm, JweEncryption::A256GCM);
+      let encoder: jwe::Encoder<'_> = jwe::Encoder::new()
+        .protected(&header)
+        .async_key_agreement(&remote_key)
+        .recipient(recipient.public().as_ref());
+
+      let encoded: String = encoder.encode_async(b"claims").await.unwrap();
+      let decoded: jwe::Token = decoder.decode(encoded.as_bytes()).unwrap();
+      assert_eq!(decoded.1, b"claims");
+    }
+
+    // Keys unable to perform key exchanges are rejected.
+    let location: KeyLocation = store.key_generate(&did, KeyType::Ed25519, "key-2").await.unwrap();
+    let remote_key: RemoteKey<'_> = RemoteKey::new(&did, &location, &store);
+    let header: JweHeader = JweHeader::new(JweAlgorithm::ECDH_1PU, JweEncryption::A256GCM);
+    let encoder: jwe::Encoder<'_> = jwe::Encoder::new()
+      .protected(&header)
+      .async_key_agreement(&remote_key)
+      .recipient(recipient.public().as_ref());
+    assert!(encoder.encode_async(b"claims").await.is_err());
+  }
+}
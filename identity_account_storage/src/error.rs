@@ -39,6 +39,12 @@ pub enum Error {
   /// Caused by failing to encrypt data.
   #[error("failed to encrypt data")]
   EncryptionFailure(#[source] crypto::error::Error),
+  /// Caused by failing to derive a key from a seed.
+  #[error("failed to derive key")]
+  KeyDerivationFailure(#[source] crypto::error::Error),
+  /// Caused by providing an invalid BIP-39 mnemonic.
+  #[error("invalid mnemonic: {0}")]
+  InvalidMnemonic(String),
   /// Caused by attempting to find a key in storage that does not exist.
   #[error("key not found")]
   KeyNotFound,
@@ -51,6 +57,18 @@ pub enum Error {
   /// Caused by attempting to write a poisoned shared resource.
   #[error("shared resource poisoned: write")]
   SharedWritePoisoned,
+  /// Caused by attempting to export keys from a storage that does not reveal private keys.
+  #[error("the storage does not support exporting keys")]
+  KeyExportUnsupported,
+  /// Caused by attempting a key exchange with a storage that does not reveal shared secrets.
+  #[error("the storage does not support key exchanges")]
+  KeyExchangeUnsupported,
+  /// Caused by attempting to access an identity that does not exist.
+  #[error("identity not found")]
+  IdentityNotFound,
+  /// Caused by reading an archive that is malformed or inconsistent.
+  #[error("invalid archive: {0}")]
+  InvalidArchive(String),
   /// Caused by attempting to create a DID that already exists.
   #[error("identity already exists")]
   IdentityAlreadyExists,
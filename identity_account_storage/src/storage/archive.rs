@@ -0,0 +1,532 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use std::collections::BTreeSet;
+
+use crypto::ciphers::aes_gcm::Aes256Gcm;
+use crypto::ciphers::traits::Aead;
+use crypto::keys::pbkdf::PBKDF2_HMAC_SHA512;
+use identity_core::convert::FromJson;
+use identity_core::convert::ToJson;
+use identity_core::crypto::KeyPair;
+use identity_core::crypto::KeyType;
+use identity_core::crypto::PrivateKey;
+use identity_iota_core::did::IotaDID;
+use identity_iota_core::tangle::NetworkName;
+use serde::Deserialize;
+use serde::Serialize;
+use zeroize::Zeroize;
+
+use crate::error::Error;
+use crate::error::Result;
+use crate::storage::Storage;
+use crate::types::KeyLocation;
+use crate::utils::EncryptionKey;
+
+/// Identifies the encrypted representation of a [`StorageArchive`].
+const ARCHIVE_MAGIC: &[u8; 16] = b"identity-archive";
+
+/// The version of the encrypted representation.
+const ARCHIVE_VERSION: u8 = 1;
+
+/// The number of PBKDF2 iterations used to derive the encryption key from the password.
+const PBKDF_ITERATIONS: u32 = 100_000;
+
+/// The maximum number of PBKDF2 iterations accepted when decrypting an archive.
+const PBKDF_MAX_ITERATIONS: u32 = 10 * PBKDF_ITERATIONS;
+
+const SALT_LEN: usize = 16;
+
+/// The length of the header, which is authenticated as associated data: magic, version, iterations and salt.
+const HEADER_LEN: usize = ARCHIVE_MAGIC.len() + 1 + 4 + SALT_LEN;
+
+/// A portable copy of identities in a [`Storage`], consisting of the private keys and the blob of each DID.
+/// The blob holds the state an account stores for its identity, such as the document and the chain state.
+///
+/// An archive is created with [`StorageArchive::export`] and written to any [`Storage`] with
+/// [`StorageArchive::import`]. It is persisted or transferred in its password-encrypted form, see
+/// [`StorageArchive::encrypt`] and [`StorageArchive::decrypt`].
+///
+/// Exporting requires a storage that reveals its keys through [`Storage::key_list`] and
+/// [`Storage::key_export`], such as the [`MemStore`](crate::storage::MemStore) and a `Stronghold` with
+/// key export enabled. Importing works with every storage.
+#[derive(Deserialize, Serialize)]
+pub struct StorageArchive {
+  identities: Vec<ArchivedIdentity>,
+}
+
+impl StorageArchive {
+  /// Exports the identities with the given `dids` from the `storage`.
+  ///
+  /// Fails if one of the identities does not exist or the storage does not support exporting keys.
+  pub async fn export(storage: &dyn Storage, dids: &[IotaDID]) -> Result<Self> {
+    let mut identities: Vec<ArchivedIdentity> = Vec::new();
+
+    for did in dids.iter().collect::<BTreeSet<&IotaDID>>() {
+      if !storage.did_exists(did).await? {
+        return Err(Error::IdentityNotFound);
+      }
+
+      let mut keys: Vec<ArchivedKey> = Vec::new();
+
+      for location in storage.key_list(did).await? {
+        let private_key: PrivateKey = storage.key_export(did, &location).await?;
+
+        keys.push(ArchivedKey {
+          location,
+          private_key: private_key.as_ref().to_vec(),
+        });
+      }
+
+      let identity: ArchivedIdentity = ArchivedIdentity {
+        did: did.clone(),
+        blob: storage.blob_get(did).await?,
+        keys,
+      };
+
+      // Never produce an archive that cannot be imported.
+      identity.verify()?;
+
+      identities.push(identity);
+    }
+
+    Ok(Self { identities })
+  }
+
+  /// Exports all identities from the `storage`.
+  pub async fn export_all(storage: &dyn Storage) -> Result<Self> {
+    let dids: Vec<IotaDID> = storage.did_list().await?;
+
+    Self::export(storage, &dids).await
+  }
+
+  /// Copies the identities with the given `dids` from the `source` to the `target` storage, without
+  /// encrypting them.
+  ///
+  /// See [`StorageArchive::export`] and [`StorageArchive::import`].
+  pub async fn migrate(source: &dyn Storage, target: &dyn Storage, dids: &[IotaDID]) -> Result<()> {
+    Self::export(source, dids).await?.import(target).await
+  }
+
+  /// Returns the DIDs of the archived identities.
+  pub fn dids(&self) -> Vec<&IotaDID> {
+    self.identities.iter().map(|identity| &identity.did).collect()
+  }
+
+  /// Writes the archived identities to the `storage`.
+  ///
+  /// Every key is checked against its location and the DID against its key before anything is written.
+  /// Fails without modifying the storage if one of the identities exists in it already. If writing an
+  /// identity fails, the identities imported so far are removed again.
+  pub async fn import(&self, storage: &dyn Storage) -> Result<()> {
+    let root_keys: Vec<&ArchivedKey> = self
+      .identities
+      .iter()
+      .map(ArchivedIdentity::verify)
+      .collect::<Result<_>>()?;
+
+    for identity in self.identities.iter() {
+      if storage.did_exists(&identity.did).await? {
+        return Err(Error::IdentityAlreadyExists);
+      }
+    }
+
+    for (index, (identity, root_key)) in self.identities.iter().zip(root_keys).enumerate() {
+      if let Err(error) = identity.import(storage, root_key).await {
+        for imported in self.identities[..index].iter() {
+          if let Err(purge_error) = storage.did_purge(&imported.did).await {
+            log::warn!(
+              "[StorageArchive::import] failed to remove {}: {}",
+              imported.did,
+              purge_error
+            );
+          }
+        }
+
+        return Err(error);
+      }
+    }
+
+    storage.flush_changes().await
+  }
+
+  /// Serializes the archive and encrypts it with a key derived from the `password`.
+  ///
+  /// The result is authenticated, so [`StorageArchive::decrypt`] detects any modification.
+  pub fn encrypt(&self, password: &str) -> Result<Vec<u8>> {
+    self.encrypt_with_iterations(password, PBKDF_ITERATIONS)
+  }
+
+  fn encrypt_with_iterations(&self, password: &str, iterations: u32) -> Result<Vec<u8>> {
+    let mut salt: [u8; SALT_LEN] = [0; SALT_LEN];
+    crypto::utils::rand::fill(&mut salt).map_err(Error::EncryptionFailure)?;
+
+    let mut nonce: Vec<u8> = vec![0; Aes256Gcm::NONCE_LENGTH];
+    crypto::utils::rand::fill(&mut nonce).map_err(Error::EncryptionFailure)?;
+
+    let mut output: Vec<u8> = Vec::new();
+    output.extend_from_slice(ARCHIVE_MAGIC);
+    output.push(ARCHIVE_VERSION);
+    output.extend_from_slice(&iterations.to_be_bytes());
+    output.extend_from_slice(&salt);
+
+    let mut plaintext: Vec<u8> = self.to_json_vec()?;
+    let mut ciphertext: Vec<u8> = vec![0; plaintext.len()];
+    let mut tag: Vec<u8> = vec![0; Aes256Gcm::TAG_LENGTH];
+    let mut key: EncryptionKey = derive_key(password, &salt, iterations)?;
+
+    let result: Result<()> = Aes256Gcm::try_encrypt(&key, &nonce, &output, &plaintext, &mut ciphertext, &mut tag)
+      .map_err(Error::EncryptionFailure);
+    key.zeroize();
+    plaintext.zeroize();
+    result?;
+
+    output.extend_from_slice(&nonce);
+    output.extend_from_slice(&tag);
+    output.extend_from_slice(&ciphertext);
+
+    Ok(output)
+  }
+
+  /// Decrypts an archive created with [`StorageArchive::encrypt`].
+  ///
+  /// Fails with [`Error::DecryptionFailure`] if the `password` is wrong or the data was modified.
+  pub fn decrypt(data: &[u8], password: &str) -> Result<Self> {
+    if data.len() < HEADER_LEN + Aes256Gcm::NONCE_LENGTH + Aes256Gcm::TAG_LENGTH
+      || &data[..ARCHIVE_MAGIC.len()] != ARCHIVE_MAGIC
+    {
+      return Err(Error::InvalidArchive("unknown format".to_owned()));
+    }
+
+    let version: u8 = data[ARCHIVE_MAGIC.len()];
+    if version != ARCHIVE_VERSION {
+      return Err(Error::InvalidArchive(format!("unsupported version {}", version)));
+    }
+
+    let (header, data): (&[u8], &[u8]) = data.split_at(HEADER_LEN);
+    let (nonce, data): (&[u8], &[u8]) = data.split_at(Aes256Gcm::NONCE_LENGTH);
+    let (tag, ciphertext): (&[u8], &[u8]) = data.split_at(Aes256Gcm::TAG_LENGTH);
+
+    let mut iterations: [u8; 4] = [0; 4];
+    iterations.copy_from_slice(&header[ARCHIVE_MAGIC.len() + 1..HEADER_LEN - SALT_LEN]);
+    let iterations: u32 = u32::from_be_bytes(iterations);
+    if iterations == 0 || iterations > PBKDF_MAX_ITERATIONS {
+      return Err(Error::InvalidArchive(format!(
+        "unsupported iteration count {}",
+        iterations
+      )));
+    }
+
+    let salt: &[u8] = &header[HEADER_LEN - SALT_LEN..];
+    let mut plaintext: Vec<u8> = vec![0; ciphertext.len()];
+    let mut key: EncryptionKey = derive_key(password, salt, iterations)?;
+
+    let result: Result<usize> =
+      Aes256Gcm::try_decrypt(&key, nonce, header, &mut plaintext, ciphertext, tag).map_err(Error::DecryptionFailure);
+    key.zeroize();
+
+    let archive: Result<Self> = result
+      .and_then(|len| Self::from_json_slice(&plaintext[..len]).map_err(|err| Error::InvalidArchive(err.to_string())));
+    plaintext.zeroize();
+    let archive: Self = archive?;
+
+    let dids: BTreeSet<&IotaDID> = archive.dids().into_iter().collect();
+    if dids.len() != archive.identities.len() {
+      return Err(Error::InvalidArchive("duplicate identities".to_owned()));
+    }
+
+    Ok(archive)
+  }
+}
+
+impl Debug for StorageArchive {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    f.debug_struct("StorageArchive").field("dids", &self.dids()).finish()
+  }
+}
+
+/// The keys and the blob of a DID.
+#[derive(Deserialize, Serialize)]
+struct ArchivedIdentity {
+  did: IotaDID,
+  blob: Option<Vec<u8>>,
+  keys: Vec<ArchivedKey>,
+}
+
+impl ArchivedIdentity {
+  /// Checks that every key matches its location and returns the key the DID is derived from.
+  fn verify(&self) -> Result<&ArchivedKey> {
+    let network: NetworkName =
+      NetworkName::try_from(self.did.network_str().to_owned()).map_err(|err| Error::InvalidArchive(err.to_string()))?;
+
+    let mut root_key: Option<&ArchivedKey> = None;
+
+    for key in self.keys.iter() {
+      let keypair: KeyPair = KeyPair::try_from_private_key_bytes(key.location.key_type, &key.private_key)
+        .map_err(|err| Error::InvalidArchive(err.to_string()))?;
+
+      let location: KeyLocation = KeyLocation::new(
+        key.location.key_type,
+        key.location.fragment().to_owned(),
+        keypair.public().as_ref(),
+      );
+      if location != key.location {
+        return Err(Error::InvalidArchive(format!(
+          "the key at {} does not match its location",
+          location
+        )));
+      }
+
+      if root_key.is_none() && key.location.key_type == KeyType::Ed25519 {
+        let did: Option<IotaDID> = IotaDID::new_with_network(keypair.public().as_ref(), network.clone()).ok();
+
+        if did.as_ref() == Some(&self.did) {
+          root_key = Some(key);
+        }
+      }
+    }
+
+    root_key.ok_or_else(|| Error::InvalidArchive(format!("missing the key of {}", self.did)))
+  }
+
+  /// Creates the identity in the `storage`, removing it again if writing its keys or blob fails.
+  async fn import(&self, storage: &dyn Storage, root_key: &ArchivedKey) -> Result<()> {
+    let network: NetworkName =
+      NetworkName::try_from(self.did.network_str().to_owned()).map_err(|err| Error::InvalidArchive(err.to_string()))?;
+
+    let private_key: PrivateKey = PrivateKey::from(root_key.private_key.clone());
+    let (did, _): (IotaDID, KeyLocation) = storage
+      .did_create(network, root_key.location.fragment(), Some(private_key))
+      .await?;
+
+    let result: Result<()> = self.import_keys_and_blob(storage, &did, root_key).await;
+
+    if result.is_err() {
+      if let Err(purge_error) = storage.did_purge(&did).await {
+        log::warn!("[StorageArchive::import] failed to remove {}: {}", did, purge_error);
+      }
+    }
+
+    result
+  }
+
+  async fn import_keys_and_blob(&self, storage: &dyn Storage, did: &IotaDID, root_key: &ArchivedKey) -> Result<()> {
+    for key in self.keys.iter().filter(|key| key.location != root_key.location) {
+      let private_key: PrivateKey = PrivateKey::from(key.private_key.clone());
+      storage.key_insert(did, &key.location, private_key).await?;
+    }
+
+    if let Some(blob) = &self.blob {
+      storage.blob_set(did, blob.clone()).await?;
+    }
+
+    Ok(())
+  }
+}
+
+/// A private key and its location.
+#[derive(Deserialize, Serialize)]
+struct ArchivedKey {
+  location: KeyLocation,
+  private_key: Vec<u8>,
+}
+
+impl Drop for ArchivedKey {
+  fn drop(&mut self) {
+    self.private_key.zeroize();
+  }
+}
+
+/// Derives the encryption key of an archive from the `password`.
+fn derive_key(password: &str, salt: &[u8], iterations: u32) -> Result<EncryptionKey> {
+  let mut key: EncryptionKey = Default::default();
+
+  PBKDF2_HMAC_SHA512(password.as_bytes(), salt, iterations as usize, &mut key).map_err(Error::EncryptionFailure)?;
+
+  Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::crypto::KeyType;
+  use identity_core::crypto::PrivateKey;
+  use identity_core::crypto::PublicKey;
+  use identity_iota_core::did::IotaDID;
+  use identity_iota_core::tangle::NetworkName;
+
+  use crate::error::Error;
+  use crate::storage::MemStore;
+  use crate::storage::Storage;
+  use crate::types::KeyLocation;
+
+  use super::StorageArchive;
+
+  // Keeps the tests fast, the number of iterations is part of the archive.
+  const ITERATIONS: u32 = 10;
+
+  async fn setup_storage(count: usize) -> (MemStore, Vec<IotaDID>) {
+    let storage: MemStore = MemStore::new();
+    let mut dids: Vec<IotaDID> = Vec::new();
+
+    for index in 0..count {
+      let network: NetworkName = NetworkName::try_from("dev").unwrap();
+      let (did, _): (IotaDID, KeyLocation) = storage.did_create(network, "sign-0", None).await.unwrap();
+
+      storage.key_generate(&did, KeyType::Ed25519, "key-1").await.unwrap();
+      storage.key_generate(&did, KeyType::X25519, "kex-1").await.unwrap();
+      storage.blob_set(&did, vec![index as u8; 8]).await.unwrap();
+
+      dids.push(did);
+    }
+
+    (storage, dids)
+  }
+
+  async fn assert_identities_equal(source: &dyn Storage, target: &dyn Storage, dids: &[IotaDID]) {
+    let mut target_dids: Vec<IotaDID> = target.did_list().await.unwrap();
+    target_dids.sort();
+    let mut expected_dids: Vec<IotaDID> = dids.to_vec();
+    expected_dids.sort();
+    assert_eq!(target_dids, expected_dids);
+
+    for did in dids {
+      assert_eq!(source.blob_get(did).await.unwrap(), target.blob_get(did).await.unwrap());
+
+      let locations: Vec<KeyLocation> = source.key_list(did).await.unwrap();
+      assert_eq!(locations.len(), 3);
+      assert_eq!(target.key_list(did).await.unwrap().len(), locations.len());
+
+      for location in locations {
+        let public_key: PublicKey = source.key_public(did, &location).await.unwrap();
+        assert_eq!(
+          target.key_public(did, &location).await.unwrap().as_ref(),
+          public_key.as_ref()
+        );
+
+        let private_key: PrivateKey = source.key_export(did, &location).await.unwrap();
+        assert_eq!(
+          target.key_export(did, &location).await.unwrap().as_ref(),
+          private_key.as_ref()
+        );
+      }
+    }
+  }
+
+  #[tokio::test]
+  async fn test_archive_roundtrip() {
+    let (source, dids): (MemStore, Vec<IotaDID>) = setup_storage(2).await;
+
+    let archive: StorageArchive = StorageArchive::export_all(&source).await.unwrap();
+    let data: Vec<u8> = archive.encrypt_with_iterations("password", ITERATIONS).unwrap();
+
+    let archive: StorageArchive = StorageArchive::decrypt(&data, "password").unwrap();
+    let target: MemStore = MemStore::new();
+    archive.import(&target).await.unwrap();
+
+    assert_identities_equal(&source, &target, &dids).await;
+
+    // Existing identities are not overwritten.
+    assert!(matches!(
+      archive.import(&target).await.unwrap_err(),
+      Error::IdentityAlreadyExists
+    ));
+    assert_identities_equal(&source, &target, &dids).await;
+  }
+
+  #[tokio::test]
+  async fn test_archive_integrity() {
+    let (source, dids): (MemStore, Vec<IotaDID>) = setup_storage(1).await;
+
+    let archive: StorageArchive = StorageArchive::export(&source, &dids).await.unwrap();
+    let data: Vec<u8> = archive.encrypt_with_iterations("password", ITERATIONS).unwrap();
+
+    assert!(matches!(
+      StorageArchive::decrypt(&data, "wrong password").unwrap_err(),
+      Error::DecryptionFailure(_)
+    ));
+
+    // Modifications of the header or the ciphertext are detected.
+    for index in [super::HEADER_LEN - 1, data.len() - 1] {
+      let mut modified: Vec<u8> = data.clone();
+      modified[index] ^= 1;
+      assert!(matches!(
+        StorageArchive::decrypt(&modified, "password").unwrap_err(),
+        Error::DecryptionFailure(_)
+      ));
+    }
+
+    assert!(matches!(
+      StorageArchive::decrypt(&data[..32], "password").unwrap_err(),
+      Error::InvalidArchive(_)
+    ));
+
+    // Exporting an unknown identity fails.
+    let (_, unknown): (MemStore, Vec<IotaDID>) = setup_storage(1).await;
+    assert!(matches!(
+      StorageArchive::export(&source, &unknown).await.unwrap_err(),
+      Error::IdentityNotFound
+    ));
+  }
+
+  #[tokio::test]
+  async fn test_archive_migrate() {
+    let (source, dids): (MemStore, Vec<IotaDID>) = setup_storage(3).await;
+    let target: MemStore = MemStore::new();
+
+    StorageArchive::migrate(&source, &target, &dids[..2]).await.unwrap();
+
+    assert_identities_equal(&source, &target, &dids[..2]).await;
+  }
+
+  #[cfg(feature = "stronghold")]
+  #[tokio::test]
+  async fn test_archive_stronghold_roundtrip() {
+    use crate::stronghold::test_util::random_string;
+    use crate::stronghold::test_util::random_temporary_path;
+    use crate::stronghold::Stronghold;
+
+    let (source, dids): (MemStore, Vec<IotaDID>) = setup_storage(2).await;
+    let mut stronghold: Stronghold = Stronghold::new(&random_temporary_path(), random_string(), Some(false))
+      .await
+      .unwrap();
+
+    StorageArchive::migrate(&source, &stronghold, &dids).await.unwrap();
+
+    // Keys are only exported from a stronghold that opted in.
+    assert!(matches!(
+      StorageArchive::export_all(&stronghold).await,
+      Err(Error::KeyExportUnsupported)
+    ));
+    stronghold.set_key_export(true);
+
+    assert_identities_equal(&source, &stronghold, &dids).await;
+
+    // Keys created and deleted in the stronghold are tracked for exporting.
+    let locations: Vec<KeyLocation> = stronghold.key_list(&dids[0]).await.unwrap();
+    let location: &KeyLocation = locations
+      .iter()
+      .find(|location| location.fragment() == "key-1")
+      .unwrap();
+    stronghold.key_delete(&dids[0], location).await.unwrap();
+    stronghold
+      .key_generate(&dids[0], KeyType::Ed25519, "key-2")
+      .await
+      .unwrap();
+
+    let data: Vec<u8> = StorageArchive::export_all(&stronghold)
+      .await
+      .unwrap()
+      .encrypt_with_iterations("password", ITERATIONS)
+      .unwrap();
+    let target: MemStore = MemStore::new();
+    StorageArchive::decrypt(&data, "password")
+      .unwrap()
+      .import(&target)
+      .await
+      .unwrap();
+
+    assert_identities_equal(&stronghold, &target, &dids).await;
+  }
+}
@@ -18,7 +18,6 @@ use identity_core::crypto::KeyType;
 use identity_core::crypto::PrivateKey;
 use identity_core::crypto::PublicKey;
 use identity_core::crypto::Sign;
-#[cfg(feature = "encryption")]
 use identity_core::crypto::X25519;
 use identity_iota_core::did::IotaDID;
 use identity_iota_core::tangle::NetworkName;
@@ -248,6 +247,52 @@ impl Storage for MemStore {
     }
   }
 
+  async fn key_list(&self, did: &IotaDID) -> Result<Vec<KeyLocation>> {
+    // Obtain read access to the vaults.
+    let vaults: RwLockReadGuard<'_, _> = self.vaults.read()?;
+
+    // An unknown DID has no keys.
+    Ok(
+      vaults
+        .get(did)
+        .map(|vault| vault.keys().cloned().collect())
+        .unwrap_or_default(),
+    )
+  }
+
+  async fn key_export(&self, did: &IotaDID, location: &KeyLocation) -> Result<PrivateKey> {
+    // Obtain read access to the vaults.
+    let vaults: RwLockReadGuard<'_, _> = self.vaults.read()?;
+    // Lookup the vault for the given DID.
+    let vault: &MemVault = vaults.get(did).ok_or(Error::KeyVaultNotFound)?;
+    // Lookup the key pair within the vault.
+    let keypair: &KeyPair = vault.get(location).ok_or(Error::KeyNotFound)?;
+
+    Ok(keypair.private().clone())
+  }
+
+  async fn key_exchange(&self, did: &IotaDID, location: &KeyLocation, public_key: PublicKey) -> Result<Vec<u8>> {
+    // Obtain read access to the vaults.
+    let vaults: RwLockReadGuard<'_, _> = self.vaults.read()?;
+    // Lookup the vault for the given DID.
+    let vault: &MemVault = vaults.get(did).ok_or(Error::KeyVaultNotFound)?;
+    // Lookup the key pair within the vault.
+    let keypair: &KeyPair = vault.get(location).ok_or(Error::KeyNotFound)?;
+
+    if keypair.type_() != KeyType::X25519 {
+      return Err(Error::InvalidPrivateKey(
+        "only X25519 keys are supported for key exchanges".to_owned(),
+      ));
+    }
+
+    let public_key: [u8; X25519::PUBLIC_KEY_LENGTH] = public_key
+      .as_ref()
+      .try_into()
+      .map_err(|_| Error::InvalidPublicKey(format!("expected public key of length {}", X25519::PUBLIC_KEY_LENGTH)))?;
+
+    Ok(X25519::key_exchange(keypair.private(), &public_key)?.to_vec())
+  }
+
   #[cfg(feature = "encryption")]
   async fn data_encrypt(
     &self,
@@ -1,6 +1,7 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+mod archive;
 mod memstore;
 #[cfg(feature = "stronghold")]
 pub(crate) mod stronghold;
@@ -8,6 +9,7 @@ pub(crate) mod stronghold;
 mod test_suite;
 mod traits;
 
+pub use self::archive::*;
 pub use self::memstore::*;
 pub use self::traits::*;
 #[cfg(feature = "stronghold")]
@@ -5,6 +5,7 @@ use std::collections::BTreeSet;
 
 use async_trait::async_trait;
 use crypto::ciphers::aes_gcm::Aes256Gcm;
+use crypto::ciphers::aes_kw::Aes256Kw;
 use crypto::ciphers::traits::Aead;
 use futures::executor;
 use identity_core::convert::FromJson;
@@ -51,6 +52,8 @@ static INDEX_CLIENT_PATH: &str = "$index";
 // This happens to be the same as the client path, but for explicitness we define them separately.
 static INDEX_STORE_KEY: &str = INDEX_CLIENT_PATH;
 static BLOB_STORE_KEY: &str = "$blob";
+// The key in the store of a DID's client that contains the locations of its keys.
+static KEY_INDEX_STORE_KEY: &str = "$keys";
 // The static identifier for vaults inside clients.
 static VAULT_PATH: &[u8; 6] = b"$vault";
 
@@ -130,6 +133,8 @@ impl Storage for Stronghold {
       // Within client, move the key from the tmp location to the expected location.
       move_key(&client, &tmp_location, &location)?;
 
+      set_key_index(&client.store(), std::slice::from_ref(&location))?;
+
       Ok(())
     })?;
 
@@ -215,13 +220,17 @@ impl Storage for Stronghold {
       let location: KeyLocation = KeyLocation::new(key_type, fragment.to_owned(), public_key.as_ref());
 
       move_key(&client, &tmp_location, &location)?;
+      update_key_index(&client, |index| insert_key_location(index, &location))?;
 
       Ok(location)
     })
   }
 
   async fn key_insert(&self, did: &IotaDID, location: &KeyLocation, private_key: PrivateKey) -> Result<()> {
-    self.mutate_client(did, |client| insert_private_key(&client, private_key, location))
+    self.mutate_client(did, |client| {
+      insert_private_key(&client, private_key, location)?;
+      update_key_index(&client, |index| insert_key_location(index, location))
+    })
   }
 
   async fn key_public(&self, did: &IotaDID, location: &KeyLocation) -> Result<PublicKey> {
@@ -251,6 +260,8 @@ impl Storage for Stronghold {
         .map_err(|err| procedure_error::<procedures::RevokeData>(vec![location.clone()], err))
         .map_err(crate::Error::from)?;
 
+      update_key_index(&client, |index| index.retain(|indexed| indexed != location))?;
+
       Ok(exists)
     })
   }
@@ -273,6 +284,83 @@ impl Storage for Stronghold {
       .map_err(Into::into)
   }
 
+  /// Returns the locations of all keys of the given `did`.
+  ///
+  /// The locations are tracked since key listing was added to this storage. Identities created in a
+  /// snapshot written before cannot be listed and return [`Error::KeyExportUnsupported`].
+  async fn key_list(&self, did: &IotaDID) -> Result<Vec<KeyLocation>> {
+    let client: Client = self.client(&ClientPath::from(did))?;
+
+    match get_key_index(&client.store())? {
+      Some(index) => Ok(index),
+      // An unknown DID has no keys.
+      None if !self.did_exists(did).await? => Ok(Vec::new()),
+      None => Err(Error::KeyExportUnsupported),
+    }
+  }
+
+  /// Returns the private key at the specified `location`.
+  ///
+  /// Stronghold never reveals a secret directly, so the key is wrapped with a random, temporary key
+  /// encryption key inside the vault and unwrapped outside of it.
+  ///
+  /// Fails with [`Error::KeyExportUnsupported`] unless enabled with [`Stronghold::set_key_export`].
+  async fn key_export(&self, did: &IotaDID, location: &KeyLocation) -> Result<PrivateKey> {
+    if !self.key_export {
+      return Err(Error::KeyExportUnsupported);
+    }
+
+    let client: Client = self.client(&ClientPath::from(did))?;
+
+    if !self.key_exists(did, location).await? {
+      return Err(Error::KeyNotFound);
+    }
+
+    let mut wrapping_key: Vec<u8> = vec![0; Aes256Kw::KEY_LENGTH];
+    crypto::utils::rand::fill(&mut wrapping_key).map_err(Error::EncryptionFailure)?;
+
+    // Changes won't be written to the snapshot state since the wrapping key is temporary.
+    let wrapping_location: Location = random_stronghold_location();
+    client
+      .vault(VAULT_PATH)
+      .write_secret(wrapping_location.clone(), wrapping_key.clone())
+      .map_err(|err| StrongholdError::Vault(VaultOperation::WriteSecret, err))?;
+
+    let wrapped_key: Result<Vec<u8>> = aes_256_wrap_key(&client, wrapping_location.clone(), location.into());
+
+    client
+      .execute_procedure(procedures::RevokeData {
+        location: wrapping_location,
+        should_gc: true,
+      })
+      .map_err(|err| procedure_error::<procedures::RevokeData>(vec![], err))?;
+
+    let wrapped_key: Vec<u8> = wrapped_key?;
+    let key_len: usize = match wrapped_key.len().checked_sub(Aes256Kw::BLOCK) {
+      Some(key_len) => key_len,
+      None => {
+        wrapping_key.zeroize();
+        return Err(Error::DecryptionFailure(crypto::Error::BufferSize {
+          name: "plaintext key",
+          needs: Aes256Kw::BLOCK,
+          has: wrapped_key.len(),
+        }));
+      }
+    };
+    let mut private_key: Vec<u8> = vec![0; key_len];
+    let unwrapped: Result<()> = Aes256Kw::new(&wrapping_key)
+      .unwrap_key(&wrapped_key, &mut private_key)
+      .map_err(Error::DecryptionFailure);
+    wrapping_key.zeroize();
+
+    if let Err(error) = unwrapped {
+      private_key.zeroize();
+      return Err(error);
+    }
+
+    Ok(PrivateKey::from(private_key))
+  }
+
   #[cfg(feature = "encryption")]
   async fn data_encrypt(
     &self,
@@ -690,6 +778,46 @@ fn set_index(store: &Store, index: BTreeSet<IotaDID>) -> Result<()> {
   Ok(())
 }
 
+fn get_key_index(store: &Store) -> Result<Option<Vec<KeyLocation>>> {
+  let data: Option<Vec<u8>> = store
+    .get(KEY_INDEX_STORE_KEY.as_bytes())
+    .map_err(|err| StrongholdError::Store(StoreOperation::Get, err))?;
+
+  data
+    .map(|index_vec| Vec::<KeyLocation>::from_json_slice(&index_vec))
+    .transpose()
+    .map_err(Into::into)
+}
+
+fn set_key_index(store: &Store, index: &[KeyLocation]) -> Result<()> {
+  let index_vec: Vec<u8> = index.to_json_vec()?;
+
+  store
+    .insert(KEY_INDEX_STORE_KEY.as_bytes().to_vec(), index_vec, None)
+    .map_err(|err| StrongholdError::Store(StoreOperation::Insert, err))?;
+
+  Ok(())
+}
+
+// Updates the key index of the `client`. Clients created before keys were indexed have no index,
+// which is not started here since it would be incomplete.
+fn update_key_index(client: &Client, f: impl FnOnce(&mut Vec<KeyLocation>)) -> Result<()> {
+  let store: Store = client.store();
+
+  if let Some(mut index) = get_key_index(&store)? {
+    f(&mut index);
+    set_key_index(&store, &index)?;
+  }
+
+  Ok(())
+}
+
+fn insert_key_location(index: &mut Vec<KeyLocation>, location: &KeyLocation) {
+  if !index.contains(location) {
+    index.push(location.clone());
+  }
+}
+
 impl From<&KeyLocation> for Location {
   fn from(key_location: &KeyLocation) -> Self {
     let record_path: Vec<u8> = key_location.canonical().into_bytes();
@@ -11,6 +11,7 @@ use identity_core::crypto::PublicKey;
 use identity_iota_core::did::IotaDID;
 use identity_iota_core::tangle::NetworkName;
 
+use crate::error::Error;
 use crate::error::Result;
 #[cfg(feature = "encryption")]
 use crate::types::CekAlgorithm;
@@ -122,6 +123,34 @@ pub trait Storage: storage_sub_trait::StorageSendSyncMaybe + Debug {
   /// Returns `true` if a key exists at the specified `location`.
   async fn key_exists(&self, did: &IotaDID, location: &KeyLocation) -> Result<bool>;
 
+  /// Returns the locations of all keys of the given `did`.
+  ///
+  /// This operation is optional and only required to export identities, see
+  /// [`StorageArchive`][crate::storage::StorageArchive]. The default implementation returns
+  /// [`Error::KeyExportUnsupported`].
+  async fn key_list(&self, _did: &IotaDID) -> Result<Vec<KeyLocation>> {
+    Err(Error::KeyExportUnsupported)
+  }
+
+  /// Returns the private key at the specified `location`.
+  ///
+  /// This operation is optional and only required to export identities, see
+  /// [`StorageArchive`][crate::storage::StorageArchive]. Implementations that never reveal private keys
+  /// keep the default implementation, which returns [`Error::KeyExportUnsupported`].
+  async fn key_export(&self, _did: &IotaDID, _location: &KeyLocation) -> Result<PrivateKey> {
+    Err(Error::KeyExportUnsupported)
+  }
+
+  /// Performs an X25519 Diffie-Hellman key exchange between the private key at the specified `location`
+  /// and `public_key`, returning the shared secret.
+  ///
+  /// This operation is optional and only required for static key agreements of JWE tokens, see
+  /// [`RemoteKey`][crate::crypto::RemoteKey]. Implementations that never reveal secrets keep the default
+  /// implementation, which returns [`Error::KeyExchangeUnsupported`].
+  async fn key_exchange(&self, _did: &IotaDID, _location: &KeyLocation, _public_key: PublicKey) -> Result<Vec<u8>> {
+    Err(Error::KeyExchangeUnsupported)
+  }
+
   /// Encrypts the given `plaintext` with the specified `encryption_algorithm` and `cek_algorithm`.
   ///
   /// Returns an [`EncryptedData`] instance.
@@ -33,6 +33,7 @@ pub struct Stronghold {
   key_provider: KeyProvider,
   pub(crate) index_lock: RwLock<()>,
   pub(crate) dropsave: bool,
+  pub(crate) key_export: bool,
 }
 
 impl Stronghold {
@@ -76,6 +77,7 @@ impl Stronghold {
       key_provider,
       index_lock: RwLock::new(()),
       dropsave: dropsave.unwrap_or(true),
+      key_export: false,
     })
   }
 
@@ -89,6 +91,21 @@ impl Stronghold {
     self.dropsave = dropsave;
   }
 
+  /// Returns whether exporting private keys is enabled.
+  pub fn key_export_enabled(&self) -> bool {
+    self.key_export
+  }
+
+  /// Sets whether private keys can be exported through [`Storage::key_export`][crate::storage::Storage::key_export],
+  /// e.g. to create a [`StorageArchive`][crate::storage::StorageArchive]. Default: false.
+  ///
+  /// Enabling this gives up the main guarantee of Stronghold: every holder of the storage, including
+  /// code only given an `Arc<dyn Storage>`, can read the private keys in plaintext. Only enable it for
+  /// the duration of an export or migration.
+  pub fn set_key_export(&mut self, key_export: bool) {
+    self.key_export = key_export;
+  }
+
   /// Load the client identified by the given `client_path` or create it, if it doesn't exist.
   pub(crate) fn client(&self, client_path: &ClientPath) -> StrongholdResult<Client> {
     match self.stronghold.load_client(client_path.as_ref()) {
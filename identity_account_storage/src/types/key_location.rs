@@ -6,6 +6,7 @@ use core::fmt::Display;
 use core::fmt::Formatter;
 use core::fmt::Result;
 use identity_core::crypto::KeyType;
+use identity_did::verification::jwk_key_type;
 use identity_did::verification::MethodData;
 use identity_did::verification::MethodType;
 use identity_iota_core::document::IotaVerificationMethod;
@@ -58,6 +59,10 @@ impl KeyLocation {
     let key_type: KeyType = match method.type_() {
       MethodType::Ed25519VerificationKey2018 => KeyType::Ed25519,
       MethodType::X25519KeyAgreementKey2019 => KeyType::X25519,
+      MethodType::JsonWebKey2020 => method_data
+        .public_key_jwk()
+        .and_then(jwk_key_type)
+        .ok_or(crate::Error::DIDError(identity_did::Error::InvalidKeyDataJwk))?,
     };
 
     let public_key: Vec<u8> = method_data.try_decode()?;
@@ -65,6 +70,11 @@ impl KeyLocation {
     Ok(KeyLocation::new(key_type, fragment.to_owned(), public_key.as_ref()))
   }
 
+  /// Returns the fragment of the method the key belongs to.
+  pub fn fragment(&self) -> &str {
+    &self.fragment
+  }
+
   /// Returns the canonical string representation of the location.
   ///
   /// This should be used as the representation for storage keys.
@@ -0,0 +1,289 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::borrow::Borrow;
+use core::fmt::Debug;
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+
+use identity_diff::Diff;
+use identity_diff::DiffVec;
+use identity_diff::Error;
+use identity_diff::Result;
+
+use crate::common::KeyComparable;
+
+/// A [`KeyComparable`] type whose key has an owned, serializable form.
+///
+/// Implemented for every type whose [`KeyComparable::Key`] implements [`ToOwned`] with a serializable
+/// owned form, which allows sets of the type to be diffed by key, see [`DiffSet`].
+pub trait DiffKey: KeyComparable {
+  /// The owned form of [`KeyComparable::Key`].
+  type OwnedKey: Borrow<Self::Key> + Clone + Debug + PartialEq + Serialize + DeserializeOwned;
+
+  /// Returns an owned copy of the key.
+  fn owned_key(&self) -> Self::OwnedKey;
+}
+
+impl<T> DiffKey for T
+where
+  T: KeyComparable + ?Sized,
+  T::Key: ToOwned,
+  <T::Key as ToOwned>::Owned: Clone + Debug + PartialEq + Serialize + DeserializeOwned,
+{
+  type OwnedKey = <T::Key as ToOwned>::Owned;
+
+  fn owned_key(&self) -> Self::OwnedKey {
+    self.key().to_owned()
+  }
+}
+
+/// The Diff Type for [`OrderedSet`](crate::common::OrderedSet) and [`OneOrSet`](crate::common::OneOrSet).
+///
+/// Elements are identified by their [`KeyComparable::key`] instead of their index, so removing or
+/// updating an element does not affect how the remaining elements are represented.
+///
+/// Changes are merged in order: elements are removed by key, updated by key, and finally inserted at
+/// their index in the resulting set. Elements whose position relative to the others changed are
+/// removed and inserted again.
+///
+/// Diffs of sets were previously represented by a [`DiffVec`], identifying elements by their index.
+/// Such diffs, e.g. of published diff messages, can still be deserialized and merged.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(transparent, bound(serialize = "T: Serialize", deserialize = "T: DeserializeOwned"))]
+pub struct DiffSet<T>(Changes<T>)
+where
+  T: Diff + DiffKey;
+
+/// The representations of a [`DiffSet`].
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(untagged, bound(serialize = "T: Serialize", deserialize = "T: DeserializeOwned"))]
+enum Changes<T>
+where
+  T: Diff + DiffKey,
+{
+  Keyed {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    remove: Vec<T::OwnedKey>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    update: Vec<(T::OwnedKey, <T as Diff>::Type)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    insert: Vec<(usize, <T as Diff>::Type)>,
+  },
+  Indexed(DiffVec<T>),
+}
+
+impl<T> DiffSet<T>
+where
+  T: Diff + DiffKey + Serialize + DeserializeOwned,
+{
+  /// Returns `true` if merging the diff does not change a set.
+  pub fn is_empty(&self) -> bool {
+    match &self.0 {
+      Changes::Keyed { remove, update, insert } => remove.is_empty() && update.is_empty() && insert.is_empty(),
+      Changes::Indexed(diff) => diff.0.is_empty(),
+    }
+  }
+
+  /// Computes the changes that transform the elements of `old` into the elements of `new`.
+  pub(crate) fn between(old: &[T], new: &[T]) -> Result<Self> {
+    let positions: Vec<Option<usize>> = new
+      .iter()
+      .map(|item| old.iter().position(|other| other.key() == item.key()))
+      .collect();
+    let retained: Vec<Option<usize>> = retain_ordered(&positions);
+
+    let remove: Vec<T::OwnedKey> = old
+      .iter()
+      .enumerate()
+      .filter(|(index, _)| !retained.contains(&Some(*index)))
+      .map(|(_, item)| item.owned_key())
+      .collect();
+
+    let mut update: Vec<(T::OwnedKey, <T as Diff>::Type)> = Vec::new();
+    let mut insert: Vec<(usize, <T as Diff>::Type)> = Vec::new();
+
+    for (index, (item, position)) in new.iter().zip(retained).enumerate() {
+      match position.map(|position| &old[position]) {
+        Some(previous) if previous == item => {}
+        Some(previous) => update.push((item.owned_key(), previous.diff(item)?)),
+        None => insert.push((index, item.clone().into_diff()?)),
+      }
+    }
+
+    Ok(Self(Changes::Keyed { remove, update, insert }))
+  }
+
+  /// Creates a diff that inserts all `items` into an empty set.
+  pub(crate) fn from_items(items: Vec<T>) -> Result<Self> {
+    let insert: Vec<(usize, <T as Diff>::Type)> = items
+      .into_iter()
+      .enumerate()
+      .map(|(index, item)| item.into_diff().map(|diff| (index, diff)))
+      .collect::<Result<_>>()?;
+
+    Ok(Self(Changes::Keyed {
+      remove: Vec::new(),
+      update: Vec::new(),
+      insert,
+    }))
+  }
+
+  /// Merges the changes into `items`.
+  pub(crate) fn merge_into(self, mut items: Vec<T>) -> Result<Vec<T>> {
+    let (remove, update, insert) = match self.0 {
+      Changes::Keyed { remove, update, insert } => (remove, update, insert),
+      Changes::Indexed(diff) => return items.merge(diff),
+    };
+
+    for key in remove.iter() {
+      let index: usize = position(&items, key)?;
+      items.remove(index);
+    }
+
+    for (key, diff) in update.into_iter() {
+      let index: usize = position(&items, &key)?;
+      items[index] = items[index].merge(diff)?;
+    }
+
+    for (index, diff) in insert.into_iter() {
+      if index > items.len() {
+        return Err(Error::merge(format!("insert index `{}` out of bounds", index)));
+      }
+
+      items.insert(index, T::from_diff(diff)?);
+    }
+
+    Ok(items)
+  }
+}
+
+fn position<T>(items: &[T], key: &T::OwnedKey) -> Result<usize>
+where
+  T: DiffKey,
+{
+  items
+    .iter()
+    .position(|item| item.key() == Borrow::<T::Key>::borrow(key))
+    .ok_or_else(|| Error::merge(format!("no element with key `{:?}`", key)))
+}
+
+/// Keeps the entries of `positions` that form a longest increasing subsequence, i.e. the largest
+/// number of elements whose relative order is unchanged, and sets all other entries to `None`.
+fn retain_ordered(positions: &[Option<usize>]) -> Vec<Option<usize>> {
+  // The length of the longest subsequence ending at each entry and the previous entry of that subsequence.
+  let mut lengths: Vec<usize> = vec![0; positions.len()];
+  let mut previous: Vec<Option<usize>> = vec![None; positions.len()];
+
+  for (index, position) in positions.iter().enumerate() {
+    let position: usize = match position {
+      Some(position) => *position,
+      None => continue,
+    };
+
+    lengths[index] = 1;
+
+    for (other, other_position) in positions[..index].iter().enumerate() {
+      if matches!(other_position, Some(other_position) if *other_position < position)
+        && lengths[other] + 1 > lengths[index]
+      {
+        lengths[index] = lengths[other] + 1;
+        previous[index] = Some(other);
+      }
+    }
+  }
+
+  let mut retained: Vec<Option<usize>> = vec![None; positions.len()];
+  let mut current: Option<usize> = (0..positions.len())
+    .max_by_key(|index| lengths[*index])
+    .filter(|index| lengths[*index] > 0);
+
+  while let Some(index) = current {
+    retained[index] = positions[index];
+    current = previous[index];
+  }
+
+  retained
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::convert::FromJson;
+  use crate::convert::ToJson;
+
+  use super::*;
+
+  fn items(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+  }
+
+  fn roundtrip(old: &[&str], new: &[&str]) -> DiffSet<String> {
+    let diff: DiffSet<String> = DiffSet::between(&items(old), &items(new)).unwrap();
+    assert_eq!(diff.clone().merge_into(items(old)).unwrap(), items(new));
+
+    let json: String = diff.to_json().unwrap();
+    assert_eq!(DiffSet::from_json(&json).unwrap(), diff);
+
+    diff
+  }
+
+  #[test]
+  fn test_remove_by_key() {
+    let diff: DiffSet<String> = roundtrip(&["a", "b", "c", "d"], &["b", "c", "d"]);
+
+    assert_eq!(diff.to_json().unwrap(), r#"{"remove":["a"]}"#);
+  }
+
+  #[test]
+  fn test_insert() {
+    let diff: DiffSet<String> = roundtrip(&["a", "c"], &["a", "b", "c", "d"]);
+
+    assert_eq!(diff.to_json().unwrap(), r#"{"insert":[[1,"b"],[3,"d"]]}"#);
+  }
+
+  #[test]
+  fn test_reorder() {
+    let diff: DiffSet<String> = roundtrip(&["a", "b", "c", "d"], &["b", "c", "a", "d"]);
+
+    // Only the moved element is removed and inserted again.
+    assert_eq!(diff.to_json().unwrap(), r#"{"remove":["a"],"insert":[[2,"a"]]}"#);
+
+    roundtrip(&["a", "b", "c"], &["c", "b", "a"]);
+    roundtrip(&["a", "b"], &["c", "a", "d"]);
+    roundtrip(&[], &["a"]);
+    roundtrip(&["a"], &[]);
+  }
+
+  #[test]
+  fn test_equal() {
+    assert!(roundtrip(&["a", "b"], &["a", "b"]).is_empty());
+  }
+
+  #[test]
+  fn test_merge_invalid() {
+    let diff: DiffSet<String> = DiffSet::between(&items(&["a", "b"]), &items(&["b"])).unwrap();
+    assert!(diff.merge_into(items(&["b"])).is_err());
+
+    let diff: DiffSet<String> = DiffSet::between(&items(&["a"]), &items(&["a", "b", "c"])).unwrap();
+    assert!(diff.merge_into(Vec::new()).is_err());
+  }
+
+  #[test]
+  fn test_indexed() {
+    // Diffs of sets were previously represented as a `DiffVec`.
+    let json: &str = r#"[{"index":0,"item":"b"},{"index":1,"item":"c"},{"count":1}]"#;
+    let diff: DiffSet<String> = DiffSet::from_json(json).unwrap();
+
+    assert_eq!(diff.to_json().unwrap(), json);
+    assert!(!diff.is_empty());
+    assert_eq!(diff.merge_into(items(&["a", "b", "c"])).unwrap(), items(&["b", "c"]));
+  }
+
+  #[test]
+  fn test_from_items() {
+    let diff: DiffSet<String> = DiffSet::from_items(items(&["a", "b"])).unwrap();
+    assert_eq!(diff.merge_into(Vec::new()).unwrap(), items(&["a", "b"]));
+  }
+}
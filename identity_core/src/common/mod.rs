@@ -4,6 +4,8 @@
 //! Definitions of common types (`Url`, `Timestamp`, JSON types, etc).
 
 pub use self::context::Context;
+pub use self::diff_set::DiffKey;
+pub use self::diff_set::DiffSet;
 pub use self::fragment::Fragment;
 pub use self::key_comparable::KeyComparable;
 pub use self::object::Object;
@@ -16,6 +18,7 @@ pub use self::timestamp::Timestamp;
 pub use self::url::Url;
 
 mod context;
+mod diff_set;
 mod fragment;
 mod key_comparable;
 mod object;
@@ -15,8 +15,9 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use identity_diff::Diff;
-use identity_diff::DiffVec;
 
+use crate::common::DiffKey;
+use crate::common::DiffSet;
 use crate::common::KeyComparable;
 use crate::error::Error;
 use crate::error::Result;
@@ -284,28 +285,28 @@ where
 
 impl<T> Diff for OrderedSet<T>
 where
-  T: Diff + KeyComparable + Serialize + for<'de> Deserialize<'de>,
+  T: Diff + DiffKey + Serialize + for<'de> Deserialize<'de>,
 {
-  type Type = DiffVec<T>;
+  type Type = DiffSet<T>;
 
   fn diff(&self, other: &Self) -> identity_diff::Result<Self::Type> {
-    self.clone().into_vec().diff(&other.clone().into_vec())
+    DiffSet::between(self.as_slice(), other.as_slice())
   }
 
   fn merge(&self, diff: Self::Type) -> identity_diff::Result<Self> {
-    self
-      .clone()
-      .into_vec()
-      .merge(diff)
+    diff
+      .merge_into(self.clone().into_vec())
       .and_then(|this| Self::try_from(this).map_err(identity_diff::Error::merge))
   }
 
   fn from_diff(diff: Self::Type) -> identity_diff::Result<Self> {
-    Vec::from_diff(diff).and_then(|this| Self::try_from(this).map_err(identity_diff::Error::convert))
+    diff
+      .merge_into(Vec::new())
+      .and_then(|this| Self::try_from(this).map_err(identity_diff::Error::convert))
   }
 
   fn into_diff(self) -> identity_diff::Result<Self::Type> {
-    self.into_vec().into_diff()
+    DiffSet::from_items(self.into_vec())
   }
 }
 
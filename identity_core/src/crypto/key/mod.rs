@@ -13,5 +13,6 @@ pub use self::x25519::X25519;
 mod ed25519;
 mod key;
 mod pair;
+mod pkcs8;
 mod type_;
 mod x25519;
@@ -9,14 +9,20 @@ pub use self::key::KeyType;
 pub use self::key::PrivateKey;
 pub use self::key::PublicKey;
 pub use self::key::X25519;
+pub use self::proof::Ed25519Signature2020;
+pub use self::proof::EdDsaJcs2022;
+pub use self::proof::EdDsaRdfc2022;
 pub use self::proof::JcsEd25519;
 pub use self::proof::Proof;
 pub use self::proof::ProofOptions;
 pub use self::proof::ProofPurpose;
 pub use self::proof::ProofValue;
+pub use self::proof::ProofView;
+pub use self::signature::GetProofs;
 pub use self::signature::GetSignature;
 pub use self::signature::GetSignatureMut;
 pub use self::signature::Named;
+pub use self::signature::PushProof;
 pub use self::signature::SetSignature;
 pub use self::signature::Sign;
 pub use self::signature::Signer;
@@ -0,0 +1,112 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::hashes::sha::SHA256;
+use crypto::hashes::sha::SHA256_LEN;
+use serde::Serialize;
+
+use crate::common::Object;
+use crate::common::Value;
+use crate::convert::FromJson;
+use crate::convert::ToJson;
+use crate::error::Error;
+use crate::error::Result;
+use crate::jsonld::JsonLdProcessor;
+
+/// The property names of the value of a proof.
+const PROOF_VALUE_KEYS: [&str; 3] = ["proofValue", "jws", "signatureValue"];
+
+/// Transforms the secured `data` into its proof configuration and unsecured document.
+///
+/// The proof configuration is the single proof of `data` without a proof value, with the
+/// `@context` of the document. Other proofs of a proof set are removed from the document, except
+/// for the proofs referenced by the `previousProof` of the proof configuration.
+///
+/// [More Info](https://www.w3.org/TR/vc-data-integrity/#add-proof-set-chain)
+pub(crate) fn split_proof<X>(data: &X) -> Result<(Object, Object)>
+where
+  X: Serialize + ?Sized,
+{
+  let mut document: Object = Object::from_json_value(data.to_json_value()?)?;
+
+  let proofs: Vec<Object> = match document.remove("proof") {
+    Some(Value::Array(proofs)) => proofs
+      .into_iter()
+      .map(Object::from_json_value)
+      .collect::<Result<Vec<Object>>>()?,
+    Some(proof) => vec![Object::from_json_value(proof)?],
+    None => return Err(Error::MissingSignature),
+  };
+
+  let (mut configuration, others): (Vec<Object>, Vec<Object>) = proofs
+    .into_iter()
+    .partition(|proof| !PROOF_VALUE_KEYS.iter().any(|key| proof.contains_key(*key)));
+
+  if configuration.len() > 1 {
+    return Err(Error::InvalidProofValue("expected a single proof without value"));
+  }
+  let mut configuration: Object = configuration.pop().ok_or(Error::MissingSignature)?;
+
+  if let Some(previous_proof) = configuration.get("previousProof").and_then(Value::as_str) {
+    let mut previous: Vec<Value> = others
+      .into_iter()
+      .filter(|proof| proof.get("id").and_then(Value::as_str) == Some(previous_proof))
+      .map(|proof| Value::Object(proof.into_iter().collect()))
+      .collect();
+
+    let previous: Value = match previous.len() {
+      0 => return Err(Error::InvalidProofValue("missing previous proof")),
+      1 => previous.remove(0),
+      _ => Value::Array(previous),
+    };
+    document.insert("proof".to_owned(), previous);
+  }
+
+  if let Some(context) = document.get("@context") {
+    configuration.insert("@context".to_owned(), context.clone());
+  }
+
+  Ok((configuration, document))
+}
+
+/// Returns the concatenation of the SHA-256 hashes of the canonical N-Quads of the proof
+/// `configuration` and the `document`.
+///
+/// The `@context` of the document must include one of the `suite_contexts` defining the proof.
+pub(crate) fn hash_canonical(configuration: Object, document: Object, suite_contexts: &[&str]) -> Result<Vec<u8>> {
+  let includes_suite_context: bool = match document.get("@context") {
+    Some(Value::Array(contexts)) => contexts.iter().any(|context| {
+      context
+        .as_str()
+        .map_or(false, |context| suite_contexts.contains(&context))
+    }),
+    Some(Value::String(context)) => suite_contexts.contains(&context.as_str()),
+    _ => false,
+  };
+
+  if !includes_suite_context {
+    return Err(Error::InvalidJsonLd("missing suite context"));
+  }
+
+  let processor: JsonLdProcessor = JsonLdProcessor::default();
+
+  let mut message: Vec<u8> = Vec::with_capacity(2 * SHA256_LEN);
+  message.extend_from_slice(&sha256(
+    processor
+      .canonicalize(&Value::Object(configuration.into_iter().collect()))?
+      .as_bytes(),
+  ));
+  message.extend_from_slice(&sha256(
+    processor
+      .canonicalize(&Value::Object(document.into_iter().collect()))?
+      .as_bytes(),
+  ));
+
+  Ok(message)
+}
+
+pub(crate) fn sha256(data: &[u8]) -> [u8; SHA256_LEN] {
+  let mut digest: [u8; SHA256_LEN] = [0; SHA256_LEN];
+  SHA256(data, &mut digest);
+  digest
+}
@@ -0,0 +1,205 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::marker::PhantomData;
+
+use serde::Serialize;
+
+use crate::common::Object;
+use crate::crypto::proof::data_integrity::hash_canonical;
+use crate::crypto::proof::data_integrity::split_proof;
+use crate::crypto::Ed25519;
+use crate::crypto::Named;
+use crate::crypto::ProofValue;
+use crate::crypto::Sign;
+use crate::crypto::Signer;
+use crate::crypto::Verifier;
+use crate::crypto::Verify;
+use crate::error::Error;
+use crate::error::Result;
+use crate::utils::BaseEncoding;
+
+/// The JSON-LD context defining the `Ed25519Signature2020` proof type.
+const SUITE_CONTEXTS: [&str; 1] = ["https://w3id.org/security/suites/ed25519-2020/v1"];
+
+/// An implementation of the [Ed25519 Signature 2020][SPEC1] suite for Linked Data Proofs.
+///
+/// Users should use the [`Sign`]/[`Verify`] traits to access this implementation.
+///
+/// The proof configuration and the document are canonicalized with [URDNA2015][SPEC2] and
+/// hashed separately. The `@context` of the document must include the suite context
+/// `https://w3id.org/security/suites/ed25519-2020/v1`.
+///
+/// [SPEC1]: https://w3c.github.io/vc-di-eddsa/#ed25519signature2020-0
+/// [SPEC2]: https://www.w3.org/TR/rdf-canon/
+pub struct Ed25519Signature2020<T = Ed25519>(PhantomData<T>);
+
+impl<T> Named for Ed25519Signature2020<T> {
+  const NAME: &'static str = "Ed25519Signature2020";
+}
+
+impl<T> Signer<T::Private> for Ed25519Signature2020<T>
+where
+  T: Sign,
+  T::Output: AsRef<[u8]>,
+{
+  fn sign<X>(data: &X, private: &T::Private) -> Result<ProofValue>
+  where
+    X: Serialize,
+  {
+    let message: Vec<u8> = hash_data(data)?;
+    let signature: T::Output = T::sign(&message, private)?;
+
+    Ok(ProofValue::Proof(BaseEncoding::encode_multibase(
+      signature.as_ref(),
+      None,
+    )))
+  }
+}
+
+impl<T> Verifier<T::Public> for Ed25519Signature2020<T>
+where
+  T: Verify,
+{
+  fn verify<X>(data: &X, signature: &ProofValue, public: &T::Public) -> Result<()>
+  where
+    X: Serialize + ?Sized,
+  {
+    let signature: &str = signature
+      .as_proof()
+      .ok_or(Error::InvalidProofValue("Ed25519Signature2020"))?;
+
+    let signature: Vec<u8> = BaseEncoding::decode_multibase(signature)?;
+    let message: Vec<u8> = hash_data(data)?;
+
+    T::verify(&message, &signature, public)?;
+
+    Ok(())
+  }
+}
+
+/// Transforms the secured `data` into its proof configuration and unsecured document and returns
+/// the concatenation of the hashes of their canonical forms.
+///
+/// [More Info](https://w3c.github.io/vc-di-eddsa/#hashing-ed25519signature2020)
+fn hash_data<X>(data: &X) -> Result<Vec<u8>>
+where
+  X: Serialize + ?Sized,
+{
+  let (configuration, document): (Object, Object) = split_proof(data)?;
+
+  hash_canonical(configuration, document, &SUITE_CONTEXTS)
+}
+
+#[cfg(test)]
+mod tests {
+  use serde::Serialize;
+
+  use crate::common::Object;
+  use crate::common::Value;
+  use crate::convert::FromJson;
+  use crate::crypto::Ed25519;
+  use crate::crypto::Ed25519Signature2020;
+  use crate::crypto::GetSignature;
+  use crate::crypto::GetSignatureMut;
+  use crate::crypto::KeyPair;
+  use crate::crypto::KeyType;
+  use crate::crypto::PrivateKey;
+  use crate::crypto::Proof;
+  use crate::crypto::ProofOptions;
+  use crate::crypto::PublicKey;
+  use crate::crypto::SetSignature;
+  use crate::crypto::Signer as _;
+  use crate::crypto::Verifier as _;
+  use crate::error::Error;
+  use crate::error::Result;
+  use crate::json;
+
+  const METHOD: &str = "did:example:issuer#key-1";
+
+  type Signer = Ed25519Signature2020<Ed25519<PrivateKey>>;
+
+  type Verifier = Ed25519Signature2020<Ed25519<PublicKey>>;
+
+  #[derive(Serialize)]
+  struct Data {
+    #[serde(flatten)]
+    properties: Object,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proof: Option<Proof>,
+  }
+
+  impl Data {
+    fn new(context: Value) -> Self {
+      let properties: Value = json!({
+        "@context": context,
+        "id": "http://example.edu/credentials/3732",
+        "type": ["VerifiableCredential"],
+        "issuer": "did:example:issuer",
+        "issuanceDate": "2020-03-10T04:24:12Z",
+        "credentialSubject": {
+          "id": "did:example:subject",
+        },
+      });
+
+      Self {
+        properties: Object::from_json_value(properties).unwrap(),
+        proof: None,
+      }
+    }
+  }
+
+  impl GetSignature for Data {
+    fn signature(&self) -> Option<&Proof> {
+      self.proof.as_ref()
+    }
+  }
+
+  impl GetSignatureMut for Data {
+    fn signature_mut(&mut self) -> Option<&mut Proof> {
+      self.proof.as_mut()
+    }
+  }
+
+  impl SetSignature for Data {
+    fn set_signature(&mut self, signature: Proof) {
+      self.proof = Some(signature);
+    }
+  }
+
+  #[test]
+  fn test_sign_verify() {
+    let key1: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+    let key2: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+
+    let mut data: Data = Data::new(json!([
+      "https://www.w3.org/2018/credentials/v1",
+      "https://w3id.org/security/suites/ed25519-2020/v1",
+    ]));
+    Signer::create_signature(&mut data, METHOD, key1.private(), ProofOptions::default()).unwrap();
+
+    let proof: &Proof = data.signature().unwrap();
+    assert_eq!(proof.type_(), "Ed25519Signature2020");
+    assert_eq!(proof.cryptosuite(), None);
+    assert!(proof.value().as_str().starts_with('z'));
+
+    assert!(Verifier::verify_signature(&data, key1.public()).is_ok());
+    assert!(Verifier::verify_signature(&data, key2.public()).is_err());
+
+    // Modified data is invalid.
+    data
+      .properties
+      .insert("issuer".to_owned(), Value::String("did:example:other".to_owned()));
+    assert!(Verifier::verify_signature(&data, key1.public()).is_err());
+  }
+
+  #[test]
+  fn test_missing_suite_context() {
+    let key: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+
+    let mut data: Data = Data::new(json!(["https://www.w3.org/2018/credentials/v1"]));
+    let result: Result<()> = Signer::create_signature(&mut data, METHOD, key.private(), ProofOptions::default());
+
+    assert!(matches!(result.unwrap_err(), Error::InvalidJsonLd(_)));
+  }
+}
@@ -0,0 +1,312 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::marker::PhantomData;
+
+use crypto::hashes::sha::SHA256_LEN;
+use serde::Serialize;
+
+use crate::common::Object;
+use crate::convert::ToJson;
+use crate::crypto::proof::data_integrity::sha256;
+use crate::crypto::proof::data_integrity::split_proof;
+use crate::crypto::Ed25519;
+use crate::crypto::Named;
+use crate::crypto::ProofValue;
+use crate::crypto::Sign;
+use crate::crypto::Signer;
+use crate::crypto::Verifier;
+use crate::crypto::Verify;
+use crate::error::Error;
+use crate::error::Result;
+use crate::utils::BaseEncoding;
+
+/// An implementation of the [`eddsa-jcs-2022`][SPEC1] cryptosuite for [Data Integrity][SPEC2]
+/// proofs of the type `DataIntegrityProof`.
+///
+/// Users should use the [`Sign`]/[`Verify`] traits to access this implementation.
+///
+/// Unlike [`JcsEd25519`](crate::crypto::JcsEd25519), the proof configuration and the document
+/// are hashed separately and other proofs of a [proof set][SPEC3] are excluded from the signed
+/// data. Only the proofs referenced by the `previousProof` of the proof are included, forming
+/// a proof chain.
+///
+/// The proof being created or verified is identified as the single proof of the document
+/// without a proof value.
+///
+/// [SPEC1]: https://www.w3.org/TR/vc-di-eddsa/#eddsa-jcs-2022
+/// [SPEC2]: https://www.w3.org/TR/vc-data-integrity/
+/// [SPEC3]: https://www.w3.org/TR/vc-data-integrity/#proof-sets
+pub struct EdDsaJcs2022<T = Ed25519>(PhantomData<T>);
+
+impl<T> Named for EdDsaJcs2022<T> {
+  const NAME: &'static str = "DataIntegrityProof";
+  const CRYPTOSUITE: Option<&'static str> = Some("eddsa-jcs-2022");
+}
+
+impl<T> Signer<T::Private> for EdDsaJcs2022<T>
+where
+  T: Sign,
+  T::Output: AsRef<[u8]>,
+{
+  fn sign<X>(data: &X, private: &T::Private) -> Result<ProofValue>
+  where
+    X: Serialize,
+  {
+    let message: Vec<u8> = hash_data(data)?;
+    let signature: T::Output = T::sign(&message, private)?;
+
+    Ok(ProofValue::Proof(BaseEncoding::encode_multibase(
+      signature.as_ref(),
+      None,
+    )))
+  }
+}
+
+impl<T> Verifier<T::Public> for EdDsaJcs2022<T>
+where
+  T: Verify,
+{
+  fn verify<X>(data: &X, signature: &ProofValue, public: &T::Public) -> Result<()>
+  where
+    X: Serialize + ?Sized,
+  {
+    let signature: &str = signature.as_proof().ok_or(Error::InvalidProofValue("eddsa-jcs-2022"))?;
+
+    let signature: Vec<u8> = BaseEncoding::decode_multibase(signature)?;
+    let message: Vec<u8> = hash_data(data)?;
+
+    T::verify(&message, &signature, public)?;
+
+    Ok(())
+  }
+}
+
+/// Transforms the secured `data` into its proof configuration and unsecured document and returns
+/// the concatenation of their SHA-256 hashes.
+///
+/// [More Info](https://www.w3.org/TR/vc-di-eddsa/#hashing-eddsa-jcs-2022)
+fn hash_data<X>(data: &X) -> Result<Vec<u8>>
+where
+  X: Serialize + ?Sized,
+{
+  let (configuration, document): (Object, Object) = split_proof(data)?;
+
+  let mut message: Vec<u8> = Vec::with_capacity(2 * SHA256_LEN);
+  message.extend_from_slice(&sha256(&configuration.to_jcs()?));
+  message.extend_from_slice(&sha256(&document.to_jcs()?));
+
+  Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+  use serde::Serialize;
+
+  use crate::common::Object;
+  use crate::common::Timestamp;
+  use crate::common::Value;
+  use crate::convert::FromJson;
+  use crate::crypto::Ed25519;
+  use crate::crypto::EdDsaJcs2022;
+  use crate::crypto::GetProofs;
+  use crate::crypto::GetSignature;
+  use crate::crypto::GetSignatureMut;
+  use crate::crypto::KeyPair;
+  use crate::crypto::KeyType;
+  use crate::crypto::PrivateKey;
+  use crate::crypto::Proof;
+  use crate::crypto::ProofOptions;
+  use crate::crypto::ProofPurpose;
+  use crate::crypto::ProofView;
+  use crate::crypto::PublicKey;
+  use crate::crypto::PushProof;
+  use crate::crypto::Signer as _;
+  use crate::crypto::Verifier as _;
+  use crate::json;
+  use crate::utils::BaseEncoding;
+
+  type Signer = EdDsaJcs2022<Ed25519<PrivateKey>>;
+
+  type Verifier = EdDsaJcs2022<Ed25519<PublicKey>>;
+
+  #[derive(Serialize)]
+  struct Data {
+    #[serde(flatten)]
+    properties: Object,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    proof: Vec<Proof>,
+  }
+
+  impl Data {
+    fn new() -> Self {
+      let properties: Value = json!({
+        "@context": ["https://www.w3.org/ns/credentials/v2"],
+        "id": "urn:uuid:58172aac-d8ba-11ed-83dd-0b3aef56cc33",
+        "type": ["VerifiableCredential"],
+      });
+
+      Self {
+        properties: Object::from_json_value(properties).unwrap(),
+        proof: Vec::new(),
+      }
+    }
+  }
+
+  impl GetSignature for Data {
+    fn signature(&self) -> Option<&Proof> {
+      self.proof.last()
+    }
+  }
+
+  impl GetSignatureMut for Data {
+    fn signature_mut(&mut self) -> Option<&mut Proof> {
+      self.proof.last_mut()
+    }
+  }
+
+  impl GetProofs for Data {
+    fn proofs(&self) -> &[Proof] {
+      &self.proof
+    }
+  }
+
+  impl PushProof for Data {
+    fn push_proof(&mut self, proof: Proof) {
+      self.proof.push(proof);
+    }
+  }
+
+  #[test]
+  fn test_sign_verify() {
+    let key1: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+    let key2: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+
+    let mut data: Data = Data::new();
+    Signer::append_signature(&mut data, "#key-1", key1.private(), ProofOptions::default()).unwrap();
+
+    let proof: &Proof = data.signature().unwrap();
+    assert_eq!(proof.type_(), "DataIntegrityProof");
+    assert_eq!(proof.cryptosuite(), Some("eddsa-jcs-2022"));
+    assert!(proof.value().as_str().starts_with('z'));
+
+    assert!(Verifier::verify_signature(&data, key1.public()).is_ok());
+    assert!(Verifier::verify_signature(&data, key2.public()).is_err());
+
+    // Modified data is invalid.
+    data
+      .properties
+      .insert("id".to_owned(), Value::String("urn:uuid:other".to_owned()));
+    assert!(Verifier::verify_signature(&data, key1.public()).is_err());
+  }
+
+  #[test]
+  fn test_proof_set() {
+    let key1: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+    let key2: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+
+    let mut data: Data = Data::new();
+    Signer::append_signature(&mut data, "#key-1", key1.private(), ProofOptions::default()).unwrap();
+    Signer::append_signature(&mut data, "#key-2", key2.private(), ProofOptions::default()).unwrap();
+    assert_eq!(data.proofs().len(), 2);
+
+    // Each proof of the set is verified independently of the others.
+    let proofs: &[Proof] = data.proofs();
+    assert!(Verifier::verify_proof(&data, &proofs[0], key1.public()).is_ok());
+    assert!(Verifier::verify_proof(&data, &proofs[1], key2.public()).is_ok());
+    assert!(Verifier::verify_proof(&data, &proofs[0], key2.public()).is_err());
+    assert!(Verifier::verify_signature(&ProofView::new(&data, &proofs[0]), key1.public()).is_ok());
+
+    // The value of the verified proof is restored.
+    assert!(!proofs[0].value().is_none());
+    assert!(Verifier::verify_proof(&data, &proofs[1], key2.public()).is_ok());
+  }
+
+  #[test]
+  fn test_proof_chain() {
+    let key1: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+    let key2: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+
+    let mut data: Data = Data::new();
+    let options: ProofOptions = ProofOptions::new().id("urn:uuid:1".to_owned());
+    Signer::append_signature(&mut data, "#key-1", key1.private(), options).unwrap();
+    let options: ProofOptions = ProofOptions::new().previous_proof("urn:uuid:1".to_owned());
+    Signer::append_signature(&mut data, "#key-2", key2.private(), options).unwrap();
+
+    assert!(Verifier::verify_proof(&data, &data.proofs()[0], key1.public()).is_ok());
+    assert!(Verifier::verify_proof(&data, &data.proofs()[1], key2.public()).is_ok());
+
+    // The chained proof secures the previous proof.
+    data.proof[0].created = Some(Timestamp::now_utc());
+    assert!(Verifier::verify_proof(&data, &data.proofs()[1], key2.public()).is_err());
+
+    // A missing previous proof is an error.
+    let mut data: Data = Data::new();
+    let options: ProofOptions = ProofOptions::new().previous_proof("urn:uuid:1".to_owned());
+    assert!(Signer::append_signature(&mut data, "#key-2", key2.private(), options).is_err());
+  }
+
+  // The eddsa-jcs-2022 test vector of https://www.w3.org/TR/vc-di-eddsa/.
+  #[test]
+  fn test_spec_vector() {
+    const PUBLIC_KEY: &str = "z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2";
+    const SECRET_KEY: &str = "z3u2en7t5LR2WtQH5PfFqMqwVHBeXouLzo6haApm8XHqvjxq";
+    const METHOD: &str =
+      "did:key:z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2#z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2";
+
+    // The multibase keys are prefixed with their two byte multicodec identifiers.
+    let secret_key: Vec<u8> = BaseEncoding::decode_multibase(SECRET_KEY).unwrap();
+    let keypair: KeyPair = KeyPair::try_from_private_key_bytes(KeyType::Ed25519, &secret_key[2..]).unwrap();
+    let public_key: Vec<u8> = BaseEncoding::decode_multibase(PUBLIC_KEY).unwrap();
+    assert_eq!(keypair.public().as_ref(), &public_key[2..]);
+
+    let properties: Value = json!({
+      "@context": [
+        "https://www.w3.org/ns/credentials/v2",
+        "https://www.w3.org/ns/credentials/examples/v2",
+      ],
+      "id": "urn:uuid:58172aac-d8ba-11ed-83dd-0b3aef56cc33",
+      "type": ["VerifiableCredential", "AlumniCredential"],
+      "name": "Alumni Credential",
+      "description": "A minimum viable example of an Alumni Credential.",
+      "issuer": "https://vc.example/issuers/5678",
+      "validFrom": "2023-01-01T00:00:00Z",
+      "credentialSubject": {
+        "id": "did:example:abcdefgh",
+        "alumniOf": "The School of Examples",
+      },
+    });
+    let mut data: Data = Data {
+      properties: Object::from_json_value(properties).unwrap(),
+      proof: Vec::new(),
+    };
+
+    let options: ProofOptions = ProofOptions::new()
+      .created(Timestamp::parse("2023-02-24T23:36:38Z").unwrap())
+      .purpose(ProofPurpose::AssertionMethod);
+    Signer::append_signature(&mut data, METHOD, keypair.private(), options).unwrap();
+
+    // The hash of the proof configuration is followed by the hash of the document.
+    let mut unsigned: Data = Data {
+      properties: data.properties.clone(),
+      proof: data.proof.clone(),
+    };
+    unsigned.proof[0].clear_value();
+    let hash: String = super::hash_data(&unsigned)
+      .unwrap()
+      .iter()
+      .map(|byte| format!("{:02x}", byte))
+      .collect();
+    assert_eq!(
+      hash,
+      "66ab154f5c2890a140cb8388a22a160454f80575f6eae09e5a097cabe539a1db\
+       59b7cb6251b8991add1ce0bc83107e3db9dbbab5bd2c28f687db1a03abc92f19"
+    );
+
+    assert_eq!(
+      data.proof[0].value().as_str(),
+      "z2HnFSSPPBzR36zdDgK8PbEHeXbR56YF24jwMpt3R1eHXQzJDMWS93FCzpvJpwTWd3GAVFuUfjoJdcnTMuVor51aX"
+    );
+    assert!(Verifier::verify_signature(&data, keypair.public()).is_ok());
+  }
+}
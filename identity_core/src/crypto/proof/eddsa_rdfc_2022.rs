@@ -0,0 +1,316 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::marker::PhantomData;
+
+use serde::Serialize;
+
+use crate::common::Object;
+use crate::crypto::proof::data_integrity::hash_canonical;
+use crate::crypto::proof::data_integrity::split_proof;
+use crate::crypto::Ed25519;
+use crate::crypto::Named;
+use crate::crypto::ProofValue;
+use crate::crypto::Sign;
+use crate::crypto::Signer;
+use crate::crypto::Verifier;
+use crate::crypto::Verify;
+use crate::error::Error;
+use crate::error::Result;
+use crate::utils::BaseEncoding;
+
+/// The JSON-LD contexts defining the `DataIntegrityProof` proof type.
+const SUITE_CONTEXTS: [&str; 2] = [
+  "https://www.w3.org/ns/credentials/v2",
+  "https://w3id.org/security/data-integrity/v2",
+];
+
+/// An implementation of the [`eddsa-rdfc-2022`][SPEC1] cryptosuite for [Data Integrity][SPEC2]
+/// proofs of the type `DataIntegrityProof`.
+///
+/// Users should use the [`Sign`]/[`Verify`] traits to access this implementation.
+///
+/// Like [`EdDsaJcs2022`](crate::crypto::EdDsaJcs2022), other proofs of a proof set are excluded
+/// from the signed data, but the proof configuration and the document are canonicalized with
+/// [URDNA2015][SPEC3] instead of JCS. The `@context` of the document must include either the
+/// Verifiable Credentials v2 or the Data Integrity v2 context.
+///
+/// [SPEC1]: https://www.w3.org/TR/vc-di-eddsa/#eddsa-rdfc-2022
+/// [SPEC2]: https://www.w3.org/TR/vc-data-integrity/
+/// [SPEC3]: https://www.w3.org/TR/rdf-canon/
+pub struct EdDsaRdfc2022<T = Ed25519>(PhantomData<T>);
+
+impl<T> Named for EdDsaRdfc2022<T> {
+  const NAME: &'static str = "DataIntegrityProof";
+  const CRYPTOSUITE: Option<&'static str> = Some("eddsa-rdfc-2022");
+}
+
+impl<T> Signer<T::Private> for EdDsaRdfc2022<T>
+where
+  T: Sign,
+  T::Output: AsRef<[u8]>,
+{
+  fn sign<X>(data: &X, private: &T::Private) -> Result<ProofValue>
+  where
+    X: Serialize,
+  {
+    let message: Vec<u8> = hash_data(data)?;
+    let signature: T::Output = T::sign(&message, private)?;
+
+    Ok(ProofValue::Proof(BaseEncoding::encode_multibase(
+      signature.as_ref(),
+      None,
+    )))
+  }
+}
+
+impl<T> Verifier<T::Public> for EdDsaRdfc2022<T>
+where
+  T: Verify,
+{
+  fn verify<X>(data: &X, signature: &ProofValue, public: &T::Public) -> Result<()>
+  where
+    X: Serialize + ?Sized,
+  {
+    let signature: &str = signature
+      .as_proof()
+      .ok_or(Error::InvalidProofValue("eddsa-rdfc-2022"))?;
+
+    let signature: Vec<u8> = BaseEncoding::decode_multibase(signature)?;
+    let message: Vec<u8> = hash_data(data)?;
+
+    T::verify(&message, &signature, public)?;
+
+    Ok(())
+  }
+}
+
+/// Transforms the secured `data` into its proof configuration and unsecured document and returns
+/// the concatenation of the hashes of their canonical forms.
+///
+/// [More Info](https://w3c.github.io/vc-di-eddsa/#hashing-eddsa-rdfc-2022)
+fn hash_data<X>(data: &X) -> Result<Vec<u8>>
+where
+  X: Serialize + ?Sized,
+{
+  let (configuration, document): (Object, Object) = split_proof(data)?;
+
+  hash_canonical(configuration, document, &SUITE_CONTEXTS)
+}
+
+#[cfg(test)]
+mod tests {
+  use serde::Serialize;
+
+  use crate::common::Object;
+  use crate::common::Timestamp;
+  use crate::common::Value;
+  use crate::convert::FromJson;
+  use crate::crypto::Ed25519;
+  use crate::crypto::EdDsaRdfc2022;
+  use crate::crypto::GetSignature;
+  use crate::crypto::GetSignatureMut;
+  use crate::crypto::KeyPair;
+  use crate::crypto::KeyType;
+  use crate::crypto::PrivateKey;
+  use crate::crypto::Proof;
+  use crate::crypto::ProofOptions;
+  use crate::crypto::ProofPurpose;
+  use crate::crypto::PublicKey;
+  use crate::crypto::SetSignature;
+  use crate::crypto::Signer as _;
+  use crate::crypto::Verifier as _;
+  use crate::error::Error;
+  use crate::error::Result;
+  use crate::json;
+  use crate::jsonld::JsonLdProcessor;
+  use crate::utils::BaseEncoding;
+
+  const METHOD: &str = "did:example:issuer#key-1";
+
+  type Signer = EdDsaRdfc2022<Ed25519<PrivateKey>>;
+
+  type Verifier = EdDsaRdfc2022<Ed25519<PublicKey>>;
+
+  #[derive(Serialize)]
+  struct Data {
+    #[serde(flatten)]
+    properties: Object,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proof: Option<Proof>,
+  }
+
+  impl Data {
+    fn new(context: Value) -> Self {
+      let properties: Value = json!({
+        "@context": context,
+        "id": "http://example.edu/credentials/3732",
+        "type": ["VerifiableCredential"],
+        "issuer": "did:example:issuer",
+        "issuanceDate": "2020-03-10T04:24:12Z",
+        "credentialSubject": {
+          "id": "did:example:subject",
+        },
+      });
+
+      Self {
+        properties: Object::from_json_value(properties).unwrap(),
+        proof: None,
+      }
+    }
+  }
+
+  impl GetSignature for Data {
+    fn signature(&self) -> Option<&Proof> {
+      self.proof.as_ref()
+    }
+  }
+
+  impl GetSignatureMut for Data {
+    fn signature_mut(&mut self) -> Option<&mut Proof> {
+      self.proof.as_mut()
+    }
+  }
+
+  impl SetSignature for Data {
+    fn set_signature(&mut self, signature: Proof) {
+      self.proof = Some(signature);
+    }
+  }
+
+  #[test]
+  fn test_sign_verify() {
+    let key1: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+    let key2: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+
+    let mut data: Data = Data::new(json!(["https://www.w3.org/ns/credentials/v2"]));
+    Signer::create_signature(&mut data, METHOD, key1.private(), ProofOptions::default()).unwrap();
+
+    let proof: &Proof = data.signature().unwrap();
+    assert_eq!(proof.type_(), "DataIntegrityProof");
+    assert_eq!(proof.cryptosuite(), Some("eddsa-rdfc-2022"));
+    assert!(proof.value().as_str().starts_with('z'));
+
+    assert!(Verifier::verify_signature(&data, key1.public()).is_ok());
+    assert!(Verifier::verify_signature(&data, key2.public()).is_err());
+
+    // Modified data is invalid.
+    data
+      .properties
+      .insert("issuer".to_owned(), Value::String("did:example:other".to_owned()));
+    assert!(Verifier::verify_signature(&data, key1.public()).is_err());
+  }
+
+  #[test]
+  fn test_missing_suite_context() {
+    let key: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+
+    let mut data: Data = Data::new(json!(["https://www.w3.org/2018/credentials/v1"]));
+    let result: Result<()> = Signer::create_signature(&mut data, METHOD, key.private(), ProofOptions::default());
+
+    assert!(matches!(result.unwrap_err(), Error::InvalidJsonLd(_)));
+  }
+
+  // The eddsa-rdfc-2022 test vector of https://www.w3.org/TR/vc-di-eddsa/.
+  #[test]
+  fn test_spec_vector() {
+    const PUBLIC_KEY: &str = "z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2";
+    const SECRET_KEY: &str = "z3u2en7t5LR2WtQH5PfFqMqwVHBeXouLzo6haApm8XHqvjxq";
+    const METHOD: &str =
+      "did:key:z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2#z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2";
+
+    // The multibase keys are prefixed with their two byte multicodec identifiers.
+    let secret_key: Vec<u8> = BaseEncoding::decode_multibase(SECRET_KEY).unwrap();
+    let keypair: KeyPair = KeyPair::try_from_private_key_bytes(KeyType::Ed25519, &secret_key[2..]).unwrap();
+    let public_key: Vec<u8> = BaseEncoding::decode_multibase(PUBLIC_KEY).unwrap();
+    assert_eq!(keypair.public().as_ref(), &public_key[2..]);
+
+    let context: Value = json!([
+      "https://www.w3.org/ns/credentials/v2",
+      "https://www.w3.org/ns/credentials/examples/v2",
+    ]);
+    let document: Value = json!({
+      "@context": context,
+      "id": "urn:uuid:58172aac-d8ba-11ed-83dd-0b3aef56cc33",
+      "type": ["VerifiableCredential", "AlumniCredential"],
+      "name": "Alumni Credential",
+      "description": "A minimum viable example of an Alumni Credential.",
+      "issuer": "https://vc.example/issuers/5678",
+      "validFrom": "2023-01-01T00:00:00Z",
+      "credentialSubject": {
+        "id": "did:example:abcdefgh",
+        "alumniOf": "The School of Examples",
+      },
+    });
+    let configuration: Value = json!({
+      "@context": context,
+      "type": "DataIntegrityProof",
+      "cryptosuite": "eddsa-rdfc-2022",
+      "created": "2023-02-24T23:36:38Z",
+      "verificationMethod": METHOD,
+      "proofPurpose": "assertionMethod",
+    });
+
+    let processor: JsonLdProcessor = JsonLdProcessor::default();
+    assert_eq!(
+      processor.canonicalize(&document).unwrap(),
+      "<did:example:abcdefgh> <https://www.w3.org/ns/credentials/examples#alumniOf> \"The School of Examples\" .\n\
+       <urn:uuid:58172aac-d8ba-11ed-83dd-0b3aef56cc33> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> \
+       <https://www.w3.org/2018/credentials#VerifiableCredential> .\n\
+       <urn:uuid:58172aac-d8ba-11ed-83dd-0b3aef56cc33> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> \
+       <https://www.w3.org/ns/credentials/examples#AlumniCredential> .\n\
+       <urn:uuid:58172aac-d8ba-11ed-83dd-0b3aef56cc33> <https://schema.org/description> \
+       \"A minimum viable example of an Alumni Credential.\" .\n\
+       <urn:uuid:58172aac-d8ba-11ed-83dd-0b3aef56cc33> <https://schema.org/name> \"Alumni Credential\" .\n\
+       <urn:uuid:58172aac-d8ba-11ed-83dd-0b3aef56cc33> <https://www.w3.org/2018/credentials#credentialSubject> \
+       <did:example:abcdefgh> .\n\
+       <urn:uuid:58172aac-d8ba-11ed-83dd-0b3aef56cc33> <https://www.w3.org/2018/credentials#issuer> \
+       <https://vc.example/issuers/5678> .\n\
+       <urn:uuid:58172aac-d8ba-11ed-83dd-0b3aef56cc33> <https://www.w3.org/2018/credentials#validFrom> \
+       \"2023-01-01T00:00:00Z\"^^<http://www.w3.org/2001/XMLSchema#dateTime> .\n"
+    );
+    assert_eq!(
+      processor.canonicalize(&configuration).unwrap(),
+      "_:c14n0 <http://purl.org/dc/terms/created> \
+       \"2023-02-24T23:36:38Z\"^^<http://www.w3.org/2001/XMLSchema#dateTime> .\n\
+       _:c14n0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://w3id.org/security#DataIntegrityProof> .\n\
+       _:c14n0 <https://w3id.org/security#cryptosuite> \
+       \"eddsa-rdfc-2022\"^^<https://w3id.org/security#cryptosuiteString> .\n\
+       _:c14n0 <https://w3id.org/security#proofPurpose> <https://w3id.org/security#assertionMethod> .\n\
+       _:c14n0 <https://w3id.org/security#verificationMethod> \
+       <did:key:z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2#z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2> .\n"
+    );
+
+    let mut data: Data = Data {
+      properties: Object::from_json_value(document).unwrap(),
+      proof: None,
+    };
+    let options: ProofOptions = ProofOptions::new()
+      .created(Timestamp::parse("2023-02-24T23:36:38Z").unwrap())
+      .purpose(ProofPurpose::AssertionMethod);
+    Signer::create_signature(&mut data, METHOD, keypair.private(), options).unwrap();
+
+    // The hash of the proof configuration is followed by the hash of the document.
+    let mut unsigned: Data = Data {
+      properties: data.properties.clone(),
+      proof: data.proof.clone(),
+    };
+    unsigned.signature_mut().unwrap().clear_value();
+    let hash: String = super::hash_data(&unsigned)
+      .unwrap()
+      .iter()
+      .map(|byte| format!("{:02x}", byte))
+      .collect();
+    assert_eq!(
+      hash,
+      "bea7b7acfbad0126b135104024a5f1733e705108f42d59668b05c0c50004c6b0\
+       517744132ae165a5349155bef0bb0cf2258fff99dfe1dbd914b938d775a36017"
+    );
+
+    assert_eq!(
+      data.signature().unwrap().value().as_str(),
+      "z2YwC8z3ap7yx1nZYCg4L3j3ApHsF8kgPdSb5xoS1VR7vPG3F561B52hYnQF9iseabecm3ijx4K1FBTQsCZahKZme"
+    );
+    assert!(Verifier::verify_signature(&data, keypair.public()).is_ok());
+  }
+}
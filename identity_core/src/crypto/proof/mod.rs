@@ -6,13 +6,22 @@
 //! Types and traits for helping ensure the authenticity and integrity of
 //! DID Documents and Verifiable Credentials.
 
+pub use self::ed25519_signature_2020::Ed25519Signature2020;
+pub use self::eddsa_jcs_2022::EdDsaJcs2022;
+pub use self::eddsa_rdfc_2022::EdDsaRdfc2022;
 pub use self::jcs_ed25519::JcsEd25519;
 pub use self::proof::Proof;
 pub use self::proof_options::ProofOptions;
 pub use self::proof_options::ProofPurpose;
 pub use self::proof_value::ProofValue;
+pub use self::proof_view::ProofView;
 
+mod data_integrity;
+mod ed25519_signature_2020;
+mod eddsa_jcs_2022;
+mod eddsa_rdfc_2022;
 mod jcs_ed25519;
 mod proof;
 mod proof_options;
 mod proof_value;
+mod proof_view;
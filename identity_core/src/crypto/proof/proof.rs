@@ -26,11 +26,19 @@ use crate::error::Result;
 pub struct Proof {
   #[serde(rename = "type")]
   type_: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  cryptosuite: Option<String>,
   #[serde(flatten)]
   value: ProofValue,
   #[serde(rename = "verificationMethod")]
   method: String,
 
+  /// An identifier of the proof, referenced by the `previousProof` of a proof chain.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub id: Option<String>,
+  /// The identifier of the proof preceding this proof in a proof chain.
+  #[serde(rename = "previousProof", skip_serializing_if = "Option::is_none")]
+  pub previous_proof: Option<String>,
   /// When the proof was generated.
   #[serde(skip_serializing_if = "Option::is_none")]
   pub created: Option<Timestamp>,
@@ -62,8 +70,11 @@ impl Proof {
   pub fn new_with_options(type_: impl Into<String>, method: impl Into<String>, options: ProofOptions) -> Self {
     Self {
       type_: type_.into(),
+      cryptosuite: None,
       value: ProofValue::None,
       method: method.into(),
+      id: options.id,
+      previous_proof: options.previous_proof,
       created: options.created,
       expires: options.expires,
       challenge: options.challenge,
@@ -73,11 +84,31 @@ impl Proof {
     }
   }
 
+  /// Creates a new [Data Integrity][SPEC] [`Proof`] instance with the given `type_`, `cryptosuite`
+  /// and properties.
+  ///
+  /// [SPEC]: https://www.w3.org/TR/vc-data-integrity/
+  pub fn new_with_cryptosuite(
+    type_: impl Into<String>,
+    cryptosuite: impl Into<String>,
+    method: impl Into<String>,
+    options: ProofOptions,
+  ) -> Self {
+    let mut proof: Self = Self::new_with_options(type_, method, options);
+    proof.cryptosuite = Some(cryptosuite.into());
+    proof
+  }
+
   /// Returns the `type` property of the proof.
   pub fn type_(&self) -> &str {
     &*self.type_
   }
 
+  /// Returns the `cryptosuite` property of a Data Integrity proof.
+  pub fn cryptosuite(&self) -> Option<&str> {
+    self.cryptosuite.as_deref()
+  }
+
   /// Returns the identifier of the DID method used to create this proof.
   pub fn verification_method(&self) -> &str {
     &*self.method
@@ -122,8 +153,11 @@ impl Debug for Proof {
   fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
     f.debug_struct("Proof")
       .field("type_", &self.type_)
+      .field("cryptosuite", &self.cryptosuite)
       .field("value", &self.value)
       .field("method", &self.method)
+      .field("id", &self.id)
+      .field("previous_proof", &self.previous_proof)
       .field("created", &self.created)
       .field("expires", &self.expires)
       .field("challenge", &self.challenge)
@@ -145,6 +179,9 @@ impl Serialize for Proof {
     } else {
       3 // type + method + value
     };
+    count_fields += if self.cryptosuite.is_some() { 1 } else { 0 };
+    count_fields += if self.id.is_some() { 1 } else { 0 };
+    count_fields += if self.previous_proof.is_some() { 1 } else { 0 };
     count_fields += if self.created.is_some() { 1 } else { 0 };
     count_fields += if self.expires.is_some() { 1 } else { 0 };
     count_fields += if self.challenge.is_some() { 1 } else { 0 };
@@ -153,11 +190,21 @@ impl Serialize for Proof {
     let mut state: S::SerializeMap = serializer.serialize_map(Some(count_fields))?;
 
     state.serialize_entry("type", &self.type_)?;
+    if let Some(cryptosuite) = &self.cryptosuite {
+      state.serialize_entry("cryptosuite", &cryptosuite)?;
+    }
     state.serialize_entry("verificationMethod", &self.method)?;
     if !hide {
       Serialize::serialize(&self.value, FlatMapSerializer(&mut state))?;
     }
 
+    if let Some(id) = &self.id {
+      state.serialize_entry("id", &id)?;
+    }
+    if let Some(previous_proof) = &self.previous_proof {
+      state.serialize_entry("previousProof", &previous_proof)?;
+    }
+
     if let Some(created) = &self.created {
       state.serialize_entry("created", &created)?;
     }
@@ -244,6 +291,8 @@ mod tests {
       challenge: Some("some-challenge".to_owned()),
       domain: Some("some.domain".to_owned()),
       purpose: Some(ProofPurpose::Authentication),
+      id: None,
+      previous_proof: None,
     }
   }
 
@@ -280,4 +329,29 @@ mod tests {
     let deserialized: Proof = Proof::from_json(&signature.to_json().unwrap()).unwrap();
     assert_eq!(signature, deserialized);
   }
+
+  #[test]
+  fn test_data_integrity_proof_json() {
+    let options: ProofOptions = ProofOptions::new()
+      .id("urn:uuid:2".to_owned())
+      .previous_proof("urn:uuid:1".to_owned())
+      .purpose(ProofPurpose::AssertionMethod);
+    let mut signature: Proof = Proof::new_with_cryptosuite("DataIntegrityProof", "eddsa-jcs-2022", "#sign-0", options);
+    assert_eq!(signature.cryptosuite(), Some("eddsa-jcs-2022"));
+    signature.set_value(ProofValue::Proof("zsomeproofvalue".to_owned()));
+
+    let expected = json!({
+      "type":"DataIntegrityProof",
+      "cryptosuite":"eddsa-jcs-2022",
+      "verificationMethod":"#sign-0",
+      "proofValue":"zsomeproofvalue",
+      "id":"urn:uuid:2",
+      "previousProof":"urn:uuid:1",
+      "proofPurpose":"assertionMethod",
+    });
+    assert_eq!(signature.to_json_value().unwrap(), expected);
+
+    let deserialized: Proof = Proof::from_json(&signature.to_json().unwrap()).unwrap();
+    assert_eq!(signature, deserialized);
+  }
 }
@@ -26,6 +26,12 @@ pub struct ProofOptions {
   pub domain: Option<String>,
   /// [`Proof::purpose`](crate::crypto::Proof::purpose)
   pub purpose: Option<ProofPurpose>,
+  /// [`Proof::id`](crate::crypto::Proof::id)
+  #[serde(default)]
+  pub id: Option<String>,
+  /// [`Proof::previous_proof`](crate::crypto::Proof::previous_proof)
+  #[serde(default, rename = "previousProof")]
+  pub previous_proof: Option<String>,
 }
 
 impl ProofOptions {
@@ -37,6 +43,8 @@ impl ProofOptions {
       challenge: None,
       domain: None,
       purpose: None,
+      id: None,
+      previous_proof: None,
     }
   }
 
@@ -75,6 +83,21 @@ impl ProofOptions {
     self.purpose = Some(purpose);
     self
   }
+
+  /// Sets the [`Proof::id`](crate::crypto::Proof::id) field.
+  #[must_use]
+  pub fn id(mut self, id: String) -> Self {
+    self.id = Some(id);
+    self
+  }
+
+  /// Sets the [`Proof::previous_proof`](crate::crypto::Proof::previous_proof) field, chaining
+  /// the proof to the proof with the given identifier.
+  #[must_use]
+  pub fn previous_proof(mut self, previous_proof: String) -> Self {
+    self.previous_proof = Some(previous_proof);
+    self
+  }
 }
 
 /// Associates a purpose with a [`Proof`](crate::crypto::Proof).
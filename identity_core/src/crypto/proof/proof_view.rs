@@ -0,0 +1,50 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::Serialize;
+use serde::Serializer;
+
+use crate::crypto::GetSignature;
+use crate::crypto::Proof;
+
+/// A view of `data` exposing a single [`Proof`] of its proof set through [`GetSignature`].
+///
+/// Allows verifying the proofs of a proof set one at a time with any verifier of
+/// [`GetSignature`] types. The view serializes as `data`.
+#[derive(Debug)]
+pub struct ProofView<'a, T: ?Sized> {
+  data: &'a T,
+  proof: &'a Proof,
+}
+
+impl<'a, T: ?Sized> ProofView<'a, T> {
+  /// Creates a new `ProofView` of `data` exposing `proof`.
+  ///
+  /// The `proof` MUST be a reference to a proof contained in `data`.
+  pub fn new(data: &'a T, proof: &'a Proof) -> Self {
+    Self { data, proof }
+  }
+
+  /// Returns a reference to the viewed data.
+  pub fn data(&self) -> &'a T {
+    self.data
+  }
+}
+
+impl<T> Serialize for ProofView<'_, T>
+where
+  T: Serialize + ?Sized,
+{
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    self.data.serialize(serializer)
+  }
+}
+
+impl<T: ?Sized> GetSignature for ProofView<'_, T> {
+  fn signature(&self) -> Option<&Proof> {
+    Some(self.proof)
+  }
+}
@@ -7,6 +7,7 @@ use crate::crypto::GetSignature;
 use crate::crypto::Proof;
 use crate::crypto::ProofOptions;
 use crate::crypto::ProofValue;
+use crate::crypto::PushProof;
 use crate::crypto::SetSignature;
 use crate::error::Error;
 use crate::error::Result;
@@ -42,6 +43,11 @@ pub trait Verify {
 pub trait Named {
   /// A unique identifier for the signatures created by this suite.
   const NAME: &'static str;
+
+  /// The identifier of the cryptographic suite of a [Data Integrity][SPEC] proof, if any.
+  ///
+  /// [SPEC]: https://www.w3.org/TR/vc-data-integrity/
+  const CRYPTOSUITE: Option<&'static str> = None;
 }
 
 // =============================================================================
@@ -59,7 +65,7 @@ pub trait Signer<Secret: ?Sized>: Named {
   where
     T: Serialize + SetSignature,
   {
-    let signature: Proof = Proof::new_with_options(Self::NAME, method, options);
+    let signature: Proof = Self::new_proof(method, options);
     data.set_signature(signature);
 
     let value: ProofValue = Self::sign(&data, secret)?;
@@ -68,6 +74,33 @@ pub trait Signer<Secret: ?Sized>: Named {
 
     Ok(())
   }
+
+  /// Creates a signature [proof][`Proof`] and appends it to the proof set of the given `data`,
+  /// leaving any existing proofs intact.
+  ///
+  /// The existing proofs are part of the signed data unless the signature suite excludes
+  /// them, as the Data Integrity suites do for proof sets.
+  fn append_signature<T>(data: &mut T, method: impl Into<String>, secret: &Secret, options: ProofOptions) -> Result<()>
+  where
+    T: Serialize + PushProof,
+  {
+    let signature: Proof = Self::new_proof(method, options);
+    data.push_proof(signature);
+
+    let value: ProofValue = Self::sign(&data, secret)?;
+    let write: &mut Proof = data.signature_mut().ok_or(Error::MissingSignature)?;
+    write.set_value(value);
+
+    Ok(())
+  }
+
+  /// Creates a new [`Proof`] of this signature suite without a value.
+  fn new_proof(method: impl Into<String>, options: ProofOptions) -> Proof {
+    match Self::CRYPTOSUITE {
+      Some(cryptosuite) => Proof::new_with_cryptosuite(Self::NAME, cryptosuite, method, options),
+      None => Proof::new_with_options(Self::NAME, method, options),
+    }
+  }
 }
 
 // =============================================================================
@@ -87,16 +120,27 @@ pub trait Verifier<Public: ?Sized>: Named {
   {
     let signature: &Proof = data.signature().ok_or(Error::MissingSignature)?;
 
-    if signature.type_() != Self::NAME {
+    Self::verify_proof(data, signature, public)
+  }
+
+  /// Verifies the [proof][`Proof`] `signature` of the given `data`.
+  ///
+  /// The `signature` MUST be a reference to a proof contained in `data`, e.g. a member of
+  /// its proof set, since its value is hidden while `data` is serialized.
+  fn verify_proof<T>(data: &T, signature: &Proof, public: &Public) -> Result<()>
+  where
+    T: Serialize + ?Sized,
+  {
+    if signature.type_() != Self::NAME || signature.cryptosuite() != Self::CRYPTOSUITE {
       return Err(Error::InvalidProofValue("signature name"));
     }
 
     signature.hide_value();
 
-    Self::verify(data, signature.value(), public)?;
+    let result: Result<()> = Self::verify(data, signature.value(), public);
 
     signature.show_value();
 
-    Ok(())
+    result
   }
 }
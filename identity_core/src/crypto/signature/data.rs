@@ -62,3 +62,51 @@ where
     (**self).set_signature(signature);
   }
 }
+
+// =============================================================================
+// =============================================================================
+
+/// A trait for types that can provide a set of [`Proof`]s, e.g. a
+/// [proof set or chain](https://www.w3.org/TR/vc-data-integrity/#proof-sets).
+pub trait GetProofs {
+  /// Returns the [`Proof`]s in the order they were added.
+  fn proofs(&self) -> &[Proof];
+}
+
+impl<T> GetProofs for &T
+where
+  T: GetProofs,
+{
+  fn proofs(&self) -> &[Proof] {
+    (**self).proofs()
+  }
+}
+
+impl<T> GetProofs for &mut T
+where
+  T: GetProofs,
+{
+  fn proofs(&self) -> &[Proof] {
+    (**self).proofs()
+  }
+}
+
+// =============================================================================
+// =============================================================================
+
+/// A trait for types that can store multiple digital [signatures][`Proof`].
+///
+/// The [`GetSignature`] implementation of these types MUST return the last added [`Proof`].
+pub trait PushProof: GetSignatureMut + GetProofs {
+  /// Appends `proof` to the [`Proof`]s of `self`.
+  fn push_proof(&mut self, proof: Proof);
+}
+
+impl<T> PushProof for &mut T
+where
+  T: PushProof,
+{
+  fn push_proof(&mut self, proof: Proof) {
+    (**self).push_proof(proof);
+  }
+}
@@ -8,8 +8,10 @@ pub use self::core::Sign;
 pub use self::core::Signer;
 pub use self::core::Verifier;
 pub use self::core::Verify;
+pub use self::data::GetProofs;
 pub use self::data::GetSignature;
 pub use self::data::GetSignatureMut;
+pub use self::data::PushProof;
 pub use self::data::SetSignature;
 
 mod core;
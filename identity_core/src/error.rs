@@ -60,6 +60,15 @@ pub enum Error {
   /// Caused by a failed attempt at retrieving a digital signature.
   #[error("Signature Not Found")]
   MissingSignature,
+  /// Caused by processing an invalid JSON-LD document or context.
+  #[error("Invalid JSON-LD: {0}")]
+  InvalidJsonLd(&'static str),
+  /// Caused by a JSON-LD context that cannot be resolved by the document loader.
+  #[error("JSON-LD Context Not Found: {0}")]
+  JsonLdContextNotFound(String),
+  /// Caused by a JSON-LD term that is not defined by the active context.
+  #[error("Undefined JSON-LD Term: {0}")]
+  JsonLdUndefinedTerm(String),
 }
 
 impl From<crypto::Error> for Error {
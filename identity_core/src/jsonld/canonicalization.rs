@@ -0,0 +1,428 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use crypto::hashes::sha::SHA256;
+use crypto::hashes::sha::SHA256_LEN;
+
+use crate::jsonld::issuer::IdentifierIssuer;
+use crate::jsonld::Quad;
+use crate::jsonld::Term;
+
+/// Returns the canonical form of the RDF `dataset`, relabeling its blank nodes with the
+/// [URDNA2015][SPEC] algorithm.
+///
+/// The quads are sorted by their N-Quads serialization and duplicates are removed, so that
+/// isomorphic datasets result in equal canonical forms.
+///
+/// [SPEC]: https://www.w3.org/TR/rdf-canon/
+pub fn canonicalize(dataset: &[Quad]) -> Vec<Quad> {
+  let mut blank_node_quads: BTreeMap<&str, Vec<&Quad>> = BTreeMap::new();
+
+  for quad in dataset {
+    for term in [Some(&quad.subject), Some(&quad.object), quad.graph.as_ref()]
+      .into_iter()
+      .flatten()
+    {
+      if let Some(id) = term.as_blank_node() {
+        let quads: &mut Vec<&Quad> = blank_node_quads.entry(id).or_default();
+
+        if !quads.last().map_or(false, |last| core::ptr::eq(*last, quad)) {
+          quads.push(quad);
+        }
+      }
+    }
+  }
+
+  let mut canonicalizer: Canonicalizer<'_> = Canonicalizer {
+    blank_node_quads: &blank_node_quads,
+    canonical_issuer: IdentifierIssuer::new("_:c14n"),
+  };
+
+  let mut non_normalized: BTreeSet<&str> = blank_node_quads.keys().copied().collect();
+  let mut hash_to_blank_nodes: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+  let mut simple: bool = true;
+
+  while simple {
+    simple = false;
+    hash_to_blank_nodes.clear();
+
+    for id in non_normalized.iter().copied() {
+      hash_to_blank_nodes
+        .entry(canonicalizer.hash_first_degree(id))
+        .or_default()
+        .push(id);
+    }
+
+    let unique: Vec<(String, &str)> = hash_to_blank_nodes
+      .iter()
+      .filter(|(_, ids)| ids.len() == 1)
+      .map(|(hash, ids)| (hash.clone(), ids[0]))
+      .collect();
+
+    for (hash, id) in unique {
+      canonicalizer.canonical_issuer.issue(id);
+      non_normalized.remove(id);
+      hash_to_blank_nodes.remove(&hash);
+      simple = true;
+    }
+  }
+
+  for ids in hash_to_blank_nodes.values() {
+    let mut hash_path_list: Vec<(String, IdentifierIssuer)> = Vec::new();
+
+    for id in ids {
+      if canonicalizer.canonical_issuer.has(id) {
+        continue;
+      }
+
+      let mut issuer: IdentifierIssuer = IdentifierIssuer::new("_:b");
+      issuer.issue(id);
+      hash_path_list.push(canonicalizer.hash_n_degree(id, issuer));
+    }
+
+    hash_path_list.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (_, issuer) in hash_path_list {
+      for existing in issuer.issued() {
+        canonicalizer.canonical_issuer.issue(existing);
+      }
+    }
+  }
+
+  let issuer: &IdentifierIssuer = &canonicalizer.canonical_issuer;
+  let relabel = |id: &str| issuer.get(id).unwrap_or(id).to_owned();
+
+  let mut canonical: Vec<(String, Quad)> = dataset
+    .iter()
+    .map(|quad| quad.relabel(&relabel))
+    .map(|quad| (quad.to_string(), quad))
+    .collect();
+
+  canonical.sort_by(|a, b| a.0.cmp(&b.0));
+  canonical.dedup_by(|a, b| a.0 == b.0);
+  canonical.into_iter().map(|(_, quad)| quad).collect()
+}
+
+struct Canonicalizer<'a> {
+  blank_node_quads: &'a BTreeMap<&'a str, Vec<&'a Quad>>,
+  canonical_issuer: IdentifierIssuer,
+}
+
+impl<'a> Canonicalizer<'a> {
+  fn quads(&self, id: &str) -> &'a [&'a Quad] {
+    self.blank_node_quads.get(id).map(Vec::as_slice).unwrap_or_default()
+  }
+
+  /// [More Info](https://www.w3.org/TR/rdf-canon/#hash-1d-quads)
+  fn hash_first_degree(&self, id: &str) -> String {
+    let relabel = |other: &str| (if other == id { "_:a" } else { "_:z" }).to_owned();
+
+    let mut nquads: Vec<String> = self
+      .quads(id)
+      .iter()
+      .map(|quad| format!("{}\n", quad.relabel(&relabel)))
+      .collect();
+
+    nquads.sort_unstable();
+    sha256_hex(nquads.concat().as_bytes())
+  }
+
+  /// [More Info](https://www.w3.org/TR/rdf-canon/#hash-related-blank-node)
+  fn hash_related(&self, related: &str, quad: &Quad, issuer: &IdentifierIssuer, position: char) -> String {
+    let identifier: String = match self.canonical_issuer.get(related).or_else(|| issuer.get(related)) {
+      Some(identifier) => identifier.to_owned(),
+      None => self.hash_first_degree(related),
+    };
+
+    let mut input: String = String::from(position);
+
+    if position != 'g' {
+      input.push_str(&quad.predicate.to_string());
+    }
+
+    input.push_str(&identifier);
+    sha256_hex(input.as_bytes())
+  }
+
+  /// [More Info](https://www.w3.org/TR/rdf-canon/#hash-nd-quads)
+  fn hash_n_degree(&self, id: &str, mut issuer: IdentifierIssuer) -> (String, IdentifierIssuer) {
+    let mut hash_to_related: BTreeMap<String, Vec<&'a str>> = BTreeMap::new();
+
+    for quad in self.quads(id) {
+      let terms: [(Option<&'a Term>, char); 3] = [
+        (Some(&quad.subject), 's'),
+        (Some(&quad.object), 'o'),
+        (quad.graph.as_ref(), 'g'),
+      ];
+
+      for (term, position) in terms {
+        if let Some(related) = term.and_then(Term::as_blank_node).filter(|related| *related != id) {
+          hash_to_related
+            .entry(self.hash_related(related, quad, &issuer, position))
+            .or_default()
+            .push(related);
+        }
+      }
+    }
+
+    let mut data: String = String::new();
+
+    for (related_hash, blank_nodes) in hash_to_related {
+      data.push_str(&related_hash);
+
+      let mut chosen_path: String = String::new();
+      let mut chosen_issuer: Option<IdentifierIssuer> = None;
+
+      for permutation in permutations(&blank_nodes) {
+        let mut issuer_copy: IdentifierIssuer = issuer.clone();
+        let mut path: String = String::new();
+        let mut recursion_list: Vec<&str> = Vec::new();
+        let mut skip: bool = false;
+
+        for related in permutation {
+          if let Some(canonical) = self.canonical_issuer.get(related) {
+            path.push_str(canonical);
+          } else {
+            if !issuer_copy.has(related) {
+              recursion_list.push(related);
+            }
+            path.push_str(&issuer_copy.issue(related));
+          }
+
+          if is_longer_path(&path, &chosen_path) {
+            skip = true;
+            break;
+          }
+        }
+
+        if skip {
+          continue;
+        }
+
+        for related in recursion_list {
+          let (hash, result_issuer): (String, IdentifierIssuer) = self.hash_n_degree(related, issuer_copy.clone());
+
+          path.push_str(&issuer_copy.issue(related));
+          path.push('<');
+          path.push_str(&hash);
+          path.push('>');
+          issuer_copy = result_issuer;
+
+          if is_longer_path(&path, &chosen_path) {
+            skip = true;
+            break;
+          }
+        }
+
+        if skip {
+          continue;
+        }
+
+        if chosen_path.is_empty() || path < chosen_path {
+          chosen_path = path;
+          chosen_issuer = Some(issuer_copy);
+        }
+      }
+
+      data.push_str(&chosen_path);
+
+      if let Some(chosen_issuer) = chosen_issuer {
+        issuer = chosen_issuer;
+      }
+    }
+
+    (sha256_hex(data.as_bytes()), issuer)
+  }
+}
+
+/// Returns `true` if `path` cannot become lexicographically smaller than `chosen_path`.
+fn is_longer_path(path: &str, chosen_path: &str) -> bool {
+  !chosen_path.is_empty() && path.len() >= chosen_path.len() && path > chosen_path
+}
+
+/// Returns all permutations of `items`, in lexicographic order of their indices.
+fn permutations<'a>(items: &[&'a str]) -> Vec<Vec<&'a str>> {
+  let mut indices: Vec<usize> = (0..items.len()).collect();
+  let mut permutations: Vec<Vec<&'a str>> = Vec::new();
+
+  loop {
+    permutations.push(indices.iter().map(|index| items[*index]).collect());
+
+    // Find the next permutation of the indices, stopping after the last one.
+    let pivot: usize = match (1..indices.len())
+      .rev()
+      .find(|index| indices[index - 1] < indices[*index])
+    {
+      Some(index) => index - 1,
+      None => return permutations,
+    };
+
+    let successor: usize = (pivot + 1..indices.len())
+      .rev()
+      .find(|index| indices[*index] > indices[pivot])
+      .unwrap_or(pivot);
+
+    indices.swap(pivot, successor);
+    indices[pivot + 1..].reverse();
+  }
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+  let mut digest: [u8; SHA256_LEN] = [0; SHA256_LEN];
+  SHA256(data, &mut digest);
+  digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn iri(value: &str) -> Term {
+    Term::Iri(value.to_owned())
+  }
+
+  fn blank(value: &str) -> Term {
+    Term::BlankNode(value.to_owned())
+  }
+
+  fn quad(subject: Term, predicate: &str, object: Term) -> Quad {
+    Quad {
+      subject,
+      predicate: iri(predicate),
+      object,
+      graph: None,
+    }
+  }
+
+  #[test]
+  fn test_canonicalize_simple() {
+    let dataset: Vec<Quad> = vec![
+      quad(blank("_:x"), "http://example.org/vocab#p", blank("_:y")),
+      quad(
+        blank("_:y"),
+        "http://example.org/vocab#q",
+        Term::Literal {
+          value: "a \"quoted\"\nvalue".to_owned(),
+          datatype: "http://www.w3.org/2001/XMLSchema#string".to_owned(),
+          language: None,
+        },
+      ),
+    ];
+
+    let canonical: Vec<Quad> = canonicalize(&dataset);
+    let nquads: String = crate::jsonld::to_nquads(&canonical);
+
+    // The first-degree hashes of both blank nodes are unique, so they are labeled in hash order.
+    assert_eq!(canonical.len(), 2);
+    assert!(nquads.contains(r#"<http://example.org/vocab#q> "a \"quoted\"\nvalue" ."#));
+    assert!(nquads.lines().all(|line| line.starts_with("_:c14n")));
+  }
+
+  #[test]
+  fn test_canonicalize_isomorphic() {
+    // A cycle of three indistinguishable blank nodes requires N-degree hashing.
+    let cycle = |a: &str, b: &str, c: &str| {
+      vec![
+        quad(blank(a), "http://example.org/vocab#next", blank(b)),
+        quad(blank(b), "http://example.org/vocab#next", blank(c)),
+        quad(blank(c), "http://example.org/vocab#next", blank(a)),
+        quad(blank(a), "http://example.org/vocab#label", iri("http://example.org/a")),
+      ]
+    };
+
+    let mut reordered: Vec<Quad> = cycle("_:q", "_:r", "_:p");
+    reordered.reverse();
+
+    let expected: Vec<Quad> = canonicalize(&cycle("_:a", "_:b", "_:c"));
+    assert_eq!(canonicalize(&reordered), expected);
+    assert_eq!(canonicalize(&cycle("_:b1", "_:b0", "_:b2")), expected);
+
+    // Non-isomorphic datasets result in different canonical forms.
+    let mut other: Vec<Quad> = cycle("_:a", "_:b", "_:c");
+    other[3].subject = blank("_:b");
+    other[3].object = iri("http://example.org/b");
+    assert_ne!(canonicalize(&other), expected);
+  }
+
+  #[test]
+  fn test_canonicalize_symmetric() {
+    // Two disconnected, identical components can only be ordered by N-degree hashing.
+    let dataset: Vec<Quad> = vec![
+      quad(blank("_:a"), "http://example.org/vocab#p", blank("_:b")),
+      quad(blank("_:b"), "http://example.org/vocab#p", blank("_:a")),
+      quad(blank("_:c"), "http://example.org/vocab#p", blank("_:d")),
+      quad(blank("_:d"), "http://example.org/vocab#p", blank("_:c")),
+    ];
+
+    let canonical: Vec<Quad> = canonicalize(&dataset);
+    let labels: BTreeSet<String> = canonical
+      .iter()
+      .flat_map(|quad| [quad.subject.to_string(), quad.object.to_string()])
+      .collect();
+
+    assert_eq!(canonical.len(), 4);
+    assert_eq!(
+      labels.into_iter().collect::<Vec<String>>(),
+      ["_:c14n0", "_:c14n1", "_:c14n2", "_:c14n3"]
+    );
+  }
+
+  // The examples of the canonicalization algorithm in https://www.w3.org/TR/rdf-canon/.
+  #[test]
+  fn test_canonicalize_spec_examples() {
+    let ex = |name: &str| format!("http://example.com/#{}", name);
+
+    // The first-degree hashes of all blank nodes are unique.
+    let unique: Vec<Quad> = vec![
+      quad(iri(&ex("p")), &ex("q"), blank("_:e0")),
+      quad(iri(&ex("p")), &ex("r"), blank("_:e1")),
+      quad(blank("_:e0"), &ex("s"), iri(&ex("u"))),
+      quad(blank("_:e1"), &ex("t"), iri(&ex("u"))),
+    ];
+
+    assert_eq!(
+      crate::jsonld::to_nquads(&canonicalize(&unique)),
+      "<http://example.com/#p> <http://example.com/#q> _:c14n0 .\n\
+       <http://example.com/#p> <http://example.com/#r> _:c14n1 .\n\
+       _:c14n0 <http://example.com/#s> <http://example.com/#u> .\n\
+       _:c14n1 <http://example.com/#t> <http://example.com/#u> .\n"
+    );
+
+    // The blank nodes share first-degree hashes in pairs.
+    let shared: Vec<Quad> = vec![
+      quad(iri(&ex("p")), &ex("q"), blank("_:e0")),
+      quad(iri(&ex("p")), &ex("q"), blank("_:e1")),
+      quad(blank("_:e0"), &ex("p"), blank("_:e2")),
+      quad(blank("_:e1"), &ex("p"), blank("_:e3")),
+      quad(blank("_:e2"), &ex("r"), blank("_:e3")),
+    ];
+
+    assert_eq!(
+      crate::jsonld::to_nquads(&canonicalize(&shared)),
+      "<http://example.com/#p> <http://example.com/#q> _:c14n2 .\n\
+       <http://example.com/#p> <http://example.com/#q> _:c14n3 .\n\
+       _:c14n0 <http://example.com/#r> _:c14n1 .\n\
+       _:c14n2 <http://example.com/#p> _:c14n1 .\n\
+       _:c14n3 <http://example.com/#p> _:c14n0 .\n"
+    );
+  }
+
+  #[test]
+  fn test_permutations() {
+    assert_eq!(permutations(&["a"]), [["a"]]);
+    assert_eq!(
+      permutations(&["a", "b", "c"]),
+      [
+        ["a", "b", "c"],
+        ["a", "c", "b"],
+        ["b", "a", "c"],
+        ["b", "c", "a"],
+        ["c", "a", "b"],
+        ["c", "b", "a"],
+      ]
+    );
+  }
+}
@@ -0,0 +1,811 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use crate::common::Url;
+use crate::common::Value;
+use crate::error::Error;
+use crate::error::Result;
+use crate::jsonld::DocumentLoader;
+use serde_json::Map;
+
+/// The maximum depth of nested remote contexts.
+const MAX_REMOTE_CONTEXTS: usize = 32;
+
+/// The JSON-LD 1.1 keywords.
+const KEYWORDS: [&str; 23] = [
+  "@base",
+  "@container",
+  "@context",
+  "@direction",
+  "@graph",
+  "@id",
+  "@import",
+  "@included",
+  "@index",
+  "@json",
+  "@language",
+  "@list",
+  "@nest",
+  "@none",
+  "@prefix",
+  "@propagate",
+  "@protected",
+  "@reverse",
+  "@set",
+  "@type",
+  "@value",
+  "@version",
+  "@vocab",
+];
+
+/// The valid container mappings of a term definition.
+const CONTAINERS: [&str; 7] = ["@graph", "@id", "@index", "@language", "@list", "@set", "@type"];
+
+/// The entries of a local context that are not term definitions.
+const CONTEXT_KEYWORDS: [&str; 8] = [
+  "@base",
+  "@direction",
+  "@import",
+  "@language",
+  "@propagate",
+  "@protected",
+  "@version",
+  "@vocab",
+];
+
+/// The valid entries of an expanded term definition.
+const TERM_KEYWORDS: [&str; 11] = [
+  "@container",
+  "@context",
+  "@direction",
+  "@id",
+  "@index",
+  "@language",
+  "@nest",
+  "@prefix",
+  "@protected",
+  "@reverse",
+  "@type",
+];
+
+/// Returns `true` if `value` is a JSON-LD keyword.
+pub(crate) fn is_keyword(value: &str) -> bool {
+  KEYWORDS.contains(&value)
+}
+
+/// Returns `true` if `value` has the form of a keyword, i.e. `@` followed by ASCII letters only.
+pub(crate) fn is_keyword_like(value: &str) -> bool {
+  value.len() > 1 && value.starts_with('@') && value[1..].bytes().all(|byte| byte.is_ascii_alphabetic())
+}
+
+/// Returns `true` if `value` is a blank node identifier.
+pub(crate) fn is_blank_node(value: &str) -> bool {
+  value.starts_with("_:")
+}
+
+/// Returns `true` if `value` is an absolute IRI, i.e. starts with a scheme.
+pub(crate) fn is_absolute_iri(value: &str) -> bool {
+  match value.find(':') {
+    Some(index) if index > 0 => {
+      let scheme: &str = &value[..index];
+      scheme.starts_with(|char: char| char.is_ascii_alphabetic())
+        && scheme
+          .chars()
+          .all(|char| char.is_ascii_alphanumeric() || matches!(char, '+' | '-' | '.'))
+    }
+    _ => false,
+  }
+}
+
+/// Returns the index of the first colon of `value` after its first character.
+fn colon_index(value: &str) -> Option<usize> {
+  value
+    .char_indices()
+    .skip(1)
+    .find(|(_, char)| *char == ':')
+    .map(|(index, _)| index)
+}
+
+/// Returns `true` if `value` ends with a generic delimiter of RFC 3986.
+fn ends_with_gen_delim(value: &str) -> bool {
+  value.ends_with([':', '/', '?', '#', '[', ']', '@'])
+}
+
+/// The definition of a term of an [`ActiveContext`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct TermDefinition {
+  pub(crate) iri: Option<String>,
+  pub(crate) prefix: bool,
+  pub(crate) protected: bool,
+  pub(crate) reverse: bool,
+  pub(crate) type_mapping: Option<String>,
+  pub(crate) container: Vec<String>,
+  pub(crate) language: Option<Option<String>>,
+  pub(crate) index: Option<String>,
+  pub(crate) nest: Option<String>,
+  pub(crate) context: Option<Value>,
+}
+
+impl TermDefinition {
+  /// Returns `true` if the container mapping of the term includes `container`.
+  pub(crate) fn has_container(&self, container: &str) -> bool {
+    self.container.iter().any(|value| value == container)
+  }
+
+  /// Returns `true` if the definitions are equal, ignoring whether they are protected.
+  fn same_as(&self, other: &Self) -> bool {
+    self.iri == other.iri
+      && self.prefix == other.prefix
+      && self.reverse == other.reverse
+      && self.type_mapping == other.type_mapping
+      && self.container == other.container
+      && self.language == other.language
+      && self.index == other.index
+      && self.nest == other.nest
+      && self.context == other.context
+  }
+}
+
+/// The context used to resolve terms while expanding a JSON-LD document.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ActiveContext {
+  base: Option<String>,
+  vocab: Option<String>,
+  language: Option<String>,
+  terms: BTreeMap<String, TermDefinition>,
+  previous: Option<Box<ActiveContext>>,
+}
+
+impl ActiveContext {
+  /// Creates a new empty `ActiveContext`.
+  pub(crate) fn new() -> Self {
+    Self::default()
+  }
+
+  /// Returns the definition of `term`.
+  pub(crate) fn term(&self, term: &str) -> Option<&TermDefinition> {
+    self.terms.get(term)
+  }
+
+  /// Returns the context to revert to when leaving the node of a non-propagated context.
+  pub(crate) fn previous(&self) -> Option<&ActiveContext> {
+    self.previous.as_deref()
+  }
+
+  /// Returns the default language of strings.
+  pub(crate) fn language(&self) -> Option<&str> {
+    self.language.as_deref()
+  }
+
+  /// Expands `value` to an absolute IRI, a blank node identifier or a keyword.
+  ///
+  /// Returns `None` if `value` has the form of a keyword or is explicitly mapped to `null`.
+  ///
+  /// [More Info](https://www.w3.org/TR/json-ld11-api/#iri-expansion)
+  pub(crate) fn expand_iri(&self, value: &str, document_relative: bool, vocab: bool) -> Option<String> {
+    if is_keyword(value) {
+      return Some(value.to_owned());
+    }
+    if is_keyword_like(value) {
+      return None;
+    }
+
+    if let Some(definition) = self.terms.get(value) {
+      if let Some(iri) = definition.iri.as_deref().filter(|iri| is_keyword(iri)) {
+        return Some(iri.to_owned());
+      }
+      if vocab {
+        return definition.iri.clone();
+      }
+    }
+
+    if let Some(index) = colon_index(value) {
+      let (prefix, suffix): (&str, &str) = (&value[..index], &value[index + 1..]);
+
+      if prefix == "_" || suffix.starts_with("//") {
+        return Some(value.to_owned());
+      }
+      if let Some(definition) = self.terms.get(prefix).filter(|definition| definition.prefix) {
+        if let Some(iri) = definition.iri.as_deref() {
+          return Some(format!("{}{}", iri, suffix));
+        }
+      }
+      if is_absolute_iri(value) {
+        return Some(value.to_owned());
+      }
+    }
+
+    if vocab {
+      if let Some(mapping) = self.vocab.as_deref() {
+        return Some(format!("{}{}", mapping, value));
+      }
+    }
+
+    if document_relative {
+      if let Some(base) = self.base.as_deref() {
+        if let Ok(iri) = Url::parse(base).and_then(|base| base.join(value)) {
+          return Some(iri.into_string());
+        }
+      }
+    }
+
+    Some(value.to_owned())
+  }
+}
+
+/// Processes local contexts, resolving remote contexts with a [`DocumentLoader`].
+///
+/// [More Info](https://www.w3.org/TR/json-ld11-api/#context-processing-algorithm)
+pub(crate) struct ContextProcessor<'a> {
+  loader: &'a dyn DocumentLoader,
+}
+
+impl<'a> ContextProcessor<'a> {
+  /// Creates a new `ContextProcessor` resolving remote contexts with `loader`.
+  pub(crate) fn new(loader: &'a dyn DocumentLoader) -> Self {
+    Self { loader }
+  }
+
+  /// Returns the result of updating `active` with the `local` context.
+  ///
+  /// Scoped contexts of term definitions are stored as-is and only processed when applied.
+  pub(crate) fn process(
+    &self,
+    active: &ActiveContext,
+    local: &Value,
+    remote_contexts: &[String],
+    override_protected: bool,
+    propagate: bool,
+  ) -> Result<ActiveContext> {
+    let mut result: ActiveContext = active.clone();
+
+    let propagate: bool = match local.as_object().and_then(|local| local.get("@propagate")) {
+      Some(value) => value
+        .as_bool()
+        .ok_or(Error::InvalidJsonLd("invalid @propagate value"))?,
+      None => propagate,
+    };
+
+    if !propagate && result.previous.is_none() {
+      result.previous = Some(Box::new(active.clone()));
+    }
+
+    let contexts: &[Value] = match local {
+      Value::Array(contexts) => contexts,
+      context => core::slice::from_ref(context),
+    };
+
+    for context in contexts {
+      match context {
+        Value::Null => {
+          if !override_protected && result.terms.values().any(|definition| definition.protected) {
+            return Err(Error::InvalidJsonLd("invalid context nullification"));
+          }
+
+          let previous: ActiveContext = result;
+          result = ActiveContext::new();
+
+          if !propagate {
+            result.previous = Some(Box::new(previous));
+          }
+        }
+        Value::String(url) => {
+          if remote_contexts.contains(url) {
+            return Err(Error::InvalidJsonLd("recursive context inclusion"));
+          }
+          if remote_contexts.len() >= MAX_REMOTE_CONTEXTS {
+            return Err(Error::InvalidJsonLd("context overflow"));
+          }
+
+          let document: Value = self.loader.load_context(url)?;
+          let loaded: &Value = document
+            .as_object()
+            .and_then(|document| document.get("@context"))
+            .ok_or(Error::InvalidJsonLd("invalid remote context"))?;
+
+          let mut remote_contexts: Vec<String> = remote_contexts.to_vec();
+          remote_contexts.push(url.clone());
+
+          result = self.process(&result, loaded, &remote_contexts, false, true)?;
+        }
+        Value::Object(context) => {
+          self.process_object(&mut result, context, remote_contexts, override_protected)?;
+        }
+        _ => return Err(Error::InvalidJsonLd("invalid local context")),
+      }
+    }
+
+    Ok(result)
+  }
+
+  fn process_object(
+    &self,
+    result: &mut ActiveContext,
+    context: &Map<String, Value>,
+    remote_contexts: &[String],
+    override_protected: bool,
+  ) -> Result<()> {
+    let mut context: Map<String, Value> = context.clone();
+
+    if let Some(import) = context.remove("@import") {
+      let url: &str = import.as_str().ok_or(Error::InvalidJsonLd("invalid @import value"))?;
+      let document: Value = self.loader.load_context(url)?;
+      let mut imported: Map<String, Value> = document
+        .as_object()
+        .and_then(|document| document.get("@context"))
+        .and_then(Value::as_object)
+        .filter(|imported| !imported.contains_key("@import"))
+        .cloned()
+        .ok_or(Error::InvalidJsonLd("invalid remote context"))?;
+
+      imported.append(&mut context);
+      context = imported;
+    }
+
+    if let Some(version) = context.get("@version") {
+      if version.as_f64() != Some(1.1) {
+        return Err(Error::InvalidJsonLd("invalid @version value"));
+      }
+    }
+
+    if let Some(base) = context.get("@base").filter(|_| remote_contexts.is_empty()) {
+      result.base = match base {
+        Value::Null => None,
+        Value::String(base) if is_absolute_iri(base) => Some(base.clone()),
+        Value::String(base) => Some(
+          result
+            .base
+            .as_deref()
+            .and_then(|current| Url::parse(current).and_then(|current| current.join(base)).ok())
+            .ok_or(Error::InvalidJsonLd("invalid base IRI"))?
+            .into_string(),
+        ),
+        _ => return Err(Error::InvalidJsonLd("invalid base IRI")),
+      };
+    }
+
+    if let Some(vocab) = context.get("@vocab") {
+      result.vocab = match vocab {
+        Value::Null => None,
+        Value::String(vocab) => result.expand_iri(vocab, true, true),
+        _ => return Err(Error::InvalidJsonLd("invalid vocab mapping")),
+      };
+    }
+
+    if let Some(language) = context.get("@language") {
+      result.language = match language {
+        Value::Null => None,
+        Value::String(language) => Some(language.to_lowercase()),
+        _ => return Err(Error::InvalidJsonLd("invalid default language")),
+      };
+    }
+
+    if let Some(direction) = context.get("@direction") {
+      if !matches!(direction.as_str(), Some("ltr" | "rtl")) && !direction.is_null() {
+        return Err(Error::InvalidJsonLd("invalid base direction"));
+      }
+    }
+
+    if let Some(propagate) = context.get("@propagate") {
+      if !propagate.is_boolean() {
+        return Err(Error::InvalidJsonLd("invalid @propagate value"));
+      }
+    }
+
+    let protected: bool = match context.get("@protected") {
+      Some(protected) => protected
+        .as_bool()
+        .ok_or(Error::InvalidJsonLd("invalid @protected value"))?,
+      None => false,
+    };
+
+    let mut definer: TermDefiner<'_> = TermDefiner {
+      context: &context,
+      defined: BTreeMap::new(),
+      protected,
+      override_protected,
+    };
+
+    for term in context.keys() {
+      if !CONTEXT_KEYWORDS.contains(&term.as_str()) {
+        definer.define(result, term)?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// Creates the term definitions of a local context.
+///
+/// [More Info](https://www.w3.org/TR/json-ld11-api/#create-term-definition)
+struct TermDefiner<'a> {
+  context: &'a Map<String, Value>,
+  defined: BTreeMap<String, bool>,
+  protected: bool,
+  override_protected: bool,
+}
+
+impl<'a> TermDefiner<'a> {
+  fn define(&mut self, active: &mut ActiveContext, term: &str) -> Result<()> {
+    match self.defined.get(term) {
+      Some(true) => return Ok(()),
+      Some(false) => return Err(Error::InvalidJsonLd("cyclic IRI mapping")),
+      None => {}
+    }
+
+    if term.is_empty() {
+      return Err(Error::InvalidJsonLd("invalid term definition"));
+    }
+
+    self.defined.insert(term.to_owned(), false);
+
+    let context: &'a Map<String, Value> = self.context;
+    let value: &Value = context
+      .get(term)
+      .ok_or(Error::InvalidJsonLd("invalid term definition"))?;
+
+    if term == "@type" {
+      let valid: bool = value.as_object().map_or(false, |value| {
+        !value.is_empty()
+          && value
+            .iter()
+            .all(|(key, value)| (key == "@container" && value == "@set") || (key == "@protected" && value.is_boolean()))
+      });
+
+      if !valid {
+        return Err(Error::InvalidJsonLd("keyword redefinition"));
+      }
+    } else if is_keyword(term) {
+      return Err(Error::InvalidJsonLd("keyword redefinition"));
+    } else if is_keyword_like(term) {
+      self.defined.insert(term.to_owned(), true);
+      return Ok(());
+    }
+
+    let previous: Option<TermDefinition> = active.terms.remove(term);
+
+    let (value, simple_term): (Map<String, Value>, bool) = match value {
+      Value::Null => ([("@id".to_owned(), Value::Null)].into_iter().collect(), false),
+      Value::String(iri) => (
+        [("@id".to_owned(), Value::String(iri.clone()))].into_iter().collect(),
+        true,
+      ),
+      Value::Object(value) => (value.clone(), false),
+      _ => return Err(Error::InvalidJsonLd("invalid term definition")),
+    };
+
+    if value.keys().any(|key| !TERM_KEYWORDS.contains(&key.as_str())) {
+      return Err(Error::InvalidJsonLd("invalid term definition"));
+    }
+
+    let mut definition: TermDefinition = TermDefinition {
+      protected: match value.get("@protected") {
+        Some(protected) => protected
+          .as_bool()
+          .ok_or(Error::InvalidJsonLd("invalid @protected value"))?,
+        None => self.protected,
+      },
+      ..TermDefinition::default()
+    };
+
+    if let Some(type_) = value.get("@type") {
+      let type_: &str = type_.as_str().ok_or(Error::InvalidJsonLd("invalid type mapping"))?;
+      let type_: String = self
+        .expand_iri(active, type_, false, true)?
+        .filter(|type_| matches!(type_.as_str(), "@id" | "@json" | "@none" | "@vocab") || is_absolute_iri(type_))
+        .ok_or(Error::InvalidJsonLd("invalid type mapping"))?;
+
+      definition.type_mapping = Some(type_);
+    }
+
+    if let Some(reverse) = value.get("@reverse") {
+      if value.contains_key("@id") || value.contains_key("@nest") {
+        return Err(Error::InvalidJsonLd("invalid reverse property"));
+      }
+
+      let reverse: &str = reverse.as_str().ok_or(Error::InvalidJsonLd("invalid IRI mapping"))?;
+
+      if is_keyword_like(reverse) {
+        self.defined.insert(term.to_owned(), true);
+        return Ok(());
+      }
+
+      let iri: String = self
+        .expand_iri(active, reverse, false, true)?
+        .filter(|iri| is_absolute_iri(iri) || is_blank_node(iri))
+        .ok_or(Error::InvalidJsonLd("invalid IRI mapping"))?;
+
+      definition.iri = Some(iri);
+      definition.reverse = true;
+
+      match value.get("@container") {
+        None | Some(Value::Null) => {}
+        Some(Value::String(container)) if container == "@set" || container == "@index" => {
+          definition.container = vec![container.clone()];
+        }
+        Some(_) => return Err(Error::InvalidJsonLd("invalid reverse property")),
+      }
+
+      return self.finish(active, term, definition, previous);
+    }
+
+    match value.get("@id") {
+      Some(id) if id.as_str() != Some(term) => match id {
+        Value::Null => {}
+        Value::String(id) => {
+          if !is_keyword(id) && is_keyword_like(id) {
+            self.defined.insert(term.to_owned(), true);
+            return Ok(());
+          }
+
+          let iri: String = self
+            .expand_iri(active, id, false, true)?
+            .filter(|iri| is_keyword(iri) || is_absolute_iri(iri) || is_blank_node(iri))
+            .ok_or(Error::InvalidJsonLd("invalid IRI mapping"))?;
+
+          if iri == "@context" {
+            return Err(Error::InvalidJsonLd("invalid keyword alias"));
+          }
+
+          if colon_index(term).map_or(false, |index| index + 1 < term.len()) || term.contains('/') {
+            self.defined.insert(term.to_owned(), true);
+
+            if self.expand_iri(active, term, false, true)?.as_deref() != Some(iri.as_str()) {
+              return Err(Error::InvalidJsonLd("invalid IRI mapping"));
+            }
+          }
+
+          if !term.contains(':') && !term.contains('/') && simple_term {
+            definition.prefix = ends_with_gen_delim(&iri) || is_blank_node(&iri);
+          }
+
+          definition.iri = Some(iri);
+        }
+        _ => return Err(Error::InvalidJsonLd("invalid IRI mapping")),
+      },
+      _ => {
+        if let Some(index) = colon_index(term) {
+          let (prefix, suffix): (&str, &str) = (&term[..index], &term[index + 1..]);
+
+          if context.contains_key(prefix) {
+            self.define(active, prefix)?;
+          }
+
+          definition.iri = match active.terms.get(prefix).and_then(|prefix| prefix.iri.as_deref()) {
+            Some(iri) => Some(format!("{}{}", iri, suffix)),
+            None => Some(term.to_owned()),
+          };
+        } else if term.contains('/') {
+          let iri: String = active
+            .expand_iri(term, false, true)
+            .filter(|iri| is_absolute_iri(iri))
+            .ok_or(Error::InvalidJsonLd("invalid IRI mapping"))?;
+
+          definition.iri = Some(iri);
+        } else if term == "@type" {
+          definition.iri = Some(term.to_owned());
+        } else if let Some(vocab) = active.vocab.as_deref() {
+          definition.iri = Some(format!("{}{}", vocab, term));
+        } else {
+          return Err(Error::InvalidJsonLd("invalid IRI mapping"));
+        }
+      }
+    }
+
+    if let Some(container) = value.get("@container") {
+      let mut container: Vec<String> = match container {
+        Value::String(container) => vec![container.clone()],
+        Value::Array(containers) => containers
+          .iter()
+          .map(|container| container.as_str().map(ToOwned::to_owned))
+          .collect::<Option<Vec<String>>>()
+          .ok_or(Error::InvalidJsonLd("invalid container mapping"))?,
+        _ => return Err(Error::InvalidJsonLd("invalid container mapping")),
+      };
+
+      container.sort_unstable();
+
+      let valid: bool = container.iter().all(|value| CONTAINERS.contains(&value.as_str()))
+        && (!container.iter().any(|value| value == "@list") || container.len() == 1);
+
+      if !valid {
+        return Err(Error::InvalidJsonLd("invalid container mapping"));
+      }
+
+      definition.container = container;
+
+      if definition.has_container("@type") {
+        match definition.type_mapping.as_deref() {
+          None => definition.type_mapping = Some("@id".to_owned()),
+          Some("@id" | "@vocab") => {}
+          Some(_) => return Err(Error::InvalidJsonLd("invalid type mapping")),
+        }
+      }
+    }
+
+    if let Some(index) = value.get("@index") {
+      if !definition.has_container("@index") {
+        return Err(Error::InvalidJsonLd("invalid term definition"));
+      }
+
+      let index: &str = index
+        .as_str()
+        .filter(|index| !index.starts_with('@'))
+        .ok_or(Error::InvalidJsonLd("invalid term definition"))?;
+
+      definition.index = Some(index.to_owned());
+    }
+
+    if let Some(context) = value.get("@context") {
+      definition.context = Some(context.clone());
+    }
+
+    if let Some(language) = value.get("@language").filter(|_| !value.contains_key("@type")) {
+      definition.language = match language {
+        Value::Null => Some(None),
+        Value::String(language) => Some(Some(language.to_lowercase())),
+        _ => return Err(Error::InvalidJsonLd("invalid language mapping")),
+      };
+    }
+
+    if let Some(direction) = value.get("@direction") {
+      if !matches!(direction.as_str(), Some("ltr" | "rtl")) && !direction.is_null() {
+        return Err(Error::InvalidJsonLd("invalid base direction"));
+      }
+    }
+
+    if let Some(nest) = value.get("@nest") {
+      let nest: &str = nest
+        .as_str()
+        .filter(|nest| !is_keyword(nest) || *nest == "@nest")
+        .ok_or(Error::InvalidJsonLd("invalid @nest value"))?;
+
+      definition.nest = Some(nest.to_owned());
+    }
+
+    if let Some(prefix) = value.get("@prefix") {
+      if term.contains(':') || term.contains('/') {
+        return Err(Error::InvalidJsonLd("invalid term definition"));
+      }
+
+      definition.prefix = prefix.as_bool().ok_or(Error::InvalidJsonLd("invalid @prefix value"))?;
+
+      if definition.prefix && definition.iri.as_deref().map_or(false, is_keyword) {
+        return Err(Error::InvalidJsonLd("invalid term definition"));
+      }
+    }
+
+    self.finish(active, term, definition, previous)
+  }
+
+  /// Adds the `definition` of `term` to the `active` context, unless it redefines a protected term.
+  fn finish(
+    &mut self,
+    active: &mut ActiveContext,
+    term: &str,
+    definition: TermDefinition,
+    previous: Option<TermDefinition>,
+  ) -> Result<()> {
+    let definition: TermDefinition = match previous {
+      Some(previous) if previous.protected && !self.override_protected => {
+        if !previous.same_as(&definition) {
+          return Err(Error::InvalidJsonLd("protected term redefinition"));
+        }
+        previous
+      }
+      _ => definition,
+    };
+
+    active.terms.insert(term.to_owned(), definition);
+    self.defined.insert(term.to_owned(), true);
+
+    Ok(())
+  }
+
+  /// Expands `value` with the `active` context, first defining the terms of the local context it
+  /// depends on.
+  fn expand_iri(
+    &mut self,
+    active: &mut ActiveContext,
+    value: &str,
+    document_relative: bool,
+    vocab: bool,
+  ) -> Result<Option<String>> {
+    if is_keyword(value) {
+      return Ok(Some(value.to_owned()));
+    }
+    if is_keyword_like(value) {
+      return Ok(None);
+    }
+
+    if self.context.contains_key(value) && self.defined.get(value) != Some(&true) {
+      self.define(active, value)?;
+    }
+
+    if let Some(index) = colon_index(value) {
+      let prefix: &str = &value[..index];
+
+      if prefix != "_"
+        && !value[index + 1..].starts_with("//")
+        && self.context.contains_key(prefix)
+        && self.defined.get(prefix) != Some(&true)
+      {
+        self.define(active, prefix)?;
+      }
+    }
+
+    Ok(active.expand_iri(value, document_relative, vocab))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::json;
+  use crate::jsonld::ContextCache;
+
+  fn process(local: Value) -> Result<ActiveContext> {
+    let cache: ContextCache = ContextCache::new();
+    ContextProcessor::new(&cache).process(&ActiveContext::new(), &local, &[], false, true)
+  }
+
+  #[test]
+  fn test_expand_iri() {
+    let context: ActiveContext = process(json!({
+      "@vocab": "https://example.com/vocab#",
+      "ex": "https://example.com/",
+      "name": "https://schema.org/name",
+      "id": "@id",
+    }))
+    .unwrap();
+
+    assert_eq!(context.expand_iri("id", false, true).unwrap(), "@id");
+    assert_eq!(
+      context.expand_iri("name", false, true).unwrap(),
+      "https://schema.org/name"
+    );
+    assert_eq!(
+      context.expand_iri("ex:foo", false, true).unwrap(),
+      "https://example.com/foo"
+    );
+    assert_eq!(
+      context.expand_iri("foo", false, true).unwrap(),
+      "https://example.com/vocab#foo"
+    );
+    assert_eq!(context.expand_iri("foo", true, false).unwrap(), "foo");
+    assert_eq!(
+      context.expand_iri("did:example:123", false, true).unwrap(),
+      "did:example:123"
+    );
+    assert_eq!(context.expand_iri("_:b0", false, true).unwrap(), "_:b0");
+    assert!(context.expand_iri("@unknown", false, true).is_none());
+  }
+
+  #[test]
+  fn test_protected_term_redefinition() {
+    let result: Result<ActiveContext> = process(json!([
+      "https://www.w3.org/2018/credentials/v1",
+      { "VerifiableCredential": "https://example.com/Other" },
+    ]));
+    assert!(matches!(
+      result,
+      Err(Error::InvalidJsonLd("protected term redefinition"))
+    ));
+
+    // Identical redefinitions of protected terms are allowed.
+    let result: Result<ActiveContext> = process(json!([
+      "https://www.w3.org/2018/credentials/v1",
+      "https://w3id.org/security/suites/ed25519-2020/v1",
+    ]));
+    assert!(result.is_ok());
+  }
+
+  #[test]
+  fn test_remote_context_not_found() {
+    let result: Result<ActiveContext> = process(json!("https://example.com/unknown/v1"));
+    assert!(matches!(result, Err(Error::JsonLdContextNotFound(url)) if url == "https://example.com/unknown/v1"));
+  }
+}
@@ -0,0 +1,624 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::borrow::Cow;
+
+use crate::common::Value;
+use crate::error::Error;
+use crate::error::Result;
+use crate::jsonld::context::is_absolute_iri;
+use crate::jsonld::context::is_blank_node;
+use crate::jsonld::context::is_keyword;
+use crate::jsonld::context::ActiveContext;
+use crate::jsonld::context::ContextProcessor;
+use crate::jsonld::context::TermDefinition;
+use serde_json::Map;
+
+/// Converts `value` into an array, if necessary.
+pub(crate) fn into_array(value: Value) -> Vec<Value> {
+  match value {
+    Value::Null => Vec::new(),
+    Value::Array(values) => values,
+    value => vec![value],
+  }
+}
+
+/// Appends `value`, or each item of an array `value`, to the array entry `key` of `object`.
+pub(crate) fn add_value(object: &mut Map<String, Value>, key: &str, value: Value) {
+  let entry: &mut Value = object.entry(key.to_owned()).or_insert_with(|| Value::Array(Vec::new()));
+
+  if !entry.is_array() {
+    *entry = Value::Array(vec![entry.take()]);
+  }
+
+  if let Value::Array(values) = entry {
+    values.extend(into_array(value));
+  }
+}
+
+fn single(key: &str, value: Value) -> Value {
+  Value::Object([(key.to_owned(), value)].into_iter().collect())
+}
+
+fn is_list_object(value: &Value) -> bool {
+  value.as_object().map_or(false, |object| object.contains_key("@list"))
+}
+
+fn is_value_object(value: &Value) -> bool {
+  value.as_object().map_or(false, |object| object.contains_key("@value"))
+}
+
+fn is_graph_object(value: &Value) -> bool {
+  value.as_object().map_or(false, |object| {
+    object.contains_key("@graph")
+      && object
+        .keys()
+        .all(|key| matches!(key.as_str(), "@graph" | "@id" | "@index" | "@context"))
+  })
+}
+
+/// Expands JSON-LD documents, removing their contexts.
+///
+/// In safe mode, terms that do not expand to an absolute IRI and relative IRIs are rejected
+/// instead of silently dropped.
+///
+/// [More Info](https://www.w3.org/TR/json-ld11-api/#expansion-algorithm)
+pub(crate) struct Expander<'a> {
+  processor: ContextProcessor<'a>,
+  safe_mode: bool,
+}
+
+impl<'a> Expander<'a> {
+  /// Creates a new `Expander`.
+  pub(crate) fn new(processor: ContextProcessor<'a>, safe_mode: bool) -> Self {
+    Self { processor, safe_mode }
+  }
+
+  /// Returns the expanded form of `document`.
+  pub(crate) fn expand_document(&self, document: &Value) -> Result<Vec<Value>> {
+    let expanded: Value = self.expand(&ActiveContext::new(), None, document, false)?;
+
+    match expanded {
+      Value::Object(mut object) if object.len() == 1 && object.contains_key("@graph") => {
+        Ok(into_array(object.remove("@graph").unwrap_or_default()))
+      }
+      expanded => Ok(into_array(expanded)),
+    }
+  }
+
+  fn expand(&self, active: &ActiveContext, property: Option<&str>, element: &Value, from_map: bool) -> Result<Value> {
+    match element {
+      Value::Null => Ok(Value::Null),
+      Value::Array(items) => {
+        let is_list: bool = property
+          .and_then(|property| active.term(property))
+          .map_or(false, |definition| definition.has_container("@list"));
+
+        let mut result: Vec<Value> = Vec::new();
+
+        for item in items {
+          match self.expand(active, property, item, from_map)? {
+            Value::Array(values) if is_list => result.push(single("@list", Value::Array(values))),
+            Value::Array(values) => result.extend(values),
+            Value::Null => {}
+            value => result.push(value),
+          }
+        }
+
+        Ok(Value::Array(result))
+      }
+      Value::Object(object) => self.expand_object(active, property, object, from_map),
+      scalar => match property {
+        None | Some("@graph") => Ok(Value::Null),
+        Some(property) => match active.term(property).and_then(|definition| definition.context.as_ref()) {
+          Some(context) => {
+            let active: ActiveContext = self.processor.process(active, context, &[], true, true)?;
+            self.expand_value(&active, property, scalar)
+          }
+          None => self.expand_value(active, property, scalar),
+        },
+      },
+    }
+  }
+
+  fn expand_object(
+    &self,
+    active_context: &ActiveContext,
+    property: Option<&str>,
+    element: &Map<String, Value>,
+    from_map: bool,
+  ) -> Result<Value> {
+    let property_context: Option<&Value> = property
+      .and_then(|property| active_context.term(property))
+      .and_then(|definition| definition.context.as_ref());
+
+    let mut active: Cow<'_, ActiveContext> = Cow::Borrowed(active_context);
+
+    if !from_map && !is_value_or_reference(active_context, element) {
+      if let Some(previous) = active_context.previous() {
+        active = Cow::Borrowed(previous);
+      }
+    }
+
+    if let Some(context) = property_context {
+      active = Cow::Owned(self.processor.process(&active, context, &[], true, true)?);
+    }
+
+    if let Some(context) = element.get("@context") {
+      active = Cow::Owned(self.processor.process(&active, context, &[], false, true)?);
+    }
+
+    let type_scoped: Cow<'_, ActiveContext> = active.clone();
+    let mut input_type: Option<String> = None;
+
+    for (key, value) in element {
+      if type_scoped.expand_iri(key, false, true).as_deref() != Some("@type") {
+        continue;
+      }
+
+      let mut terms: Vec<&str> = match value {
+        Value::Array(values) => values.iter().filter_map(Value::as_str).collect(),
+        value => value.as_str().into_iter().collect(),
+      };
+
+      if input_type.is_none() {
+        input_type = terms.last().and_then(|term| type_scoped.expand_iri(term, true, true));
+      }
+
+      terms.sort_unstable();
+
+      for term in terms {
+        if let Some(context) = type_scoped
+          .term(term)
+          .and_then(|definition| definition.context.as_ref())
+        {
+          active = Cow::Owned(self.processor.process(&active, context, &[], false, false)?);
+        }
+      }
+    }
+
+    let mut result: Map<String, Value> = Map::new();
+
+    self.expand_entries(
+      &active,
+      &type_scoped,
+      property,
+      element,
+      &mut result,
+      input_type.as_deref(),
+    )?;
+
+    if let Some(value) = result.get("@value") {
+      let valid_keys: bool = result
+        .keys()
+        .all(|key| matches!(key.as_str(), "@direction" | "@index" | "@language" | "@type" | "@value"));
+
+      if !valid_keys
+        || (result.contains_key("@type") && (result.contains_key("@language") || result.contains_key("@direction")))
+      {
+        return Err(Error::InvalidJsonLd("invalid value object"));
+      }
+
+      if result.get("@type").and_then(Value::as_str) != Some("@json") {
+        if value.is_null() {
+          return Ok(Value::Null);
+        }
+        if !value.is_string() && result.contains_key("@language") {
+          return Err(Error::InvalidJsonLd("invalid language-tagged value"));
+        }
+        if let Some(type_) = result.get("@type") {
+          if !type_.as_str().map_or(false, is_absolute_iri) {
+            return Err(Error::InvalidJsonLd("invalid typed value"));
+          }
+        }
+      }
+    } else if let Some(types) = result.get_mut("@type") {
+      if !types.is_array() {
+        *types = Value::Array(vec![types.take()]);
+      }
+    } else if result.contains_key("@set") || result.contains_key("@list") {
+      if result.len() > 2 || (result.len() == 2 && !result.contains_key("@index")) {
+        return Err(Error::InvalidJsonLd("invalid set or list object"));
+      }
+      if let Some(set) = result.remove("@set") {
+        return Ok(set);
+      }
+    }
+
+    if result.len() == 1 && result.contains_key("@language") {
+      return Ok(Value::Null);
+    }
+
+    if matches!(property, None | Some("@graph"))
+      && (result.is_empty()
+        || result.contains_key("@value")
+        || result.contains_key("@list")
+        || (result.len() == 1 && result.contains_key("@id")))
+    {
+      return Ok(Value::Null);
+    }
+
+    Ok(Value::Object(result))
+  }
+
+  fn expand_entries(
+    &self,
+    active: &ActiveContext,
+    type_scoped: &ActiveContext,
+    property: Option<&str>,
+    element: &Map<String, Value>,
+    result: &mut Map<String, Value>,
+    input_type: Option<&str>,
+  ) -> Result<()> {
+    let mut nests: Vec<&Value> = Vec::new();
+
+    for (key, value) in element {
+      if key == "@context" {
+        continue;
+      }
+
+      let expanded_property: String = match active.expand_iri(key, false, true) {
+        Some(expanded) if is_keyword(&expanded) || expanded.contains(':') => expanded,
+        _ if self.safe_mode => return Err(Error::JsonLdUndefinedTerm(key.clone())),
+        _ => continue,
+      };
+
+      if is_keyword(&expanded_property) {
+        if property == Some("@reverse") {
+          return Err(Error::InvalidJsonLd("invalid reverse property map"));
+        }
+        if result.contains_key(&expanded_property) && !matches!(expanded_property.as_str(), "@included" | "@type") {
+          return Err(Error::InvalidJsonLd("colliding keywords"));
+        }
+
+        let expanded_value: Value = match expanded_property.as_str() {
+          "@id" => {
+            let id: &str = value.as_str().ok_or(Error::InvalidJsonLd("invalid @id value"))?;
+            Value::String(self.expand_id(active, id)?)
+          }
+          "@type" => {
+            let types: Vec<Value> = match value {
+              Value::String(type_) => vec![Value::String(self.expand_type(type_scoped, type_)?)],
+              Value::Array(types) => types
+                .iter()
+                .map(|type_| match type_ {
+                  Value::String(type_) => self.expand_type(type_scoped, type_).map(Value::String),
+                  _ => Err(Error::InvalidJsonLd("invalid type value")),
+                })
+                .collect::<Result<Vec<Value>>>()?,
+              _ => return Err(Error::InvalidJsonLd("invalid type value")),
+            };
+
+            match result.remove("@type") {
+              Some(existing) => {
+                let mut values: Vec<Value> = into_array(existing);
+                values.extend(types);
+                Value::Array(values)
+              }
+              None if value.is_string() => types.into_iter().next().unwrap_or_default(),
+              None => Value::Array(types),
+            }
+          }
+          "@graph" => Value::Array(into_array(self.expand(active, Some("@graph"), value, false)?)),
+          "@included" => {
+            let included: Vec<Value> = into_array(self.expand(active, None, value, false)?);
+
+            if included
+              .iter()
+              .any(|node| is_value_object(node) || is_list_object(node))
+            {
+              return Err(Error::InvalidJsonLd("invalid @included value"));
+            }
+
+            let mut values: Vec<Value> = result.remove("@included").map(into_array).unwrap_or_default();
+            values.extend(included);
+            Value::Array(values)
+          }
+          "@value" => {
+            if input_type != Some("@json") && (value.is_object() || value.is_array()) {
+              return Err(Error::InvalidJsonLd("invalid value object value"));
+            }
+            result.insert(expanded_property, value.clone());
+            continue;
+          }
+          "@language" => Value::String(
+            value
+              .as_str()
+              .ok_or(Error::InvalidJsonLd("invalid language-tagged string"))?
+              .to_lowercase(),
+          ),
+          "@direction" => match value.as_str() {
+            Some("ltr" | "rtl") => value.clone(),
+            _ => return Err(Error::InvalidJsonLd("invalid base direction")),
+          },
+          "@index" => match value {
+            Value::String(_) => value.clone(),
+            _ => return Err(Error::InvalidJsonLd("invalid @index value")),
+          },
+          "@list" => {
+            if matches!(property, None | Some("@graph")) {
+              continue;
+            }
+            Value::Array(into_array(self.expand(active, property, value, false)?))
+          }
+          "@set" => self.expand(active, property, value, false)?,
+          "@reverse" => {
+            if !value.is_object() {
+              return Err(Error::InvalidJsonLd("invalid @reverse value"));
+            }
+            self.expand_reverse(active, value, result)?;
+            continue;
+          }
+          "@nest" => {
+            nests.push(value);
+            continue;
+          }
+          _ => continue,
+        };
+
+        if !expanded_value.is_null() {
+          result.insert(expanded_property, expanded_value);
+        }
+        continue;
+      }
+
+      let definition: Option<&TermDefinition> = active.term(key);
+      let has_container = |container: &str| definition.map_or(false, |definition| definition.has_container(container));
+
+      let mut expanded_value: Value = match value {
+        _ if definition.and_then(|definition| definition.type_mapping.as_deref()) == Some("@json") => {
+          let mut object: Map<String, Value> = Map::new();
+          object.insert("@value".to_owned(), value.clone());
+          object.insert("@type".to_owned(), Value::String("@json".to_owned()));
+          Value::Object(object)
+        }
+        Value::Object(map) if has_container("@language") => self.expand_language_map(active, map)?,
+        Value::Object(map) if has_container("@index") || has_container("@type") || has_container("@id") => {
+          self.expand_index_map(active, key, definition, map)?
+        }
+        _ => self.expand(active, Some(key), value, false)?,
+      };
+
+      if expanded_value.is_null() {
+        continue;
+      }
+
+      if has_container("@list") && !is_list_object(&expanded_value) {
+        expanded_value = single("@list", Value::Array(into_array(expanded_value)));
+      }
+
+      if has_container("@graph") && !has_container("@id") && !has_container("@index") {
+        expanded_value = Value::Array(
+          into_array(expanded_value)
+            .into_iter()
+            .map(|value| single("@graph", Value::Array(into_array(value))))
+            .collect(),
+        );
+      }
+
+      if definition.map_or(false, |definition| definition.reverse) {
+        let reverse: &mut Value = result
+          .entry("@reverse".to_owned())
+          .or_insert_with(|| Value::Object(Map::new()));
+
+        if let Value::Object(reverse) = reverse {
+          for item in into_array(expanded_value) {
+            if is_value_object(&item) || is_list_object(&item) {
+              return Err(Error::InvalidJsonLd("invalid reverse property value"));
+            }
+            add_value(reverse, &expanded_property, item);
+          }
+        }
+      } else {
+        add_value(result, &expanded_property, expanded_value);
+      }
+    }
+
+    for nested in nests.into_iter().flat_map(|value| into_array(value.clone())) {
+      let nested: &Map<String, Value> = nested
+        .as_object()
+        .filter(|nested| {
+          !nested
+            .keys()
+            .any(|key| active.expand_iri(key, false, true).as_deref() == Some("@value"))
+        })
+        .ok_or(Error::InvalidJsonLd("invalid @nest value"))?;
+
+      self.expand_entries(active, type_scoped, property, nested, result, input_type)?;
+    }
+
+    Ok(())
+  }
+
+  fn expand_reverse(&self, active: &ActiveContext, value: &Value, result: &mut Map<String, Value>) -> Result<()> {
+    let mut expanded: Map<String, Value> = match self.expand(active, Some("@reverse"), value, false)? {
+      Value::Object(expanded) => expanded,
+      _ => return Ok(()),
+    };
+
+    if let Some(Value::Object(reverse)) = expanded.remove("@reverse") {
+      for (property, item) in reverse {
+        add_value(result, &property, item);
+      }
+    }
+
+    if expanded.is_empty() {
+      return Ok(());
+    }
+
+    let reverse: &mut Value = result
+      .entry("@reverse".to_owned())
+      .or_insert_with(|| Value::Object(Map::new()));
+
+    if let Value::Object(reverse) = reverse {
+      for (property, items) in expanded {
+        for item in into_array(items) {
+          if is_value_object(&item) || is_list_object(&item) {
+            return Err(Error::InvalidJsonLd("invalid reverse property value"));
+          }
+          add_value(reverse, &property, item);
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  fn expand_language_map(&self, active: &ActiveContext, map: &Map<String, Value>) -> Result<Value> {
+    let mut values: Vec<Value> = Vec::new();
+
+    for (language, items) in map {
+      let expanded: Option<String> = active.expand_iri(language, false, true);
+
+      for item in into_array(items.clone()) {
+        let item: String = match item {
+          Value::String(item) => item,
+          _ => return Err(Error::InvalidJsonLd("invalid language map value")),
+        };
+
+        let mut value: Map<String, Value> = Map::new();
+        value.insert("@value".to_owned(), Value::String(item));
+
+        if expanded.as_deref() != Some("@none") {
+          value.insert("@language".to_owned(), Value::String(language.to_lowercase()));
+        }
+
+        values.push(Value::Object(value));
+      }
+    }
+
+    Ok(Value::Array(values))
+  }
+
+  fn expand_index_map(
+    &self,
+    active: &ActiveContext,
+    key: &str,
+    definition: Option<&TermDefinition>,
+    map: &Map<String, Value>,
+  ) -> Result<Value> {
+    let has_container = |container: &str| definition.map_or(false, |definition| definition.has_container(container));
+    let mut values: Vec<Value> = Vec::new();
+
+    for (index, items) in map {
+      let mut map_context: Cow<'_, ActiveContext> = Cow::Borrowed(active);
+
+      if has_container("@id") || has_container("@type") {
+        map_context = Cow::Borrowed(active.previous().unwrap_or(active));
+      }
+
+      if has_container("@type") {
+        let context: Option<Value> = map_context
+          .term(index)
+          .and_then(|definition| definition.context.clone());
+
+        if let Some(context) = context {
+          map_context = Cow::Owned(self.processor.process(&map_context, &context, &[], false, true)?);
+        }
+      }
+
+      let expanded_index: Option<String> = active.expand_iri(index, false, true);
+      let items: Value = Value::Array(into_array(items.clone()));
+
+      for mut item in into_array(self.expand(&map_context, Some(key), &items, true)?) {
+        if has_container("@graph") && !is_graph_object(&item) {
+          item = single("@graph", Value::Array(vec![item]));
+        }
+
+        let expanded_index: Option<&str> = expanded_index.as_deref().filter(|index| *index != "@none");
+
+        if let (Value::Object(object), Some(expanded_index)) = (&mut item, expanded_index) {
+          if has_container("@index") {
+            object
+              .entry("@index".to_owned())
+              .or_insert_with(|| Value::String(index.clone()));
+          } else if has_container("@id") {
+            if !object.contains_key("@id") {
+              object.insert("@id".to_owned(), Value::String(self.expand_id(active, index)?));
+            }
+          } else if has_container("@type") {
+            let mut types: Vec<Value> = vec![Value::String(expanded_index.to_owned())];
+            types.extend(object.remove("@type").map(into_array).unwrap_or_default());
+            object.insert("@type".to_owned(), Value::Array(types));
+          }
+        }
+
+        values.push(item);
+      }
+    }
+
+    Ok(Value::Array(values))
+  }
+
+  /// Expands the scalar `value` of `property` into a node reference or value object.
+  fn expand_value(&self, active: &ActiveContext, property: &str, value: &Value) -> Result<Value> {
+    let definition: Option<&TermDefinition> = active.term(property);
+    let type_mapping: Option<&str> = definition.and_then(|definition| definition.type_mapping.as_deref());
+
+    match (type_mapping, value) {
+      (Some("@id"), Value::String(id)) => return Ok(single("@id", Value::String(self.expand_id(active, id)?))),
+      (Some("@vocab"), Value::String(id)) => {
+        let iri: String = active.expand_iri(id, true, true).unwrap_or_else(|| id.clone());
+
+        if self.safe_mode && !is_absolute_iri(&iri) && !is_blank_node(&iri) {
+          return Err(Error::JsonLdUndefinedTerm(id.clone()));
+        }
+
+        return Ok(single("@id", Value::String(iri)));
+      }
+      _ => {}
+    }
+
+    let mut result: Map<String, Value> = Map::new();
+    result.insert("@value".to_owned(), value.clone());
+
+    match type_mapping {
+      Some(type_) if !matches!(type_, "@id" | "@vocab" | "@none") => {
+        result.insert("@type".to_owned(), Value::String(type_.to_owned()));
+      }
+      _ if value.is_string() => {
+        let language: Option<&str> = match definition.and_then(|definition| definition.language.as_ref()) {
+          Some(language) => language.as_deref(),
+          None => active.language(),
+        };
+
+        if let Some(language) = language {
+          result.insert("@language".to_owned(), Value::String(language.to_owned()));
+        }
+      }
+      _ => {}
+    }
+
+    Ok(Value::Object(result))
+  }
+
+  /// Expands the node identifier `id`, rejecting relative IRIs in safe mode.
+  fn expand_id(&self, active: &ActiveContext, id: &str) -> Result<String> {
+    let iri: String = active.expand_iri(id, true, false).unwrap_or_else(|| id.to_owned());
+
+    if self.safe_mode && !is_absolute_iri(&iri) && !is_blank_node(&iri) {
+      return Err(Error::InvalidJsonLd("relative IRI"));
+    }
+
+    Ok(iri)
+  }
+
+  /// Expands the node type `type_`, rejecting undefined terms in safe mode.
+  fn expand_type(&self, active: &ActiveContext, type_: &str) -> Result<String> {
+    let iri: String = active.expand_iri(type_, true, true).unwrap_or_else(|| type_.to_owned());
+
+    if self.safe_mode && !is_absolute_iri(&iri) && !is_blank_node(&iri) && !is_keyword(&iri) {
+      return Err(Error::JsonLdUndefinedTerm(type_.to_owned()));
+    }
+
+    Ok(iri)
+  }
+}
+
+/// Returns `true` if `element` is a value object or a node reference, which do not revert
+/// non-propagated contexts.
+fn is_value_or_reference(active: &ActiveContext, element: &Map<String, Value>) -> bool {
+  let keys: Vec<Option<String>> = element.keys().map(|key| active.expand_iri(key, false, true)).collect();
+
+  keys.iter().any(|key| key.as_deref() == Some("@value")) || (keys.len() == 1 && keys[0].as_deref() == Some("@id"))
+}
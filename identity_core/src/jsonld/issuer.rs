@@ -0,0 +1,61 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+/// Issues blank node identifiers with a common prefix, in order.
+///
+/// [More Info](https://www.w3.org/TR/rdf-canon/#issue-identifier)
+#[derive(Clone, Debug)]
+pub(crate) struct IdentifierIssuer {
+  prefix: &'static str,
+  counter: usize,
+  issued: BTreeMap<String, String>,
+  order: Vec<String>,
+}
+
+impl IdentifierIssuer {
+  /// Creates a new `IdentifierIssuer` issuing identifiers starting with `prefix`.
+  pub(crate) fn new(prefix: &'static str) -> Self {
+    Self {
+      prefix,
+      counter: 0,
+      issued: BTreeMap::new(),
+      order: Vec::new(),
+    }
+  }
+
+  /// Returns the identifier issued for `existing`, issuing a new one if necessary.
+  pub(crate) fn issue(&mut self, existing: &str) -> String {
+    if let Some(issued) = self.issued.get(existing) {
+      return issued.clone();
+    }
+
+    let issued: String = self.next();
+    self.issued.insert(existing.to_owned(), issued.clone());
+    self.order.push(existing.to_owned());
+    issued
+  }
+
+  /// Returns a new identifier that is not associated with an existing identifier.
+  pub(crate) fn next(&mut self) -> String {
+    let issued: String = format!("{}{}", self.prefix, self.counter);
+    self.counter += 1;
+    issued
+  }
+
+  /// Returns the identifier issued for `existing`, if any.
+  pub(crate) fn get(&self, existing: &str) -> Option<&str> {
+    self.issued.get(existing).map(String::as_str)
+  }
+
+  /// Returns `true` if an identifier was issued for `existing`.
+  pub(crate) fn has(&self, existing: &str) -> bool {
+    self.issued.contains_key(existing)
+  }
+
+  /// Returns the existing identifiers in the order their identifiers were issued.
+  pub(crate) fn issued(&self) -> impl Iterator<Item = &str> + '_ {
+    self.order.iter().map(String::as_str)
+  }
+}
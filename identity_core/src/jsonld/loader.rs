@@ -0,0 +1,188 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+use std::sync::RwLockReadGuard;
+use std::sync::RwLockWriteGuard;
+
+use crate::common::Value;
+use crate::convert::FromJson;
+use crate::error::Error;
+use crate::error::Result;
+
+/// The contexts of a [`ContextCache::new`], served without network access.
+///
+/// The examples v1 context omits the import of the ODRL context, whose terms are therefore undefined.
+const PRELOADED_CONTEXTS: [(&str, &str); 9] = [
+  (
+    "https://www.w3.org/2018/credentials/v1",
+    include_str!("contexts/credentials_v1.jsonld"),
+  ),
+  (
+    "https://www.w3.org/2018/credentials/examples/v1",
+    include_str!("contexts/credentials_examples_v1.jsonld"),
+  ),
+  (
+    "https://www.w3.org/ns/credentials/v2",
+    include_str!("contexts/credentials_v2.jsonld"),
+  ),
+  (
+    "https://www.w3.org/ns/credentials/examples/v2",
+    include_str!("contexts/credentials_examples_v2.jsonld"),
+  ),
+  ("https://www.w3.org/ns/did/v1", include_str!("contexts/did_v1.jsonld")),
+  (
+    "https://w3id.org/security/v1",
+    include_str!("contexts/security_v1.jsonld"),
+  ),
+  (
+    "https://w3id.org/security/v2",
+    include_str!("contexts/security_v2.jsonld"),
+  ),
+  (
+    "https://w3id.org/security/suites/ed25519-2020/v1",
+    include_str!("contexts/ed25519_2020_v1.jsonld"),
+  ),
+  (
+    "https://w3id.org/security/data-integrity/v2",
+    include_str!("contexts/data_integrity_v2.jsonld"),
+  ),
+];
+
+/// Resolves the URLs of remote JSON-LD contexts to context documents.
+pub trait DocumentLoader: Send + Sync {
+  /// Returns the document of the remote context `url`, i.e. a JSON object with an `@context` entry.
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`Error::JsonLdContextNotFound`] if the context cannot be resolved.
+  fn load_context(&self, url: &str) -> Result<Value>;
+}
+
+/// An offline [`DocumentLoader`] serving JSON-LD contexts from memory.
+///
+/// Contexts are never fetched from the network: documents referencing a context that was not
+/// registered fail to load with [`Error::JsonLdContextNotFound`].
+#[derive(Debug)]
+pub struct ContextCache {
+  contexts: RwLock<BTreeMap<String, Value>>,
+}
+
+impl ContextCache {
+  /// Creates a new `ContextCache` preloaded with the Verifiable Credentials v1 and v2 and their
+  /// examples, DID v1, security v1 and v2, Ed25519Signature2020 and Data Integrity v2 contexts.
+  pub fn new() -> Self {
+    let cache: Self = Self::empty();
+
+    for (url, document) in PRELOADED_CONTEXTS {
+      let document: Value = Value::from_json(document).expect("invalid preloaded context");
+      cache.write().insert(url.to_owned(), document);
+    }
+
+    cache
+  }
+
+  /// Creates a new `ContextCache` without any contexts.
+  pub fn empty() -> Self {
+    Self {
+      contexts: RwLock::new(BTreeMap::new()),
+    }
+  }
+
+  /// Registers the context `document` under `url`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `document` is not a context document or a different context is already registered
+  /// under `url`.
+  pub fn register(&self, url: impl Into<String>, document: Value) -> Result<()> {
+    let is_context: bool = document
+      .as_object()
+      .and_then(|document| document.get("@context"))
+      .map_or(false, |context| {
+        context.is_object() || context.is_array() || context.is_string()
+      });
+
+    if !is_context {
+      return Err(Error::InvalidJsonLd("invalid remote context"));
+    }
+
+    let url: String = url.into();
+    let mut contexts: RwLockWriteGuard<'_, BTreeMap<String, Value>> = self.write();
+
+    match contexts.get(&url) {
+      Some(registered) if *registered != document => Err(Error::InvalidJsonLd("context already registered")),
+      Some(_) => Ok(()),
+      None => {
+        contexts.insert(url, document);
+        Ok(())
+      }
+    }
+  }
+
+  /// Returns `true` if a context is registered under `url`.
+  pub fn contains(&self, url: &str) -> bool {
+    self.read().contains_key(url)
+  }
+
+  fn read(&self) -> RwLockReadGuard<'_, BTreeMap<String, Value>> {
+    self.contexts.read().unwrap_or_else(PoisonError::into_inner)
+  }
+
+  fn write(&self) -> RwLockWriteGuard<'_, BTreeMap<String, Value>> {
+    self.contexts.write().unwrap_or_else(PoisonError::into_inner)
+  }
+}
+
+impl Default for ContextCache {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl DocumentLoader for ContextCache {
+  fn load_context(&self, url: &str) -> Result<Value> {
+    self
+      .read()
+      .get(url)
+      .cloned()
+      .ok_or_else(|| Error::JsonLdContextNotFound(url.to_owned()))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::json;
+
+  #[test]
+  fn test_context_cache_register() {
+    let cache: ContextCache = ContextCache::empty();
+    let document: Value = json!({ "@context": { "name": "https://schema.org/name" } });
+
+    assert!(matches!(
+      cache.load_context("https://example.com/v1"),
+      Err(Error::JsonLdContextNotFound(_))
+    ));
+    assert!(cache.register("https://example.com/v1", document.clone()).is_ok());
+    assert_eq!(cache.load_context("https://example.com/v1").unwrap(), document);
+
+    // Registering the same context again is a no-op, a different one is rejected.
+    assert!(cache.register("https://example.com/v1", document).is_ok());
+    let other: Value = json!({ "@context": { "name": "https://example.com/name" } });
+    assert!(cache.register("https://example.com/v1", other).is_err());
+    assert!(cache
+      .register("https://example.com/v2", json!({ "name": "x" }))
+      .is_err());
+  }
+
+  #[test]
+  fn test_context_cache_preloaded() {
+    let cache: ContextCache = ContextCache::new();
+    for (url, _) in PRELOADED_CONTEXTS {
+      assert!(cache.load_context(url).unwrap().get("@context").is_some());
+    }
+  }
+}
@@ -0,0 +1,23 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! JSON-LD processing of credentials: expansion, conversion to RDF and canonicalization.
+//!
+//! Contexts are resolved offline with the [`DocumentLoader`] of a [`JsonLdProcessor`], e.g. a
+//! [`ContextCache`] preloaded with the Verifiable Credentials, DID and security contexts.
+
+pub use self::canonicalization::canonicalize;
+pub use self::loader::ContextCache;
+pub use self::loader::DocumentLoader;
+pub use self::processor::JsonLdProcessor;
+pub use self::rdf::to_nquads;
+pub use self::rdf::Quad;
+pub use self::rdf::Term;
+
+mod canonicalization;
+mod context;
+mod expansion;
+mod issuer;
+mod loader;
+mod processor;
+mod rdf;
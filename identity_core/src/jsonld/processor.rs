@@ -0,0 +1,205 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use crate::common::Value;
+use crate::error::Result;
+use crate::jsonld::canonicalize;
+use crate::jsonld::context::ContextProcessor;
+use crate::jsonld::expansion::Expander;
+use crate::jsonld::rdf::to_rdf;
+use crate::jsonld::to_nquads;
+use crate::jsonld::ContextCache;
+use crate::jsonld::DocumentLoader;
+use crate::jsonld::Quad;
+
+/// Expands JSON-LD documents and converts them to canonical RDF datasets.
+///
+/// Remote contexts are resolved with the [`DocumentLoader`] owned by the processor, a preloaded
+/// [`ContextCache`] by default. Clones of a processor share its loader.
+///
+/// In safe mode, which is enabled by default, documents with terms that are not defined by their
+/// contexts or relative IRIs are rejected instead of silently dropping that data.
+#[derive(Clone)]
+pub struct JsonLdProcessor {
+  loader: Arc<dyn DocumentLoader>,
+  safe_mode: bool,
+}
+
+impl JsonLdProcessor {
+  /// Creates a new `JsonLdProcessor` resolving remote contexts with `loader`.
+  pub fn new(loader: impl DocumentLoader + 'static) -> Self {
+    Self {
+      loader: Arc::new(loader),
+      safe_mode: true,
+    }
+  }
+
+  /// Returns the loader resolving remote contexts.
+  pub fn loader(&self) -> &dyn DocumentLoader {
+    self.loader.as_ref()
+  }
+
+  /// Sets whether data that cannot be expanded is rejected.
+  #[must_use]
+  pub fn safe_mode(mut self, value: bool) -> Self {
+    self.safe_mode = value;
+    self
+  }
+
+  /// Returns the expanded form of `document`.
+  ///
+  /// [More Info](https://www.w3.org/TR/json-ld11-api/#expansion-algorithm)
+  ///
+  /// # Errors
+  ///
+  /// Fails if a context cannot be resolved or processed, or if `document` contains an undefined
+  /// term in safe mode.
+  pub fn expand(&self, document: &Value) -> Result<Vec<Value>> {
+    Expander::new(ContextProcessor::new(self.loader()), self.safe_mode).expand_document(document)
+  }
+
+  /// Returns the RDF dataset of `document`.
+  ///
+  /// # Errors
+  ///
+  /// See [`JsonLdProcessor::expand`].
+  pub fn to_rdf(&self, document: &Value) -> Result<Vec<Quad>> {
+    to_rdf(&self.expand(document)?)
+  }
+
+  /// Returns the canonical N-Quads of `document`, canonicalized with the URDNA2015 algorithm.
+  ///
+  /// # Errors
+  ///
+  /// See [`JsonLdProcessor::expand`].
+  pub fn canonicalize(&self, document: &Value) -> Result<String> {
+    self.to_rdf(document).map(|dataset| to_nquads(&canonicalize(&dataset)))
+  }
+}
+
+impl Default for JsonLdProcessor {
+  fn default() -> Self {
+    Self::new(ContextCache::new())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::error::Error;
+  use crate::json;
+
+  fn credential() -> Value {
+    json!({
+      "@context": [
+        "https://www.w3.org/2018/credentials/v1",
+        { "name": "https://schema.org/name" },
+      ],
+      "id": "https://example.edu/credentials/3732",
+      "type": ["VerifiableCredential"],
+      "issuer": "did:example:issuer",
+      "issuanceDate": "2010-01-01T19:23:24Z",
+      "credentialSubject": {
+        "id": "did:example:subject",
+        "name": "Alice",
+      },
+    })
+  }
+
+  #[test]
+  fn test_expand() {
+    let expanded: Vec<Value> = JsonLdProcessor::default().expand(&credential()).unwrap();
+
+    assert_eq!(
+      expanded,
+      vec![json!({
+        "@id": "https://example.edu/credentials/3732",
+        "@type": ["https://www.w3.org/2018/credentials#VerifiableCredential"],
+        "https://www.w3.org/2018/credentials#credentialSubject": [{
+          "@id": "did:example:subject",
+          "https://schema.org/name": [{ "@value": "Alice" }],
+        }],
+        "https://www.w3.org/2018/credentials#issuanceDate": [{
+          "@type": "http://www.w3.org/2001/XMLSchema#dateTime",
+          "@value": "2010-01-01T19:23:24Z",
+        }],
+        "https://www.w3.org/2018/credentials#issuer": [{ "@id": "did:example:issuer" }],
+      })]
+    );
+  }
+
+  #[test]
+  fn test_expand_undefined_term() {
+    let mut document: Value = credential();
+    document["credentialSubject"]["degree"] = json!("Bachelor of Science");
+
+    let result: Result<Vec<Value>> = JsonLdProcessor::default().expand(&document);
+    assert!(matches!(result, Err(Error::JsonLdUndefinedTerm(term)) if term == "degree"));
+
+    // Undefined terms are dropped outside of safe mode.
+    let expanded: Vec<Value> = JsonLdProcessor::default().safe_mode(false).expand(&document).unwrap();
+    assert!(!expanded[0].to_string().contains("Bachelor"));
+
+    // Terms of the credential are not defined in the scope of the subject.
+    let mut document: Value = credential();
+    document["credentialSubject"]["issuer"] = json!("did:example:other");
+    assert!(JsonLdProcessor::default().expand(&document).is_err());
+
+    // All terms are defined by the `@vocab` of the v2 context.
+    let mut document: Value = credential();
+    document["@context"] = json!(["https://www.w3.org/ns/credentials/v2"]);
+    document["credentialSubject"]["degree"] = json!("Bachelor of Science");
+    assert!(JsonLdProcessor::default().expand(&document).is_ok());
+  }
+
+  #[test]
+  fn test_canonicalize() {
+    let nquads: String = JsonLdProcessor::default().canonicalize(&credential()).unwrap();
+
+    assert_eq!(
+      nquads,
+      "<did:example:subject> <https://schema.org/name> \"Alice\" .\n\
+       <https://example.edu/credentials/3732> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> \
+       <https://www.w3.org/2018/credentials#VerifiableCredential> .\n\
+       <https://example.edu/credentials/3732> <https://www.w3.org/2018/credentials#credentialSubject> \
+       <did:example:subject> .\n\
+       <https://example.edu/credentials/3732> <https://www.w3.org/2018/credentials#issuanceDate> \
+       \"2010-01-01T19:23:24Z\"^^<http://www.w3.org/2001/XMLSchema#dateTime> .\n\
+       <https://example.edu/credentials/3732> <https://www.w3.org/2018/credentials#issuer> \
+       <did:example:issuer> .\n"
+    );
+  }
+
+  #[test]
+  fn test_to_rdf_literals_and_graphs() {
+    let document: Value = json!({
+      "@context": {
+        "@vocab": "https://example.com/vocab#",
+        "list": { "@container": "@list" },
+        "proof": { "@id": "https://w3id.org/security#proof", "@container": "@graph" },
+      },
+      "integer": 5,
+      "double": 1.1,
+      "boolean": true,
+      "list": ["a", "b"],
+      "proof": { "type": "Proof", "value": "z" },
+    });
+
+    let nquads: String = JsonLdProcessor::new(ContextCache::empty())
+      .canonicalize(&document)
+      .unwrap();
+
+    assert!(nquads.contains("vocab#integer> \"5\"^^<http://www.w3.org/2001/XMLSchema#integer>"));
+    assert!(nquads.contains("vocab#double> \"1.1E0\"^^<http://www.w3.org/2001/XMLSchema#double>"));
+    assert!(nquads.contains("vocab#boolean> \"true\"^^<http://www.w3.org/2001/XMLSchema#boolean>"));
+    assert!(nquads.contains("<http://www.w3.org/1999/02/22-rdf-syntax-ns#first> \"a\""));
+    assert!(nquads.contains("rdf-syntax-ns#rest> <http://www.w3.org/1999/02/22-rdf-syntax-ns#nil>"));
+
+    // The proof is a named graph of its own.
+    assert!(nquads
+      .lines()
+      .any(|line| line.contains("<https://example.com/vocab#value> \"z\" _:c14n")));
+  }
+}
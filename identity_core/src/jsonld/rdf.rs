@@ -0,0 +1,555 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+use std::collections::BTreeMap;
+
+use crate::common::Value;
+use crate::convert::ToJson;
+use crate::error::Error;
+use crate::error::Result;
+use crate::jsonld::context::is_absolute_iri;
+use crate::jsonld::context::is_blank_node;
+use crate::jsonld::context::is_keyword;
+use crate::jsonld::expansion::add_value;
+use crate::jsonld::expansion::into_array;
+use crate::jsonld::issuer::IdentifierIssuer;
+use serde_json::Map;
+
+const RDF_FIRST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#first";
+const RDF_JSON: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#JSON";
+const RDF_LANGSTRING: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString";
+const RDF_NIL: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#nil";
+const RDF_REST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#rest";
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const XSD_BOOLEAN: &str = "http://www.w3.org/2001/XMLSchema#boolean";
+const XSD_DOUBLE: &str = "http://www.w3.org/2001/XMLSchema#double";
+const XSD_INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+
+/// The name of the default graph in a node map.
+const DEFAULT_GRAPH: &str = "@default";
+
+/// An RDF term.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Term {
+  /// An absolute IRI.
+  Iri(String),
+  /// A blank node, identified including the `_:` prefix.
+  BlankNode(String),
+  /// A literal.
+  Literal {
+    /// The lexical form of the literal.
+    value: String,
+    /// The datatype IRI of the literal.
+    datatype: String,
+    /// The language tag of a `rdf:langString` literal.
+    language: Option<String>,
+  },
+}
+
+impl Term {
+  /// Returns the identifier of a blank node term.
+  pub fn as_blank_node(&self) -> Option<&str> {
+    match self {
+      Self::BlankNode(id) => Some(id),
+      _ => None,
+    }
+  }
+
+  /// Returns a copy of the term, relabeling a blank node with `f`.
+  pub(crate) fn relabel(&self, f: &impl Fn(&str) -> String) -> Self {
+    match self {
+      Self::BlankNode(id) => Self::BlankNode(f(id)),
+      term => term.clone(),
+    }
+  }
+}
+
+impl Display for Term {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    match self {
+      Self::Iri(iri) => write!(f, "<{}>", iri),
+      Self::BlankNode(id) => f.write_str(id),
+      Self::Literal {
+        value,
+        datatype,
+        language,
+      } => {
+        write!(f, "\"{}\"", escape(value))?;
+
+        if let Some(language) = language {
+          write!(f, "@{}", language)
+        } else if datatype != XSD_STRING {
+          write!(f, "^^<{}>", datatype)
+        } else {
+          Ok(())
+        }
+      }
+    }
+  }
+}
+
+/// An RDF quad, i.e. a triple in the default graph or a named graph.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Quad {
+  /// The subject of the quad.
+  pub subject: Term,
+  /// The predicate of the quad.
+  pub predicate: Term,
+  /// The object of the quad.
+  pub object: Term,
+  /// The named graph of the quad, `None` for the default graph.
+  pub graph: Option<Term>,
+}
+
+impl Quad {
+  /// Returns a copy of the quad, relabeling its blank nodes with `f`.
+  pub(crate) fn relabel(&self, f: &impl Fn(&str) -> String) -> Self {
+    Self {
+      subject: self.subject.relabel(f),
+      predicate: self.predicate.clone(),
+      object: self.object.relabel(f),
+      graph: self.graph.as_ref().map(|graph| graph.relabel(f)),
+    }
+  }
+}
+
+/// Formats the quad as a line of an N-Quads document, without the trailing newline.
+impl Display for Quad {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    write!(f, "{} {} {} ", self.subject, self.predicate, self.object)?;
+
+    if let Some(graph) = &self.graph {
+      write!(f, "{} ", graph)?;
+    }
+
+    f.write_str(".")
+  }
+}
+
+/// Serializes `quads` as an N-Quads document.
+pub fn to_nquads(quads: &[Quad]) -> String {
+  quads.iter().map(|quad| format!("{}\n", quad)).collect()
+}
+
+/// Escapes the lexical form of a literal.
+fn escape(value: &str) -> String {
+  let mut escaped: String = String::with_capacity(value.len());
+
+  for char in value.chars() {
+    match char {
+      '\\' => escaped.push_str("\\\\"),
+      '"' => escaped.push_str("\\\""),
+      '\n' => escaped.push_str("\\n"),
+      '\r' => escaped.push_str("\\r"),
+      char => escaped.push(char),
+    }
+  }
+
+  escaped
+}
+
+/// Formats `value` in the canonical lexical form of `xsd:double`, e.g. `1.1E0`.
+fn format_double(value: f64) -> String {
+  let formatted: String = format!("{:.15E}", value);
+  let (mantissa, exponent): (&str, &str) = formatted.split_once('E').unwrap_or((formatted.as_str(), "0"));
+  let mantissa: &str = mantissa.trim_end_matches('0');
+
+  if mantissa.ends_with('.') {
+    format!("{}0E{}", mantissa, exponent)
+  } else {
+    format!("{}E{}", mantissa, exponent)
+  }
+}
+
+/// The subject a value of a node object is added to while generating a node map.
+enum ActiveSubject<'a> {
+  None,
+  Node(&'a str),
+  /// The node referenced by a reverse property.
+  Reverse(&'a str),
+}
+
+/// The nodes of each graph of a document, by graph name and node identifier.
+type NodeMap = BTreeMap<String, BTreeMap<String, Map<String, Value>>>;
+
+/// Returns the RDF dataset of the `expanded` JSON-LD document.
+///
+/// [More Info](https://www.w3.org/TR/json-ld11-api/#deserialize-json-ld-to-rdf-algorithm)
+pub(crate) fn to_rdf(expanded: &[Value]) -> Result<Vec<Quad>> {
+  RdfSerializer {
+    issuer: IdentifierIssuer::new("_:b"),
+  }
+  .serialize(expanded)
+}
+
+/// Converts expanded JSON-LD documents to RDF datasets, labeling blank nodes in order.
+struct RdfSerializer {
+  issuer: IdentifierIssuer,
+}
+
+impl RdfSerializer {
+  fn serialize(mut self, expanded: &[Value]) -> Result<Vec<Quad>> {
+    let mut node_map: NodeMap = NodeMap::new();
+    node_map.insert(DEFAULT_GRAPH.to_owned(), BTreeMap::new());
+
+    for element in expanded {
+      self.generate(element, &mut node_map, DEFAULT_GRAPH, &ActiveSubject::None, None, None)?;
+    }
+
+    let mut quads: Vec<Quad> = Vec::new();
+
+    for (graph_name, graph) in &node_map {
+      let graph_term: Option<Term> = match graph_name.as_str() {
+        DEFAULT_GRAPH => None,
+        name => match to_resource(name) {
+          Some(term) => Some(term),
+          None => continue,
+        },
+      };
+
+      for (subject, node) in graph {
+        let subject: Term = match to_resource(subject) {
+          Some(subject) => subject,
+          None => continue,
+        };
+
+        for (property, values) in node {
+          if property == "@type" {
+            for type_ in values.as_array().into_iter().flatten().filter_map(Value::as_str) {
+              if let Some(type_) = to_resource(type_) {
+                quads.push(Quad {
+                  subject: subject.clone(),
+                  predicate: Term::Iri(RDF_TYPE.to_owned()),
+                  object: type_,
+                  graph: graph_term.clone(),
+                });
+              }
+            }
+            continue;
+          }
+
+          if is_keyword(property) || !is_absolute_iri(property) {
+            continue;
+          }
+
+          for item in values.as_array().into_iter().flatten() {
+            let mut triples: Vec<Quad> = Vec::new();
+
+            let object: Option<Term> = match item.get("@list") {
+              Some(list) => Some(self.list_to_rdf(list, &mut triples, &graph_term)?),
+              None => self.object_to_rdf(item)?,
+            };
+
+            if let Some(object) = object {
+              quads.push(Quad {
+                subject: subject.clone(),
+                predicate: Term::Iri(property.clone()),
+                object,
+                graph: graph_term.clone(),
+              });
+            }
+
+            quads.extend(triples);
+          }
+        }
+      }
+    }
+
+    Ok(quads)
+  }
+
+  /// Adds the nodes of `element` to the `node_map`.
+  ///
+  /// [More Info](https://www.w3.org/TR/json-ld11-api/#node-map-generation)
+  fn generate(
+    &mut self,
+    element: &Value,
+    node_map: &mut NodeMap,
+    graph: &str,
+    subject: &ActiveSubject<'_>,
+    property: Option<&str>,
+    mut list: Option<&mut Vec<Value>>,
+  ) -> Result<()> {
+    let mut element: Map<String, Value> = match element {
+      Value::Array(items) => {
+        for item in items {
+          self.generate(item, node_map, graph, subject, property, list.as_deref_mut())?;
+        }
+        return Ok(());
+      }
+      Value::Object(element) => element.clone(),
+      _ => return Err(Error::InvalidJsonLd("invalid expanded document")),
+    };
+
+    node_map.entry(graph.to_owned()).or_default();
+
+    if let Some(Value::Array(types)) = element.get_mut("@type") {
+      for type_ in types {
+        if let Some(id) = type_.as_str().filter(|id| is_blank_node(id)) {
+          *type_ = Value::String(self.issuer.issue(id));
+        }
+      }
+    }
+
+    if element.contains_key("@value") {
+      let value: Value = Value::Object(element);
+
+      match (list, subject, property) {
+        (Some(list), _, _) => list.push(value),
+        (None, ActiveSubject::Node(subject), Some(property)) => {
+          add_unique(node(node_map, graph, subject), property, value);
+        }
+        _ => {}
+      }
+
+      return Ok(());
+    }
+
+    if let Some(items) = element.remove("@list") {
+      let mut result: Vec<Value> = Vec::new();
+      self.generate(&items, node_map, graph, subject, property, Some(&mut result))?;
+
+      let value: Value = Value::Object([("@list".to_owned(), Value::Array(result))].into_iter().collect());
+
+      match (list, subject, property) {
+        (Some(list), _, _) => list.push(value),
+        (None, ActiveSubject::Node(subject), Some(property)) => {
+          add_value(node(node_map, graph, subject), property, value);
+        }
+        _ => {}
+      }
+
+      return Ok(());
+    }
+
+    let id: String = match element.remove("@id") {
+      Some(Value::String(id)) if is_blank_node(&id) => self.issuer.issue(&id),
+      Some(Value::String(id)) => id,
+      None => self.issuer.next(),
+      Some(_) => return Err(Error::InvalidJsonLd("invalid @id value")),
+    };
+
+    node(node_map, graph, &id);
+
+    let reference: Value = Value::Object([("@id".to_owned(), Value::String(id.clone()))].into_iter().collect());
+
+    match (subject, property) {
+      (ActiveSubject::Reverse(referenced), Some(property)) => {
+        let referenced: Value = Value::Object(
+          [("@id".to_owned(), Value::String((*referenced).to_owned()))]
+            .into_iter()
+            .collect(),
+        );
+        add_unique(node(node_map, graph, &id), property, referenced);
+      }
+      (ActiveSubject::Node(subject), Some(property)) => match list {
+        Some(list) => list.push(reference),
+        None => add_unique(node(node_map, graph, subject), property, reference),
+      },
+      _ => {}
+    }
+
+    if let Some(types) = element.remove("@type") {
+      for type_ in into_array(types) {
+        add_unique(node(node_map, graph, &id), "@type", type_);
+      }
+    }
+
+    if let Some(index) = element.remove("@index") {
+      let node: &mut Map<String, Value> = node(node_map, graph, &id);
+
+      match node.get("@index") {
+        Some(existing) if *existing != index => return Err(Error::InvalidJsonLd("conflicting indexes")),
+        _ => {
+          node.insert("@index".to_owned(), index);
+        }
+      }
+    }
+
+    if let Some(Value::Object(reverse)) = element.remove("@reverse") {
+      for (property, values) in reverse {
+        for value in into_array(values) {
+          self.generate(
+            &value,
+            node_map,
+            graph,
+            &ActiveSubject::Reverse(&id),
+            Some(&property),
+            None,
+          )?;
+        }
+      }
+    }
+
+    if let Some(graph_value) = element.remove("@graph") {
+      self.generate(&graph_value, node_map, &id, &ActiveSubject::None, None, None)?;
+    }
+
+    if let Some(included) = element.remove("@included") {
+      self.generate(&included, node_map, graph, &ActiveSubject::None, None, None)?;
+    }
+
+    for (property, value) in element {
+      let property: String = if is_blank_node(&property) {
+        self.issuer.issue(&property)
+      } else {
+        property
+      };
+
+      node(node_map, graph, &id)
+        .entry(property.clone())
+        .or_insert_with(|| Value::Array(Vec::new()));
+
+      self.generate(
+        &value,
+        node_map,
+        graph,
+        &ActiveSubject::Node(&id),
+        Some(&property),
+        None,
+      )?;
+    }
+
+    Ok(())
+  }
+
+  /// Returns the RDF term of the node reference or value object `item`.
+  fn object_to_rdf(&self, item: &Value) -> Result<Option<Term>> {
+    let item: &Map<String, Value> = item
+      .as_object()
+      .ok_or(Error::InvalidJsonLd("invalid expanded document"))?;
+
+    let value: &Value = match item.get("@value") {
+      Some(value) => value,
+      None => return Ok(item.get("@id").and_then(Value::as_str).and_then(to_resource)),
+    };
+
+    let mut datatype: Option<String> = item.get("@type").and_then(Value::as_str).map(ToOwned::to_owned);
+    let language: Option<&str> = item.get("@language").and_then(Value::as_str);
+
+    if let Some(datatype) = datatype.as_deref() {
+      if datatype != "@json" && !is_absolute_iri(datatype) {
+        return Ok(None);
+      }
+    }
+
+    let lexical: String = if datatype.as_deref() == Some("@json") {
+      datatype = Some(RDF_JSON.to_owned());
+      String::from_utf8(value.to_jcs()?).map_err(|_| Error::InvalidJsonLd("invalid JSON literal"))?
+    } else {
+      match value {
+        Value::Bool(value) => {
+          datatype.get_or_insert_with(|| XSD_BOOLEAN.to_owned());
+          value.to_string()
+        }
+        Value::Number(number) => {
+          let float: f64 = number.as_f64().unwrap_or_default();
+          let is_integer: bool = number.is_i64() || number.is_u64() || (float.fract() == 0.0 && float.abs() < 1e21);
+
+          if !is_integer || datatype.as_deref() == Some(XSD_DOUBLE) {
+            datatype.get_or_insert_with(|| XSD_DOUBLE.to_owned());
+            format_double(float)
+          } else {
+            datatype.get_or_insert_with(|| XSD_INTEGER.to_owned());
+            match (number.as_i64(), number.as_u64()) {
+              (Some(integer), _) => integer.to_string(),
+              (None, Some(integer)) => integer.to_string(),
+              (None, None) => format!("{:.0}", float),
+            }
+          }
+        }
+        Value::String(value) => {
+          if language.is_some() {
+            datatype = Some(RDF_LANGSTRING.to_owned());
+          }
+          datatype.get_or_insert_with(|| XSD_STRING.to_owned());
+          value.clone()
+        }
+        _ => return Ok(None),
+      }
+    };
+
+    Ok(Some(Term::Literal {
+      value: lexical,
+      datatype: datatype.unwrap_or_else(|| XSD_STRING.to_owned()),
+      language: language.filter(|_| value.is_string()).map(ToOwned::to_owned),
+    }))
+  }
+
+  /// Adds the triples of the RDF collection of `list` to `triples` and returns its head.
+  fn list_to_rdf(&mut self, list: &Value, triples: &mut Vec<Quad>, graph: &Option<Term>) -> Result<Term> {
+    let items: &[Value] = list.as_array().map(Vec::as_slice).unwrap_or_default();
+
+    if items.is_empty() {
+      return Ok(Term::Iri(RDF_NIL.to_owned()));
+    }
+
+    let nodes: Vec<String> = items.iter().map(|_| self.issuer.next()).collect();
+
+    for (index, item) in items.iter().enumerate() {
+      let subject: Term = Term::BlankNode(nodes[index].clone());
+
+      let object: Option<Term> = match item.get("@list") {
+        Some(list) => Some(self.list_to_rdf(list, triples, graph)?),
+        None => self.object_to_rdf(item)?,
+      };
+
+      if let Some(object) = object {
+        triples.push(Quad {
+          subject: subject.clone(),
+          predicate: Term::Iri(RDF_FIRST.to_owned()),
+          object,
+          graph: graph.clone(),
+        });
+      }
+
+      let rest: Term = match nodes.get(index + 1) {
+        Some(next) => Term::BlankNode(next.clone()),
+        None => Term::Iri(RDF_NIL.to_owned()),
+      };
+
+      triples.push(Quad {
+        subject,
+        predicate: Term::Iri(RDF_REST.to_owned()),
+        object: rest,
+        graph: graph.clone(),
+      });
+    }
+
+    Ok(Term::BlankNode(nodes[0].clone()))
+  }
+}
+
+/// Returns the IRI or blank node term of `id`, `None` for relative IRIs.
+fn to_resource(id: &str) -> Option<Term> {
+  if is_blank_node(id) {
+    Some(Term::BlankNode(id.to_owned()))
+  } else if is_absolute_iri(id) {
+    Some(Term::Iri(id.to_owned()))
+  } else {
+    None
+  }
+}
+
+/// Returns the node `id` of `graph`, creating it if necessary.
+fn node<'a>(node_map: &'a mut NodeMap, graph: &str, id: &str) -> &'a mut Map<String, Value> {
+  node_map
+    .entry(graph.to_owned())
+    .or_default()
+    .entry(id.to_owned())
+    .or_insert_with(|| [("@id".to_owned(), Value::String(id.to_owned()))].into_iter().collect())
+}
+
+/// Appends `value` to the array entry `key` of `object`, unless it is already included.
+fn add_unique(object: &mut Map<String, Value>, key: &str, value: Value) {
+  let entry: &mut Value = object.entry(key.to_owned()).or_insert_with(|| Value::Array(Vec::new()));
+
+  if let Value::Array(values) = entry {
+    if !values.contains(&value) {
+      values.push(value);
+    }
+  }
+}
@@ -28,6 +28,7 @@ pub mod common;
 pub mod convert;
 pub mod crypto;
 pub mod error;
+pub mod jsonld;
 pub mod utils;
 
 pub use self::error::Error;
@@ -11,11 +11,17 @@ use identity_core::common::Object;
 use identity_core::common::OneOrMany;
 use identity_core::common::Timestamp;
 use identity_core::common::Url;
+use identity_core::common::Value;
 use identity_core::convert::FmtJson;
+use identity_core::convert::ToJson;
+use identity_core::crypto::GetProofs;
 use identity_core::crypto::GetSignature;
 use identity_core::crypto::GetSignatureMut;
 use identity_core::crypto::Proof;
+use identity_core::crypto::PushProof;
 use identity_core::crypto::SetSignature;
+use identity_core::jsonld::JsonLdProcessor;
+use identity_core::Error as CoreError;
 use identity_did::verification::MethodUriType;
 use identity_did::verification::TryMethod;
 
@@ -79,9 +85,9 @@ pub struct Credential<T = Object> {
   /// Miscellaneous properties.
   #[serde(flatten)]
   pub properties: T,
-  /// Proof(s) used to verify a `Credential`
-  #[serde(skip_serializing_if = "Option::is_none")]
-  pub proof: Option<Proof>,
+  /// Proof(s) used to verify a `Credential`, e.g. a proof set or chain.
+  #[serde(default, skip_serializing_if = "OneOrMany::is_empty")]
+  pub proof: OneOrMany<Proof>,
 }
 
 impl<T> Credential<T> {
@@ -119,7 +125,7 @@ impl<T> Credential<T> {
       evidence: builder.evidence.into(),
       non_transferable: builder.non_transferable,
       properties: builder.properties,
-      proof: None,
+      proof: OneOrMany::default(),
     };
 
     this.check_structure()?;
@@ -128,6 +134,9 @@ impl<T> Credential<T> {
   }
 
   /// Validates the semantic structure of the `Credential`.
+  ///
+  /// Terms undefined by the JSON-LD contexts are not rejected, since many credentials in use rely on
+  /// them. Use [`Credential::check_terms`] to check them explicitly.
   pub fn check_structure(&self) -> Result<()> {
     // Ensure the base context is present and in the correct location
     match self.context.get(0) {
@@ -155,14 +164,49 @@ impl<T> Credential<T> {
     Ok(())
   }
 
-  /// Returns a reference to the proof.
+  /// Returns a reference to the proof, i.e. the last added proof of a proof set.
   pub fn proof(&self) -> Option<&Proof> {
-    self.proof.as_ref()
+    self.proof.last()
   }
 
-  /// Returns a mutable reference to the proof.
+  /// Returns a mutable reference to the proof, i.e. the last added proof of a proof set.
   pub fn proof_mut(&mut self) -> Option<&mut Proof> {
-    self.proof.as_mut()
+    self
+      .proof
+      .len()
+      .checked_sub(1)
+      .and_then(|index| self.proof.get_mut(index))
+  }
+}
+
+impl<T> Credential<T>
+where
+  T: Serialize,
+{
+  /// Checks that all terms of the `Credential`, except for those of its proofs, are defined by its
+  /// JSON-LD contexts, which are resolved with the given `processor`.
+  ///
+  /// This check is not part of [`Credential::check_structure`] and must be requested explicitly, so
+  /// that existing credentials using undefined terms remain valid.
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`Error::UndefinedTerm`] if a term is not defined and with [`Error::InvalidJsonLd`]
+  /// if the `Credential` cannot be expanded, e.g. since one of its contexts is unknown to the
+  /// loader of the `processor`.
+  pub fn check_terms(&self, processor: &JsonLdProcessor) -> Result<()> {
+    let mut document: Value = self.to_json_value().map_err(Error::InvalidJsonLd)?;
+
+    // Proofs are defined by their signature suite and validated separately.
+    if let Value::Object(ref mut object) = document {
+      object.remove("proof");
+    }
+
+    match processor.clone().safe_mode(true).expand(&document) {
+      Ok(_) => Ok(()),
+      Err(CoreError::JsonLdUndefinedTerm(term)) => Err(Error::UndefinedTerm(term)),
+      Err(error) => Err(Error::InvalidJsonLd(error)),
+    }
   }
 }
 
@@ -177,19 +221,31 @@ where
 
 impl<T> GetSignature for Credential<T> {
   fn signature(&self) -> Option<&Proof> {
-    self.proof.as_ref()
+    self.proof()
   }
 }
 
 impl<T> GetSignatureMut for Credential<T> {
   fn signature_mut(&mut self) -> Option<&mut Proof> {
-    self.proof.as_mut()
+    self.proof_mut()
   }
 }
 
 impl<T> SetSignature for Credential<T> {
   fn set_signature(&mut self, value: Proof) {
-    self.proof.replace(value);
+    self.proof = OneOrMany::One(value);
+  }
+}
+
+impl<T> GetProofs for Credential<T> {
+  fn proofs(&self) -> &[Proof] {
+    self.proof.as_slice()
+  }
+}
+
+impl<T> PushProof for Credential<T> {
+  fn push_proof(&mut self, value: Proof) {
+    self.proof.push(value);
   }
 }
 
@@ -199,9 +255,18 @@ impl<T> TryMethod for Credential<T> {
 
 #[cfg(test)]
 mod tests {
+  use identity_core::common::Object;
+  use identity_core::common::Url;
+  use identity_core::common::Value;
   use identity_core::convert::FromJson;
+  use identity_core::jsonld::ContextCache;
+  use identity_core::jsonld::JsonLdProcessor;
+  use identity_core::Error as CoreError;
 
   use crate::credential::Credential;
+  use crate::credential::CredentialBuilder;
+  use crate::credential::Subject;
+  use crate::error::Error;
 
   const JSON1: &str = include_str!("../../tests/fixtures/credential-1.json");
   const JSON2: &str = include_str!("../../tests/fixtures/credential-2.json");
@@ -231,4 +296,56 @@ mod tests {
     let _credential: Credential = Credential::from_json(JSON11).unwrap();
     let _credential: Credential = Credential::from_json(JSON12).unwrap();
   }
+
+  #[test]
+  fn test_check_terms() {
+    let mut properties: Object = Object::new();
+    properties.insert("name".to_owned(), Value::String("Alice".to_owned()));
+    let subject: Subject = Subject::with_id_and_properties(Url::parse("did:example:subject").unwrap(), properties);
+
+    // Undefined terms do not affect the structure of a credential.
+    let credential: Credential = CredentialBuilder::default()
+      .id(Url::parse("https://example.edu/credentials/3732").unwrap())
+      .issuer(Url::parse("did:example:issuer").unwrap())
+      .subject(subject)
+      .build()
+      .unwrap();
+    let processor: JsonLdProcessor = JsonLdProcessor::default();
+
+    // `name` is not defined by the base context.
+    assert!(matches!(credential.check_terms(&processor), Err(Error::UndefinedTerm(term)) if term == "name"));
+
+    // Safe mode is enforced.
+    assert!(credential.check_terms(&processor.clone().safe_mode(false)).is_err());
+
+    // The terms are defined by the preloaded examples context.
+    let mut defined: Credential = credential.clone();
+    defined.context.push(
+      Url::parse("https://www.w3.org/2018/credentials/examples/v1")
+        .unwrap()
+        .into(),
+    );
+    assert!(defined.check_terms(&processor).is_ok());
+
+    // Unknown contexts fail the check.
+    let mut unknown: Credential = credential.clone();
+    unknown
+      .context
+      .push(Url::parse("https://example.com/contexts/v1").unwrap().into());
+    assert!(matches!(
+      unknown.check_terms(&processor),
+      Err(Error::InvalidJsonLd(CoreError::JsonLdContextNotFound(_)))
+    ));
+
+    // Contexts registered with the loader of the processor are resolved.
+    let cache: ContextCache = ContextCache::new();
+    let document: Value = Value::from_json(r#"{ "@context": { "name": "https://schema.org/name" } }"#).unwrap();
+    cache.register("https://example.com/contexts/v1", document).unwrap();
+    assert!(unknown.check_terms(&JsonLdProcessor::new(cache)).is_ok());
+
+    // Undefined types are rejected as well.
+    let mut typed: Credential = defined;
+    typed.types.push("UndefinedCredential".to_owned());
+    assert!(matches!(typed.check_terms(&processor), Err(Error::UndefinedTerm(_))));
+  }
 }
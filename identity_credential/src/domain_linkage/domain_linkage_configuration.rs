@@ -0,0 +1,115 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+
+use identity_core::common::Url;
+use identity_core::convert::FmtJson;
+
+use crate::credential::Credential;
+use crate::domain_linkage::DomainLinkageValidationError;
+
+lazy_static! {
+  static ref WELL_KNOWN_CONTEXT: Url =
+    Url::parse("https://identity.foundation/.well-known/did-configuration/v1").unwrap();
+}
+
+/// The DID configuration resource served by a web origin at
+/// `/.well-known/did-configuration.json`, listing the DIDs linked to the origin.
+///
+/// [Specification](https://identity.foundation/.well-known/resources/did-configuration/#did-configuration-resource)
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct DomainLinkageConfiguration {
+  #[serde(rename = "@context")]
+  context: Url,
+  linked_dids: Vec<Credential>,
+}
+
+impl DomainLinkageConfiguration {
+  /// The path of the DID configuration resource relative to the origin.
+  pub const WELL_KNOWN_PATH: &'static str = "/.well-known/did-configuration.json";
+
+  /// Creates a new `DomainLinkageConfiguration` listing the Domain Linkage credentials `linked_dids`.
+  pub fn new(linked_dids: Vec<Credential>) -> Self {
+    Self {
+      context: Self::well_known_context().clone(),
+      linked_dids,
+    }
+  }
+
+  /// Returns the JSON-LD context of the DID configuration resource and Domain Linkage credentials.
+  pub fn well_known_context() -> &'static Url {
+    &*WELL_KNOWN_CONTEXT
+  }
+
+  /// Returns the URL of the DID configuration resource of the origin of `domain`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `domain` is not a web URL.
+  pub fn well_known_url(domain: &Url) -> Result<Url, DomainLinkageValidationError> {
+    if !matches!(domain.scheme(), "https" | "http") || !domain.origin().is_tuple() {
+      return Err(DomainLinkageValidationError::InvalidDomain("expected a web origin"));
+    }
+
+    domain
+      .join(Self::WELL_KNOWN_PATH)
+      .map_err(|_| DomainLinkageValidationError::InvalidDomain("expected a web origin"))
+  }
+
+  /// Returns the JSON-LD context of the resource.
+  pub fn context(&self) -> &Url {
+    &self.context
+  }
+
+  /// Returns the Domain Linkage credentials of the resource.
+  pub fn linked_dids(&self) -> &[Credential] {
+    &self.linked_dids
+  }
+
+  /// Returns a mutable reference to the Domain Linkage credentials of the resource.
+  pub fn linked_dids_mut(&mut self) -> &mut Vec<Credential> {
+    &mut self.linked_dids
+  }
+
+  /// Returns the issuers of the Domain Linkage credentials, i.e. the linked DIDs.
+  pub fn issuers(&self) -> impl Iterator<Item = &Url> + '_ {
+    self.linked_dids.iter().map(|credential| credential.issuer.url())
+  }
+}
+
+impl Display for DomainLinkageConfiguration {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    self.fmt_json(f)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::convert::FromJson;
+  use identity_core::convert::ToJson;
+
+  use super::*;
+
+  #[test]
+  fn test_domain_linkage_configuration_well_known_url() {
+    let domain: Url = Url::parse("https://foo.example.com/some/path").unwrap();
+    assert_eq!(
+      DomainLinkageConfiguration::well_known_url(&domain).unwrap().as_str(),
+      "https://foo.example.com/.well-known/did-configuration.json"
+    );
+    assert!(DomainLinkageConfiguration::well_known_url(&Url::parse("did:example:123").unwrap()).is_err());
+  }
+
+  #[test]
+  fn test_domain_linkage_configuration_json() {
+    let configuration: DomainLinkageConfiguration = DomainLinkageConfiguration::new(Vec::new());
+    let json: String = configuration.to_json().unwrap();
+    assert_eq!(
+      json,
+      r#"{"@context":"https://identity.foundation/.well-known/did-configuration/v1","linked_dids":[]}"#
+    );
+    assert_eq!(DomainLinkageConfiguration::from_json(&json).unwrap(), configuration);
+  }
+}
@@ -0,0 +1,153 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Context;
+use identity_core::common::Object;
+use identity_core::common::Timestamp;
+use identity_core::common::Url;
+use identity_core::common::Value;
+
+use crate::credential::Credential;
+use crate::credential::CredentialBuilder;
+use crate::credential::Subject;
+use crate::domain_linkage::DomainLinkageConfiguration;
+use crate::error::Error;
+use crate::error::Result;
+
+/// Convenient builder to create a spec compliant Domain Linkage credential.
+///
+/// The issuer and the subject of the credential are the same DID.
+///
+/// [Specification](https://identity.foundation/.well-known/resources/did-configuration/#linked-data-proof-format)
+#[derive(Clone, Debug, Default)]
+pub struct DomainLinkageCredentialBuilder {
+  pub(crate) issuer: Option<Url>,
+  pub(crate) issuance_date: Option<Timestamp>,
+  pub(crate) expiration_date: Option<Timestamp>,
+  pub(crate) origin: Option<Url>,
+}
+
+impl DomainLinkageCredentialBuilder {
+  /// The type of a Domain Linkage credential.
+  pub const TYPE: &'static str = "DomainLinkageCredential";
+
+  /// Creates a new `DomainLinkageCredentialBuilder`.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the value of the `issuer` and the id of the `credentialSubject`, i.e. the DID.
+  #[must_use]
+  pub fn issuer(mut self, did: Url) -> Self {
+    self.issuer = Some(did);
+    self
+  }
+
+  /// Sets the value of the `issuanceDate`.
+  ///
+  /// Defaults to the current time if unset.
+  #[must_use]
+  pub fn issuance_date(mut self, value: Timestamp) -> Self {
+    self.issuance_date = Some(value);
+    self
+  }
+
+  /// Sets the value of the `expirationDate`.
+  #[must_use]
+  pub fn expiration_date(mut self, value: Timestamp) -> Self {
+    self.expiration_date = Some(value);
+    self
+  }
+
+  /// Sets the origin linked to the DID.
+  ///
+  /// Only the origin of `value` is used, any path, query or fragment is discarded.
+  #[must_use]
+  pub fn origin(mut self, value: Url) -> Self {
+    self.origin = Some(value);
+    self
+  }
+
+  /// Returns a new Domain Linkage [`Credential`] based on the `DomainLinkageCredentialBuilder` configuration.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the issuer, the origin or the expiration date is missing, or the origin is not a web origin.
+  pub fn build(self) -> Result<Credential> {
+    let issuer: Url = self.issuer.ok_or(Error::MissingIssuer)?;
+    let origin: Url = self.origin.ok_or(Error::MissingOrigin)?;
+    let expiration_date: Timestamp = self.expiration_date.ok_or(Error::MissingExpirationDate)?;
+
+    if !origin.origin().is_tuple() {
+      return Err(Error::InvalidOrigin);
+    }
+
+    let mut properties: Object = Object::new();
+    properties.insert(
+      "origin".to_owned(),
+      Value::String(origin.origin().ascii_serialization()),
+    );
+
+    CredentialBuilder::default()
+      .context(Context::Url(DomainLinkageConfiguration::well_known_context().clone()))
+      .type_(Self::TYPE)
+      .issuer(issuer.clone())
+      .subject(Subject::with_id_and_properties(issuer, properties))
+      .issuance_date(self.issuance_date.unwrap_or_else(Timestamp::now_utc))
+      .expiration_date(expiration_date)
+      .build()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_domain_linkage_credential_builder() {
+    let credential: Credential = DomainLinkageCredentialBuilder::new()
+      .issuer(Url::parse("did:example:123").unwrap())
+      .origin(Url::parse("https://foo.example.com/some/path?query").unwrap())
+      .issuance_date(Timestamp::parse("2022-01-01T00:00:00Z").unwrap())
+      .expiration_date(Timestamp::parse("2023-01-01T00:00:00Z").unwrap())
+      .build()
+      .unwrap();
+
+    assert_eq!(credential.context.len(), 2);
+    assert_eq!(credential.types.get(1).unwrap(), DomainLinkageCredentialBuilder::TYPE);
+    assert_eq!(credential.issuer.url().as_str(), "did:example:123");
+    assert!(credential.id.is_none());
+
+    let subject: &Subject = credential.credential_subject.get(0).unwrap();
+    assert_eq!(subject.id.as_ref().unwrap().as_str(), "did:example:123");
+    assert_eq!(
+      subject.properties.get("origin").unwrap(),
+      &Value::String("https://foo.example.com".to_owned())
+    );
+  }
+
+  #[test]
+  fn test_domain_linkage_credential_builder_missing_properties() {
+    let builder: DomainLinkageCredentialBuilder = DomainLinkageCredentialBuilder::new()
+      .issuer(Url::parse("did:example:123").unwrap())
+      .origin(Url::parse("https://foo.example.com").unwrap());
+
+    assert!(matches!(builder.clone().build(), Err(Error::MissingExpirationDate)));
+
+    let builder: DomainLinkageCredentialBuilder =
+      builder.expiration_date(Timestamp::parse("2023-01-01T00:00:00Z").unwrap());
+    assert!(builder.clone().build().is_ok());
+    assert!(matches!(
+      builder.clone().origin(Url::parse("did:example:456").unwrap()).build(),
+      Err(Error::InvalidOrigin)
+    ));
+    assert!(matches!(
+      DomainLinkageCredentialBuilder {
+        issuer: None,
+        ..builder
+      }
+      .build(),
+      Err(Error::MissingIssuer)
+    ));
+  }
+}
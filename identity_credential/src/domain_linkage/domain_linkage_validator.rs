@@ -0,0 +1,423 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::error::Error;
+
+use identity_core::common::Context;
+use identity_core::common::Url;
+use identity_core::convert::FromJson;
+use identity_did::did::DID;
+use identity_did::document::Document;
+use identity_did::service::LinkedDomainService;
+use identity_did::service::Service;
+
+use crate::credential::Credential;
+use crate::credential::Subject;
+use crate::domain_linkage::DomainLinkageConfiguration;
+use crate::domain_linkage::DomainLinkageCredentialBuilder;
+use crate::domain_linkage::DomainLinkageValidationError;
+use crate::validator::CredentialValidationOptions;
+use crate::validator::CredentialValidator;
+use crate::validator::FailFast;
+
+type Result<T, E = DomainLinkageValidationError> = ::core::result::Result<T, E>;
+
+/// Retrieves DID configuration resources, e.g. over HTTPS.
+///
+/// Abstracting the retrieval allows validating domain linkage without network access.
+#[async_trait::async_trait(?Send)]
+pub trait DidConfigurationFetcher {
+  /// Fetches the resource at `url` and returns its body.
+  async fn fetch(&self, url: &Url) -> Result<Vec<u8>, Box<dyn Error + Send + Sync + 'static>>;
+}
+
+/// A struct for validating the link between DIDs and web origins in both directions.
+///
+/// [Specification](https://identity.foundation/.well-known/resources/did-configuration/#did-configuration-resource-verification)
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct DomainLinkageValidator;
+
+impl DomainLinkageValidator {
+  /// Validates a Domain Linkage `credential` linking the DID of `issuer` to the origin of `domain`.
+  ///
+  /// Apart from the structure required by the specification, the credential is validated
+  /// against the DID Document of its issuer with the given `options`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the credential is malformed, was not issued by `issuer` for the origin of `domain`,
+  /// or fails validation.
+  pub fn validate_credential<DOC: Document>(
+    issuer: &DOC,
+    credential: &Credential,
+    domain: &Url,
+    options: &CredentialValidationOptions,
+  ) -> Result<()> {
+    let context: Context = Context::Url(DomainLinkageConfiguration::well_known_context().clone());
+    if !credential.context.iter().any(|value| value == &context) {
+      return Err(DomainLinkageValidationError::InvalidCredential("missing context"));
+    }
+
+    if !credential
+      .types
+      .iter()
+      .any(|type_| type_ == DomainLinkageCredentialBuilder::TYPE)
+    {
+      return Err(DomainLinkageValidationError::InvalidCredential("missing type"));
+    }
+
+    if credential.issuer.url().as_str() != issuer.id().as_str() {
+      return Err(DomainLinkageValidationError::InvalidCredential("issuer"));
+    }
+
+    if credential.expiration_date.is_none() {
+      return Err(DomainLinkageValidationError::InvalidCredential(
+        "missing expiration date",
+      ));
+    }
+
+    let subject: &Subject = match credential.credential_subject.as_slice() {
+      [subject] => subject,
+      _ => {
+        return Err(DomainLinkageValidationError::InvalidCredential(
+          "expected a single subject",
+        ))
+      }
+    };
+
+    if subject.id.as_ref().map(|id| id.as_str()) != Some(issuer.id().as_str()) {
+      return Err(DomainLinkageValidationError::InvalidCredential("subject id"));
+    }
+
+    let origin: Url = subject
+      .properties
+      .get("origin")
+      .and_then(|origin| origin.as_str())
+      .and_then(|origin| Url::parse(origin).ok())
+      .ok_or(DomainLinkageValidationError::InvalidCredential("missing origin"))?;
+
+    if origin.origin() != domain.origin() {
+      return Err(DomainLinkageValidationError::InvalidCredential("origin"));
+    }
+
+    CredentialValidator::validate(credential, issuer, options, FailFast::FirstError)
+      .map_err(DomainLinkageValidationError::CredentialValidation)
+  }
+
+  /// Validates that the DID configuration resource `configuration` of the origin of `domain`
+  /// contains a valid Domain Linkage credential issued by `issuer`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the configuration contains no credential of `issuer`, or none is valid.
+  pub fn validate_linkage<DOC: Document>(
+    issuer: &DOC,
+    configuration: &DomainLinkageConfiguration,
+    domain: &Url,
+    options: &CredentialValidationOptions,
+  ) -> Result<()> {
+    let mut result: Result<()> = Err(DomainLinkageValidationError::MissingCredential);
+
+    for credential in configuration
+      .linked_dids()
+      .iter()
+      .filter(|credential| credential.issuer.url().as_str() == issuer.id().as_str())
+    {
+      result = Self::validate_credential(issuer, credential, domain, options);
+      if result.is_ok() {
+        break;
+      }
+    }
+
+    result
+  }
+
+  /// Validates the link between the DID of `document` and the origin of `domain` in both
+  /// directions: the DID Document MUST list the origin in a `LinkedDomains` service and the
+  /// DID configuration resource of the origin MUST contain a valid Domain Linkage credential
+  /// issued by the DID.
+  ///
+  /// # Errors
+  ///
+  /// Fails if either direction of the link is missing or invalid.
+  pub fn validate_did_linkage<DOC>(
+    document: &DOC,
+    configuration: &DomainLinkageConfiguration,
+    domain: &Url,
+    options: &CredentialValidationOptions,
+  ) -> Result<()>
+  where
+    DOC: Document,
+    DOC::V: Clone,
+  {
+    if !Self::linked_domains(document)?
+      .iter()
+      .any(|linked| linked.origin() == domain.origin())
+    {
+      return Err(DomainLinkageValidationError::MissingLinkedDomain);
+    }
+
+    Self::validate_linkage(document, configuration, domain, options)
+  }
+
+  /// Returns the origins listed in the `LinkedDomains` services of `document`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if a `LinkedDomains` service is malformed.
+  pub fn linked_domains<DOC>(document: &DOC) -> Result<Vec<Url>>
+  where
+    DOC: Document,
+    DOC::V: Clone,
+  {
+    let mut domains: Vec<Url> = Vec::new();
+
+    for service in document
+      .services()
+      .iter()
+      .filter(|service| service.type_().contains(LinkedDomainService::<DOC::D, DOC::V>::TYPE))
+    {
+      let service: Service<DOC::D, DOC::V> = service.clone();
+      let linked_domains: LinkedDomainService<DOC::D, DOC::V> =
+        LinkedDomainService::try_from(service).map_err(DomainLinkageValidationError::InvalidService)?;
+      domains.extend(linked_domains.domains().iter().cloned());
+    }
+
+    Ok(domains)
+  }
+
+  /// Fetches the DID configuration resource of the origin of `domain` with `fetcher`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `domain` is not a web URL, or the resource cannot be fetched or deserialized.
+  pub async fn fetch_configuration<F>(domain: &Url, fetcher: &F) -> Result<DomainLinkageConfiguration>
+  where
+    F: DidConfigurationFetcher + ?Sized,
+  {
+    let url: Url = DomainLinkageConfiguration::well_known_url(domain)?;
+    let body: Vec<u8> = fetcher.fetch(&url).await.map_err(DomainLinkageValidationError::Fetch)?;
+
+    DomainLinkageConfiguration::from_json_slice(&body).map_err(DomainLinkageValidationError::InvalidConfiguration)
+  }
+
+  /// Verifies the link of the DID of `document` to each origin listed in its `LinkedDomains`
+  /// services, fetching the DID configuration resources with `fetcher`.
+  ///
+  /// Returns the result of the verification for each origin.
+  ///
+  /// # Errors
+  ///
+  /// Fails if a `LinkedDomains` service is malformed.
+  pub async fn verify_did<DOC, F>(
+    document: &DOC,
+    fetcher: &F,
+    options: &CredentialValidationOptions,
+  ) -> Result<Vec<(Url, Result<()>)>>
+  where
+    DOC: Document,
+    DOC::V: Clone,
+    F: DidConfigurationFetcher + ?Sized,
+  {
+    let mut results: Vec<(Url, Result<()>)> = Vec::new();
+
+    for domain in Self::linked_domains(document)? {
+      let result: Result<()> = match Self::fetch_configuration(&domain, fetcher).await {
+        Ok(configuration) => Self::validate_linkage(document, &configuration, &domain, options),
+        Err(error) => Err(error),
+      };
+      results.push((domain, result));
+    }
+
+    Ok(results)
+  }
+
+  /// Verifies the link of the origin of `domain` to the DIDs in its DID configuration resource
+  /// `configuration`, given the resolved DID Documents of the `issuers`.
+  ///
+  /// Returns the DID Documents whose link is valid in both directions. DIDs without a
+  /// Document in `issuers` are ignored, see [`DomainLinkageConfiguration::issuers`].
+  pub fn verify_domain<'a, DOC>(
+    domain: &Url,
+    configuration: &DomainLinkageConfiguration,
+    issuers: &'a [DOC],
+    options: &CredentialValidationOptions,
+  ) -> Vec<&'a DOC>
+  where
+    DOC: Document,
+    DOC::V: Clone,
+  {
+    issuers
+      .iter()
+      .filter(|issuer| Self::validate_did_linkage(*issuer, configuration, domain, options).is_ok())
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::BTreeMap;
+
+  use identity_core::common::Object;
+  use identity_core::common::OrderedSet;
+  use identity_core::common::Timestamp;
+  use identity_core::convert::ToJson;
+  use identity_core::crypto::KeyPair;
+  use identity_core::crypto::ProofOptions;
+  use identity_did::document::CoreDocument;
+
+  use crate::validator::test_utils;
+
+  use super::*;
+
+  struct InMemoryFetcher(BTreeMap<String, String>);
+
+  #[async_trait::async_trait(?Send)]
+  impl DidConfigurationFetcher for InMemoryFetcher {
+    async fn fetch(&self, url: &Url) -> Result<Vec<u8>, Box<dyn Error + Send + Sync + 'static>> {
+      self
+        .0
+        .get(url.as_str())
+        .map(|body| body.as_bytes().to_vec())
+        .ok_or_else(|| "not found".into())
+    }
+  }
+
+  fn domain() -> Url {
+    Url::parse("https://foo.example.com").unwrap()
+  }
+
+  fn linked_document() -> (CoreDocument, KeyPair) {
+    let (mut document, keypair) = test_utils::generate_document_with_keys();
+    let service: LinkedDomainService = LinkedDomainService::new(
+      document.id().to_url().join("#linked-domain").unwrap(),
+      OrderedSet::try_from(vec![domain()]).unwrap(),
+      Object::new(),
+    )
+    .unwrap();
+    assert!(document.service_mut().append(service.into_service()));
+    (document, keypair)
+  }
+
+  fn domain_linkage_credential(document: &CoreDocument, keypair: &KeyPair, origin: &Url) -> Credential {
+    let mut credential: Credential = DomainLinkageCredentialBuilder::new()
+      .issuer(Url::parse(document.id().as_str()).unwrap())
+      .origin(origin.clone())
+      .issuance_date(Timestamp::parse("2020-01-01T00:00:00Z").unwrap())
+      .expiration_date(Timestamp::parse("2100-01-01T00:00:00Z").unwrap())
+      .build()
+      .unwrap();
+    document
+      .signer(keypair.private())
+      .options(ProofOptions::default())
+      .method(document.methods().next().unwrap().id())
+      .sign(&mut credential)
+      .unwrap();
+    credential
+  }
+
+  #[test]
+  fn test_validate_did_linkage() {
+    let (document, keypair): (CoreDocument, KeyPair) = linked_document();
+    let credential: Credential = domain_linkage_credential(&document, &keypair, &domain());
+    let configuration: DomainLinkageConfiguration = DomainLinkageConfiguration::new(vec![credential]);
+    let options: CredentialValidationOptions = CredentialValidationOptions::default();
+
+    assert!(DomainLinkageValidator::validate_did_linkage(&document, &configuration, &domain(), &options).is_ok());
+
+    // The configuration of another origin is not valid for the DID.
+    let other: Url = Url::parse("https://bar.example.com").unwrap();
+    assert!(matches!(
+      DomainLinkageValidator::validate_did_linkage(&document, &configuration, &other, &options),
+      Err(DomainLinkageValidationError::MissingLinkedDomain)
+    ));
+    assert!(matches!(
+      DomainLinkageValidator::validate_linkage(&document, &configuration, &other, &options),
+      Err(DomainLinkageValidationError::InvalidCredential("origin"))
+    ));
+
+    // A DID without a credential in the configuration is not linked.
+    let (other_document, _): (CoreDocument, KeyPair) = test_utils::generate_document_with_keys();
+    assert!(matches!(
+      DomainLinkageValidator::validate_linkage(&other_document, &configuration, &domain(), &options),
+      Err(DomainLinkageValidationError::MissingCredential)
+    ));
+  }
+
+  #[test]
+  fn test_validate_credential_invalid() {
+    let (document, keypair): (CoreDocument, KeyPair) = linked_document();
+    let options: CredentialValidationOptions = CredentialValidationOptions::default();
+
+    // A credential signed by another key fails validation.
+    let (_, other_keypair): (CoreDocument, KeyPair) = test_utils::generate_document_with_keys();
+    let credential: Credential = domain_linkage_credential(&document, &other_keypair, &domain());
+    assert!(matches!(
+      DomainLinkageValidator::validate_credential(&document, &credential, &domain(), &options),
+      Err(DomainLinkageValidationError::CredentialValidation(_))
+    ));
+
+    // A credential whose subject is not the issuer is malformed.
+    let mut credential: Credential = domain_linkage_credential(&document, &keypair, &domain());
+    credential.credential_subject.get_mut(0).unwrap().id = Some(Url::parse("did:example:other").unwrap());
+    assert!(matches!(
+      DomainLinkageValidator::validate_credential(&document, &credential, &domain(), &options),
+      Err(DomainLinkageValidationError::InvalidCredential("subject id"))
+    ));
+
+    let mut credential: Credential = domain_linkage_credential(&document, &keypair, &domain());
+    credential.expiration_date = None;
+    assert!(matches!(
+      DomainLinkageValidator::validate_credential(&document, &credential, &domain(), &options),
+      Err(DomainLinkageValidationError::InvalidCredential(
+        "missing expiration date"
+      ))
+    ));
+  }
+
+  #[tokio::test]
+  async fn test_verify_did_and_domain() {
+    let (document, keypair): (CoreDocument, KeyPair) = linked_document();
+    let (unlinked_document, unlinked_keypair): (CoreDocument, KeyPair) = test_utils::generate_document_with_keys();
+    let options: CredentialValidationOptions = CredentialValidationOptions::default();
+
+    // The origin lists both DIDs, but only one DID Document lists the origin.
+    let configuration: DomainLinkageConfiguration = DomainLinkageConfiguration::new(vec![
+      domain_linkage_credential(&document, &keypair, &domain()),
+      domain_linkage_credential(&unlinked_document, &unlinked_keypair, &domain()),
+    ]);
+
+    let mut resources: BTreeMap<String, String> = BTreeMap::new();
+    resources.insert(
+      "https://foo.example.com/.well-known/did-configuration.json".to_owned(),
+      configuration.to_json().unwrap(),
+    );
+    let fetcher: InMemoryFetcher = InMemoryFetcher(resources);
+
+    let results: Vec<(Url, Result<()>)> = DomainLinkageValidator::verify_did(&document, &fetcher, &options)
+      .await
+      .unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].0, domain());
+    assert!(results[0].1.is_ok());
+
+    let fetched: DomainLinkageConfiguration = DomainLinkageValidator::fetch_configuration(&domain(), &fetcher)
+      .await
+      .unwrap();
+    assert_eq!(fetched, configuration);
+    assert_eq!(fetched.issuers().count(), 2);
+
+    let issuers: Vec<CoreDocument> = vec![document.clone(), unlinked_document];
+    let verified: Vec<&CoreDocument> = DomainLinkageValidator::verify_domain(&domain(), &fetched, &issuers, &options);
+    assert_eq!(verified.len(), 1);
+    assert_eq!(verified[0].id(), document.id());
+
+    // A missing resource fails the verification of the origin.
+    let fetcher: InMemoryFetcher = InMemoryFetcher(BTreeMap::new());
+    let results: Vec<(Url, Result<()>)> = DomainLinkageValidator::verify_did(&document, &fetcher, &options)
+      .await
+      .unwrap();
+    assert!(matches!(results[0].1, Err(DomainLinkageValidationError::Fetch(_))));
+  }
+}
@@ -0,0 +1,34 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::validator::CompoundCredentialValidationError;
+
+/// An error caused by a failure to verify the link between a DID and a web origin.
+#[derive(Debug, thiserror::Error, strum::IntoStaticStr)]
+#[non_exhaustive]
+pub enum DomainLinkageValidationError {
+  /// Caused by a domain that is not a web origin.
+  #[error("invalid domain: {0}")]
+  InvalidDomain(&'static str),
+  /// Caused by a Domain Linkage credential with a missing or invalid property.
+  #[error("invalid domain linkage credential: {0}")]
+  InvalidCredential(&'static str),
+  /// Caused by a Domain Linkage credential that failed validation against the DID Document of its issuer.
+  #[error("domain linkage credential validation failed")]
+  CredentialValidation(#[source] CompoundCredentialValidationError),
+  /// Caused by a DID configuration without a Domain Linkage credential issued by the DID.
+  #[error("no domain linkage credential issued by the DID")]
+  MissingCredential,
+  /// Caused by a DID Document without a `LinkedDomains` service listing the domain.
+  #[error("the DID Document does not link to the domain")]
+  MissingLinkedDomain,
+  /// Caused by a DID Document with a malformed `LinkedDomains` service.
+  #[error("invalid linked domain service")]
+  InvalidService(#[source] identity_did::Error),
+  /// Caused by a DID configuration resource that could not be deserialized.
+  #[error("invalid DID configuration")]
+  InvalidConfiguration(#[source] identity_core::Error),
+  /// Caused by a failure to fetch the DID configuration resource of a domain.
+  #[error("failed to fetch the DID configuration")]
+  Fetch(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+}
@@ -0,0 +1,17 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! The DIF Well-Known DID Configuration, linking DIDs to web origins and vice versa.
+//!
+//! [Specification](https://identity.foundation/.well-known/resources/did-configuration/)
+
+pub use self::domain_linkage_configuration::DomainLinkageConfiguration;
+pub use self::domain_linkage_credential_builder::DomainLinkageCredentialBuilder;
+pub use self::domain_linkage_validator::DidConfigurationFetcher;
+pub use self::domain_linkage_validator::DomainLinkageValidator;
+pub use self::error::DomainLinkageValidationError;
+
+mod domain_linkage_configuration;
+mod domain_linkage_credential_builder;
+mod domain_linkage_validator;
+mod error;
@@ -27,4 +27,19 @@ pub enum Error {
   /// Caused when trying to construct an invalid status.
   #[error("invalid credential status: {0}")]
   InvalidStatus(String),
+  /// Caused when constructing a Domain Linkage credential without an origin.
+  #[error("missing origin")]
+  MissingOrigin,
+  /// Caused when constructing a Domain Linkage credential with an origin that is not a web origin.
+  #[error("invalid origin")]
+  InvalidOrigin,
+  /// Caused when constructing a Domain Linkage credential without an expiration date.
+  #[error("missing expiration date")]
+  MissingExpirationDate,
+  /// Caused when a credential uses a term that is not defined by its JSON-LD contexts.
+  #[error("undefined JSON-LD term: {0}")]
+  UndefinedTerm(String),
+  /// Caused when a credential is not a valid JSON-LD document.
+  #[error("invalid JSON-LD document")]
+  InvalidJsonLd(#[source] identity_core::Error),
 }
@@ -27,5 +27,9 @@ pub mod credential;
 pub mod error;
 pub mod presentation;
 
+#[cfg(feature = "domain-linkage")]
+pub mod domain_linkage;
+#[cfg(feature = "oidc")]
+pub mod oidc;
 #[cfg(feature = "validator")]
 pub mod validator;
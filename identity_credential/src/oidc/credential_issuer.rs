@@ -0,0 +1,623 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::time::Duration;
+
+use identity_core::common::Object;
+use identity_core::common::Timestamp;
+use identity_core::common::Url;
+use identity_core::common::Value;
+use identity_core::convert::ToJson;
+use identity_core::crypto::KeyPair;
+use identity_core::crypto::ProofOptions;
+use identity_did::did::DID;
+use identity_did::document::CoreDocument;
+use identity_did::document::Document;
+use identity_did::verification::jwk_from_keypair;
+use identity_did::verification::Jwk;
+use identity_did::verification::VerificationMethod;
+use libjose::jws::Encoder;
+use libjose::jws::JwsHeader;
+use libjose::jws::Recipient;
+use libjose::jwt::JwtClaims;
+use libjose::utils::encode_b64;
+use libjose::utils::random_bytes;
+
+use crate::credential::Credential;
+use crate::oidc::CredentialOffer;
+use crate::oidc::CredentialRequest;
+use crate::oidc::CredentialResponse;
+use crate::oidc::OidcError;
+use crate::oidc::PreAuthorizedCodeGrant;
+use crate::oidc::ProofOfPossession;
+use crate::oidc::TokenRequest;
+use crate::oidc::TokenResponse;
+use crate::oidc::VcJwtCredential;
+use crate::oidc::FORMAT_JWT_VC_JSON;
+use crate::oidc::FORMAT_LDP_VC;
+use crate::oidc::PRE_AUTHORIZED_CODE_GRANT;
+use crate::oidc::PROOF_TYPE_JWT;
+
+type Result<T, E = OidcError> = ::core::result::Result<T, E>;
+
+/// Signs the credentials issued by a [`CredentialIssuer`].
+pub trait CredentialSigner {
+  /// Secures `credential` with a Linked Data Proof, i.e. the [`FORMAT_LDP_VC`] format.
+  fn sign_ldp(&self, credential: &mut Credential) -> Result<()>;
+
+  /// Encodes `credential` as a signed VC-JWT, i.e. the [`FORMAT_JWT_VC_JSON`] format.
+  fn sign_jwt(&self, credential: &Credential) -> Result<String>;
+}
+
+/// A [`CredentialSigner`] signing with an Ed25519 verification method of the issuer's DID Document.
+#[derive(Debug)]
+pub struct DocumentCredentialSigner {
+  document: CoreDocument,
+  keypair: KeyPair,
+  fragment: String,
+}
+
+impl DocumentCredentialSigner {
+  /// Creates a new `DocumentCredentialSigner` signing with the verification method identified
+  /// by `fragment` and the corresponding `keypair`.
+  pub fn new(document: CoreDocument, keypair: KeyPair, fragment: impl Into<String>) -> Self {
+    Self {
+      document,
+      keypair,
+      fragment: fragment.into(),
+    }
+  }
+
+  /// Returns the DID Document of the issuer.
+  pub fn document(&self) -> &CoreDocument {
+    &self.document
+  }
+}
+
+impl CredentialSigner for DocumentCredentialSigner {
+  fn sign_ldp(&self, credential: &mut Credential) -> Result<()> {
+    self
+      .document
+      .signer(self.keypair.private())
+      .options(ProofOptions::default())
+      .method(self.fragment.as_str())
+      .sign(credential)
+      .map_err(|error| OidcError::CredentialSigning(error.into()))
+  }
+
+  fn sign_jwt(&self, credential: &Credential) -> Result<String> {
+    let method: &VerificationMethod = self
+      .document
+      .resolve_method(self.fragment.as_str(), None)
+      .ok_or_else(|| OidcError::CredentialSigning(identity_did::Error::MethodNotFound.into()))?;
+
+    let header: JwsHeader = VcJwtCredential::header(&method.id().to_string());
+    let claims: JwtClaims<Object> = VcJwtCredential::claims(credential)?;
+    let secret: Jwk = jwk_from_keypair(&self.keypair);
+
+    Encoder::new()
+      .recipient(Recipient::new(&secret).protected(&header))
+      .encode_serde(&claims)
+      .map_err(|error| OidcError::CredentialSigning(error.into()))
+  }
+}
+
+/// The issuer side of OpenID for Verifiable Credential Issuance with the pre-authorized
+/// code flow.
+///
+/// The issuer creates [`CredentialOffer`]s of credential templates, exchanges their
+/// pre-authorized codes for access tokens at the token endpoint, and issues the credentials
+/// at the credential endpoint to the holder proving possession of a key of its DID Document.
+///
+/// [More Info](https://openid.net/specs/openid-4-verifiable-credential-issuance-1_0.html)
+#[derive(Debug)]
+pub struct CredentialIssuer<S> {
+  credential_issuer: Url,
+  signer: S,
+  offer_expires_in: Duration,
+  token_expires_in: Duration,
+  state: Mutex<IssuerState>,
+}
+
+#[derive(Debug, Default)]
+struct IssuerState {
+  offers: BTreeMap<String, PendingOffer>,
+  grants: BTreeMap<String, AccessGrant>,
+}
+
+#[derive(Debug)]
+struct PendingOffer {
+  credentials: BTreeMap<String, Credential>,
+  user_pin: Option<String>,
+  expires_at: i64,
+}
+
+#[derive(Debug)]
+struct AccessGrant {
+  credentials: BTreeMap<String, Credential>,
+  c_nonce: String,
+  expires_at: i64,
+}
+
+impl<S> CredentialIssuer<S>
+where
+  S: CredentialSigner,
+{
+  /// The default lifetime of pre-authorized codes.
+  pub const DEFAULT_OFFER_EXPIRES_IN: Duration = Duration::from_secs(600);
+
+  /// The default lifetime of access tokens and nonces.
+  pub const DEFAULT_TOKEN_EXPIRES_IN: Duration = Duration::from_secs(300);
+
+  /// Creates a new `CredentialIssuer` identified by the URL `credential_issuer` and
+  /// signing credentials with `signer`.
+  pub fn new(credential_issuer: Url, signer: S) -> Self {
+    Self {
+      credential_issuer,
+      signer,
+      offer_expires_in: Self::DEFAULT_OFFER_EXPIRES_IN,
+      token_expires_in: Self::DEFAULT_TOKEN_EXPIRES_IN,
+      state: Mutex::new(IssuerState::default()),
+    }
+  }
+
+  /// Sets the lifetime of pre-authorized codes.
+  #[must_use]
+  pub fn offer_expires_in(mut self, value: Duration) -> Self {
+    self.offer_expires_in = value;
+    self
+  }
+
+  /// Sets the lifetime of access tokens and nonces.
+  #[must_use]
+  pub fn token_expires_in(mut self, value: Duration) -> Self {
+    self.token_expires_in = value;
+    self
+  }
+
+  /// Returns the URL identifying the credential issuer.
+  pub fn credential_issuer(&self) -> &Url {
+    &self.credential_issuer
+  }
+
+  /// Returns the signer of issued credentials.
+  pub fn signer(&self) -> &S {
+    &self.signer
+  }
+
+  /// Creates an offer of `credentials` with a pre-authorized code, optionally protected by
+  /// a `user_pin` transmitted to the user out of band.
+  ///
+  /// Each credential is a template with a single subject; its `id` is set to the DID of the
+  /// holder on issuance. The credentials are offered by their most specific type.
+  ///
+  /// # Errors
+  ///
+  /// Fails if a credential does not have a single subject, or if two credentials have the
+  /// same type.
+  pub fn create_offer(&self, credentials: Vec<Credential>, user_pin: Option<String>) -> Result<CredentialOffer> {
+    let mut templates: BTreeMap<String, Credential> = BTreeMap::new();
+
+    for credential in credentials {
+      if credential.credential_subject.len() != 1 {
+        return Err(OidcError::InvalidCredential("expected a single subject"));
+      }
+
+      let credential_type: String = credential
+        .types
+        .as_slice()
+        .last()
+        .cloned()
+        .ok_or(OidcError::InvalidCredential("missing type"))?;
+
+      if templates.insert(credential_type, credential).is_some() {
+        return Err(OidcError::InvalidCredential("duplicate type"));
+      }
+    }
+
+    let code: String = random_token()?;
+    let credential_types: Vec<String> = templates.keys().cloned().collect();
+    let mut offer: CredentialOffer = CredentialOffer::new(self.credential_issuer.clone(), credential_types);
+    offer.grants.pre_authorized_code = Some(PreAuthorizedCodeGrant {
+      pre_authorized_code: code.clone(),
+      user_pin_required: user_pin.is_some(),
+    });
+
+    self.state()?.offers.insert(
+      code,
+      PendingOffer {
+        credentials: templates,
+        user_pin,
+        expires_at: expires_at(self.offer_expires_in),
+      },
+    );
+
+    Ok(offer)
+  }
+
+  /// Handles a request to the token endpoint, exchanging a pre-authorized code for an
+  /// access token and the first `c_nonce`.
+  ///
+  /// A pre-authorized code can only be redeemed once, including failed attempts.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the grant type is not supported, the code is unknown, redeemed or expired,
+  /// or the user PIN does not match.
+  pub fn token(&self, request: &TokenRequest) -> Result<TokenResponse> {
+    if request.grant_type != PRE_AUTHORIZED_CODE_GRANT {
+      return Err(OidcError::InvalidGrant("unsupported grant_type"));
+    }
+
+    let mut state: MutexGuard<'_, IssuerState> = self.state()?;
+
+    let offer: PendingOffer = state
+      .offers
+      .remove(&request.pre_authorized_code)
+      .ok_or(OidcError::InvalidGrant("unknown pre-authorized code"))?;
+
+    if offer.expires_at <= now() {
+      return Err(OidcError::InvalidGrant("expired pre-authorized code"));
+    }
+
+    if offer.user_pin != request.user_pin {
+      return Err(OidcError::InvalidGrant("user_pin"));
+    }
+
+    let access_token: String = random_token()?;
+    let c_nonce: String = random_token()?;
+
+    state.grants.insert(
+      access_token.clone(),
+      AccessGrant {
+        credentials: offer.credentials,
+        c_nonce: c_nonce.clone(),
+        expires_at: expires_at(self.token_expires_in),
+      },
+    );
+
+    Ok(TokenResponse {
+      access_token,
+      token_type: "bearer".to_owned(),
+      expires_in: self.token_expires_in.as_secs(),
+      c_nonce: Some(c_nonce),
+      c_nonce_expires_in: Some(self.token_expires_in.as_secs()),
+    })
+  }
+
+  /// Handles a request to the credential endpoint authorized by `access_token`, issuing
+  /// the requested credential to `holder`.
+  ///
+  /// The DID Document of the holder can be resolved from the DID returned by
+  /// [`ProofOfPossession::extract_holder`] for the proof of the request.
+  ///
+  /// Each offered credential is issued once. The response contains a fresh `c_nonce` for
+  /// the next request.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the access token is unknown or expired, the credential type was not offered
+  /// or already issued, the format is not supported, the proof of possession is invalid, or
+  /// the credential cannot be signed.
+  pub fn credential<DOC: Document>(
+    &self,
+    access_token: &str,
+    request: &CredentialRequest,
+    holder: &DOC,
+  ) -> Result<CredentialResponse> {
+    let mut state: MutexGuard<'_, IssuerState> = self.state()?;
+
+    if matches!(state.grants.get(access_token), Some(grant) if grant.expires_at <= now()) {
+      state.grants.remove(access_token);
+    }
+
+    let grant: &mut AccessGrant = state.grants.get_mut(access_token).ok_or(OidcError::InvalidToken)?;
+
+    if request.format != FORMAT_LDP_VC && request.format != FORMAT_JWT_VC_JSON {
+      return Err(OidcError::UnsupportedCredentialFormat);
+    }
+
+    let credential_type: &str = request
+      .credential_type()
+      .filter(|credential_type| grant.credentials.contains_key(*credential_type))
+      .ok_or(OidcError::UnsupportedCredentialType)?;
+
+    let proof: &str = match request.proof.as_ref() {
+      Some(proof) if proof.proof_type == PROOF_TYPE_JWT => proof.jwt.as_str(),
+      Some(_) => return Err(OidcError::InvalidProof(libjose::Error::InvalidParam("proof_type"))),
+      None => return Err(OidcError::InvalidProof(libjose::Error::MissingParam("proof"))),
+    };
+
+    ProofOfPossession::verify(proof, holder, self.credential_issuer.as_str(), &grant.c_nonce)?;
+
+    let mut credential: Credential = grant.credentials[credential_type].clone();
+    credential.issuance_date = Timestamp::now_utc();
+
+    if let Some(subject) = credential.credential_subject.get_mut(0) {
+      subject.id = Some(Url::parse(holder.id().as_str()).map_err(|_| OidcError::InvalidCredential("holder"))?);
+    }
+
+    let issued: Value = if request.format == FORMAT_LDP_VC {
+      self.signer.sign_ldp(&mut credential)?;
+      credential
+        .to_json_value()
+        .map_err(|error| OidcError::InvalidJson("credential", error))?
+    } else {
+      Value::String(self.signer.sign_jwt(&credential)?)
+    };
+
+    // Each credential is issued once and each nonce is used once.
+    let c_nonce: String = random_token()?;
+    grant.credentials.remove(credential_type);
+    grant.c_nonce = c_nonce.clone();
+
+    Ok(CredentialResponse {
+      format: request.format.clone(),
+      credential: issued,
+      c_nonce: Some(c_nonce),
+      c_nonce_expires_in: Some(self.token_expires_in.as_secs()),
+    })
+  }
+
+  fn state(&self) -> Result<MutexGuard<'_, IssuerState>> {
+    self.state.lock().map_err(|_| OidcError::StatePoisoned)
+  }
+}
+
+fn random_token() -> Result<String> {
+  random_bytes(32)
+    .map(|bytes| encode_b64(&bytes))
+    .map_err(OidcError::RandomGeneration)
+}
+
+fn now() -> i64 {
+  Timestamp::now_utc().to_unix()
+}
+
+fn expires_at(expires_in: Duration) -> i64 {
+  now().saturating_add(i64::try_from(expires_in.as_secs()).unwrap_or(i64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::oidc::CredentialRequestProof;
+  use crate::validator::test_utils;
+
+  use super::*;
+
+  struct TestSetup {
+    issuer: CredentialIssuer<DocumentCredentialSigner>,
+    holder_doc: CoreDocument,
+    holder_key: KeyPair,
+  }
+
+  impl TestSetup {
+    fn new() -> Self {
+      let (issuer_doc, issuer_key) = test_utils::generate_document_with_keys();
+      let (holder_doc, holder_key) = test_utils::generate_document_with_keys();
+
+      let signer: DocumentCredentialSigner = DocumentCredentialSigner::new(issuer_doc, issuer_key, "#root");
+      let issuer: CredentialIssuer<DocumentCredentialSigner> =
+        CredentialIssuer::new(Url::parse("https://issuer.example.com").unwrap(), signer);
+
+      Self {
+        issuer,
+        holder_doc,
+        holder_key,
+      }
+    }
+
+    fn template(&self) -> Credential {
+      let (subject_doc, _) = test_utils::generate_document_with_keys();
+
+      test_utils::generate_credential(
+        self.issuer.signer().document(),
+        &[subject_doc],
+        Timestamp::parse("2020-01-01T00:00:00Z").unwrap(),
+        Timestamp::parse("2100-01-01T00:00:00Z").unwrap(),
+      )
+    }
+
+    fn proof(&self, c_nonce: &str) -> CredentialRequestProof {
+      let kid: String = self.holder_doc.methods().next().unwrap().id().to_string();
+      let header: JwsHeader = ProofOfPossession::header(&kid);
+      let claims: JwtClaims<Object> = ProofOfPossession::claims(self.issuer.credential_issuer().as_str(), c_nonce);
+      let secret: Jwk = jwk_from_keypair(&self.holder_key);
+
+      let jwt: String = Encoder::new()
+        .recipient(Recipient::new(&secret).protected(&header))
+        .encode_serde(&claims)
+        .unwrap();
+
+      CredentialRequestProof::new_jwt(jwt)
+    }
+
+    fn request(&self, format: &str, c_nonce: &str) -> CredentialRequest {
+      let types: Vec<String> = vec![
+        "VerifiableCredential".to_owned(),
+        "UniversityDegreeCredential".to_owned(),
+      ];
+      let mut request: CredentialRequest = CredentialRequest::new(format, types);
+      request.proof = Some(self.proof(c_nonce));
+      request
+    }
+  }
+
+  #[test]
+  fn test_issue_ldp_credential() {
+    let setup: TestSetup = TestSetup::new();
+    let offer: CredentialOffer = setup.issuer.create_offer(vec![setup.template()], None).unwrap();
+    assert_eq!(offer.credentials, ["UniversityDegreeCredential"]);
+
+    let code: String = offer.pre_authorized_code().unwrap().pre_authorized_code.clone();
+    let token: TokenResponse = setup
+      .issuer
+      .token(&TokenRequest::new_pre_authorized(code, None))
+      .unwrap();
+    let c_nonce: String = token.c_nonce.unwrap();
+
+    let response: CredentialResponse = setup
+      .issuer
+      .credential(
+        &token.access_token,
+        &setup.request(FORMAT_LDP_VC, &c_nonce),
+        &setup.holder_doc,
+      )
+      .unwrap();
+
+    let credential: Credential = response.ldp_credential().unwrap();
+    assert_eq!(
+      credential
+        .credential_subject
+        .get(0)
+        .unwrap()
+        .id
+        .as_ref()
+        .unwrap()
+        .as_str(),
+      setup.holder_doc.id().as_str()
+    );
+    assert!(crate::validator::CredentialValidator::verify_signature(
+      &credential,
+      &[setup.issuer.signer().document()],
+      &identity_did::verifiable::VerifierOptions::default()
+    )
+    .is_ok());
+    assert_ne!(response.c_nonce.as_deref(), Some(c_nonce.as_str()));
+
+    // Each offered credential is issued once.
+    let new_nonce: String = response.c_nonce.unwrap();
+    assert!(matches!(
+      setup.issuer.credential(
+        &token.access_token,
+        &setup.request(FORMAT_LDP_VC, &new_nonce),
+        &setup.holder_doc
+      ),
+      Err(OidcError::UnsupportedCredentialType)
+    ));
+  }
+
+  #[test]
+  fn test_issue_jwt_credential() {
+    let setup: TestSetup = TestSetup::new();
+    let offer: CredentialOffer = setup.issuer.create_offer(vec![setup.template()], None).unwrap();
+    let code: String = offer.pre_authorized_code().unwrap().pre_authorized_code.clone();
+    let token: TokenResponse = setup
+      .issuer
+      .token(&TokenRequest::new_pre_authorized(code, None))
+      .unwrap();
+
+    let response: CredentialResponse = setup
+      .issuer
+      .credential(
+        &token.access_token,
+        &setup.request(FORMAT_JWT_VC_JSON, token.c_nonce.as_deref().unwrap()),
+        &setup.holder_doc,
+      )
+      .unwrap();
+
+    let jwt: &str = response.jwt_credential().unwrap();
+    let credential: Credential = VcJwtCredential::decode(jwt, setup.issuer.signer().document()).unwrap();
+    assert!(credential.proof.is_empty());
+    assert_eq!(
+      credential
+        .credential_subject
+        .get(0)
+        .unwrap()
+        .id
+        .as_ref()
+        .unwrap()
+        .as_str(),
+      setup.holder_doc.id().as_str()
+    );
+
+    // The VC-JWT is not accepted for another issuer.
+    assert!(VcJwtCredential::decode(jwt, &setup.holder_doc).is_err());
+  }
+
+  #[test]
+  fn test_token_pre_authorized_code() {
+    let setup: TestSetup = TestSetup::new();
+    let offer: CredentialOffer = setup
+      .issuer
+      .create_offer(vec![setup.template()], Some("1234".to_owned()))
+      .unwrap();
+    let grant: &PreAuthorizedCodeGrant = offer.pre_authorized_code().unwrap();
+    assert!(grant.user_pin_required);
+
+    // A wrong PIN consumes the code.
+    let request: TokenRequest = TokenRequest::new_pre_authorized(grant.pre_authorized_code.clone(), None);
+    assert!(matches!(
+      setup.issuer.token(&request),
+      Err(OidcError::InvalidGrant("user_pin"))
+    ));
+
+    let request: TokenRequest =
+      TokenRequest::new_pre_authorized(grant.pre_authorized_code.clone(), Some("1234".to_owned()));
+    assert!(matches!(setup.issuer.token(&request), Err(OidcError::InvalidGrant(_))));
+
+    // Expired codes are rejected.
+    let issuer: CredentialIssuer<DocumentCredentialSigner> = setup.issuer.offer_expires_in(Duration::ZERO);
+    let offer: CredentialOffer = issuer.create_offer(Vec::new(), None).unwrap();
+    let code: String = offer.pre_authorized_code().unwrap().pre_authorized_code.clone();
+    assert!(matches!(
+      issuer.token(&TokenRequest::new_pre_authorized(code, None)),
+      Err(OidcError::InvalidGrant("expired pre-authorized code"))
+    ));
+  }
+
+  #[test]
+  fn test_credential_invalid_proof() {
+    let setup: TestSetup = TestSetup::new();
+    let offer: CredentialOffer = setup.issuer.create_offer(vec![setup.template()], None).unwrap();
+    let code: String = offer.pre_authorized_code().unwrap().pre_authorized_code.clone();
+    let token: TokenResponse = setup
+      .issuer
+      .token(&TokenRequest::new_pre_authorized(code, None))
+      .unwrap();
+    let c_nonce: &str = token.c_nonce.as_deref().unwrap();
+
+    // Wrong nonce.
+    let request: CredentialRequest = setup.request(FORMAT_LDP_VC, "replayed");
+    assert!(matches!(
+      setup
+        .issuer
+        .credential(&token.access_token, &request, &setup.holder_doc),
+      Err(OidcError::InvalidProof(libjose::Error::InvalidClaim("nonce")))
+    ));
+
+    // Missing proof.
+    let mut request: CredentialRequest = setup.request(FORMAT_LDP_VC, c_nonce);
+    request.proof = None;
+    assert!(matches!(
+      setup
+        .issuer
+        .credential(&token.access_token, &request, &setup.holder_doc),
+      Err(OidcError::InvalidProof(_))
+    ));
+
+    // Signed by a key of another DID.
+    let (other_doc, _) = test_utils::generate_document_with_keys();
+    let request: CredentialRequest = setup.request(FORMAT_LDP_VC, c_nonce);
+    assert!(matches!(
+      setup.issuer.credential(&token.access_token, &request, &other_doc),
+      Err(OidcError::InvalidProof(_))
+    ));
+
+    // Unknown access token and format.
+    assert!(matches!(
+      setup.issuer.credential("unknown", &request, &setup.holder_doc),
+      Err(OidcError::InvalidToken)
+    ));
+    let request: CredentialRequest = setup.request("mso_mdoc", c_nonce);
+    assert!(matches!(
+      setup
+        .issuer
+        .credential(&token.access_token, &request, &setup.holder_doc),
+      Err(OidcError::UnsupportedCredentialFormat)
+    ));
+  }
+}
@@ -0,0 +1,103 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Url;
+use identity_core::convert::FromJson;
+use identity_core::convert::ToJson;
+
+use crate::oidc::OidcError;
+
+type Result<T, E = OidcError> = ::core::result::Result<T, E>;
+
+/// Grant type of the pre-authorized code flow.
+pub const PRE_AUTHORIZED_CODE_GRANT: &str = "urn:ietf:params:oauth:grant-type:pre-authorized_code";
+
+/// An offer of a credential issuer to issue credentials to a wallet.
+///
+/// [More Info](https://openid.net/specs/openid-4-verifiable-credential-issuance-1_0.html#name-credential-offer)
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CredentialOffer {
+  /// The URL of the credential issuer, used as audience of the proof of possession.
+  pub credential_issuer: Url,
+  /// The types of the offered credentials, e.g. `UniversityDegreeCredential`.
+  pub credentials: Vec<String>,
+  /// The grants the wallet may use to obtain an access token.
+  #[serde(default, skip_serializing_if = "Grants::is_empty")]
+  pub grants: Grants,
+}
+
+impl CredentialOffer {
+  /// Creates a new `CredentialOffer` of `credentials` without grants.
+  pub fn new(credential_issuer: Url, credentials: Vec<String>) -> Self {
+    Self {
+      credential_issuer,
+      credentials,
+      grants: Grants::default(),
+    }
+  }
+
+  /// Returns the pre-authorized code grant of the offer, if present.
+  pub fn pre_authorized_code(&self) -> Option<&PreAuthorizedCodeGrant> {
+    self.grants.pre_authorized_code.as_ref()
+  }
+
+  /// Appends the offer to the query of `base`, e.g. `openid-credential-offer://`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the offer cannot be serialized.
+  pub fn to_url(&self, mut base: Url) -> Result<Url> {
+    let json: String = self
+      .to_json()
+      .map_err(|error| OidcError::InvalidJson("credential_offer", error))?;
+
+    base.query_pairs_mut().append_pair("credential_offer", &json);
+
+    Ok(base)
+  }
+
+  /// Parses an offer passed by value in the `credential_offer` parameter of `url`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the parameter is missing or invalid.
+  pub fn from_url(url: &Url) -> Result<Self> {
+    let json: String = url
+      .query_pairs()
+      .find(|(key, _)| key == "credential_offer")
+      .map(|(_, value)| value.into_owned())
+      .ok_or(OidcError::InvalidRequest("missing credential_offer"))?;
+
+    Self::from_json(&json).map_err(|error| OidcError::InvalidJson("credential_offer", error))
+  }
+}
+
+/// The grants of a [`CredentialOffer`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Grants {
+  /// The grant of the pre-authorized code flow.
+  #[serde(
+    rename = "urn:ietf:params:oauth:grant-type:pre-authorized_code",
+    skip_serializing_if = "Option::is_none"
+  )]
+  pub pre_authorized_code: Option<PreAuthorizedCodeGrant>,
+}
+
+impl Grants {
+  /// Returns `true` if no grants are present.
+  pub fn is_empty(&self) -> bool {
+    self.pre_authorized_code.is_none()
+  }
+}
+
+/// A code that authorizes the wallet to obtain an access token without user interaction
+/// with the issuer.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PreAuthorizedCodeGrant {
+  /// The pre-authorized code, exchanged for an access token at the token endpoint.
+  #[serde(rename = "pre-authorized_code")]
+  pub pre_authorized_code: String,
+  /// Whether the user must provide a PIN, transmitted out of band, to redeem the code.
+  #[serde(default)]
+  pub user_pin_required: bool,
+}
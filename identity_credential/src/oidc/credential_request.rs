@@ -0,0 +1,116 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Value;
+use identity_core::convert::FromJson;
+
+use crate::credential::Credential;
+use crate::oidc::OidcError;
+use crate::oidc::FORMAT_LDP_VC;
+
+/// Format identifier of Verifiable Credentials encoded as JWTs.
+pub const FORMAT_JWT_VC_JSON: &str = "jwt_vc_json";
+
+/// Proof type of a proof of possession encoded as JWT.
+pub const PROOF_TYPE_JWT: &str = "jwt";
+
+/// A request to the credential endpoint of a credential issuer.
+///
+/// [More Info](https://openid.net/specs/openid-4-verifiable-credential-issuance-1_0.html#name-credential-request)
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CredentialRequest {
+  /// The requested format, i.e. [`FORMAT_LDP_VC`] or [`FORMAT_JWT_VC_JSON`].
+  pub format: String,
+  /// The types of the requested credential; the last one is the offered type.
+  pub types: Vec<String>,
+  /// The proof of possession of the key the credential is bound to.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub proof: Option<CredentialRequestProof>,
+}
+
+impl CredentialRequest {
+  /// Creates a new `CredentialRequest` for a credential of the given `format` and `types`.
+  pub fn new(format: impl Into<String>, types: Vec<String>) -> Self {
+    Self {
+      format: format.into(),
+      types,
+      proof: None,
+    }
+  }
+
+  /// Returns the offered credential type requested, i.e. the most specific type.
+  pub fn credential_type(&self) -> Option<&str> {
+    self.types.last().map(String::as_str)
+  }
+}
+
+/// A proof of possession of the key a credential is bound to.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CredentialRequestProof {
+  /// The proof type, i.e. [`PROOF_TYPE_JWT`].
+  pub proof_type: String,
+  /// The proof JWT, see [`ProofOfPossession`](crate::oidc::ProofOfPossession).
+  pub jwt: String,
+}
+
+impl CredentialRequestProof {
+  /// Creates a new `CredentialRequestProof` of type [`PROOF_TYPE_JWT`].
+  pub fn new_jwt(jwt: impl Into<String>) -> Self {
+    Self {
+      proof_type: PROOF_TYPE_JWT.to_owned(),
+      jwt: jwt.into(),
+    }
+  }
+}
+
+/// A successful response of the credential endpoint of a credential issuer.
+///
+/// [More Info](https://openid.net/specs/openid-4-verifiable-credential-issuance-1_0.html#name-credential-response)
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CredentialResponse {
+  /// The format of the issued credential.
+  pub format: String,
+  /// The issued credential, a JSON object secured with a Linked Data Proof or a VC-JWT string.
+  pub credential: Value,
+  /// The nonce the next proof of possession MUST include.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub c_nonce: Option<String>,
+  /// The lifetime of the `c_nonce` in seconds.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub c_nonce_expires_in: Option<u64>,
+}
+
+impl CredentialResponse {
+  /// Returns the issued credential of format [`FORMAT_LDP_VC`].
+  ///
+  /// The proof of the credential is **not** verified, see
+  /// [`CredentialValidator`](crate::validator::CredentialValidator).
+  ///
+  /// # Errors
+  ///
+  /// Fails if the response has another format or the credential is invalid.
+  pub fn ldp_credential(&self) -> Result<Credential, OidcError> {
+    if self.format != FORMAT_LDP_VC {
+      return Err(OidcError::UnsupportedCredentialFormat);
+    }
+
+    Credential::from_json_value(self.credential.clone()).map_err(|error| OidcError::InvalidJson("credential", error))
+  }
+
+  /// Returns the issued VC-JWT of format [`FORMAT_JWT_VC_JSON`], see
+  /// [`VcJwtCredential::decode`](crate::oidc::VcJwtCredential::decode).
+  ///
+  /// # Errors
+  ///
+  /// Fails if the response has another format or the credential is not a string.
+  pub fn jwt_credential(&self) -> Result<&str, OidcError> {
+    if self.format != FORMAT_JWT_VC_JSON {
+      return Err(OidcError::UnsupportedCredentialFormat);
+    }
+
+    self
+      .credential
+      .as_str()
+      .ok_or(OidcError::InvalidCredential("expected VC-JWT string"))
+  }
+}
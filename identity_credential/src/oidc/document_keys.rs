@@ -0,0 +1,79 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::crypto::KeyType;
+use identity_core::crypto::PublicKey;
+use identity_did::did::DID;
+use identity_did::document::Document;
+use identity_did::verification::jwk_from_public_key;
+use identity_did::verification::Jwk;
+use identity_did::verification::MethodScope;
+use identity_did::verification::MethodType;
+use identity_did::verification::VerificationMethod;
+use libjose::jws::JwkResolver;
+use libjose::jws::JwsAlgorithm;
+
+/// Resolves the keys of JWS signed by a DID subject from its DID Document.
+///
+/// The `kid` header parameter of the token MUST be the DID URL of a verification
+/// method in the document. Ed25519 methods and methods with a `publicKeyJwk` are
+/// supported. Resolution can be restricted to a verification relationship with
+/// [`DocumentKeys::scope`].
+#[derive(Clone, Copy, Debug)]
+pub struct DocumentKeys<'a, DOC> {
+  document: &'a DOC,
+  scope: Option<MethodScope>,
+}
+
+impl<'a, DOC> DocumentKeys<'a, DOC>
+where
+  DOC: Document,
+{
+  /// Creates a new `DocumentKeys` resolving keys from `document`.
+  pub fn new(document: &'a DOC) -> Self {
+    Self { document, scope: None }
+  }
+
+  /// Only resolves keys of verification methods in the given `scope`, e.g.
+  /// [`MethodScope::authentication`].
+  pub fn scope(mut self, scope: MethodScope) -> Self {
+    self.scope = Some(scope);
+    self
+  }
+
+  /// Returns the public key of the verification method identified by the DID URL `kid`,
+  /// if it belongs to the document and the configured scope.
+  pub fn resolve_jwk(&self, kid: &str) -> Option<Jwk> {
+    // Only methods of the document subject may sign on its behalf.
+    let (did, _): (&str, &str) = kid.split_once('#')?;
+
+    if did != self.document.id().as_str() {
+      return None;
+    }
+
+    method_jwk(self.document.resolve_method(kid, self.scope)?)
+  }
+}
+
+impl<DOC> JwkResolver for DocumentKeys<'_, DOC>
+where
+  DOC: Document,
+{
+  fn resolve(&self, kid: Option<&str>, _algorithm: JwsAlgorithm) -> libjose::Result<Vec<Jwk>> {
+    Ok(kid.and_then(|kid| self.resolve_jwk(kid)).into_iter().collect())
+  }
+}
+
+fn method_jwk<D: DID, U>(method: &VerificationMethod<D, U>) -> Option<Jwk> {
+  if let Some(jwk) = method.data().public_key_jwk() {
+    return Some(jwk.clone());
+  }
+
+  match method.type_() {
+    MethodType::Ed25519VerificationKey2018 => {
+      let public_key: PublicKey = method.data().try_decode().ok()?.into();
+      Some(jwk_from_public_key(KeyType::Ed25519, &public_key))
+    }
+    MethodType::X25519KeyAgreementKey2019 | MethodType::JsonWebKey2020 => None,
+  }
+}
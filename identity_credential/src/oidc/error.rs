@@ -0,0 +1,108 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::validator::CompoundPresentationValidationError;
+
+/// An error caused by an invalid SIOPv2 or OpenID4VP request or response.
+#[derive(Debug, thiserror::Error, strum::IntoStaticStr)]
+#[non_exhaustive]
+pub enum OidcError {
+  /// Caused by an authorization request with a missing or invalid parameter.
+  #[error("invalid authorization request: {0}")]
+  InvalidRequest(&'static str),
+  /// Caused by an authorization response with a missing or invalid parameter.
+  #[error("invalid authorization response: {0}")]
+  InvalidResponse(&'static str),
+  /// Caused by a request object that could not be decoded or verified.
+  #[error("invalid request object")]
+  InvalidRequestObject(#[source] libjose::Error),
+  /// Caused by an ID token that could not be decoded, verified or validated.
+  #[error("invalid id token")]
+  InvalidIdToken(#[source] libjose::Error),
+  /// Caused by a presentation submission that does not match the presentation definition.
+  #[error("invalid presentation submission: {0}")]
+  InvalidPresentationSubmission(&'static str),
+  /// Caused by a presentation in the `vp_token` that failed validation.
+  #[error("invalid presentation")]
+  InvalidPresentation(#[source] CompoundPresentationValidationError),
+  /// Caused by a parameter with invalid JSON content.
+  #[error("invalid JSON parameter `{0}`")]
+  InvalidJson(&'static str, #[source] identity_core::Error),
+  /// Caused by an unknown, expired or already redeemed authorization grant, or a wrong user PIN.
+  #[error("invalid grant: {0}")]
+  InvalidGrant(&'static str),
+  /// Caused by an unknown or expired access token.
+  #[error("invalid access token")]
+  InvalidToken,
+  /// Caused by a missing or invalid proof of possession of the holder key.
+  #[error("invalid proof of possession")]
+  InvalidProof(#[source] libjose::Error),
+  /// Caused by a request for a credential type that was not offered.
+  #[error("unsupported credential type")]
+  UnsupportedCredentialType,
+  /// Caused by a request for an unsupported credential format.
+  #[error("unsupported credential format")]
+  UnsupportedCredentialFormat,
+  /// Caused by a credential that could not be issued or decoded.
+  #[error("invalid credential: {0}")]
+  InvalidCredential(&'static str),
+  /// Caused by a VC-JWT that could not be encoded, decoded or verified.
+  #[error("invalid VC-JWT")]
+  InvalidCredentialJwt(#[source] libjose::Error),
+  /// Caused by a failure to sign an issued credential.
+  #[error("credential signing failed")]
+  CredentialSigning(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+  /// Caused by a failure to generate a random code, access token or nonce.
+  #[error("random generation failed")]
+  RandomGeneration(#[source] libjose::Error),
+  /// Caused by attempting to access the poisoned state of a credential issuer.
+  #[error("credential issuer state poisoned")]
+  StatePoisoned,
+  /// Caused by an error response of a remote credential issuer.
+  #[error("credential issuer error: {}", .0.error)]
+  IssuerError(ErrorResponse),
+  /// Caused by a failure of the transport to a remote credential issuer.
+  #[error("transport error")]
+  TransportError(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+}
+
+impl OidcError {
+  /// Returns the OAuth 2.0 error code of the error, e.g. `invalid_grant`.
+  ///
+  /// [More Info](https://openid.net/specs/openid-4-verifiable-credential-issuance-1_0.html#name-credential-error-response)
+  pub fn error_code(&self) -> &str {
+    match self {
+      Self::InvalidGrant(_) => "invalid_grant",
+      Self::InvalidToken => "invalid_token",
+      Self::InvalidProof(_) => "invalid_proof",
+      Self::UnsupportedCredentialType => "unsupported_credential_type",
+      Self::UnsupportedCredentialFormat => "unsupported_credential_format",
+      Self::IssuerError(response) => response.error.as_str(),
+      Self::CredentialSigning(_) | Self::RandomGeneration(_) | Self::StatePoisoned | Self::TransportError(_) => {
+        "server_error"
+      }
+      _ => "invalid_request",
+    }
+  }
+
+  /// Returns the error response sent by a credential issuer for this error.
+  pub fn to_error_response(&self) -> ErrorResponse {
+    match self {
+      Self::IssuerError(response) => response.clone(),
+      _ => ErrorResponse {
+        error: self.error_code().to_owned(),
+        error_description: Some(self.to_string()),
+      },
+    }
+  }
+}
+
+/// An OAuth 2.0 error response returned by the token and credential endpoints.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ErrorResponse {
+  /// The error code, e.g. `invalid_grant`.
+  pub error: String,
+  /// A human-readable description of the error.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub error_description: Option<String>,
+}
@@ -0,0 +1,81 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::time::Duration;
+
+use identity_core::common::Object;
+use identity_core::common::Timestamp;
+use identity_core::common::Value;
+use identity_core::convert::FromJson;
+use identity_core::utils::Base;
+use identity_core::utils::BaseEncoding;
+use identity_did::did::DID;
+use libjose::jws::JwsAlgorithm;
+use libjose::jws::JwsHeader;
+use libjose::jwt::JwtClaims;
+
+use crate::oidc::AuthorizationRequest;
+use crate::oidc::OidcError;
+
+/// Helpers for Self-Issued ID Tokens using the DID subject syntax type.
+///
+/// The issuer (iss) and subject (sub) of the token are the DID of the holder, and the
+/// token is signed by a verification method of its DID Document.
+///
+/// [More Info](https://openid.net/specs/openid-connect-self-issued-v2-1_0.html#name-self-issued-id-token)
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct SelfIssuedIdToken;
+
+impl SelfIssuedIdToken {
+  /// Returns the claims of an ID token issued by `did` in response to `request`, valid for `expires_in`.
+  pub fn claims(did: &str, request: &AuthorizationRequest, expires_in: Duration) -> JwtClaims<Object> {
+    let issued_at: i64 = Timestamp::now_utc().to_unix();
+    let expires_in: i64 = i64::try_from(expires_in.as_secs()).unwrap_or(i64::MAX);
+
+    let mut custom: Object = Object::new();
+    custom.insert("nonce".to_owned(), Value::String(request.nonce.clone()));
+
+    let mut claims: JwtClaims<Object> = JwtClaims::new();
+    claims.set_iss(did);
+    claims.set_sub(did);
+    claims.set_aud([request.client_id.as_str()]);
+    claims.set_iat(issued_at);
+    claims.set_exp(issued_at.saturating_add(expires_in));
+    claims.set_custom(custom);
+    claims
+  }
+
+  /// Returns the protected header of an ID token signed with EdDSA by the verification
+  /// method identified by the DID URL `kid`.
+  pub fn header(kid: &str) -> JwsHeader {
+    let mut header: JwsHeader = JwsHeader::new(JwsAlgorithm::EdDSA);
+    header.set_typ("JWT");
+    header.set_kid(kid);
+    header
+  }
+
+  /// Returns the DID of the issuer of the `id_token` **without verifying the token**.
+  ///
+  /// Used to resolve the DID Document required to validate the token, see
+  /// [`ResponseValidator::validate_id_token`](crate::oidc::ResponseValidator::validate_id_token).
+  ///
+  /// # Errors
+  ///
+  /// Fails if the token is malformed or the issuer is not a valid DID.
+  pub fn extract_issuer<D: DID>(id_token: &str) -> Result<D, OidcError> {
+    let payload: &str = id_token
+      .split('.')
+      .nth(1)
+      .ok_or(OidcError::InvalidResponse("malformed id_token"))?;
+    let payload: Vec<u8> =
+      BaseEncoding::decode(payload, Base::Base64Url).map_err(|error| OidcError::InvalidJson("id_token", error))?;
+    let claims: JwtClaims<Object> =
+      JwtClaims::from_json_slice(&payload).map_err(|error| OidcError::InvalidJson("id_token", error))?;
+
+    claims
+      .iss()
+      .and_then(|iss| D::from_str(iss).ok())
+      .ok_or(OidcError::InvalidResponse("invalid id_token issuer"))
+  }
+}
@@ -0,0 +1,53 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Self-Issued OpenID Provider v2 (SIOPv2) and OpenID for Verifiable Presentations (OpenID4VP)
+//! authorization requests and responses, and OpenID for Verifiable Credential Issuance (OpenID4VCI).
+
+pub use self::credential_issuer::CredentialIssuer;
+pub use self::credential_issuer::CredentialSigner;
+pub use self::credential_issuer::DocumentCredentialSigner;
+pub use self::credential_offer::CredentialOffer;
+pub use self::credential_offer::Grants;
+pub use self::credential_offer::PreAuthorizedCodeGrant;
+pub use self::credential_offer::PRE_AUTHORIZED_CODE_GRANT;
+pub use self::credential_request::CredentialRequest;
+pub use self::credential_request::CredentialRequestProof;
+pub use self::credential_request::CredentialResponse;
+pub use self::credential_request::FORMAT_JWT_VC_JSON;
+pub use self::credential_request::PROOF_TYPE_JWT;
+pub use self::document_keys::DocumentKeys;
+pub use self::error::ErrorResponse;
+pub use self::error::OidcError;
+pub use self::id_token::SelfIssuedIdToken;
+pub use self::presentation_exchange::InputDescriptor;
+pub use self::presentation_exchange::InputDescriptorMapping;
+pub use self::presentation_exchange::PresentationDefinition;
+pub use self::presentation_exchange::PresentationSubmission;
+pub use self::presentation_exchange::FORMAT_LDP_VC;
+pub use self::presentation_exchange::FORMAT_LDP_VP;
+pub use self::proof_of_possession::ProofOfPossession;
+pub use self::request::AuthorizationRequest;
+pub use self::response::AuthorizationResponse;
+pub use self::token::TokenRequest;
+pub use self::token::TokenResponse;
+pub use self::validator::ResponseValidator;
+pub use self::vc_jwt::VcJwtCredential;
+pub use self::wallet_client::IssuerTransport;
+pub use self::wallet_client::LocalTransport;
+pub use self::wallet_client::WalletClient;
+
+mod credential_issuer;
+mod credential_offer;
+mod credential_request;
+mod document_keys;
+mod error;
+mod id_token;
+mod presentation_exchange;
+mod proof_of_possession;
+mod request;
+mod response;
+mod token;
+mod validator;
+mod vc_jwt;
+mod wallet_client;
@@ -0,0 +1,116 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Object;
+
+/// Format identifier of Verifiable Presentations secured with Linked Data Proofs.
+pub const FORMAT_LDP_VP: &str = "ldp_vp";
+
+/// Format identifier of Verifiable Credentials secured with Linked Data Proofs.
+pub const FORMAT_LDP_VC: &str = "ldp_vc";
+
+/// Describes the proofs a verifier requires, see
+/// [DIF Presentation Exchange](https://identity.foundation/presentation-exchange/spec/v2.0.0/#presentation-definition).
+///
+/// Only the identifiers are interpreted, constraints are preserved as-is.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PresentationDefinition {
+  /// The unique identifier of the definition.
+  pub id: String,
+  /// The inputs required by the verifier.
+  pub input_descriptors: Vec<InputDescriptor>,
+  /// Miscellaneous properties, e.g. `name`, `purpose` and `format`.
+  #[serde(flatten)]
+  pub properties: Object,
+}
+
+impl PresentationDefinition {
+  /// Creates a new `PresentationDefinition` with the given `id` and `input_descriptors`.
+  pub fn new(id: impl Into<String>, input_descriptors: Vec<InputDescriptor>) -> Self {
+    Self {
+      id: id.into(),
+      input_descriptors,
+      properties: Object::new(),
+    }
+  }
+
+  /// Returns the [`InputDescriptor`] with the given `id`, if present.
+  pub fn input_descriptor(&self, id: &str) -> Option<&InputDescriptor> {
+    self.input_descriptors.iter().find(|descriptor| descriptor.id == id)
+  }
+}
+
+/// Describes a single input required by a [`PresentationDefinition`].
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct InputDescriptor {
+  /// The identifier of the input, unique within the definition.
+  pub id: String,
+  /// Miscellaneous properties, e.g. `name`, `purpose` and `constraints`.
+  #[serde(flatten)]
+  pub properties: Object,
+}
+
+impl InputDescriptor {
+  /// Creates a new `InputDescriptor` with the given `id`.
+  pub fn new(id: impl Into<String>) -> Self {
+    Self {
+      id: id.into(),
+      properties: Object::new(),
+    }
+  }
+}
+
+/// Describes how the submitted proofs satisfy a [`PresentationDefinition`], see
+/// [DIF Presentation Exchange](https://identity.foundation/presentation-exchange/spec/v2.0.0/#presentation-submission).
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PresentationSubmission {
+  /// The unique identifier of the submission.
+  pub id: String,
+  /// The identifier of the [`PresentationDefinition`] this submission fulfills.
+  pub definition_id: String,
+  /// Maps the input descriptors of the definition to the submitted proofs.
+  pub descriptor_map: Vec<InputDescriptorMapping>,
+}
+
+impl PresentationSubmission {
+  /// Creates a new `PresentationSubmission` for the given `definition` with an empty descriptor map.
+  pub fn new(id: impl Into<String>, definition: &PresentationDefinition) -> Self {
+    Self {
+      id: id.into(),
+      definition_id: definition.id.clone(),
+      descriptor_map: Vec::new(),
+    }
+  }
+
+  /// Maps the input descriptor `id` to the credential at `index` of an `ldp_vp` presentation
+  /// submitted as the `vp_token`.
+  pub fn map_credential(&mut self, id: impl Into<String>, index: usize) {
+    let id: String = id.into();
+
+    self.descriptor_map.push(InputDescriptorMapping {
+      id: id.clone(),
+      format: FORMAT_LDP_VP.to_owned(),
+      path: "$".to_owned(),
+      path_nested: Some(Box::new(InputDescriptorMapping {
+        id,
+        format: FORMAT_LDP_VC.to_owned(),
+        path: format!("$.verifiableCredential[{}]", index),
+        path_nested: None,
+      })),
+    });
+  }
+}
+
+/// Maps an [`InputDescriptor`] to a submitted proof.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct InputDescriptorMapping {
+  /// The identifier of the [`InputDescriptor`].
+  pub id: String,
+  /// The format of the proof, e.g. `ldp_vp`.
+  pub format: String,
+  /// A JSONPath expression selecting the proof, e.g. `$` for the `vp_token` itself.
+  pub path: String,
+  /// The mapping of a proof embedded in the selected proof, e.g. a credential in a presentation.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub path_nested: Option<Box<InputDescriptorMapping>>,
+}
@@ -0,0 +1,119 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Object;
+use identity_core::common::Timestamp;
+use identity_core::common::Value;
+use identity_core::convert::FromJson;
+use identity_core::utils::Base;
+use identity_core::utils::BaseEncoding;
+use identity_did::did::DID;
+use identity_did::document::Document;
+use libjose::jws::Decoder;
+use libjose::jws::JwsAlgorithm;
+use libjose::jws::JwsHeader;
+use libjose::jwt::CoreProfile;
+use libjose::jwt::JwtClaims;
+use libjose::jwt::JwtToken;
+use libjose::jwt::JwtValidator;
+use libjose::jwt::Validation;
+
+use crate::oidc::DocumentKeys;
+use crate::oidc::OidcError;
+
+type Result<T, E = OidcError> = ::core::result::Result<T, E>;
+
+/// Helpers for proofs of possession of a holder key, sent as JWT in a
+/// [`CredentialRequest`](crate::oidc::CredentialRequest).
+///
+/// The proof is signed by a verification method of the holder's DID Document, identified
+/// by the DID URL in the `kid` header parameter. The issued credential is bound to the DID.
+///
+/// [More Info](https://openid.net/specs/openid-4-verifiable-credential-issuance-1_0.html#name-jwt-proof-type)
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ProofOfPossession;
+
+impl ProofOfPossession {
+  /// The media type of a proof of possession.
+  pub const TYP: &'static str = "openid4vci-proof+jwt";
+
+  /// Returns the claims of a proof for the credential issuer `credential_issuer` including
+  /// the `c_nonce` provided by the issuer.
+  pub fn claims(credential_issuer: &str, c_nonce: &str) -> JwtClaims<Object> {
+    let mut custom: Object = Object::new();
+    custom.insert("nonce".to_owned(), Value::String(c_nonce.to_owned()));
+
+    let mut claims: JwtClaims<Object> = JwtClaims::new();
+    claims.set_aud([credential_issuer]);
+    claims.set_iat(Timestamp::now_utc().to_unix());
+    claims.set_custom(custom);
+    claims
+  }
+
+  /// Returns the protected header of a proof signed with EdDSA by the verification
+  /// method identified by the DID URL `kid`.
+  pub fn header(kid: &str) -> JwsHeader {
+    let mut header: JwsHeader = JwsHeader::new(JwsAlgorithm::EdDSA);
+    header.set_typ(Self::TYP);
+    header.set_kid(kid);
+    header
+  }
+
+  /// Returns the DID of the holder from the `kid` of the proof **without verifying the proof**.
+  ///
+  /// Used to resolve the DID Document required to verify the proof, see
+  /// [`ProofOfPossession::verify`].
+  ///
+  /// # Errors
+  ///
+  /// Fails if the proof is malformed or the `kid` is not a DID URL.
+  pub fn extract_holder<D: DID>(jwt: &str) -> Result<D> {
+    let header: &str = jwt.split('.').next().unwrap_or_default();
+    let header: Vec<u8> =
+      BaseEncoding::decode(header, Base::Base64Url).map_err(|error| OidcError::InvalidJson("proof", error))?;
+    let header: JwsHeader =
+      JwsHeader::from_json_slice(&header).map_err(|error| OidcError::InvalidJson("proof", error))?;
+
+    header
+      .kid()
+      .and_then(|kid| kid.split_once('#'))
+      .and_then(|(did, _)| D::from_str(did).ok())
+      .ok_or(OidcError::InvalidProof(libjose::Error::InvalidParam("kid")))
+  }
+
+  /// Verifies a proof of possession of a key of `holder`, returning the validated token.
+  ///
+  /// The proof MUST be signed by a verification method of `holder`, be explicitly typed,
+  /// have the `credential_issuer` as audience and include the `c_nonce` of the issuer.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the proof cannot be verified or does not satisfy the above conditions.
+  pub fn verify<DOC: Document>(jwt: &str, holder: &DOC, credential_issuer: &str, c_nonce: &str) -> Result<JwtToken> {
+    let keys: DocumentKeys<'_, DOC> = DocumentKeys::new(holder);
+
+    let mut profile: CoreProfile = CoreProfile::new();
+    profile.set_aud(credential_issuer.to_owned());
+    profile.set_iat(Validation::Required);
+
+    let validator: JwtValidator<'_, '_, CoreProfile> = JwtValidator::new(Decoder::new_with_resolver(&keys), profile);
+    let token: JwtToken = validator.decode(jwt.as_bytes()).map_err(OidcError::InvalidProof)?;
+
+    if token.header.typ() != Some(Self::TYP) {
+      return Err(OidcError::InvalidProof(libjose::Error::InvalidParam("typ")));
+    }
+
+    let nonce: Option<&str> = token
+      .claims
+      .custom()
+      .and_then(|custom| custom.get("nonce"))
+      .and_then(|nonce| nonce.as_str());
+
+    if nonce != Some(c_nonce) {
+      return Err(OidcError::InvalidProof(libjose::Error::InvalidClaim("nonce")));
+    }
+
+    Ok(token)
+  }
+}
@@ -0,0 +1,362 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Object;
+use identity_core::common::Timestamp;
+use identity_core::common::Url;
+use identity_core::common::Value;
+use identity_core::convert::FromJson;
+use identity_core::convert::ToJson;
+use libjose::jws::Decoder;
+use libjose::jws::Encoder;
+use libjose::jws::Token;
+use libjose::jwt::JwtClaims;
+
+use crate::oidc::OidcError;
+use crate::oidc::PresentationDefinition;
+
+type Result<T, E = OidcError> = ::core::result::Result<T, E>;
+
+/// Authorization request parameters holding JSON values instead of strings.
+const JSON_PARAMETERS: &[&str] = &["client_metadata", "presentation_definition"];
+
+/// A SIOPv2 and/or OpenID4VP authorization request sent by a verifier to a wallet.
+///
+/// See [SIOPv2](https://openid.net/specs/openid-connect-self-issued-v2-1_0.html#name-self-issued-openid-provider-a)
+/// and [OpenID4VP](https://openid.net/specs/openid-4-verifiable-presentations-1_0.html#name-authorization-request).
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct AuthorizationRequest {
+  /// The requested response types, e.g. `id_token`, `vp_token` or `vp_token id_token`.
+  pub response_type: String,
+  /// The identifier of the verifier, e.g. its DID or redirect URI.
+  pub client_id: String,
+  /// The URI the response is redirected to.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub redirect_uri: Option<Url>,
+  /// The URI the response is posted to with the `direct_post` response mode.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub response_uri: Option<Url>,
+  /// The mechanism used to return the response, e.g. `fragment` or `direct_post`.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub response_mode: Option<String>,
+  /// The requested scope, e.g. `openid`.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub scope: Option<String>,
+  /// A value binding the response to this request, preventing replay.
+  pub nonce: String,
+  /// An opaque value returned unmodified in the response.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub state: Option<String>,
+  /// Metadata of the verifier, e.g. supported formats and algorithms.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub client_metadata: Option<Object>,
+  /// The presentations requested in the `vp_token`.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub presentation_definition: Option<PresentationDefinition>,
+}
+
+impl AuthorizationRequest {
+  /// Response type requesting a Self-Issued ID Token.
+  pub const ID_TOKEN: &'static str = "id_token";
+
+  /// Response type requesting a Verifiable Presentation.
+  pub const VP_TOKEN: &'static str = "vp_token";
+
+  /// Response mode posting the response to the `response_uri`.
+  pub const DIRECT_POST: &'static str = "direct_post";
+
+  /// Creates a new `AuthorizationRequest` with the required parameters.
+  pub fn new(response_type: impl Into<String>, client_id: impl Into<String>, nonce: impl Into<String>) -> Self {
+    Self {
+      response_type: response_type.into(),
+      client_id: client_id.into(),
+      redirect_uri: None,
+      response_uri: None,
+      response_mode: None,
+      scope: None,
+      nonce: nonce.into(),
+      state: None,
+      client_metadata: None,
+      presentation_definition: None,
+    }
+  }
+
+  /// Returns whether a Self-Issued ID Token is requested.
+  pub fn requests_id_token(&self) -> bool {
+    self.response_types().any(|value| value == Self::ID_TOKEN)
+  }
+
+  /// Returns whether a Verifiable Presentation is requested.
+  pub fn requests_vp_token(&self) -> bool {
+    self.response_types().any(|value| value == Self::VP_TOKEN)
+  }
+
+  /// Returns whether the response is posted to the `response_uri`.
+  pub fn is_direct_post(&self) -> bool {
+    self.response_mode.as_deref() == Some(Self::DIRECT_POST)
+  }
+
+  fn response_types(&self) -> impl Iterator<Item = &str> + '_ {
+    self.response_type.split_ascii_whitespace()
+  }
+
+  /// Validates the semantic structure of the request.
+  ///
+  /// # Errors
+  ///
+  /// Fails if a required parameter is missing, or an unsupported response type is requested.
+  pub fn check_structure(&self) -> Result<()> {
+    if self.client_id.is_empty() {
+      return Err(OidcError::InvalidRequest("missing client_id"));
+    }
+
+    if self.nonce.is_empty() {
+      return Err(OidcError::InvalidRequest("missing nonce"));
+    }
+
+    if self.response_types().next().is_none()
+      || !self
+        .response_types()
+        .all(|value| value == Self::ID_TOKEN || value == Self::VP_TOKEN)
+    {
+      return Err(OidcError::InvalidRequest("unsupported response_type"));
+    }
+
+    if self.requests_vp_token() && self.presentation_definition.is_none() {
+      return Err(OidcError::InvalidRequest("missing presentation_definition"));
+    }
+
+    if self.is_direct_post() {
+      if self.response_uri.is_none() {
+        return Err(OidcError::InvalidRequest("missing response_uri"));
+      }
+    } else if self.redirect_uri.is_none() {
+      return Err(OidcError::InvalidRequest("missing redirect_uri"));
+    }
+
+    Ok(())
+  }
+
+  /// Parses an authorization request from the query parameters of `url`, e.g.
+  /// `openid://?response_type=id_token&client_id=...`.
+  ///
+  /// Requests passed by value in a `request` parameter must be decoded with
+  /// [`AuthorizationRequest::from_request_object`], see [`AuthorizationRequest::request_object`].
+  ///
+  /// # Errors
+  ///
+  /// Fails if the parameters are invalid or the request is passed by value or reference.
+  pub fn from_url(url: &Url) -> Result<Self> {
+    if url
+      .query_pairs()
+      .any(|(key, _)| key == "request" || key == "request_uri")
+    {
+      return Err(OidcError::InvalidRequest("request object"));
+    }
+
+    let mut params: Object = Object::new();
+
+    for (key, value) in url.query_pairs() {
+      let value: Value = match JSON_PARAMETERS.iter().find(|param| **param == key) {
+        Some(&param) => Value::from_json(&value).map_err(|error| OidcError::InvalidJson(param, error))?,
+        None => Value::String(value.into_owned()),
+      };
+
+      params.insert(key.into_owned(), value);
+    }
+
+    let this: Self = Self::from_json_value(Value::Object(params.into_iter().collect()))
+      .map_err(|error| OidcError::InvalidJson("request", error))?;
+
+    this.check_structure()?;
+
+    Ok(this)
+  }
+
+  /// Returns the request object passed by value in the `request` parameter of `url`, if present.
+  pub fn request_object(url: &Url) -> Option<String> {
+    url
+      .query_pairs()
+      .find(|(key, _)| key == "request")
+      .map(|(_, value)| value.into_owned())
+  }
+
+  /// Appends the request parameters to the query of `base`, e.g. `openid://`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if a JSON parameter cannot be serialized.
+  pub fn to_url(&self, mut base: Url) -> Result<Url> {
+    let params: Value = self
+      .to_json_value()
+      .map_err(|error| OidcError::InvalidJson("request", error))?;
+
+    if let Value::Object(params) = params {
+      let mut query: _ = base.query_pairs_mut();
+
+      for (key, value) in params {
+        match value {
+          Value::String(value) => query.append_pair(&key, &value),
+          value => query.append_pair(&key, &value.to_string()),
+        };
+      }
+    }
+
+    Ok(base)
+  }
+
+  /// Decodes and verifies a request object, i.e. the request parameters as JWT claims
+  /// signed by the verifier.
+  ///
+  /// The `decoder` MUST verify the signature with a key of the verifier, e.g. with a
+  /// [`DocumentKeys`][crate::oidc::DocumentKeys] resolver for a verifier identified by a DID.
+  ///
+  /// [More Info](https://openid.net/specs/openid-4-verifiable-presentations-1_0.html#name-authorization-request)
+  ///
+  /// # Errors
+  ///
+  /// Fails if the signature is invalid, the request object is expired, its issuer is not
+  /// the `client_id`, or the parameters are invalid.
+  pub fn from_request_object(token: &str, decoder: &Decoder<'_, '_>) -> Result<Self> {
+    let token: Token<'_> = decoder
+      .decode(token.as_bytes())
+      .map_err(OidcError::InvalidRequestObject)?;
+
+    let claims: JwtClaims<Object> =
+      JwtClaims::from_json_slice(&token.claims).map_err(|error| OidcError::InvalidJson("request", error))?;
+    let this: Self = Self::from_json_slice(&token.claims).map_err(|error| OidcError::InvalidJson("request", error))?;
+
+    // If present, the issuer MUST be the verifier.
+    if matches!(claims.iss(), Some(iss) if iss != this.client_id) {
+      return Err(OidcError::InvalidRequest("iss"));
+    }
+
+    if matches!(claims.exp(), Some(exp) if exp <= Timestamp::now_utc().to_unix()) {
+      return Err(OidcError::InvalidRequest("expired request object"));
+    }
+
+    this.check_structure()?;
+
+    Ok(this)
+  }
+
+  /// Encodes the request as a request object signed by the recipient of `encoder`.
+  ///
+  /// The `client_id` is set as the issuer (iss).
+  ///
+  /// # Errors
+  ///
+  /// Fails if the request cannot be serialized or signed.
+  pub fn to_request_object(&self, encoder: &Encoder<'_>) -> Result<String> {
+    let mut params: Object = Object::from_json_value(
+      self
+        .to_json_value()
+        .map_err(|error| OidcError::InvalidJson("request", error))?,
+    )
+    .map_err(|error| OidcError::InvalidJson("request", error))?;
+
+    params.insert("iss".to_owned(), Value::String(self.client_id.clone()));
+
+    encoder.encode_serde(&params).map_err(OidcError::InvalidRequestObject)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Url;
+  use identity_did::did::DID;
+  use identity_did::document::CoreDocument;
+  use identity_did::verification::jwk_from_keypair;
+  use identity_did::verification::Jwk;
+  use libjose::jws::JwsAlgorithm;
+  use libjose::jws::JwsHeader;
+  use libjose::jws::Recipient;
+
+  use crate::oidc::DocumentKeys;
+  use crate::oidc::InputDescriptor;
+  use crate::validator::test_utils;
+
+  use super::*;
+
+  fn request(client_id: &str) -> AuthorizationRequest {
+    let mut request: AuthorizationRequest = AuthorizationRequest::new("vp_token id_token", client_id, "n-0S6_WzA2Mj");
+    request.redirect_uri = Some(Url::parse("https://verifier.example.com/cb").unwrap());
+    request.scope = Some("openid".to_owned());
+    request.presentation_definition = Some(PresentationDefinition::new(
+      "degree",
+      vec![InputDescriptor::new("bachelor_degree")],
+    ));
+    request
+  }
+
+  #[test]
+  fn test_url_roundtrip() {
+    let request: AuthorizationRequest = request("https://verifier.example.com/cb");
+    let url: Url = request.to_url(Url::parse("openid://").unwrap()).unwrap();
+
+    assert_eq!(AuthorizationRequest::from_url(&url).unwrap(), request);
+    assert!(AuthorizationRequest::request_object(&url).is_none());
+  }
+
+  #[test]
+  fn test_check_structure() {
+    let mut request: AuthorizationRequest = request("https://verifier.example.com/cb");
+    assert!(request.check_structure().is_ok());
+
+    request.presentation_definition = None;
+    assert!(matches!(
+      request.check_structure(),
+      Err(OidcError::InvalidRequest("missing presentation_definition"))
+    ));
+
+    request.response_type = "code".to_owned();
+    assert!(matches!(
+      request.check_structure(),
+      Err(OidcError::InvalidRequest("unsupported response_type"))
+    ));
+
+    request.response_type = AuthorizationRequest::ID_TOKEN.to_owned();
+    request.response_mode = Some(AuthorizationRequest::DIRECT_POST.to_owned());
+    assert!(matches!(
+      request.check_structure(),
+      Err(OidcError::InvalidRequest("missing response_uri"))
+    ));
+  }
+
+  #[test]
+  fn test_request_object() {
+    let (verifier_doc, verifier_key) = test_utils::generate_document_with_keys();
+    let (other_doc, _) = test_utils::generate_document_with_keys();
+
+    let request: AuthorizationRequest = request(verifier_doc.id().as_str());
+    let kid: String = verifier_doc.methods().next().unwrap().id().to_string();
+    let secret: Jwk = jwk_from_keypair(&verifier_key);
+    let mut header: JwsHeader = JwsHeader::new(JwsAlgorithm::EdDSA);
+    header.set_kid(kid);
+
+    let token: String = request
+      .to_request_object(&Encoder::new().recipient(Recipient::new(&secret).protected(&header)))
+      .unwrap();
+
+    // The request object is passed by value.
+    let mut url: Url = Url::parse("openid://").unwrap();
+    url.query_pairs_mut().append_pair("request", &token);
+    assert!(matches!(
+      AuthorizationRequest::from_url(&url),
+      Err(OidcError::InvalidRequest("request object"))
+    ));
+    let token: String = AuthorizationRequest::request_object(&url).unwrap();
+
+    let keys: DocumentKeys<'_, CoreDocument> = DocumentKeys::new(&verifier_doc);
+    let decoded: AuthorizationRequest =
+      AuthorizationRequest::from_request_object(&token, &Decoder::new_with_resolver(&keys)).unwrap();
+    assert_eq!(decoded, request);
+
+    // Keys of another DID are not accepted.
+    let keys: DocumentKeys<'_, CoreDocument> = DocumentKeys::new(&other_doc);
+    assert!(matches!(
+      AuthorizationRequest::from_request_object(&token, &Decoder::new_with_resolver(&keys)),
+      Err(OidcError::InvalidRequestObject(_))
+    ));
+  }
+}
@@ -0,0 +1,132 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Url;
+use identity_core::convert::FromJson;
+use identity_core::convert::ToJson;
+
+use crate::oidc::AuthorizationRequest;
+use crate::oidc::OidcError;
+use crate::oidc::PresentationSubmission;
+use crate::presentation::Presentation;
+
+type Result<T, E = OidcError> = ::core::result::Result<T, E>;
+
+/// A SIOPv2 and/or OpenID4VP authorization response returned by a wallet to a verifier.
+///
+/// See [SIOPv2](https://openid.net/specs/openid-connect-self-issued-v2-1_0.html#name-self-issued-openid-provider-au)
+/// and [OpenID4VP](https://openid.net/specs/openid-4-verifiable-presentations-1_0.html#name-response).
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct AuthorizationResponse {
+  /// The Self-Issued ID Token, if requested.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub id_token: Option<String>,
+  /// The Verifiable Presentation secured with a Linked Data Proof, if requested.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub vp_token: Option<Presentation>,
+  /// Describes how the `vp_token` satisfies the presentation definition of the request.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub presentation_submission: Option<PresentationSubmission>,
+  /// The `state` of the request.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub state: Option<String>,
+}
+
+impl AuthorizationResponse {
+  /// Creates a new, empty `AuthorizationResponse` for `request`.
+  pub fn new(request: &AuthorizationRequest) -> Self {
+    Self {
+      state: request.state.clone(),
+      ..Self::default()
+    }
+  }
+
+  /// Encodes the response parameters as `application/x-www-form-urlencoded`, e.g. as the body
+  /// of the `direct_post` response mode or the fragment of a redirect.
+  ///
+  /// # Errors
+  ///
+  /// Fails if a JSON parameter cannot be serialized.
+  pub fn to_form_urlencoded(&self) -> Result<String> {
+    let mut form: form_urlencoded::Serializer<'_, String> = form_urlencoded::Serializer::new(String::new());
+
+    if let Some(id_token) = self.id_token.as_deref() {
+      form.append_pair("id_token", id_token);
+    }
+
+    if let Some(vp_token) = self.vp_token.as_ref() {
+      let json: String = vp_token
+        .to_json()
+        .map_err(|error| OidcError::InvalidJson("vp_token", error))?;
+      form.append_pair("vp_token", &json);
+    }
+
+    if let Some(submission) = self.presentation_submission.as_ref() {
+      let json: String = submission
+        .to_json()
+        .map_err(|error| OidcError::InvalidJson("presentation_submission", error))?;
+      form.append_pair("presentation_submission", &json);
+    }
+
+    if let Some(state) = self.state.as_deref() {
+      form.append_pair("state", state);
+    }
+
+    Ok(form.finish())
+  }
+
+  /// Decodes the response parameters from `application/x-www-form-urlencoded` `input`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if a JSON parameter is invalid.
+  pub fn from_form_urlencoded(input: &str) -> Result<Self> {
+    let mut this: Self = Self::default();
+
+    for (key, value) in form_urlencoded::parse(input.as_bytes()) {
+      match &*key {
+        "id_token" => this.id_token = Some(value.into_owned()),
+        "vp_token" => {
+          this.vp_token =
+            Some(Presentation::from_json(&value).map_err(|error| OidcError::InvalidJson("vp_token", error))?);
+        }
+        "presentation_submission" => {
+          this.presentation_submission = Some(
+            PresentationSubmission::from_json(&value)
+              .map_err(|error| OidcError::InvalidJson("presentation_submission", error))?,
+          );
+        }
+        "state" => this.state = Some(value.into_owned()),
+        _ => {}
+      }
+    }
+
+    Ok(this)
+  }
+
+  /// Returns the `redirect_uri` of `request` with the response parameters in the fragment,
+  /// or in the query if the `query` response mode was requested.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the request has no `redirect_uri` or uses the `direct_post` response mode.
+  pub fn to_redirect_url(&self, request: &AuthorizationRequest) -> Result<Url> {
+    if request.is_direct_post() {
+      return Err(OidcError::InvalidRequest("direct_post response mode"));
+    }
+
+    let mut url: Url = request
+      .redirect_uri
+      .clone()
+      .ok_or(OidcError::InvalidRequest("missing redirect_uri"))?;
+    let params: String = self.to_form_urlencoded()?;
+
+    if request.response_mode.as_deref() == Some("query") {
+      url.set_query(Some(&params));
+    } else {
+      url.set_fragment(Some(&params));
+    }
+
+    Ok(url)
+  }
+}
@@ -0,0 +1,111 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::oidc::OidcError;
+use crate::oidc::PRE_AUTHORIZED_CODE_GRANT;
+
+type Result<T, E = OidcError> = ::core::result::Result<T, E>;
+
+/// A request to the token endpoint of a credential issuer.
+///
+/// [More Info](https://openid.net/specs/openid-4-verifiable-credential-issuance-1_0.html#name-token-request)
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TokenRequest {
+  /// The grant type, e.g. [`PRE_AUTHORIZED_CODE_GRANT`].
+  pub grant_type: String,
+  /// The pre-authorized code of the credential offer.
+  #[serde(rename = "pre-authorized_code")]
+  pub pre_authorized_code: String,
+  /// The PIN provided by the user, if required by the offer.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub user_pin: Option<String>,
+}
+
+impl TokenRequest {
+  /// Creates a new `TokenRequest` redeeming a pre-authorized code.
+  pub fn new_pre_authorized(pre_authorized_code: impl Into<String>, user_pin: Option<String>) -> Self {
+    Self {
+      grant_type: PRE_AUTHORIZED_CODE_GRANT.to_owned(),
+      pre_authorized_code: pre_authorized_code.into(),
+      user_pin,
+    }
+  }
+
+  /// Encodes the request as `application/x-www-form-urlencoded`.
+  pub fn to_form_urlencoded(&self) -> String {
+    let mut form: form_urlencoded::Serializer<'_, String> = form_urlencoded::Serializer::new(String::new());
+
+    form.append_pair("grant_type", &self.grant_type);
+    form.append_pair("pre-authorized_code", &self.pre_authorized_code);
+
+    if let Some(user_pin) = self.user_pin.as_deref() {
+      form.append_pair("user_pin", user_pin);
+    }
+
+    form.finish()
+  }
+
+  /// Decodes the request from `application/x-www-form-urlencoded` `input`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if a required parameter is missing.
+  pub fn from_form_urlencoded(input: &str) -> Result<Self> {
+    let mut grant_type: Option<String> = None;
+    let mut pre_authorized_code: Option<String> = None;
+    let mut user_pin: Option<String> = None;
+
+    for (key, value) in form_urlencoded::parse(input.as_bytes()) {
+      match &*key {
+        "grant_type" => grant_type = Some(value.into_owned()),
+        "pre-authorized_code" => pre_authorized_code = Some(value.into_owned()),
+        "user_pin" => user_pin = Some(value.into_owned()),
+        _ => {}
+      }
+    }
+
+    Ok(Self {
+      grant_type: grant_type.ok_or(OidcError::InvalidRequest("missing grant_type"))?,
+      pre_authorized_code: pre_authorized_code.ok_or(OidcError::InvalidRequest("missing pre-authorized_code"))?,
+      user_pin,
+    })
+  }
+}
+
+/// A successful response of the token endpoint of a credential issuer.
+///
+/// [More Info](https://openid.net/specs/openid-4-verifiable-credential-issuance-1_0.html#name-successful-token-response)
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TokenResponse {
+  /// The access token authorizing requests to the credential endpoint.
+  pub access_token: String,
+  /// The type of the access token, i.e. `bearer`.
+  pub token_type: String,
+  /// The lifetime of the access token in seconds.
+  pub expires_in: u64,
+  /// The nonce the next proof of possession MUST include.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub c_nonce: Option<String>,
+  /// The lifetime of the `c_nonce` in seconds.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub c_nonce_expires_in: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_token_request_form_roundtrip() {
+    let request: TokenRequest = TokenRequest::new_pre_authorized("SplxlOBeZQQYbYS6WxSbIA", Some("493536".to_owned()));
+    let form: String = request.to_form_urlencoded();
+
+    assert_eq!(
+      form,
+      "grant_type=urn%3Aietf%3Aparams%3Aoauth%3Agrant-type%3Apre-authorized_code\
+       &pre-authorized_code=SplxlOBeZQQYbYS6WxSbIA&user_pin=493536"
+    );
+    assert_eq!(TokenRequest::from_form_urlencoded(&form).unwrap(), request);
+    assert!(TokenRequest::from_form_urlencoded("grant_type=authorization_code").is_err());
+  }
+}
@@ -0,0 +1,462 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_did::did::DID;
+use identity_did::document::Document;
+use identity_did::verification::MethodScope;
+use libjose::jws::Decoder;
+use libjose::jwt::IdTokenProfile;
+use libjose::jwt::JwtToken;
+use libjose::jwt::JwtValidator;
+
+use crate::oidc::AuthorizationRequest;
+use crate::oidc::AuthorizationResponse;
+use crate::oidc::DocumentKeys;
+use crate::oidc::InputDescriptorMapping;
+use crate::oidc::OidcError;
+use crate::oidc::PresentationDefinition;
+use crate::oidc::PresentationSubmission;
+use crate::oidc::FORMAT_LDP_VC;
+use crate::oidc::FORMAT_LDP_VP;
+use crate::presentation::Presentation;
+use crate::validator::FailFast;
+use crate::validator::PresentationValidationOptions;
+use crate::validator::PresentationValidator;
+use crate::validator::ValidatorDocument;
+
+type Result<T, E = OidcError> = ::core::result::Result<T, E>;
+
+/// A struct for validating SIOPv2 and OpenID4VP [`AuthorizationResponse`]s.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ResponseValidator;
+
+impl ResponseValidator {
+  /// Validates an [`AuthorizationResponse`] to `request`.
+  ///
+  /// The following properties are validated:
+  /// - the `state` matches the request,
+  /// - the `id_token`, if requested, see [`ResponseValidator::validate_id_token`],
+  /// - the `presentation_submission`, if a `vp_token` is requested, see
+  /// [`ResponseValidator::check_presentation_submission`],
+  /// - the `vp_token`, if requested, with [`PresentationValidator::validate`]. The presentation
+  /// proof MUST use the `nonce` of the request as challenge and its `client_id` as domain.
+  ///
+  /// # Warning
+  /// The caller must ensure that the DID Documents in `holder` and `issuers` are up-to-date,
+  /// see [`PresentationValidator::validate`].
+  ///
+  /// # Errors
+  /// An error is returned whenever a validated condition is not satisfied.
+  pub fn validate<HDOC: Document, IDOC: ValidatorDocument>(
+    response: &AuthorizationResponse,
+    request: &AuthorizationRequest,
+    holder: &HDOC,
+    issuers: &[IDOC],
+    options: &PresentationValidationOptions,
+    fail_fast: FailFast,
+  ) -> Result<()> {
+    if response.state != request.state {
+      return Err(OidcError::InvalidResponse("state"));
+    }
+
+    if request.requests_id_token() {
+      let id_token: &str = response
+        .id_token
+        .as_deref()
+        .ok_or(OidcError::InvalidResponse("missing id_token"))?;
+
+      Self::validate_id_token(id_token, request, holder)?;
+    }
+
+    if request.requests_vp_token() {
+      let presentation: &Presentation = response
+        .vp_token
+        .as_ref()
+        .ok_or(OidcError::InvalidResponse("missing vp_token"))?;
+      let submission: &PresentationSubmission = response
+        .presentation_submission
+        .as_ref()
+        .ok_or(OidcError::InvalidResponse("missing presentation_submission"))?;
+      let definition: &PresentationDefinition = request
+        .presentation_definition
+        .as_ref()
+        .ok_or(OidcError::InvalidRequest("missing presentation_definition"))?;
+
+      Self::check_presentation_submission(submission, definition, presentation)?;
+
+      // Bind the presentation to this request.
+      let mut options: PresentationValidationOptions = options.clone();
+      options.presentation_verifier_options = options
+        .presentation_verifier_options
+        .challenge(request.nonce.clone())
+        .domain(request.client_id.clone());
+
+      PresentationValidator::validate(presentation, holder, issuers, &options, fail_fast)
+        .map_err(OidcError::InvalidPresentation)?;
+    }
+
+    Ok(())
+  }
+
+  /// Validates a Self-Issued ID Token issued by `holder` in response to `request`, returning
+  /// the validated token.
+  ///
+  /// The token MUST be signed by an authentication method of `holder`, the issuer and subject
+  /// MUST be its DID, the audience MUST include the `client_id` and the `nonce` MUST match
+  /// the request.
+  ///
+  /// # Errors
+  /// Fails if the token cannot be verified or does not satisfy the above conditions.
+  pub fn validate_id_token<HDOC: Document>(
+    id_token: &str,
+    request: &AuthorizationRequest,
+    holder: &HDOC,
+  ) -> Result<JwtToken> {
+    let did: &str = holder.id().as_str();
+    let keys: DocumentKeys<'_, HDOC> = DocumentKeys::new(holder).scope(MethodScope::authentication());
+
+    let mut profile: IdTokenProfile = IdTokenProfile::new(did, request.client_id.as_str());
+    profile.set_nonce(request.nonce.as_str());
+
+    let validator: JwtValidator<'_, '_, IdTokenProfile> = JwtValidator::new(Decoder::new_with_resolver(&keys), profile);
+    let token: JwtToken = validator
+      .decode(id_token.as_bytes())
+      .map_err(OidcError::InvalidIdToken)?;
+
+    if token.claims.sub() != Some(did) {
+      return Err(OidcError::InvalidIdToken(libjose::Error::InvalidClaim("sub")));
+    }
+
+    Ok(token)
+  }
+
+  /// Checks that `submission` maps every input descriptor of `definition` to the
+  /// `ldp_vp` `presentation` submitted as `vp_token`.
+  ///
+  /// # Errors
+  /// Fails if the submission refers to another definition, omits or adds input descriptors,
+  /// or refers to an unsupported format or path.
+  pub fn check_presentation_submission(
+    submission: &PresentationSubmission,
+    definition: &PresentationDefinition,
+    presentation: &Presentation,
+  ) -> Result<()> {
+    if submission.definition_id != definition.id {
+      return Err(OidcError::InvalidPresentationSubmission("definition_id"));
+    }
+
+    let unmapped: bool = definition.input_descriptors.iter().any(|descriptor| {
+      !submission
+        .descriptor_map
+        .iter()
+        .any(|mapping| mapping.id == descriptor.id)
+    });
+
+    if unmapped {
+      return Err(OidcError::InvalidPresentationSubmission("unmapped input descriptor"));
+    }
+
+    for mapping in submission.descriptor_map.iter() {
+      if definition.input_descriptor(&mapping.id).is_none() {
+        return Err(OidcError::InvalidPresentationSubmission("unknown input descriptor"));
+      }
+
+      if mapping.format != FORMAT_LDP_VP || mapping.path != "$" {
+        return Err(OidcError::InvalidPresentationSubmission("unsupported format or path"));
+      }
+
+      if let Some(nested) = mapping.path_nested.as_deref() {
+        Self::check_nested_credential(nested, presentation)?;
+      }
+    }
+
+    Ok(())
+  }
+
+  // Checks that a nested mapping selects a credential of the presentation.
+  fn check_nested_credential(mapping: &InputDescriptorMapping, presentation: &Presentation) -> Result<()> {
+    let index: Option<usize> = mapping
+      .path
+      .strip_prefix("$.verifiableCredential[")
+      .and_then(|path| path.strip_suffix(']'))
+      .and_then(|index| index.parse().ok());
+
+    match index {
+      Some(index) if mapping.format == FORMAT_LDP_VC && index < presentation.verifiable_credential.len() => Ok(()),
+      _ => Err(OidcError::InvalidPresentationSubmission("path_nested")),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use core::time::Duration;
+
+  use identity_core::common::Object;
+  use identity_core::common::Timestamp;
+  use identity_core::common::Url;
+  use identity_core::crypto::KeyPair;
+  use identity_core::crypto::KeyType;
+  use identity_core::crypto::ProofOptions;
+  use identity_did::did::CoreDIDUrl;
+  use identity_did::document::CoreDocument;
+  use identity_did::verification::jwk_from_keypair;
+  use identity_did::verification::Jwk;
+  use identity_did::verification::MethodRelationship;
+  use identity_did::verification::VerificationMethod;
+  use libjose::jws::Encoder;
+  use libjose::jws::JwsHeader;
+  use libjose::jws::Recipient;
+  use libjose::jwt::JwtClaims;
+
+  use crate::credential::Credential;
+  use crate::oidc::InputDescriptor;
+  use crate::oidc::SelfIssuedIdToken;
+  use crate::presentation::PresentationBuilder;
+  use crate::validator::test_utils;
+
+  use super::*;
+
+  const NONCE: &str = "n-0S6_WzA2Mj";
+  const CLIENT_ID: &str = "https://verifier.example.com/cb";
+
+  struct TestSetup {
+    holder_doc: CoreDocument,
+    holder_key: KeyPair,
+    issuer_doc: CoreDocument,
+    request: AuthorizationRequest,
+    credential: Credential,
+  }
+
+  impl TestSetup {
+    fn new() -> Self {
+      let (mut holder_doc, holder_key) = test_utils::generate_document_with_keys();
+      let (issuer_doc, issuer_key) = test_utils::generate_document_with_keys();
+
+      // The holder authenticates with its key.
+      let method_id: CoreDIDUrl = holder_doc.methods().next().unwrap().id().clone();
+      holder_doc
+        .attach_method_relationship(&method_id, MethodRelationship::Authentication)
+        .unwrap();
+
+      let mut credential: Credential = test_utils::generate_credential(
+        &issuer_doc,
+        &[holder_doc.clone()],
+        Timestamp::parse("2020-01-01T00:00:00Z").unwrap(),
+        Timestamp::parse("2100-01-01T00:00:00Z").unwrap(),
+      );
+      issuer_doc
+        .signer(issuer_key.private())
+        .options(ProofOptions::default())
+        .method(issuer_doc.methods().next().unwrap().id())
+        .sign(&mut credential)
+        .unwrap();
+
+      let mut request: AuthorizationRequest = AuthorizationRequest::new("vp_token id_token", CLIENT_ID, NONCE);
+      request.redirect_uri = Some(Url::parse(CLIENT_ID).unwrap());
+      request.state = Some("af0ifjsldkj".to_owned());
+      request.presentation_definition = Some(PresentationDefinition::new(
+        "degree",
+        vec![InputDescriptor::new("bachelor_degree")],
+      ));
+
+      Self {
+        holder_doc,
+        holder_key,
+        issuer_doc,
+        request,
+        credential,
+      }
+    }
+
+    fn id_token(&self, request: &AuthorizationRequest) -> String {
+      let kid: String = self.holder_doc.methods().next().unwrap().id().to_string();
+      self.id_token_signed_by(request, &kid, &self.holder_key)
+    }
+
+    fn id_token_signed_by(&self, request: &AuthorizationRequest, kid: &str, key: &KeyPair) -> String {
+      let claims: JwtClaims<Object> =
+        SelfIssuedIdToken::claims(self.holder_doc.id().as_str(), request, Duration::from_secs(600));
+      let header: JwsHeader = SelfIssuedIdToken::header(kid);
+      let secret: Jwk = jwk_from_keypair(key);
+
+      Encoder::new()
+        .recipient(Recipient::new(&secret).protected(&header))
+        .encode_serde(&claims)
+        .unwrap()
+    }
+
+    fn presentation(&self, challenge: &str, domain: &str) -> Presentation {
+      let mut presentation: Presentation = PresentationBuilder::default()
+        .holder(Url::parse(self.holder_doc.id().as_str()).unwrap())
+        .credential(self.credential.clone())
+        .build()
+        .unwrap();
+
+      self
+        .holder_doc
+        .signer(self.holder_key.private())
+        .options(
+          ProofOptions::new()
+            .challenge(challenge.to_owned())
+            .domain(domain.to_owned()),
+        )
+        .method(self.holder_doc.methods().next().unwrap().id())
+        .sign(&mut presentation)
+        .unwrap();
+
+      presentation
+    }
+
+    fn response(&self) -> AuthorizationResponse {
+      let mut submission: PresentationSubmission =
+        PresentationSubmission::new("submission", self.request.presentation_definition.as_ref().unwrap());
+      submission.map_credential("bachelor_degree", 0);
+
+      let mut response: AuthorizationResponse = AuthorizationResponse::new(&self.request);
+      response.id_token = Some(self.id_token(&self.request));
+      response.vp_token = Some(self.presentation(NONCE, CLIENT_ID));
+      response.presentation_submission = Some(submission);
+      response
+    }
+
+    fn validate(&self, response: &AuthorizationResponse) -> Result<()> {
+      ResponseValidator::validate(
+        response,
+        &self.request,
+        &self.holder_doc,
+        &[&self.issuer_doc],
+        &PresentationValidationOptions::default(),
+        FailFast::FirstError,
+      )
+    }
+  }
+
+  #[test]
+  fn test_validate_response() {
+    let setup: TestSetup = TestSetup::new();
+    let response: AuthorizationResponse = setup.response();
+
+    // The response survives the form encoding of the redirect.
+    let url: Url = response.to_redirect_url(&setup.request).unwrap();
+    let decoded: AuthorizationResponse = AuthorizationResponse::from_form_urlencoded(url.fragment().unwrap()).unwrap();
+    assert_eq!(decoded, response);
+
+    assert!(setup.validate(&decoded).is_ok());
+    assert_eq!(
+      SelfIssuedIdToken::extract_issuer::<identity_did::did::CoreDID>(decoded.id_token.as_deref().unwrap()).unwrap(),
+      *setup.holder_doc.id()
+    );
+  }
+
+  #[test]
+  fn test_validate_response_state_mismatch() {
+    let setup: TestSetup = TestSetup::new();
+    let mut response: AuthorizationResponse = setup.response();
+    response.state = Some("other".to_owned());
+
+    assert!(matches!(
+      setup.validate(&response),
+      Err(OidcError::InvalidResponse("state"))
+    ));
+  }
+
+  #[test]
+  fn test_validate_id_token_nonce_mismatch() {
+    let setup: TestSetup = TestSetup::new();
+    let mut other: AuthorizationRequest = setup.request.clone();
+    other.nonce = "replayed".to_owned();
+
+    let mut response: AuthorizationResponse = setup.response();
+    response.id_token = Some(setup.id_token(&other));
+
+    assert!(matches!(setup.validate(&response), Err(OidcError::InvalidIdToken(_))));
+  }
+
+  #[test]
+  fn test_validate_id_token_foreign_key() {
+    let setup: TestSetup = TestSetup::new();
+    let (other_doc, _) = test_utils::generate_document_with_keys();
+
+    // The token is signed by the holder but validated against another document.
+    let id_token: String = setup.id_token(&setup.request);
+    let result: Result<JwtToken> = ResponseValidator::validate_id_token(&id_token, &setup.request, &other_doc);
+
+    assert!(matches!(result, Err(OidcError::InvalidIdToken(_))));
+  }
+
+  #[test]
+  fn test_validate_id_token_assertion_method() {
+    let mut setup: TestSetup = TestSetup::new();
+
+    // The token is signed by a method of the holder that may only issue assertions.
+    let keypair: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+    let method: VerificationMethod = VerificationMethod::new(
+      setup.holder_doc.id().clone(),
+      KeyType::Ed25519,
+      keypair.public(),
+      "#assertion",
+    )
+    .unwrap();
+    let kid: String = method.id().to_string();
+    setup
+      .holder_doc
+      .insert_method(method, MethodScope::assertion_method())
+      .unwrap();
+
+    let id_token: String = setup.id_token_signed_by(&setup.request, &kid, &keypair);
+    let result: Result<JwtToken> = ResponseValidator::validate_id_token(&id_token, &setup.request, &setup.holder_doc);
+
+    assert!(matches!(result, Err(OidcError::InvalidIdToken(_))));
+  }
+
+  #[test]
+  fn test_validate_presentation_unbound() {
+    let setup: TestSetup = TestSetup::new();
+
+    for (challenge, domain) in [("replayed", CLIENT_ID), (NONCE, "https://other.example.com")] {
+      let mut response: AuthorizationResponse = setup.response();
+      response.vp_token = Some(setup.presentation(challenge, domain));
+
+      assert!(matches!(
+        setup.validate(&response),
+        Err(OidcError::InvalidPresentation(_))
+      ));
+    }
+  }
+
+  #[test]
+  fn test_check_presentation_submission() {
+    let setup: TestSetup = TestSetup::new();
+    let definition: &PresentationDefinition = setup.request.presentation_definition.as_ref().unwrap();
+    let presentation: Presentation = setup.presentation(NONCE, CLIENT_ID);
+
+    let mut submission: PresentationSubmission = PresentationSubmission::new("submission", definition);
+    assert!(matches!(
+      ResponseValidator::check_presentation_submission(&submission, definition, &presentation),
+      Err(OidcError::InvalidPresentationSubmission("unmapped input descriptor"))
+    ));
+
+    submission.map_credential("bachelor_degree", 1);
+    assert!(matches!(
+      ResponseValidator::check_presentation_submission(&submission, definition, &presentation),
+      Err(OidcError::InvalidPresentationSubmission("path_nested"))
+    ));
+
+    submission.descriptor_map.clear();
+    submission.map_credential("bachelor_degree", 0);
+    submission.map_credential("master_degree", 0);
+    assert!(matches!(
+      ResponseValidator::check_presentation_submission(&submission, definition, &presentation),
+      Err(OidcError::InvalidPresentationSubmission("unknown input descriptor"))
+    ));
+
+    submission.descriptor_map.pop();
+    submission.definition_id = "other".to_owned();
+    assert!(matches!(
+      ResponseValidator::check_presentation_submission(&submission, definition, &presentation),
+      Err(OidcError::InvalidPresentationSubmission("definition_id"))
+    ));
+  }
+}
@@ -0,0 +1,125 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Object;
+use identity_core::common::OneOrMany;
+use identity_core::common::Value;
+use identity_core::convert::FromJson;
+use identity_core::convert::ToJson;
+use identity_did::did::DID;
+use identity_did::document::Document;
+use libjose::jws::Decoder;
+use libjose::jws::JwsAlgorithm;
+use libjose::jws::JwsHeader;
+use libjose::jwt::JwtClaims;
+use libjose::jwt::JwtToken;
+use libjose::jwt::JwtValidator;
+use libjose::jwt::VcJwtProfile;
+
+use crate::credential::Credential;
+use crate::oidc::DocumentKeys;
+use crate::oidc::OidcError;
+
+type Result<T, E = OidcError> = ::core::result::Result<T, E>;
+
+/// Helpers for Verifiable Credentials encoded as JWTs signed by a verification method
+/// of the issuer's DID Document.
+///
+/// [More Info](https://www.w3.org/TR/vc-data-model/#json-web-token)
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct VcJwtCredential;
+
+impl VcJwtCredential {
+  /// Returns the claims of a VC-JWT securing `credential`.
+  ///
+  /// The credential, without any Linked Data Proof, is embedded in the `vc` claim, and
+  /// its issuer, subject, identifier and dates are mapped to the registered claims.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the credential cannot be serialized.
+  pub fn claims(credential: &Credential) -> Result<JwtClaims<Object>> {
+    let mut unsecured: Credential = credential.clone();
+    unsecured.proof = OneOrMany::default();
+
+    let mut claims: JwtClaims<Object> = JwtClaims::new();
+    claims.set_iss(credential.issuer.url().as_str());
+    claims.set_nbf(credential.issuance_date.to_unix());
+
+    if let Some(subject) = credential
+      .credential_subject
+      .get(0)
+      .and_then(|subject| subject.id.as_ref())
+    {
+      claims.set_sub(subject.as_str());
+    }
+
+    if let Some(expiration_date) = credential.expiration_date {
+      claims.set_exp(expiration_date.to_unix());
+    }
+
+    if let Some(id) = credential.id.as_ref() {
+      claims.set_jti(id.as_str());
+    }
+
+    match unsecured
+      .to_json_value()
+      .map_err(|error| OidcError::InvalidJson("credential", error))?
+    {
+      Value::Object(vc) => claims.set_vc(vc),
+      _ => return Err(OidcError::InvalidCredential("expected JSON object")),
+    }
+
+    Ok(claims)
+  }
+
+  /// Returns the protected header of a VC-JWT signed with EdDSA by the verification
+  /// method identified by the DID URL `kid`.
+  pub fn header(kid: &str) -> JwsHeader {
+    let mut header: JwsHeader = JwsHeader::new(JwsAlgorithm::EdDSA);
+    header.set_typ("JWT");
+    header.set_kid(kid);
+    header
+  }
+
+  /// Verifies a VC-JWT signed by a verification method of `issuer`, returning the
+  /// credential of the `vc` claim.
+  ///
+  /// Only the signature and the registered claims are validated, see
+  /// [`CredentialValidator`](crate::validator::CredentialValidator) for the semantic
+  /// validation of the credential.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the token cannot be verified, was not issued by `issuer` or does not
+  /// contain a valid credential.
+  pub fn decode<DOC: Document>(jwt: &str, issuer: &DOC) -> Result<Credential> {
+    let keys: DocumentKeys<'_, DOC> = DocumentKeys::new(issuer);
+
+    let mut profile: VcJwtProfile = VcJwtProfile::credential();
+    profile.set_iss(issuer.id().as_str().to_owned());
+
+    let validator: JwtValidator<'_, '_, VcJwtProfile> = JwtValidator::new(Decoder::new_with_resolver(&keys), profile);
+    let token: JwtToken = validator
+      .decode(jwt.as_bytes())
+      .map_err(OidcError::InvalidCredentialJwt)?;
+
+    let vc: Value = token
+      .claims
+      .vc()
+      .cloned()
+      .map(Value::Object)
+      .ok_or(OidcError::InvalidCredentialJwt(libjose::Error::MissingClaim("vc")))?;
+
+    let credential: Credential =
+      Credential::from_json_value(vc).map_err(|error| OidcError::InvalidJson("credential", error))?;
+
+    // The embedded credential MUST agree with the verified issuer.
+    if credential.issuer.url().as_str() != issuer.id().as_str() {
+      return Err(OidcError::InvalidCredential("issuer"));
+    }
+
+    Ok(credential)
+  }
+}
@@ -0,0 +1,293 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Object;
+use identity_did::did::CoreDID;
+use identity_did::document::Document;
+use libjose::jws::Encoder;
+use libjose::jwt::JwtClaims;
+
+use crate::credential::Credential;
+use crate::oidc::CredentialIssuer;
+use crate::oidc::CredentialOffer;
+use crate::oidc::CredentialRequest;
+use crate::oidc::CredentialRequestProof;
+use crate::oidc::CredentialResponse;
+use crate::oidc::CredentialSigner;
+use crate::oidc::OidcError;
+use crate::oidc::PreAuthorizedCodeGrant;
+use crate::oidc::ProofOfPossession;
+use crate::oidc::TokenRequest;
+use crate::oidc::TokenResponse;
+
+type Result<T, E = OidcError> = ::core::result::Result<T, E>;
+
+/// Sends requests to the endpoints of a credential issuer, e.g. over HTTPS.
+///
+/// Error responses of a remote issuer should be returned as [`OidcError::IssuerError`].
+#[async_trait::async_trait(?Send)]
+pub trait IssuerTransport {
+  /// Sends `request` to the token endpoint of the issuer of `offer`.
+  async fn token(&self, offer: &CredentialOffer, request: &TokenRequest) -> Result<TokenResponse>;
+
+  /// Sends `request`, authorized by `access_token`, to the credential endpoint of the
+  /// issuer of `offer`.
+  async fn credential(
+    &self,
+    offer: &CredentialOffer,
+    access_token: &str,
+    request: &CredentialRequest,
+  ) -> Result<CredentialResponse>;
+}
+
+/// An [`IssuerTransport`] calling a [`CredentialIssuer`] in the same process.
+///
+/// The DID Documents of holders are resolved with the `resolve` callback.
+#[derive(Debug)]
+pub struct LocalTransport<'a, S, F> {
+  issuer: &'a CredentialIssuer<S>,
+  resolve: F,
+}
+
+impl<'a, S, F> LocalTransport<'a, S, F> {
+  /// Creates a new `LocalTransport` to `issuer`, resolving holder DID Documents with `resolve`.
+  pub fn new(issuer: &'a CredentialIssuer<S>, resolve: F) -> Self {
+    Self { issuer, resolve }
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl<S, F, DOC> IssuerTransport for LocalTransport<'_, S, F>
+where
+  S: CredentialSigner,
+  F: Fn(&CoreDID) -> Option<DOC>,
+  DOC: Document,
+{
+  async fn token(&self, _offer: &CredentialOffer, request: &TokenRequest) -> Result<TokenResponse> {
+    self.issuer.token(request)
+  }
+
+  async fn credential(
+    &self,
+    _offer: &CredentialOffer,
+    access_token: &str,
+    request: &CredentialRequest,
+  ) -> Result<CredentialResponse> {
+    let proof: &CredentialRequestProof = request
+      .proof
+      .as_ref()
+      .ok_or(OidcError::InvalidProof(libjose::Error::MissingParam("proof")))?;
+
+    let holder: CoreDID = ProofOfPossession::extract_holder(&proof.jwt)?;
+    let document: DOC = (self.resolve)(&holder).ok_or(OidcError::InvalidProof(libjose::Error::InvalidParam("kid")))?;
+
+    self.issuer.credential(access_token, request, &document)
+  }
+}
+
+/// The wallet side of OpenID for Verifiable Credential Issuance with the pre-authorized
+/// code flow.
+#[derive(Debug)]
+pub struct WalletClient<T> {
+  transport: T,
+}
+
+impl<T> WalletClient<T>
+where
+  T: IssuerTransport,
+{
+  /// Creates a new `WalletClient` sending requests with `transport`.
+  pub fn new(transport: T) -> Self {
+    Self { transport }
+  }
+
+  /// Returns the transport of the client.
+  pub fn transport(&self) -> &T {
+    &self.transport
+  }
+
+  /// Redeems the pre-authorized code of `offer` at the token endpoint, with the `user_pin`
+  /// if required by the offer.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the offer has no pre-authorized code, a required PIN is missing, or the
+  /// request fails.
+  pub async fn request_token(&self, offer: &CredentialOffer, user_pin: Option<String>) -> Result<TokenResponse> {
+    let grant: &PreAuthorizedCodeGrant = offer
+      .pre_authorized_code()
+      .ok_or(OidcError::InvalidRequest("missing pre-authorized code grant"))?;
+
+    if grant.user_pin_required && user_pin.is_none() {
+      return Err(OidcError::InvalidRequest("missing user_pin"));
+    }
+
+    let request: TokenRequest = TokenRequest::new_pre_authorized(grant.pre_authorized_code.clone(), user_pin);
+
+    self.transport.token(offer, &request).await
+  }
+
+  /// Requests the offered credential of `credential_type` in `format`, proving possession
+  /// of the holder key with `encoder`.
+  ///
+  /// The `encoder` MUST sign with a verification method of the holder's DID Document and
+  /// use the [`ProofOfPossession::header`]. The `c_nonce` of `token` is updated with the
+  /// nonce of the response for subsequent requests.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the credential type was not offered, `token` has no `c_nonce`, the proof
+  /// cannot be signed, or the request fails.
+  pub async fn request_credential(
+    &self,
+    offer: &CredentialOffer,
+    token: &mut TokenResponse,
+    format: &str,
+    credential_type: &str,
+    encoder: &Encoder<'_>,
+  ) -> Result<CredentialResponse> {
+    if !offer.credentials.iter().any(|offered| offered == credential_type) {
+      return Err(OidcError::UnsupportedCredentialType);
+    }
+
+    let c_nonce: &str = token
+      .c_nonce
+      .as_deref()
+      .ok_or(OidcError::InvalidResponse("missing c_nonce"))?;
+
+    let claims: JwtClaims<Object> = ProofOfPossession::claims(offer.credential_issuer.as_str(), c_nonce);
+    let jwt: String = encoder
+      .encode_serde_async(&claims)
+      .await
+      .map_err(OidcError::InvalidProof)?;
+
+    let types: Vec<String> = vec![Credential::<Object>::base_type().to_owned(), credential_type.to_owned()];
+    let mut request: CredentialRequest = CredentialRequest::new(format, types);
+    request.proof = Some(CredentialRequestProof::new_jwt(jwt));
+
+    let response: CredentialResponse = self.transport.credential(offer, &token.access_token, &request).await?;
+
+    if response.c_nonce.is_some() {
+      token.c_nonce = response.c_nonce.clone();
+      token.c_nonce_expires_in = response.c_nonce_expires_in;
+    }
+
+    Ok(response)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Timestamp;
+  use identity_core::common::Url;
+  use identity_core::crypto::KeyPair;
+  use identity_did::document::CoreDocument;
+  use identity_did::verification::jwk_from_keypair;
+  use identity_did::verification::Jwk;
+  use libjose::jws::JwsHeader;
+  use libjose::jws::Recipient;
+
+  use crate::oidc::DocumentCredentialSigner;
+  use crate::oidc::VcJwtCredential;
+  use crate::oidc::FORMAT_JWT_VC_JSON;
+  use crate::oidc::FORMAT_LDP_VC;
+  use crate::validator::test_utils;
+
+  use super::*;
+
+  fn issuer() -> CredentialIssuer<DocumentCredentialSigner> {
+    let (issuer_doc, issuer_key) = test_utils::generate_document_with_keys();
+    let signer: DocumentCredentialSigner = DocumentCredentialSigner::new(issuer_doc, issuer_key, "#root");
+
+    CredentialIssuer::new(Url::parse("https://issuer.example.com").unwrap(), signer)
+  }
+
+  fn template(issuer: &CredentialIssuer<DocumentCredentialSigner>, credential_type: &str) -> Credential {
+    let (subject_doc, _) = test_utils::generate_document_with_keys();
+    let mut credential: Credential = test_utils::generate_credential(
+      issuer.signer().document(),
+      &[subject_doc],
+      Timestamp::parse("2020-01-01T00:00:00Z").unwrap(),
+      Timestamp::parse("2100-01-01T00:00:00Z").unwrap(),
+    );
+    credential.types = vec![Credential::<Object>::base_type().to_owned(), credential_type.to_owned()].into();
+    credential
+  }
+
+  #[tokio::test]
+  async fn test_wallet_client_local_transport() {
+    let issuer: CredentialIssuer<DocumentCredentialSigner> = issuer();
+    let (holder_doc, holder_key): (CoreDocument, KeyPair) = test_utils::generate_document_with_keys();
+
+    let credentials: Vec<Credential> = vec![template(&issuer, "DegreeCredential"), template(&issuer, "IdCredential")];
+    let offer: CredentialOffer = issuer.create_offer(credentials, Some("493536".to_owned())).unwrap();
+
+    // The offer is passed to the wallet by value, e.g. in a QR code.
+    let url: Url = offer.to_url(Url::parse("openid-credential-offer://").unwrap()).unwrap();
+    let offer: CredentialOffer = CredentialOffer::from_url(&url).unwrap();
+
+    let resolve = |did: &CoreDID| (did == holder_doc.id()).then(|| holder_doc.clone());
+    let client: WalletClient<_> = WalletClient::new(LocalTransport::new(&issuer, resolve));
+
+    assert!(matches!(
+      client.request_token(&offer, None).await,
+      Err(OidcError::InvalidRequest("missing user_pin"))
+    ));
+    let mut token: TokenResponse = client.request_token(&offer, Some("493536".to_owned())).await.unwrap();
+
+    let kid: String = holder_doc.methods().next().unwrap().id().to_string();
+    let header: JwsHeader = ProofOfPossession::header(&kid);
+    let secret: Jwk = jwk_from_keypair(&holder_key);
+    let encoder: Encoder<'_> = Encoder::new().recipient(Recipient::new(&secret).protected(&header));
+
+    let response: CredentialResponse = client
+      .request_credential(&offer, &mut token, FORMAT_LDP_VC, "DegreeCredential", &encoder)
+      .await
+      .unwrap();
+    assert_eq!(
+      response.ldp_credential().unwrap().types.get(1).unwrap(),
+      "DegreeCredential"
+    );
+
+    // The nonce of the response is used for the next request.
+    assert_eq!(token.c_nonce, response.c_nonce);
+    let response: CredentialResponse = client
+      .request_credential(&offer, &mut token, FORMAT_JWT_VC_JSON, "IdCredential", &encoder)
+      .await
+      .unwrap();
+    let credential: Credential =
+      VcJwtCredential::decode(response.jwt_credential().unwrap(), issuer.signer().document()).unwrap();
+    assert_eq!(credential.types.get(1).unwrap(), "IdCredential");
+
+    assert!(matches!(
+      client
+        .request_credential(&offer, &mut token, FORMAT_LDP_VC, "OtherCredential", &encoder)
+        .await,
+      Err(OidcError::UnsupportedCredentialType)
+    ));
+  }
+
+  #[tokio::test]
+  async fn test_wallet_client_unknown_holder() {
+    let issuer: CredentialIssuer<DocumentCredentialSigner> = issuer();
+    let (holder_doc, holder_key): (CoreDocument, KeyPair) = test_utils::generate_document_with_keys();
+
+    let offer: CredentialOffer = issuer
+      .create_offer(vec![template(&issuer, "DegreeCredential")], None)
+      .unwrap();
+    let client: WalletClient<_> = WalletClient::new(LocalTransport::new(&issuer, |_: &CoreDID| None::<CoreDocument>));
+    let mut token: TokenResponse = client.request_token(&offer, None).await.unwrap();
+
+    let kid: String = holder_doc.methods().next().unwrap().id().to_string();
+    let header: JwsHeader = ProofOfPossession::header(&kid);
+    let secret: Jwk = jwk_from_keypair(&holder_key);
+    let encoder: Encoder<'_> = Encoder::new().recipient(Recipient::new(&secret).protected(&header));
+
+    assert!(matches!(
+      client
+        .request_credential(&offer, &mut token, FORMAT_LDP_VC, "DegreeCredential", &encoder)
+        .await,
+      Err(OidcError::InvalidProof(libjose::Error::InvalidParam("kid")))
+    ));
+  }
+}
@@ -11,9 +11,11 @@ use identity_core::common::Object;
 use identity_core::common::OneOrMany;
 use identity_core::common::Url;
 use identity_core::convert::FmtJson;
+use identity_core::crypto::GetProofs;
 use identity_core::crypto::GetSignature;
 use identity_core::crypto::GetSignatureMut;
 use identity_core::crypto::Proof;
+use identity_core::crypto::PushProof;
 use identity_core::crypto::SetSignature;
 use identity_did::verification::MethodUriType;
 use identity_did::verification::TryMethod;
@@ -52,9 +54,9 @@ pub struct Presentation<T = Object, U = Object> {
   /// Miscellaneous properties.
   #[serde(flatten)]
   pub properties: T,
-  /// Proof(s) used to verify a `Presentation`
-  #[serde(skip_serializing_if = "Option::is_none")]
-  pub proof: Option<Proof>,
+  /// Proof(s) used to verify a `Presentation`, e.g. a proof set or chain.
+  #[serde(default, skip_serializing_if = "OneOrMany::is_empty")]
+  pub proof: OneOrMany<Proof>,
 }
 
 impl<T, U> Presentation<T, U> {
@@ -86,7 +88,7 @@ impl<T, U> Presentation<T, U> {
       refresh_service: builder.refresh_service.into(),
       terms_of_use: builder.terms_of_use.into(),
       properties: builder.properties,
-      proof: None,
+      proof: OneOrMany::default(),
     };
 
     this.check_structure()?;
@@ -115,14 +117,18 @@ impl<T, U> Presentation<T, U> {
     Ok(())
   }
 
-  /// Returns a reference to the `Presentation` proof.
+  /// Returns a reference to the `Presentation` proof, i.e. the last added proof of a proof set.
   pub fn proof(&self) -> Option<&Proof> {
-    self.proof.as_ref()
+    self.proof.last()
   }
 
-  /// Returns a mutable reference to the `Presentation` proof.
+  /// Returns a mutable reference to the `Presentation` proof, i.e. the last added proof of a proof set.
   pub fn proof_mut(&mut self) -> Option<&mut Proof> {
-    self.proof.as_mut()
+    self
+      .proof
+      .len()
+      .checked_sub(1)
+      .and_then(|index| self.proof.get_mut(index))
   }
 }
 
@@ -138,19 +144,31 @@ where
 
 impl<T, U> GetSignature for Presentation<T, U> {
   fn signature(&self) -> Option<&Proof> {
-    self.proof.as_ref()
+    self.proof()
   }
 }
 
 impl<T, U> GetSignatureMut for Presentation<T, U> {
   fn signature_mut(&mut self) -> Option<&mut Proof> {
-    self.proof.as_mut()
+    self.proof_mut()
   }
 }
 
 impl<T, U> SetSignature for Presentation<T, U> {
   fn set_signature(&mut self, value: Proof) {
-    self.proof.replace(value);
+    self.proof = OneOrMany::One(value);
+  }
+}
+
+impl<T, U> GetProofs for Presentation<T, U> {
+  fn proofs(&self) -> &[Proof] {
+    self.proof.as_slice()
+  }
+}
+
+impl<T, U> PushProof for Presentation<T, U> {
+  fn push_proof(&mut self, value: Proof) {
+    self.proof.push(value);
   }
 }
 
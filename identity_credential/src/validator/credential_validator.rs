@@ -8,6 +8,7 @@ use serde::Serialize;
 use identity_core::common::OneOrMany;
 use identity_core::common::Timestamp;
 use identity_core::common::Url;
+use identity_core::jsonld::JsonLdProcessor;
 use identity_did::did::CoreDID;
 use identity_did::did::DID;
 #[cfg(feature = "revocation-bitmap")]
@@ -23,8 +24,10 @@ use super::errors::SignerContext;
 use super::errors::ValidationError;
 #[cfg(feature = "revocation-bitmap")]
 use super::validation_options::StatusCheck;
+use super::validator_document::verify_proof_set;
 use super::CredentialValidationOptions;
 use super::FailFast;
+use super::ProofSetPolicy;
 use super::SubjectHolderRelationship;
 use super::ValidatorDocument;
 
@@ -72,13 +75,25 @@ impl CredentialValidator {
   /// Validates the semantic structure of the [`Credential`].
   ///
   /// # Warning
-  /// This does not validate against the credential's schema nor the structure of the subject claims.
+  /// This does not validate against the credential's schema nor the structure of the subject claims,
+  /// and does not check that its terms are defined by its JSON-LD contexts, see
+  /// [`CredentialValidator::check_terms`].
   pub fn check_structure<T>(credential: &Credential<T>) -> ValidationUnitResult {
     credential
       .check_structure()
       .map_err(ValidationError::CredentialStructure)
   }
 
+  /// Validates that all terms of the [`Credential`] are defined by its JSON-LD contexts, which are
+  /// resolved with the given `processor`.
+  ///
+  /// See [`Credential::check_terms`].
+  pub fn check_terms<T: Serialize>(credential: &Credential<T>, processor: &JsonLdProcessor) -> ValidationUnitResult {
+    credential
+      .check_terms(processor)
+      .map_err(ValidationError::CredentialStructure)
+  }
+
   /// Validate that the [`Credential`] expires on or after the specified [`Timestamp`].
   pub fn check_expires_on_or_after<T>(credential: &Credential<T>, timestamp: Timestamp) -> ValidationUnitResult {
     let is_ok = if let Some(expiration_date) = credential.expiration_date {
@@ -98,17 +113,30 @@ impl CredentialValidator {
 
   /// Verify the signature using the DID Document of a trusted issuer.
   ///
+  /// If the credential is secured by a proof set, every proof must be valid.
+  ///
   /// # Warning
   /// The caller must ensure that the DID Documents of the trusted issuers are up-to-date.
   ///
   /// # Errors
   /// This method immediately returns an error if
-  /// the credential issuer' url cannot be parsed to a DID belonging to one of the trusted issuers. Otherwise an attempt
-  /// to verify the credential's signature will be made and an error is returned upon failure.
+  /// the credential issuer' url cannot be parsed to a DID belonging to one of the trusted issuers, or the DID Document
+  /// of the issuer has been deactivated. Otherwise an attempt to verify the credential's signature will be made and an
+  /// error is returned upon failure.
   pub fn verify_signature<DOC: ValidatorDocument, T: Serialize>(
     credential: &Credential<T>,
     trusted_issuers: &[DOC],
     options: &VerifierOptions,
+  ) -> ValidationUnitResult {
+    Self::verify_proof_set(credential, trusted_issuers, options, ProofSetPolicy::All)
+  }
+
+  // Verifies the proofs of the credential, requiring all or any of them to be valid according to `policy`.
+  fn verify_proof_set<DOC: ValidatorDocument, T: Serialize>(
+    credential: &Credential<T>,
+    trusted_issuers: &[DOC],
+    options: &VerifierOptions,
+    policy: ProofSetPolicy,
   ) -> ValidationUnitResult {
     let issuer_did: CoreDID = Self::extract_issuer(credential)?;
     trusted_issuers
@@ -116,12 +144,13 @@ impl CredentialValidator {
       .find(|issuer_doc| issuer_doc.did_str() == issuer_did.as_str())
       .ok_or(ValidationError::DocumentMismatch(SignerContext::Issuer))
       .and_then(|issuer| {
-        issuer
-          .verify_data(credential, options)
-          .map_err(|err| ValidationError::Signature {
-            source: err.into(),
-            signer_ctx: SignerContext::Issuer,
-          })
+        if issuer.is_deactivated() {
+          return Err(ValidationError::DeactivatedDocument(SignerContext::Issuer));
+        }
+        verify_proof_set(issuer, credential, options, policy).map_err(|err| ValidationError::Signature {
+          source: err.into(),
+          signer_ctx: SignerContext::Issuer,
+        })
       })
   }
 
@@ -232,8 +261,9 @@ impl CredentialValidator {
     fail_fast: FailFast,
   ) -> CredentialValidationResult {
     // Run all single concern validations in turn and fail immediately if `fail_fast` is true.
-    let signature_validation =
-      std::iter::once_with(|| Self::verify_signature(credential, issuers, &options.verifier_options));
+    let signature_validation = std::iter::once_with(|| {
+      Self::verify_proof_set(credential, issuers, &options.verifier_options, options.proof_set_policy)
+    });
 
     let expiry_date_validation = std::iter::once_with(|| {
       Self::check_expires_on_or_after(credential, options.earliest_expiry_date.unwrap_or_default())
@@ -245,6 +275,14 @@ impl CredentialValidator {
 
     let structure_validation = std::iter::once_with(|| Self::check_structure(credential));
 
+    let terms_validation = std::iter::once_with(|| {
+      if options.check_terms {
+        Self::check_terms(credential, &JsonLdProcessor::default())
+      } else {
+        Ok(())
+      }
+    });
+
     let subject_holder_validation = std::iter::once_with(|| {
       relationship_criterion
         .map(|(holder, relationship)| Self::check_subject_holder_relationship(credential, holder, relationship))
@@ -254,6 +292,7 @@ impl CredentialValidator {
     let validation_units_iter = issuance_date_validation
       .chain(expiry_date_validation)
       .chain(structure_validation)
+      .chain(terms_validation)
       .chain(subject_holder_validation)
       .chain(signature_validation);
 
@@ -623,6 +662,81 @@ mod tests {
     assert!(matches!(error, &ValidationError::Signature { .. }));
   }
 
+  #[test]
+  fn test_verify_proof_set() {
+    let Setup {
+      issuer_doc,
+      issuer_key,
+      unsigned_credential: mut credential,
+      ..
+    } = Setup::new();
+    let (_, other_keys) = test_utils::generate_document_with_keys();
+
+    issuer_doc
+      .signer(issuer_key.private())
+      .method(issuer_doc.methods().next().unwrap().id())
+      .append(&mut credential)
+      .unwrap();
+    issuer_doc
+      .signer(issuer_key.private())
+      .method(issuer_doc.methods().next().unwrap().id())
+      .append(&mut credential)
+      .unwrap();
+    assert_eq!(credential.proof.len(), 2);
+    assert!(CredentialValidator::verify_signature(&credential, &[&issuer_doc], &VerifierOptions::default()).is_ok());
+
+    // add a proof created with the wrong key
+    issuer_doc
+      .signer(other_keys.private())
+      .method(issuer_doc.methods().next().unwrap().id())
+      .append(&mut credential)
+      .unwrap();
+    assert!(matches!(
+      CredentialValidator::verify_signature(&credential, &[&issuer_doc], &VerifierOptions::default()).unwrap_err(),
+      ValidationError::Signature { .. }
+    ));
+
+    let expires_on_or_after: Timestamp = Timestamp::parse("2022-01-01T00:00:00Z").unwrap();
+    let options = CredentialValidationOptions::default().earliest_expiry_date(expires_on_or_after);
+    assert!(CredentialValidator::validate(&credential, &issuer_doc, &options, FailFast::FirstError).is_err());
+
+    let options = options.proof_set_policy(ProofSetPolicy::Any);
+    assert!(CredentialValidator::validate(&credential, &issuer_doc, &options, FailFast::FirstError).is_ok());
+
+    // no valid proof
+    credential.proof = OneOrMany::default();
+    issuer_doc
+      .signer(other_keys.private())
+      .method(issuer_doc.methods().next().unwrap().id())
+      .append(&mut credential)
+      .unwrap();
+    assert!(CredentialValidator::validate(&credential, &issuer_doc, &options, FailFast::FirstError).is_err());
+  }
+
+  #[test]
+  fn test_verify_signature_deactivated_issuer() {
+    let Setup {
+      issuer_doc,
+      issuer_key,
+      unsigned_credential: mut credential,
+      ..
+    } = Setup::new();
+
+    issuer_doc
+      .signer(issuer_key.private())
+      .options(ProofOptions::default())
+      .method(issuer_doc.methods().next().unwrap().id())
+      .sign(&mut credential)
+      .unwrap();
+
+    // The signature is valid, but the issuer's DID has since been deactivated.
+    let deactivated_doc = test_utils::DeactivatedDocument(issuer_doc);
+    assert!(matches!(
+      CredentialValidator::verify_signature(&credential, &[&deactivated_doc], &VerifierOptions::default()).unwrap_err(),
+      ValidationError::DeactivatedDocument(SignerContext::Issuer)
+    ));
+  }
+
   #[test]
   fn test_check_subject_holder_relationship() {
     let Setup {
@@ -846,6 +960,43 @@ mod tests {
     assert!(matches!(error, &ValidationError::CredentialStructure(_)));
   }
 
+  #[test]
+  fn test_full_validation_check_terms() {
+    let Setup {
+      issuer_doc,
+      issuer_key,
+      unsigned_credential: mut credential,
+      issuance_date,
+      expiration_date,
+    } = Setup::new();
+
+    issuer_doc
+      .signer(issuer_key.private())
+      .options(ProofOptions::default())
+      .method(issuer_doc.methods().next().unwrap().id())
+      .sign(&mut credential)
+      .unwrap();
+
+    let options = CredentialValidationOptions::default()
+      .latest_issuance_date(issuance_date.checked_add(Duration::days(14)).unwrap())
+      .earliest_expiry_date(expiration_date.checked_sub(Duration::hours(1)).unwrap());
+    assert!(CredentialValidator::validate(&credential, &issuer_doc, &options, FailFast::FirstError).is_ok());
+
+    // The claims of the subjects are not defined by the base context.
+    let options = options.check_terms(true);
+    let validation_errors = CredentialValidator::validate(&credential, &issuer_doc, &options, FailFast::FirstError)
+      .unwrap_err()
+      .validation_errors;
+
+    assert!(matches!(
+      validation_errors.as_slice(),
+      [ValidationError::CredentialStructure(crate::Error::UndefinedTerm(_))]
+    ));
+
+    // All terms are defined by the examples context.
+    assert!(CredentialValidator::check_terms(&SIMPLE_CREDENTIAL, &JsonLdProcessor::default()).is_ok());
+  }
+
   #[test]
   fn test_full_validation_multiple_errors_fail_fast() {
     let Setup {
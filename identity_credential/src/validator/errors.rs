@@ -46,6 +46,12 @@ pub enum ValidationError {
   #[non_exhaustive]
   DocumentMismatch(SignerContext),
 
+  /// Indicates that the DID Document of the credential's issuer (resp. presentation's holder)
+  /// has been deactivated.
+  #[error("the {0}'s DID Document has been deactivated")]
+  #[non_exhaustive]
+  DeactivatedDocument(SignerContext),
+
   /// Indicates that the structure of the [Credential](crate::credential::Credential) is not semantically
   /// correct.
   #[error("the credential's structure is not semantically correct")]
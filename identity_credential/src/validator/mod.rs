@@ -12,6 +12,7 @@ pub use self::presentation_validator::PresentationValidator;
 pub use self::validation_options::CredentialValidationOptions;
 pub use self::validation_options::FailFast;
 pub use self::validation_options::PresentationValidationOptions;
+pub use self::validation_options::ProofSetPolicy;
 pub use self::validation_options::StatusCheck;
 pub use self::validation_options::SubjectHolderRelationship;
 pub use self::validator_document::ValidatorDocument;
@@ -20,6 +21,6 @@ mod credential_validator;
 mod errors;
 mod presentation_validator;
 #[cfg(test)]
-mod test_utils;
+pub(crate) mod test_utils;
 mod validation_options;
 mod validator_document;
@@ -17,9 +17,11 @@ use super::errors::CompoundCredentialValidationError;
 use super::errors::CompoundPresentationValidationError;
 use super::errors::SignerContext;
 use super::errors::ValidationError;
+use super::validator_document::verify_proof_set;
 use super::CredentialValidator;
 use super::FailFast;
 use super::PresentationValidationOptions;
+use super::ProofSetPolicy;
 use super::ValidatorDocument;
 
 /// A struct for validating [`Presentation`]s.
@@ -105,27 +107,41 @@ impl PresentationValidator {
 
   /// Verify the presentation's signature using the resolved document of the holder.
   ///
+  /// If the presentation is secured by a proof set, every proof must be valid.
+  ///
   /// # Warning
   /// The caller must ensure that the DID Document of the holder is up-to-date.
   ///
   /// # Errors
   /// Fails if the `holder` does not match the `presentation`'s holder property.
+  /// Fails if the DID Document of the `holder` has been deactivated.
   /// Fails if signature verification against the holder document fails.
   pub fn verify_presentation_signature<U: Serialize, V: Serialize, DOC: ValidatorDocument + ?Sized>(
     presentation: &Presentation<U, V>,
     holder: &DOC,
     options: &VerifierOptions,
+  ) -> ValidationUnitResult {
+    Self::verify_presentation_proof_set(presentation, holder, options, ProofSetPolicy::All)
+  }
+
+  // Verifies the proofs of the presentation, requiring all or any of them to be valid according to `policy`.
+  fn verify_presentation_proof_set<U: Serialize, V: Serialize, DOC: ValidatorDocument + ?Sized>(
+    presentation: &Presentation<U, V>,
+    holder: &DOC,
+    options: &VerifierOptions,
+    policy: ProofSetPolicy,
   ) -> ValidationUnitResult {
     let did: CoreDID = Self::extract_holder(presentation)?;
     if did.as_str() != holder.did_str() {
       return Err(ValidationError::DocumentMismatch(SignerContext::Holder));
     }
-    holder
-      .verify_data(&presentation, options)
-      .map_err(|err| ValidationError::Signature {
-        source: err.into(),
-        signer_ctx: SignerContext::Holder,
-      })
+    if holder.is_deactivated() {
+      return Err(ValidationError::DeactivatedDocument(SignerContext::Holder));
+    }
+    verify_proof_set(holder, presentation, options, policy).map_err(|err| ValidationError::Signature {
+      source: err.into(),
+      signer_ctx: SignerContext::Holder,
+    })
   }
 
   /// Validates the semantic structure of the [Presentation].
@@ -148,7 +164,12 @@ impl PresentationValidator {
   ) -> Result<(), Vec<ValidationError>> {
     let structure_validation = std::iter::once_with(|| Self::check_structure(presentation));
     let signature_validation = std::iter::once_with(|| {
-      Self::verify_presentation_signature(presentation, holder, &options.presentation_verifier_options)
+      Self::verify_presentation_proof_set(
+        presentation,
+        holder,
+        &options.presentation_verifier_options,
+        options.presentation_proof_set_policy,
+      )
     });
 
     let presentation_validation_errors_iter = structure_validation
@@ -443,6 +464,32 @@ mod tests {
     ));
   }
 
+  #[test]
+  fn test_verify_presentation_signature_deactivated_holder() {
+    let TestSetup {
+      subject_foo_doc,
+      subject_foo_key,
+      credential_foo,
+      ..
+    } = TestSetup::new_with_signed_credentials();
+
+    let mut presentation = build_presentation(&subject_foo_doc, [credential_foo].to_vec());
+    subject_foo_doc
+      .signer(subject_foo_key.private())
+      .options(ProofOptions::default())
+      .method(subject_foo_doc.methods().next().unwrap().id())
+      .sign(&mut presentation)
+      .unwrap();
+
+    // The signature is valid, but the holder's DID has since been deactivated.
+    let holder_doc = test_utils::DeactivatedDocument(subject_foo_doc);
+    assert!(matches!(
+      PresentationValidator::verify_presentation_signature(&presentation, &holder_doc, &VerifierOptions::default())
+        .unwrap_err(),
+      ValidationError::DeactivatedDocument(SignerContext::Holder)
+    ));
+  }
+
   #[test]
   fn test_full_validation_invalid_credential() {
     // create a first credential
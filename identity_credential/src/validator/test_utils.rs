@@ -5,6 +5,7 @@ use identity_core::common::Object;
 use identity_core::common::Timestamp;
 use identity_core::common::Url;
 use identity_core::convert::FromJson;
+use identity_core::crypto::GetSignature;
 use identity_core::crypto::KeyPair;
 use identity_core::crypto::KeyType;
 use identity_core::json;
@@ -12,13 +13,19 @@ use identity_core::utils::BaseEncoding;
 use identity_did::did::CoreDID;
 use identity_did::did::DID;
 use identity_did::document::CoreDocument;
+use identity_did::document::Document;
+use identity_did::service::Service;
+use identity_did::utils::DIDUrlQuery;
+use identity_did::verifiable::VerifierOptions;
+use identity_did::verification::MethodScope;
 use identity_did::verification::VerificationMethod;
+use serde::Serialize;
 
 use crate::credential::Credential;
 use crate::credential::CredentialBuilder;
 use crate::credential::Subject;
 
-pub(super) fn generate_document_with_keys() -> (CoreDocument, KeyPair) {
+pub(crate) fn generate_document_with_keys() -> (CoreDocument, KeyPair) {
   let keypair: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
   let did: CoreDID = CoreDID::parse(&format!(
     "did:example:{}",
@@ -33,7 +40,53 @@ pub(super) fn generate_document_with_keys() -> (CoreDocument, KeyPair) {
   (document, keypair)
 }
 
-pub(super) fn generate_credential(
+/// A [`CoreDocument`] whose DID has been deactivated.
+pub(super) struct DeactivatedDocument(pub(super) CoreDocument);
+
+impl Document for DeactivatedDocument {
+  type D = CoreDID;
+  type U = Object;
+  type V = Object;
+
+  fn id(&self) -> &Self::D {
+    self.0.id()
+  }
+
+  fn resolve_service<'query, 'me, Q>(&'me self, query: Q) -> Option<&'me Service<Self::D, Self::V>>
+  where
+    Q: Into<DIDUrlQuery<'query>>,
+  {
+    self.0.resolve_service(query)
+  }
+
+  fn services(&self) -> &[Service<Self::D, Self::V>] {
+    self.0.services()
+  }
+
+  fn resolve_method<'query, 'me, Q>(
+    &'me self,
+    query: Q,
+    scope: Option<MethodScope>,
+  ) -> Option<&'me VerificationMethod<Self::D, Self::U>>
+  where
+    Q: Into<DIDUrlQuery<'query>>,
+  {
+    self.0.resolve_method(query, scope)
+  }
+
+  fn verify_data<X>(&self, data: &X, options: &VerifierOptions) -> identity_did::Result<()>
+  where
+    X: Serialize + GetSignature + ?Sized,
+  {
+    self.0.verify_data(data, options)
+  }
+
+  fn is_deactivated(&self) -> bool {
+    true
+  }
+}
+
+pub(crate) fn generate_credential(
   issuer: &CoreDocument,
   subjects: &[CoreDocument],
   issuance_date: Timestamp,
@@ -31,6 +31,20 @@ pub struct CredentialValidationOptions {
   /// Options which affect the verification of the signature on the credential.
   #[serde(default)]
   pub verifier_options: VerifierOptions,
+
+  /// Declares which proofs of a credential secured by a proof set must be valid.
+  ///
+  /// Default: [`ProofSetPolicy::All`].
+  #[serde(default)]
+  pub proof_set_policy: ProofSetPolicy,
+
+  /// Declares that all terms of the credential must be defined by its JSON-LD contexts, which are
+  /// resolved with the preloaded contexts of a default
+  /// [`JsonLdProcessor`](identity_core::jsonld::JsonLdProcessor).
+  ///
+  /// Default: `false`.
+  #[serde(default)]
+  pub check_terms: bool,
 }
 
 impl CredentialValidationOptions {
@@ -64,6 +78,18 @@ impl CredentialValidationOptions {
     self.verifier_options = options;
     self
   }
+
+  /// Declares which proofs of a credential secured by a proof set must be valid.
+  pub fn proof_set_policy(mut self, policy: ProofSetPolicy) -> Self {
+    self.proof_set_policy = policy;
+    self
+  }
+
+  /// Declares whether all terms of the credential must be defined by its JSON-LD contexts.
+  pub fn check_terms(mut self, value: bool) -> Self {
+    self.check_terms = value;
+    self
+  }
 }
 
 /// Controls validation behaviour when checking whether or not a credential has been revoked by its
@@ -91,6 +117,25 @@ impl Default for StatusCheck {
   }
 }
 
+/// Declares which proofs of a [proof set](https://www.w3.org/TR/vc-data-integrity/#proof-sets) must be valid
+/// for the signature of a credential or presentation to be considered valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde_repr::Serialize_repr, serde_repr::Deserialize_repr)]
+#[repr(u8)]
+pub enum ProofSetPolicy {
+  /// Every proof must be valid.
+  ///
+  /// This is the default.
+  All = 0,
+  /// At least one proof must be valid.
+  Any = 1,
+}
+
+impl Default for ProofSetPolicy {
+  fn default() -> Self {
+    Self::All
+  }
+}
+
 /// Declares how credential subjects must relate to the presentation holder during validation.
 /// See [`PresentationValidationOptions::subject_holder_relationship()`].
 ///
@@ -140,6 +185,10 @@ pub struct PresentationValidationOptions {
   /// Default: [`SubjectHolderRelationship::AlwaysSubject`].
   #[serde(default)]
   pub subject_holder_relationship: SubjectHolderRelationship,
+  /// Declares which proofs of a presentation secured by a proof set must be valid.
+  /// Default: [`ProofSetPolicy::All`].
+  #[serde(default)]
+  pub presentation_proof_set_policy: ProofSetPolicy,
 }
 
 impl PresentationValidationOptions {
@@ -164,4 +213,10 @@ impl PresentationValidationOptions {
     self.subject_holder_relationship = options;
     self
   }
+
+  /// Declares which proofs of the presentation must be valid if it is secured by a proof set.
+  pub fn presentation_proof_set_policy(mut self, policy: ProofSetPolicy) -> Self {
+    self.presentation_proof_set_policy = policy;
+    self
+  }
 }
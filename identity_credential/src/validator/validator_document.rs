@@ -1,12 +1,17 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use identity_core::crypto::GetProofs;
 use identity_core::crypto::GetSignature;
+use identity_core::crypto::ProofView;
 use identity_did::did::DID;
 use identity_did::document::Document;
 #[cfg(feature = "revocation-bitmap")]
 use identity_did::revocation::RevocationBitmap;
 use identity_did::verifiable::VerifierOptions;
+use serde::Serialize;
+
+use crate::validator::ProofSetPolicy;
 
 use self::private::Sealed;
 use self::private::Verifiable;
@@ -38,6 +43,9 @@ pub trait ValidatorDocument: Sealed {
   /// serialization fails, or the verification operation fails.
   fn verify_data(&self, data: &dyn Verifiable, options: &VerifierOptions) -> identity_did::Result<()>;
 
+  /// Returns whether the DID of the DID Document has been deactivated.
+  fn is_deactivated(&self) -> bool;
+
   /// Extracts the `RevocationBitmap` from the referenced service in the DID Document.
   ///
   /// # Errors
@@ -84,6 +92,10 @@ impl ValidatorDocument for &dyn ValidatorDocument {
     (*self).verify_data(data, options)
   }
 
+  fn is_deactivated(&self) -> bool {
+    (*self).is_deactivated()
+  }
+
   #[cfg(feature = "revocation-bitmap")]
   fn resolve_revocation_bitmap(
     &self,
@@ -105,6 +117,10 @@ where
     self.verify_data(data, options).map_err(Into::into)
   }
 
+  fn is_deactivated(&self) -> bool {
+    Document::is_deactivated(self)
+  }
+
   #[cfg(feature = "revocation-bitmap")]
   fn resolve_revocation_bitmap(
     &self,
@@ -118,3 +134,43 @@ where
       .and_then(RevocationBitmap::try_from)
   }
 }
+
+/// Verifies each proof of the proof set of `data` against the DID Document `signer`, requiring
+/// all or any of them to be valid according to `policy`.
+///
+/// Returns the error of the first invalid proof for [`ProofSetPolicy::All`] and of the last proof
+/// for [`ProofSetPolicy::Any`].
+pub(crate) fn verify_proof_set<DOC, X>(
+  signer: &DOC,
+  data: &X,
+  options: &VerifierOptions,
+  policy: ProofSetPolicy,
+) -> identity_did::Result<()>
+where
+  DOC: ValidatorDocument + ?Sized,
+  X: Serialize + GetSignature + GetProofs,
+{
+  if data.proofs().is_empty() {
+    // Fails with a missing signature error.
+    return signer.verify_data(data, options);
+  }
+
+  let results = data
+    .proofs()
+    .iter()
+    .map(|proof| signer.verify_data(&ProofView::new(data, proof), options));
+
+  match policy {
+    ProofSetPolicy::All => results.collect(),
+    ProofSetPolicy::Any => {
+      let mut last: identity_did::Result<()> = Ok(());
+      for result in results {
+        if result.is_ok() {
+          return result;
+        }
+        last = result;
+      }
+      last
+    }
+  }
+}
@@ -4,13 +4,13 @@
 use serde::Deserialize;
 use serde::Serialize;
 
-use identity_core::common::KeyComparable;
+use identity_core::common::DiffKey;
+use identity_core::common::DiffSet;
 use identity_core::common::Object;
 use identity_core::common::OneOrSet;
 use identity_core::common::OrderedSet;
 use identity_core::common::Url;
 use identity_core::diff::Diff;
-use identity_core::diff::DiffVec;
 use identity_core::diff::Error;
 use identity_core::diff::Result;
 
@@ -25,7 +25,7 @@ use crate::verification::VerificationMethod;
 #[serde(bound(deserialize = ""))]
 pub struct DiffDocument<D = CoreDID, T = Object, U = Object, V = Object>
 where
-  D: Diff + DID + KeyComparable + Serialize + for<'__de> Deserialize<'__de>,
+  D: Diff + DID + DiffKey + Serialize + for<'__de> Deserialize<'__de>,
   T: Diff + Serialize + for<'__de> Deserialize<'__de>,
   U: Diff + Serialize + for<'__de> Deserialize<'__de> + Default,
   V: Diff + Serialize + for<'__de> Deserialize<'__de> + Default,
@@ -33,30 +33,30 @@ where
   #[serde(skip_serializing_if = "Option::is_none")]
   id: Option<<D as Diff>::Type>,
   #[serde(skip_serializing_if = "Option::is_none")]
-  controller: Option<Option<DiffVec<D>>>,
+  controller: Option<Option<DiffSet<D>>>,
   #[serde(skip_serializing_if = "Option::is_none")]
-  also_known_as: Option<DiffVec<Url>>,
+  also_known_as: Option<DiffSet<Url>>,
   #[serde(skip_serializing_if = "Option::is_none")]
-  verification_method: Option<DiffVec<VerificationMethod<D, U>>>,
+  verification_method: Option<DiffSet<VerificationMethod<D, U>>>,
   #[serde(skip_serializing_if = "Option::is_none")]
-  authentication: Option<DiffVec<MethodRef<D, U>>>,
+  authentication: Option<DiffSet<MethodRef<D, U>>>,
   #[serde(skip_serializing_if = "Option::is_none")]
-  assertion_method: Option<DiffVec<MethodRef<D, U>>>,
+  assertion_method: Option<DiffSet<MethodRef<D, U>>>,
   #[serde(skip_serializing_if = "Option::is_none")]
-  key_agreement: Option<DiffVec<MethodRef<D, U>>>,
+  key_agreement: Option<DiffSet<MethodRef<D, U>>>,
   #[serde(skip_serializing_if = "Option::is_none")]
-  capability_delegation: Option<DiffVec<MethodRef<D, U>>>,
+  capability_delegation: Option<DiffSet<MethodRef<D, U>>>,
   #[serde(skip_serializing_if = "Option::is_none")]
-  capability_invocation: Option<DiffVec<MethodRef<D, U>>>,
+  capability_invocation: Option<DiffSet<MethodRef<D, U>>>,
   #[serde(skip_serializing_if = "Option::is_none")]
-  service: Option<DiffVec<Service<D, V>>>,
+  service: Option<DiffSet<Service<D, V>>>,
   #[serde(skip_serializing_if = "Option::is_none")]
   properties: Option<<T as Diff>::Type>,
 }
 
 impl<D, T, U, V> Diff for CoreDocument<D, T, U, V>
 where
-  D: DID + KeyComparable + Diff + Serialize + for<'de> Deserialize<'de>,
+  D: DID + DiffKey + Diff + Serialize + for<'de> Deserialize<'de>,
   T: Diff + Serialize + for<'de> Deserialize<'de> + Default,
   U: Diff + Serialize + for<'de> Deserialize<'de> + Default,
   V: Diff + Serialize + for<'de> Deserialize<'de> + Default,
@@ -82,7 +82,7 @@ where
       also_known_as: if self.also_known_as() == other.also_known_as() {
         None
       } else {
-        Some(self.also_known_as().to_vec().diff(&other.also_known_as().to_vec())?)
+        Some(self.also_known_as().diff(other.also_known_as())?)
       },
       verification_method: if self.verification_method() == other.verification_method() {
         None
@@ -492,6 +492,34 @@ mod test {
     assert_eq!(merge, new);
   }
 
+  #[test]
+  fn test_method_diff_by_key() {
+    let doc: CoreDocument = document();
+    let mut new: CoreDocument = doc.clone();
+
+    // Removing the first method must not affect how the following methods are represented.
+    let first: VerificationMethod = new.verification_method().first().unwrap().clone();
+    new.verification_method_mut().remove(&first);
+
+    let diff: DiffDocument = doc.diff(&new).unwrap();
+    assert_eq!(
+      diff.verification_method.to_json().unwrap(),
+      r#"{"remove":["did:example:1234#key-1"]}"#
+    );
+    assert_eq!(doc.merge(diff).unwrap(), new);
+
+    // Updating a method is recorded by its id.
+    let mut updated: CoreDocument = new.clone();
+    let second: CoreDIDUrl = updated.verification_method().first().unwrap().id().clone();
+    *updated.resolve_method_mut(&second, None).unwrap().data_mut() = MethodData::new_multibase(b"updated");
+
+    let diff: DiffDocument = new.diff(&updated).unwrap();
+    let json: Value = diff.verification_method.to_json_value().unwrap();
+    assert_eq!(json["update"][0][0], Value::from(second.to_string()));
+    assert!(json.get("remove").is_none() && json.get("insert").is_none());
+    assert_eq!(new.merge(diff).unwrap(), updated);
+  }
+
   #[test]
   fn test_add_authentication() {
     let doc = document();
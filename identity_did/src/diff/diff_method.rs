@@ -172,8 +172,14 @@ mod test {
   use identity_core::common::Value;
   use identity_core::convert::FromJson;
   use identity_core::convert::ToJson;
+  use identity_core::crypto::KeyPair;
+  use identity_core::crypto::KeyType;
   use identity_core::diff::DiffString;
 
+  use crate::verification::jwk_from_keypair;
+  use crate::verification::jwk_from_public_key;
+  use crate::verification::Jwk;
+
   use super::*;
 
   fn test_method() -> VerificationMethod {
@@ -341,6 +347,24 @@ mod test {
     assert_eq!(merge, new);
   }
 
+  #[test]
+  fn test_data_jwk() {
+    let keypair: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+    let method = test_method();
+    let mut new = method.clone();
+    *new.data_mut() = MethodData::new_jwk(jwk_from_public_key(KeyType::Ed25519, keypair.public())).unwrap();
+
+    let diff = method.diff(&new).unwrap();
+    let merge = method.merge(diff).unwrap();
+    assert_eq!(merge, new);
+
+    // Diffs cannot publish private key material.
+    let private: Jwk = jwk_from_keypair(&keypair);
+    let mut diff = method.diff(&new).unwrap();
+    diff.data = Some(DiffMethodData::PublicKeyJwk(Some(Box::new(private))));
+    assert!(method.merge(diff).is_err());
+  }
+
   #[test]
   fn test_from_diff() {
     let method = test_method();
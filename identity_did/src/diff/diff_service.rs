@@ -176,13 +176,13 @@ mod test {
   use indexmap::IndexMap;
 
   use crate::did::CoreDIDUrl;
+  use identity_core::common::DiffSet;
   use identity_core::common::Object;
   use identity_core::common::OrderedSet;
   use identity_core::common::Url;
   use identity_core::convert::FromJson;
   use identity_core::convert::ToJson;
   use identity_core::diff::DiffString;
-  use identity_core::diff::DiffVec;
 
   use super::*;
 
@@ -405,12 +405,12 @@ mod test {
     let set0: OrderedSet<Service> = OrderedSet::new();
     let set1: OrderedSet<Service> = OrderedSet::try_from(vec![service]).unwrap();
 
-    let diff: DiffVec<Service> = Diff::diff(&set0, &set1).unwrap();
+    let diff: DiffSet<Service> = Diff::diff(&set0, &set1).unwrap();
     let merge: OrderedSet<Service> = set0.merge(diff.clone()).unwrap();
     assert_eq!(merge, set1);
 
     let ser: String = diff.to_json().unwrap();
-    let de: DiffVec<Service> = DiffVec::from_json(&ser).unwrap();
+    let de: DiffSet<Service> = DiffSet::from_json(&ser).unwrap();
     assert_eq!(diff, de);
   }
 }
@@ -3,14 +3,17 @@
 
 use identity_core::diff::Diff;
 use identity_core::diff::DiffString;
+use identity_core::diff::Error;
 use identity_core::diff::Result;
 
+use crate::verification::Jwk;
 use crate::verification::MethodData;
 
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub enum DiffMethodData {
   PublicKeyMultibase(#[serde(skip_serializing_if = "Option::is_none")] Option<DiffString>),
   PublicKeyBase58(#[serde(skip_serializing_if = "Option::is_none")] Option<DiffString>),
+  PublicKeyJwk(#[serde(skip_serializing_if = "Option::is_none")] Option<Box<Jwk>>),
 }
 
 impl Diff for MethodData {
@@ -26,6 +29,8 @@ impl Diff for MethodData {
       }
       (Self::PublicKeyBase58(a), Self::PublicKeyBase58(b)) if a == b => Ok(DiffMethodData::PublicKeyBase58(None)),
       (Self::PublicKeyBase58(a), Self::PublicKeyBase58(b)) => a.diff(b).map(Some).map(DiffMethodData::PublicKeyBase58),
+      // JWKs are replaced as a whole.
+      (Self::PublicKeyJwk(a), Self::PublicKeyJwk(b)) if a == b => Ok(DiffMethodData::PublicKeyJwk(None)),
       (_, _) => other.clone().into_diff(),
     }
   }
@@ -40,6 +45,7 @@ impl Diff for MethodData {
       }
       (Self::PublicKeyBase58(a), DiffMethodData::PublicKeyBase58(Some(b))) => a.merge(b).map(Self::PublicKeyBase58),
       (Self::PublicKeyBase58(a), DiffMethodData::PublicKeyBase58(None)) => Ok(Self::PublicKeyBase58(a.clone())),
+      (Self::PublicKeyJwk(a), DiffMethodData::PublicKeyJwk(None)) => Ok(Self::PublicKeyJwk(a.clone())),
       (_, diff) => Self::from_diff(diff),
     }
   }
@@ -50,6 +56,9 @@ impl Diff for MethodData {
       DiffMethodData::PublicKeyMultibase(None) => Ok(Self::PublicKeyMultibase(Default::default())),
       DiffMethodData::PublicKeyBase58(Some(value)) => Diff::from_diff(value).map(Self::PublicKeyBase58),
       DiffMethodData::PublicKeyBase58(None) => Ok(Self::PublicKeyBase58(Default::default())),
+      // JWKs from diffs are validated like new ones, so a diff cannot publish private key material.
+      DiffMethodData::PublicKeyJwk(Some(value)) => Self::new_jwk(*value).map_err(Error::convert),
+      DiffMethodData::PublicKeyJwk(None) => Err(Error::convert("Missing JWK")),
     }
   }
 
@@ -57,6 +66,7 @@ impl Diff for MethodData {
     match self {
       Self::PublicKeyMultibase(value) => value.into_diff().map(Some).map(DiffMethodData::PublicKeyMultibase),
       Self::PublicKeyBase58(value) => value.into_diff().map(Some).map(DiffMethodData::PublicKeyBase58),
+      Self::PublicKeyJwk(value) => Ok(DiffMethodData::PublicKeyJwk(Some(value))),
     }
   }
 }
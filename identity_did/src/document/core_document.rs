@@ -5,6 +5,7 @@ use core::convert::TryInto as _;
 use core::fmt::Display;
 use core::fmt::Formatter;
 
+use serde::de::DeserializeOwned;
 use serde::Serialize;
 
 use identity_core::common::KeyComparable;
@@ -15,12 +16,18 @@ use identity_core::common::Timestamp;
 use identity_core::common::Url;
 use identity_core::convert::FmtJson;
 use identity_core::crypto::Ed25519;
+use identity_core::crypto::Ed25519Signature2020;
+use identity_core::crypto::EdDsaJcs2022;
+use identity_core::crypto::EdDsaRdfc2022;
 use identity_core::crypto::GetSignature;
 use identity_core::crypto::JcsEd25519;
+use identity_core::crypto::KeyType;
+use identity_core::crypto::Named;
 use identity_core::crypto::PrivateKey;
 use identity_core::crypto::Proof;
 use identity_core::crypto::ProofPurpose;
 use identity_core::crypto::Verifier;
+use identity_core::diff::JsonPatch;
 
 use crate::did::CoreDID;
 use crate::did::DIDUrl;
@@ -34,6 +41,8 @@ use crate::utils::DIDUrlQuery;
 use crate::utils::Queryable;
 use crate::verifiable::DocumentSigner;
 use crate::verifiable::VerifierOptions;
+use crate::verification::jwk_key_type;
+use crate::verification::Jwk;
 use crate::verification::MethodRef;
 use crate::verification::MethodRelationship;
 use crate::verification::MethodScope;
@@ -592,6 +601,31 @@ where
     }
   }
 
+  /// Ensures that the embedded [`VerificationMethods`](VerificationMethod) have unique `id`s and that all
+  /// methods referenced by verification relationships exist in the document.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if a method `id` is used more than once or a referenced method does not exist.
+  pub fn check_methods(&self) -> Result<()> {
+    let method_ids: Vec<&DIDUrl<D>> = self.methods().map(VerificationMethod::id).collect();
+
+    for (index, method_id) in method_ids.iter().enumerate() {
+      if method_ids[..index].contains(method_id) {
+        return Err(Error::MethodAlreadyExists);
+      }
+    }
+
+    if self
+      .verification_relationships()
+      .any(|method_ref| self.resolve_method_ref(method_ref).is_none())
+    {
+      return Err(Error::MethodNotFound);
+    }
+
+    Ok(())
+  }
+
   #[doc(hidden)]
   pub fn resolve_method_ref<'a>(&'a self, method_ref: &'a MethodRef<D, U>) -> Option<&'a VerificationMethod<D, U>> {
     match method_ref {
@@ -744,11 +778,44 @@ where
 
     match method.type_() {
       MethodType::Ed25519VerificationKey2018 => {
-        JcsEd25519::<Ed25519>::verify_signature(data, &public_key)?;
+        Self::verify_ed25519(data, &public_key)?;
       }
       MethodType::X25519KeyAgreementKey2019 => {
         return Err(Error::InvalidMethodType);
       }
+      MethodType::JsonWebKey2020 => {
+        // The signature suite is determined by the key type of the JWK.
+        let jwk: &Jwk = method.data().public_key_jwk().ok_or(Error::InvalidKeyDataJwk)?;
+        match jwk_key_type(jwk) {
+          Some(KeyType::Ed25519) => Self::verify_ed25519(data, &public_key)?,
+          _ => return Err(Error::InvalidMethodType),
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Verifies an Ed25519 signature, selecting the signature suite by the type of the proof:
+  /// `Ed25519Signature2020` proofs use the suite of the same name, `DataIntegrityProof` proofs the
+  /// `eddsa-rdfc-2022` or `eddsa-jcs-2022` cryptosuite and all others `JcsEd25519Signature2020`.
+  fn verify_ed25519<X>(data: &X, public_key: &[u8]) -> Result<()>
+  where
+    X: Serialize + GetSignature + ?Sized,
+  {
+    let (type_, cryptosuite): (&str, Option<&str>) = data
+      .signature()
+      .map(|signature| (signature.type_(), signature.cryptosuite()))
+      .unwrap_or_default();
+
+    if type_ == Ed25519Signature2020::<Ed25519>::NAME {
+      Ed25519Signature2020::<Ed25519>::verify_signature(data, public_key)?;
+    } else if type_ == EdDsaRdfc2022::<Ed25519>::NAME && cryptosuite == EdDsaRdfc2022::<Ed25519>::CRYPTOSUITE {
+      EdDsaRdfc2022::<Ed25519>::verify_signature(data, public_key)?;
+    } else if type_ == EdDsaJcs2022::<Ed25519>::NAME {
+      EdDsaJcs2022::<Ed25519>::verify_signature(data, public_key)?;
+    } else {
+      JcsEd25519::<Ed25519>::verify_signature(data, public_key)?;
     }
 
     Ok(())
@@ -774,6 +841,10 @@ where
     self.service().query(query.into())
   }
 
+  fn services(&self) -> &[Service<Self::D, Self::V>] {
+    self.service().as_slice()
+  }
+
   fn resolve_method<'query, 'me, Q>(
     &'me self,
     query: Q,
@@ -857,6 +928,37 @@ mod core_document_revocation {
   }
 }
 
+// =============================================================================
+// JSON Patch
+// =============================================================================
+
+impl<D, T, U, V> CoreDocument<D, T, U, V>
+where
+  D: DID + KeyComparable,
+  Self: Serialize + DeserializeOwned,
+{
+  /// Applies a [JSON Patch](https://www.rfc-editor.org/rfc/rfc6902) to the JSON representation of the
+  /// document and returns the patched document, leaving `self` unmodified.
+  ///
+  /// The patched document is validated with [`CoreDocument::check_methods`].
+  ///
+  /// # Errors
+  ///
+  /// Fails if the patch cannot be applied, the result is not a valid document, the `id` of the
+  /// document is changed or the verification methods of the result are inconsistent.
+  pub fn apply_json_patch(&self, patch: &JsonPatch) -> Result<Self> {
+    let document: Self = patch.apply_to(self).map_err(Error::InvalidPatch)?;
+
+    if document.id() != self.id() {
+      return Err(Error::InvalidDocument("id", None));
+    }
+
+    document.check_methods()?;
+
+    Ok(document)
+  }
+}
+
 // =============================================================================
 // Signature Extensions
 // =============================================================================
@@ -893,6 +995,10 @@ where
 
 #[cfg(test)]
 mod tests {
+  use identity_core::common::Value;
+  use identity_core::diff::Diff;
+  use serde_json::json;
+
   use crate::verification::MethodData;
 
   use super::*;
@@ -1208,6 +1314,55 @@ mod tests {
     assert!(document.verification_method().query(method3.id()).is_none());
   }
 
+  #[test]
+  fn test_apply_json_patch() {
+    let controller: CoreDID = controller();
+    let original: CoreDocument = CoreDocument::builder(Default::default())
+      .id(controller.clone())
+      .verification_method(method(&controller, "#key-1"))
+      .authentication(controller.to_url().join("#key-1").unwrap())
+      .build()
+      .unwrap();
+
+    let mut updated: CoreDocument = original.clone();
+    updated
+      .insert_method(method(&controller, "#key-2"), MethodScope::assertion_method())
+      .unwrap();
+    updated
+      .properties_mut()
+      .insert("foo".to_owned(), Value::String("bar".to_owned()));
+
+    let patch: JsonPatch = JsonPatch::from_diff(&original, original.diff(&updated).unwrap()).unwrap();
+    assert_eq!(original.apply_json_patch(&patch).unwrap(), updated);
+
+    let patch = |operations: Value| -> JsonPatch { serde_json::from_value(operations).unwrap() };
+
+    // The id of the document cannot be changed.
+    let result: Result<CoreDocument> = original.apply_json_patch(&patch(json!([
+      { "op": "replace", "path": "/id", "value": "did:example:other" }
+    ])));
+    assert!(matches!(result, Err(Error::InvalidDocument("id", None))));
+
+    // Referenced methods must exist.
+    let result: Result<CoreDocument> = original.apply_json_patch(&patch(json!([
+      { "op": "remove", "path": "/verificationMethod" }
+    ])));
+    assert!(matches!(result, Err(Error::MethodNotFound)));
+
+    // Method ids must be unique.
+    let duplicate: Value = serde_json::to_value(method(&controller, "#key-1")).unwrap();
+    let result: Result<CoreDocument> = original.apply_json_patch(&patch(json!([
+      { "op": "add", "path": "/assertionMethod", "value": [duplicate] }
+    ])));
+    assert!(matches!(result, Err(Error::MethodAlreadyExists)));
+
+    // The patch must apply.
+    let result: Result<CoreDocument> = original.apply_json_patch(&patch(json!([
+      { "op": "remove", "path": "/service" }
+    ])));
+    assert!(matches!(result, Err(Error::InvalidPatch(_))));
+  }
+
   #[cfg(feature = "revocation-bitmap")]
   #[test]
   fn test_revocation() {
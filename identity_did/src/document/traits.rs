@@ -31,6 +31,9 @@ pub trait Document {
   where
     Q: Into<DIDUrlQuery<'query>>;
 
+  /// Returns all [`Service`]s of the `Document`.
+  fn services(&self) -> &[Service<Self::D, Self::V>];
+
   /// Returns the first [`VerificationMethod`] with an `id` property matching the
   /// provided `query` and the verification relationship specified by `scope`, if present.
   fn resolve_method<'query, 'me, Q>(
@@ -50,6 +53,13 @@ pub trait Document {
   fn verify_data<X>(&self, data: &X, options: &VerifierOptions) -> Result<()>
   where
     X: Serialize + GetSignature + ?Sized;
+
+  /// Returns whether the DID of the `Document` has been deactivated.
+  ///
+  /// Defaults to `false` for DID methods without deactivation support.
+  fn is_deactivated(&self) -> bool {
+    false
+  }
 }
 
 impl<DOC: Document> Document for &DOC {
@@ -68,6 +78,10 @@ impl<DOC: Document> Document for &DOC {
     DOC::resolve_service(self, query)
   }
 
+  fn services(&self) -> &[Service<Self::D, Self::V>] {
+    DOC::services(self)
+  }
+
   fn resolve_method<'query, 'me, Q>(
     &'me self,
     query: Q,
@@ -85,4 +99,8 @@ impl<DOC: Document> Document for &DOC {
   {
     DOC::verify_data(self, data, options)
   }
+
+  fn is_deactivated(&self) -> bool {
+    DOC::is_deactivated(self)
+  }
 }
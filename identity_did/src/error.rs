@@ -21,6 +21,9 @@ pub enum Error {
   /// Caused by invalid or missing properties when constructing a [`CoreDocument`](crate::document::CoreDocument).
   #[error("invalid document property: {0}")]
   InvalidDocument(&'static str, #[source] Option<::identity_core::Error>),
+  /// Caused by a JSON Patch that cannot be applied to a [`CoreDocument`](crate::document::CoreDocument).
+  #[error("invalid document patch")]
+  InvalidPatch(#[source] ::identity_core::diff::Error),
   /// Caused by invalid or missing properties when constructing a [`Service`](crate::service::Service).
   #[error("invalid service property: {0}")]
   InvalidService(&'static str),
@@ -52,6 +55,11 @@ pub enum Error {
   InvalidKeyDataBase58,
   #[error("Invalid Multibase Key Data")]
   InvalidKeyDataMultibase,
+  #[error("Invalid JWK Key Data")]
+  InvalidKeyDataJwk,
+  /// Caused by a JWK containing private key material, which must not be published.
+  #[error("JWK contains private key material")]
+  PrivateKeyJwk,
 
   #[error("signature verification failed: {0}")]
   InvalidSignature(&'static str),
@@ -3,7 +3,6 @@
 
 use std::io::Write;
 
-use dataurl::DataUrl;
 use flate2::write::ZlibDecoder;
 use flate2::write::ZlibEncoder;
 use flate2::Compression;
@@ -19,6 +18,7 @@ use crate::service::Service;
 use crate::service::ServiceEndpoint;
 
 const DATA_URL_MEDIA_TYPE: &str = "application/octet-stream";
+const DATA_URL_SCHEME: &str = "data";
 
 /// A compressed bitmap for managing credential revocation.
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -66,28 +66,42 @@ impl RevocationBitmap {
   pub fn to_endpoint(&self) -> Result<ServiceEndpoint> {
     let endpoint_data: String = self.serialize_compressed_base64()?;
 
-    let mut data_url: DataUrl = DataUrl::new();
-    data_url.set_media_type(Some(DATA_URL_MEDIA_TYPE.to_owned()));
-    data_url.set_is_base64_encoded(true);
-    data_url.set_data(endpoint_data.as_bytes());
+    let data_url: String = format!(
+      "{}:{};base64,{}",
+      DATA_URL_SCHEME,
+      DATA_URL_MEDIA_TYPE,
+      BaseEncoding::encode(endpoint_data.as_bytes(), Base::Base64Pad)
+    );
 
-    Ok(ServiceEndpoint::One(Url::parse(data_url.to_string())?))
+    Ok(ServiceEndpoint::One(Url::parse(data_url)?))
   }
 
   /// Construct a `RevocationBitmap` from a data url embedded in `service_endpoint`.
   pub fn from_endpoint(service_endpoint: &ServiceEndpoint) -> Result<Self> {
     if let ServiceEndpoint::One(url) = service_endpoint {
-      let data_url: DataUrl =
-        DataUrl::parse(url.as_str()).map_err(|_| Error::InvalidService("invalid url - expected a data url"))?;
-
-      if !data_url.get_is_base64_encoded() || data_url.get_media_type() != DATA_URL_MEDIA_TYPE {
+      let (media_type, data): (&str, &str) = url
+        .path()
+        .split_once(',')
+        .filter(|_| url.scheme() == DATA_URL_SCHEME)
+        .ok_or(Error::InvalidService("invalid url - expected a data url"))?;
+
+      let mut parameters = media_type.split(';').map(str::trim);
+      if !parameters
+        .next()
+        .unwrap_or_default()
+        .eq_ignore_ascii_case(DATA_URL_MEDIA_TYPE)
+        || !parameters.any(|parameter| parameter.eq_ignore_ascii_case("base64"))
+      {
         return Err(Error::InvalidService(
           "invalid url - expected an `application/octet-stream;base64` data url",
         ));
       }
 
+      let decoded_data: Vec<u8> =
+        BaseEncoding::decode(data, Base::Base64Pad).map_err(|e| Error::Base64DecodingError(data.to_owned(), e))?;
+
       RevocationBitmap::deserialize_compressed_base64(
-        std::str::from_utf8(data_url.get_data())
+        std::str::from_utf8(&decoded_data)
           .map_err(|_| Error::InvalidService("invalid data url - expected valid utf-8"))?,
       )
     } else {
@@ -193,6 +207,10 @@ mod tests {
       RevocationBitmap::from_endpoint(&crate::service::ServiceEndpoint::One(Url::parse(URL).unwrap())).unwrap();
 
     assert!(bitmap.is_empty());
+    assert_eq!(
+      bitmap.to_endpoint().unwrap(),
+      crate::service::ServiceEndpoint::One(Url::parse(URL).unwrap())
+    );
   }
 
   #[test]
@@ -0,0 +1,229 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use indexmap::map::IndexMap;
+
+use identity_core::common::Object;
+use identity_core::common::OrderedSet;
+use identity_core::common::Url;
+
+use crate::did::CoreDID;
+use crate::did::DIDUrl;
+use crate::did::DID;
+use crate::error::Error;
+use crate::error::Result;
+use crate::service::Service;
+use crate::service::ServiceEndpoint;
+
+/// A DID Document Service linking the DID subject to one or more web origins.
+///
+/// A single origin is expressed as a URL endpoint, multiple origins as a map with the
+/// `origins` entry.
+///
+/// [Specification](https://identity.foundation/.well-known/resources/did-configuration/#linked-domain-service-endpoint)
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinkedDomainService<D = CoreDID, T = Object>
+where
+  D: DID,
+{
+  service: Service<D, T>,
+}
+
+impl<D, T> LinkedDomainService<D, T>
+where
+  D: DID,
+{
+  /// The name of the service type.
+  pub const TYPE: &'static str = "LinkedDomains";
+
+  /// The key of the service endpoint map listing multiple origins.
+  pub const ORIGINS: &'static str = "origins";
+
+  /// Creates a new `LinkedDomainService` with the given `id` linking to `domains`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `domains` is empty, a domain is not a web origin or `id` has no fragment.
+  pub fn new(id: DIDUrl<D>, domains: OrderedSet<Url>, properties: T) -> Result<Self> {
+    domains.iter().try_for_each(check_origin)?;
+
+    let service_endpoint: ServiceEndpoint = match domains.len() {
+      0 => return Err(Error::InvalidService("invalid endpoint - expected at least one origin")),
+      1 => ServiceEndpoint::One(domains.into_vec().remove(0)),
+      _ => {
+        let mut map: IndexMap<String, OrderedSet<Url>> = IndexMap::new();
+        map.insert(Self::ORIGINS.to_owned(), domains);
+        ServiceEndpoint::Map(map)
+      }
+    };
+
+    let service: Service<D, T> = Service::builder(properties)
+      .id(id)
+      .type_(Self::TYPE)
+      .service_endpoint(service_endpoint)
+      .build()?;
+
+    Ok(Self { service })
+  }
+
+  /// Returns the origins the DID subject is linked to.
+  pub fn domains(&self) -> &[Url] {
+    match self.service.service_endpoint() {
+      ServiceEndpoint::One(url) => core::slice::from_ref(url),
+      ServiceEndpoint::Map(map) => map.get(Self::ORIGINS).map(OrderedSet::as_slice).unwrap_or_default(),
+      ServiceEndpoint::Set(_) => &[],
+    }
+  }
+
+  /// Returns `true` if the DID subject is linked to the origin of `domain`.
+  pub fn links_to(&self, domain: &Url) -> bool {
+    self.domains().iter().any(|linked| linked.origin() == domain.origin())
+  }
+
+  /// Returns a reference to the underlying [`Service`].
+  pub fn service(&self) -> &Service<D, T> {
+    &self.service
+  }
+
+  /// Consumes the `LinkedDomainService` and returns the underlying [`Service`].
+  pub fn into_service(self) -> Service<D, T> {
+    self.service
+  }
+}
+
+impl<D, T> From<LinkedDomainService<D, T>> for Service<D, T>
+where
+  D: DID,
+{
+  fn from(service: LinkedDomainService<D, T>) -> Self {
+    service.service
+  }
+}
+
+impl<D, T> TryFrom<Service<D, T>> for LinkedDomainService<D, T>
+where
+  D: DID,
+{
+  type Error = Error;
+
+  fn try_from(service: Service<D, T>) -> Result<Self> {
+    if !service.type_().contains(Self::TYPE) {
+      return Err(Error::InvalidService("invalid type - expected `LinkedDomains`"));
+    }
+
+    match service.service_endpoint() {
+      ServiceEndpoint::One(url) => check_origin(url)?,
+      ServiceEndpoint::Map(map) => {
+        let origins: &OrderedSet<Url> = map
+          .get(Self::ORIGINS)
+          .filter(|_| map.len() == 1)
+          .ok_or(Error::InvalidService("invalid endpoint - expected an `origins` map"))?;
+        if origins.is_empty() {
+          return Err(Error::InvalidService("invalid endpoint - expected at least one origin"));
+        }
+        origins.iter().try_for_each(check_origin)?;
+      }
+      ServiceEndpoint::Set(_) => {
+        return Err(Error::InvalidService(
+          "invalid endpoint - expected a single origin or an `origins` map",
+        ))
+      }
+    }
+
+    Ok(Self { service })
+  }
+}
+
+/// Checks that `url` is a web origin, i.e. has no path, query or fragment.
+fn check_origin(url: &Url) -> Result<()> {
+  if !matches!(url.scheme(), "https" | "http")
+    || url.host().is_none()
+    || url.path() != "/"
+    || url.query().is_some()
+    || url.fragment().is_some()
+  {
+    return Err(Error::InvalidService("invalid endpoint - expected a web origin"));
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::convert::FromJson;
+  use identity_core::convert::ToJson;
+
+  use super::*;
+
+  fn service_id() -> DIDUrl<CoreDID> {
+    DIDUrl::parse("did:example:123#linked-domain").unwrap()
+  }
+
+  #[test]
+  fn test_linked_domain_service_one() {
+    let domain: Url = Url::parse("https://foo.example.com").unwrap();
+    let service: LinkedDomainService = LinkedDomainService::new(
+      service_id(),
+      OrderedSet::try_from(vec![domain.clone()]).unwrap(),
+      Object::new(),
+    )
+    .unwrap();
+
+    assert_eq!(service.domains(), std::slice::from_ref(&domain));
+    assert!(service.links_to(&Url::parse("https://foo.example.com/.well-known/did-configuration.json").unwrap()));
+    assert!(!service.links_to(&Url::parse("https://bar.example.com").unwrap()));
+
+    let service: Service = service.into_service();
+    assert_eq!(service.service_endpoint(), &ServiceEndpoint::One(domain));
+    assert!(LinkedDomainService::try_from(service).is_ok());
+  }
+
+  #[test]
+  fn test_linked_domain_service_origins() {
+    let domains: OrderedSet<Url> = OrderedSet::try_from(vec![
+      Url::parse("https://foo.example.com").unwrap(),
+      Url::parse("https://identity.foundation").unwrap(),
+    ])
+    .unwrap();
+    let service: LinkedDomainService = LinkedDomainService::new(service_id(), domains.clone(), Object::new()).unwrap();
+    assert_eq!(service.domains(), domains.as_slice());
+
+    let json: String = Service::from(service.clone()).to_json().unwrap();
+    let endpoint: &str = r#""serviceEndpoint":{"origins":["https://foo.example.com/","https://identity.foundation/"]}"#;
+    assert!(json.contains(endpoint));
+
+    let deserialized: Service = Service::from_json(&json).unwrap();
+    assert_eq!(LinkedDomainService::try_from(deserialized).unwrap(), service);
+  }
+
+  #[test]
+  fn test_linked_domain_service_invalid() {
+    let empty: OrderedSet<Url> = OrderedSet::new();
+    assert!(LinkedDomainService::new(service_id(), empty, Object::new()).is_err());
+
+    for domain in [
+      "https://foo.example.com/path",
+      "https://foo.example.com/?query",
+      "did:example:123",
+    ] {
+      let domains: OrderedSet<Url> = OrderedSet::try_from(vec![Url::parse(domain).unwrap()]).unwrap();
+      assert!(LinkedDomainService::new(service_id(), domains, Object::new()).is_err());
+    }
+
+    let service: Service = Service::builder(Object::new())
+      .id(service_id())
+      .type_("OtherService")
+      .service_endpoint(Url::parse("https://foo.example.com").unwrap())
+      .build()
+      .unwrap();
+    assert!(LinkedDomainService::try_from(service).is_err());
+
+    let service: Service = Service::builder(Object::new())
+      .id(service_id())
+      .type_(LinkedDomainService::<CoreDID>::TYPE)
+      .service_endpoint(OrderedSet::try_from(vec![Url::parse("https://foo.example.com").unwrap()]).unwrap())
+      .build()
+      .unwrap();
+    assert!(LinkedDomainService::try_from(service).is_err());
+  }
+}
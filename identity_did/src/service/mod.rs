@@ -5,9 +5,11 @@
 #![allow(clippy::module_inception)]
 
 mod builder;
+mod linked_domain_service;
 mod service;
 mod service_endpoint;
 
 pub use self::builder::ServiceBuilder;
+pub use self::linked_domain_service::LinkedDomainService;
 pub use self::service::Service;
 pub use self::service_endpoint::ServiceEndpoint;
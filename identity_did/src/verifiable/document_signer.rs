@@ -9,15 +9,19 @@ use identity_core::common::KeyComparable;
 use identity_core::common::Object;
 use identity_core::common::Timestamp;
 use identity_core::crypto::Ed25519;
+use identity_core::crypto::EdDsaJcs2022;
 use identity_core::crypto::JcsEd25519;
+use identity_core::crypto::KeyType;
 use identity_core::crypto::PrivateKey;
 use identity_core::crypto::ProofOptions;
 use identity_core::crypto::ProofPurpose;
+use identity_core::crypto::PushProof;
 use identity_core::crypto::SetSignature;
 use identity_core::crypto::Signer;
 
 use crate::document::CoreDocument;
 use crate::utils::DIDUrlQuery;
+use crate::verification::jwk_key_type;
 use crate::verification::MethodType;
 use crate::verification::TryMethod;
 use crate::verification::VerificationMethod;
@@ -93,6 +97,22 @@ where
     self.options = self.options.purpose(purpose);
     self
   }
+
+  /// Sets the [`Proof::id`](identity_core::crypto::Proof::id) field, allowing later proofs of a
+  /// proof chain to reference the proof.
+  #[must_use]
+  pub fn id(mut self, id: String) -> Self {
+    self.options = self.options.id(id);
+    self
+  }
+
+  /// Sets the [`Proof::previous_proof`](identity_core::crypto::Proof::previous_proof) field.
+  /// The proof will secure the proof with the given `id`, forming a proof chain.
+  #[must_use]
+  pub fn previous_proof(mut self, previous_proof: String) -> Self {
+    self.options = self.options.previous_proof(previous_proof);
+    self
+  }
 }
 
 impl<'base, 'query, D, T, U, V> DocumentSigner<'base, 'query, D, T, U, V>
@@ -123,8 +143,7 @@ where
   where
     X: Serialize + SetSignature + TryMethod,
   {
-    let query: DIDUrlQuery<'_> = self.method.clone().ok_or(Error::MethodNotFound)?;
-    let method: &VerificationMethod<D, U> = self.document.resolve_method(query, None).ok_or(Error::MethodNotFound)?;
+    let method: &VerificationMethod<D, U> = self.resolve_method()?;
     let method_uri: String = X::try_method(method)?;
 
     match method.type_() {
@@ -134,7 +153,52 @@ where
       MethodType::X25519KeyAgreementKey2019 => {
         return Err(Error::InvalidMethodType);
       }
+      MethodType::JsonWebKey2020 => match method.data().public_key_jwk().and_then(jwk_key_type) {
+        Some(KeyType::Ed25519) => {
+          JcsEd25519::<Ed25519>::create_signature(that, method_uri, self.private.as_ref(), self.options.clone())?;
+        }
+        _ => return Err(Error::InvalidMethodType),
+      },
+    }
+    Ok(())
+  }
+
+  /// Adds a `DataIntegrityProof` created with the configured verification method to the proofs
+  /// of the provided data, keeping any existing proofs.
+  ///
+  /// Proofs are created with the `eddsa-jcs-2022` cryptosuite, other proofs of the data should use
+  /// the same cryptosuite to form a valid proof set.
+  ///
+  /// # Errors
+  ///
+  /// Fails if an unsupported verification method is used, document
+  /// serialization fails, or the signature operation fails.
+  pub fn append<X>(&self, that: &mut X) -> Result<()>
+  where
+    X: Serialize + PushProof + TryMethod,
+  {
+    let method: &VerificationMethod<D, U> = self.resolve_method()?;
+    let method_uri: String = X::try_method(method)?;
+
+    match method.type_() {
+      MethodType::Ed25519VerificationKey2018 => {
+        EdDsaJcs2022::<Ed25519>::append_signature(that, method_uri, self.private.as_ref(), self.options.clone())?;
+      }
+      MethodType::X25519KeyAgreementKey2019 => {
+        return Err(Error::InvalidMethodType);
+      }
+      MethodType::JsonWebKey2020 => match method.data().public_key_jwk().and_then(jwk_key_type) {
+        Some(KeyType::Ed25519) => {
+          EdDsaJcs2022::<Ed25519>::append_signature(that, method_uri, self.private.as_ref(), self.options.clone())?;
+        }
+        _ => return Err(Error::InvalidMethodType),
+      },
     }
     Ok(())
   }
+
+  fn resolve_method(&self) -> Result<&VerificationMethod<D, U>> {
+    let query: DIDUrlQuery<'_> = self.method.clone().ok_or(Error::MethodNotFound)?;
+    self.document.resolve_method(query, None).ok_or(Error::MethodNotFound)
+  }
 }
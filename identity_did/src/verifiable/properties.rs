@@ -5,23 +5,25 @@ use std::ops::Deref;
 use std::ops::DerefMut;
 
 use identity_core::common::Object;
+use identity_core::common::OneOrMany;
+use identity_core::crypto::GetProofs;
 use identity_core::crypto::GetSignature;
 use identity_core::crypto::GetSignatureMut;
 use identity_core::crypto::Proof;
+use identity_core::crypto::PushProof;
 use identity_core::crypto::SetSignature;
 use identity_core::diff::Diff;
 
 use crate::verification::MethodUriType;
 use crate::verification::TryMethod;
 
-/// A generic container for a [`digital signature`][Proof] and a set of properties.
+/// A generic container for one or more [`digital signatures`][Proof] and a set of properties.
 #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
 pub struct VerifiableProperties<T = Object> {
   #[serde(flatten)]
   pub properties: T,
-  // TODO: Support multiple signatures (?)
-  #[serde(skip_serializing_if = "Option::is_none")]
-  pub(crate) proof: Option<Proof>,
+  #[serde(default, skip_serializing_if = "OneOrMany::is_empty")]
+  pub(crate) proof: OneOrMany<Proof>,
 }
 
 impl<T> VerifiableProperties<T> {
@@ -29,7 +31,7 @@ impl<T> VerifiableProperties<T> {
   pub const fn new(properties: T) -> Self {
     Self {
       properties,
-      proof: None,
+      proof: OneOrMany::Many(Vec::new()),
     }
   }
 
@@ -37,7 +39,7 @@ impl<T> VerifiableProperties<T> {
   pub const fn new_with_proof(properties: T, proof: Proof) -> Self {
     Self {
       properties,
-      proof: Some(proof),
+      proof: OneOrMany::One(proof),
     }
   }
 }
@@ -64,7 +66,7 @@ where
     let properties: T = T::from_diff(diff)?;
     Ok(VerifiableProperties {
       properties,
-      proof: None, // proof intentionally excluded
+      proof: OneOrMany::default(), // proof intentionally excluded
     })
   }
 
@@ -89,19 +91,35 @@ impl<T> DerefMut for VerifiableProperties<T> {
 
 impl<T> GetSignature for VerifiableProperties<T> {
   fn signature(&self) -> Option<&Proof> {
-    self.proof.as_ref()
+    self.proof.last()
   }
 }
 
 impl<T> GetSignatureMut for VerifiableProperties<T> {
   fn signature_mut(&mut self) -> Option<&mut Proof> {
-    self.proof.as_mut()
+    self
+      .proof
+      .len()
+      .checked_sub(1)
+      .and_then(|index| self.proof.get_mut(index))
   }
 }
 
 impl<T> SetSignature for VerifiableProperties<T> {
   fn set_signature(&mut self, signature: Proof) {
-    self.proof = Some(signature);
+    self.proof = OneOrMany::One(signature);
+  }
+}
+
+impl<T> GetProofs for VerifiableProperties<T> {
+  fn proofs(&self) -> &[Proof] {
+    self.proof.as_slice()
+  }
+}
+
+impl<T> PushProof for VerifiableProperties<T> {
+  fn push_proof(&mut self, proof: Proof) {
+    self.proof.push(proof);
   }
 }
 
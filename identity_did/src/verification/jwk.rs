@@ -0,0 +1,190 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Conversions between keys and [JSON Web Keys](https://tools.ietf.org/html/rfc7517).
+
+use identity_core::crypto::KeyPair;
+use identity_core::crypto::KeyType;
+use identity_core::crypto::PublicKey;
+use identity_core::utils::Base;
+use identity_core::utils::BaseEncoding;
+use libjose::jwk::EcCurve;
+use libjose::jwk::EcxCurve;
+use libjose::jwk::EdCurve;
+pub use libjose::jwk::Jwk;
+use libjose::jwk::JwkParams;
+use libjose::jwk::JwkParamsEc;
+use libjose::jwk::JwkParamsOkp;
+
+use crate::error::Error;
+use crate::error::Result;
+
+/// Marker of an uncompressed elliptic curve point in SEC1 encoding.
+const SEC1_UNCOMPRESSED: u8 = 0x04;
+
+/// Creates a public [`Jwk`] with key type `OKP` from an Ed25519 or X25519 `public_key`.
+pub fn jwk_from_public_key(key_type: KeyType, public_key: &PublicKey) -> Jwk {
+  let mut params: JwkParamsOkp = JwkParamsOkp::new();
+  params.crv = okp_curve(key_type).to_owned();
+  params.x = encode_b64(public_key.as_ref());
+  Jwk::from_params(params)
+}
+
+/// Creates a private [`Jwk`] with key type `OKP` from an Ed25519 or X25519 `keypair`.
+///
+/// NOTE: the result contains the private key and MUST NOT be published in a DID document.
+pub fn jwk_from_keypair(keypair: &KeyPair) -> Jwk {
+  let mut params: JwkParamsOkp = JwkParamsOkp::new();
+  params.crv = okp_curve(keypair.type_()).to_owned();
+  params.x = encode_b64(keypair.public().as_ref());
+  params.d = Some(encode_b64(keypair.private().as_ref()));
+  Jwk::from_params(params)
+}
+
+/// Creates a public [`Jwk`] with key type `EC` from an uncompressed SEC1-encoded `public_key`,
+/// i.e. `0x04 || x || y`.
+pub fn jwk_from_ec_public_key(curve: EcCurve, public_key: &[u8]) -> Result<Jwk> {
+  let coordinate_len: usize = ec_coordinate_len(curve);
+  match public_key.split_first() {
+    Some((&SEC1_UNCOMPRESSED, coordinates)) if coordinates.len() == 2 * coordinate_len => {
+      let (x, y): (&[u8], &[u8]) = coordinates.split_at(coordinate_len);
+      let mut params: JwkParamsEc = JwkParamsEc::new();
+      params.crv = curve.name().to_owned();
+      params.x = encode_b64(x);
+      params.y = encode_b64(y);
+      Ok(Jwk::from_params(params))
+    }
+    _ => Err(Error::InvalidKeyDataJwk),
+  }
+}
+
+/// Returns the [`KeyType`] of an `OKP` [`Jwk`], if it is an Ed25519 or X25519 key.
+pub fn jwk_key_type(jwk: &Jwk) -> Option<KeyType> {
+  let params: &JwkParamsOkp = jwk.try_okp_params().ok()?;
+  if params.try_ed_curve().ok() == Some(EdCurve::Ed25519) {
+    Some(KeyType::Ed25519)
+  } else if params.try_ecx_curve().ok() == Some(EcxCurve::X25519) {
+    Some(KeyType::X25519)
+  } else {
+    None
+  }
+}
+
+/// Returns the [`KeyType`] and public key of an Ed25519 or X25519 `OKP` [`Jwk`].
+pub fn jwk_to_public_key(jwk: &Jwk) -> Result<(KeyType, PublicKey)> {
+  let key_type: KeyType = jwk_key_type(jwk).ok_or(Error::InvalidKeyDataJwk)?;
+  let params: &JwkParamsOkp = jwk.try_okp_params().map_err(|_| Error::InvalidKeyDataJwk)?;
+  Ok((key_type, decode_b64(&params.x)?.into()))
+}
+
+/// Returns the curve and the uncompressed SEC1-encoded public key, i.e. `0x04 || x || y`,
+/// of an `EC` [`Jwk`].
+pub fn jwk_to_ec_public_key(jwk: &Jwk) -> Result<(EcCurve, Vec<u8>)> {
+  let params: &JwkParamsEc = jwk.try_ec_params().map_err(|_| Error::InvalidKeyDataJwk)?;
+  let curve: EcCurve = params.try_ec_curve().map_err(|_| Error::InvalidKeyDataJwk)?;
+  let coordinate_len: usize = ec_coordinate_len(curve);
+
+  let x: Vec<u8> = decode_b64(&params.x)?;
+  let y: Vec<u8> = decode_b64(&params.y)?;
+  if x.len() != coordinate_len || y.len() != coordinate_len {
+    return Err(Error::InvalidKeyDataJwk);
+  }
+
+  let mut public_key: Vec<u8> = Vec::with_capacity(1 + 2 * coordinate_len);
+  public_key.push(SEC1_UNCOMPRESSED);
+  public_key.extend_from_slice(&x);
+  public_key.extend_from_slice(&y);
+  Ok((curve, public_key))
+}
+
+/// Creates a [`KeyPair`] from a private Ed25519 or X25519 `OKP` [`Jwk`].
+pub fn jwk_to_keypair(jwk: &Jwk) -> Result<KeyPair> {
+  let key_type: KeyType = jwk_key_type(jwk).ok_or(Error::InvalidKeyDataJwk)?;
+  let params: &JwkParamsOkp = jwk.try_okp_params().map_err(|_| Error::InvalidKeyDataJwk)?;
+  let private_key: Vec<u8> = decode_b64(params.d.as_deref().ok_or(Error::InvalidKeyDataJwk)?)?;
+  let keypair: KeyPair = KeyPair::try_from_private_key_bytes(key_type, &private_key)?;
+
+  // Ensure the public key matches the private key.
+  if keypair.public().as_ref() != decode_b64(&params.x)?.as_slice() {
+    return Err(Error::InvalidKeyDataJwk);
+  }
+
+  Ok(keypair)
+}
+
+/// Returns `true` if the [`Jwk`] contains no private key material.
+///
+/// Symmetric (`oct`) keys are never public.
+pub fn jwk_is_public(jwk: &Jwk) -> bool {
+  match jwk.params() {
+    JwkParams::Oct(_) => false,
+    params => *params == params.to_public(),
+  }
+}
+
+fn okp_curve(key_type: KeyType) -> &'static str {
+  match key_type {
+    KeyType::Ed25519 => EdCurve::Ed25519.name(),
+    KeyType::X25519 => EcxCurve::X25519.name(),
+  }
+}
+
+fn ec_coordinate_len(curve: EcCurve) -> usize {
+  match curve {
+    EcCurve::P256 | EcCurve::Secp256K1 => 32,
+    EcCurve::P384 => 48,
+    EcCurve::P521 => 66,
+  }
+}
+
+fn encode_b64(data: &[u8]) -> String {
+  BaseEncoding::encode(data, Base::Base64Url)
+}
+
+fn decode_b64(data: &str) -> Result<Vec<u8>> {
+  BaseEncoding::decode(data, Base::Base64Url).map_err(|_| Error::InvalidKeyDataJwk)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_okp_roundtrip() {
+    for key_type in [KeyType::Ed25519, KeyType::X25519] {
+      let keypair: KeyPair = KeyPair::new(key_type).unwrap();
+
+      let public: Jwk = jwk_from_public_key(key_type, keypair.public());
+      assert!(jwk_is_public(&public));
+      assert_eq!(jwk_key_type(&public), Some(key_type));
+      let (decoded_type, decoded_key): (KeyType, PublicKey) = jwk_to_public_key(&public).unwrap();
+      assert_eq!(decoded_type, key_type);
+      assert_eq!(decoded_key.as_ref(), keypair.public().as_ref());
+
+      let private: Jwk = jwk_from_keypair(&keypair);
+      assert!(!jwk_is_public(&private));
+      assert_eq!(private.to_public(), public);
+      let decoded: KeyPair = jwk_to_keypair(&private).unwrap();
+      assert_eq!(decoded.public().as_ref(), keypair.public().as_ref());
+      assert_eq!(decoded.private().as_ref(), keypair.private().as_ref());
+
+      assert!(jwk_to_keypair(&public).is_err());
+    }
+  }
+
+  #[test]
+  fn test_ec_roundtrip() {
+    let private: Jwk = Jwk::random(libjose::jws::JwsAlgorithm::ES256).unwrap();
+    let public: Jwk = private.to_public();
+    assert!(!jwk_is_public(&private));
+    assert!(jwk_is_public(&public));
+
+    let (curve, sec1): (EcCurve, Vec<u8>) = jwk_to_ec_public_key(&public).unwrap();
+    assert_eq!(curve, EcCurve::P256);
+    assert_eq!(sec1.len(), 65);
+    assert_eq!(jwk_from_ec_public_key(curve, &sec1).unwrap(), public);
+
+    assert!(jwk_from_ec_public_key(EcCurve::P256, &sec1[1..]).is_err());
+    assert!(jwk_key_type(&public).is_none());
+  }
+}
@@ -4,9 +4,15 @@
 use core::fmt::Debug;
 use core::fmt::Formatter;
 use identity_core::utils::BaseEncoding;
+use libjose::jwk::Jwk;
+use libjose::jwk::JwkParams;
+use serde::Deserializer;
 
 use crate::error::Error;
 use crate::error::Result;
+use crate::verification::jwk::jwk_is_public;
+use crate::verification::jwk::jwk_to_ec_public_key;
+use crate::verification::jwk::jwk_to_public_key;
 
 /// Supported verification method data formats.
 #[derive(Clone, PartialEq, Deserialize, Serialize)]
@@ -15,6 +21,7 @@ use crate::error::Result;
 pub enum MethodData {
   PublicKeyMultibase(String),
   PublicKeyBase58(String),
+  PublicKeyJwk(#[serde(deserialize_with = "deserialize_public_jwk")] Box<Jwk>),
 }
 
 impl MethodData {
@@ -30,6 +37,26 @@ impl MethodData {
     Self::PublicKeyMultibase(BaseEncoding::encode_multibase(&data, None))
   }
 
+  /// Creates a new `MethodData` variant from a public [`Jwk`].
+  ///
+  /// # Errors
+  ///
+  /// Fails if the [`Jwk`] contains private key material.
+  pub fn new_jwk(jwk: Jwk) -> Result<Self> {
+    if !jwk_is_public(&jwk) {
+      return Err(Error::PrivateKeyJwk);
+    }
+    Ok(Self::PublicKeyJwk(Box::new(jwk)))
+  }
+
+  /// Returns the [`Jwk`] if this is a `PublicKeyJwk` variant.
+  pub fn public_key_jwk(&self) -> Option<&Jwk> {
+    match self {
+      Self::PublicKeyJwk(jwk) => Some(jwk),
+      _ => None,
+    }
+  }
+
   /// Returns a `Vec<u8>` containing the decoded bytes of the `MethodData`.
   ///
   /// This is generally a public key identified by a `MethodType` value.
@@ -38,12 +65,25 @@ impl MethodData {
   ///
   /// Decoding can fail if `MethodData` has invalid content or cannot be
   /// represented as a vector of bytes.
+  ///
+  /// `OKP` keys are decoded to the raw public key, `EC` keys to the uncompressed SEC1
+  /// encoding of the public key.
   pub fn try_decode(&self) -> Result<Vec<u8>> {
     match self {
       Self::PublicKeyMultibase(input) => {
         BaseEncoding::decode_multibase(input).map_err(|_| Error::InvalidKeyDataMultibase)
       }
       Self::PublicKeyBase58(input) => BaseEncoding::decode_base58(input).map_err(|_| Error::InvalidKeyDataBase58),
+      Self::PublicKeyJwk(jwk) => {
+        if !jwk_is_public(jwk) {
+          return Err(Error::PrivateKeyJwk);
+        }
+        match jwk.params() {
+          JwkParams::Okp(_) => jwk_to_public_key(jwk).map(|(_, public_key)| public_key.as_ref().to_vec()),
+          JwkParams::Ec(_) => jwk_to_ec_public_key(jwk).map(|(_, public_key)| public_key),
+          _ => Err(Error::InvalidKeyDataJwk),
+        }
+      }
     }
   }
 }
@@ -53,6 +93,61 @@ impl Debug for MethodData {
     match self {
       Self::PublicKeyMultibase(inner) => f.write_fmt(format_args!("PublicKeyMultibase({})", inner)),
       Self::PublicKeyBase58(inner) => f.write_fmt(format_args!("PublicKeyBase58({})", inner)),
+      Self::PublicKeyJwk(inner) => f.write_fmt(format_args!("PublicKeyJwk({:?})", inner)),
     }
   }
 }
+
+/// Rejects JWKs containing private key material, which must never be published.
+fn deserialize_public_jwk<'de, D>(deserializer: D) -> Result<Box<Jwk>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let jwk: Box<Jwk> = <Box<Jwk> as serde::Deserialize<'de>>::deserialize(deserializer)?;
+  if !jwk_is_public(&jwk) {
+    return Err(serde::de::Error::custom(Error::PrivateKeyJwk));
+  }
+  Ok(jwk)
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::convert::FromJson;
+  use identity_core::convert::ToJson;
+  use identity_core::crypto::KeyPair;
+  use identity_core::crypto::KeyType;
+
+  use crate::verification::jwk::jwk_from_keypair;
+  use crate::verification::jwk::jwk_from_public_key;
+
+  use super::*;
+
+  #[test]
+  fn test_jwk_method_data() {
+    let keypair: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+    let jwk: Jwk = jwk_from_public_key(KeyType::Ed25519, keypair.public());
+
+    let data: MethodData = MethodData::new_jwk(jwk.clone()).unwrap();
+    assert_eq!(data.public_key_jwk(), Some(&jwk));
+    assert_eq!(data.try_decode().unwrap(), keypair.public().as_ref());
+
+    let json: String = data.to_json().unwrap();
+    assert!(json.starts_with(r#"{"publicKeyJwk":"#));
+    assert_eq!(MethodData::from_json(&json).unwrap(), data);
+  }
+
+  #[test]
+  fn test_jwk_method_data_private_key() {
+    let keypair: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+    let private: Jwk = jwk_from_keypair(&keypair);
+
+    assert!(matches!(
+      MethodData::new_jwk(private.clone()),
+      Err(Error::PrivateKeyJwk)
+    ));
+
+    let data: MethodData = MethodData::PublicKeyJwk(Box::new(private));
+    assert!(matches!(data.try_decode(), Err(Error::PrivateKeyJwk)));
+    assert!(MethodData::from_json(&data.to_json().unwrap()).is_err());
+  }
+}
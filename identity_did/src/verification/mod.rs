@@ -7,6 +7,7 @@
 //! `identity_iota_core` crate.
 
 mod builder;
+mod jwk;
 mod method_data;
 mod method_ref;
 mod method_relationship;
@@ -16,6 +17,15 @@ mod traits;
 mod verification_method;
 
 pub use self::builder::MethodBuilder;
+pub use self::jwk::jwk_from_ec_public_key;
+pub use self::jwk::jwk_from_keypair;
+pub use self::jwk::jwk_from_public_key;
+pub use self::jwk::jwk_is_public;
+pub use self::jwk::jwk_key_type;
+pub use self::jwk::jwk_to_ec_public_key;
+pub use self::jwk::jwk_to_keypair;
+pub use self::jwk::jwk_to_public_key;
+pub use self::jwk::Jwk;
 pub use self::method_data::MethodData;
 pub use self::method_ref::MethodRef;
 pub use self::method_relationship::MethodRelationship;
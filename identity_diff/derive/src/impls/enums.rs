@@ -1,4 +1,4 @@
-// Copyright 2020-2021 IOTA Stiftung
+// Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
 #![allow(non_snake_case)]
@@ -7,6 +7,7 @@ use crate::model::DataFields;
 use crate::model::EVariant;
 use crate::model::InputModel;
 use crate::model::SVariant;
+use core::fmt::Display;
 use proc_macro2::Ident;
 use proc_macro2::TokenStream;
 use quote::format_ident;
@@ -22,20 +23,11 @@ pub fn derive_diff_enum(input: &InputModel) -> TokenStream {
   let diff: &Ident = input.diff();
   let evariants: &Vec<EVariant> = input.e_variants();
 
-  let serde_attrs = if input.from_into() {
-    let name = input.name();
-    let stype = quote!(#name).to_string();
-
-    quote! {
-        #[serde(from=#stype, into=#stype)]
-    }
-  } else {
-    quote! {}
-  };
+  let serde_attrs: TokenStream = input.serde_attrs();
 
   let param_decls: &Punctuated<GenericParam, Comma> = input.param_decls();
 
-  let clause = quote! {};
+  let clause: &WhereClause = input.clause();
 
   let param_decls: Vec<TokenStream> = param_decls
     .iter()
@@ -60,28 +52,42 @@ pub fn derive_diff_enum(input: &InputModel) -> TokenStream {
     .iter()
     .map(|var| {
       let vname = &var.name;
+      let vserde = &var.serde;
       let typs: Vec<TokenStream> = var.fields.iter().map(|f| f.typ_as_tokens()).collect();
 
       match var.variant {
         // for named variant.
         SVariant::Named => {
           let fnames: Vec<&Ident> = var.fields.iter().map(|f| f.name()).collect();
+          let fserde: Vec<TokenStream> = var.fields.iter().map(|f| f.serde_attrs()).collect();
 
           // generate code.
           quote! {
+              #vserde
               #vname {
                   #(
-                      #[doc(hidden)] #[serde(skip_serializing_if = "Option::is_none")] #fnames: #typs,
+                      #[doc(hidden)] #[serde(skip_serializing_if = "Option::is_none")] #fserde #fnames: #typs,
                   )*
               },
           }
         }
         // generate code for tuple variant.
-        SVariant::Tuple => quote! {
-            #vname( #( #[doc(hidden)] #[serde(skip_serializing_if = "Option::is_none")] #typs, )* ),
-        },
+        SVariant::Tuple => {
+          // fields are deserialized by position, so only a single field can be skipped.
+          let skip = if var.fields.len() == 1 {
+            quote! { #[serde(skip_serializing_if = "Option::is_none")] }
+          } else {
+            quote! {}
+          };
+
+          quote! {
+              #vserde
+              #vname( #( #[doc(hidden)] #skip #typs, )* ),
+          }
+        }
         // generate code for unit variant.
         SVariant::Unit => quote! {
+            #vserde
             #vname,
         },
       }
@@ -743,11 +749,13 @@ fn parse_from_into(
           if f.should_ignore() {
             quote! {#fname: Default::default()}
           } else {
+            let missing: TokenStream = missing_field(f, diff, vname, fname);
+
             quote! {
                 #fname: <#ftyp>::from_diff(
                     match #fname {
                         Some(v) => v,
-                        None => <#ftyp>::default().into_diff()?
+                        None => #missing
                     }
                 )?
             }
@@ -811,11 +819,13 @@ fn parse_from_into(
           if f.should_ignore() {
             quote! { Default::default() }
           } else {
+            let missing: TokenStream = missing_field(f, diff, vname, f.position());
+
             quote! {
                 <#ftyp>::from_diff(
                     match #fname {
                         Some(v) => v,
-                        None => <#ftyp>::default().into_diff()?
+                        None => #missing
                     }
                 )?
             }
@@ -883,6 +893,20 @@ fn parse_from_into(
   (from_body.to_vec(), into_body.to_vec())
 }
 
+/// handle a field missing from the diff of a variant.  A missing `Option` is `None`, whereas other fields are always
+/// part of the diff of a variant that is converted, so no `Default` is required for them.
+fn missing_field(field: &DataFields, diff: &Ident, vname: &Ident, fname: impl Display) -> TokenStream {
+  let ftyp = field.typ();
+
+  if field.is_option() {
+    quote! { <#ftyp>::default().into_diff()? }
+  } else {
+    let message: String = format!("`{}::{}` is missing field `{}`", diff, vname, fname);
+
+    quote! { return Err(identity_diff::Error::convert(#message)) }
+  }
+}
+
 // create field names based on thee size of an enum.
 fn populate_field_names(vfields: &[DataFields], fmax: usize, struct_type: SVariant) -> (Vec<Ident>, Vec<Ident>) {
   match struct_type {
@@ -1,4 +1,4 @@
-// Copyright 2020-2021 IOTA Stiftung
+// Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
 #![allow(non_snake_case)]
@@ -19,16 +19,7 @@ pub fn derive_diff_struct(input: &InputModel) -> TokenStream {
   let fields = input.fields();
   let param_decls = input.param_decls();
   let clause = input.clause();
-  let serde_attrs = if input.from_into() {
-    let name = input.name();
-    let stype = quote!(#name).to_string();
-
-    quote! {
-        #[serde(from=#stype, into=#stype)]
-    }
-  } else {
-    quote! {}
-  };
+  let serde_attrs = input.serde_attrs();
 
   // set the param declarations.
   let param_decls: Vec<TokenStream> = param_decls
@@ -63,8 +54,9 @@ pub fn derive_diff_struct(input: &InputModel) -> TokenStream {
   match svariant {
     // for name structs.
     SVariant::Named => {
-      // get te field names
+      // get te field names and their serde attributes.
       let field_names: Vec<&Ident> = fields.iter().map(|field| field.name()).collect();
+      let field_serde: Vec<TokenStream> = fields.iter().map(|field| field.serde_attrs()).collect();
 
       // generate the Diff struct.
       quote! {
@@ -74,18 +66,30 @@ pub fn derive_diff_struct(input: &InputModel) -> TokenStream {
           pub struct #diff<#(#param_decls),*>
               #clause
           {
-              #( #[doc(hidden)] #[serde(skip_serializing_if = "Option::is_none")] pub(self) #field_names: #field_tps, )*
+              #(
+                  #[doc(hidden)]
+                  #[serde(skip_serializing_if = "Option::is_none")]
+                  #field_serde
+                  pub(self) #field_names: #field_tps,
+              )*
           }
       }
     }
     // for Tuple variant Structs.
     SVariant::Tuple => {
+      // fields are deserialized by position, so only a single field can be skipped.
+      let skip = if fields.len() == 1 {
+        quote! { #[serde(skip_serializing_if = "Option::is_none")] }
+      } else {
+        quote! {}
+      };
+
       // generate the Diff struct.
       quote! {
           #[derive(Clone, PartialEq, serde::Deserialize, serde::Serialize, Default)]
           #serde_attrs
           pub struct #diff<#(#param_decls),*> (
-              #( #[doc(hidden)] #[serde(skip_serializing_if = "Option::is_none")] pub(self) #field_tps, )*
+              #( #[doc(hidden)] #skip pub(self) #field_tps, )*
           ) #clause ;
       }
     }
@@ -18,6 +18,7 @@ mod utils;
 
 /// Entry point for the `Diff` derive proc macro.  `Diff` implements the `Diff` trait from the `identity_diff` crate on
 /// any Enum or Struct type.  Contains and optional attribute `should_ignore` which will ignore an appended field.
+/// Unsupported input, such as unions, results in a compile error.
 #[proc_macro_derive(Diff, attributes(diff))]
 pub fn derive_diff(input: TokenStream) -> TokenStream {
   let input = parse_macro_input!(input as DeriveInput);
@@ -26,7 +27,10 @@ pub fn derive_diff(input: TokenStream) -> TokenStream {
 
 /// Function for dealing with the internal logic of the macro.
 fn internal(input: DeriveInput) -> TokenStream {
-  let model: InputModel = InputModel::parse(&input);
+  let model: InputModel = match InputModel::parse(&input) {
+    Ok(model) => model,
+    Err(error) => return error.to_compile_error().into(),
+  };
   // debug implementation derivation.
   let debug = model.impl_debug();
   // diff type derivation.
@@ -1,4 +1,4 @@
-// Copyright 2020-2021 IOTA Stiftung
+// Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::extract_option_segment;
@@ -11,20 +11,29 @@ use crate::impls::impl_diff_enum;
 use crate::impls::impl_from_into;
 use crate::parse_from_into;
 use crate::should_ignore;
+use crate::utils::forward_serde;
+use crate::utils::uses_params;
 use proc_macro2::Ident;
 use proc_macro2::Literal;
 use proc_macro2::Span;
 use proc_macro2::TokenStream;
 use quote::format_ident;
 use quote::quote;
+use syn::parse_quote;
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
 use syn::Data;
 use syn::DataEnum;
 use syn::DataStruct;
+use syn::DataUnion;
 use syn::DeriveInput;
+use syn::Error;
 use syn::Fields;
+use syn::GenericArgument;
 use syn::GenericParam;
+use syn::PathArguments;
+use syn::PathSegment;
+use syn::Result;
 use syn::Token;
 use syn::Type;
 use syn::Variant;
@@ -54,6 +63,8 @@ pub struct InputEnum {
   pub clause: WhereClause,
   // should this enum be serialized/deserialized as its non-diff counterpart.
   pub from_into: bool,
+  // serde attributes forwarded to the diff type.
+  pub serde: TokenStream,
 }
 
 /// Sorts data regarding incoming Structs.
@@ -75,6 +86,8 @@ pub struct InputStruct {
   pub clause: WhereClause,
   // should this struct be serialized/deserialized as its non-diff counterpart/
   pub from_into: bool,
+  // serde attributes forwarded to the diff type.
+  pub serde: TokenStream,
 }
 
 /// Enum variant data.
@@ -86,6 +99,8 @@ pub struct EVariant {
   pub name: Ident,
   // variant fields.
   pub fields: Vec<DataFields>,
+  // serde attributes forwarded to the diff variant.
+  pub serde: TokenStream,
 }
 
 /// Struct Variant structure types.
@@ -106,6 +121,8 @@ pub enum DataFields {
     typ: Type,
     // should ignore flag.
     should_ignore: bool,
+    // serde attributes forwarded to the diff field.
+    serde: TokenStream,
   },
   Unnamed {
     // field position.
@@ -118,32 +135,47 @@ pub enum DataFields {
 }
 
 impl InputModel {
-  // parse the `DeriveInput` into an `InputModel`.
-  pub fn parse(input: &DeriveInput) -> Self {
+  // parse the `DeriveInput` into an `InputModel`, or return a compile error for unsupported input.
+  pub fn parse(input: &DeriveInput) -> Result<Self> {
+    // the diff type has to be deserializable without borrowing, which rules out lifetime parameters.
+    if let Some(lifetime) = input.generics.lifetimes().next() {
+      return Err(Error::new_spanned(
+        lifetime,
+        "`Diff` cannot be derived for types with lifetime parameters",
+      ));
+    }
+
     match &input.data {
       // Check for a struct with fields.
       Data::Struct(DataStruct { fields, .. }) if !fields.is_empty() => Self::parse_struct(input, fields),
       // check for a unit struct.
       Data::Struct(DataStruct { .. }) => Self::parse_unit(input),
       // check for an enum.
-      Data::Enum(DataEnum { variants, .. }) => Self::parse_enum(input, variants),
-      _ => panic!("Data Type not supported"),
+      Data::Enum(DataEnum { variants, .. }) if !variants.is_empty() => Self::parse_enum(input, variants),
+      // an enum without variants has no values to diff.
+      Data::Enum(DataEnum { enum_token, .. }) => Err(Error::new_spanned(
+        enum_token,
+        "`Diff` cannot be derived for enums without variants",
+      )),
+      Data::Union(DataUnion { union_token, .. }) => {
+        Err(Error::new_spanned(union_token, "`Diff` cannot be derived for unions"))
+      }
     }
   }
 
   /// parse structs.
-  fn parse_struct(input: &DeriveInput, fields: &Fields) -> Self {
-    Self::Struct(InputStruct::parse(input, fields))
+  fn parse_struct(input: &DeriveInput, fields: &Fields) -> Result<Self> {
+    InputStruct::parse(input, fields).map(Self::Struct)
   }
 
   /// parse unit structs.
-  fn parse_unit(input: &DeriveInput) -> Self {
-    Self::Struct(InputStruct::parse_unit(input))
+  fn parse_unit(input: &DeriveInput) -> Result<Self> {
+    InputStruct::parse_unit(input).map(Self::Struct)
   }
 
   /// parse enums.
-  fn parse_enum(input: &DeriveInput, variants: &Punctuated<Variant, Comma>) -> Self {
-    Self::Enum(InputEnum::parse(input, variants))
+  fn parse_enum(input: &DeriveInput, variants: &Punctuated<Variant, Comma>) -> Result<Self> {
+    InputEnum::parse(input, variants).map(Self::Enum)
   }
 
   /// get struct variant.
@@ -248,110 +280,152 @@ impl InputModel {
       Self::Enum(InputEnum { from_into, .. }) => *from_into,
     }
   }
+
+  /// get the serde attributes for the Diff type.
+  pub fn serde_attrs(&self) -> TokenStream {
+    let (name, params, serde) = match self {
+      Self::Enum(InputEnum {
+        name, params, serde, ..
+      }) => (name, params, serde),
+      Self::Struct(InputStruct {
+        name, params, serde, ..
+      }) => (name, params, serde),
+    };
+
+    if self.from_into() {
+      let stype = if params.is_empty() {
+        quote!(#name).to_string()
+      } else {
+        quote!(#name<#params>).to_string()
+      };
+
+      quote! {
+          #serde
+          #[serde(from=#stype, into=#stype)]
+      }
+    } else {
+      serde.clone()
+    }
+  }
 }
 
 impl InputEnum {
   /// create a new `InputEnum`.
-  pub fn new(input: &DeriveInput) -> Self {
-    let from_into = parse_from_into(input);
-    Self {
+  pub fn new(input: &DeriveInput) -> Result<Self> {
+    let from_into = parse_from_into(input)?;
+    Ok(Self {
       name: input.ident.clone(),
       diff: format_ident!("Diff{}", &input.ident),
       variants: Vec::new(),
-      param_decls: input.generics.params.clone(),
-      params: input
-        .generics
-        .type_params()
-        .map(|type_param| type_param.ident.clone())
-        .collect(),
+      param_decls: param_decls(input),
+      params: params(input),
       clause: input.generics.where_clause.clone().unwrap_or_else(|| WhereClause {
         where_token: Token![where](Span::call_site()),
         predicates: Punctuated::new(),
       }),
       from_into,
-    }
+      serde: forward_serde(&input.attrs, &["rename_all"])?,
+    })
   }
 
   /// parse the enum.
-  fn parse(input: &DeriveInput, variants: &Punctuated<Variant, Comma>) -> Self {
-    let mut model = Self::new(input);
-    variants.iter().for_each(|vars| {
+  fn parse(input: &DeriveInput, variants: &Punctuated<Variant, Comma>) -> Result<Self> {
+    let mut model = Self::new(input)?;
+    for vars in variants.iter() {
       let mut variant = EVariant::new(&vars.ident);
+      variant.serde = forward_serde(&vars.attrs, &["rename", "alias"])?;
 
-      vars.fields.iter().enumerate().for_each(|(idx, fs)| {
+      for (idx, fs) in vars.fields.iter().enumerate() {
         if let Some(ident) = fs.ident.as_ref() {
           variant.variant = SVariant::Named;
           variant.fields.push(DataFields::Named {
             name: ident.clone(),
             typ: fs.ty.clone(),
-            should_ignore: should_ignore(fs),
+            should_ignore: should_ignore(fs)?,
+            serde: forward_serde(&fs.attrs, &["rename", "alias"])?,
           });
         } else {
           variant.variant = SVariant::Tuple;
           variant.fields.push(DataFields::Unnamed {
             position: Literal::usize_unsuffixed(idx),
             typ: fs.ty.clone(),
-            should_ignore: should_ignore(fs),
+            should_ignore: should_ignore(fs)?,
           });
         }
-      });
+      }
+
+      add_field_bounds(&mut model.clause, input, &variant.fields);
       model.variants.push(variant);
-    });
+    }
+
+    if !model.from_into {
+      let bound: TokenStream = serde_bound(input, model.variants.iter().flat_map(|variant| variant.fields.iter()));
+      model.serde.extend(bound);
+    }
 
-    model
+    Ok(model)
   }
 }
 
 impl InputStruct {
   /// create a new `InputStruct`.
-  pub fn new(input: &DeriveInput) -> Self {
-    let from_into = parse_from_into(input);
+  pub fn new(input: &DeriveInput) -> Result<Self> {
+    let from_into = parse_from_into(input)?;
 
-    Self {
+    Ok(Self {
       variant: SVariant::Unit,
       name: input.ident.clone(),
       diff: format_ident!("Diff{}", &input.ident),
       fields: Vec::new(),
-      param_decls: input.generics.params.clone(),
-      params: input.generics.type_params().map(|tp| tp.ident.clone()).collect(),
+      param_decls: param_decls(input),
+      params: params(input),
       clause: input.generics.where_clause.clone().unwrap_or_else(|| WhereClause {
         where_token: Token![where](Span::call_site()),
         predicates: Punctuated::new(),
       }),
       from_into,
-    }
+      serde: forward_serde(&input.attrs, &["rename_all"])?,
+    })
   }
 
   /// parse the ast into for the `InputStruct`.
-  fn parse(input: &DeriveInput, fields: &Fields) -> Self {
-    let mut model = Self::new(input);
-    fields.iter().enumerate().for_each(|(idx, fs)| {
+  fn parse(input: &DeriveInput, fields: &Fields) -> Result<Self> {
+    let mut model = Self::new(input)?;
+    for (idx, fs) in fields.iter().enumerate() {
       if let Some(ident) = fs.ident.as_ref() {
         model.variant = SVariant::Named;
         model.fields.push(DataFields::Named {
           name: ident.clone(),
           typ: fs.ty.clone(),
-          should_ignore: should_ignore(fs),
+          should_ignore: should_ignore(fs)?,
+          serde: forward_serde(&fs.attrs, &["rename", "alias"])?,
         });
       } else {
         model.variant = SVariant::Tuple;
         model.fields.push(DataFields::Unnamed {
           position: Literal::usize_unsuffixed(idx),
           typ: fs.ty.clone(),
-          should_ignore: should_ignore(fs),
+          should_ignore: should_ignore(fs)?,
         });
       }
-    });
+    }
 
-    model
+    add_field_bounds(&mut model.clause, input, &model.fields);
+
+    if !model.from_into {
+      let bound: TokenStream = serde_bound(input, model.fields.iter());
+      model.serde.extend(bound);
+    }
+
+    Ok(model)
   }
 
   /// parse data for a unit struct.
-  fn parse_unit(input: &DeriveInput) -> Self {
-    let mut model = Self::new(input);
+  fn parse_unit(input: &DeriveInput) -> Result<Self> {
+    let mut model = Self::new(input)?;
     model.variant = SVariant::Unit;
 
-    model
+    Ok(model)
   }
 }
 
@@ -362,6 +436,7 @@ impl EVariant {
       variant: SVariant::Unit,
       name: name.clone(),
       fields: Vec::new(),
+      serde: TokenStream::new(),
     }
   }
 }
@@ -419,6 +494,30 @@ impl DataFields {
     }
   }
 
+  /// get the serde attributes for the field of the Diff type.
+  pub fn serde_attrs(&self) -> TokenStream {
+    match self {
+      Self::Named { serde, .. } => serde.clone(),
+      Self::Unnamed { .. } => TokenStream::new(),
+    }
+  }
+
+  /// get the `T` of a field of type `Option<T>`.
+  pub fn option_inner(&self) -> Option<&Type> {
+    let segment: &PathSegment = match self.typ() {
+      Type::Path(typepath) if typepath.qself.is_none() => extract_option_segment(&typepath.path)?,
+      _ => return None,
+    };
+
+    match &segment.arguments {
+      PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+        GenericArgument::Type(typ) => Some(typ),
+        _ => None,
+      }),
+      _ => None,
+    }
+  }
+
   /// check to see if the should ignore flag is set for the field.
   pub fn should_ignore(&self) -> bool {
     match self {
@@ -427,3 +526,100 @@ impl DataFields {
     }
   }
 }
+
+/// get the generic param declarations without their defaults, which are not allowed on impl blocks.
+fn param_decls(input: &DeriveInput) -> Punctuated<GenericParam, Comma> {
+  input
+    .generics
+    .params
+    .iter()
+    .cloned()
+    .map(|mut param| {
+      match &mut param {
+        GenericParam::Type(typ) => {
+          typ.eq_token = None;
+          typ.default = None;
+        }
+        GenericParam::Const(cp) => {
+          cp.eq_token = None;
+          cp.default = None;
+        }
+        GenericParam::Lifetime(_) => {}
+      }
+      param
+    })
+    .collect()
+}
+
+/// get the type and const params used to name the type in impl blocks.
+fn params(input: &DeriveInput) -> Punctuated<Ident, Comma> {
+  input
+    .generics
+    .params
+    .iter()
+    .filter_map(|param| match param {
+      GenericParam::Type(typ) => Some(typ.ident.clone()),
+      GenericParam::Const(cp) => Some(cp.ident.clone()),
+      GenericParam::Lifetime(_) => None,
+    })
+    .collect()
+}
+
+/// require `identity_diff::Diff` for field types built from the type params, e.g. `Vec<T>`, since the diff type
+/// refers to their `Diff::Type`.  `Option<T>` fields require the bounds of its `Diff` implementation on `T` instead,
+/// which keeps `Diff::Type` of the field known to be a `DiffOption`.
+fn add_field_bounds(clause: &mut WhereClause, input: &DeriveInput, fields: &[DataFields]) {
+  let type_params: Vec<&Ident> = input.generics.type_params().map(|typ| &typ.ident).collect();
+
+  for field in fields.iter().filter(|field| !field.should_ignore()) {
+    let typ: &Type = field.typ();
+
+    if !uses_params(typ, &type_params) {
+      continue;
+    }
+
+    match field.option_inner() {
+      Some(inner) => clause.predicates.push(parse_quote! {
+        #inner: identity_diff::Diff
+          + std::clone::Clone
+          + std::fmt::Debug
+          + std::cmp::PartialEq
+          + std::default::Default
+          + serde::de::DeserializeOwned
+          + serde::Serialize
+      }),
+      None => clause.predicates.push(parse_quote!(#typ: identity_diff::Diff)),
+    }
+  }
+}
+
+/// replace the bounds serde infers for the type params of the diff type.  Fields that are diffed are (de)serialized
+/// through their `Diff::Type`, so only the ignored fields built from the type params need bounds.
+fn serde_bound<'a>(input: &DeriveInput, fields: impl Iterator<Item = &'a DataFields>) -> TokenStream {
+  let type_params: Vec<&Ident> = input.generics.type_params().map(|typ| &typ.ident).collect();
+
+  if type_params.is_empty() {
+    return TokenStream::new();
+  }
+
+  let ignored: Vec<&Type> = fields
+    .filter(|field| field.should_ignore())
+    .map(|field| field.typ())
+    .filter(|typ| uses_params(typ, &type_params))
+    .collect();
+
+  let serialize: String = ignored
+    .iter()
+    .map(|typ| quote!(#typ: serde::Serialize).to_string())
+    .collect::<Vec<String>>()
+    .join(", ");
+  let deserialize: String = ignored
+    .iter()
+    .map(|typ| quote!(#typ: serde::de::DeserializeOwned).to_string())
+    .collect::<Vec<String>>()
+    .join(", ");
+
+  quote! {
+      #[serde(bound(serialize = #serialize, deserialize = #deserialize))]
+  }
+}
@@ -1,18 +1,22 @@
-// Copyright 2020-2021 IOTA Stiftung
+// Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use proc_macro2::Delimiter;
+use proc_macro2::Ident;
+use proc_macro2::TokenStream;
 use proc_macro2::TokenTree;
-use quote::format_ident;
+use quote::quote;
+use quote::ToTokens;
+use syn::Attribute;
 use syn::DeriveInput;
+use syn::Error;
 use syn::Field;
 use syn::Meta;
 use syn::MetaList;
 use syn::NestedMeta;
 use syn::Path;
 use syn::PathSegment;
-
-const PARENS: Delimiter = Delimiter::Parenthesis;
+use syn::Result;
+use syn::Type;
 
 /// checks to see if a field's type is `Option`.  This logic is necessary to find cases where fields contain nested
 /// Options and avoid a `Some(None)` case.
@@ -29,52 +33,71 @@ pub fn extract_option_segment(path: &Path) -> Option<&PathSegment> {
 }
 
 /// checks to see if the `should_ignore` attribute has been put before a field.
-pub fn should_ignore(field: &Field) -> bool {
-  let find = field.attrs.iter().find(|field| {
-    let attr_seg: Vec<String> = field.path.segments.iter().map(|seg| format!("{}", seg.ident)).collect();
+pub fn should_ignore(field: &Field) -> Result<bool> {
+  has_diff_option(&field.attrs, "should_ignore")
+}
 
-    let diff_attr = attr_seg == ["diff"];
-    let arg_iter = field.tokens.clone().into_iter().next();
+/// checks to see if the `from_into` attribute has been put before a struct or enum.
+pub fn parse_from_into(input: &DeriveInput) -> Result<bool> {
+  has_diff_option(&input.attrs, "from_into")
+}
 
-    let should_ignore = match arg_iter {
-      Some(TokenTree::Group(gr)) if gr.delimiter() == PARENS => gr
-        .stream()
-        .into_iter()
-        .map(|tt| format!("{}", tt))
-        .any(|x| x == "should_ignore"),
-      _ => false,
+/// checks the `diff` attributes for the `expected` option and rejects any other option.
+fn has_diff_option(attrs: &[Attribute], expected: &str) -> Result<bool> {
+  let mut found: bool = false;
+
+  for attr in attrs.iter().filter(|attr| attr.path.is_ident("diff")) {
+    let nested = match attr.parse_meta()? {
+      Meta::List(MetaList { nested, .. }) => nested,
+      meta => return Err(Error::new_spanned(meta, format!("expected `#[diff({})]`", expected))),
     };
 
-    diff_attr && should_ignore
-  });
+    for meta in nested.iter() {
+      match meta {
+        NestedMeta::Meta(Meta::Path(path)) if path.is_ident(expected) => found = true,
+        _ => {
+          return Err(Error::new_spanned(
+            meta,
+            format!("unsupported diff attribute, expected `{}`", expected),
+          ))
+        }
+      }
+    }
+  }
 
-  find.is_some()
+  Ok(found)
 }
 
-pub fn parse_from_into(input: &DeriveInput) -> bool {
-  let find = input.attrs.iter().find(|a| {
-    if let Meta::List(MetaList { path, nested, .. }) = a.parse_meta().unwrap() {
-      {
-        if let Some(ident) = path.get_ident() {
-          if "diff" == format!("{}", format_ident!("{}", ident)) {
-            let find_nested = nested.iter().find(|m| {
-              if let NestedMeta::Meta(Meta::Path(p)) = m {
-                if let Some(ident) = p.get_ident() {
-                  if "from_into" == format!("{}", format_ident!("{}", ident)) {
-                    return true;
-                  }
-                }
-              }
-              false
-            });
+/// collects the `serde` attribute options named in `forwarded` so they can be put on the Diff type.  Keeps the names
+/// used by the Diff type in line with the names used by the original type.
+pub fn forward_serde(attrs: &[Attribute], forwarded: &[&str]) -> Result<TokenStream> {
+  let mut options: Vec<NestedMeta> = Vec::new();
 
-            return find_nested.is_some();
-          }
-        }
-      }
+  for attr in attrs.iter().filter(|attr| attr.path.is_ident("serde")) {
+    if let Meta::List(MetaList { nested, .. }) = attr.parse_meta()? {
+      options.extend(nested.into_iter().filter(|meta| match meta {
+        NestedMeta::Meta(meta) => forwarded.iter().any(|name| meta.path().is_ident(name)),
+        NestedMeta::Lit(_) => false,
+      }));
     }
-    false
-  });
+  }
+
+  if options.is_empty() {
+    Ok(quote! {})
+  } else {
+    Ok(quote! { #[serde(#(#options),*)] })
+  }
+}
+
+/// checks to see if a field's type refers to any of the type parameters in `params`.
+pub fn uses_params(typ: &Type, params: &[&Ident]) -> bool {
+  fn visit(tokens: TokenStream, params: &[&Ident]) -> bool {
+    tokens.into_iter().any(|tt| match tt {
+      TokenTree::Ident(ident) => params.iter().any(|param| **param == ident),
+      TokenTree::Group(group) => visit(group.stream(), params),
+      _ => false,
+    })
+  }
 
-  find.is_some()
+  visit(typ.to_token_stream(), params)
 }
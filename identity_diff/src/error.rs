@@ -13,6 +13,8 @@ pub enum Error {
   MergeError(String),
   #[error("Conversion Error: {0}")]
   ConversionError(String),
+  #[error("Patch Error: {0}")]
+  PatchError(String),
 }
 
 impl Error {
@@ -36,4 +38,11 @@ impl Error {
   {
     Self::ConversionError(format!("{}", message))
   }
+
+  pub fn patch<T>(message: T) -> Self
+  where
+    T: Display,
+  {
+    Self::PatchError(format!("{}", message))
+  }
 }
@@ -0,0 +1,363 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::Diff;
+use crate::Error;
+use crate::Result;
+
+/// A JSON Patch document as defined by [RFC 6902](https://www.rfc-editor.org/rfc/rfc6902), i.e. a sequence of
+/// operations that are applied to a JSON value in order.
+///
+/// Unlike the `Diff::Type` of a value, a `JsonPatch` is a standard format that can be read without this library.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct JsonPatch(pub Vec<PatchOperation>);
+
+/// A single operation of a [`JsonPatch`].
+///
+/// The `path` and `from` members are JSON Pointers as defined by [RFC 6901](https://www.rfc-editor.org/rfc/rfc6901).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+  /// Adds `value` at `path`, inserting it if the target is an array.
+  Add { path: String, value: Value },
+  /// Removes the value at `path`.
+  Remove { path: String },
+  /// Replaces the value at `path` with `value`.
+  Replace { path: String, value: Value },
+  /// Removes the value at `from` and adds it at `path`.
+  Move { from: String, path: String },
+  /// Adds a copy of the value at `from` at `path`.
+  Copy { from: String, path: String },
+  /// Ensures the value at `path` is equal to `value`.
+  Test { path: String, value: Value },
+}
+
+impl JsonPatch {
+  /// Creates a `JsonPatch` describing the changes that `diff` makes to `original`.
+  ///
+  /// A `Diff::Type` is relative to the value it was computed from, so `original` must be the
+  /// value that was passed as `self` to [`Diff::diff`].
+  pub fn from_diff<T>(original: &T, diff: T::Type) -> Result<Self>
+  where
+    T: Diff + Serialize,
+  {
+    let updated: T = original.merge(diff)?;
+
+    Ok(Self::between(&to_value(original)?, &to_value(&updated)?))
+  }
+
+  /// Creates a `JsonPatch` that transforms `old` into `new`.
+  ///
+  /// Objects are compared by member and arrays by index.
+  pub fn between(old: &Value, new: &Value) -> Self {
+    let mut operations: Vec<PatchOperation> = Vec::new();
+    diff_values(&mut operations, String::new(), old, new);
+    Self(operations)
+  }
+
+  /// Returns the operations of the patch.
+  pub fn operations(&self) -> &[PatchOperation] {
+    &self.0
+  }
+
+  /// Returns `true` if the patch does not contain any operations.
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  /// Applies the patch to `target`.
+  ///
+  /// The patch is applied atomically: if any operation fails, `target` remains unmodified.
+  pub fn apply(&self, target: &mut Value) -> Result<()> {
+    let mut patched: Value = target.clone();
+
+    for operation in self.0.iter() {
+      operation.apply(&mut patched)?;
+    }
+
+    *target = patched;
+
+    Ok(())
+  }
+
+  /// Applies the patch to the JSON representation of `value` and deserializes the result.
+  pub fn apply_to<T>(&self, value: &T) -> Result<T>
+  where
+    T: Serialize + DeserializeOwned,
+  {
+    let mut target: Value = to_value(value)?;
+    self.apply(&mut target)?;
+    serde_json::from_value(target).map_err(Error::patch)
+  }
+}
+
+impl PatchOperation {
+  fn apply(&self, target: &mut Value) -> Result<()> {
+    match self {
+      Self::Add { path, value } => add(target, path, value.clone()),
+      Self::Remove { path } => remove(target, path).map(|_| ()),
+      Self::Replace { path, value } => {
+        *target.pointer_mut(path).ok_or_else(|| missing(path))? = value.clone();
+        Ok(())
+      }
+      Self::Move { from, path } => {
+        if path.starts_with(from.as_str()) && path[from.len()..].starts_with('/') {
+          return Err(Error::patch(format!("cannot move `{}` into one of its children", from)));
+        }
+
+        let value: Value = remove(target, from)?;
+        add(target, path, value)
+      }
+      Self::Copy { from, path } => {
+        let value: Value = target.pointer(from).cloned().ok_or_else(|| missing(from))?;
+        add(target, path, value)
+      }
+      Self::Test { path, value } => {
+        if target.pointer(path).ok_or_else(|| missing(path))? == value {
+          Ok(())
+        } else {
+          Err(Error::patch(format!("test failed at `{}`", path)))
+        }
+      }
+    }
+  }
+}
+
+fn diff_values(operations: &mut Vec<PatchOperation>, path: String, old: &Value, new: &Value) {
+  match (old, new) {
+    _ if old == new => {}
+    (Value::Object(old), Value::Object(new)) => {
+      for key in old.keys().filter(|key| !new.contains_key(*key)) {
+        operations.push(PatchOperation::Remove {
+          path: member_pointer(&path, key),
+        });
+      }
+
+      for (key, value) in new.iter() {
+        match old.get(key) {
+          Some(previous) => diff_values(operations, member_pointer(&path, key), previous, value),
+          None => operations.push(PatchOperation::Add {
+            path: member_pointer(&path, key),
+            value: value.clone(),
+          }),
+        }
+      }
+    }
+    (Value::Array(old), Value::Array(new)) => {
+      for (index, (previous, value)) in old.iter().zip(new.iter()).enumerate() {
+        diff_values(operations, format!("{}/{}", path, index), previous, value);
+      }
+
+      // Remove from the back so the indices of the remaining elements do not shift.
+      for index in (new.len()..old.len()).rev() {
+        operations.push(PatchOperation::Remove {
+          path: format!("{}/{}", path, index),
+        });
+      }
+
+      for (index, value) in new.iter().enumerate().skip(old.len()) {
+        operations.push(PatchOperation::Add {
+          path: format!("{}/{}", path, index),
+          value: value.clone(),
+        });
+      }
+    }
+    _ => operations.push(PatchOperation::Replace {
+      path,
+      value: new.clone(),
+    }),
+  }
+}
+
+fn add(target: &mut Value, path: &str, value: Value) -> Result<()> {
+  if path.is_empty() {
+    *target = value;
+    return Ok(());
+  }
+
+  let (parent, token): (&str, String) = split_pointer(path)?;
+
+  match target.pointer_mut(parent).ok_or_else(|| missing(parent))? {
+    Value::Object(object) => {
+      object.insert(token, value);
+    }
+    Value::Array(array) => {
+      let index: usize = if token == "-" {
+        array.len()
+      } else {
+        array_index(&token, array.len() + 1, path)?
+      };
+      array.insert(index, value);
+    }
+    _ => return Err(Error::patch(format!("cannot add a member to a scalar at `{}`", path))),
+  }
+
+  Ok(())
+}
+
+fn remove(target: &mut Value, path: &str) -> Result<Value> {
+  if path.is_empty() {
+    return Err(Error::patch("cannot remove the whole document"));
+  }
+
+  let (parent, token): (&str, String) = split_pointer(path)?;
+
+  match target.pointer_mut(parent).ok_or_else(|| missing(parent))? {
+    Value::Object(object) => object.remove(&token).ok_or_else(|| missing(path)),
+    Value::Array(array) => {
+      let index: usize = array_index(&token, array.len(), path)?;
+      Ok(array.remove(index))
+    }
+    _ => Err(missing(path)),
+  }
+}
+
+/// Splits a JSON Pointer into the pointer of its parent and its unescaped last reference token.
+fn split_pointer(pointer: &str) -> Result<(&str, String)> {
+  let (parent, token): (&str, &str) = pointer
+    .rsplit_once('/')
+    .ok_or_else(|| Error::patch(format!("invalid JSON pointer `{}`", pointer)))?;
+
+  Ok((parent, token.replace("~1", "/").replace("~0", "~")))
+}
+
+/// Returns the JSON Pointer to the member `key` of the object at `parent`.
+pub(crate) fn member_pointer(parent: &str, key: &str) -> String {
+  format!("{}/{}", parent, key.replace('~', "~0").replace('/', "~1"))
+}
+
+/// Parses an array index without leading zeros that is lower than `bound`.
+fn array_index(token: &str, bound: usize, path: &str) -> Result<usize> {
+  let is_canonical: bool = token == "0" || !token.starts_with('0');
+
+  Some(token)
+    .filter(|token| is_canonical && !token.is_empty() && token.bytes().all(|byte| byte.is_ascii_digit()))
+    .and_then(|token| token.parse::<usize>().ok())
+    .filter(|index| *index < bound)
+    .ok_or_else(|| Error::patch(format!("invalid array index at `{}`", path)))
+}
+
+fn missing(path: &str) -> Error {
+  Error::patch(format!("no value at `{}`", path))
+}
+
+pub(crate) fn to_value<T>(value: &T) -> Result<Value>
+where
+  T: Serialize + ?Sized,
+{
+  serde_json::to_value(value).map_err(Error::convert)
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::json;
+
+  use super::*;
+
+  fn parse(operations: Value) -> JsonPatch {
+    serde_json::from_value(operations).unwrap()
+  }
+
+  #[test]
+  fn test_apply_operations() {
+    let mut target: Value = json!({ "foo": ["bar", "baz"], "a/b": { "c": 1 } });
+
+    parse(json!([
+      { "op": "add", "path": "/foo/1", "value": "qux" },
+      { "op": "add", "path": "/foo/-", "value": "end" },
+      { "op": "replace", "path": "/a~1b/c", "value": 2 },
+      { "op": "copy", "from": "/a~1b", "path": "/copied" },
+      { "op": "move", "from": "/foo/0", "path": "/moved" },
+      { "op": "remove", "path": "/foo/2" },
+      { "op": "test", "path": "/copied/c", "value": 2 },
+    ]))
+    .apply(&mut target)
+    .unwrap();
+
+    assert_eq!(
+      target,
+      json!({ "foo": ["qux", "baz"], "a/b": { "c": 2 }, "copied": { "c": 2 }, "moved": "bar" })
+    );
+  }
+
+  #[test]
+  fn test_apply_is_atomic() {
+    let original: Value = json!({ "foo": "bar" });
+    let mut target: Value = original.clone();
+
+    let result: Result<()> = parse(json!([
+      { "op": "add", "path": "/baz", "value": "qux" },
+      { "op": "test", "path": "/foo", "value": "other" },
+    ]))
+    .apply(&mut target);
+
+    assert!(matches!(result, Err(Error::PatchError(_))));
+    assert_eq!(target, original);
+  }
+
+  #[test]
+  fn test_apply_invalid_paths() {
+    let target: Value = json!({ "foo": [1, 2], "bar": 1 });
+
+    for operations in [
+      json!([{ "op": "remove", "path": "/missing" }]),
+      json!([{ "op": "replace", "path": "/missing", "value": 1 }]),
+      json!([{ "op": "add", "path": "/missing/child", "value": 1 }]),
+      json!([{ "op": "add", "path": "/foo/3", "value": 3 }]),
+      json!([{ "op": "remove", "path": "/foo/01" }]),
+      json!([{ "op": "add", "path": "/bar/child", "value": 1 }]),
+      json!([{ "op": "move", "from": "/foo", "path": "/foo/0" }]),
+      json!([{ "op": "remove", "path": "" }]),
+    ] {
+      assert!(parse(operations).apply(&mut target.clone()).is_err());
+    }
+  }
+
+  #[test]
+  fn test_between() {
+    let old: Value = json!({ "a": 1, "b": { "c": [1, 2, 3] }, "d~e": true });
+    let new: Value = json!({ "a": 2, "b": { "c": [1, 4] }, "f": null });
+
+    let patch: JsonPatch = JsonPatch::between(&old, &new);
+
+    assert_eq!(
+      patch,
+      parse(json!([
+        { "op": "remove", "path": "/d~0e" },
+        { "op": "replace", "path": "/a", "value": 2 },
+        { "op": "replace", "path": "/b/c/1", "value": 4 },
+        { "op": "remove", "path": "/b/c/2" },
+        { "op": "add", "path": "/f", "value": null },
+      ]))
+    );
+
+    let mut target: Value = old.clone();
+    patch.apply(&mut target).unwrap();
+    assert_eq!(target, new);
+
+    assert!(JsonPatch::between(&new, &new).is_empty());
+  }
+
+  #[test]
+  fn test_from_diff() {
+    let old: Vec<String> = vec!["a".to_owned(), "b".to_owned()];
+    let new: Vec<String> = vec!["c".to_owned()];
+
+    let patch: JsonPatch = JsonPatch::from_diff(&old, old.diff(&new).unwrap()).unwrap();
+
+    assert_eq!(
+      patch,
+      parse(json!([
+        { "op": "replace", "path": "/0", "value": "c" },
+        { "op": "remove", "path": "/1" },
+      ]))
+    );
+    assert_eq!(patch.apply_to(&old).unwrap(), new);
+  }
+}
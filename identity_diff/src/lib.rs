@@ -26,19 +26,27 @@ pub use self::error::Error;
 pub use self::error::Result;
 pub use self::hashmap::DiffHashMap;
 pub use self::hashset::DiffHashSet;
+pub use self::json_patch::JsonPatch;
+pub use self::json_patch::PatchOperation;
+pub use self::merge_patch::MergePatch;
 pub use self::object::DiffObject;
 pub use self::option::DiffOption;
 pub use self::string::DiffString;
+pub use self::three_way::MergeConflict;
+pub use self::three_way::ThreeWayMerge;
 pub use self::traits::Diff;
 pub use self::vec::DiffVec;
 
 mod error;
 mod hashmap;
 mod hashset;
+mod json_patch;
 mod macros;
+mod merge_patch;
 mod object;
 mod option;
 mod string;
+mod three_way;
 mod traits;
 mod value;
 mod vec;
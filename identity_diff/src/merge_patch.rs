@@ -0,0 +1,184 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::json_patch::to_value;
+use crate::Diff;
+use crate::Error;
+use crate::Result;
+
+/// A JSON Merge Patch document as defined by [RFC 7396](https://www.rfc-editor.org/rfc/rfc7396).
+///
+/// A merge patch mirrors the structure of the patched value: members set to `null` are removed and
+/// all other members are merged recursively. Arrays are always replaced as a whole, and members
+/// cannot be set to `null`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MergePatch(pub Value);
+
+impl MergePatch {
+  /// Creates a `MergePatch` describing the changes that `diff` makes to `original`.
+  ///
+  /// A `Diff::Type` is relative to the value it was computed from, so `original` must be the
+  /// value that was passed as `self` to [`Diff::diff`].
+  pub fn from_diff<T>(original: &T, diff: T::Type) -> Result<Self>
+  where
+    T: Diff + Serialize,
+  {
+    let updated: T = original.merge(diff)?;
+
+    Ok(Self::between(&to_value(original)?, &to_value(&updated)?))
+  }
+
+  /// Creates a `MergePatch` that transforms `old` into `new`.
+  ///
+  /// Members of `new` with a `null` value cannot be represented and are removed when applying the patch.
+  pub fn between(old: &Value, new: &Value) -> Self {
+    Self(diff_values(old, new))
+  }
+
+  /// Returns the patch as a JSON value.
+  pub fn as_value(&self) -> &Value {
+    &self.0
+  }
+
+  /// Returns `true` if applying the patch does not modify an object.
+  pub fn is_empty(&self) -> bool {
+    matches!(self.0, Value::Object(ref object) if object.is_empty())
+  }
+
+  /// Applies the patch to `target`.
+  pub fn apply(&self, target: &mut Value) {
+    merge_values(target, &self.0);
+  }
+
+  /// Applies the patch to the JSON representation of `value` and deserializes the result.
+  pub fn apply_to<T>(&self, value: &T) -> Result<T>
+  where
+    T: Serialize + DeserializeOwned,
+  {
+    let mut target: Value = to_value(value)?;
+    self.apply(&mut target);
+    serde_json::from_value(target).map_err(Error::patch)
+  }
+}
+
+fn diff_values(old: &Value, new: &Value) -> Value {
+  match (old, new) {
+    (Value::Object(old), Value::Object(new)) => {
+      let mut patch: Map<String, Value> = Map::new();
+
+      for key in old.keys().filter(|key| !new.contains_key(*key)) {
+        patch.insert(key.clone(), Value::Null);
+      }
+
+      for (key, value) in new.iter() {
+        match old.get(key) {
+          Some(previous) if previous == value => {}
+          Some(previous) => {
+            patch.insert(key.clone(), diff_values(previous, value));
+          }
+          None => {
+            patch.insert(key.clone(), value.clone());
+          }
+        }
+      }
+
+      Value::Object(patch)
+    }
+    _ => new.clone(),
+  }
+}
+
+/// Implements the `MergePatch` function of RFC 7396, section 2.
+fn merge_values(target: &mut Value, patch: &Value) {
+  let patch: &Map<String, Value> = match patch {
+    Value::Object(patch) => patch,
+    _ => {
+      *target = patch.clone();
+      return;
+    }
+  };
+
+  if !target.is_object() {
+    *target = Value::Object(Map::new());
+  }
+
+  if let Value::Object(target) = target {
+    for (key, value) in patch.iter() {
+      if value.is_null() {
+        target.remove(key);
+      } else {
+        merge_values(target.entry(key.clone()).or_insert(Value::Null), value);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_apply() {
+    // Example from RFC 7396, section 3.
+    let mut target: Value = json!({
+      "title": "Goodbye!",
+      "author": { "givenName": "John", "familyName": "Doe" },
+      "tags": ["example", "sample"],
+      "content": "This will be unchanged",
+    });
+
+    MergePatch(json!({
+      "title": "Hello!",
+      "phoneNumber": "+01-123-456-7890",
+      "author": { "familyName": null },
+      "tags": ["example"],
+    }))
+    .apply(&mut target);
+
+    assert_eq!(
+      target,
+      json!({
+        "title": "Hello!",
+        "author": { "givenName": "John" },
+        "tags": ["example"],
+        "content": "This will be unchanged",
+        "phoneNumber": "+01-123-456-7890",
+      })
+    );
+  }
+
+  #[test]
+  fn test_between() {
+    let old: Value = json!({ "a": 1, "b": { "c": 1, "d": 2 }, "e": [1, 2], "f": "g" });
+    let new: Value = json!({ "a": 1, "b": { "c": 3, "d": 2 }, "e": [1] });
+
+    let patch: MergePatch = MergePatch::between(&old, &new);
+    assert_eq!(patch.as_value(), &json!({ "b": { "c": 3 }, "e": [1], "f": null }));
+
+    let mut target: Value = old.clone();
+    patch.apply(&mut target);
+    assert_eq!(target, new);
+
+    assert!(MergePatch::between(&new, &new).is_empty());
+  }
+
+  #[test]
+  fn test_from_diff() {
+    let old: Option<String> = Some("a".to_owned());
+    let new: Option<String> = Some("b".to_owned());
+
+    let patch: MergePatch = MergePatch::from_diff(&old, old.diff(&new).unwrap()).unwrap();
+
+    assert_eq!(patch.as_value(), &json!("b"));
+    assert_eq!(patch.apply_to(&old).unwrap(), new);
+  }
+}
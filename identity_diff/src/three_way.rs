@@ -0,0 +1,295 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::json_patch::member_pointer;
+use crate::json_patch::to_value;
+use crate::Error;
+use crate::Result;
+
+/// The result of combining two sets of changes made independently to the same base value.
+///
+/// Changes made by only one side are applied, and identical changes made by both sides are applied
+/// once. Objects are merged member by member. Arrays whose elements are strings or objects with a
+/// string `id` member are merged as sets, i.e. elements are matched by their key and their order follows
+/// `ours`, followed by the elements only present in `theirs`. All other values are compared as a whole.
+///
+/// If both sides changed the same value differently, a [`MergeConflict`] is reported for its path and the
+/// merged value keeps the value of `ours`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ThreeWayMerge {
+  merged: Value,
+  conflicts: Vec<MergeConflict>,
+}
+
+/// A value changed differently by both sides of a [`ThreeWayMerge`].
+///
+/// The `path` is a JSON Pointer, except that elements of arrays merged as sets are referenced by their
+/// key instead of their index. A value of `None` means the value is absent on that side.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergeConflict {
+  pub path: String,
+  pub base: Option<Value>,
+  pub ours: Option<Value>,
+  pub theirs: Option<Value>,
+}
+
+impl ThreeWayMerge {
+  /// Merges the changes `ours` and `theirs` made to `base`, based on their JSON representations.
+  pub fn new<T>(base: &T, ours: &T, theirs: &T) -> Result<Self>
+  where
+    T: Serialize + ?Sized,
+  {
+    Ok(Self::between(&to_value(base)?, &to_value(ours)?, &to_value(theirs)?))
+  }
+
+  /// Merges the changes `ours` and `theirs` made to `base`.
+  pub fn between(base: &Value, ours: &Value, theirs: &Value) -> Self {
+    let mut conflicts: Vec<MergeConflict> = Vec::new();
+    let merged: Value =
+      merge_values(&mut conflicts, String::new(), Some(base), Some(ours), Some(theirs)).unwrap_or(Value::Null);
+
+    Self { merged, conflicts }
+  }
+
+  /// Returns the conflicting changes.
+  pub fn conflicts(&self) -> &[MergeConflict] {
+    &self.conflicts
+  }
+
+  /// Returns `true` if the changes were merged without conflicts.
+  pub fn is_clean(&self) -> bool {
+    self.conflicts.is_empty()
+  }
+
+  /// Returns the merged value, in which conflicting values are taken from `ours`.
+  pub fn merged(&self) -> &Value {
+    &self.merged
+  }
+
+  /// Deserializes the merged value.
+  ///
+  /// Fails if the merge has conflicts.
+  pub fn into_merged<T>(self) -> Result<T>
+  where
+    T: DeserializeOwned,
+  {
+    if !self.is_clean() {
+      let paths: Vec<&str> = self.conflicts.iter().map(|conflict| conflict.path.as_str()).collect();
+      return Err(Error::merge(format!("conflicting changes at `{}`", paths.join("`, `"))));
+    }
+
+    serde_json::from_value(self.merged).map_err(Error::merge)
+  }
+}
+
+fn merge_values(
+  conflicts: &mut Vec<MergeConflict>,
+  path: String,
+  base: Option<&Value>,
+  ours: Option<&Value>,
+  theirs: Option<&Value>,
+) -> Option<Value> {
+  if ours == theirs || theirs == base {
+    return ours.cloned();
+  }
+
+  if ours == base {
+    return theirs.cloned();
+  }
+
+  match (ours, theirs) {
+    (Some(Value::Object(ours)), Some(Value::Object(theirs))) => {
+      let base: Option<&Map<String, Value>> = base.and_then(Value::as_object);
+      let mut merged: Map<String, Value> = Map::new();
+
+      for key in ours.keys().chain(theirs.keys().filter(|key| !ours.contains_key(*key))) {
+        let value: Option<Value> = merge_values(
+          conflicts,
+          member_pointer(&path, key),
+          base.and_then(|base| base.get(key)),
+          ours.get(key),
+          theirs.get(key),
+        );
+
+        if let Some(value) = value {
+          merged.insert(key.clone(), value);
+        }
+      }
+
+      Some(Value::Object(merged))
+    }
+    (Some(Value::Array(ours_array)), Some(Value::Array(theirs_array))) => {
+      let base_array: &[Value] = base.and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default();
+
+      match (set_keys(base_array), set_keys(ours_array), set_keys(theirs_array)) {
+        (Some(base_keys), Some(ours_keys), Some(theirs_keys)) => {
+          let mut merged: Vec<Value> = Vec::new();
+
+          for key in ours_keys
+            .iter()
+            .chain(theirs_keys.iter().filter(|key| !ours_keys.contains(*key)))
+          {
+            let element = |keys: &[&str], values: &[Value]| -> Option<Value> {
+              keys
+                .iter()
+                .position(|other| other == key)
+                .map(|index| values[index].clone())
+            };
+
+            let value: Option<Value> = merge_values(
+              conflicts,
+              member_pointer(&path, key),
+              element(&base_keys, base_array).as_ref(),
+              element(&ours_keys, ours_array).as_ref(),
+              element(&theirs_keys, theirs_array).as_ref(),
+            );
+
+            merged.extend(value);
+          }
+
+          Some(Value::Array(merged))
+        }
+        _ => conflict(conflicts, path, base, ours, theirs),
+      }
+    }
+    _ => conflict(conflicts, path, base, ours, theirs),
+  }
+}
+
+fn conflict(
+  conflicts: &mut Vec<MergeConflict>,
+  path: String,
+  base: Option<&Value>,
+  ours: Option<&Value>,
+  theirs: Option<&Value>,
+) -> Option<Value> {
+  conflicts.push(MergeConflict {
+    path,
+    base: base.cloned(),
+    ours: ours.cloned(),
+    theirs: theirs.cloned(),
+  });
+
+  ours.cloned()
+}
+
+/// Returns the keys of the elements if the array can be merged as a set.
+fn set_keys(array: &[Value]) -> Option<Vec<&str>> {
+  let keys: Vec<&str> = array
+    .iter()
+    .map(|value| match value {
+      Value::String(key) => Some(key.as_str()),
+      Value::Object(object) => object.get("id").and_then(Value::as_str),
+      _ => None,
+    })
+    .collect::<Option<_>>()?;
+
+  let is_unique: bool = keys.iter().enumerate().all(|(index, key)| !keys[..index].contains(key));
+
+  Some(keys).filter(|_| is_unique)
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_merge_objects() {
+    let base: Value = json!({ "a": 1, "b": { "c": 1, "d": 1 }, "e": 1, "f": 1 });
+    let ours: Value = json!({ "a": 2, "b": { "c": 2, "d": 1 }, "e": 1 });
+    let theirs: Value = json!({ "a": 1, "b": { "c": 1, "d": 2 }, "g": 1 });
+
+    let merge: ThreeWayMerge = ThreeWayMerge::between(&base, &ours, &theirs);
+
+    assert!(merge.is_clean());
+    assert_eq!(merge.merged(), &json!({ "a": 2, "b": { "c": 2, "d": 2 }, "g": 1 }));
+  }
+
+  #[test]
+  fn test_merge_conflicts() {
+    let base: Value = json!({ "a": 1, "b": 1, "c": [1, 2], "d": 1 });
+    let ours: Value = json!({ "a": 2, "b": 2, "c": [1, 3] });
+    let theirs: Value = json!({ "a": 2, "b": 3, "c": [1, 4], "d": 2 });
+
+    let merge: ThreeWayMerge = ThreeWayMerge::between(&base, &ours, &theirs);
+
+    let paths: Vec<&str> = merge
+      .conflicts()
+      .iter()
+      .map(|conflict| conflict.path.as_str())
+      .collect();
+    assert_eq!(paths, ["/b", "/c", "/d"]);
+    assert_eq!(
+      merge.conflicts()[2],
+      MergeConflict {
+        path: "/d".to_owned(),
+        base: Some(json!(1)),
+        ours: None,
+        theirs: Some(json!(2)),
+      }
+    );
+    assert_eq!(merge.merged(), &ours);
+    assert!(matches!(merge.into_merged::<Value>(), Err(Error::MergeError(_))));
+  }
+
+  #[test]
+  fn test_merge_sets() {
+    let base: Value = json!({
+      "methods": [{ "id": "#a", "key": 1 }, { "id": "#b", "key": 1 }, { "id": "#c", "key": 1 }],
+      "refs": ["#a", "#b"],
+    });
+    let ours: Value = json!({
+      "methods": [{ "id": "#b", "key": 2 }, { "id": "#c", "key": 1 }, { "id": "#d", "key": 1 }],
+      "refs": ["#b", "#d"],
+    });
+    let theirs: Value = json!({
+      "methods": [{ "id": "#a", "key": 1 }, { "id": "#b", "key": 1 }, { "id": "#e", "key": 1 }],
+      "refs": ["#a", "#b", "#e"],
+    });
+
+    let merged: Value = ThreeWayMerge::between(&base, &ours, &theirs).into_merged().unwrap();
+
+    assert_eq!(
+      merged,
+      json!({
+        "methods": [{ "id": "#b", "key": 2 }, { "id": "#d", "key": 1 }, { "id": "#e", "key": 1 }],
+        "refs": ["#b", "#d", "#e"],
+      })
+    );
+  }
+
+  #[test]
+  fn test_merge_sets_conflict() {
+    let base: Value = json!([{ "id": "#a", "key": 1 }]);
+    let ours: Value = json!([]);
+    let theirs: Value = json!([{ "id": "#a", "key": 2 }]);
+
+    let merge: ThreeWayMerge = ThreeWayMerge::between(&base, &ours, &theirs);
+
+    assert_eq!(merge.conflicts().len(), 1);
+    assert_eq!(merge.conflicts()[0].path, "/#a");
+    assert_eq!(merge.merged(), &json!([]));
+  }
+
+  #[test]
+  fn test_new() {
+    let base: Vec<String> = vec!["a".to_owned()];
+    let ours: Vec<String> = vec!["a".to_owned(), "b".to_owned()];
+    let theirs: Vec<String> = vec!["c".to_owned(), "a".to_owned()];
+
+    let merged: Vec<String> = ThreeWayMerge::new(&base, &ours, &theirs)
+      .unwrap()
+      .into_merged()
+      .unwrap();
+
+    assert_eq!(merged, ["a", "b", "c"]);
+  }
+}
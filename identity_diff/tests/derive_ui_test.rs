@@ -0,0 +1,11 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(feature = "derive")]
+
+#[test]
+fn test_derive_ui() {
+  let cases = trybuild::TestCases::new();
+  cases.pass("tests/ui/pass/*.rs");
+  cases.compile_fail("tests/ui/fail/*.rs");
+}
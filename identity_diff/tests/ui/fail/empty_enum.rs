@@ -0,0 +1,11 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(deprecated)]
+
+use identity_diff::Diff;
+
+#[derive(Diff, Debug, Clone, PartialEq)]
+pub enum Never {}
+
+fn main() {}
@@ -0,0 +1,14 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(deprecated)]
+
+use identity_diff::Diff;
+
+#[derive(Diff)]
+pub union Number {
+  signed: i64,
+  unsigned: u64,
+}
+
+fn main() {}
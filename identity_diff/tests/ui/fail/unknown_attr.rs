@@ -0,0 +1,20 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(deprecated)]
+
+use identity_diff::Diff;
+
+#[derive(Diff, Debug, Clone, PartialEq, Default)]
+pub struct Ignored {
+  #[diff(skip)]
+  id: String,
+}
+
+#[derive(Diff, Debug, Clone, PartialEq, Default)]
+#[diff(should_ignore)]
+pub struct Misplaced {
+  id: String,
+}
+
+fn main() {}
@@ -0,0 +1,55 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(deprecated)]
+
+use identity_diff::Diff;
+use serde::Deserialize;
+use serde::Serialize;
+
+pub trait Extension: Clone + Default {}
+
+impl Extension for String {}
+
+// Field types built from type params only need to implement `Diff` themselves.
+#[derive(Diff, Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Service<T: Extension, U = usize>
+where
+  U: Copy,
+{
+  ids: Vec<T>,
+  endpoint: Option<T>,
+  weight: U,
+}
+
+#[derive(Diff, Debug, Clone, PartialEq, Default)]
+pub struct Fixed<const N: usize>(Vec<String>);
+
+// Variant fields are not required to implement `Default`.
+#[derive(Diff, Debug, Clone, PartialEq)]
+pub enum Endpoint<T>
+where
+  T: Clone,
+{
+  One(T),
+  Many(Vec<T>, Option<T>),
+}
+
+fn main() {
+  let old: Service<String> = Service::default();
+  let new: Service<String> = Service {
+    ids: vec!["a".to_owned()],
+    endpoint: Some("b".to_owned()),
+    weight: 1,
+  };
+  assert_eq!(old.merge(old.diff(&new).unwrap()).unwrap(), new);
+
+  let old: Fixed<2> = Fixed(vec!["a".to_owned()]);
+  let new: Fixed<2> = Fixed(vec!["b".to_owned()]);
+  assert_eq!(old.merge(old.diff(&new).unwrap()).unwrap(), new);
+
+  let old: Endpoint<String> = Endpoint::One("a".to_owned());
+  let new: Endpoint<String> = Endpoint::Many(vec!["b".to_owned()], None);
+  assert_eq!(old.merge(old.diff(&new).unwrap()).unwrap(), new);
+  assert_eq!(Endpoint::from_diff(new.clone().into_diff().unwrap()).unwrap(), new);
+}
@@ -0,0 +1,59 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(deprecated)]
+
+use identity_diff::Diff;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Diff, Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Extension<T> {
+  service_endpoint: String,
+  #[serde(rename = "type")]
+  kind: String,
+  #[serde(flatten)]
+  properties: T,
+}
+
+#[derive(Diff, Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Properties {
+  priority: usize,
+}
+
+#[derive(Diff, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+  Active {
+    since_time: usize,
+  },
+  #[serde(rename = "off")]
+  Deactivated,
+}
+
+fn main() {
+  let old: Extension<Properties> = Extension::default();
+  let new: Extension<Properties> = Extension {
+    service_endpoint: "https://example.com".to_owned(),
+    kind: "LinkedDomains".to_owned(),
+    properties: Properties { priority: 1 },
+  };
+
+  let diff: DiffExtension<Properties> = old.diff(&new).unwrap();
+  let json: String = serde_json::to_string(&diff).unwrap();
+
+  // Flattened fields are diffed under their field name.
+  assert_eq!(
+    json,
+    r#"{"serviceEndpoint":"https://example.com","type":"LinkedDomains","properties":{"priority":1}}"#
+  );
+  assert_eq!(old.merge(serde_json::from_str(&json).unwrap()).unwrap(), new);
+
+  let old: Status = Status::Active { since_time: 1 };
+  let diff: DiffStatus = old.diff(&Status::Deactivated).unwrap();
+  assert_eq!(serde_json::to_string(&diff).unwrap(), r#""off""#);
+
+  let diff: DiffStatus = Status::Deactivated.diff(&old).unwrap();
+  assert_eq!(serde_json::to_string(&diff).unwrap(), r#"{"active":{"since_time":1}}"#);
+}
@@ -0,0 +1,39 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(deprecated)]
+
+use identity_diff::Diff;
+
+#[derive(Diff, Debug, Clone, PartialEq)]
+pub enum Key {
+  Single(String),
+  Pair(String, usize, String),
+}
+
+#[derive(Diff, Debug, Clone, PartialEq, Default)]
+pub struct Triple(String, usize, String);
+
+fn main() {
+  // Unchanged fields keep their position when the diff is serialized.
+  let old: Key = Key::Pair("a".to_owned(), 1, "b".to_owned());
+  let new: Key = Key::Pair("a".to_owned(), 1, "c".to_owned());
+
+  let json: String = serde_json::to_string(&old.diff(&new).unwrap()).unwrap();
+  assert_eq!(json, r#"{"Pair":[null,null,"c"]}"#);
+  assert_eq!(old.merge(serde_json::from_str(&json).unwrap()).unwrap(), new);
+
+  let old: Triple = Triple("a".to_owned(), 1, "b".to_owned());
+  let new: Triple = Triple("a".to_owned(), 2, "b".to_owned());
+
+  let json: String = serde_json::to_string(&old.diff(&new).unwrap()).unwrap();
+  assert_eq!(json, "[null,2,null]");
+  assert_eq!(old.merge(serde_json::from_str(&json).unwrap()).unwrap(), new);
+
+  let key: Key = Key::Single("a".to_owned());
+  assert_eq!(
+    format!("{:?}", key.clone().into_diff().unwrap()),
+    r#"DiffKey::Single(DiffString("a"))"#
+  );
+  assert_eq!(Key::from_diff(key.clone().into_diff().unwrap()).unwrap(), key);
+}
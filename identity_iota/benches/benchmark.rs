@@ -7,6 +7,7 @@ use criterion::criterion_group;
 use criterion::criterion_main;
 use criterion::BenchmarkId;
 use criterion::Criterion;
+use criterion::Throughput;
 
 use identity_core::crypto::KeyType;
 use identity_iota::client::DocumentChain;
@@ -15,7 +16,9 @@ use identity_iota::crypto::KeyPair;
 use identity_iota::iota_core::IotaDID;
 use identity_iota::iota_core::IotaDocument;
 
+use self::diff_chain::diff_message_size;
 use self::diff_chain::setup_diff_chain_bench;
+use self::diff_chain::setup_method_removal;
 use self::diff_chain::update_diff_chain;
 use self::diff_chain::update_integration_chain;
 
@@ -86,11 +89,32 @@ fn bench_integration_chain_updates(c: &mut Criterion) {
   group.finish();
 }
 
+fn bench_diff_message_size(c: &mut Criterion) {
+  static METHODS: &[usize] = &[1, 10, 100];
+
+  let keypair = KeyPair::new(KeyType::Ed25519).unwrap();
+
+  let mut group = c.benchmark_group("diff message removing the first method");
+  for size in METHODS.iter() {
+    let (document, updated) = setup_method_removal(*size, &keypair);
+
+    // Report the diff message size as the throughput, the size should not grow with the number of methods.
+    let bytes: usize = diff_message_size(&document, &updated, &keypair);
+    group.throughput(Throughput::Bytes(bytes as u64));
+
+    group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, &_| {
+      b.iter(|| diff_message_size(&document, &updated, &keypair));
+    });
+  }
+  group.finish();
+}
+
 criterion_group!(
   benches,
   bench_generate_signed_document,
   bench_generate_did,
   bench_diff_chain_updates,
   bench_integration_chain_updates,
+  bench_diff_message_size,
 );
 criterion_main!(benches);
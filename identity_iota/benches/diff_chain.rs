@@ -8,15 +8,18 @@ use identity_core::crypto::ProofOptions;
 use identity_iota::client::DocumentChain;
 use identity_iota::client::TangleRef;
 use identity_iota::core::Timestamp;
+use identity_iota::core::ToJson;
 use identity_iota::crypto::KeyPair;
 use identity_iota::did::MethodBuilder;
 use identity_iota::did::MethodData;
 use identity_iota::did::MethodRef;
+use identity_iota::did::MethodScope;
 use identity_iota::did::MethodType;
 use identity_iota::did::DID;
 use identity_iota::iota_core::DiffMessage;
 use identity_iota::iota_core::IotaDID;
 use identity_iota::iota_core::IotaDocument;
+use identity_iota::iota_core::IotaVerificationMethod;
 use identity_iota::iota_core::MessageId;
 use identity_iota_client::document::ResolvedIotaDocument;
 
@@ -105,3 +108,40 @@ pub fn update_integration_chain(n: usize, chain: &mut DocumentChain, keypair: &K
     chain.try_push_integration(new).unwrap();
   }
 }
+
+/// Creates a document with `n` verification methods and an update removing the first of them.
+pub fn setup_method_removal(n: usize, keypair: &KeyPair) -> (IotaDocument, IotaDocument) {
+  let mut document: IotaDocument = IotaDocument::new(keypair).unwrap();
+
+  for i in 0..n {
+    let method: IotaVerificationMethod = IotaVerificationMethod::new(
+      document.id().clone(),
+      keypair.type_(),
+      keypair.public(),
+      &format!("key-{}", i),
+    )
+    .unwrap();
+    document.insert_method(method, MethodScope::VerificationMethod).unwrap();
+  }
+
+  let mut updated: IotaDocument = document.clone();
+  updated
+    .remove_method(&document.id().to_url().join("#key-0").unwrap())
+    .unwrap();
+
+  (document, updated)
+}
+
+/// Returns the size in bytes of the serialized diff message between `current` and `updated`.
+pub fn diff_message_size(current: &IotaDocument, updated: &IotaDocument, keypair: &KeyPair) -> usize {
+  let diff: DiffMessage = current
+    .diff(
+      updated,
+      MessageId::new([8; 32]),
+      keypair.private(),
+      current.default_signing_method().unwrap().id(),
+    )
+    .unwrap();
+
+  diff.to_json_vec().unwrap().len()
+}
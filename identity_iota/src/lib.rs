@@ -74,6 +74,7 @@ pub mod client {
 
   pub use identity_iota_client::chain::*;
   pub use identity_iota_client::document::*;
+  pub use identity_iota_client::resolution::*;
   pub use identity_iota_client::tangle::*;
 
   pub use identity_iota_client::Error;
@@ -188,6 +188,12 @@ impl DiffChain {
       return Err(Error::ChainError { error: "invalid DID" });
     }
 
+    if document.document.is_deactivated() {
+      return Err(Error::ChainError {
+        error: "deactivated DID",
+      });
+    }
+
     if diff.message_id().is_null() {
       return Err(Error::ChainError {
         error: "invalid message id",
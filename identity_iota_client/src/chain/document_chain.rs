@@ -466,6 +466,46 @@ mod test {
     ));
   }
 
+  #[test]
+  fn test_integration_chain_rejects_addition_after_deactivation() {
+    let (resolved, keypair): (ResolvedIotaDocument, KeyPair) = create_initial_document();
+    let mut chain: DocumentChain = DocumentChain::new(IntegrationChain::new(resolved).unwrap());
+
+    // =========================================================================
+    // Push Deactivation
+    // =========================================================================
+    let mut tombstone: ResolvedIotaDocument = chain.current().clone();
+    tombstone.document.deactivate().unwrap();
+    tombstone.document.metadata.previous_message_id = *chain.integration_message_id();
+    chain
+      .current()
+      .document
+      .sign_data(
+        &mut tombstone.document,
+        keypair.private(),
+        chain.current().document.default_signing_method().unwrap().id(),
+        ProofOptions::default(),
+      )
+      .unwrap();
+    tombstone.set_message_id(MessageId::new([2; 32]));
+    chain.try_push_integration(tombstone).unwrap();
+    assert!(chain.current().document.is_deactivated());
+
+    // =========================================================================
+    // Reject Further Updates
+    // =========================================================================
+    let mut update: ResolvedIotaDocument = chain.current().clone();
+    update.document.metadata.deactivated = None;
+    update.document.metadata.previous_message_id = *chain.integration_message_id();
+    update.set_message_id(MessageId::new([3; 32]));
+    assert!(matches!(
+      chain.try_push_integration(update).unwrap_err(),
+      Error::ChainError {
+        error: "Deactivated DID"
+      }
+    ));
+  }
+
   fn create_initial_document() -> (ResolvedIotaDocument, KeyPair) {
     let keypair: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
     let mut document: IotaDocument = IotaDocument::new(&keypair).unwrap();
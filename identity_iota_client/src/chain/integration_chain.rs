@@ -163,13 +163,20 @@ impl IntegrationChain {
   ///
   /// # Errors
   ///
-  /// Fails if the document signature is invalid or the Tangle message
-  /// references within the [`ResolvedIotaDocument`] are invalid.
+  /// Fails if the current document is deactivated, the document signature is invalid or the
+  /// Tangle message references within the [`ResolvedIotaDocument`] are invalid.
   pub fn check_valid_addition(&self, document: &ResolvedIotaDocument) -> Result<()> {
     if document.document.id() != self.current.document.id() {
       return Err(Error::ChainError { error: "Invalid DID" });
     }
 
+    // A deactivated document is final, no further updates are accepted.
+    if self.current.document.is_deactivated() {
+      return Err(Error::ChainError {
+        error: "Deactivated DID",
+      });
+    }
+
     if document.message_id().is_null() {
       return Err(Error::ChainError {
         error: "Missing Message Id",
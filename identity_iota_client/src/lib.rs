@@ -21,6 +21,7 @@ pub use self::error::Result;
 
 pub mod chain;
 pub mod document;
+pub mod resolution;
 pub mod tangle;
 
 mod error;
@@ -0,0 +1,92 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::str::FromStr;
+
+use serde;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::resolution::ResolutionErrorCode;
+
+/// The media type of a DID document representation.
+///
+/// See [DID Core - Representations](https://www.w3.org/TR/did-core/#representations).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum ContentType {
+  /// The plain JSON representation (`application/did+json`).
+  #[serde(rename = "application/did+json")]
+  Json,
+  /// The JSON-LD representation (`application/did+ld+json`) which includes an `@context` entry.
+  #[serde(rename = "application/did+ld+json")]
+  JsonLd,
+}
+
+impl ContentType {
+  /// The DID Core v1 JSON-LD context included in the [`ContentType::JsonLd`] representation.
+  pub const DID_CONTEXT: &'static str = "https://www.w3.org/ns/did/v1";
+
+  /// Returns the media type string of this [`ContentType`].
+  pub const fn as_str(&self) -> &'static str {
+    match self {
+      Self::Json => "application/did+json",
+      Self::JsonLd => "application/did+ld+json",
+    }
+  }
+}
+
+impl Default for ContentType {
+  fn default() -> Self {
+    Self::Json
+  }
+}
+
+impl Display for ContentType {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.write_str(self.as_str())
+  }
+}
+
+impl FromStr for ContentType {
+  type Err = ResolutionErrorCode;
+
+  fn from_str(string: &str) -> Result<Self, Self::Err> {
+    // Ignore media type parameters such as `charset` or `profile`.
+    let essence: &str = string.split(';').next().unwrap_or_default().trim();
+    match essence {
+      "application/did+json" | "application/json" => Ok(Self::Json),
+      "application/did+ld+json" | "application/ld+json" => Ok(Self::JsonLd),
+      _ => Err(ResolutionErrorCode::RepresentationNotSupported),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_content_type_from_str() {
+    assert_eq!(
+      "application/did+json".parse::<ContentType>().unwrap(),
+      ContentType::Json
+    );
+    assert_eq!(
+      "application/did+ld+json".parse::<ContentType>().unwrap(),
+      ContentType::JsonLd
+    );
+    assert_eq!(
+      "application/ld+json;profile=\"https://w3id.org/did-resolution\""
+        .parse::<ContentType>()
+        .unwrap(),
+      ContentType::JsonLd
+    );
+    assert_eq!(
+      "application/did+cbor".parse::<ContentType>().unwrap_err(),
+      ResolutionErrorCode::RepresentationNotSupported
+    );
+  }
+}
@@ -0,0 +1,271 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+
+use serde;
+use serde::Deserialize;
+use serde::Serialize;
+
+use identity_core::convert::FmtJson;
+use identity_core::convert::ToJson;
+use identity_did::utils::Queryable;
+use identity_iota_core::did::IotaDIDUrl;
+use identity_iota_core::document::IotaCoreDocument;
+use identity_iota_core::document::IotaService;
+use identity_iota_core::document::IotaVerificationMethod;
+
+use crate::chain::DocumentHistory;
+use crate::document::ResolvedIotaDocument;
+use crate::error::Error;
+use crate::error::Result;
+use crate::resolution::resolution_result::represent;
+use crate::resolution::resolution_result::select_version;
+use crate::resolution::ContentType;
+use crate::resolution::DocumentMetadata;
+use crate::resolution::ResolutionErrorCode;
+use crate::resolution::ResolutionMetadata;
+use crate::resolution::ResolutionOptions;
+
+/// A resource obtained by dereferencing a DID URL.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum DereferencedResource {
+  /// The DID URL identified a verification method.
+  Method(IotaVerificationMethod),
+  /// The DID URL identified a service.
+  Service(IotaService),
+  /// The DID URL identified the DID document itself.
+  Document(IotaCoreDocument),
+}
+
+/// The result of dereferencing a DID URL.
+///
+/// See [DID Core - DID URL Dereferencing](https://www.w3.org/TR/did-core/#did-url-dereferencing).
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DereferenceResult {
+  /// Metadata about the dereferencing process.
+  pub dereferencing_metadata: ResolutionMetadata,
+  /// The dereferenced resource, absent if dereferencing failed.
+  pub content_stream: Option<DereferencedResource>,
+  /// Metadata about the dereferenced resource. Contains the DID document metadata when the
+  /// resource is a DID document.
+  pub content_metadata: DocumentMetadata,
+}
+
+impl DereferenceResult {
+  /// Dereferences the given [`IotaDIDUrl`] against the [`ResolvedIotaDocument`] of its DID.
+  ///
+  /// A DID URL without a fragment dereferences to the DID document, a fragment is dereferenced
+  /// to the verification method or service with a matching `id`. The query of the DID URL, such as
+  /// `versionId`, is ignored when matching.
+  pub fn dereference(did_url: &IotaDIDUrl, resolved: &ResolvedIotaDocument, content_type: ContentType) -> Self {
+    if did_url.did() != resolved.document.id() {
+      return Self::from_error(
+        ResolutionErrorCode::InvalidDidUrl,
+        format!("DID URL `{}` does not belong to `{}`", did_url, resolved.document.id()),
+      );
+    }
+
+    if did_url.path().is_some() {
+      return Self::from_error(
+        ResolutionErrorCode::NotFound,
+        format!("DID URL path of `{}` cannot be dereferenced", did_url),
+      );
+    }
+
+    let resource: Option<DereferencedResource> = match did_url.fragment() {
+      None => Some(DereferencedResource::Document(
+        resolved.document.core_document().clone(),
+      )),
+      Some(fragment) => {
+        // Match on the DID and fragment only, the query of the DID URL does not identify the resource.
+        let query: String = format!("{}#{}", did_url.did(), fragment);
+        resolved
+          .document
+          .resolve_method(&query, None)
+          .cloned()
+          .map(DereferencedResource::Method)
+          .or_else(|| {
+            resolved
+              .document
+              .service()
+              .query(&query)
+              .cloned()
+              .map(DereferencedResource::Service)
+          })
+      }
+    };
+
+    match resource {
+      Some(resource @ DereferencedResource::Document(_)) => Self {
+        dereferencing_metadata: ResolutionMetadata::new(content_type),
+        content_stream: Some(resource),
+        content_metadata: DocumentMetadata::from(resolved),
+      },
+      Some(resource) => Self {
+        dereferencing_metadata: ResolutionMetadata::new(content_type),
+        content_stream: Some(resource),
+        content_metadata: DocumentMetadata::new(),
+      },
+      None => Self::from_error(
+        ResolutionErrorCode::NotFound,
+        format!("no verification method or service matches `{}`", did_url),
+      ),
+    }
+  }
+
+  /// Dereferences the given [`IotaDIDUrl`] against the version of its DID document requested by the
+  /// `versionId` or `versionTime` of the [`ResolutionOptions`].
+  ///
+  /// See [`ResolutionResult::from_history`](crate::resolution::ResolutionResult::from_history) for how
+  /// versions are selected.
+  pub fn from_history(did_url: &IotaDIDUrl, history: &DocumentHistory, options: &ResolutionOptions) -> Self {
+    match select_version(history, options) {
+      Ok((resolved, metadata)) => {
+        let mut result: Self = Self::dereference(did_url, &resolved, options.accept.unwrap_or_default());
+        if let Some(DereferencedResource::Document(_)) = result.content_stream {
+          result.content_metadata = metadata;
+        }
+        result
+      }
+      Err((code, message)) => Self::from_error(code, message),
+    }
+  }
+
+  /// Creates a failed [`DereferenceResult`] with the given error code.
+  pub fn from_error(error: ResolutionErrorCode, message: impl Into<String>) -> Self {
+    Self {
+      dereferencing_metadata: ResolutionMetadata::from_error(error, message),
+      content_stream: None,
+      content_metadata: DocumentMetadata::new(),
+    }
+  }
+
+  /// Returns `true` if dereferencing failed.
+  pub fn is_error(&self) -> bool {
+    self.dereferencing_metadata.is_error()
+  }
+
+  /// Returns the error code of a failed dereferencing.
+  pub fn error(&self) -> Option<ResolutionErrorCode> {
+    self.dereferencing_metadata.error
+  }
+
+  /// Serializes the dereferenced resource according to the [`ContentType`] of the
+  /// dereferencing metadata.
+  ///
+  /// # Errors
+  ///
+  /// Fails if dereferencing was not successful or serialization fails.
+  pub fn content_representation(&self) -> Result<Vec<u8>> {
+    let content_type: ContentType = self.dereferencing_metadata.content_type.unwrap_or_default();
+    match self.content_stream {
+      Some(DereferencedResource::Document(ref document)) => Ok(represent(document, content_type)?.to_json_vec()?),
+      Some(DereferencedResource::Method(ref method)) => Ok(represent(method, content_type)?.to_json_vec()?),
+      Some(DereferencedResource::Service(ref service)) => Ok(represent(service, content_type)?.to_json_vec()?),
+      None => Err(Error::DIDNotFound(format!(
+        "no resource to represent: {}",
+        self.error().unwrap_or(ResolutionErrorCode::NotFound)
+      ))),
+    }
+  }
+}
+
+impl Display for DereferenceResult {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    self.fmt_json(f)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Object;
+  use identity_core::common::Url;
+  use identity_core::convert::FromJson;
+  use identity_core::crypto::KeyPair;
+  use identity_core::crypto::KeyType;
+  use identity_did::did::DID;
+  use identity_did::service::Service;
+  use identity_iota_core::document::IotaDocument;
+
+  use super::*;
+
+  fn generate_resolved() -> ResolvedIotaDocument {
+    let keypair: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+    let mut document: IotaDocument = IotaDocument::new(&keypair).unwrap();
+    let service: IotaService = Service::builder(Object::new())
+      .id(document.id().to_url().join("#linked-domain").unwrap())
+      .type_("LinkedDomains")
+      .service_endpoint(Url::parse("https://iota.org").unwrap())
+      .build()
+      .unwrap();
+    assert!(document.insert_service(service));
+    ResolvedIotaDocument::from(document)
+  }
+
+  #[test]
+  fn test_dereference_document() {
+    let resolved: ResolvedIotaDocument = generate_resolved();
+    let result: DereferenceResult =
+      DereferenceResult::dereference(&resolved.document.id().to_url(), &resolved, ContentType::Json);
+    assert!(!result.is_error());
+    assert!(matches!(result.content_stream, Some(DereferencedResource::Document(_))));
+  }
+
+  #[test]
+  fn test_dereference_method() {
+    let resolved: ResolvedIotaDocument = generate_resolved();
+    let method_url: IotaDIDUrl = resolved.document.default_signing_method().unwrap().id().clone();
+    let result: DereferenceResult = DereferenceResult::dereference(&method_url, &resolved, ContentType::JsonLd);
+    match result.content_stream {
+      Some(DereferencedResource::Method(ref method)) => assert_eq!(method.id(), &method_url),
+      _ => panic!("expected verification method"),
+    }
+
+    let representation: Object = Object::from_json_slice(&result.content_representation().unwrap()).unwrap();
+    assert_eq!(representation["@context"], ContentType::DID_CONTEXT);
+  }
+
+  #[test]
+  fn test_dereference_service() {
+    let resolved: ResolvedIotaDocument = generate_resolved();
+    let service_url: IotaDIDUrl = resolved.document.id().to_url().join("#linked-domain").unwrap();
+    let result: DereferenceResult = DereferenceResult::dereference(&service_url, &resolved, ContentType::Json);
+    match result.content_stream {
+      Some(DereferencedResource::Service(ref service)) => assert_eq!(service.id(), &service_url),
+      _ => panic!("expected service"),
+    }
+  }
+
+  #[test]
+  fn test_dereference_method_with_query() {
+    let resolved: ResolvedIotaDocument = generate_resolved();
+    let method_url: IotaDIDUrl = resolved.document.default_signing_method().unwrap().id().clone();
+    let query_url: IotaDIDUrl = IotaDIDUrl::parse(format!(
+      "{}?versionId={}#{}",
+      resolved.document.id(),
+      "1".repeat(64),
+      IotaDocument::DEFAULT_METHOD_FRAGMENT
+    ))
+    .unwrap();
+    let result: DereferenceResult = DereferenceResult::dereference(&query_url, &resolved, ContentType::Json);
+    match result.content_stream {
+      Some(DereferencedResource::Method(ref method)) => assert_eq!(method.id(), &method_url),
+      _ => panic!("expected verification method"),
+    }
+  }
+
+  #[test]
+  fn test_dereference_not_found() {
+    let resolved: ResolvedIotaDocument = generate_resolved();
+    let missing_url: IotaDIDUrl = resolved.document.id().to_url().join("#missing").unwrap();
+    let result: DereferenceResult = DereferenceResult::dereference(&missing_url, &resolved, ContentType::Json);
+    assert_eq!(result.error(), Some(ResolutionErrorCode::NotFound));
+    assert!(result.content_representation().is_err());
+  }
+}
@@ -0,0 +1,84 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde;
+use serde::Deserialize;
+use serde::Serialize;
+
+use identity_core::common::Object;
+use identity_core::common::Timestamp;
+use identity_iota_core::tangle::MessageId;
+use identity_iota_core::tangle::MessageIdExt;
+
+use crate::document::ResolvedIotaDocument;
+
+/// Metadata about a resolved DID document.
+///
+/// See [DID Core - DID Document Metadata](https://www.w3.org/TR/did-core/#did-document-metadata).
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentMetadata {
+  /// The timestamp of the creation of the DID document.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub created: Option<Timestamp>,
+  /// The timestamp of the last update of the DID document.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub updated: Option<Timestamp>,
+  /// Whether the DID has been deactivated.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub deactivated: Option<bool>,
+  /// The version of the resolved DID document, i.e. the [`MessageId`] of its latest diff chain message or,
+  /// without diffs, of its integration chain message.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub version_id: Option<MessageId>,
+  /// The timestamp of the next update of the DID document, if resolving a previous version.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub next_update: Option<Timestamp>,
+  /// The version of the next update of the DID document, if resolving a previous version.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub next_version_id: Option<MessageId>,
+  /// Additional, method-specific metadata.
+  #[serde(flatten)]
+  pub properties: Object,
+}
+
+impl DocumentMetadata {
+  /// Creates new, empty [`DocumentMetadata`].
+  pub fn new() -> Self {
+    Self::default()
+  }
+}
+
+impl From<&ResolvedIotaDocument> for DocumentMetadata {
+  fn from(resolved: &ResolvedIotaDocument) -> Self {
+    let mut properties: Object = resolved.document.metadata.properties.clone();
+    if !resolved.document.metadata.previous_message_id.is_null() {
+      properties.insert(
+        "previousMessageId".to_owned(),
+        resolved.document.metadata.previous_message_id.encode_hex().into(),
+      );
+    }
+    if !resolved.diff_message_id.is_null() {
+      properties.insert("diffMessageId".to_owned(), resolved.diff_message_id.encode_hex().into());
+    }
+
+    Self {
+      created: resolved.document.metadata.created,
+      updated: resolved.document.metadata.updated,
+      deactivated: resolved.document.metadata.deactivated,
+      version_id: Some(version_message_id(resolved)).filter(|message_id| !message_id.is_null()),
+      next_update: None,
+      next_version_id: None,
+      properties,
+    }
+  }
+}
+
+/// Returns the [`MessageId`] identifying the version of a [`ResolvedIotaDocument`].
+pub(crate) fn version_message_id(resolved: &ResolvedIotaDocument) -> MessageId {
+  if resolved.diff_message_id.is_null() {
+    resolved.integration_message_id
+  } else {
+    resolved.diff_message_id
+  }
+}
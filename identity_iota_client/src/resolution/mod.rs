@@ -0,0 +1,22 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Types modelling the result of DID resolution and DID URL dereferencing as defined by
+//! the [W3C DID Resolution](https://w3c-ccg.github.io/did-resolution/) specification.
+
+pub use self::content_type::ContentType;
+pub use self::dereference::DereferenceResult;
+pub use self::dereference::DereferencedResource;
+pub use self::document_metadata::DocumentMetadata;
+pub use self::resolution_error::ResolutionErrorCode;
+pub use self::resolution_metadata::ResolutionMetadata;
+pub use self::resolution_options::ResolutionOptions;
+pub use self::resolution_result::ResolutionResult;
+
+mod content_type;
+mod dereference;
+mod document_metadata;
+mod resolution_error;
+mod resolution_metadata;
+mod resolution_options;
+mod resolution_result;
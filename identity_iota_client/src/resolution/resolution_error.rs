@@ -0,0 +1,68 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+
+use serde;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::error::Error;
+
+/// Error codes reported in the `error` property of the DID resolution or dereferencing metadata.
+///
+/// See [DID Core - DID Resolution Metadata](https://www.w3.org/TR/did-core/#did-resolution-metadata).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ResolutionErrorCode {
+  /// The DID supplied to the DID resolution function does not conform to valid syntax.
+  InvalidDid,
+  /// The DID URL supplied to the DID URL dereferencing function does not conform to valid syntax.
+  InvalidDidUrl,
+  /// The DID resolver was unable to find the DID document or the dereferenced resource.
+  NotFound,
+  /// The resolution options supplied to the DID resolution function are invalid.
+  InvalidOptions,
+  /// The representation requested via the `accept` input metadata property is not supported.
+  RepresentationNotSupported,
+  /// The DID method of the DID supplied to the resolution function is not supported.
+  MethodNotSupported,
+  /// An unexpected error occurred during resolution or dereferencing.
+  InternalError,
+}
+
+impl ResolutionErrorCode {
+  /// Returns the string representation of this error code as used in the resolution metadata.
+  pub const fn as_str(&self) -> &'static str {
+    match self {
+      Self::InvalidDid => "invalidDid",
+      Self::InvalidDidUrl => "invalidDidUrl",
+      Self::NotFound => "notFound",
+      Self::InvalidOptions => "invalidOptions",
+      Self::RepresentationNotSupported => "representationNotSupported",
+      Self::MethodNotSupported => "methodNotSupported",
+      Self::InternalError => "internalError",
+    }
+  }
+
+  /// Maps an error returned while resolving a DID to the matching [`ResolutionErrorCode`].
+  pub(crate) fn from_resolution_error(error: &Error) -> Self {
+    match error {
+      Error::InvalidDID(_) => Self::InvalidDid,
+      Error::IotaCoreError(identity_iota_core::Error::InvalidDID(_))
+      | Error::IotaCoreError(identity_iota_core::Error::InvalidNetworkName) => Self::InvalidDid,
+      Error::DIDNotFound(_) | Error::ChainError { .. } => Self::NotFound,
+      _ => Self::InternalError,
+    }
+  }
+}
+
+impl Display for ResolutionErrorCode {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.write_str(self.as_str())
+  }
+}
+
+impl std::error::Error for ResolutionErrorCode {}
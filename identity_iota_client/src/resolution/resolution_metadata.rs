@@ -0,0 +1,58 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde;
+use serde::Deserialize;
+use serde::Serialize;
+
+use identity_core::common::Object;
+
+use crate::resolution::ContentType;
+use crate::resolution::ResolutionErrorCode;
+
+/// Metadata about the DID resolution or DID URL dereferencing process.
+///
+/// See [DID Core - DID Resolution Metadata](https://www.w3.org/TR/did-core/#did-resolution-metadata).
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolutionMetadata {
+  /// The media type of the returned representation, if resolution was successful.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub content_type: Option<ContentType>,
+  /// The error code of a failed resolution.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub error: Option<ResolutionErrorCode>,
+  /// A human-readable description of the error, if any.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub error_message: Option<String>,
+  /// Additional, method-specific metadata.
+  #[serde(flatten)]
+  pub properties: Object,
+}
+
+impl ResolutionMetadata {
+  /// Creates new [`ResolutionMetadata`] for a successful resolution producing the given [`ContentType`].
+  pub fn new(content_type: ContentType) -> Self {
+    Self {
+      content_type: Some(content_type),
+      error: None,
+      error_message: None,
+      properties: Object::new(),
+    }
+  }
+
+  /// Creates new [`ResolutionMetadata`] for a failed resolution.
+  pub fn from_error(error: ResolutionErrorCode, message: impl Into<String>) -> Self {
+    Self {
+      content_type: None,
+      error: Some(error),
+      error_message: Some(message.into()),
+      properties: Object::new(),
+    }
+  }
+
+  /// Returns `true` if the metadata reports an error.
+  pub fn is_error(&self) -> bool {
+    self.error.is_some()
+  }
+}
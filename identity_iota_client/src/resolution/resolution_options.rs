@@ -0,0 +1,65 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde;
+use serde::Deserialize;
+use serde::Serialize;
+
+use identity_core::common::Timestamp;
+use identity_iota_core::tangle::MessageId;
+
+use crate::resolution::ContentType;
+
+/// Input metadata for DID resolution and DID URL dereferencing.
+///
+/// See [DID Core - DID Resolution Options](https://www.w3.org/TR/did-core/#did-resolution-options).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolutionOptions {
+  /// The requested representation of the DID document, defaults to [`ContentType::Json`].
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub accept: Option<ContentType>,
+  /// Resolve the DID document version published in the integration chain message with this [`MessageId`].
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub version_id: Option<MessageId>,
+  /// Resolve the DID document version that was valid at this point in time.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub version_time: Option<Timestamp>,
+}
+
+impl ResolutionOptions {
+  /// Creates a new `ResolutionOptions` with all options unset.
+  pub fn new() -> Self {
+    Self {
+      accept: None,
+      version_id: None,
+      version_time: None,
+    }
+  }
+
+  /// Sets the requested [`ContentType`] of the DID document representation.
+  #[must_use]
+  pub fn accept(mut self, accept: ContentType) -> Self {
+    self.accept = Some(accept);
+    self
+  }
+
+  /// Sets the [`MessageId`] of the requested DID document version.
+  #[must_use]
+  pub fn version_id(mut self, version_id: MessageId) -> Self {
+    self.version_id = Some(version_id);
+    self
+  }
+
+  /// Sets the point in time of the requested DID document version.
+  #[must_use]
+  pub fn version_time(mut self, version_time: Timestamp) -> Self {
+    self.version_time = Some(version_time);
+    self
+  }
+
+  /// Returns `true` if a specific, possibly previous, version of the DID document is requested.
+  pub fn is_version_query(&self) -> bool {
+    self.version_id.is_some() || self.version_time.is_some()
+  }
+}
@@ -0,0 +1,390 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+
+use serde;
+use serde::Deserialize;
+use serde::Serialize;
+
+use identity_core::common::Object;
+use identity_core::common::Value;
+use identity_core::convert::FmtJson;
+use identity_core::convert::FromJson;
+use identity_core::convert::ToJson;
+use identity_iota_core::diff::DiffMessage;
+use identity_iota_core::document::IotaCoreDocument;
+
+use crate::chain::DocumentHistory;
+use crate::document::ResolvedIotaDocument;
+use crate::error::Error;
+use crate::error::Result;
+use crate::resolution::document_metadata::version_message_id;
+use crate::resolution::ContentType;
+use crate::resolution::DocumentMetadata;
+use crate::resolution::ResolutionErrorCode;
+use crate::resolution::ResolutionMetadata;
+use crate::resolution::ResolutionOptions;
+
+/// The result of resolving a DID, consisting of the DID document and metadata about both the
+/// resolution process and the document.
+///
+/// See [DID Core - DID Resolution](https://www.w3.org/TR/did-core/#did-resolution).
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolutionResult {
+  /// Metadata about the resolution process.
+  pub did_resolution_metadata: ResolutionMetadata,
+  /// The resolved DID document, absent if resolution failed.
+  pub did_document: Option<IotaCoreDocument>,
+  /// Metadata about the resolved DID document.
+  pub did_document_metadata: DocumentMetadata,
+}
+
+impl ResolutionResult {
+  /// The JSON-LD context of a serialized [`ResolutionResult`].
+  pub const RESOLUTION_CONTEXT: &'static str = "https://w3id.org/did-resolution/v1";
+
+  /// The media type of a serialized [`ResolutionResult`].
+  pub const MEDIA_TYPE: &'static str = "application/ld+json;profile=\"https://w3id.org/did-resolution\"";
+
+  /// Creates a successful [`ResolutionResult`] from a [`ResolvedIotaDocument`] to be
+  /// represented as `content_type`.
+  pub fn from_resolved(resolved: &ResolvedIotaDocument, content_type: ContentType) -> Self {
+    Self {
+      did_resolution_metadata: ResolutionMetadata::new(content_type),
+      did_document: Some(resolved.document.core_document().clone()),
+      did_document_metadata: DocumentMetadata::from(resolved),
+    }
+  }
+
+  /// Creates a [`ResolutionResult`] for the version of a DID document requested by the
+  /// `versionId` or `versionTime` of the [`ResolutionOptions`].
+  ///
+  /// The versions of a DID document are its integration chain messages followed by the diff chain
+  /// messages of the last of them. If both options are set, the version must match both of them.
+  /// When a previous version is resolved, the `nextUpdate` and `nextVersionId` document metadata
+  /// properties refer to its successor.
+  pub fn from_history(history: &DocumentHistory, options: &ResolutionOptions) -> Self {
+    match select_version(history, options) {
+      Ok((resolved, metadata)) => Self {
+        did_resolution_metadata: ResolutionMetadata::new(options.accept.unwrap_or_default()),
+        did_document: Some(resolved.document.core_document().clone()),
+        did_document_metadata: metadata,
+      },
+      Err((code, message)) => Self::from_error(code, message),
+    }
+  }
+
+  /// Creates a failed [`ResolutionResult`] with the given error code.
+  pub fn from_error(error: ResolutionErrorCode, message: impl Into<String>) -> Self {
+    Self {
+      did_resolution_metadata: ResolutionMetadata::from_error(error, message),
+      did_document: None,
+      did_document_metadata: DocumentMetadata::new(),
+    }
+  }
+
+  /// Returns `true` if resolution failed.
+  pub fn is_error(&self) -> bool {
+    self.did_resolution_metadata.is_error()
+  }
+
+  /// Returns the error code of a failed resolution.
+  pub fn error(&self) -> Option<ResolutionErrorCode> {
+    self.did_resolution_metadata.error
+  }
+
+  /// Returns the [`ContentType`] of the DID document representation.
+  pub fn content_type(&self) -> ContentType {
+    self.did_resolution_metadata.content_type.unwrap_or_default()
+  }
+
+  /// Serializes the DID document into its representation according to the [`ContentType`] of
+  /// the resolution metadata.
+  ///
+  /// This corresponds to the `didDocumentStream` of the `resolveRepresentation` function.
+  ///
+  /// # Errors
+  ///
+  /// Fails if resolution was not successful or serialization fails.
+  pub fn document_representation(&self) -> Result<Vec<u8>> {
+    let document: &IotaCoreDocument = self.did_document.as_ref().ok_or_else(|| {
+      Error::DIDNotFound(format!(
+        "no DID document to represent: {}",
+        self.error().unwrap_or(ResolutionErrorCode::NotFound)
+      ))
+    })?;
+    Ok(represent(document, self.content_type())?.to_json_vec()?)
+  }
+
+  /// Serializes the full resolution result including the DID document in its requested
+  /// representation, as returned for the [`ResolutionResult::MEDIA_TYPE`] media type.
+  ///
+  /// # Errors
+  ///
+  /// Fails if serialization fails.
+  pub fn to_representation(&self) -> Result<Vec<u8>> {
+    let mut result: Object = Object::new();
+    result.insert("@context".to_owned(), Self::RESOLUTION_CONTEXT.into());
+    result.insert(
+      "didDocument".to_owned(),
+      match self.did_document {
+        Some(ref document) => represent(document, self.content_type())?.to_json_value()?,
+        None => Value::Null,
+      },
+    );
+    result.insert(
+      "didResolutionMetadata".to_owned(),
+      self.did_resolution_metadata.to_json_value()?,
+    );
+    result.insert(
+      "didDocumentMetadata".to_owned(),
+      self.did_document_metadata.to_json_value()?,
+    );
+    Ok(result.to_json_vec()?)
+  }
+}
+
+/// Selects the version of a DID document requested by the `versionId` and `versionTime` of the
+/// [`ResolutionOptions`] from its [`DocumentHistory`], along with the [`DocumentMetadata`] of that version.
+pub(crate) fn select_version(
+  history: &DocumentHistory,
+  options: &ResolutionOptions,
+) -> core::result::Result<(ResolvedIotaDocument, DocumentMetadata), (ResolutionErrorCode, String)> {
+  // Every diff applied to the last integration chain document yields another version.
+  let mut versions: Vec<ResolvedIotaDocument> = history.integration_chain_data.clone();
+  #[allow(deprecated)]
+  let diffs: &[DiffMessage] = &history.diff_chain_data;
+  if let Some(mut current) = versions.last().cloned() {
+    for diff in diffs {
+      current
+        .merge_diff_message(diff)
+        .map_err(|error| (ResolutionErrorCode::InternalError, error.to_string()))?;
+      versions.push(current.clone());
+    }
+  }
+
+  let selected: Option<usize> = versions.iter().rposition(|version| {
+    let id_matches: bool = options
+      .version_id
+      .map_or(true, |version_id| version_message_id(version) == version_id);
+    let time_matches: bool = options.version_time.map_or(true, |version_time| {
+      version
+        .document
+        .metadata
+        .updated
+        .or(version.document.metadata.created)
+        .map_or(false, |updated| updated <= version_time)
+    });
+    id_matches && time_matches
+  });
+  let index: usize = selected.ok_or_else(|| {
+    (
+      ResolutionErrorCode::NotFound,
+      "no DID document version matches the requested versionId or versionTime".to_owned(),
+    )
+  })?;
+
+  let mut metadata: DocumentMetadata = DocumentMetadata::from(&versions[index]);
+  if let Some(next) = versions.get(index + 1) {
+    metadata.next_update = next.document.metadata.updated;
+    metadata.next_version_id = Some(version_message_id(next));
+  }
+  Ok((versions.swap_remove(index), metadata))
+}
+
+/// Converts a DID document to a JSON object according to the given [`ContentType`].
+pub(crate) fn represent<T: Serialize>(document: &T, content_type: ContentType) -> Result<Object> {
+  let mut object: Object = Object::from_json_value(document.to_json_value()?)?;
+  if content_type == ContentType::JsonLd && !object.contains_key("@context") {
+    object.insert("@context".to_owned(), ContentType::DID_CONTEXT.into());
+  }
+  Ok(object)
+}
+
+impl Display for ResolutionResult {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    self.fmt_json(f)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Timestamp;
+  use identity_core::crypto::KeyPair;
+  use identity_core::crypto::KeyType;
+  use identity_did::did::DID;
+  use identity_iota_core::document::IotaDocument;
+  use identity_iota_core::tangle::MessageId;
+
+  use super::*;
+
+  fn generate_resolved() -> ResolvedIotaDocument {
+    let keypair: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+    let mut resolved: ResolvedIotaDocument = ResolvedIotaDocument::from(IotaDocument::new(&keypair).unwrap());
+    resolved.integration_message_id = MessageId::new([1; 32]);
+    resolved
+  }
+
+  #[test]
+  fn test_resolution_result_json() {
+    let resolved: ResolvedIotaDocument = generate_resolved();
+    let result: ResolutionResult = ResolutionResult::from_resolved(&resolved, ContentType::Json);
+    assert!(!result.is_error());
+    assert_eq!(
+      result.did_document_metadata.version_id,
+      Some(resolved.integration_message_id)
+    );
+    assert_eq!(result.did_document_metadata.created, resolved.document.metadata.created);
+
+    let representation: Object = Object::from_json_slice(&result.document_representation().unwrap()).unwrap();
+    assert!(!representation.contains_key("@context"));
+    assert_eq!(representation["id"], resolved.document.id().as_str());
+
+    let deserialized: ResolutionResult = ResolutionResult::from_json(&result.to_json().unwrap()).unwrap();
+    assert_eq!(deserialized, result);
+  }
+
+  #[test]
+  fn test_resolution_result_json_ld() {
+    let resolved: ResolvedIotaDocument = generate_resolved();
+    let result: ResolutionResult = ResolutionResult::from_resolved(&resolved, ContentType::JsonLd);
+
+    let representation: Object = Object::from_json_slice(&result.document_representation().unwrap()).unwrap();
+    assert_eq!(representation["@context"], ContentType::DID_CONTEXT);
+
+    let full: Object = Object::from_json_slice(&result.to_representation().unwrap()).unwrap();
+    assert_eq!(full["@context"], ResolutionResult::RESOLUTION_CONTEXT);
+    assert_eq!(full["didDocument"]["@context"], ContentType::DID_CONTEXT);
+    assert_eq!(
+      full["didResolutionMetadata"]["contentType"],
+      ContentType::JsonLd.as_str()
+    );
+  }
+
+  fn generate_history() -> (DocumentHistory, KeyPair) {
+    let keypair: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+    let document: IotaDocument = IotaDocument::new(&keypair).unwrap();
+    let integration_chain_data: Vec<ResolvedIotaDocument> = (1..=3)
+      .map(|index| {
+        let mut resolved: ResolvedIotaDocument = ResolvedIotaDocument::from(document.clone());
+        resolved.integration_message_id = MessageId::new([index; 32]);
+        resolved.document.metadata.updated = Some(Timestamp::from_unix(1_000 * index as i64).unwrap());
+        resolved
+      })
+      .collect();
+    let history: DocumentHistory = DocumentHistory {
+      integration_chain_data,
+      integration_chain_spam: Vec::new(),
+      diff_chain_data: Vec::new(),
+      diff_chain_spam: Vec::new(),
+    };
+    (history, keypair)
+  }
+
+  #[test]
+  fn test_resolution_result_version_id() {
+    let (history, _): (DocumentHistory, KeyPair) = generate_history();
+
+    let options: ResolutionOptions = ResolutionOptions::new().version_id(MessageId::new([2; 32]));
+    let result: ResolutionResult = ResolutionResult::from_history(&history, &options);
+    assert_eq!(result.did_document_metadata.version_id, Some(MessageId::new([2; 32])));
+    assert_eq!(
+      result.did_document_metadata.next_version_id,
+      Some(MessageId::new([3; 32]))
+    );
+    assert_eq!(
+      result.did_document_metadata.next_update,
+      Some(Timestamp::from_unix(3_000).unwrap())
+    );
+
+    let options: ResolutionOptions = ResolutionOptions::new().version_id(MessageId::new([3; 32]));
+    let result: ResolutionResult = ResolutionResult::from_history(&history, &options);
+    assert_eq!(result.did_document_metadata.version_id, Some(MessageId::new([3; 32])));
+    assert!(result.did_document_metadata.next_version_id.is_none());
+
+    let options: ResolutionOptions = ResolutionOptions::new().version_id(MessageId::new([9; 32]));
+    let result: ResolutionResult = ResolutionResult::from_history(&history, &options);
+    assert_eq!(result.error(), Some(ResolutionErrorCode::NotFound));
+  }
+
+  #[test]
+  fn test_resolution_result_version_time() {
+    let (history, _): (DocumentHistory, KeyPair) = generate_history();
+
+    let options: ResolutionOptions = ResolutionOptions::new().version_time(Timestamp::from_unix(2_500).unwrap());
+    let result: ResolutionResult = ResolutionResult::from_history(&history, &options);
+    assert_eq!(result.did_document_metadata.version_id, Some(MessageId::new([2; 32])));
+
+    let options: ResolutionOptions = ResolutionOptions::new().version_time(Timestamp::from_unix(5_000).unwrap());
+    let result: ResolutionResult = ResolutionResult::from_history(&history, &options);
+    assert_eq!(result.did_document_metadata.version_id, Some(MessageId::new([3; 32])));
+
+    let options: ResolutionOptions = ResolutionOptions::new().version_time(Timestamp::from_unix(500).unwrap());
+    let result: ResolutionResult = ResolutionResult::from_history(&history, &options);
+    assert_eq!(result.error(), Some(ResolutionErrorCode::NotFound));
+  }
+
+  #[test]
+  #[allow(deprecated)]
+  fn test_resolution_result_diff_version() {
+    let (mut history, keypair): (DocumentHistory, KeyPair) = generate_history();
+    let last: &ResolvedIotaDocument = history.integration_chain_data.last().unwrap();
+    let mut updated: IotaDocument = last.document.clone();
+    updated.metadata.updated = Some(Timestamp::from_unix(4_000).unwrap());
+    updated.metadata.properties.insert("diffed".to_owned(), true.into());
+    let mut diff: DiffMessage = last
+      .document
+      .diff(
+        &updated,
+        last.integration_message_id,
+        keypair.private(),
+        last.document.default_signing_method().unwrap().id(),
+      )
+      .unwrap();
+    diff.set_message_id(MessageId::new([4; 32]));
+    history.diff_chain_data.push(diff);
+
+    // The latest integration chain document is followed by the diff.
+    let options: ResolutionOptions = ResolutionOptions::new().version_id(MessageId::new([3; 32]));
+    let result: ResolutionResult = ResolutionResult::from_history(&history, &options);
+    assert!(!result.did_document_metadata.properties.contains_key("diffed"));
+    assert_eq!(
+      result.did_document_metadata.next_version_id,
+      Some(MessageId::new([4; 32]))
+    );
+    assert_eq!(
+      result.did_document_metadata.next_update,
+      Some(Timestamp::from_unix(4_000).unwrap())
+    );
+
+    let options: ResolutionOptions = ResolutionOptions::new().version_id(MessageId::new([4; 32]));
+    let result: ResolutionResult = ResolutionResult::from_history(&history, &options);
+    assert_eq!(result.did_document_metadata.version_id, Some(MessageId::new([4; 32])));
+    assert_eq!(result.did_document_metadata.properties["diffed"], true);
+    assert!(result.did_document_metadata.next_version_id.is_none());
+
+    let options: ResolutionOptions = ResolutionOptions::new().version_time(Timestamp::from_unix(3_500).unwrap());
+    let result: ResolutionResult = ResolutionResult::from_history(&history, &options);
+    assert_eq!(result.did_document_metadata.version_id, Some(MessageId::new([3; 32])));
+
+    let options: ResolutionOptions = ResolutionOptions::new().version_time(Timestamp::from_unix(5_000).unwrap());
+    let result: ResolutionResult = ResolutionResult::from_history(&history, &options);
+    assert_eq!(result.did_document_metadata.version_id, Some(MessageId::new([4; 32])));
+  }
+
+  #[test]
+  fn test_resolution_result_error() {
+    let result: ResolutionResult = ResolutionResult::from_error(ResolutionErrorCode::NotFound, "not found");
+    assert!(result.is_error());
+    assert!(result.did_document.is_none());
+    assert!(result.document_representation().is_err());
+
+    let full: Object = Object::from_json_slice(&result.to_representation().unwrap()).unwrap();
+    assert_eq!(full["didResolutionMetadata"]["error"], "notFound");
+    assert!(full["didDocument"].is_null());
+  }
+}
@@ -118,9 +118,15 @@ impl Client {
     }
   }
 
+  /// Returns the message data arbitrary JSON data is published as, encoded with the configured
+  /// [`DIDMessageEncoding`].
+  pub fn pack_message<T: ToJson>(&self, data: &T) -> Result<Vec<u8>> {
+    crate::tangle::pack_did_message(data, self.encoding)
+  }
+
   /// Compresses and publishes arbitrary JSON data to the specified index on the Tangle.
   pub async fn publish_json<T: ToJson>(&self, index: &str, data: &T) -> Result<Receipt> {
-    let message_data: Vec<u8> = crate::tangle::pack_did_message(data, self.encoding)?;
+    let message_data: Vec<u8> = self.pack_message(data)?;
     self
       .client
       .message()
@@ -14,7 +14,11 @@ use identity_credential::validator::FailFast;
 use identity_credential::validator::PresentationValidationOptions;
 use identity_credential::validator::PresentationValidator;
 use identity_credential::validator::ValidatorDocument;
+use identity_did::did::CoreDID;
+use identity_did::did::CoreDIDUrl;
+use identity_did::did::DID;
 use identity_iota_core::did::IotaDID;
+use identity_iota_core::did::IotaDIDUrl;
 use identity_iota_core::diff::DiffMessage;
 use identity_iota_core::document::IotaDocument;
 use identity_iota_core::tangle::NetworkName;
@@ -24,6 +28,11 @@ use crate::chain::DocumentHistory;
 use crate::document::ResolvedIotaDocument;
 use crate::error::Error;
 use crate::error::Result;
+use crate::resolution::ContentType;
+use crate::resolution::DereferenceResult;
+use crate::resolution::ResolutionErrorCode;
+use crate::resolution::ResolutionOptions;
+use crate::resolution::ResolutionResult;
 use crate::tangle::Client;
 use crate::tangle::ClientBuilder;
 use crate::tangle::SharedPtr;
@@ -78,12 +87,92 @@ where
     })
   }
 
+  /// Checks that the network of the given [`IotaDID`] matches one of the resolver clients, distinguishing between
+  /// invalid and unsupported networks.
+  fn check_network(&self, did: &IotaDID) -> core::result::Result<(), (ResolutionErrorCode, String)> {
+    let network_name: NetworkName = did
+      .network()
+      .map_err(|error| (ResolutionErrorCode::InvalidDid, error.to_string()))?
+      .name();
+    if self.client_map.contains_key(&network_name) {
+      Ok(())
+    } else {
+      Err((
+        ResolutionErrorCode::MethodNotSupported,
+        format!("DID network `{}` is not supported by the resolver", network_name),
+      ))
+    }
+  }
+
   /// Fetches the [`ResolvedIotaDocument`] of the given [`IotaDID`].
   pub async fn resolve(&self, did: &IotaDID) -> Result<ResolvedIotaDocument> {
     let client: &Client = self.get_client_for_did(did)?.deref();
     client.read_document(did).await
   }
 
+  /// Resolves the given DID into a [`ResolutionResult`] as specified by
+  /// [W3C DID Resolution](https://w3c-ccg.github.io/did-resolution/).
+  ///
+  /// Unlike [`Resolver::resolve`], failures are reported via the `error` property of the
+  /// [`ResolutionMetadata`](crate::resolution::ResolutionMetadata) rather than returned.
+  ///
+  /// Previous versions of the DID document can be requested with the `versionId` and `versionTime`
+  /// [`ResolutionOptions`], see [`ResolutionResult::from_history`].
+  pub async fn resolve_result(&self, did: &str, options: &ResolutionOptions) -> ResolutionResult {
+    let content_type: ContentType = options.accept.unwrap_or_default();
+    let did: IotaDID = match parse_iota_did(did).and_then(|did| self.check_network(&did).map(|_| did)) {
+      Ok(did) => did,
+      Err((code, message)) => return ResolutionResult::from_error(code, message),
+    };
+
+    if options.is_version_query() {
+      return match self.resolve_history(&did).await {
+        Ok(history) => ResolutionResult::from_history(&history, options),
+        Err(error) => {
+          ResolutionResult::from_error(ResolutionErrorCode::from_resolution_error(&error), error.to_string())
+        }
+      };
+    }
+
+    match self.resolve(&did).await {
+      Ok(resolved) => ResolutionResult::from_resolved(&resolved, content_type),
+      Err(error) => ResolutionResult::from_error(ResolutionErrorCode::from_resolution_error(&error), error.to_string()),
+    }
+  }
+
+  /// Dereferences the given DID URL into a [`DereferenceResult`] as specified by
+  /// [W3C DID Resolution](https://w3c-ccg.github.io/did-resolution/).
+  ///
+  /// A DID URL with a fragment is dereferenced to the matching verification method or service
+  /// of the resolved DID document, otherwise to the DID document itself.
+  ///
+  /// Previous versions of the DID document can be requested with the `versionId` and `versionTime`
+  /// [`ResolutionOptions`], see [`DereferenceResult::from_history`].
+  pub async fn dereference(&self, did_url: &str, options: &ResolutionOptions) -> DereferenceResult {
+    let content_type: ContentType = options.accept.unwrap_or_default();
+    let did_url: IotaDIDUrl =
+      match parse_iota_did_url(did_url).and_then(|did_url| self.check_network(did_url.did()).map(|_| did_url)) {
+        Ok(did_url) => did_url,
+        Err((code, message)) => return DereferenceResult::from_error(code, message),
+      };
+
+    if options.is_version_query() {
+      return match self.resolve_history(did_url.did()).await {
+        Ok(history) => DereferenceResult::from_history(&did_url, &history, options),
+        Err(error) => {
+          DereferenceResult::from_error(ResolutionErrorCode::from_resolution_error(&error), error.to_string())
+        }
+      };
+    }
+
+    match self.resolve(did_url.did()).await {
+      Ok(resolved) => DereferenceResult::dereference(&did_url, &resolved, content_type),
+      Err(error) => {
+        DereferenceResult::from_error(ResolutionErrorCode::from_resolution_error(&error), error.to_string())
+      }
+    }
+  }
+
   /// Fetches the [`DocumentHistory`] of the given [`IotaDID`].
   pub async fn resolve_history(&self, did: &IotaDID) -> Result<DocumentHistory> {
     let client: &Client = self.get_client_for_did(did)?.deref();
@@ -201,6 +290,33 @@ where
   }
 }
 
+/// Parses an [`IotaDID`], distinguishing between invalid syntax and unsupported DID methods.
+fn parse_iota_did(input: &str) -> core::result::Result<IotaDID, (ResolutionErrorCode, String)> {
+  let did: CoreDID = CoreDID::parse(input).map_err(|error| (ResolutionErrorCode::InvalidDid, error.to_string()))?;
+  if did.method() != IotaDID::METHOD {
+    return Err((
+      ResolutionErrorCode::MethodNotSupported,
+      format!("DID method `{}` is not supported", did.method()),
+    ));
+  }
+  IotaDID::try_from_core(did).map_err(|error| (ResolutionErrorCode::InvalidDid, error.to_string()))
+}
+
+/// Parses an [`IotaDIDUrl`], distinguishing between invalid syntax and unsupported DID methods.
+fn parse_iota_did_url(input: &str) -> core::result::Result<IotaDIDUrl, (ResolutionErrorCode, String)> {
+  let did_url: CoreDIDUrl =
+    CoreDIDUrl::parse(input).map_err(|error| (ResolutionErrorCode::InvalidDidUrl, error.to_string()))?;
+  if did_url.did().method() != IotaDID::METHOD {
+    return Err((
+      ResolutionErrorCode::MethodNotSupported,
+      format!("DID method `{}` is not supported", did_url.did().method()),
+    ));
+  }
+  did_url
+    .try_map(IotaDID::try_from_core)
+    .map_err(|error| (ResolutionErrorCode::InvalidDidUrl, error.to_string()))
+}
+
 /// Builder for configuring [`Clients`][Client] when constructing a [`Resolver`].
 #[derive(Default)]
 pub struct ResolverBuilder<C = Arc<Client>>
@@ -460,6 +576,65 @@ mod tests {
       .is_ok());
   }
 
+  #[test]
+  fn test_parse_iota_did_resolution_errors() {
+    assert_eq!(
+      parse_iota_did("not-a-did").unwrap_err().0,
+      ResolutionErrorCode::InvalidDid
+    );
+    assert_eq!(
+      parse_iota_did("did:example:123").unwrap_err().0,
+      ResolutionErrorCode::MethodNotSupported
+    );
+    assert_eq!(
+      parse_iota_did("did:iota:invalid-tag").unwrap_err().0,
+      ResolutionErrorCode::InvalidDid
+    );
+
+    let keypair: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+    let document: IotaDocument = IotaDocument::new(&keypair).unwrap();
+    assert_eq!(&parse_iota_did(document.id().as_str()).unwrap(), document.id());
+
+    let method_url: String = document.default_signing_method().unwrap().id().to_string();
+    assert_eq!(
+      parse_iota_did_url(&method_url).unwrap().fragment(),
+      Some(IotaDocument::DEFAULT_METHOD_FRAGMENT)
+    );
+    assert_eq!(
+      parse_iota_did_url("did:example:123#key-1").unwrap_err().0,
+      ResolutionErrorCode::MethodNotSupported
+    );
+  }
+
+  #[tokio::test]
+  async fn test_resolve_result_unsupported_network() {
+    let resolver: Resolver = Resolver::<Arc<Client>>::builder()
+      .client_builder(Client::builder().network(Network::Devnet).node_sync_disabled())
+      .build()
+      .await
+      .unwrap();
+
+    let keypair: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+    let document: IotaDocument = IotaDocument::new(&keypair).unwrap();
+    let result: ResolutionResult = resolver
+      .resolve_result(document.id().as_str(), &ResolutionOptions::new())
+      .await;
+    assert_eq!(result.error(), Some(ResolutionErrorCode::MethodNotSupported));
+    assert!(result.did_document.is_none());
+
+    let method_url: String = document.default_signing_method().unwrap().id().to_string();
+    let result: DereferenceResult = resolver.dereference(&method_url, &ResolutionOptions::new()).await;
+    assert_eq!(result.error(), Some(ResolutionErrorCode::MethodNotSupported));
+
+    let result: ResolutionResult = resolver
+      .resolve_result(
+        "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK",
+        &ResolutionOptions::new(),
+      )
+      .await;
+    assert_eq!(result.error(), Some(ResolutionErrorCode::MethodNotSupported));
+  }
+
   #[test]
   fn test_validate_presentation_mixed() {
     let MixedTestSetup {
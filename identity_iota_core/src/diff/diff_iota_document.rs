@@ -87,3 +87,65 @@ impl Diff for IotaDocument {
     })
   }
 }
+
+#[cfg(test)]
+mod test {
+  use identity_core::convert::FromJson;
+  use identity_core::convert::ToJson;
+  use identity_core::crypto::KeyPair;
+  use identity_core::crypto::KeyType;
+  use identity_did::did::DID;
+  use identity_did::utils::Queryable;
+  use identity_did::verification::MethodScope;
+
+  use crate::diff::DiffMessage;
+  use crate::document::IotaVerificationMethod;
+
+  use super::*;
+
+  #[test]
+  fn test_remove_method_by_key() {
+    let keypair: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+    let mut document: IotaDocument = IotaDocument::new(&keypair).unwrap();
+
+    for fragment in ["key-1", "key-2", "key-3"] {
+      let method: IotaVerificationMethod =
+        IotaVerificationMethod::new(document.id().clone(), keypair.type_(), keypair.public(), fragment).unwrap();
+      document.insert_method(method, MethodScope::VerificationMethod).unwrap();
+    }
+
+    let mut updated: IotaDocument = document.clone();
+    updated
+      .remove_method(&document.id().to_url().join("#key-1").unwrap())
+      .unwrap();
+
+    // Only the removed method is referenced, the remaining methods are not part of the diff.
+    let diff: DiffIotaDocument = Diff::diff(&document, &updated).unwrap();
+    let removed: String = document.id().to_url().join("#key-1").unwrap().to_string();
+    assert_eq!(
+      diff.to_json_value().unwrap()["doc"]["verification_method"],
+      identity_core::json!({ "remove": [removed] })
+    );
+    assert_eq!(document.merge(diff).unwrap(), updated);
+  }
+
+  #[test]
+  fn test_merge_indexed_diff_message() {
+    // Published before sets were diffed by key, when the elements of sets were identified by index.
+    const DOCUMENT: &str = r##"{"doc":{"id":"did:iota:EKTHV8xerxASMyb5Bg2juXhAf1wctvSDftHSC6oWcJEo","verificationMethod":[{"id":"did:iota:EKTHV8xerxASMyb5Bg2juXhAf1wctvSDftHSC6oWcJEo#key-1","controller":"did:iota:EKTHV8xerxASMyb5Bg2juXhAf1wctvSDftHSC6oWcJEo","type":"Ed25519VerificationKey2018","publicKeyMultibase":"z9hSR6S7WPtxmTojgo6GG3k4yDPecgJY292j7xrsUGWBu"},{"id":"did:iota:EKTHV8xerxASMyb5Bg2juXhAf1wctvSDftHSC6oWcJEo#key-2","controller":"did:iota:EKTHV8xerxASMyb5Bg2juXhAf1wctvSDftHSC6oWcJEo","type":"Ed25519VerificationKey2018","publicKeyMultibase":"zGyGKxMyg1p9SsHfm15MkNUu1u9TN2JtTspcdmrtGUdse"},{"id":"did:iota:EKTHV8xerxASMyb5Bg2juXhAf1wctvSDftHSC6oWcJEo#key-3","controller":"did:iota:EKTHV8xerxASMyb5Bg2juXhAf1wctvSDftHSC6oWcJEo","type":"Ed25519VerificationKey2018","publicKeyMultibase":"zEdmxWPmx2WH6WgFfTdu9xfkYf3k1g5wD1zccTVySEEh1"}],"capabilityInvocation":[{"id":"did:iota:EKTHV8xerxASMyb5Bg2juXhAf1wctvSDftHSC6oWcJEo#sign-0","controller":"did:iota:EKTHV8xerxASMyb5Bg2juXhAf1wctvSDftHSC6oWcJEo","type":"Ed25519VerificationKey2018","publicKeyMultibase":"zAKnL4NNf3DGWZJS6cPknBuEGnVsV4A4m5tgebLHaRSZ9"}]},"meta":{"created":"2022-06-01T00:00:00Z","updated":"2022-06-01T00:00:00Z"},"proof":{"type":"JcsEd25519Signature2020","verificationMethod":"#sign-0","signatureValue":"5TJF7YqeDTALFYA4C5w4PrVWrG9HkMrpVCbxbMLXQfJoPKYTwuQQouUjGGqXvT5tbxY6dmwL91hKzywG2gm7MMNz"}}"##;
+    const DIFF_MESSAGE: &str = r##"{"id":"did:iota:EKTHV8xerxASMyb5Bg2juXhAf1wctvSDftHSC6oWcJEo","diff":{"doc":{"verification_method":[{"index":0,"item":{"id":"did:iota:EKTHV8xerxASMyb5Bg2juXhAf1wctvSDftHSC6oWcJEo#key-2","data":{"PublicKeyMultibase":"zGyGKxMyg1p9SsHfm15MkNUu1u9TN2JtTspcdmrtGUdse"}}},{"index":1,"item":{"id":"did:iota:EKTHV8xerxASMyb5Bg2juXhAf1wctvSDftHSC6oWcJEo#key-3","data":{"PublicKeyMultibase":"zEdmxWPmx2WH6WgFfTdu9xfkYf3k1g5wD1zccTVySEEh1"}}},{"count":1}],"service":[{"id":"did:iota:EKTHV8xerxASMyb5Bg2juXhAf1wctvSDftHSC6oWcJEo#linked-domain","type_":["LinkedDomains"],"service_endpoint":"https://example.com/"}]},"meta":{"updated":"2022-06-02T00:00:00Z"}},"previousMessageId":"0303030303030303030303030303030303030303030303030303030303030303","proof":{"type":"JcsEd25519Signature2020","verificationMethod":"#sign-0","signatureValue":"3BtJGGCB6AhamyBqZLh12gEu4mRHF8Rak8SU7p57oCUaemw7gesBqMcYtoFdf5XBzGf66xjJjrfM2kKDYxx6t3Sk"}}"##;
+
+    let document: IotaDocument = IotaDocument::from_json(DOCUMENT).unwrap();
+    let diff: DiffMessage = DiffMessage::from_json(DIFF_MESSAGE).unwrap();
+
+    // The diff is serialized unchanged, so its signature remains valid.
+    assert_eq!(diff.to_json().unwrap(), DIFF_MESSAGE);
+    document.verify_diff(&diff).unwrap();
+
+    let merged: IotaDocument = diff.merge(&document).unwrap();
+    assert!(merged.resolve_method("#key-1", None).is_none());
+    assert!(merged.resolve_method("#key-2", None).is_some());
+    assert!(merged.resolve_method("#key-3", None).is_some());
+    assert!(merged.service().query("#linked-domain").is_some());
+  }
+}
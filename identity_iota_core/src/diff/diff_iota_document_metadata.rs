@@ -116,6 +116,8 @@ impl Diff for IotaDocumentMetadata {
       created,
       updated,
       previous_message_id,
+      // Deactivation can only be published in an integration message, never in a diff.
+      deactivated: self.deactivated,
       properties,
     })
   }
@@ -149,6 +151,7 @@ impl Diff for IotaDocumentMetadata {
       created,
       updated,
       previous_message_id,
+      deactivated: None,
       properties,
     })
   }
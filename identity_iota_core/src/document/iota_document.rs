@@ -12,6 +12,7 @@ use serde::Serialize;
 use identity_core::common::Object;
 use identity_core::common::OneOrSet;
 use identity_core::common::OrderedSet;
+use identity_core::common::Timestamp;
 use identity_core::common::Url;
 use identity_core::convert::FmtJson;
 use identity_core::crypto::Ed25519;
@@ -19,18 +20,21 @@ use identity_core::crypto::GetSignature;
 use identity_core::crypto::GetSignatureMut;
 use identity_core::crypto::JcsEd25519;
 use identity_core::crypto::KeyPair;
+use identity_core::crypto::KeyType;
 use identity_core::crypto::PrivateKey;
 use identity_core::crypto::Proof;
 use identity_core::crypto::ProofOptions;
 use identity_core::crypto::PublicKey;
 use identity_core::crypto::SetSignature;
 use identity_core::crypto::Signer;
+use identity_core::diff::JsonPatch;
 use identity_did::document::CoreDocument;
 use identity_did::document::Document;
 use identity_did::service::Service;
 use identity_did::utils::DIDUrlQuery;
 use identity_did::verifiable::DocumentSigner;
 use identity_did::verifiable::VerifierOptions;
+use identity_did::verification::jwk_key_type;
 use identity_did::verification::MethodRef;
 use identity_did::verification::MethodRelationship;
 use identity_did::verification::MethodScope;
@@ -231,6 +235,57 @@ impl IotaDocument {
       .ok_or(Error::MissingSigningKey)
   }
 
+  /// Checks that the verification methods of the document are consistent, see
+  /// [`CoreDocument::check_methods`], and that the
+  /// document contains a default signing method of a supported type unless it is deactivated.
+  ///
+  /// # Errors
+  ///
+  /// Fails if a method is invalid or the default signing method is missing.
+  pub fn check_methods(&self) -> Result<()> {
+    self.core_document().check_methods()?;
+
+    if !self.is_deactivated() {
+      let method: &IotaVerificationMethod = self.default_signing_method()?;
+
+      if !Self::is_signing_method_type(method.type_()) {
+        return Err(Error::InvalidDocumentSigningMethodType);
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Returns whether the DID document has been deactivated, see [`IotaDocument::deactivate`].
+  pub fn is_deactivated(&self) -> bool {
+    self.metadata.deactivated.unwrap_or(false)
+  }
+
+  /// Deactivates the DID document by removing all verification methods, services, controllers
+  /// and properties, and marking it as deactivated in the metadata.
+  ///
+  /// The resulting tombstone must be published as an integration chain update, signed by a
+  /// capability invocation method of the previous document. No further updates can be
+  /// published afterwards.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the document is already deactivated.
+  pub fn deactivate(&mut self) -> Result<()> {
+    if self.is_deactivated() {
+      return Err(Error::DocumentDeactivated);
+    }
+
+    self.document = IotaCoreDocument::builder(Default::default())
+      .id(self.id().clone())
+      .build()?;
+    self.metadata.deactivated = Some(true);
+    self.metadata.updated = Some(Timestamp::now_utc());
+    self.proof = None;
+
+    Ok(())
+  }
+
   /// Returns a reference to the custom DID Document properties.
   pub fn properties(&self) -> &Object {
     self.document.properties()
@@ -432,6 +487,18 @@ impl IotaDocument {
           None,
         ));
       }
+      MethodType::JsonWebKey2020 => match method.data().public_key_jwk().and_then(jwk_key_type) {
+        Some(KeyType::Ed25519) => {
+          JcsEd25519::<Ed25519>::create_signature(self, method_id, private_key.as_ref(), ProofOptions::default())
+            .map_err(|err| Error::DocumentSignError("Ed25519 signature failed", Some(err)))?;
+        }
+        _ => {
+          return Err(Error::DocumentSignError(
+            "JsonWebKey2020 can only sign documents with Ed25519 keys",
+            None,
+          ));
+        }
+      },
     }
 
     Ok(())
@@ -572,6 +639,34 @@ impl IotaDocument {
     Ok(())
   }
 
+  // ===========================================================================
+  // JSON Patch
+  // ===========================================================================
+
+  /// Applies a [`JsonPatch`] to the JSON representation of the document and returns the result,
+  /// leaving `self` unmodified.
+  ///
+  /// The patched document is validated as with [`CoreDocument::apply_json_patch`] and must still
+  /// contain a default signing method unless it is deactivated. Any existing proof is removed, since
+  /// the patched document must be signed again before publishing.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the patch cannot be applied or the patched document is invalid.
+  pub fn apply_json_patch(&self, patch: &JsonPatch) -> Result<Self> {
+    let mut document: IotaDocument = patch.apply_to(self)?;
+
+    if document.id() != self.id() {
+      return Err(Error::InvalidDoc(identity_did::Error::InvalidDocument("id", None)));
+    }
+
+    document.check_methods()?;
+
+    document.proof = None;
+
+    Ok(document)
+  }
+
   // ===========================================================================
   // Publishing
   // ===========================================================================
@@ -637,6 +732,10 @@ impl Document for IotaDocument {
     self.core_document().resolve_service(query)
   }
 
+  fn services(&self) -> &[Service<Self::D, Self::V>] {
+    self.service().as_slice()
+  }
+
   fn resolve_method<'query, 'me, Q>(
     &'me self,
     query: Q,
@@ -654,6 +753,10 @@ impl Document for IotaDocument {
   {
     self.core_document().verify_data(data, options)
   }
+
+  fn is_deactivated(&self) -> bool {
+    IotaDocument::is_deactivated(self)
+  }
 }
 
 #[cfg(feature = "revocation-bitmap")]
@@ -758,6 +861,7 @@ mod tests {
   use identity_core::crypto::KeyType;
   use identity_core::utils::BaseEncoding;
   use identity_did::did::DID;
+  use identity_did::service::LinkedDomainService;
   use identity_did::verifiable::VerifiableProperties;
   use identity_did::verification::MethodData;
 
@@ -1193,6 +1297,52 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_apply_json_patch() {
+    let keypair: KeyPair = generate_testkey();
+    let mut document: IotaDocument = IotaDocument::new(&keypair).unwrap();
+    document
+      .sign_self(keypair.private(), IotaDocument::DEFAULT_METHOD_FRAGMENT)
+      .unwrap();
+
+    let patch = |operations: Value| -> JsonPatch { JsonPatch::from_json_value(operations).unwrap() };
+
+    // A valid patch is applied and the outdated proof is removed.
+    let patched: IotaDocument = document
+      .apply_json_patch(&patch(identity_core::json!([
+        { "op": "add", "path": "/doc/foo", "value": 123 },
+      ])))
+      .unwrap();
+    assert_eq!(patched.properties().get("foo").unwrap(), &Value::from(123));
+    assert!(patched.proof.is_none());
+    assert!(document.proof.is_some());
+
+    // The patched document must keep its id.
+    let other: IotaDID = IotaDID::new(&[1; 32]).unwrap();
+    assert!(matches!(
+      document.apply_json_patch(&patch(identity_core::json!([
+        { "op": "replace", "path": "/doc/id", "value": other.to_string() },
+      ]))),
+      Err(Error::InvalidDoc(identity_did::Error::InvalidDocument("id", None)))
+    ));
+
+    // The patched document must keep a signing method.
+    assert!(matches!(
+      document.apply_json_patch(&patch(identity_core::json!([
+        { "op": "remove", "path": "/doc/capabilityInvocation" },
+      ]))),
+      Err(Error::MissingSigningKey)
+    ));
+
+    // Operations on missing values fail.
+    assert!(matches!(
+      document.apply_json_patch(&patch(identity_core::json!([
+        { "op": "remove", "path": "/doc/service" },
+      ]))),
+      Err(Error::DiffError(_))
+    ));
+  }
+
   #[test]
   fn test_diff_properties() {
     // Ensure custom fields added to properties are retained by diffs.
@@ -1581,6 +1731,27 @@ mod tests {
     assert_eq!(0, document.service().len());
   }
 
+  #[test]
+  fn test_document_linked_domain_service() {
+    let keypair: KeyPair = generate_testkey();
+    let mut document: IotaDocument = IotaDocument::new(&keypair).unwrap();
+    let domains: OrderedSet<Url> = OrderedSet::try_from(vec![
+      Url::parse("https://foo.example.com").unwrap(),
+      Url::parse("https://bar.example.com").unwrap(),
+    ])
+    .unwrap();
+    let linked_domains: LinkedDomainService<IotaDID> = LinkedDomainService::new(
+      document.id().to_url().join("#linked-domain").unwrap(),
+      domains,
+      Object::new(),
+    )
+    .unwrap();
+    assert!(document.insert_service(linked_domains.clone().into_service()));
+
+    let service: IotaService = document.services()[0].clone();
+    assert_eq!(LinkedDomainService::try_from(service).unwrap(), linked_domains);
+  }
+
   #[test]
   fn test_relative_method_uri() {
     let keypair: KeyPair = generate_testkey();
@@ -1643,6 +1814,29 @@ mod tests {
     assert_eq!(document.methods().count(), 1);
   }
 
+  #[test]
+  fn test_deactivate() {
+    let keypair: KeyPair = generate_testkey();
+    let mut document: IotaDocument = IotaDocument::new(&keypair).unwrap();
+    assert!(!document.is_deactivated());
+
+    let did: IotaDID = document.id().clone();
+    document.deactivate().unwrap();
+    assert!(document.is_deactivated());
+    assert_eq!(document.id(), &did);
+    assert_eq!(document.methods().count(), 0);
+    assert!(document.core_document().capability_invocation().is_empty());
+    assert!(document.default_signing_method().is_err());
+
+    // The deactivated flag survives a serialization round-trip.
+    let json: String = document.to_json().unwrap();
+    let deserialized: IotaDocument = IotaDocument::from_json(&json).unwrap();
+    assert!(deserialized.is_deactivated());
+
+    // Deactivating twice is not allowed.
+    assert!(matches!(document.deactivate(), Err(Error::DocumentDeactivated)));
+  }
+
   #[test]
   fn test_document_equality() {
     let keypair1: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
@@ -29,6 +29,8 @@ pub struct IotaDocumentMetadata {
     skip_serializing_if = "MessageId::is_null"
   )]
   pub previous_message_id: MessageId,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub deactivated: Option<bool>,
   #[serde(flatten)]
   pub properties: Object,
 }
@@ -42,6 +44,7 @@ impl IotaDocumentMetadata {
       created: Some(now),
       updated: Some(now),
       previous_message_id: MessageId::null(),
+      deactivated: None,
       properties: Object::default(),
     }
   }
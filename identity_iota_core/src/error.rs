@@ -17,6 +17,8 @@ pub enum Error {
 
   #[error("signing failed: {0}")]
   DocumentSignError(&'static str, #[source] Option<identity_core::Error>),
+  #[error("Invalid Document - Deactivated")]
+  DocumentDeactivated,
   #[error("Invalid Document - Missing Message Id")]
   InvalidDocumentMessageId,
   #[error("Invalid Document - Signing Verification Method Type Not Supported")]
@@ -0,0 +1,174 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::SocketAddr;
+
+use identity_iota_client::tangle::Client;
+use identity_iota_client::tangle::ClientBuilder;
+use identity_iota_client::tangle::Resolver;
+use identity_iota_client::tangle::ResolverBuilder;
+use identity_iota_core::tangle::Network;
+
+use crate::error::Error;
+use crate::error::Result;
+
+/// Environment variable holding the socket address the driver listens on.
+const ENV_LISTEN_ADDRESS: &str = "IOTA_RESOLVER_LISTEN_ADDRESS";
+/// Environment variable holding the networks the driver resolves DIDs on.
+const ENV_NETWORKS: &str = "IOTA_RESOLVER_NETWORKS";
+
+const DEFAULT_LISTEN_ADDRESS: &str = "0.0.0.0:8080";
+const DEFAULT_NETWORKS: &str = "main,dev";
+
+/// Configuration of a Tangle network the driver resolves DIDs on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NetworkConfig {
+  /// The Tangle network.
+  pub network: Network,
+  /// The node URLs used for this network, the default node of the network is used if empty.
+  pub nodes: Vec<String>,
+}
+
+impl NetworkConfig {
+  /// Parses a network entry of the form `name` or `name=url[|url...]`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the network name is invalid.
+  pub fn parse(entry: &str) -> Result<Self> {
+    let (name, nodes): (&str, Vec<String>) = match entry.split_once('=') {
+      Some((name, urls)) => (
+        name,
+        urls
+          .split('|')
+          .map(str::trim)
+          .filter(|url| !url.is_empty())
+          .map(ToOwned::to_owned)
+          .collect(),
+      ),
+      None => (entry, Vec::new()),
+    };
+    let network: Network = Network::try_from_name(name.trim().to_owned())?;
+
+    Ok(Self { network, nodes })
+  }
+
+  /// Returns a [`ClientBuilder`] configured for this network.
+  ///
+  /// # Errors
+  ///
+  /// Fails if a node URL is invalid.
+  pub fn client_builder(&self) -> Result<ClientBuilder> {
+    let mut builder: ClientBuilder = Client::builder().network(self.network.clone());
+    if !self.nodes.is_empty() {
+      let nodes: Vec<&str> = self.nodes.iter().map(String::as_str).collect();
+      builder = builder.nodes(&nodes)?.node_sync_disabled();
+    }
+    Ok(builder)
+  }
+}
+
+/// Configuration of the resolver driver.
+///
+/// Can be loaded from the environment with [`DriverConfig::from_env`]:
+///
+/// - `IOTA_RESOLVER_LISTEN_ADDRESS`: the socket address to listen on, defaults to `0.0.0.0:8080`.
+/// - `IOTA_RESOLVER_NETWORKS`: a comma-separated list of networks of the form `name` or `name=url[|url...]`,
+///   defaults to `main,dev`. Setting node URLs allows pointing the driver at private or local nodes,
+///   e.g. `dev=http://127.0.0.1:14265`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DriverConfig {
+  /// The socket address the driver listens on.
+  pub listen_address: SocketAddr,
+  /// The networks the driver resolves DIDs on.
+  pub networks: Vec<NetworkConfig>,
+}
+
+impl DriverConfig {
+  /// Creates a new [`DriverConfig`] from a listen address and a comma-separated list of networks.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the address or a network entry is invalid, or if no network is given.
+  pub fn parse(listen_address: &str, networks: &str) -> Result<Self> {
+    let listen_address: SocketAddr = listen_address
+      .parse()
+      .map_err(|_| Error::InvalidConfig(format!("invalid listen address `{}`", listen_address)))?;
+    let networks: Vec<NetworkConfig> = networks
+      .split(',')
+      .map(str::trim)
+      .filter(|entry| !entry.is_empty())
+      .map(NetworkConfig::parse)
+      .collect::<Result<_>>()?;
+
+    if networks.is_empty() {
+      return Err(Error::InvalidConfig("no networks configured".to_owned()));
+    }
+
+    Ok(Self {
+      listen_address,
+      networks,
+    })
+  }
+
+  /// Loads the [`DriverConfig`] from the environment.
+  ///
+  /// # Errors
+  ///
+  /// Fails if a configured value is invalid.
+  pub fn from_env() -> Result<Self> {
+    let listen_address: String =
+      std::env::var(ENV_LISTEN_ADDRESS).unwrap_or_else(|_| DEFAULT_LISTEN_ADDRESS.to_owned());
+    let networks: String = std::env::var(ENV_NETWORKS).unwrap_or_else(|_| DEFAULT_NETWORKS.to_owned());
+    Self::parse(&listen_address, &networks)
+  }
+
+  /// Builds a [`Resolver`] with a [`Client`] for each configured network.
+  ///
+  /// # Errors
+  ///
+  /// Fails if a [`Client`] cannot be constructed.
+  pub async fn build_resolver(&self) -> Result<Resolver> {
+    let mut builder: ResolverBuilder = ResolverBuilder::new();
+    for network in self.networks.iter() {
+      builder = builder.client_builder(network.client_builder()?);
+    }
+    Ok(builder.build().await?)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_iota_core::tangle::NetworkName;
+
+  use super::*;
+
+  #[test]
+  fn test_parse_config() {
+    let config: DriverConfig = DriverConfig::parse(
+      "127.0.0.1:8080",
+      "main, dev=http://127.0.0.1:14265|http://127.0.0.1:14266,local=http://localhost:14265",
+    )
+    .unwrap();
+    assert_eq!(config.listen_address, "127.0.0.1:8080".parse().unwrap());
+    assert_eq!(config.networks.len(), 3);
+    assert_eq!(config.networks[0].network, Network::Mainnet);
+    assert!(config.networks[0].nodes.is_empty());
+    assert_eq!(config.networks[1].network, Network::Devnet);
+    assert_eq!(
+      config.networks[1].nodes,
+      vec!["http://127.0.0.1:14265".to_owned(), "http://127.0.0.1:14266".to_owned()]
+    );
+    assert_eq!(
+      config.networks[2].network,
+      Network::Other(NetworkName::try_from("local").unwrap())
+    );
+  }
+
+  #[test]
+  fn test_parse_config_invalid() {
+    assert!(DriverConfig::parse("not-an-address", "main").is_err());
+    assert!(DriverConfig::parse("127.0.0.1:8080", "").is_err());
+    assert!(DriverConfig::parse("127.0.0.1:8080", "network-name-too-long").is_err());
+  }
+}
@@ -0,0 +1,677 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::borrow::Cow;
+use std::convert::Infallible;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::rc::Rc;
+
+use hyper::header::HeaderValue;
+use hyper::header::ACCEPT;
+use hyper::header::CONTENT_TYPE;
+use hyper::service::make_service_fn;
+use hyper::service::service_fn;
+use hyper::Body;
+use hyper::Method;
+use hyper::Request;
+use hyper::Response;
+use hyper::Server;
+use hyper::StatusCode;
+use identity_core::common::Timestamp;
+use identity_core::convert::ToJson;
+use identity_iota_client::resolution::ContentType;
+use identity_iota_client::resolution::DereferenceResult;
+use identity_iota_client::resolution::ResolutionErrorCode;
+use identity_iota_client::resolution::ResolutionOptions;
+use identity_iota_client::resolution::ResolutionResult;
+use identity_iota_core::tangle::MessageId;
+use identity_iota_core::tangle::MessageIdExt;
+use percent_encoding::percent_decode_str;
+
+use crate::error::Result;
+use crate::resolver::DriverResolver;
+
+/// The representation requested by the `Accept` header of a request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Representation {
+  /// The full DID resolution result including metadata.
+  ResolutionResult,
+  /// Only the DID document (or dereferenced resource) in the given [`ContentType`].
+  Content(ContentType),
+}
+
+impl Representation {
+  /// Selects the first supported media type of an `Accept` header value.
+  fn from_accept(accept: Option<&HeaderValue>) -> Result<Self, ResolutionErrorCode> {
+    let accept: &str = match accept.map(HeaderValue::to_str) {
+      None => return Ok(Self::ResolutionResult),
+      Some(Ok(accept)) => accept,
+      Some(Err(_)) => return Err(ResolutionErrorCode::RepresentationNotSupported),
+    };
+
+    for media_type in accept.split(',').map(str::trim) {
+      let essence: &str = media_type.split(';').next().unwrap_or_default().trim();
+      if essence == "*/*" || (essence == "application/ld+json" && media_type.contains("did-resolution")) {
+        return Ok(Self::ResolutionResult);
+      }
+      if let Ok(content_type) = essence.parse::<ContentType>() {
+        return Ok(Self::Content(content_type));
+      }
+    }
+
+    Err(ResolutionErrorCode::RepresentationNotSupported)
+  }
+}
+
+/// An HTTP driver implementing the
+/// [Universal Resolver driver interface](https://github.com/decentralized-identity/universal-resolver)
+/// for IOTA DIDs.
+///
+/// Serves `GET /1.0/identifiers/{did}` with the optional `versionId` and `versionTime` query
+/// parameters. A percent-encoded DID URL with a fragment is dereferenced to the matching
+/// verification method or service.
+pub struct Driver<R> {
+  resolver: R,
+}
+
+impl<R> Driver<R>
+where
+  R: DriverResolver + 'static,
+{
+  /// The path prefix of the identifiers endpoint.
+  pub const IDENTIFIERS_PATH: &'static str = "/1.0/identifiers/";
+
+  /// Creates a new [`Driver`] resolving DIDs with the given resolver.
+  pub fn new(resolver: R) -> Self {
+    Self { resolver }
+  }
+
+  /// Returns a reference to the underlying resolver.
+  pub fn resolver(&self) -> &R {
+    &self.resolver
+  }
+
+  /// Handles a single HTTP request.
+  pub async fn handle(&self, request: Request<Body>) -> Response<Body> {
+    if request.method() != Method::GET {
+      return plain_response(StatusCode::METHOD_NOT_ALLOWED, "method not allowed");
+    }
+
+    let identifier: Cow<'_, str> = match request.uri().path().strip_prefix(Self::IDENTIFIERS_PATH) {
+      Some(identifier) => match percent_decode_str(identifier).decode_utf8() {
+        Ok(identifier) => identifier,
+        Err(_) => return plain_response(StatusCode::BAD_REQUEST, "identifier is not valid UTF-8"),
+      },
+      None => return plain_response(StatusCode::NOT_FOUND, "not found"),
+    };
+    log::debug!("resolving `{}`", identifier);
+
+    let representation: Representation = match Representation::from_accept(request.headers().get(ACCEPT)) {
+      Ok(representation) => representation,
+      Err(code) => {
+        let result: ResolutionResult = ResolutionResult::from_error(code, "requested representation is not supported");
+        return resolution_response(&result, Representation::ResolutionResult);
+      }
+    };
+
+    let options: ResolutionOptions = match parse_options(request.uri().query(), representation) {
+      Ok(options) => options,
+      Err(message) => {
+        let result: ResolutionResult = ResolutionResult::from_error(ResolutionErrorCode::InvalidOptions, message);
+        return resolution_response(&result, Representation::ResolutionResult);
+      }
+    };
+
+    if is_did_url(&identifier) {
+      let result: DereferenceResult = self.resolver.dereference(&identifier, &options).await;
+      dereference_response(&result, representation)
+    } else {
+      let result: ResolutionResult = self.resolver.resolve(&identifier, &options).await;
+      resolution_response(&result, representation)
+    }
+  }
+
+  /// Serves HTTP requests on the given address until the server fails.
+  ///
+  /// Requests are handled on a single thread since the Tangle [`Resolver`](identity_iota_client::tangle::Resolver)
+  /// is not required to be [`Send`].
+  ///
+  /// # Errors
+  ///
+  /// Fails if the address cannot be bound or the server encounters an error.
+  pub async fn serve(self, address: SocketAddr) -> Result<()> {
+    let driver: Rc<Self> = Rc::new(self);
+    let make_service = make_service_fn(move |_| {
+      let driver: Rc<Self> = Rc::clone(&driver);
+      async move {
+        Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+          let driver: Rc<Self> = Rc::clone(&driver);
+          async move { Ok::<_, Infallible>(driver.handle(request).await) }
+        }))
+      }
+    });
+
+    let server = Server::try_bind(&address)?.executor(LocalExec).serve(make_service);
+    log::info!("listening on http://{}", address);
+
+    tokio::task::LocalSet::new().run_until(server).await?;
+    Ok(())
+  }
+}
+
+/// Executor spawning connection tasks on the current [`LocalSet`](tokio::task::LocalSet).
+#[derive(Clone, Copy, Debug)]
+struct LocalExec;
+
+impl<F> hyper::rt::Executor<F> for LocalExec
+where
+  F: Future + 'static,
+{
+  fn execute(&self, future: F) {
+    tokio::task::spawn_local(future);
+  }
+}
+
+/// Returns `true` if the identifier is a DID URL rather than a plain DID.
+fn is_did_url(identifier: &str) -> bool {
+  identifier.contains(['/', '?', '#'])
+}
+
+/// Parses the `versionId` and `versionTime` query parameters into [`ResolutionOptions`].
+fn parse_options(query: Option<&str>, representation: Representation) -> Result<ResolutionOptions, String> {
+  let mut options: ResolutionOptions = ResolutionOptions::new().accept(match representation {
+    Representation::ResolutionResult => ContentType::JsonLd,
+    Representation::Content(content_type) => content_type,
+  });
+
+  for (key, value) in form_urlencoded::parse(query.unwrap_or_default().as_bytes()) {
+    match key.as_ref() {
+      "versionId" => {
+        let version_id: MessageId =
+          MessageId::decode_hex(&value).map_err(|_| format!("invalid versionId `{}`", value))?;
+        options = options.version_id(version_id);
+      }
+      "versionTime" => {
+        let version_time: Timestamp =
+          Timestamp::parse(&value).map_err(|_| format!("invalid versionTime `{}`", value))?;
+        options = options.version_time(version_time);
+      }
+      _ => {}
+    }
+  }
+
+  Ok(options)
+}
+
+/// Returns the HTTP status code for a resolution error code.
+fn status_code(error: Option<ResolutionErrorCode>) -> StatusCode {
+  match error {
+    None => StatusCode::OK,
+    Some(ResolutionErrorCode::InvalidDid)
+    | Some(ResolutionErrorCode::InvalidDidUrl)
+    | Some(ResolutionErrorCode::InvalidOptions) => StatusCode::BAD_REQUEST,
+    Some(ResolutionErrorCode::NotFound) => StatusCode::NOT_FOUND,
+    Some(ResolutionErrorCode::RepresentationNotSupported) => StatusCode::NOT_ACCEPTABLE,
+    Some(ResolutionErrorCode::MethodNotSupported) => StatusCode::NOT_IMPLEMENTED,
+    Some(ResolutionErrorCode::InternalError) => StatusCode::INTERNAL_SERVER_ERROR,
+  }
+}
+
+fn resolution_response(result: &ResolutionResult, representation: Representation) -> Response<Body> {
+  let body: Result<(Vec<u8>, &str), identity_iota_client::Error> = match representation {
+    Representation::Content(content_type) if !result.is_error() => result
+      .document_representation()
+      .map(|body| (body, content_type.as_str())),
+    _ => result
+      .to_representation()
+      .map(|body| (body, ResolutionResult::MEDIA_TYPE)),
+  };
+  // Deactivated DIDs still return their (empty) document but are reported as gone.
+  let status: StatusCode = if result.did_document_metadata.deactivated == Some(true) {
+    StatusCode::GONE
+  } else {
+    status_code(result.error())
+  };
+  json_response(status, body)
+}
+
+fn dereference_response(result: &DereferenceResult, representation: Representation) -> Response<Body> {
+  let body: Result<(Vec<u8>, &str), identity_iota_client::Error> = match representation {
+    Representation::Content(content_type) if !result.is_error() => result
+      .content_representation()
+      .map(|body| (body, content_type.as_str())),
+    _ => result
+      .to_json_vec()
+      .map(|body| (body, ResolutionResult::MEDIA_TYPE))
+      .map_err(Into::into),
+  };
+  json_response(status_code(result.error()), body)
+}
+
+fn json_response(status: StatusCode, body: Result<(Vec<u8>, &str), identity_iota_client::Error>) -> Response<Body> {
+  match body {
+    Ok((body, content_type)) => Response::builder()
+      .status(status)
+      .header(CONTENT_TYPE, content_type)
+      .body(Body::from(body))
+      .unwrap_or_else(|_| plain_response(StatusCode::INTERNAL_SERVER_ERROR, "invalid response")),
+    Err(error) => {
+      log::error!("failed to serialize response: {}", error);
+      plain_response(StatusCode::INTERNAL_SERVER_ERROR, "internal error")
+    }
+  }
+}
+
+fn plain_response(status: StatusCode, message: &'static str) -> Response<Body> {
+  let mut response: Response<Body> = Response::new(Body::from(message));
+  *response.status_mut() = status;
+  response
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+  use std::sync::Arc;
+
+  use identity_core::common::Object;
+  use identity_core::common::Url;
+  use identity_core::common::Value;
+  use identity_core::convert::FromJson;
+  use identity_core::crypto::KeyPair;
+  use identity_core::crypto::KeyType;
+  use identity_core::json;
+  use identity_did::did::DID;
+  use identity_did::service::Service;
+  use identity_iota_client::document::ResolvedIotaDocument;
+  use identity_iota_client::tangle::Resolver;
+  use identity_iota_core::did::IotaDIDUrl;
+  #[allow(deprecated)]
+  use identity_iota_core::diff::DiffMessage;
+  use identity_iota_core::document::IotaDocument;
+  use identity_iota_core::document::IotaService;
+  use identity_iota_core::tangle::Message;
+  use identity_iota_core::tangle::Network;
+  use iota_client::bee_message::parents::Parents;
+  use iota_client::bee_message::payload::indexation::IndexationPayload;
+  use iota_client::bee_message::payload::Payload;
+  use iota_client::bee_message::MessageBuilder;
+  use iota_client::bee_rest_api::types::dtos::MessageDto;
+
+  use crate::config::DriverConfig;
+
+  use super::*;
+
+  /// An in-memory stand-in for the Tangle resolver.
+  #[derive(Default)]
+  struct MockResolver {
+    documents: HashMap<String, ResolvedIotaDocument>,
+  }
+
+  impl MockResolver {
+    fn publish(&mut self) -> IotaDocument {
+      let keypair: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+      let document: IotaDocument = IotaDocument::new(&keypair).unwrap();
+      let mut resolved: ResolvedIotaDocument = ResolvedIotaDocument::from(document.clone());
+      resolved.integration_message_id = MessageId::new([1; 32]);
+      self.documents.insert(document.id().to_string(), resolved);
+      document
+    }
+  }
+
+  #[async_trait::async_trait(?Send)]
+  impl DriverResolver for MockResolver {
+    async fn resolve(&self, did: &str, options: &ResolutionOptions) -> ResolutionResult {
+      match self.documents.get(did) {
+        Some(resolved) => ResolutionResult::from_resolved(resolved, options.accept.unwrap_or_default()),
+        None => ResolutionResult::from_error(ResolutionErrorCode::NotFound, "not found"),
+      }
+    }
+
+    async fn dereference(&self, did_url: &str, options: &ResolutionOptions) -> DereferenceResult {
+      let did_url: IotaDIDUrl = match IotaDIDUrl::parse(did_url) {
+        Ok(did_url) => did_url,
+        Err(_) => return DereferenceResult::from_error(ResolutionErrorCode::InvalidDidUrl, "invalid DID URL"),
+      };
+      match self.documents.get(did_url.did().as_str()) {
+        Some(resolved) => DereferenceResult::dereference(&did_url, resolved, options.accept.unwrap_or_default()),
+        None => DereferenceResult::from_error(ResolutionErrorCode::NotFound, "not found"),
+      }
+    }
+  }
+
+  fn get(path: &str, accept: Option<&str>) -> Request<Body> {
+    let mut builder = Request::get(path);
+    if let Some(accept) = accept {
+      builder = builder.header(ACCEPT, accept);
+    }
+    builder.body(Body::empty()).unwrap()
+  }
+
+  async fn body_json(response: Response<Body>) -> Object {
+    let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    Object::from_json_slice(&bytes).unwrap()
+  }
+
+  #[tokio::test]
+  async fn test_resolve_resolution_result() {
+    let mut resolver: MockResolver = MockResolver::default();
+    let document: IotaDocument = resolver.publish();
+    let driver: Driver<MockResolver> = Driver::new(resolver);
+
+    let response: Response<Body> = driver
+      .handle(get(&format!("/1.0/identifiers/{}", document.id()), None))
+      .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[CONTENT_TYPE], ResolutionResult::MEDIA_TYPE);
+
+    let body: Object = body_json(response).await;
+    assert_eq!(body["didDocument"]["id"], document.id().as_str());
+    assert_eq!(body["didDocument"]["@context"], ContentType::DID_CONTEXT);
+    assert_eq!(
+      body["didDocumentMetadata"]["versionId"],
+      MessageId::new([1; 32]).encode_hex()
+    );
+  }
+
+  #[tokio::test]
+  async fn test_resolve_document_representation() {
+    let mut resolver: MockResolver = MockResolver::default();
+    let document: IotaDocument = resolver.publish();
+    let driver: Driver<MockResolver> = Driver::new(resolver);
+
+    let response: Response<Body> = driver
+      .handle(get(
+        &format!("/1.0/identifiers/{}", document.id()),
+        Some("application/did+json"),
+      ))
+      .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[CONTENT_TYPE], "application/did+json");
+
+    let body: Object = body_json(response).await;
+    assert_eq!(body["id"], document.id().as_str());
+    assert!(!body.contains_key("@context"));
+  }
+
+  #[tokio::test]
+  async fn test_resolve_errors() {
+    let mut resolver: MockResolver = MockResolver::default();
+    let document: IotaDocument = resolver.publish();
+    let driver: Driver<MockResolver> = Driver::new(resolver);
+
+    let keypair: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+    let unknown: IotaDocument = IotaDocument::new(&keypair).unwrap();
+    let response: Response<Body> = driver
+      .handle(get(&format!("/1.0/identifiers/{}", unknown.id()), None))
+      .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(body_json(response).await["didResolutionMetadata"]["error"], "notFound");
+
+    let response: Response<Body> = driver
+      .handle(get(&format!("/1.0/identifiers/{}", document.id()), Some("text/html")))
+      .await;
+    assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+
+    let response: Response<Body> = driver
+      .handle(get(
+        &format!("/1.0/identifiers/{}?versionId=invalid", document.id()),
+        None,
+      ))
+      .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      body_json(response).await["didResolutionMetadata"]["error"],
+      "invalidOptions"
+    );
+
+    let response: Response<Body> = driver.handle(get("/1.0/unknown", None)).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let request: Request<Body> = Request::post(format!("/1.0/identifiers/{}", document.id()))
+      .body(Body::empty())
+      .unwrap();
+    assert_eq!(driver.handle(request).await.status(), StatusCode::METHOD_NOT_ALLOWED);
+  }
+
+  #[tokio::test]
+  async fn test_dereference_method() {
+    let mut resolver: MockResolver = MockResolver::default();
+    let document: IotaDocument = resolver.publish();
+    let driver: Driver<MockResolver> = Driver::new(resolver);
+
+    let response: Response<Body> = driver
+      .handle(get(
+        &format!(
+          "/1.0/identifiers/{}%23{}",
+          document.id(),
+          IotaDocument::DEFAULT_METHOD_FRAGMENT
+        ),
+        Some("application/did+ld+json"),
+      ))
+      .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body: Object = body_json(response).await;
+    assert_eq!(body["id"], document.default_signing_method().unwrap().id().to_string());
+    assert_eq!(body["@context"], ContentType::DID_CONTEXT);
+  }
+
+  #[test]
+  fn test_representation_from_accept() {
+    assert_eq!(
+      Representation::from_accept(None).unwrap(),
+      Representation::ResolutionResult
+    );
+    assert_eq!(
+      Representation::from_accept(Some(&HeaderValue::from_static(
+        "application/ld+json;profile=\"https://w3id.org/did-resolution\""
+      )))
+      .unwrap(),
+      Representation::ResolutionResult
+    );
+    assert_eq!(
+      Representation::from_accept(Some(&HeaderValue::from_static("text/html, application/did+ld+json"))).unwrap(),
+      Representation::Content(ContentType::JsonLd)
+    );
+    assert_eq!(
+      Representation::from_accept(Some(&HeaderValue::from_static("text/html"))).unwrap_err(),
+      ResolutionErrorCode::RepresentationNotSupported
+    );
+  }
+
+  /// A local node serving the message endpoints of the node API that are used to read DID messages.
+  #[derive(Default)]
+  struct MockNode {
+    messages: Vec<Message>,
+  }
+
+  impl MockNode {
+    /// Publishes a DID message with the given data on the index, see `pack_did_message` in
+    /// `identity_iota_client` for the message format.
+    fn publish<T: ToJson>(&mut self, index: &str, data: &T) -> MessageId {
+      let mut message_data: Vec<u8> = vec![1, b'D', b'I', b'D', 0];
+      message_data.extend(data.to_json_vec().unwrap());
+      let payload: IndexationPayload = IndexationPayload::new(index.as_bytes(), &message_data).unwrap();
+      let message: Message = MessageBuilder::<u64>::new()
+        .with_network_id(0)
+        .with_parents(Parents::new(vec![MessageId::new([0; 32])]).unwrap())
+        .with_payload(Payload::Indexation(Box::new(payload)))
+        .with_nonce_provider(0, 0.0)
+        .finish()
+        .unwrap();
+      let (message_id, _) = message.id();
+      self.messages.push(message);
+      message_id
+    }
+
+    /// Serves the node on a local port and returns its URL.
+    ///
+    /// The node runs on its own thread since the client may block the test runtime while it connects.
+    fn serve(self) -> String {
+      let listener: std::net::TcpListener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+      let url: String = format!("http://{}", listener.local_addr().unwrap());
+      let node: Arc<Self> = Arc::new(self);
+      std::thread::spawn(move || {
+        let make_service = make_service_fn(move |_| {
+          let node: Arc<Self> = Arc::clone(&node);
+          async move {
+            Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+              let response: Response<Body> = node.handle(&request);
+              async move { Ok::<_, Infallible>(response) }
+            }))
+          }
+        });
+        let runtime: tokio::runtime::Runtime = tokio::runtime::Builder::new_current_thread()
+          .enable_all()
+          .build()
+          .unwrap();
+        runtime.block_on(async move { Server::from_tcp(listener).unwrap().serve(make_service).await })
+      });
+      url
+    }
+
+    fn handle(&self, request: &Request<Body>) -> Response<Body> {
+      let path: Vec<&str> = request.uri().path().trim_matches('/').split('/').collect();
+      let body: Value = match path.as_slice() {
+        ["api", "v1", "messages"] => {
+          let index: String = form_urlencoded::parse(request.uri().query().unwrap_or_default().as_bytes())
+            .find(|(key, _)| key == "index")
+            .map(|(_, index)| index.into_owned())
+            .unwrap_or_default();
+          let message_ids: Vec<String> = self
+            .messages
+            .iter()
+            .filter(|message| match message.payload() {
+              Some(Payload::Indexation(payload)) => encode_hex(payload.index()) == index,
+              _ => false,
+            })
+            .map(|message| message.id().0.encode_hex())
+            .collect();
+          json!({
+            "index": index,
+            "maxResults": 1000,
+            "count": message_ids.len(),
+            "messageIds": message_ids,
+          })
+        }
+        ["api", "v1", "messages", message_id] => {
+          match self
+            .messages
+            .iter()
+            .find(|message| message.id().0.encode_hex() == *message_id)
+          {
+            Some(message) => json!(MessageDto::from(message)),
+            None => return plain_response(StatusCode::NOT_FOUND, "message not found"),
+          }
+        }
+        _ => return plain_response(StatusCode::NOT_FOUND, "not found"),
+      };
+      Response::new(Body::from(json!({ "data": body }).to_json_vec().unwrap()))
+    }
+  }
+
+  fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+  }
+
+  fn generate_service(document: &IotaDocument, fragment: &str) -> IotaService {
+    Service::builder(Object::new())
+      .id(document.id().to_url().join(fragment).unwrap())
+      .type_("LinkedDomains")
+      .service_endpoint(Url::parse("https://iota.org").unwrap())
+      .build()
+      .unwrap()
+  }
+
+  #[tokio::test]
+  #[allow(deprecated)]
+  async fn test_resolve_versions_from_mock_node() {
+    let mut node: MockNode = MockNode::default();
+    let keypair: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+
+    // Publish two integration chain messages followed by a diff chain message.
+    let mut document: IotaDocument =
+      IotaDocument::new_with_options(&keypair, Some(Network::Devnet.name()), None).unwrap();
+    let did: String = document.id().to_string();
+    let method: IotaDIDUrl = document.default_signing_method().unwrap().id().clone();
+    document.metadata.updated = Some(Timestamp::parse("2022-01-01T00:00:00Z").unwrap());
+    document.sign_self(keypair.private(), &method).unwrap();
+    let first_id: MessageId = node.publish(document.integration_index(), &document);
+
+    document.metadata.previous_message_id = first_id;
+    document.metadata.updated = Some(Timestamp::parse("2022-02-01T00:00:00Z").unwrap());
+    assert!(document.insert_service(generate_service(&document, "#linked-domain")));
+    document.sign_self(keypair.private(), &method).unwrap();
+    let second_id: MessageId = node.publish(document.integration_index(), &document);
+
+    let mut updated: IotaDocument = document.clone();
+    updated.metadata.updated = Some(Timestamp::parse("2022-03-01T00:00:00Z").unwrap());
+    assert!(updated.insert_service(generate_service(&updated, "#linked-domain-2")));
+    let diff: DiffMessage = document.diff(&updated, second_id, keypair.private(), &method).unwrap();
+    let diff_id: MessageId = node.publish(&IotaDocument::diff_index(&second_id).unwrap(), &diff);
+
+    let config: DriverConfig = DriverConfig::parse("127.0.0.1:0", &format!("dev={}", node.serve())).unwrap();
+    let resolver: Resolver = config.build_resolver().await.unwrap();
+    let driver: Driver<Resolver> = Driver::new(resolver);
+
+    // The latest version includes the diff.
+    let response: Response<Body> = driver.handle(get(&format!("/1.0/identifiers/{}", did), None)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Object = body_json(response).await;
+    assert_eq!(body["didDocumentMetadata"]["versionId"], diff_id.encode_hex());
+    assert_eq!(body["didDocument"]["service"].as_array().unwrap().len(), 2);
+
+    let response: Response<Body> = driver
+      .handle(get(
+        &format!("/1.0/identifiers/{}?versionId={}", did, first_id.encode_hex()),
+        None,
+      ))
+      .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Object = body_json(response).await;
+    assert_eq!(body["didDocumentMetadata"]["versionId"], first_id.encode_hex());
+    assert_eq!(body["didDocumentMetadata"]["nextVersionId"], second_id.encode_hex());
+    assert_eq!(body["didDocumentMetadata"]["nextUpdate"], "2022-02-01T00:00:00Z");
+    assert!(body["didDocument"].get("service").is_none());
+
+    let response: Response<Body> = driver
+      .handle(get(
+        &format!("/1.0/identifiers/{}?versionTime=2022-02-15T00:00:00Z", did),
+        None,
+      ))
+      .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Object = body_json(response).await;
+    assert_eq!(body["didDocumentMetadata"]["versionId"], second_id.encode_hex());
+    assert_eq!(body["didDocumentMetadata"]["nextVersionId"], diff_id.encode_hex());
+    assert_eq!(body["didDocument"]["service"].as_array().unwrap().len(), 1);
+
+    let response: Response<Body> = driver
+      .handle(get(
+        &format!("/1.0/identifiers/{}?versionTime=2021-01-01T00:00:00Z", did),
+        None,
+      ))
+      .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // Dereferencing a DID URL selects the requested version as well.
+    let service_path: String = format!("/1.0/identifiers/{}%23linked-domain", did);
+    let response: Response<Body> = driver
+      .handle(get(
+        &format!("{}?versionId={}", service_path, first_id.encode_hex()),
+        None,
+      ))
+      .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response: Response<Body> = driver
+      .handle(get(
+        &format!("{}?versionId={}", service_path, second_id.encode_hex()),
+        Some("application/did+json"),
+      ))
+      .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(body_json(response).await["id"], format!("{}#linked-domain", did));
+  }
+}
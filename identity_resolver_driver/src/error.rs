@@ -0,0 +1,22 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+/// Alias for a `Result` with the error type [`Error`].
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+/// Errors that may occur when configuring or running the resolver driver.
+#[derive(Debug, thiserror::Error, strum::IntoStaticStr)]
+pub enum Error {
+  /// Caused by an invalid driver configuration.
+  #[error("invalid configuration: {0}")]
+  InvalidConfig(String),
+  /// Caused by a failure to set up a Tangle client.
+  #[error("{0}")]
+  ClientError(#[from] identity_iota_client::Error),
+  /// Caused by an invalid network name.
+  #[error("{0}")]
+  IotaCoreError(#[from] identity_iota_core::Error),
+  /// Caused by a failure of the HTTP server.
+  #[error("server error: {0}")]
+  ServerError(#[from] hyper::Error),
+}
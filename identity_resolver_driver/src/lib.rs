@@ -0,0 +1,28 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+#![doc = include_str!("./../README.md")]
+#![warn(
+  rust_2018_idioms,
+  unreachable_pub,
+  missing_docs,
+  rustdoc::missing_crate_level_docs,
+  rustdoc::broken_intra_doc_links,
+  rustdoc::private_intra_doc_links,
+  rustdoc::private_doc_tests,
+  clippy::missing_safety_doc,
+  clippy::missing_errors_doc
+)]
+
+pub use self::config::DriverConfig;
+pub use self::config::NetworkConfig;
+pub use self::driver::Driver;
+pub use self::error::Error;
+pub use self::error::Result;
+pub use self::resolver::DriverResolver;
+
+mod config;
+mod driver;
+mod error;
+mod resolver;
@@ -0,0 +1,17 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_iota_client::tangle::Resolver;
+use identity_resolver_driver::Driver;
+use identity_resolver_driver::DriverConfig;
+use identity_resolver_driver::Result;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<()> {
+  pretty_env_logger::init();
+
+  let config: DriverConfig = DriverConfig::from_env()?;
+  let resolver: Resolver = config.build_resolver().await?;
+
+  Driver::new(resolver).serve(config.listen_address).await
+}
@@ -0,0 +1,36 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_iota_client::resolution::DereferenceResult;
+use identity_iota_client::resolution::ResolutionOptions;
+use identity_iota_client::resolution::ResolutionResult;
+use identity_iota_client::tangle::Client;
+use identity_iota_client::tangle::Resolver;
+use identity_iota_client::tangle::SharedPtr;
+
+/// Resolution backend of a [`Driver`](crate::Driver).
+///
+/// Implemented for the Tangle [`Resolver`], but can be replaced by an in-memory implementation
+/// to test the driver without network access.
+#[async_trait::async_trait(?Send)]
+pub trait DriverResolver {
+  /// Resolves a DID into a [`ResolutionResult`].
+  async fn resolve(&self, did: &str, options: &ResolutionOptions) -> ResolutionResult;
+
+  /// Dereferences a DID URL into a [`DereferenceResult`].
+  async fn dereference(&self, did_url: &str, options: &ResolutionOptions) -> DereferenceResult;
+}
+
+#[async_trait::async_trait(?Send)]
+impl<C> DriverResolver for Resolver<C>
+where
+  C: SharedPtr<Client>,
+{
+  async fn resolve(&self, did: &str, options: &ResolutionOptions) -> ResolutionResult {
+    self.resolve_result(did, options).await
+  }
+
+  async fn dereference(&self, did_url: &str, options: &ResolutionOptions) -> DereferenceResult {
+    Resolver::dereference(self, did_url, options).await
+  }
+}
@@ -0,0 +1,100 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+
+use identity_core::crypto::KeyPair;
+use identity_core::crypto::KeyType;
+use identity_did::verification::MethodScope;
+use identity_stardust::StardustDID;
+use identity_stardust::StardustDocument;
+use identity_stardust::StardustVerificationMethod;
+use identity_stardust::StateMetadataEncoding;
+
+static METHODS: &[usize] = &[1, 10, 50];
+
+static ENCODINGS: &[StateMetadataEncoding] = &[
+  StateMetadataEncoding::Json,
+  StateMetadataEncoding::JsonBrotli,
+  StateMetadataEncoding::Cbor,
+];
+
+fn did() -> StardustDID {
+  StardustDID::parse("did:stardust:0x8036235b6b5939435a45d68bcea7890eef399209a669c8c263fac7f5089b2ec6").unwrap()
+}
+
+fn generate_document(methods: usize) -> StardustDocument {
+  let mut document: StardustDocument = StardustDocument::new_with_id(did());
+  for index in 0..methods {
+    let keypair: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+    let method: StardustVerificationMethod = StardustVerificationMethod::new(
+      document.id().clone(),
+      keypair.type_(),
+      keypair.public(),
+      &format!("key-{index}"),
+    )
+    .unwrap();
+    document.insert_method(method, MethodScope::VerificationMethod).unwrap();
+  }
+  document
+}
+
+/// Prints the packed size of each encoding relative to JSON, since criterion only measures time.
+fn report_sizes() {
+  for methods in METHODS.iter() {
+    let document: StardustDocument = generate_document(*methods);
+    let json: usize = document
+      .clone()
+      .pack_with_encoding(StateMetadataEncoding::Json)
+      .unwrap()
+      .len();
+    for encoding in ENCODINGS.iter() {
+      let size: usize = document.clone().pack_with_encoding(*encoding).unwrap().len();
+      println!(
+        "state metadata size/{encoding:?}/{methods} methods: {size} bytes ({:.1}% of JSON)",
+        size as f64 * 100.0 / json as f64
+      );
+    }
+  }
+}
+
+fn bench_pack(c: &mut Criterion) {
+  report_sizes();
+
+  let mut group = c.benchmark_group("pack state metadata");
+  for methods in METHODS.iter() {
+    let document: StardustDocument = generate_document(*methods);
+    for encoding in ENCODINGS.iter() {
+      group.bench_with_input(
+        BenchmarkId::new(format!("{encoding:?}"), methods),
+        &document,
+        |b, document| b.iter(|| document.clone().pack_with_encoding(*encoding).unwrap()),
+      );
+    }
+  }
+  group.finish();
+}
+
+fn bench_unpack(c: &mut Criterion) {
+  let did: StardustDID = did();
+
+  let mut group = c.benchmark_group("unpack state metadata");
+  for methods in METHODS.iter() {
+    let document: StardustDocument = generate_document(*methods);
+    for encoding in ENCODINGS.iter() {
+      let packed: Vec<u8> = document.clone().pack_with_encoding(*encoding).unwrap();
+      group.bench_with_input(
+        BenchmarkId::new(format!("{encoding:?}"), methods),
+        &packed,
+        |b, packed| b.iter(|| StardustDocument::unpack(&did, packed).unwrap()),
+      );
+    }
+  }
+  group.finish();
+}
+
+criterion_group!(benches, bench_pack, bench_unpack);
+criterion_main!(benches);
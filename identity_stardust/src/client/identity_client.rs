@@ -0,0 +1,368 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::ops::Deref;
+use core::str::FromStr;
+
+use identity_did::did::DID;
+use iota_client::block::address::Address;
+use iota_client::block::output::unlock_condition::AddressUnlockCondition;
+use iota_client::block::output::unlock_condition::GovernorAddressUnlockCondition;
+use iota_client::block::output::unlock_condition::StateControllerAddressUnlockCondition;
+use iota_client::block::output::unlock_condition::UnlockCondition;
+use iota_client::block::output::AliasId;
+use iota_client::block::output::AliasOutput;
+use iota_client::block::output::AliasOutputBuilder;
+use iota_client::block::output::BasicOutput;
+use iota_client::block::output::BasicOutputBuilder;
+use iota_client::block::output::ByteCostConfig;
+use iota_client::block::output::Output;
+use iota_client::block::output::OutputId;
+
+use crate::error::Result;
+use crate::Error;
+use crate::NetworkName;
+use crate::StardustDID;
+use crate::StardustDocument;
+use crate::StardustNodeClient;
+
+/// Creates, updates, destroys and resolves DID documents stored in the state metadata of
+/// Alias Outputs, using a [`StardustNodeClient`] to interact with the ledger.
+#[derive(Debug)]
+pub struct StardustIdentityClient<C> {
+  client: C,
+}
+
+impl<C> StardustIdentityClient<C>
+where
+  C: StardustNodeClient,
+{
+  /// Creates a new [`StardustIdentityClient`] using the given [`StardustNodeClient`].
+  pub fn new(client: C) -> Self {
+    Self { client }
+  }
+
+  /// Returns a reference to the underlying [`StardustNodeClient`].
+  pub fn node_client(&self) -> &C {
+    &self.client
+  }
+
+  // ===========================================================================
+  // Output Construction
+  // ===========================================================================
+
+  /// Builds a new Alias Output containing the given `document`, with the minimum storage deposit
+  /// as its amount.
+  ///
+  /// The `address` is set as both the state controller, which may update the document, and the
+  /// governor, which may destroy the output.
+  ///
+  /// NOTE: the `document` should use a [`StardustDID::placeholder`] identifier, the DID is
+  /// derived from the Alias ID once the output is published.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the document cannot be packed or the output is invalid.
+  pub async fn new_did_output(&self, address: Address, document: StardustDocument) -> Result<AliasOutput> {
+    let byte_cost_config: ByteCostConfig = self.client.byte_cost_config().await?;
+    AliasOutputBuilder::new_with_minimum_storage_deposit(byte_cost_config, AliasId::null())?
+      .with_state_index(0)
+      .with_foundry_counter(0)
+      .with_state_metadata(document.pack()?)
+      .add_unlock_condition(UnlockCondition::StateControllerAddress(
+        StateControllerAddressUnlockCondition::new(address),
+      ))
+      .add_unlock_condition(UnlockCondition::GovernorAddress(GovernorAddressUnlockCondition::new(
+        address,
+      )))
+      .finish()
+      .map_err(Into::into)
+  }
+
+  /// Builds the state transition of the Alias Output of the `document` that replaces the
+  /// published document with the given one.
+  ///
+  /// The amount is raised to the minimum storage deposit if the updated document requires it,
+  /// otherwise the current amount is kept.
+  ///
+  /// Returns the [`OutputId`] of the current Alias Output, which must be consumed, alongside the
+  /// updated Alias Output.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the document has a placeholder identifier or its Alias Output cannot be resolved.
+  pub async fn update_did_output(&self, document: StardustDocument) -> Result<(OutputId, AliasOutput)> {
+    let alias_id: AliasId = self.alias_id(document.id())?;
+    if alias_id == AliasId::null() {
+      return Err(Error::DIDUpdateError("cannot update a document with a placeholder DID"));
+    }
+
+    let (output_id, current): (OutputId, AliasOutput) = self.client.get_alias_output(alias_id).await?;
+    let byte_cost_config: ByteCostConfig = self.client.byte_cost_config().await?;
+
+    let builder: AliasOutputBuilder = AliasOutputBuilder::from(&current)
+      // The Alias ID is only explicit after the first state transition.
+      .with_alias_id(alias_id)
+      .with_state_index(current.state_index() + 1)
+      .with_state_metadata(document.pack()?);
+
+    Ok((output_id, finish_with_storage_deposit(builder, byte_cost_config)?))
+  }
+
+  /// Builds a Basic Output transferring the amount of the Alias Output of the `did` to the
+  /// given `address`, destroying the Alias Output and the DID document it contains.
+  ///
+  /// Returns the [`OutputId`] of the Alias Output, which must be consumed, alongside the new
+  /// Basic Output.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the Alias Output of the `did` cannot be resolved.
+  pub async fn delete_did_output(&self, address: Address, did: &StardustDID) -> Result<(OutputId, BasicOutput)> {
+    let (output_id, alias_output): (OutputId, AliasOutput) = self.resolve_did_output(did).await?;
+    let basic_output: BasicOutput = BasicOutputBuilder::new_with_amount(alias_output.amount())?
+      .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(address)))
+      .finish()?;
+    Ok((output_id, basic_output))
+  }
+
+  // ===========================================================================
+  // Publishing
+  // ===========================================================================
+
+  /// Publishes a new Alias Output created with [`StardustIdentityClient::new_did_output`] and
+  /// returns the published document with its DID derived from the new Alias ID.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the transaction cannot be published.
+  pub async fn publish_did_output(&self, alias_output: AliasOutput) -> Result<StardustDocument> {
+    let network_name: NetworkName = self.client.network_name().await?;
+    let output_id: OutputId = self.publish_single(Vec::new(), alias_output.clone()).await?;
+
+    let alias_id: AliasId = if alias_output.alias_id() == &AliasId::null() {
+      AliasId::from(output_id)
+    } else {
+      *alias_output.alias_id()
+    };
+    let did: StardustDID = StardustDID::new(alias_id.deref(), &network_name);
+    StardustDocument::unpack(&did, alias_output.state_metadata())
+  }
+
+  /// Publishes the given `document` as a state transition of its Alias Output.
+  ///
+  /// See [`StardustIdentityClient::update_did_output`].
+  ///
+  /// # Errors
+  ///
+  /// Fails if the output cannot be built or the transaction cannot be published.
+  pub async fn publish_did_update(&self, document: StardustDocument) -> Result<StardustDocument> {
+    let did: StardustDID = document.id().clone();
+    let (input, alias_output): (OutputId, AliasOutput) = self.update_did_output(document).await?;
+    self.publish_single(vec![input], alias_output.clone()).await?;
+    StardustDocument::unpack(&did, alias_output.state_metadata())
+  }
+
+  /// Destroys the Alias Output of the `did`, transferring its amount to the `address`.
+  ///
+  /// WARNING: this permanently removes the DID document from the ledger, it cannot be resolved
+  /// or recovered afterwards.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the output cannot be built or the transaction cannot be published.
+  pub async fn delete_did(&self, address: Address, did: &StardustDID) -> Result<()> {
+    let (input, basic_output): (OutputId, BasicOutput) = self.delete_did_output(address, did).await?;
+    self
+      .client
+      .publish_transaction(vec![input], vec![Output::Basic(basic_output)])
+      .await?;
+    Ok(())
+  }
+
+  // ===========================================================================
+  // Resolution
+  // ===========================================================================
+
+  /// Resolves the DID document of the given `did` from the state metadata of its Alias Output.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the `did` belongs to another network, or its Alias Output cannot be resolved or
+  /// does not contain a valid document.
+  pub async fn resolve_did(&self, did: &StardustDID) -> Result<StardustDocument> {
+    let (_, alias_output): (OutputId, AliasOutput) = self.resolve_did_output(did).await?;
+    StardustDocument::unpack(did, alias_output.state_metadata())
+  }
+
+  /// Resolves the [`OutputId`] and the latest Alias Output of the given `did`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the `did` belongs to another network or its Alias Output cannot be resolved.
+  pub async fn resolve_did_output(&self, did: &StardustDID) -> Result<(OutputId, AliasOutput)> {
+    let network_name: NetworkName = self.client.network_name().await?;
+    if did.network_str() != network_name.as_ref() {
+      return Err(Error::DIDResolutionError(
+        "DID network does not match the client network",
+      ));
+    }
+    let alias_id: AliasId = self.alias_id(did)?;
+    self.client.get_alias_output(alias_id).await
+  }
+
+  fn alias_id(&self, did: &StardustDID) -> Result<AliasId> {
+    AliasId::from_str(did.tag()).map_err(Into::into)
+  }
+
+  async fn publish_single(&self, inputs: Vec<OutputId>, alias_output: AliasOutput) -> Result<OutputId> {
+    self
+      .client
+      .publish_transaction(inputs, vec![Output::Alias(alias_output)])
+      .await?
+      .into_iter()
+      .next()
+      .ok_or(Error::PublishError("missing output id of the published alias output"))
+  }
+}
+
+/// Finishes the Alias Output with the current amount of the `builder`, or the minimum storage
+/// deposit if the current amount does not cover it.
+fn finish_with_storage_deposit(builder: AliasOutputBuilder, byte_cost_config: ByteCostConfig) -> Result<AliasOutput> {
+  let current: AliasOutput = builder.clone().finish()?;
+  let minimum: AliasOutput = builder.with_minimum_storage_deposit(byte_cost_config).finish()?;
+  if minimum.amount() > current.amount() {
+    Ok(minimum)
+  } else {
+    Ok(current)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Object;
+  use identity_core::common::Url;
+  use identity_core::crypto::KeyPair;
+  use identity_core::crypto::KeyType;
+  use identity_did::document::Document;
+  use identity_did::verification::MethodScope;
+  use iota_client::block::address::Ed25519Address;
+
+  use crate::MemLedger;
+  use crate::StardustService;
+  use crate::StardustVerificationMethod;
+
+  use super::*;
+
+  fn test_client() -> StardustIdentityClient<MemLedger> {
+    StardustIdentityClient::new(MemLedger::new(NetworkName::try_from("smr").unwrap()))
+  }
+
+  fn test_address() -> Address {
+    Address::Ed25519(Ed25519Address::new([7; 32]))
+  }
+
+  async fn publish_new_document(client: &StardustIdentityClient<MemLedger>) -> StardustDocument {
+    let network_name: NetworkName = client.node_client().network_name().await.unwrap();
+    let mut document: StardustDocument = StardustDocument::new(&network_name);
+    let keypair: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+    let method: StardustVerificationMethod =
+      StardustVerificationMethod::new(document.id().clone(), keypair.type_(), keypair.public(), "#key-0").unwrap();
+    document.insert_method(method, MethodScope::VerificationMethod).unwrap();
+
+    let alias_output: AliasOutput = client.new_did_output(test_address(), document).await.unwrap();
+    client.publish_did_output(alias_output).await.unwrap()
+  }
+
+  #[tokio::test]
+  async fn test_create_and_resolve() {
+    let client = test_client();
+    let document: StardustDocument = publish_new_document(&client).await;
+
+    let network_name: NetworkName = client.node_client().network_name().await.unwrap();
+    assert_ne!(document.id(), &StardustDID::placeholder(&network_name));
+    assert!(document.resolve_method("#key-0", None).is_some());
+
+    let resolved: StardustDocument = client.resolve_did(document.id()).await.unwrap();
+    assert_eq!(resolved, document);
+
+    let (_, alias_output): (OutputId, AliasOutput) = client.resolve_did_output(document.id()).await.unwrap();
+    assert_eq!(alias_output.state_index(), 0);
+  }
+
+  #[tokio::test]
+  async fn test_update() {
+    let client = test_client();
+    let mut document: StardustDocument = publish_new_document(&client).await;
+    let (_, initial_output): (OutputId, AliasOutput) = client.resolve_did_output(document.id()).await.unwrap();
+
+    // Growing the document increases the required storage deposit.
+    for index in 0..10 {
+      assert!(document.insert_service(
+        StardustService::builder(Object::new())
+          .id(document.id().to_url().join(format!("#service-{}", index)).unwrap())
+          .type_("LinkedDomains")
+          .service_endpoint(Url::parse("https://example.com/").unwrap())
+          .build()
+          .unwrap()
+      ));
+    }
+    let updated: StardustDocument = client.publish_did_update(document.clone()).await.unwrap();
+    assert_eq!(updated, document);
+
+    let (_, updated_output): (OutputId, AliasOutput) = client.resolve_did_output(document.id()).await.unwrap();
+    assert_eq!(updated_output.state_index(), 1);
+    assert_eq!(
+      updated_output.alias_id(),
+      &AliasId::from_str(document.id().tag()).unwrap()
+    );
+    assert!(updated_output.amount() > initial_output.amount());
+    assert_eq!(client.resolve_did(document.id()).await.unwrap(), document);
+
+    // Shrinking the document keeps the current amount.
+    document.core_document_mut().service_mut().clear();
+    client.publish_did_update(document.clone()).await.unwrap();
+    let (_, shrunk_output): (OutputId, AliasOutput) = client.resolve_did_output(document.id()).await.unwrap();
+    assert_eq!(shrunk_output.state_index(), 2);
+    assert_eq!(shrunk_output.amount(), updated_output.amount());
+  }
+
+  #[tokio::test]
+  async fn test_update_placeholder_fails() {
+    let client = test_client();
+    let network_name: NetworkName = client.node_client().network_name().await.unwrap();
+    let document: StardustDocument = StardustDocument::new(&network_name);
+    assert!(matches!(
+      client.update_did_output(document).await.unwrap_err(),
+      Error::DIDUpdateError(_)
+    ));
+  }
+
+  #[tokio::test]
+  async fn test_delete() {
+    let client = test_client();
+    let document: StardustDocument = publish_new_document(&client).await;
+    let (_, alias_output): (OutputId, AliasOutput) = client.resolve_did_output(document.id()).await.unwrap();
+
+    let (input, basic_output): (OutputId, BasicOutput) =
+      client.delete_did_output(test_address(), document.id()).await.unwrap();
+    assert_eq!(basic_output.amount(), alias_output.amount());
+
+    client.delete_did(test_address(), document.id()).await.unwrap();
+    assert!(client.node_client().get_output(&input).is_none());
+    assert!(matches!(
+      client.resolve_did(document.id()).await.unwrap_err(),
+      Error::DIDResolutionError(_)
+    ));
+  }
+
+  #[tokio::test]
+  async fn test_resolve_other_network_fails() {
+    let client = test_client();
+    let did: StardustDID = StardustDID::new(&[1; 32], &NetworkName::try_from("rms").unwrap());
+    assert!(matches!(
+      client.resolve_did(&did).await.unwrap_err(),
+      Error::DIDResolutionError(_)
+    ));
+  }
+}
@@ -0,0 +1,112 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::block::output::AliasId;
+use iota_client::block::output::AliasOutput;
+use iota_client::block::output::ByteCostConfig;
+use iota_client::block::output::Output;
+use iota_client::block::output::OutputId;
+use iota_client::block::payload::transaction::TransactionEssence;
+use iota_client::block::payload::Payload;
+use iota_client::block::Block;
+use iota_client::secret::SecretManager;
+use iota_client::Client;
+
+use crate::error::Result;
+use crate::Error;
+use crate::NetworkName;
+use crate::StardustNodeClient;
+
+/// A [`StardustNodeClient`] publishing and resolving Alias Outputs through an IOTA node.
+///
+/// Transactions are signed and funded with the [`SecretManager`], which is only required
+/// for publishing.
+pub struct IotaNodeClient<'client> {
+  client: &'client Client,
+  secret_manager: Option<&'client SecretManager>,
+}
+
+impl<'client> IotaNodeClient<'client> {
+  /// Creates a new [`IotaNodeClient`] able to resolve DID documents.
+  pub fn new(client: &'client Client) -> Self {
+    Self {
+      client,
+      secret_manager: None,
+    }
+  }
+
+  /// Sets the [`SecretManager`] used to sign and fund published transactions.
+  #[must_use]
+  pub fn secret_manager(mut self, secret_manager: &'client SecretManager) -> Self {
+    self.secret_manager = Some(secret_manager);
+    self
+  }
+
+  /// Returns a reference to the underlying [`Client`].
+  pub fn client(&self) -> &Client {
+    self.client
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl StardustNodeClient for IotaNodeClient<'_> {
+  async fn network_name(&self) -> Result<NetworkName> {
+    let network_hrp: String = self.client.get_info().await?.node_info.protocol.bech32_hrp;
+    NetworkName::try_from(network_hrp)
+  }
+
+  async fn byte_cost_config(&self) -> Result<ByteCostConfig> {
+    self.client.get_byte_cost_config().await.map_err(Into::into)
+  }
+
+  async fn get_alias_output(&self, alias_id: AliasId) -> Result<(OutputId, AliasOutput)> {
+    let output_id: OutputId = self.client.alias_output_id(alias_id).await?;
+    let response = self.client.get_output(&output_id).await?;
+    match Output::try_from(&response.output)? {
+      Output::Alias(alias_output) => Ok((output_id, alias_output)),
+      _ => Err(Error::DIDResolutionError("not an alias output")),
+    }
+  }
+
+  async fn publish_transaction(&self, inputs: Vec<OutputId>, outputs: Vec<Output>) -> Result<Vec<OutputId>> {
+    let secret_manager: &SecretManager = self
+      .secret_manager
+      .ok_or(Error::PublishError("missing secret manager"))?;
+
+    let mut builder = self.client.block().with_secret_manager(secret_manager);
+    for input in inputs {
+      builder = builder.with_input(input.into())?;
+    }
+    let block: Block = builder.with_outputs(outputs.clone())?.finish().await?;
+    let _ = self.client.retry_until_included(&block.id(), None, None).await?;
+
+    created_output_ids(&block, &outputs)
+  }
+}
+
+/// Returns the [`OutputId`]s of the `outputs` created by the transaction in the `block`.
+///
+/// Outputs are matched by value since the transaction may contain additional remainder outputs
+/// in any position.
+fn created_output_ids(block: &Block, outputs: &[Output]) -> Result<Vec<OutputId>> {
+  let transaction = match block.payload() {
+    Some(Payload::Transaction(transaction)) => transaction,
+    _ => return Err(Error::PublishError("block does not contain a transaction payload")),
+  };
+  let TransactionEssence::Regular(essence) = transaction.essence();
+
+  outputs
+    .iter()
+    .map(|output| {
+      let index: usize = essence
+        .outputs()
+        .iter()
+        .position(|created| created == output)
+        .ok_or(Error::PublishError("output missing from the published transaction"))?;
+      let index: u16 = index
+        .try_into()
+        .map_err(|_| Error::PublishError("output index exceeds u16"))?;
+      OutputId::new(transaction.id(), index).map_err(Into::into)
+    })
+    .collect()
+}
@@ -0,0 +1,167 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+
+use iota_client::block::output::AliasId;
+use iota_client::block::output::AliasOutput;
+use iota_client::block::output::AliasOutputBuilder;
+use iota_client::block::output::ByteCostConfig;
+use iota_client::block::output::ByteCostConfigBuilder;
+use iota_client::block::output::Output;
+use iota_client::block::output::OutputId;
+use iota_client::block::payload::transaction::TransactionId;
+
+use crate::error::Result;
+use crate::Error;
+use crate::NetworkName;
+use crate::StardustNodeClient;
+
+/// An in-memory stand-in for the ledger, intended for testing.
+///
+/// Transactions are applied immediately without signature or balance checks. Alias Outputs
+/// are checked for a sufficient storage deposit and a valid state index.
+#[derive(Debug)]
+pub struct MemLedger {
+  network_name: NetworkName,
+  byte_cost_config: ByteCostConfig,
+  state: Mutex<LedgerState>,
+}
+
+#[derive(Debug, Default)]
+struct LedgerState {
+  transaction_count: u64,
+  unspent: HashMap<OutputId, Output>,
+  aliases: HashMap<AliasId, OutputId>,
+}
+
+impl MemLedger {
+  /// Creates an empty [`MemLedger`] for the given network with the default [`ByteCostConfig`].
+  pub fn new(network_name: NetworkName) -> Self {
+    Self::new_with_byte_cost_config(network_name, ByteCostConfigBuilder::new().finish())
+  }
+
+  /// Creates an empty [`MemLedger`] for the given network and [`ByteCostConfig`].
+  pub fn new_with_byte_cost_config(network_name: NetworkName, byte_cost_config: ByteCostConfig) -> Self {
+    Self {
+      network_name,
+      byte_cost_config,
+      state: Mutex::new(LedgerState::default()),
+    }
+  }
+
+  /// Returns the unspent [`Output`] with the given [`OutputId`], if any.
+  pub fn get_output(&self, output_id: &OutputId) -> Option<Output> {
+    self.state().ok()?.unspent.get(output_id).cloned()
+  }
+
+  fn state(&self) -> Result<MutexGuard<'_, LedgerState>> {
+    self
+      .state
+      .lock()
+      .map_err(|_| Error::PublishError("ledger lock poisoned"))
+  }
+
+  fn check_alias_output(&self, state: &LedgerState, inputs: &[OutputId], alias_output: &AliasOutput) -> Result<()> {
+    let minimum_amount: u64 = AliasOutputBuilder::from(alias_output)
+      .with_minimum_storage_deposit(self.byte_cost_config.clone())
+      .finish()?
+      .amount();
+    if alias_output.amount() < minimum_amount {
+      return Err(Error::PublishError("insufficient storage deposit"));
+    }
+
+    if alias_output.alias_id() == &AliasId::null() {
+      if alias_output.state_index() != 0 {
+        return Err(Error::PublishError("new alias output must have state index 0"));
+      }
+      return Ok(());
+    }
+
+    // Only state transitions are supported, which must consume the previous alias output.
+    let previous: &AliasOutput = state
+      .aliases
+      .get(alias_output.alias_id())
+      .filter(|output_id| inputs.contains(output_id))
+      .and_then(|output_id| state.unspent.get(output_id))
+      .and_then(|output| match output {
+        Output::Alias(previous) => Some(previous),
+        _ => None,
+      })
+      .ok_or(Error::PublishError("previous alias output is not consumed"))?;
+    if alias_output.state_index() != previous.state_index() + 1 {
+      return Err(Error::PublishError("invalid state index"));
+    }
+
+    Ok(())
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl StardustNodeClient for MemLedger {
+  async fn network_name(&self) -> Result<NetworkName> {
+    Ok(self.network_name.clone())
+  }
+
+  async fn byte_cost_config(&self) -> Result<ByteCostConfig> {
+    Ok(self.byte_cost_config.clone())
+  }
+
+  async fn get_alias_output(&self, alias_id: AliasId) -> Result<(OutputId, AliasOutput)> {
+    let state = self.state()?;
+    let output_id: OutputId = *state
+      .aliases
+      .get(&alias_id)
+      .ok_or(Error::DIDResolutionError("alias output not found"))?;
+    match state.unspent.get(&output_id) {
+      Some(Output::Alias(alias_output)) => Ok((output_id, alias_output.clone())),
+      _ => Err(Error::DIDResolutionError("alias output not found")),
+    }
+  }
+
+  async fn publish_transaction(&self, inputs: Vec<OutputId>, outputs: Vec<Output>) -> Result<Vec<OutputId>> {
+    let mut state = self.state()?;
+
+    if inputs.iter().any(|input| !state.unspent.contains_key(input)) {
+      return Err(Error::PublishError("input not found or already spent"));
+    }
+    for output in outputs.iter() {
+      if let Output::Alias(alias_output) = output {
+        self.check_alias_output(&state, &inputs, alias_output)?;
+      }
+    }
+
+    // Consume the inputs, destroying any alias outputs that are not transitioned.
+    for input in inputs.iter() {
+      state.unspent.remove(input);
+    }
+    state.aliases.retain(|_, output_id| !inputs.contains(output_id));
+
+    state.transaction_count += 1;
+    let mut transaction_id: [u8; 32] = [0; 32];
+    transaction_id[..8].copy_from_slice(&state.transaction_count.to_be_bytes());
+    let transaction_id: TransactionId = TransactionId::new(transaction_id);
+
+    let mut output_ids: Vec<OutputId> = Vec::with_capacity(outputs.len());
+    for (index, output) in outputs.into_iter().enumerate() {
+      let index: u16 = index
+        .try_into()
+        .map_err(|_| Error::PublishError("output index exceeds u16"))?;
+      let output_id: OutputId = OutputId::new(transaction_id, index)?;
+      if let Output::Alias(ref alias_output) = output {
+        let alias_id: AliasId = if alias_output.alias_id() == &AliasId::null() {
+          AliasId::from(output_id)
+        } else {
+          *alias_output.alias_id()
+        };
+        state.aliases.insert(alias_id, output_id);
+      }
+      state.unspent.insert(output_id, output);
+      output_ids.push(output_id);
+    }
+
+    Ok(output_ids)
+  }
+}
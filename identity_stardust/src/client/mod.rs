@@ -0,0 +1,12 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+pub use identity_client::StardustIdentityClient;
+pub use iota_node_client::IotaNodeClient;
+pub use mem_ledger::MemLedger;
+pub use node_client::StardustNodeClient;
+
+mod identity_client;
+mod iota_node_client;
+mod mem_ledger;
+mod node_client;
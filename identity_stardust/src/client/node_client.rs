@@ -0,0 +1,42 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::block::output::AliasId;
+use iota_client::block::output::AliasOutput;
+use iota_client::block::output::ByteCostConfig;
+use iota_client::block::output::Output;
+use iota_client::block::output::OutputId;
+
+use crate::error::Result;
+use crate::NetworkName;
+
+/// Ledger operations required by the [`StardustIdentityClient`](crate::StardustIdentityClient)
+/// to publish and resolve DID documents stored in Alias Outputs.
+///
+/// Implemented for an [`iota_client::Client`] by [`IotaNodeClient`](crate::IotaNodeClient) and
+/// for an in-memory ledger by [`MemLedger`](crate::MemLedger).
+#[async_trait::async_trait(?Send)]
+pub trait StardustNodeClient {
+  /// Returns the name of the network, used as the network name of [`StardustDID`](crate::StardustDID)s
+  /// published through this client.
+  async fn network_name(&self) -> Result<NetworkName>;
+
+  /// Returns the [`ByteCostConfig`] of the network, used to compute the storage deposit of outputs.
+  async fn byte_cost_config(&self) -> Result<ByteCostConfig>;
+
+  /// Returns the [`OutputId`] and the latest unspent [`AliasOutput`] with the given [`AliasId`].
+  ///
+  /// # Errors
+  ///
+  /// Fails if no unspent output exists for the [`AliasId`].
+  async fn get_alias_output(&self, alias_id: AliasId) -> Result<(OutputId, AliasOutput)>;
+
+  /// Publishes a transaction consuming the given `inputs` and creating the given `outputs`.
+  ///
+  /// Returns the [`OutputId`]s of the created `outputs` in the same order, once the transaction
+  /// is confirmed.
+  ///
+  /// NOTE: implementations are responsible for funding the transaction, e.g. by selecting
+  /// additional inputs to cover an increased storage deposit and creating remainder outputs.
+  async fn publish_transaction(&self, inputs: Vec<OutputId>, outputs: Vec<Output>) -> Result<Vec<OutputId>>;
+}
@@ -279,6 +279,10 @@ impl StardustDocument {
   }
 
   /// Serializes the document for inclusion in an Alias Output's state metadata.
+  ///
+  /// [`StateMetadataEncoding::JsonBrotli`] and [`StateMetadataEncoding::Cbor`] produce smaller
+  /// outputs, reducing the required storage deposit, but cannot be read by implementations
+  /// supporting only [`StateMetadataEncoding::Json`].
   pub fn pack_with_encoding(self, encoding: StateMetadataEncoding) -> Result<Vec<u8>> {
     StateMetadataDocument::from(self).pack(encoding)
   }
@@ -334,6 +338,10 @@ impl Document for StardustDocument {
     self.document.resolve_service(query)
   }
 
+  fn services(&self) -> &[Service<Self::D, Self::V>] {
+    self.document.service().as_slice()
+  }
+
   fn resolve_method<'query, 'me, Q>(
     &'me self,
     query: Q,
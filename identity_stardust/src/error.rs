@@ -26,6 +26,12 @@ pub enum Error {
   InvalidNetworkName,
   #[error("invalid state metadata {0}")]
   InvalidStateMetadata(&'static str),
+  #[error("DID resolution failed: {0}")]
+  DIDResolutionError(&'static str),
+  #[error("DID update failed: {0}")]
+  DIDUpdateError(&'static str),
+  #[error("publishing failed: {0}")]
+  PublishError(&'static str),
   #[error("credential revocation error")]
   RevocationError(#[source] identity_did::Error),
 }
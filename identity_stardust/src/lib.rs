@@ -4,6 +4,8 @@
 #![forbid(unsafe_code)]
 #![allow(clippy::upper_case_acronyms)]
 
+#[cfg(feature = "iota-client")]
+pub use client::*;
 pub use did::StardustDID;
 pub use did::StardustDIDUrl;
 pub use document::*;
@@ -13,6 +15,8 @@ pub use state_metadata::*;
 pub use self::error::Error;
 pub use self::error::Result;
 
+#[cfg(feature = "iota-client")]
+mod client;
 mod did;
 mod document;
 mod error;
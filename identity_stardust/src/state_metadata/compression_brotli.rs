@@ -0,0 +1,69 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Read;
+
+use crate::error::Result;
+use crate::Error;
+
+const BUFFER_SIZE: usize = 4096;
+const QUALITY: u32 = 5; // compression level
+const WINDOWS_SIZE: u32 = 22;
+/// Upper bound on the size of decompressed state metadata, guarding against decompression bombs.
+const MAX_DECOMPRESSED_LEN: usize = 1024 * 1024;
+
+pub(crate) fn compress_brotli<T: AsRef<[u8]>>(input: T) -> Result<Vec<u8>> {
+  let mut buf = Vec::new();
+  let mut compressor = brotli::CompressorReader::new(input.as_ref(), BUFFER_SIZE, QUALITY, WINDOWS_SIZE);
+  compressor
+    .read_to_end(&mut buf)
+    .map_err(|_| Error::InvalidStateMetadata("brotli compression failed"))?;
+  Ok(buf)
+}
+
+pub(crate) fn decompress_brotli<T: AsRef<[u8]> + ?Sized>(input: &T) -> Result<Vec<u8>> {
+  let decompressor = brotli::Decompressor::new(input.as_ref(), BUFFER_SIZE);
+  let mut buf = Vec::new();
+  decompressor
+    .take(MAX_DECOMPRESSED_LEN as u64 + 1)
+    .read_to_end(&mut buf)
+    .map_err(|_| Error::InvalidStateMetadata("brotli decompression failed"))?;
+  if buf.len() > MAX_DECOMPRESSED_LEN {
+    return Err(Error::InvalidStateMetadata("brotli decompressed data exceeds the maximum length"));
+  }
+  Ok(buf)
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_brotli() {
+    let data: &str = "{\"id\":\"did:0:0\",\"controller\":\"did:0:0\"}";
+    let compressed: Vec<u8> = compress_brotli(data).unwrap();
+    let decompressed: Vec<u8> = decompress_brotli(&compressed).unwrap();
+
+    assert_eq!(decompressed, data.as_bytes());
+  }
+
+  #[test]
+  fn test_brotli_invalid_input() {
+    assert!(decompress_brotli(&[0xff; 16]).is_err());
+  }
+
+  #[test]
+  fn test_brotli_max_decompressed_len() {
+    let data: Vec<u8> = vec![0; MAX_DECOMPRESSED_LEN];
+    let compressed: Vec<u8> = compress_brotli(&data).unwrap();
+    assert_eq!(decompress_brotli(&compressed).unwrap(), data);
+
+    let oversized: Vec<u8> = vec![0; MAX_DECOMPRESSED_LEN + 1];
+    let compressed: Vec<u8> = compress_brotli(&oversized).unwrap();
+    assert!(compressed.len() < 1024);
+    assert!(matches!(
+      decompress_brotli(&compressed),
+      Err(Error::InvalidStateMetadata(_))
+    ));
+  }
+}
@@ -16,6 +16,8 @@ use crate::StardustDID;
 use crate::StardustDocument;
 use crate::StardustDocumentMetadata;
 
+use super::compression_brotli::compress_brotli;
+use super::compression_brotli::decompress_brotli;
 use super::StateMetadataEncoding;
 use super::StateMetadataVersion;
 
@@ -56,14 +58,26 @@ impl StateMetadataDocument {
 
   /// Pack a [`StateMetadataDocument`] into bytes, suitable for inclusion in
   /// an alias output's state metadata, according to the given `encoding`.
+  ///
+  /// The version is the earliest [`StateMetadataVersion`] supporting the `encoding`.
   pub fn pack(self, encoding: StateMetadataEncoding) -> Result<Vec<u8>> {
     let encoded_message_data: Vec<u8> = match encoding {
       StateMetadataEncoding::Json => self.to_json_vec()?,
+      StateMetadataEncoding::JsonBrotli => compress_brotli(self.to_json_vec()?)?,
+      StateMetadataEncoding::Cbor => {
+        let mut buffer: Vec<u8> = Vec::new();
+        ciborium::ser::into_writer(&self, &mut buffer)
+          .map_err(|_| Error::InvalidStateMetadata("CBOR serialization failed"))?;
+        buffer
+      }
     };
 
     // Prepend flags.
-    let encoded_message_data_with_flags =
-      add_flags_to_message(encoded_message_data, StateMetadataVersion::CURRENT, encoding);
+    let encoded_message_data_with_flags = add_flags_to_message(
+      encoded_message_data,
+      StateMetadataVersion::for_encoding(encoding),
+      encoding,
+    );
     Ok(encoded_message_data_with_flags)
   }
 
@@ -82,14 +96,14 @@ impl StateMetadataDocument {
     let version: StateMetadataVersion = StateMetadataVersion::try_from(*data.get(3).ok_or(
       identity_did::Error::InvalidDocument("expected data to have at least length 4", None),
     )?)?;
-    if version != StateMetadataVersion::V1 {
-      return Err(Error::InvalidStateMetadata("unsupported version"));
-    }
 
     // Decode data.
     let encoding: StateMetadataEncoding = StateMetadataEncoding::try_from(*data.get(4).ok_or(
       identity_did::Error::InvalidDocument("expected data to have at least length 5", None),
     )?)?;
+    if !version.supports(encoding) {
+      return Err(Error::InvalidStateMetadata("unsupported encoding for version"));
+    }
 
     let inner: &[u8] = data.get(5..).ok_or(identity_did::Error::InvalidDocument(
       "expected data to have at least length 6",
@@ -98,6 +112,12 @@ impl StateMetadataDocument {
 
     match encoding {
       StateMetadataEncoding::Json => StateMetadataDocument::from_json_slice(inner).map_err(Into::into),
+      StateMetadataEncoding::JsonBrotli => {
+        StateMetadataDocument::from_json_slice(&decompress_brotli(inner)?).map_err(Into::into)
+      }
+      StateMetadataEncoding::Cbor => {
+        ciborium::de::from_reader(inner).map_err(|_| Error::InvalidStateMetadata("CBOR deserialization failed"))
+      }
     }
   }
 }
@@ -155,6 +175,7 @@ mod tests {
   use crate::StardustVerificationMethod;
   use crate::StateMetadataDocument;
   use crate::StateMetadataEncoding;
+  use crate::StateMetadataVersion;
 
   struct TestSetup {
     document: StardustDocument,
@@ -293,9 +314,59 @@ mod tests {
     let TestSetup { document, .. } = test_document();
 
     let state_metadata_doc: StateMetadataDocument = StateMetadataDocument::from(document);
-    let packed_bytes: Vec<u8> = state_metadata_doc.clone().pack(StateMetadataEncoding::Json).unwrap();
+    for encoding in [
+      StateMetadataEncoding::Json,
+      StateMetadataEncoding::JsonBrotli,
+      StateMetadataEncoding::Cbor,
+    ] {
+      let packed_bytes: Vec<u8> = state_metadata_doc.clone().pack(encoding).unwrap();
+      assert_eq!(packed_bytes[4], encoding as u8);
+
+      let unpacked_doc = StateMetadataDocument::unpack(&packed_bytes).unwrap();
+      assert_eq!(state_metadata_doc, unpacked_doc);
+    }
+  }
+
+  #[test]
+  fn test_packing_version() {
+    let TestSetup { document, .. } = test_document();
+    let state_metadata_doc: StateMetadataDocument = StateMetadataDocument::from(document);
+
+    // JSON remains readable by implementations only supporting the first version.
+    let json: Vec<u8> = state_metadata_doc.clone().pack(StateMetadataEncoding::Json).unwrap();
+    assert_eq!(json[3], StateMetadataVersion::V1 as u8);
+    let cbor: Vec<u8> = state_metadata_doc.pack(StateMetadataEncoding::Cbor).unwrap();
+    assert_eq!(cbor[3], StateMetadataVersion::V2 as u8);
+
+    // Encodings introduced by a later version are rejected under an earlier one.
+    let mut invalid: Vec<u8> = cbor.clone();
+    invalid[3] = StateMetadataVersion::V1 as u8;
+    assert!(StateMetadataDocument::unpack(&invalid).is_err());
+
+    // Unknown versions are rejected.
+    let mut invalid: Vec<u8> = cbor;
+    invalid[3] = u8::MAX;
+    assert!(StateMetadataDocument::unpack(&invalid).is_err());
+  }
+
+  #[test]
+  fn test_packing_size() {
+    let TestSetup { document, .. } = test_document();
+    let state_metadata_doc: StateMetadataDocument = StateMetadataDocument::from(document);
 
-    let unpacked_doc = StateMetadataDocument::unpack(&packed_bytes).unwrap();
-    assert_eq!(state_metadata_doc, unpacked_doc);
+    let json: usize = state_metadata_doc
+      .clone()
+      .pack(StateMetadataEncoding::Json)
+      .unwrap()
+      .len();
+    let json_brotli: usize = state_metadata_doc
+      .clone()
+      .pack(StateMetadataEncoding::JsonBrotli)
+      .unwrap()
+      .len();
+    let cbor: usize = state_metadata_doc.pack(StateMetadataEncoding::Cbor).unwrap().len();
+
+    assert!(json_brotli < json);
+    assert!(cbor < json);
   }
 }
@@ -8,7 +8,12 @@ use crate::Error;
 /// Indicates the encoding of a DID document in state metadata.
 #[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, num_derive::FromPrimitive)]
 pub enum StateMetadataEncoding {
+  /// Plain JSON.
   Json = 0,
+  /// JSON compressed with brotli.
+  JsonBrotli = 1,
+  /// CBOR as defined in [RFC 8949](https://www.rfc-editor.org/rfc/rfc8949).
+  Cbor = 2,
 }
 
 impl TryFrom<u8> for StateMetadataEncoding {
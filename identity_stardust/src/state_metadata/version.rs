@@ -4,15 +4,33 @@
 use num_traits::FromPrimitive;
 
 use crate::Error;
+use crate::StateMetadataEncoding;
 
 /// Indicates the version of a DID document in state metadata.
 #[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, num_derive::FromPrimitive)]
 pub(crate) enum StateMetadataVersion {
+  /// Supports [`StateMetadataEncoding::Json`] only.
   V1 = 1,
+  /// Adds [`StateMetadataEncoding::JsonBrotli`] and [`StateMetadataEncoding::Cbor`].
+  V2 = 2,
 }
 
 impl StateMetadataVersion {
-  pub const CURRENT: Self = Self::V1;
+  pub const CURRENT: Self = Self::V2;
+
+  /// Returns the earliest version supporting the given `encoding`, so documents remain readable
+  /// by older implementations whenever possible.
+  pub fn for_encoding(encoding: StateMetadataEncoding) -> Self {
+    match encoding {
+      StateMetadataEncoding::Json => Self::V1,
+      StateMetadataEncoding::JsonBrotli | StateMetadataEncoding::Cbor => Self::V2,
+    }
+  }
+
+  /// Returns whether the given `encoding` may be used with this version.
+  pub fn supports(self, encoding: StateMetadataEncoding) -> bool {
+    self >= Self::for_encoding(encoding) && self <= Self::CURRENT
+  }
 }
 
 impl TryFrom<u8> for StateMetadataVersion {
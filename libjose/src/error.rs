@@ -6,6 +6,9 @@
 use core::fmt::Display;
 use core::fmt::Formatter;
 
+use crate::jwt::ClaimError;
+use crate::lib::*;
+
 /// Alias for a `Result` with the error type [Error].
 pub type Result<T, E = Error> = core::result::Result<T, E>;
 
@@ -19,6 +22,7 @@ pub enum Error {
   InvalidUtf8(core::str::Utf8Error),
   InvalidClaim(&'static str),
   MissingClaim(&'static str),
+  InvalidClaims(Vec<ClaimError>),
   InvalidParam(&'static str),
   MissingParam(&'static str),
   InvalidContent(&'static str),
@@ -40,6 +44,13 @@ impl Display for Error {
       Self::InvalidUtf8(inner) => f.write_fmt(format_args!("Invalid Utf-8: {:?}", inner)),
       Self::InvalidClaim(inner) => f.write_fmt(format_args!("Invalid Claim: {}", inner)),
       Self::MissingClaim(inner) => f.write_fmt(format_args!("Missing Claim: {}", inner)),
+      Self::InvalidClaims(inner) => {
+        f.write_str("Invalid Claims:")?;
+        for error in inner {
+          f.write_fmt(format_args!(" [{}]", error))?;
+        }
+        Ok(())
+      }
       Self::InvalidParam(inner) => f.write_fmt(format_args!("Invalid Param: {}", inner)),
       Self::MissingParam(inner) => f.write_fmt(format_args!("Missing Param: {}", inner)),
       Self::InvalidContent(inner) => f.write_fmt(format_args!("Invalid Content: {}", inner)),
@@ -86,3 +97,9 @@ impl From<::rsa::errors::Error> for Error {
     Self::CryptoError(crypto::Error::CipherError { alg: "Rsa" })
   }
 }
+
+impl From<::rsa::pkcs8::Error> for Error {
+  fn from(_: ::rsa::pkcs8::Error) -> Self {
+    Self::KeyError("PKCS#8")
+  }
+}
@@ -28,10 +28,13 @@ use serde_json::to_vec;
 
 use crate::error::Error;
 use crate::error::Result;
+use crate::jwe::AsyncJweKeyAgreement;
 use crate::jwe::JweAlgorithm;
 use crate::jwe::JweEncryption;
 use crate::jwe::JweFormat;
 use crate::jwe::JweHeader;
+use crate::jwe::JweKey;
+use crate::jwe::JweKeyAgreement;
 use crate::jwe::Recipient;
 use crate::jwk::Jwk;
 use crate::jwt::JwtHeaderSet;
@@ -101,8 +104,8 @@ struct Flatten<'a> {
 pub struct Encoder<'a> {
   /// The output format of the encoded token.
   format: JweFormat,
-  /// The secret key used for key agreements.
-  secret: Option<Secret<'a>>,
+  /// The secret key or key agreement used for static key agreements.
+  secret: Option<JweKey<'a>>,
   /// Additional authenticated data.
   aad: Option<&'a [u8]>,
   /// Agreement PartyUInfo used with Concat KDF.
@@ -149,7 +152,21 @@ impl<'a> Encoder<'a> {
   }
 
   pub fn secret(mut self, value: impl Into<Secret<'a>>) -> Self {
-    self.secret = Some(value.into());
+    self.secret = Some(JweKey::Secret(value.into()));
+    self
+  }
+
+  /// Sets a [`JweKeyAgreement`] used instead of an in-memory secret key for static key agreements.
+  pub fn key_agreement(mut self, value: &'a dyn JweKeyAgreement) -> Self {
+    self.secret = Some(JweKey::KeyAgreement(value));
+    self
+  }
+
+  /// Sets an [`AsyncJweKeyAgreement`] used instead of an in-memory secret key for static key agreements.
+  ///
+  /// The token must be created with [`Encoder::encode_async`].
+  pub fn async_key_agreement(mut self, value: &'a dyn AsyncJweKeyAgreement) -> Self {
+    self.secret = Some(JweKey::AsyncKeyAgreement(value));
     self
   }
 
@@ -191,16 +208,95 @@ impl<'a> Encoder<'a> {
   }
 
   pub fn encode(&self, claims: &[u8]) -> Result<String> {
+    self.prepare()?;
+
+    let static_secrets: Vec<Option<Vec<u8>>> = self
+      .recipients
+      .iter()
+      .map(|recipient| self.static_secret(recipient))
+      .collect::<Result<_>>()?;
+
+    self.encode_with_static_secrets(claims, &static_secrets)
+  }
+
+  pub async fn encode_serde_async<T>(&self, claims: &T) -> Result<String>
+  where
+    T: Serialize,
+  {
+    self.encode_async(&to_vec(claims)?).await
+  }
+
+  /// Encodes the token like [`Encoder::encode`], additionally supporting an
+  /// [`AsyncJweKeyAgreement`] for static key agreements.
+  pub async fn encode_async(&self, claims: &[u8]) -> Result<String> {
+    self.prepare()?;
+
+    let mut static_secrets: Vec<Option<Vec<u8>>> = Vec::with_capacity(self.recipients.len());
+
+    for recipient in self.recipients.iter() {
+      let static_secret: Option<Vec<u8>> = match self.secret {
+        Some(JweKey::AsyncKeyAgreement(agreement)) if self.requires_static_secret(recipient)? => {
+          Some(agreement.diffie_hellman(recipient.ecdh_curve, recipient.public).await?)
+        }
+        _ => self.static_secret(recipient)?,
+      };
+
+      static_secrets.push(static_secret);
+    }
+
+    self.encode_with_static_secrets(claims, &static_secrets)
+  }
+
+  fn prepare(&self) -> Result<()> {
     if self.recipients.is_empty() {
       return Err(Error::EncError("Missing Recipients"));
     }
 
-    self.validate()?;
+    self.validate()
+  }
+
+  /// Returns `true` if the key management algorithm of the `recipient` requires a static key
+  /// agreement with the secret key of the sender.
+  fn requires_static_secret(&self, recipient: &Recipient<'_>) -> Result<bool> {
+    let algorithm: JweAlgorithm = HeaderSet::new()
+      .header(recipient.header)
+      .protected(self.protected)
+      .unprotected(self.unprotected)
+      .try_alg()?;
+
+    Ok(matches!(
+      algorithm,
+      JweAlgorithm::ECDH_1PU
+        | JweAlgorithm::ECDH_1PU_A128KW
+        | JweAlgorithm::ECDH_1PU_A192KW
+        | JweAlgorithm::ECDH_1PU_A256KW
+    ))
+  }
+
+  /// Computes the shared secret of the static key agreement between the sender and the `recipient`,
+  /// if required by its key management algorithm.
+  fn static_secret(&self, recipient: &Recipient<'_>) -> Result<Option<Vec<u8>>> {
+    if !self.requires_static_secret(recipient)? {
+      return Ok(None);
+    }
+
+    let static_secret: Vec<u8> = match self.secret {
+      Some(JweKey::Secret(secret)) => diffie_hellman(recipient.ecdh_curve, recipient.public, secret)?,
+      Some(JweKey::KeyAgreement(agreement)) => agreement.diffie_hellman(recipient.ecdh_curve, recipient.public)?,
+      Some(JweKey::AsyncKeyAgreement(_)) => {
+        return Err(Error::EncError("Async Key Agreement requires `encode_async`"));
+      }
+      None => return Err(Error::EncError("Missing ECDH-1PU Secret Key")),
+    };
 
+    Ok(Some(static_secret))
+  }
+
+  fn encode_with_static_secrets(&self, claims: &[u8], static_secrets: &[Option<Vec<u8>>]) -> Result<String> {
     let mut context: __Context<'_, '_> = __Context::new(self, self.recipients.len());
 
-    for recipient in self.recipients.iter() {
-      context.expand_recipient(self.protected, self.unprotected, *recipient)?;
+    for (recipient, static_secret) in self.recipients.iter().zip(static_secrets) {
+      context.expand_recipient(self.protected, self.unprotected, *recipient, static_secret.as_deref())?;
     }
 
     let encryption: JweEncryption = HeaderSet::new()
@@ -225,9 +321,10 @@ impl<'a> Encoder<'a> {
     let recipients: Vec<(Option<String>, JweHeader)> = context
       .recipients
       .into_iter()
-      .map(|(recipient, mut output)| {
+      .zip(static_secrets)
+      .map(|((recipient, mut output), static_secret)| {
         let encrypted_key: Option<String> = self
-          .encrypt_cek(&encryption_key, &mut output, recipient)?
+          .encrypt_cek(&encryption_key, &mut output, recipient, static_secret.as_deref())?
           .map(encode_b64);
 
         Ok((encrypted_key, output))
@@ -299,6 +396,7 @@ impl<'a> Encoder<'a> {
     encryption: JweEncryption,
     output: &mut JweHeader,
     recipient: Recipient<'cek>,
+    static_secret: Option<&[u8]>,
   ) -> Result<Option<Cow<'cek, [u8]>>> {
     match algorithm {
       JweAlgorithm::DIR => {
@@ -310,11 +408,11 @@ impl<'a> Encoder<'a> {
 
         Ok(Some(key))
       }
-      JweAlgorithm::ECDH_ES => EcdhDeriver::new(self, &recipient)
+      JweAlgorithm::ECDH_ES => EcdhDeriver::new(self, &recipient, static_secret)
         .derive_ecdh_es(output, encryption.name(), encryption.key_len())
         .map(Cow::Owned)
         .map(Some),
-      JweAlgorithm::ECDH_1PU => EcdhDeriver::new(self, &recipient)
+      JweAlgorithm::ECDH_1PU => EcdhDeriver::new(self, &recipient, static_secret)
         .derive_ecdh_1pu(output, encryption.name(), encryption.key_len())
         .map(Cow::Owned)
         .map(Some),
@@ -357,6 +455,7 @@ impl<'a> Encoder<'a> {
     encryption_key: &[u8],
     output: &mut JweHeader,
     recipient: Recipient<'_>,
+    static_secret: Option<&[u8]>,
   ) -> Result<Option<Vec<u8>>> {
     macro_rules! rsa {
       ($padding:ident, $encryption_key:expr, $public:expr) => {{
@@ -427,7 +526,7 @@ impl<'a> Encoder<'a> {
       ($derive:ident, $wrap:ident, $encryption_key:expr, $recipient:expr, $output:expr, $this:expr) => {{
         let algorithm: &str = $output.alg().name();
         let key_len: usize = $output.alg().try_key_len()?;
-        let deriver: EcdhDeriver<'_, '_> = EcdhDeriver::new($this, &$recipient);
+        let deriver: EcdhDeriver<'_, '_> = EcdhDeriver::new($this, &$recipient, static_secret);
         let derived: Vec<u8> = deriver.$derive($output, algorithm, key_len)?;
         let mut ctx: Vec<u8> = vec![0; $encryption_key.len() + $wrap::BLOCK];
 
@@ -447,7 +546,7 @@ impl<'a> Encoder<'a> {
       ($derive:ident, $wrap:ident, $encryption_key:expr, $recipient:expr, $output:expr, $this:expr) => {{
         let algorithm: &str = $output.alg().name();
         let key_len: usize = $output.alg().try_key_len()?;
-        let deriver: EcdhDeriver<'_, '_> = EcdhDeriver::new($this, &$recipient);
+        let deriver: EcdhDeriver<'_, '_> = EcdhDeriver::new($this, &$recipient, static_secret);
         let derived: Vec<u8> = deriver.$derive($output, algorithm, key_len)?;
         let nonce: Nonce<$wrap> = $wrap::random_nonce()?;
 
@@ -598,6 +697,7 @@ impl<'a, 'b> __Context<'a, 'b> {
     protected: Option<&'a JweHeader>,
     unprotected: Option<&'a JweHeader>,
     recipient: Recipient<'a>,
+    static_secret: Option<&[u8]>,
   ) -> Result<()> {
     let merged: HeaderSet<'_> = HeaderSet::new()
       .header(recipient.header)
@@ -615,9 +715,10 @@ impl<'a, 'b> __Context<'a, 'b> {
       JweHeader::new(algorithm, encryption)
     };
 
-    let cek: Option<Cow<'_, [u8]>> = self
-      .encoder
-      .generate_cek(algorithm, encryption, &mut output, recipient)?;
+    let cek: Option<Cow<'_, [u8]>> =
+      self
+        .encoder
+        .generate_cek(algorithm, encryption, &mut output, recipient, static_secret)?;
 
     if let Some(encryption_key) = cek {
       if let Some(cek) = self.encryption_key.as_ref() {
@@ -679,11 +780,13 @@ where
   Ok(tag)
 }
 
-struct EcdhDeriver<'a, 'b>(&'b Encoder<'a>, &'b Recipient<'a>);
+/// Derives ECDH keys for a recipient, using the precomputed shared secret of the static key
+/// agreement for ECDH-1PU.
+struct EcdhDeriver<'a, 'b>(&'b Encoder<'a>, &'b Recipient<'a>, Option<&'b [u8]>);
 
 impl<'a, 'b> EcdhDeriver<'a, 'b> {
-  fn new(encoder: &'b Encoder<'a>, recipient: &'b Recipient<'a>) -> Self {
-    Self(encoder, recipient)
+  fn new(encoder: &'b Encoder<'a>, recipient: &'b Recipient<'a>, static_secret: Option<&'b [u8]>) -> Self {
+    Self(encoder, recipient, static_secret)
   }
 
   fn derive_ecdh_es(&self, output: &mut JweHeader, algorithm: &str, key_len: usize) -> Result<Vec<u8>> {
@@ -694,11 +797,10 @@ impl<'a, 'b> EcdhDeriver<'a, 'b> {
 
   fn derive_ecdh_1pu(&self, output: &mut JweHeader, algorithm: &str, key_len: usize) -> Result<Vec<u8>> {
     self.derive_ecdh_key(output, algorithm, key_len, |eph_secret| {
-      let secret: Secret<'_> = self.0.secret.ok_or(Error::EncError("Missing ECDH-1PU Secret Key"))?;
+      let zs: &[u8] = self.2.ok_or(Error::EncError("Missing ECDH-1PU Secret Key"))?;
       let ze: Vec<u8> = diffie_hellman(self.1.ecdh_curve, self.1.public, eph_secret)?;
-      let zs: Vec<u8> = diffie_hellman(self.1.ecdh_curve, self.1.public, secret)?;
 
-      Ok([ze, zs].concat())
+      Ok([ze.as_slice(), zs].concat())
     })
   }
 
@@ -0,0 +1,37 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::Result;
+use crate::jwk::EcdhCurve;
+use crate::lib::*;
+use crate::utils::Secret;
+
+/// Performs the sender's static key agreement of ECDH-1PU without exposing the private key to the
+/// [`Encoder`][crate::jwe::Encoder], e.g. by delegating to a hardware security module or a secure key store.
+pub trait JweKeyAgreement {
+  /// Returns the shared secret `Z` of a Diffie-Hellman key agreement between the sender's static
+  /// private key and the recipient's `public` key on the given `curve`.
+  fn diffie_hellman(&self, curve: EcdhCurve, public: Secret<'_>) -> Result<Vec<u8>>;
+}
+
+/// The asynchronous variant of [`JweKeyAgreement`], used by
+/// [`Encoder::encode_async`][crate::jwe::Encoder::encode_async].
+#[async_trait::async_trait(?Send)]
+pub trait AsyncJweKeyAgreement {
+  /// Returns the shared secret `Z` of a Diffie-Hellman key agreement between the sender's static
+  /// private key and the recipient's `public` key on the given `curve`.
+  async fn diffie_hellman(&self, curve: EcdhCurve, public: Secret<'_>) -> Result<Vec<u8>>;
+}
+
+/// The sender key used by an [`Encoder`][crate::jwe::Encoder] for ECDH-1PU key agreements.
+#[derive(Clone, Copy)]
+pub enum JweKey<'a> {
+  /// Private key material held in memory.
+  Secret(Secret<'a>),
+  /// A key agreement holding the private key.
+  KeyAgreement(&'a dyn JweKeyAgreement),
+  /// An asynchronous key agreement holding the private key.
+  ///
+  /// Only supported by [`Encoder::encode_async`][crate::jwe::Encoder::encode_async].
+  AsyncKeyAgreement(&'a dyn AsyncJweKeyAgreement),
+}
@@ -10,6 +10,7 @@ mod encoder;
 mod encryption;
 mod format;
 mod header;
+mod key_agreement;
 mod recipient;
 
 pub use self::algorithm::*;
@@ -19,4 +20,5 @@ pub use self::encoder::*;
 pub use self::encryption::*;
 pub use self::format::*;
 pub use self::header::*;
+pub use self::key_agreement::*;
 pub use self::recipient::*;
@@ -515,25 +515,7 @@ impl Jwk {
       KeyParams::Rsa(bits) => {
         let secret: RsaSecretKey = RsaSecretKey::new(&mut OsRng, bits.bits())?;
 
-        let (p, q) = match secret.primes() {
-          [_] => unreachable!(),
-          [p, q] => (p, q),
-          [..] => return Err(Error::KeyError("multi prime keys are not supported")),
-        };
-
-        let values: RsaComputed = RsaComputed::new(secret.d(), p, q)?;
-
-        Ok(Jwk::from_params(JwkParamsRsa {
-          n: encode_b64(secret.n().to_bytes_be()),
-          e: encode_b64(secret.e().to_bytes_be()),
-          d: Some(encode_b64(secret.d().to_bytes_be())),
-          p: Some(encode_b64(p.to_bytes_be())),
-          q: Some(encode_b64(q.to_bytes_be())),
-          dp: Some(encode_b64(values.dp.to_bytes_be())),
-          dq: Some(encode_b64(values.dq.to_bytes_be())),
-          qi: Some(encode_b64(values.qi.to_bytes_be())),
-          oth: None,
-        }))
+        rsa_secret_params(&secret).map(Jwk::from_params)
       }
       KeyParams::Ec(curve) => match curve {
         EcCurve::P256 => generate_ec!(P256SecretKey, curve),
@@ -561,6 +543,29 @@ impl Jwk {
   }
 }
 
+/// Returns the JWK parameters of a two-prime RSA secret key.
+pub(crate) fn rsa_secret_params(secret: &RsaSecretKey) -> Result<JwkParamsRsa> {
+  let (p, q) = match secret.primes() {
+    [_] => unreachable!(),
+    [p, q] => (p, q),
+    [..] => return Err(Error::KeyError("multi prime keys are not supported")),
+  };
+
+  let values: RsaComputed = RsaComputed::new(secret.d(), p, q)?;
+
+  Ok(JwkParamsRsa {
+    n: encode_b64(secret.n().to_bytes_be()),
+    e: encode_b64(secret.e().to_bytes_be()),
+    d: Some(encode_b64(secret.d().to_bytes_be())),
+    p: Some(encode_b64(p.to_bytes_be())),
+    q: Some(encode_b64(q.to_bytes_be())),
+    dp: Some(encode_b64(values.dp.to_bytes_be())),
+    dq: Some(encode_b64(values.dq.to_bytes_be())),
+    qi: Some(encode_b64(values.qi.to_bytes_be())),
+    oth: None,
+  })
+}
+
 impl Zeroize for Jwk {
   fn zeroize(&mut self) {
     self.params.zeroize();
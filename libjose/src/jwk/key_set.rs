@@ -57,6 +57,19 @@ impl JwkSet {
       .collect()
   }
 
+  /// Returns an iterator over the keys that may verify a signature created with
+  /// `algorithm`, restricted to keys matching `kid` if given.
+  ///
+  /// Keys with a `use`, `key_ops` or `alg` property that does not permit
+  /// signature verification with `algorithm` are skipped.
+  pub fn verifying_keys<'a>(&'a self, kid: Option<&'a str>, algorithm: &'a str) -> impl Iterator<Item = &'a Jwk> + 'a {
+    self
+      .keys
+      .iter()
+      .filter(move |key| kid.is_none() || key.kid() == kid)
+      .filter(move |key| key.check_verifying_key(algorithm).is_ok())
+  }
+
   /// Adds a new `key` to the set.
   pub fn add(&mut self, key: impl Into<Jwk>) {
     self.keys.push(key.into());
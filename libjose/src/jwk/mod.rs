@@ -8,6 +8,7 @@ mod key;
 mod key_management;
 mod key_operation;
 mod key_params;
+mod key_pkcs8;
 mod key_set;
 mod key_type;
 mod key_use;
@@ -18,9 +18,14 @@ use subtle::ConstantTimeEq as _;
 use crate::error::Error;
 use crate::error::Result;
 use crate::jwk::EdCurve;
+use crate::jwk::Jwk;
+use crate::jwk::JwkSet;
+use crate::jws::AsyncJwkResolver;
+use crate::jws::JwkResolver;
 use crate::jws::JwsAlgorithm;
 use crate::jws::JwsFormat;
 use crate::jws::JwsHeader;
+use crate::jws::JwsVerifyingKey;
 use crate::jwt::JwtHeaderSet;
 use crate::lib::*;
 use crate::utils::check_slice_param;
@@ -68,6 +73,37 @@ struct Flatten<'a> {
   signature: Signature<'a>,
 }
 
+struct SigningInput<'b> {
+  algorithm: JwsAlgorithm,
+  kid: Option<String>,
+  b64: bool,
+  message: Vec<u8>,
+  signature: Vec<u8>,
+  payload: &'b [u8],
+  protected: Option<JwsHeader>,
+  unprotected: Option<JwsHeader>,
+}
+
+impl<'b> SigningInput<'b> {
+  fn kid(&self) -> Option<&str> {
+    self.kid.as_deref()
+  }
+
+  fn into_token(self) -> Result<Token<'b>> {
+    let claims: Cow<'b, [u8]> = if self.b64 {
+      Cow::Owned(decode_b64(self.payload)?)
+    } else {
+      Cow::Borrowed(self.payload)
+    };
+
+    Ok(Token {
+      protected: self.protected,
+      unprotected: self.unprotected,
+      claims,
+    })
+  }
+}
+
 // =============================================================================
 // =============================================================================
 
@@ -76,8 +112,8 @@ pub struct Decoder<'a, 'b> {
   format: JwsFormat,
   /// The curve used for EdDSA signatures.
   eddsa_curve: EdCurve,
-  /// The public key used for signature verification.
-  public: Secret<'a>,
+  /// The public key(s) used for signature verification.
+  public: JwsVerifyingKey<'a>,
   /// A list of permitted signature algorithms.
   algs: Option<Vec<JwsAlgorithm>>,
   /// A list of permitted extension parameters.
@@ -92,10 +128,32 @@ pub struct Decoder<'a, 'b> {
 
 impl<'a, 'b> Decoder<'a, 'b> {
   pub fn new(public: impl Into<Secret<'a>>) -> Self {
+    Self::new_with_key(JwsVerifyingKey::Secret(public.into()))
+  }
+
+  /// Creates a new [`Decoder`] verifying with the key of `keys` selected by the
+  /// `kid` and `alg` of the token.
+  pub fn new_with_key_set(keys: &'a JwkSet) -> Self {
+    Self::new_with_key(JwsVerifyingKey::KeySet(keys))
+  }
+
+  /// Creates a new [`Decoder`] verifying with the keys looked up by `resolver`.
+  pub fn new_with_resolver(resolver: &'a dyn JwkResolver) -> Self {
+    Self::new_with_key(JwsVerifyingKey::Resolver(resolver))
+  }
+
+  /// Creates a new [`Decoder`] verifying with the keys looked up by the asynchronous `resolver`.
+  ///
+  /// The token must be decoded with [`Decoder::decode_async`].
+  pub fn new_with_async_resolver(resolver: &'a dyn AsyncJwkResolver) -> Self {
+    Self::new_with_key(JwsVerifyingKey::AsyncResolver(resolver))
+  }
+
+  fn new_with_key(public: JwsVerifyingKey<'a>) -> Self {
     Self {
       format: JwsFormat::Compact,
       eddsa_curve: EdCurve::Ed25519,
-      public: public.into(),
+      public,
       algs: None,
       crits: None,
       key_id: None,
@@ -134,18 +192,63 @@ impl<'a, 'b> Decoder<'a, 'b> {
   }
 
   pub fn decode(&self, data: &'b [u8]) -> Result<Token<'b>> {
-    self.expand(data, |payload, signatures| {
-      for signature in signatures {
-        if let Ok(token) = self.decode_one(payload, signature) {
-          return Ok(token);
-        }
+    let (payload, signatures): (&'b [u8], Vec<Signature<'b>>) = self.expand(data)?;
+
+    for signature in signatures {
+      if let Ok(token) = self.decode_one(payload, signature) {
+        return Ok(token);
       }
+    }
 
-      Err(Error::InvalidContent("Recipient (not found)"))
-    })
+    Err(Error::InvalidContent("Recipient (not found)"))
   }
 
-  fn decode_one(&self, payload: &'b [u8], signature: Signature<'a>) -> Result<Token<'b>> {
+  /// Decodes the token like [`Decoder::decode`], additionally supporting an
+  /// [`AsyncJwkResolver`][crate::jws::AsyncJwkResolver].
+  pub async fn decode_async(&self, data: &'b [u8]) -> Result<Token<'b>> {
+    let (payload, signatures): (&'b [u8], Vec<Signature<'b>>) = self.expand(data)?;
+
+    for signature in signatures {
+      if let Ok(token) = self.decode_one_async(payload, signature).await {
+        return Ok(token);
+      }
+    }
+
+    Err(Error::InvalidContent("Recipient (not found)"))
+  }
+
+  fn decode_one(&self, payload: &'b [u8], signature: Signature<'b>) -> Result<Token<'b>> {
+    let input: SigningInput<'b> = self.prepare(payload, signature)?;
+
+    match self.public {
+      JwsVerifyingKey::Secret(public) => self.verify(input.algorithm, &input.message, &input.signature, public)?,
+      JwsVerifyingKey::KeySet(keys) => {
+        self.verify_any(&input, keys.verifying_keys(input.kid(), input.algorithm.name()))?;
+      }
+      JwsVerifyingKey::Resolver(resolver) => {
+        self.verify_any(&input, resolver.resolve(input.kid(), input.algorithm)?.iter())?;
+      }
+      JwsVerifyingKey::AsyncResolver(_) => return Err(Error::SigError("Async Resolver requires `decode_async`")),
+    }
+
+    input.into_token()
+  }
+
+  async fn decode_one_async(&self, payload: &'b [u8], signature: Signature<'b>) -> Result<Token<'b>> {
+    let resolver: &dyn AsyncJwkResolver = match self.public {
+      JwsVerifyingKey::AsyncResolver(resolver) => resolver,
+      _ => return self.decode_one(payload, signature),
+    };
+
+    let input: SigningInput<'b> = self.prepare(payload, signature)?;
+    let keys: Vec<Jwk> = resolver.resolve(input.kid(), input.algorithm).await?;
+
+    self.verify_any(&input, keys.iter())?;
+
+    input.into_token()
+  }
+
+  fn prepare(&self, payload: &'b [u8], signature: Signature<'b>) -> Result<SigningInput<'b>> {
     let protected: Option<JwsHeader> = signature.protected.map(decode_b64_json).transpose()?;
 
     validate_jws_headers(protected.as_ref(), signature.header.as_ref(), self.crits.as_deref())?;
@@ -154,31 +257,28 @@ impl<'a, 'b> Decoder<'a, 'b> {
       .protected(protected.as_ref())
       .unprotected(signature.header.as_ref());
 
-    self.check_alg(merged.try_alg()?)?;
-    self.check_kid(merged.kid())?;
+    let algorithm: JwsAlgorithm = merged.try_alg()?;
+    let kid: Option<String> = merged.kid().map(ToString::to_string);
+    let b64: bool = merged.b64().unwrap_or(true);
 
-    {
-      let protected: &[u8] = signature.protected.map(str::as_bytes).unwrap_or_default();
-      let message: Vec<u8> = create_message(protected, payload);
-      let signature: Vec<u8> = decode_b64(signature.signature)?;
+    self.check_alg(algorithm)?;
+    self.check_kid(kid.as_deref())?;
 
-      self.verify(merged.try_alg()?, &message, &signature)?;
-    }
-
-    let claims: Cow<'b, [u8]> = if merged.b64().unwrap_or(true) {
-      Cow::Owned(decode_b64(payload)?)
-    } else {
-      Cow::Borrowed(payload)
-    };
+    let message: Vec<u8> = create_message(signature.protected.map(str::as_bytes).unwrap_or_default(), payload);
 
-    Ok(Token {
+    Ok(SigningInput {
+      algorithm,
+      kid,
+      b64,
+      message,
+      signature: decode_b64(signature.signature)?,
+      payload,
       protected,
       unprotected: signature.header,
-      claims,
     })
   }
 
-  fn expand<T>(&self, data: &'b [u8], format: impl Fn(&'b [u8], Vec<Signature<'_>>) -> Result<T>) -> Result<T> {
+  fn expand(&self, data: &'b [u8]) -> Result<(&'b [u8], Vec<Signature<'b>>)> {
     match self.format {
       JwsFormat::Compact => {
         let split: Vec<&[u8]> = data.split(|byte| *byte == b'.').collect();
@@ -193,17 +293,17 @@ impl<'a, 'b> Decoder<'a, 'b> {
           signature: parse_utf8(split[2])?,
         };
 
-        format(self.expand_payload(Some(split[1]))?, vec![signature])
+        Ok((self.expand_payload(Some(split[1]))?, vec![signature]))
       }
       JwsFormat::General => {
         let data: General<'_> = from_slice(data)?;
 
-        format(self.expand_payload(data.payload)?, data.signatures)
+        Ok((self.expand_payload(data.payload)?, data.signatures))
       }
       JwsFormat::Flatten => {
         let data: Flatten<'_> = from_slice(data)?;
 
-        format(self.expand_payload(data.payload)?, vec![data.signature])
+        Ok((self.expand_payload(data.payload)?, vec![data.signature]))
       }
     }
   }
@@ -222,14 +322,28 @@ impl<'a, 'b> Decoder<'a, 'b> {
   }
 
   fn check_kid(&self, value: Option<&str>) -> Result<()> {
-    if self.key_id.as_deref() == value {
-      Ok(())
-    } else {
-      Err(Error::InvalidParam("kid"))
+    match (self.key_id.as_deref(), self.public) {
+      // Keys are selected by `kid` - any value is accepted unless explicitly configured.
+      (None, JwsVerifyingKey::KeySet(_) | JwsVerifyingKey::Resolver(_) | JwsVerifyingKey::AsyncResolver(_)) => Ok(()),
+      (expected, _) if expected == value => Ok(()),
+      (_, _) => Err(Error::InvalidParam("kid")),
     }
   }
 
-  fn verify(&self, algorithm: JwsAlgorithm, message: &[u8], signature: &[u8]) -> Result<()> {
+  fn verify_any<'k>(&self, input: &SigningInput<'b>, keys: impl IntoIterator<Item = &'k Jwk>) -> Result<()> {
+    let mut error: Error = Error::InvalidContent("Key (not found)");
+
+    for key in keys {
+      match self.verify(input.algorithm, &input.message, &input.signature, Secret::Jwk(key)) {
+        Ok(()) => return Ok(()),
+        Err(err) => error = err,
+      }
+    }
+
+    Err(error)
+  }
+
+  fn verify(&self, algorithm: JwsAlgorithm, message: &[u8], signature: &[u8], public: Secret<'_>) -> Result<()> {
     macro_rules! hmac {
       ($impl:ident, $key_len:ident, $message:expr, $signature:expr, $secret:expr) => {{
         let secret: Cow<'_, [u8]> = $secret.to_oct_key($key_len)?;
@@ -256,8 +370,6 @@ impl<'a, 'b> Decoder<'a, 'b> {
       }};
     }
 
-    let public: Secret<'_> = self.public;
-
     public.check_verifying_key(algorithm.name())?;
 
     match algorithm {
@@ -21,6 +21,7 @@ use crate::jws::CharSet;
 use crate::jws::JwsAlgorithm;
 use crate::jws::JwsFormat;
 use crate::jws::JwsHeader;
+use crate::jws::JwsKey;
 use crate::jws::Recipient;
 use crate::lib::*;
 use crate::utils::create_message;
@@ -45,6 +46,16 @@ struct Signature<'a> {
   signature: String,
 }
 
+impl<'a> Signature<'a> {
+  fn new(recipient: Recipient<'a>, protected: Option<String>, signature: String) -> Self {
+    Self {
+      header: recipient.unprotected,
+      protected,
+      signature,
+    }
+  }
+}
+
 #[derive(Serialize)]
 struct General<'a> {
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -120,6 +131,55 @@ impl<'a> Encoder<'a> {
   }
 
   pub fn encode(&self, claims: &[u8]) -> Result<String> {
+    let mut tmp: String = String::new();
+    let payload: &[u8] = self.prepare_payload(claims, &mut tmp)?;
+
+    let encoded: Vec<Signature<'a>> = self
+      .recipients
+      .iter()
+      .copied()
+      .map(|recipient| {
+        let (algorithm, protected, message): (JwsAlgorithm, Option<String>, Vec<u8>) =
+          signing_input(payload, recipient)?;
+        let signature: String = sign_recipient(algorithm, &message, recipient)?;
+
+        Ok(Signature::new(recipient, protected, signature))
+      })
+      .collect::<Result<_>>()?;
+
+    self.serialize(payload, encoded)
+  }
+
+  pub async fn encode_serde_async<T>(&self, claims: &T) -> Result<String>
+  where
+    T: Serialize,
+  {
+    self.encode_async(&to_vec(claims)?).await
+  }
+
+  /// Encodes the token like [`Encoder::encode`], additionally supporting recipients
+  /// with an [`AsyncJwsSigner`][crate::jws::AsyncJwsSigner].
+  pub async fn encode_async(&self, claims: &[u8]) -> Result<String> {
+    let mut tmp: String = String::new();
+    let payload: &[u8] = self.prepare_payload(claims, &mut tmp)?;
+
+    let mut encoded: Vec<Signature<'a>> = Vec::with_capacity(self.recipients.len());
+
+    for recipient in self.recipients.iter().copied() {
+      let (algorithm, protected, message): (JwsAlgorithm, Option<String>, Vec<u8>) = signing_input(payload, recipient)?;
+
+      let signature: String = match recipient.key {
+        JwsKey::AsyncSigner(signer) => encode_b64(signer.sign(algorithm, &message).await?),
+        _ => sign_recipient(algorithm, &message, recipient)?,
+      };
+
+      encoded.push(Signature::new(recipient, protected, signature));
+    }
+
+    self.serialize(payload, encoded)
+  }
+
+  fn prepare_payload<'p>(&self, claims: &'p [u8], tmp: &'p mut String) -> Result<&'p [u8]> {
     if self.recipients.is_empty() {
       return Err(Error::SigError("Missing Recipients"));
     }
@@ -127,28 +187,22 @@ impl<'a> Encoder<'a> {
     self.validate()?;
 
     let b64: bool = extract_b64(self.recipients[0].protected);
-    let tmp: String;
 
     // Extract the "b64" header parameter and encode the payload as required.
     //
     // See: https://tools.ietf.org/html/rfc7797#section-3
-    let payload: &[u8] = if b64 {
-      tmp = encode_b64(claims);
-      tmp.as_bytes()
+    if b64 {
+      *tmp = encode_b64(claims);
+      Ok(tmp.as_bytes())
     } else if self.detached {
-      claims
+      Ok(claims)
     } else {
       self.charset.validate(claims)?;
-      claims
-    };
-
-    let encoded: Vec<Signature<'a>> = self
-      .recipients
-      .iter()
-      .copied()
-      .map(|recipient| encode_recipient(payload, recipient))
-      .collect::<Result<_>>()?;
+      Ok(claims)
+    }
+  }
 
+  fn serialize(&self, payload: &[u8], encoded: Vec<Signature<'a>>) -> Result<String> {
     assert_eq!(encoded.len(), self.recipients.len());
 
     match (self.format, &*encoded) {
@@ -179,7 +233,7 @@ impl<'a> Encoder<'a> {
     }
   }
 
-  fn format_payload(&self, payload: &'a [u8]) -> Option<&'a str> {
+  fn format_payload<'p>(&self, payload: &'p [u8]) -> Option<&'p str> {
     if self.detached {
       None
     } else {
@@ -248,7 +302,8 @@ fn validate_recipient_b64(recipients: &[Recipient<'_>]) -> Result<()> {
     .map(|_| ())
 }
 
-fn encode_recipient<'a>(payload: &[u8], recipient: Recipient<'a>) -> Result<Signature<'a>> {
+/// Returns the algorithm, the encoded protected header, and the JWS Signing Input of the `recipient`.
+fn signing_input(payload: &[u8], recipient: Recipient<'_>) -> Result<(JwsAlgorithm, Option<String>, Vec<u8>)> {
   let algorithm: JwsAlgorithm = recipient
     .protected
     .map(JwsHeader::alg)
@@ -258,16 +313,20 @@ fn encode_recipient<'a>(payload: &[u8], recipient: Recipient<'a>) -> Result<Sign
   let protected: Option<String> = recipient.protected.map(encode_b64_json).transpose()?;
   let header: &[u8] = protected.as_deref().map(str::as_bytes).unwrap_or_default();
   let message: Vec<u8> = create_message(header, payload);
-  let signature: String = sign(algorithm, &message, recipient)?;
 
-  Ok(Signature {
-    header: recipient.unprotected,
-    protected,
-    signature,
-  })
+  Ok((algorithm, protected, message))
 }
 
-fn sign(algorithm: JwsAlgorithm, message: &[u8], recipient: Recipient<'_>) -> Result<String> {
+/// Signs the JWS Signing Input of the `recipient` with its synchronous key.
+fn sign_recipient(algorithm: JwsAlgorithm, message: &[u8], recipient: Recipient<'_>) -> Result<String> {
+  match recipient.key {
+    JwsKey::Secret(secret) => sign(algorithm, message, secret, recipient.eddsa_curve),
+    JwsKey::Signer(signer) => Ok(encode_b64(signer.sign(algorithm, message)?)),
+    JwsKey::AsyncSigner(_) => Err(Error::SigError("Async Signer requires `encode_async`")),
+  }
+}
+
+fn sign(algorithm: JwsAlgorithm, message: &[u8], secret: Secret<'_>, eddsa_curve: EdCurve) -> Result<String> {
   macro_rules! hmac {
     ($impl:ident, $key_len:ident, $message:expr, $secret:expr) => {{
       let secret: Cow<'_, [u8]> = $secret.to_oct_key($key_len)?;
@@ -292,8 +351,6 @@ fn sign(algorithm: JwsAlgorithm, message: &[u8], recipient: Recipient<'_>) -> Re
     }};
   }
 
-  let secret: Secret<'_> = recipient.secret;
-
   secret.check_signing_key(algorithm.name())?;
 
   match algorithm {
@@ -311,7 +368,7 @@ fn sign(algorithm: JwsAlgorithm, message: &[u8], recipient: Recipient<'_>) -> Re
     JwsAlgorithm::ES512 => Err(Error::AlgError("ES512")),
     JwsAlgorithm::ES256K => Ok(encode_b64(secret.to_k256_secret()?.sign(message)?)),
     JwsAlgorithm::NONE => Err(Error::AlgError("NONE")),
-    JwsAlgorithm::EdDSA => match recipient.eddsa_curve {
+    JwsAlgorithm::EdDSA => match eddsa_curve {
       EdCurve::Ed25519 => Ok(encode_b64(secret.to_ed25519_secret()?.sign(message).to_bytes())),
       EdCurve::Ed448 => Err(Error::AlgError("EdDSA/Ed448")),
     },
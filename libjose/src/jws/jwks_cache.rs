@@ -0,0 +1,131 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::cell::RefCell;
+use core::time::Duration;
+use std::time::Instant;
+
+use crate::error::Result;
+use crate::jwk::Jwk;
+use crate::jwk::JwkSet;
+use crate::jws::AsyncJwkResolver;
+use crate::jws::JwksProvider;
+use crate::jws::JwsAlgorithm;
+use crate::lib::*;
+
+/// An in-memory cache of the JWK Set of a [`JwksProvider`].
+///
+/// The JWK Set is fetched on first use and again once the cached copy is older
+/// than the configured time-to-live. If no key matches the `kid` of a token the
+/// JWK Set is re-fetched immediately to pick up rotated keys, but at most once
+/// per refresh interval.
+pub struct CachedJwks<P> {
+  provider: P,
+  ttl: Duration,
+  refresh_interval: Duration,
+  cache: RefCell<Option<(JwkSet, Instant)>>,
+}
+
+impl<P> CachedJwks<P>
+where
+  P: JwksProvider,
+{
+  /// The default time-to-live of the cached JWK Set.
+  pub const DEFAULT_TTL: Duration = Duration::from_secs(600);
+
+  /// The default minimum interval between re-fetches caused by unknown keys.
+  pub const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+  /// Creates a new `CachedJwks` fetching the JWK Set from `provider`.
+  pub fn new(provider: P) -> Self {
+    Self {
+      provider,
+      ttl: Self::DEFAULT_TTL,
+      refresh_interval: Self::DEFAULT_REFRESH_INTERVAL,
+      cache: RefCell::new(None),
+    }
+  }
+
+  pub fn ttl(mut self, value: Duration) -> Self {
+    self.ttl = value;
+    self
+  }
+
+  pub fn refresh_interval(mut self, value: Duration) -> Self {
+    self.refresh_interval = value;
+    self
+  }
+
+  /// Returns a reference to the underlying provider.
+  pub fn provider(&self) -> &P {
+    &self.provider
+  }
+
+  /// Discards the cached JWK Set.
+  pub fn clear(&self) {
+    self.cache.borrow_mut().take();
+  }
+
+  /// Returns the cached JWK Set, fetching it if missing or expired.
+  pub async fn key_set(&self) -> Result<JwkSet> {
+    if self.age().map_or(true, |age| age >= self.ttl) {
+      self.refresh().await?;
+    }
+
+    Ok(
+      self
+        .cache
+        .borrow()
+        .as_ref()
+        .map(|(keys, _)| keys.clone())
+        .unwrap_or_default(),
+    )
+  }
+
+  /// Fetches the JWK Set from the provider and replaces the cached copy.
+  pub async fn refresh(&self) -> Result<()> {
+    let keys: JwkSet = self.provider.fetch().await?;
+
+    *self.cache.borrow_mut() = Some((keys, Instant::now()));
+
+    Ok(())
+  }
+
+  fn age(&self) -> Option<Duration> {
+    self.cache.borrow().as_ref().map(|(_, fetched)| fetched.elapsed())
+  }
+
+  fn select(&self, kid: Option<&str>, algorithm: JwsAlgorithm) -> Vec<Jwk> {
+    self
+      .cache
+      .borrow()
+      .as_ref()
+      .map(|(keys, _)| keys.verifying_keys(kid, algorithm.name()).cloned().collect())
+      .unwrap_or_default()
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl<P> AsyncJwkResolver for CachedJwks<P>
+where
+  P: JwksProvider,
+{
+  async fn resolve(&self, kid: Option<&str>, algorithm: JwsAlgorithm) -> Result<Vec<Jwk>> {
+    let mut refreshed: bool = false;
+
+    if self.age().map_or(true, |age| age >= self.ttl) {
+      self.refresh().await?;
+      refreshed = true;
+    }
+
+    let keys: Vec<Jwk> = self.select(kid, algorithm);
+
+    // The issuer may have rotated its keys since the last fetch.
+    if keys.is_empty() && kid.is_some() && !refreshed && self.age().map_or(true, |age| age >= self.refresh_interval) {
+      self.refresh().await?;
+      return Ok(self.select(kid, algorithm));
+    }
+
+    Ok(keys)
+  }
+}
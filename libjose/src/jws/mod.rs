@@ -9,7 +9,11 @@ mod decoder;
 mod encoder;
 mod format;
 mod header;
+#[cfg(feature = "std")]
+mod jwks_cache;
 mod recipient;
+mod resolver;
+mod signer;
 
 pub use self::algorithm::*;
 pub use self::charset::*;
@@ -17,4 +21,8 @@ pub use self::decoder::*;
 pub use self::encoder::*;
 pub use self::format::*;
 pub use self::header::*;
+#[cfg(feature = "std")]
+pub use self::jwks_cache::*;
 pub use self::recipient::*;
+pub use self::resolver::*;
+pub use self::signer::*;
@@ -2,15 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::jwk::EdCurve;
+use crate::jws::AsyncJwsSigner;
 use crate::jws::JwsHeader;
+use crate::jws::JwsKey;
+use crate::jws::JwsSigner;
 use crate::utils::Secret;
 
 #[derive(Clone, Copy)]
 pub struct Recipient<'a> {
   /// The curve used for EdDSA signatures.
   pub eddsa_curve: EdCurve,
-  /// The private key used for signature creation.
-  pub secret: Secret<'a>,
+  /// The private key or signer used for signature creation.
+  pub key: JwsKey<'a>,
   /// The integrity-protected JOSE header.
   pub protected: Option<&'a JwsHeader>,
   /// The non integrity-protected JOSE header.
@@ -19,9 +22,25 @@ pub struct Recipient<'a> {
 
 impl<'a> Recipient<'a> {
   pub fn new(secret: impl Into<Secret<'a>>) -> Self {
+    Self::new_with_key(JwsKey::Secret(secret.into()))
+  }
+
+  /// Creates a new [`Recipient`] signing with the given `signer` instead of in-memory key material.
+  pub fn new_with_signer(signer: &'a dyn JwsSigner) -> Self {
+    Self::new_with_key(JwsKey::Signer(signer))
+  }
+
+  /// Creates a new [`Recipient`] signing with the given asynchronous `signer`.
+  ///
+  /// The token must be created with [`Encoder::encode_async`][crate::jws::Encoder::encode_async].
+  pub fn new_with_async_signer(signer: &'a dyn AsyncJwsSigner) -> Self {
+    Self::new_with_key(JwsKey::AsyncSigner(signer))
+  }
+
+  fn new_with_key(key: JwsKey<'a>) -> Self {
     Self {
       eddsa_curve: EdCurve::Ed25519,
-      secret: secret.into(),
+      key,
       protected: None,
       unprotected: None,
     }
@@ -0,0 +1,58 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::Result;
+use crate::jwk::Jwk;
+use crate::jwk::JwkSet;
+use crate::jws::JwsAlgorithm;
+use crate::lib::*;
+use crate::utils::Secret;
+
+/// Looks up the candidate keys for verifying a signature, e.g. from a key store
+/// indexed by key id.
+pub trait JwkResolver {
+  /// Returns the keys that may verify a signature created with `algorithm` by
+  /// the key identified by `kid`.
+  fn resolve(&self, kid: Option<&str>, algorithm: JwsAlgorithm) -> Result<Vec<Jwk>>;
+}
+
+impl<F> JwkResolver for F
+where
+  F: Fn(Option<&str>, JwsAlgorithm) -> Result<Vec<Jwk>>,
+{
+  fn resolve(&self, kid: Option<&str>, algorithm: JwsAlgorithm) -> Result<Vec<Jwk>> {
+    self(kid, algorithm)
+  }
+}
+
+/// The asynchronous variant of [`JwkResolver`], used by [`Decoder::decode_async`][crate::jws::Decoder::decode_async].
+#[async_trait::async_trait(?Send)]
+pub trait AsyncJwkResolver {
+  /// Returns the keys that may verify a signature created with `algorithm` by
+  /// the key identified by `kid`.
+  ///
+  /// See [`JwkResolver::resolve`].
+  async fn resolve(&self, kid: Option<&str>, algorithm: JwsAlgorithm) -> Result<Vec<Jwk>>;
+}
+
+/// Retrieves the current JWK Set of an issuer, e.g. from its `jwks_uri`.
+#[async_trait::async_trait(?Send)]
+pub trait JwksProvider {
+  /// Fetches the current JWK Set.
+  async fn fetch(&self) -> Result<JwkSet>;
+}
+
+/// The key(s) used by a [`Decoder`][crate::jws::Decoder] to verify signatures.
+#[derive(Clone, Copy)]
+pub enum JwsVerifyingKey<'a> {
+  /// A single public key.
+  Secret(Secret<'a>),
+  /// A set of public keys, selected by `kid`, `alg`, `use` and `key_ops`.
+  KeySet(&'a JwkSet),
+  /// A resolver looking up the public keys.
+  Resolver(&'a dyn JwkResolver),
+  /// An asynchronous resolver looking up the public keys.
+  ///
+  /// Only supported by [`Decoder::decode_async`][crate::jws::Decoder::decode_async].
+  AsyncResolver(&'a dyn AsyncJwkResolver),
+}
@@ -0,0 +1,39 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::Result;
+use crate::jws::JwsAlgorithm;
+use crate::lib::*;
+use crate::utils::Secret;
+
+/// Creates JWS signatures without exposing the private key to the [`Encoder`][crate::jws::Encoder],
+/// e.g. by delegating to a hardware security module or a secure key store.
+pub trait JwsSigner {
+  /// Returns the signature of the JWS Signing Input `message` for the given `algorithm`.
+  ///
+  /// The signature MUST be returned in the raw format defined for the `algorithm`, e.g. the
+  /// concatenation `R || S` for `ES256` and `EdDSA`.
+  fn sign(&self, algorithm: JwsAlgorithm, message: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// The asynchronous variant of [`JwsSigner`], used by [`Encoder::encode_async`][crate::jws::Encoder::encode_async].
+#[async_trait::async_trait(?Send)]
+pub trait AsyncJwsSigner {
+  /// Returns the signature of the JWS Signing Input `message` for the given `algorithm`.
+  ///
+  /// See [`JwsSigner::sign`].
+  async fn sign(&self, algorithm: JwsAlgorithm, message: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// The key used by a [`Recipient`][crate::jws::Recipient] to create its signature.
+#[derive(Clone, Copy)]
+pub enum JwsKey<'a> {
+  /// Private key material held in memory.
+  Secret(Secret<'a>),
+  /// A signer holding the private key.
+  Signer(&'a dyn JwsSigner),
+  /// An asynchronous signer holding the private key.
+  ///
+  /// Only supported by [`Encoder::encode_async`][crate::jws::Encoder::encode_async].
+  AsyncSigner(&'a dyn AsyncJwsSigner),
+}
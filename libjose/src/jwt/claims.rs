@@ -1,6 +1,7 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use serde::Deserializer;
 use serde_json::Map;
 use serde_json::Value;
 
@@ -23,10 +24,14 @@ pub struct JwtClaims<T = ()> {
   sub: Option<String>, // Subject
   /// Identifies the recipients that the JWT is intended for.
   ///
-  /// TODO: Handle single value
+  /// A single audience may be encoded as a string instead of an array.
   ///
   /// [More Info](https://tools.ietf.org/html/rfc7519#section-4.1.3)
-  #[serde(skip_serializing_if = "Option::is_none")]
+  #[serde(
+    default,
+    skip_serializing_if = "Option::is_none",
+    deserialize_with = "deserialize_aud"
+  )]
   aud: Option<Vec<String>>, // Audience
   /// Identifies the expiration time on or after which the JWT MUST NOT be
   /// accepted for processing.
@@ -205,3 +210,23 @@ impl<T> JwtClaims<T> {
     self.custom = Some(value.into());
   }
 }
+
+// Deserializes an audience claim (aud) given as a single string or an array of strings.
+fn deserialize_aud<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  #[derive(Deserialize)]
+  #[serde(untagged)]
+  enum Audience {
+    One(String),
+    Many(Vec<String>),
+  }
+
+  Ok(
+    <Option<Audience> as serde::Deserialize<'de>>::deserialize(deserializer)?.map(|audience| match audience {
+      Audience::One(value) => vec![value],
+      Audience::Many(values) => values,
+    }),
+  )
+}
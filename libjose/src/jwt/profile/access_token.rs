@@ -0,0 +1,94 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::jws::JwsHeader;
+use crate::jwt::custom_str;
+use crate::jwt::validate_media_type;
+use crate::jwt::validate_str;
+use crate::jwt::ClaimError;
+use crate::jwt::CoreProfile;
+use crate::jwt::JwtClaims;
+use crate::jwt::JwtProfile;
+use crate::jwt::TimeCop;
+use crate::jwt::Validation;
+use crate::lib::*;
+
+/// Validation profile for OAuth 2.0 access tokens.
+///
+/// [More Info](https://tools.ietf.org/html/rfc9068#section-4)
+#[derive(Clone, Debug)]
+pub struct AccessTokenProfile {
+  core: CoreProfile,
+  rule_client_id: Validation,
+  scopes: Vec<String>,
+}
+
+impl AccessTokenProfile {
+  /// The media type of JWT access tokens.
+  pub const TYP: &'static str = "at+jwt";
+
+  /// Creates a new `AccessTokenProfile` accepting tokens of the given `issuer`
+  /// intended for the resource server identified by `audience`.
+  pub fn new(issuer: impl Into<String>, audience: impl Into<String>) -> Self {
+    let mut core: CoreProfile = CoreProfile::new();
+    core.set_iss(issuer.into());
+    core.set_aud(audience.into());
+    core.set_sub(Validation::Required);
+    core.set_jti(Validation::Required);
+    core.set_exp(Validation::Required);
+    core.set_iat(Validation::Required);
+
+    Self {
+      core,
+      rule_client_id: Validation::Required,
+      scopes: Vec::new(),
+    }
+  }
+
+  /// Sets validation rules for the client identifier claim (client_id).
+  pub fn set_client_id(&mut self, value: impl Into<Validation>) {
+    self.rule_client_id = value.into();
+  }
+
+  /// Adds a scope that MUST be granted by the scope claim (scope).
+  pub fn add_scope(&mut self, value: impl Into<String>) {
+    self.scopes.push(value.into());
+  }
+
+  /// Sets options for timestamp validation.
+  pub fn set_timecop(&mut self, value: impl Into<TimeCop>) {
+    self.core.set_timecop(value);
+  }
+}
+
+impl JwtProfile for AccessTokenProfile {
+  fn validate_token(&self, header: &JwsHeader, claims: &JwtClaims<Map<String, Value>>, errors: &mut Vec<ClaimError>) {
+    errors.extend(validate_media_type("typ", header.typ(), &[Self::TYP], true));
+
+    self.core.validate_into(claims, errors);
+
+    errors.extend(validate_str(
+      "client_id",
+      &self.rule_client_id,
+      custom_str(claims, "client_id"),
+    ));
+
+    if !self.scopes.is_empty() {
+      match custom_str(claims, "scope") {
+        Some(scope) => {
+          if !self
+            .scopes
+            .iter()
+            .all(|expected| scope.split(' ').any(|scope| scope == expected))
+          {
+            errors.push(ClaimError::InvalidClaim("scope"));
+          }
+        }
+        None => errors.push(ClaimError::MissingClaim("scope")),
+      }
+    }
+  }
+}
@@ -2,15 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use core::time::Duration;
+use serde_json::Map;
+use serde_json::Value;
 
 #[cfg(feature = "std")]
 use std::time::SystemTime;
 #[cfg(not(feature = "std"))]
 type SystemTime = ();
 
-use crate::error::Error;
 use crate::error::Result;
+use crate::jws::JwsHeader;
+use crate::jwt::ClaimError;
 use crate::jwt::JwtClaims;
+use crate::jwt::JwtProfile;
 use crate::lib::*;
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -97,77 +101,73 @@ impl CoreProfile {
     self.timecop = Some(value.into());
   }
 
+  /// Returns the options for timestamp validation.
+  pub fn timecop(&self) -> TimeCop {
+    self.timecop.unwrap_or_else(TimeCop::new)
+  }
+
   /// Validates the given claims with the current rule configuration.
+  ///
+  /// Returns the first validation failure as an error.
   pub fn validate<T>(&self, claims: &JwtClaims<T>) -> Result<()> {
+    let mut errors: Vec<ClaimError> = Vec::new();
+
+    self.validate_into(claims, &mut errors);
+
+    match errors.into_iter().next() {
+      Some(error) => Err(error.into()),
+      None => Ok(()),
+    }
+  }
+
+  /// Validates the given claims with the current rule configuration and appends
+  /// all validation failures to `errors`.
+  pub fn validate_into<T>(&self, claims: &JwtClaims<T>, errors: &mut Vec<ClaimError>) {
     // Validate registered claims with the current rules.
-    self.validate_aud(claims)?;
-    self.validate_iss(claims)?;
-    self.validate_jti(claims)?;
-    self.validate_sub(claims)?;
+    errors.extend(self.validate_aud(claims));
+    errors.extend(self.validate_iss(claims));
+    errors.extend(self.validate_jti(claims));
+    errors.extend(self.validate_sub(claims));
 
     #[cfg(feature = "std")]
     {
       // Check expiration/issuance time/etc.
-      self.validate_timestamps(claims)?;
+      self.validate_timestamps(claims, errors);
     }
-
-    Ok(())
   }
 
   // Validates the audience (aud) claim value.
-  fn validate_aud<T>(&self, claims: &JwtClaims<T>) -> Result<()> {
+  fn validate_aud<T>(&self, claims: &JwtClaims<T>) -> Option<ClaimError> {
     match (&self.rule_aud, claims.aud()) {
-      (Validation::Optional, _) => Ok(()),
-      (Validation::Required, Some(_)) => Ok(()),
-      (Validation::Matching(expected), Some(aud)) if aud.contains(expected) => Ok(()),
-      (Validation::Required, _) => Err(Error::MissingClaim("aud")),
-      (Validation::Matching(_), _) => Err(Error::InvalidClaim("aud")),
+      (Validation::Optional, _) => None,
+      (Validation::Required, Some(_)) => None,
+      (Validation::Matching(expected), Some(aud)) if aud.contains(expected) => None,
+      (Validation::Required, _) => Some(ClaimError::MissingClaim("aud")),
+      (Validation::Matching(_), _) => Some(ClaimError::InvalidClaim("aud")),
     }
   }
 
   // Validates the issuer (iss) claim value.
-  fn validate_iss<T>(&self, claims: &JwtClaims<T>) -> Result<()> {
-    match (&self.rule_iss, claims.iss()) {
-      (Validation::Optional, _) => Ok(()),
-      (Validation::Required, Some(_)) => Ok(()),
-      (Validation::Matching(expected), Some(iss)) if iss == expected => Ok(()),
-      (Validation::Required, _) => Err(Error::MissingClaim("iss")),
-      (Validation::Matching(_), _) => Err(Error::InvalidClaim("iss")),
-    }
+  fn validate_iss<T>(&self, claims: &JwtClaims<T>) -> Option<ClaimError> {
+    validate_str("iss", &self.rule_iss, claims.iss())
   }
 
   // Validates the JWT ID (jti) claim value.
-  fn validate_jti<T>(&self, claims: &JwtClaims<T>) -> Result<()> {
-    match (&self.rule_jti, claims.jti()) {
-      (Validation::Optional, _) => Ok(()),
-      (Validation::Required, Some(_)) => Ok(()),
-      (Validation::Matching(expected), Some(jti)) if jti == expected => Ok(()),
-      (Validation::Required, _) => Err(Error::MissingClaim("jti")),
-      (Validation::Matching(_), _) => Err(Error::InvalidClaim("jti")),
-    }
+  fn validate_jti<T>(&self, claims: &JwtClaims<T>) -> Option<ClaimError> {
+    validate_str("jti", &self.rule_jti, claims.jti())
   }
 
   // Validates the subject (sub) claim value.
-  fn validate_sub<T>(&self, claims: &JwtClaims<T>) -> Result<()> {
-    match (&self.rule_sub, claims.sub()) {
-      (Validation::Optional, _) => Ok(()),
-      (Validation::Required, Some(_)) => Ok(()),
-      (Validation::Matching(expected), Some(sub)) if sub == expected => Ok(()),
-      (Validation::Required, _) => Err(Error::MissingClaim("sub")),
-      (Validation::Matching(_), _) => Err(Error::InvalidClaim("sub")),
-    }
+  fn validate_sub<T>(&self, claims: &JwtClaims<T>) -> Option<ClaimError> {
+    validate_str("sub", &self.rule_sub, claims.sub())
   }
 
   // Validates the registered timestamp claims (exp, nbf, iat)
   #[cfg(feature = "std")]
-  fn validate_timestamps<T>(&self, claims: &JwtClaims<T>) -> Result<()> {
-    fn timestamp(value: i64) -> Duration {
-      use core::convert::TryFrom as _;
-      u64::try_from(value).map(Duration::from_secs).unwrap_or_default()
-    }
-
-    let timecop: TimeCop = self.timecop.unwrap_or_else(TimeCop::new);
+  fn validate_timestamps<T>(&self, claims: &JwtClaims<T>, errors: &mut Vec<ClaimError>) {
+    let timecop: TimeCop = self.timecop();
     let current: Duration = timecop.resolve_current();
+    let leeway: Duration = timecop.leeway;
     let min_iat: Duration = timecop.min_iat.unwrap_or_default();
     let max_iat: Duration = timecop.max_iat.unwrap_or(current);
 
@@ -175,12 +175,12 @@ impl CoreProfile {
     match (&self.rule_exp, claims.exp()) {
       (Validation::Optional, _) => {}
       (Validation::Matching(_), _) => {}
-      (Validation::Required, Some(exp)) if timestamp(exp) <= current => {
-        return Err(Error::InvalidClaim("exp"));
+      (Validation::Required, Some(exp)) if timestamp(exp) + leeway <= current => {
+        errors.push(ClaimError::Expired);
       }
       (Validation::Required, Some(_)) => {}
       (Validation::Required, None) => {
-        return Err(Error::MissingClaim("exp"));
+        errors.push(ClaimError::MissingClaim("exp"));
       }
     }
 
@@ -188,12 +188,12 @@ impl CoreProfile {
     match (&self.rule_nbf, claims.nbf()) {
       (Validation::Optional, _) => {}
       (Validation::Matching(_), _) => {}
-      (Validation::Required, Some(nbf)) if timestamp(nbf) > current => {
-        return Err(Error::InvalidClaim("exp"));
+      (Validation::Required, Some(nbf)) if timestamp(nbf) > current + leeway => {
+        errors.push(ClaimError::NotYetValid);
       }
       (Validation::Required, Some(_)) => {}
       (Validation::Required, None) => {
-        return Err(Error::MissingClaim("nbf"));
+        errors.push(ClaimError::MissingClaim("nbf"));
       }
     }
 
@@ -201,19 +201,29 @@ impl CoreProfile {
     match (&self.rule_iat, claims.iat()) {
       (Validation::Optional, _) => {}
       (Validation::Matching(_), _) => {}
-      (Validation::Required, Some(iat)) if timestamp(iat) < min_iat => {
-        return Err(Error::InvalidClaim("iat"));
+      (Validation::Required, Some(iat)) if timestamp(iat) + leeway < min_iat => {
+        errors.push(ClaimError::InvalidIssuedAt);
       }
-      (Validation::Required, Some(iat)) if timestamp(iat) > max_iat => {
-        return Err(Error::InvalidClaim("iat"));
+      (Validation::Required, Some(iat)) if timestamp(iat) > max_iat + leeway => {
+        errors.push(ClaimError::InvalidIssuedAt);
       }
       (Validation::Required, Some(_)) => {}
       (Validation::Required, None) => {
-        return Err(Error::MissingClaim("iat"));
+        errors.push(ClaimError::MissingClaim("iat"));
       }
     }
+  }
+}
 
-    Ok(())
+impl Default for CoreProfile {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl JwtProfile for CoreProfile {
+  fn validate_token(&self, _header: &JwsHeader, claims: &JwtClaims<Map<String, Value>>, errors: &mut Vec<ClaimError>) {
+    self.validate_into(claims, errors);
   }
 }
 
@@ -226,6 +236,8 @@ pub struct TimeCop {
   max_iat: Option<Duration>,
   /// The minimum allowed time of the issued-at claim (iat).
   min_iat: Option<Duration>,
+  /// The tolerated clock skew between the issuer and the validator.
+  leeway: Duration,
 }
 
 impl TimeCop {
@@ -235,6 +247,7 @@ impl TimeCop {
       current: None,
       max_iat: None,
       min_iat: None,
+      leeway: Duration::from_secs(0),
     }
   }
 
@@ -250,8 +263,18 @@ impl TimeCop {
     self.min_iat = Some(value.into());
   }
 
+  /// Sets the tolerated clock skew applied to all time-related claims.
+  pub fn set_leeway(&mut self, value: impl Into<Duration>) {
+    self.leeway = value.into();
+  }
+
+  /// Returns the tolerated clock skew applied to all time-related claims.
+  pub fn leeway(&self) -> Duration {
+    self.leeway
+  }
+
   #[cfg(feature = "std")]
-  fn resolve_current(&self) -> Duration {
+  pub(crate) fn resolve_current(&self) -> Duration {
     self
       .current
       .unwrap_or_else(SystemTime::now)
@@ -259,3 +282,75 @@ impl TimeCop {
       .expect("Epoch Fail")
   }
 }
+
+impl Default for TimeCop {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Converts a NumericDate claim value to a `Duration` since the Unix epoch.
+#[cfg(feature = "std")]
+pub(crate) fn timestamp(value: i64) -> Duration {
+  use core::convert::TryFrom as _;
+  u64::try_from(value).map(Duration::from_secs).unwrap_or_default()
+}
+
+/// Validates a string claim against the given rule.
+pub(crate) fn validate_str(name: &'static str, rule: &Validation, value: Option<&str>) -> Option<ClaimError> {
+  match (rule, value) {
+    (Validation::Optional, _) => None,
+    (Validation::Required, Some(_)) => None,
+    (Validation::Matching(expected), Some(value)) if value == expected => None,
+    (Validation::Required, _) => Some(ClaimError::MissingClaim(name)),
+    (Validation::Matching(_), _) => Some(ClaimError::InvalidClaim(name)),
+  }
+}
+
+/// Validates a media type header parameter (`typ`, `cty`) against a list of allowed values.
+///
+/// Values are compared case-insensitively, with an optional `application/` prefix
+/// ([More Info](https://tools.ietf.org/html/rfc7515#section-4.1.9)).
+pub(crate) fn validate_media_type(
+  name: &'static str,
+  value: Option<&str>,
+  allowed: &[&str],
+  required: bool,
+) -> Option<ClaimError> {
+  fn normalize(value: &str) -> &str {
+    match value.get(..12) {
+      Some(prefix) if prefix.eq_ignore_ascii_case("application/") => &value[12..],
+      _ => value,
+    }
+  }
+
+  match value {
+    Some(value)
+      if allowed
+        .iter()
+        .any(|allowed| normalize(value).eq_ignore_ascii_case(allowed)) =>
+    {
+      None
+    }
+    Some(_) => Some(ClaimError::InvalidHeader(name)),
+    None if required => Some(ClaimError::MissingHeader(name)),
+    None => None,
+  }
+}
+
+/// Returns the value of a custom string claim.
+pub(crate) fn custom_str<'a>(claims: &'a JwtClaims<Map<String, Value>>, name: &str) -> Option<&'a str> {
+  claims
+    .custom()
+    .and_then(|custom| custom.get(name))
+    .and_then(Value::as_str)
+}
+
+/// Returns the value of a custom NumericDate claim.
+#[cfg(feature = "std")]
+pub(crate) fn custom_i64(claims: &JwtClaims<Map<String, Value>>, name: &str) -> Option<i64> {
+  claims
+    .custom()
+    .and_then(|custom| custom.get(name))
+    .and_then(Value::as_i64)
+}
@@ -0,0 +1,58 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+
+use crate::error::Error;
+
+/// A single failure reported by a [`JwtProfile`][crate::jwt::JwtProfile].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum ClaimError {
+  /// A required header parameter is missing.
+  MissingHeader(&'static str),
+  /// A header parameter has an unexpected value.
+  InvalidHeader(&'static str),
+  /// A required claim is missing.
+  MissingClaim(&'static str),
+  /// A claim has an unexpected value.
+  InvalidClaim(&'static str),
+  /// The token has expired (exp).
+  Expired,
+  /// The token is not yet valid (nbf).
+  NotYetValid,
+  /// The token was issued outside of the accepted issuance period (iat).
+  InvalidIssuedAt,
+  /// The end-user authentication is older than the accepted maximum age (auth_time).
+  AuthenticationExpired,
+}
+
+impl Display for ClaimError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    match self {
+      Self::MissingHeader(inner) => f.write_fmt(format_args!("Missing Header: {}", inner)),
+      Self::InvalidHeader(inner) => f.write_fmt(format_args!("Invalid Header: {}", inner)),
+      Self::MissingClaim(inner) => f.write_fmt(format_args!("Missing Claim: {}", inner)),
+      Self::InvalidClaim(inner) => f.write_fmt(format_args!("Invalid Claim: {}", inner)),
+      Self::Expired => f.write_str("Token Expired"),
+      Self::NotYetValid => f.write_str("Token Not Yet Valid"),
+      Self::InvalidIssuedAt => f.write_str("Invalid Issuance Time"),
+      Self::AuthenticationExpired => f.write_str("Authentication Expired"),
+    }
+  }
+}
+
+impl From<ClaimError> for Error {
+  fn from(other: ClaimError) -> Self {
+    match other {
+      ClaimError::MissingHeader(inner) => Self::MissingParam(inner),
+      ClaimError::InvalidHeader(inner) => Self::InvalidParam(inner),
+      ClaimError::MissingClaim(inner) => Self::MissingClaim(inner),
+      ClaimError::InvalidClaim(inner) => Self::InvalidClaim(inner),
+      ClaimError::Expired => Self::InvalidClaim("exp"),
+      ClaimError::NotYetValid => Self::InvalidClaim("nbf"),
+      ClaimError::InvalidIssuedAt => Self::InvalidClaim("iat"),
+      ClaimError::AuthenticationExpired => Self::InvalidClaim("auth_time"),
+    }
+  }
+}
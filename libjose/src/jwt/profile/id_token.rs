@@ -0,0 +1,192 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::time::Duration;
+use crypto::hashes::sha::SHA256;
+use crypto::hashes::sha::SHA256_LEN;
+use crypto::hashes::sha::SHA384;
+use crypto::hashes::sha::SHA384_LEN;
+use crypto::hashes::sha::SHA512;
+use crypto::hashes::sha::SHA512_LEN;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::jws::JwsAlgorithm;
+use crate::jws::JwsHeader;
+#[cfg(feature = "std")]
+use crate::jwt::custom_i64;
+use crate::jwt::custom_str;
+use crate::jwt::validate_media_type;
+use crate::jwt::ClaimError;
+use crate::jwt::CoreProfile;
+use crate::jwt::JwtClaims;
+use crate::jwt::JwtProfile;
+use crate::jwt::TimeCop;
+use crate::jwt::Validation;
+use crate::lib::*;
+use crate::utils::encode_b64;
+
+/// Validation profile for OpenID Connect ID tokens.
+///
+/// [More Info](https://openid.net/specs/openid-connect-core-1_0.html#IDTokenValidation)
+#[derive(Clone, Debug)]
+pub struct IdTokenProfile {
+  core: CoreProfile,
+  client_id: String,
+  nonce: Option<String>,
+  access_token: Option<String>,
+  max_age: Option<Duration>,
+  require_auth_time: bool,
+}
+
+impl IdTokenProfile {
+  /// Creates a new `IdTokenProfile` accepting tokens of the given `issuer`
+  /// issued to the relying party identified by `client_id`.
+  pub fn new(issuer: impl Into<String>, client_id: impl Into<String>) -> Self {
+    let client_id: String = client_id.into();
+
+    let mut core: CoreProfile = CoreProfile::new();
+    core.set_iss(issuer.into());
+    core.set_aud(client_id.clone());
+    core.set_sub(Validation::Required);
+    core.set_exp(Validation::Required);
+    core.set_iat(Validation::Required);
+
+    Self {
+      core,
+      client_id,
+      nonce: None,
+      access_token: None,
+      max_age: None,
+      require_auth_time: false,
+    }
+  }
+
+  /// Sets the nonce sent in the authentication request; the nonce claim (nonce)
+  /// MUST match.
+  pub fn set_nonce(&mut self, value: impl Into<String>) {
+    self.nonce = Some(value.into());
+  }
+
+  /// Sets the access token issued alongside the ID token; the access token hash
+  /// claim (at_hash) MUST match.
+  pub fn set_access_token(&mut self, value: impl Into<String>) {
+    self.access_token = Some(value.into());
+  }
+
+  /// Sets the maximum authentication age requested with `max_age`; the
+  /// authentication time claim (auth_time) MUST be within this period.
+  pub fn set_max_age(&mut self, value: impl Into<Duration>) {
+    self.max_age = Some(value.into());
+  }
+
+  /// Requires the authentication time claim (auth_time).
+  pub fn set_require_auth_time(&mut self, value: bool) {
+    self.require_auth_time = value;
+  }
+
+  /// Sets options for timestamp validation.
+  pub fn set_timecop(&mut self, value: impl Into<TimeCop>) {
+    self.core.set_timecop(value);
+  }
+
+  // Validates the authorized party (azp) claim value.
+  fn validate_azp(&self, claims: &JwtClaims<Map<String, Value>>) -> Option<ClaimError> {
+    let multiple_audiences: bool = claims.aud().map(|aud| aud.len() > 1).unwrap_or_default();
+
+    match custom_str(claims, "azp") {
+      Some(azp) if azp != self.client_id => Some(ClaimError::InvalidClaim("azp")),
+      Some(_) => None,
+      None if multiple_audiences => Some(ClaimError::MissingClaim("azp")),
+      None => None,
+    }
+  }
+
+  // Validates the nonce (nonce) claim value.
+  fn validate_nonce(&self, claims: &JwtClaims<Map<String, Value>>) -> Option<ClaimError> {
+    match (self.nonce.as_deref(), custom_str(claims, "nonce")) {
+      (None, _) => None,
+      (Some(expected), Some(nonce)) if nonce == expected => None,
+      (Some(_), Some(_)) => Some(ClaimError::InvalidClaim("nonce")),
+      (Some(_), None) => Some(ClaimError::MissingClaim("nonce")),
+    }
+  }
+
+  // Validates the access token hash (at_hash) claim value.
+  fn validate_at_hash(&self, header: &JwsHeader, claims: &JwtClaims<Map<String, Value>>) -> Option<ClaimError> {
+    let access_token: &str = self.access_token.as_deref()?;
+
+    match (custom_str(claims, "at_hash"), token_hash(header.alg(), access_token)) {
+      (Some(at_hash), Some(expected)) if at_hash == expected => None,
+      (Some(_), _) => Some(ClaimError::InvalidClaim("at_hash")),
+      (None, _) => Some(ClaimError::MissingClaim("at_hash")),
+    }
+  }
+
+  // Validates the authentication time (auth_time) claim value.
+  #[cfg(feature = "std")]
+  fn validate_auth_time(&self, claims: &JwtClaims<Map<String, Value>>) -> Option<ClaimError> {
+    let required: bool = self.require_auth_time || self.max_age.is_some();
+
+    match (custom_i64(claims, "auth_time"), self.max_age) {
+      (Some(auth_time), Some(max_age)) => {
+        let timecop: TimeCop = self.core.timecop();
+
+        if crate::jwt::timestamp(auth_time) + max_age + timecop.leeway() < timecop.resolve_current() {
+          Some(ClaimError::AuthenticationExpired)
+        } else {
+          None
+        }
+      }
+      (Some(_), None) => None,
+      (None, _) if required => Some(ClaimError::MissingClaim("auth_time")),
+      (None, _) => None,
+    }
+  }
+}
+
+impl JwtProfile for IdTokenProfile {
+  fn validate_token(&self, header: &JwsHeader, claims: &JwtClaims<Map<String, Value>>, errors: &mut Vec<ClaimError>) {
+    // ID tokens are not explicitly typed but some providers set "JWT".
+    errors.extend(validate_media_type("typ", header.typ(), &["JWT"], false));
+
+    self.core.validate_into(claims, errors);
+
+    errors.extend(self.validate_azp(claims));
+    errors.extend(self.validate_nonce(claims));
+    errors.extend(self.validate_at_hash(header, claims));
+
+    #[cfg(feature = "std")]
+    {
+      errors.extend(self.validate_auth_time(claims));
+    }
+  }
+}
+
+/// Computes the value of a token hash claim (at_hash, c_hash): the base64url-encoded
+/// left-most half of the hash of `token` with the hash algorithm of `algorithm`.
+///
+/// [More Info](https://openid.net/specs/openid-connect-core-1_0.html#CodeIDToken)
+pub fn token_hash(algorithm: JwsAlgorithm, token: &str) -> Option<String> {
+  macro_rules! digest {
+    ($impl:ident, $len:ident) => {{
+      let mut digest: [u8; $len] = [0; $len];
+      $impl(token.as_bytes(), &mut digest);
+      Some(encode_b64(&digest[..$len / 2]))
+    }};
+  }
+
+  match algorithm {
+    JwsAlgorithm::HS256 | JwsAlgorithm::RS256 | JwsAlgorithm::PS256 | JwsAlgorithm::ES256 | JwsAlgorithm::ES256K => {
+      digest!(SHA256, SHA256_LEN)
+    }
+    JwsAlgorithm::HS384 | JwsAlgorithm::RS384 | JwsAlgorithm::PS384 | JwsAlgorithm::ES384 => {
+      digest!(SHA384, SHA384_LEN)
+    }
+    // EdDSA with Ed25519 uses SHA-512.
+    JwsAlgorithm::HS512 | JwsAlgorithm::RS512 | JwsAlgorithm::PS512 | JwsAlgorithm::ES512 | JwsAlgorithm::EdDSA => {
+      digest!(SHA512, SHA512_LEN)
+    }
+    JwsAlgorithm::NONE => None,
+  }
+}
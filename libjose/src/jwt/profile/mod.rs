@@ -1,6 +1,18 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+mod access_token;
 mod core;
+mod error;
+mod id_token;
+mod traits;
+mod validator;
+mod vc_jwt;
 
+pub use self::access_token::*;
 pub use self::core::*;
+pub use self::error::*;
+pub use self::id_token::*;
+pub use self::traits::*;
+pub use self::validator::*;
+pub use self::vc_jwt::*;
@@ -0,0 +1,34 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::jws::JwsHeader;
+use crate::jwt::ClaimError;
+use crate::jwt::JwtClaims;
+use crate::lib::*;
+
+/// A set of validation rules for a specific kind of JSON Web Token.
+pub trait JwtProfile {
+  /// Validates the protected `header` and the `claims` of a token, appending
+  /// all validation failures to `errors`.
+  fn validate_token(&self, header: &JwsHeader, claims: &JwtClaims<Map<String, Value>>, errors: &mut Vec<ClaimError>);
+
+  /// Validates the protected `header` and the `claims` of a token, returning
+  /// all validation failures.
+  fn validate_all(&self, header: &JwsHeader, claims: &JwtClaims<Map<String, Value>>) -> Vec<ClaimError> {
+    let mut errors: Vec<ClaimError> = Vec::new();
+    self.validate_token(header, claims, &mut errors);
+    errors
+  }
+}
+
+impl<T> JwtProfile for &'_ T
+where
+  T: JwtProfile + ?Sized,
+{
+  fn validate_token(&self, header: &JwsHeader, claims: &JwtClaims<Map<String, Value>>, errors: &mut Vec<ClaimError>) {
+    (**self).validate_token(header, claims, errors)
+  }
+}
@@ -0,0 +1,105 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::de::DeserializeOwned;
+use serde_json::from_slice;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::error::Error;
+use crate::error::Result;
+use crate::jws::Decoder;
+use crate::jws::JwsHeader;
+use crate::jws::Token;
+use crate::jwt::ClaimError;
+use crate::jwt::JwtClaims;
+use crate::jwt::JwtProfile;
+use crate::lib::*;
+
+/// A decoded JSON Web Token whose claims have been validated.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JwtToken<T = Map<String, Value>> {
+  /// The protected header of the token.
+  pub header: JwsHeader,
+  /// The validated claims of the token.
+  pub claims: JwtClaims<T>,
+}
+
+/// Decodes JWS-secured JSON Web Tokens and validates them with a [`JwtProfile`].
+pub struct JwtValidator<'a, 'b, P> {
+  decoder: Decoder<'a, 'b>,
+  profile: P,
+}
+
+impl<'a, 'b, P> JwtValidator<'a, 'b, P>
+where
+  P: JwtProfile,
+{
+  /// Creates a new `JwtValidator` verifying signatures with `decoder` and
+  /// validating claims with `profile`.
+  pub fn new(decoder: Decoder<'a, 'b>, profile: P) -> Self {
+    Self { decoder, profile }
+  }
+
+  /// Returns a reference to the validation profile.
+  pub fn profile(&self) -> &P {
+    &self.profile
+  }
+
+  /// Returns a mutable reference to the validation profile, e.g. to set a nonce.
+  pub fn profile_mut(&mut self) -> &mut P {
+    &mut self.profile
+  }
+
+  /// Decodes and validates the token `data`.
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`Error::InvalidClaims`] listing every validation failure if the
+  /// signature is valid but the token does not satisfy the profile.
+  pub fn decode(&self, data: &'b [u8]) -> Result<JwtToken> {
+    self.decode_custom(data)
+  }
+
+  /// Decodes and validates the token `data`, deserializing the custom claims as `T`.
+  pub fn decode_custom<T>(&self, data: &'b [u8]) -> Result<JwtToken<T>>
+  where
+    T: DeserializeOwned,
+  {
+    self.validate(self.decoder.decode(data)?)
+  }
+
+  /// Decodes and validates the token `data` like [`JwtValidator::decode`], additionally
+  /// supporting decoders with an [`AsyncJwkResolver`][crate::jws::AsyncJwkResolver].
+  pub async fn decode_async(&self, data: &'b [u8]) -> Result<JwtToken> {
+    self.decode_custom_async(data).await
+  }
+
+  /// Decodes and validates the token `data` like [`JwtValidator::decode_custom`], additionally
+  /// supporting decoders with an [`AsyncJwkResolver`][crate::jws::AsyncJwkResolver].
+  pub async fn decode_custom_async<T>(&self, data: &'b [u8]) -> Result<JwtToken<T>>
+  where
+    T: DeserializeOwned,
+  {
+    self.validate(self.decoder.decode_async(data).await?)
+  }
+
+  fn validate<T>(&self, token: Token<'b>) -> Result<JwtToken<T>>
+  where
+    T: DeserializeOwned,
+  {
+    // Typed header parameters are only trusted if integrity protected.
+    let header: JwsHeader = token.protected.ok_or(Error::MissingParam("protected"))?;
+    let claims: JwtClaims<Map<String, Value>> = from_slice(&token.claims)?;
+    let errors: Vec<ClaimError> = self.profile.validate_all(&header, &claims);
+
+    if !errors.is_empty() {
+      return Err(Error::InvalidClaims(errors));
+    }
+
+    Ok(JwtToken {
+      header,
+      claims: from_slice(&token.claims)?,
+    })
+  }
+}
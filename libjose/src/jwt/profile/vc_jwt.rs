@@ -0,0 +1,132 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::jws::JwsHeader;
+use crate::jwt::custom_str;
+use crate::jwt::validate_media_type;
+use crate::jwt::ClaimError;
+use crate::jwt::CoreProfile;
+use crate::jwt::JwtClaims;
+use crate::jwt::JwtProfile;
+use crate::jwt::TimeCop;
+use crate::jwt::Validation;
+use crate::lib::*;
+
+/// The kind of object secured by a VC-JWT.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum VcJwtKind {
+  /// A Verifiable Credential in the `vc` claim.
+  Credential,
+  /// A Verifiable Presentation in the `vp` claim.
+  Presentation,
+}
+
+/// Validation profile for Verifiable Credentials and Presentations encoded as JWTs.
+///
+/// [More Info](https://www.w3.org/TR/vc-data-model/#json-web-token)
+#[derive(Clone, Debug)]
+pub struct VcJwtProfile {
+  core: CoreProfile,
+  kind: VcJwtKind,
+  nonce: Option<String>,
+}
+
+impl VcJwtProfile {
+  /// Creates a new `VcJwtProfile` for tokens containing the given `kind` of object.
+  pub fn new(kind: VcJwtKind) -> Self {
+    let mut core: CoreProfile = CoreProfile::new();
+    core.set_iss(Validation::Required);
+
+    // A credential's `issuanceDate` is encoded as `nbf`.
+    if kind == VcJwtKind::Credential {
+      core.set_nbf(Validation::Required);
+    }
+
+    Self {
+      core,
+      kind,
+      nonce: None,
+    }
+  }
+
+  /// Creates a new `VcJwtProfile` for Verifiable Credentials.
+  pub fn credential() -> Self {
+    Self::new(VcJwtKind::Credential)
+  }
+
+  /// Creates a new `VcJwtProfile` for Verifiable Presentations.
+  pub fn presentation() -> Self {
+    Self::new(VcJwtKind::Presentation)
+  }
+
+  /// Sets validation rules for the issuer claim (iss), i.e. the credential issuer
+  /// or the presentation holder.
+  pub fn set_iss(&mut self, value: impl Into<Validation>) {
+    self.core.set_iss(value);
+  }
+
+  /// Sets validation rules for the subject claim (sub), i.e. the credential subject.
+  pub fn set_sub(&mut self, value: impl Into<Validation>) {
+    self.core.set_sub(value);
+  }
+
+  /// Sets validation rules for the audience claim (aud), i.e. the presentation verifier.
+  pub fn set_aud(&mut self, value: impl Into<Validation>) {
+    self.core.set_aud(value);
+  }
+
+  /// Sets validation rules for the expiration claim (exp), i.e. the `expirationDate`.
+  pub fn set_exp(&mut self, value: impl Into<Validation>) {
+    self.core.set_exp(value);
+  }
+
+  /// Sets the challenge that the nonce claim (nonce) of a presentation MUST match.
+  pub fn set_nonce(&mut self, value: impl Into<String>) {
+    self.nonce = Some(value.into());
+  }
+
+  /// Sets options for timestamp validation.
+  pub fn set_timecop(&mut self, value: impl Into<TimeCop>) {
+    self.core.set_timecop(value);
+  }
+
+  fn typ(&self) -> &'static [&'static str] {
+    match self.kind {
+      VcJwtKind::Credential => &["JWT", "vc+ld+jwt"],
+      VcJwtKind::Presentation => &["JWT", "vp+ld+jwt"],
+    }
+  }
+
+  fn cty(&self) -> &'static [&'static str] {
+    match self.kind {
+      VcJwtKind::Credential => &["vc+ld+json"],
+      VcJwtKind::Presentation => &["vp+ld+json"],
+    }
+  }
+}
+
+impl JwtProfile for VcJwtProfile {
+  fn validate_token(&self, header: &JwsHeader, claims: &JwtClaims<Map<String, Value>>, errors: &mut Vec<ClaimError>) {
+    errors.extend(validate_media_type("typ", header.typ(), self.typ(), false));
+    errors.extend(validate_media_type("cty", header.cty(), self.cty(), false));
+
+    self.core.validate_into(claims, errors);
+
+    match self.kind {
+      VcJwtKind::Credential if claims.vc().is_none() => errors.push(ClaimError::MissingClaim("vc")),
+      VcJwtKind::Presentation if claims.vp().is_none() => errors.push(ClaimError::MissingClaim("vp")),
+      _ => {}
+    }
+
+    if let Some(expected) = self.nonce.as_deref() {
+      match custom_str(claims, "nonce") {
+        Some(nonce) if nonce == expected => {}
+        Some(_) => errors.push(ClaimError::InvalidClaim("nonce")),
+        None => errors.push(ClaimError::MissingClaim("nonce")),
+      }
+    }
+  }
+}
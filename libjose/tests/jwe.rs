@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use libjose::error::Result;
+use libjose::jwe::AsyncJweKeyAgreement;
 use libjose::jwe::Decoder;
 use libjose::jwe::Encoder;
 use libjose::jwe::JweAlgorithm;
@@ -9,8 +10,12 @@ use libjose::jwe::JweAlgorithm::*;
 use libjose::jwe::JweEncryption;
 use libjose::jwe::JweFormat;
 use libjose::jwe::JweHeader;
+use libjose::jwe::JweKeyAgreement;
 use libjose::jwe::Token;
+use libjose::jwk::EcdhCurve;
 use libjose::jwk::Jwk;
+use libjose::utils::diffie_hellman;
+use libjose::utils::Secret;
 
 const __RSA: bool = cfg!(not(feature = "test-rsa-enc"));
 
@@ -71,3 +76,50 @@ fn test_jwe_roundtrip() {
     }
   }
 }
+
+struct JwkKeyAgreement(Jwk);
+
+impl JweKeyAgreement for JwkKeyAgreement {
+  fn diffie_hellman(&self, curve: EcdhCurve, public: Secret<'_>) -> Result<Vec<u8>> {
+    diffie_hellman(curve, public, &self.0)
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl AsyncJweKeyAgreement for JwkKeyAgreement {
+  async fn diffie_hellman(&self, curve: EcdhCurve, public: Secret<'_>) -> Result<Vec<u8>> {
+    JweKeyAgreement::diffie_hellman(self, curve, public)
+  }
+}
+
+#[test]
+fn test_jwe_key_agreement() {
+  for alg in [ECDH_1PU, ECDH_1PU_A256KW] {
+    let header: JweHeader = JweHeader::new(alg, JweEncryption::A256GCM);
+
+    let secret: Jwk = Jwk::random((alg, JweEncryption::A256GCM)).unwrap();
+    let public: Jwk = secret.to_public();
+
+    let sender: JwkKeyAgreement = JwkKeyAgreement(Jwk::random((alg, JweEncryption::A256GCM)).unwrap());
+    let sender_public: Jwk = sender.0.to_public();
+
+    let mut decoder: Decoder = Decoder::new(&secret).public(&sender_public);
+
+    let encoder: Encoder = Encoder::new()
+      .protected(&header)
+      .key_agreement(&sender)
+      .recipient(&public);
+    let encoded: String = encoder.encode(CLAIMS).unwrap();
+    let decoded: Token = decoder.decode(encoded.as_bytes()).unwrap();
+    assert_eq!(decoded.1, CLAIMS);
+
+    let encoder: Encoder = Encoder::new()
+      .protected(&header)
+      .async_key_agreement(&sender)
+      .recipient(&public);
+    assert!(encoder.encode(CLAIMS).is_err());
+    let encoded: String = futures::executor::block_on(encoder.encode_async(CLAIMS)).unwrap();
+    let decoded: Token = decoder.decode(encoded.as_bytes()).unwrap();
+    assert_eq!(decoded.1, CLAIMS);
+  }
+}
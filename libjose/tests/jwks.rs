@@ -0,0 +1,267 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Read;
+use std::io::Write;
+use std::net::SocketAddr;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use futures::executor::block_on;
+use libjose::error::Error;
+use libjose::error::Result;
+use libjose::jwk::Jwk;
+use libjose::jwk::JwkSet;
+use libjose::jwk::JwkUse;
+use libjose::jws::CachedJwks;
+use libjose::jws::Decoder;
+use libjose::jws::Encoder;
+use libjose::jws::JwkResolver;
+use libjose::jws::JwksProvider;
+use libjose::jws::JwsAlgorithm;
+use libjose::jws::JwsHeader;
+use libjose::jws::Token;
+
+const CLAIMS: &[u8] = b"libjose";
+
+fn keypair(kid: &str) -> (Jwk, Jwk) {
+  let mut secret: Jwk = Jwk::random(JwsAlgorithm::EdDSA).unwrap();
+  secret.set_kid(kid);
+  let public: Jwk = secret.to_public();
+  (secret, public)
+}
+
+fn encode(secret: &Jwk) -> String {
+  let mut header: JwsHeader = JwsHeader::new(JwsAlgorithm::EdDSA);
+
+  if let Some(kid) = secret.kid() {
+    header.set_kid(kid);
+  }
+
+  Encoder::new().recipient((secret, &header)).encode(CLAIMS).unwrap()
+}
+
+/// A minimal HTTP server serving a JWK Set, standing in for an issuer's `jwks_uri`.
+struct JwksServer {
+  address: SocketAddr,
+  keys: Arc<Mutex<JwkSet>>,
+  requests: Arc<AtomicUsize>,
+}
+
+impl JwksServer {
+  fn spawn(keys: JwkSet) -> Self {
+    let listener: TcpListener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address: SocketAddr = listener.local_addr().unwrap();
+    let keys: Arc<Mutex<JwkSet>> = Arc::new(Mutex::new(keys));
+    let requests: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+
+    let server_keys: Arc<Mutex<JwkSet>> = Arc::clone(&keys);
+    let server_requests: Arc<AtomicUsize> = Arc::clone(&requests);
+
+    thread::spawn(move || {
+      for stream in listener.incoming() {
+        let mut stream: TcpStream = stream.unwrap();
+        let mut request: [u8; 1024] = [0; 1024];
+        let _ = stream.read(&mut request).unwrap();
+
+        server_requests.fetch_add(1, Ordering::SeqCst);
+
+        let body: String = serde_json::to_string(&*server_keys.lock().unwrap()).unwrap();
+        let response: String = format!(
+          "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+          body.len(),
+          body
+        );
+
+        stream.write_all(response.as_bytes()).unwrap();
+      }
+    });
+
+    Self {
+      address,
+      keys,
+      requests,
+    }
+  }
+
+  fn rotate(&self, keys: JwkSet) {
+    *self.keys.lock().unwrap() = keys;
+  }
+
+  fn requests(&self) -> usize {
+    self.requests.load(Ordering::SeqCst)
+  }
+}
+
+struct HttpJwksProvider {
+  address: SocketAddr,
+}
+
+#[async_trait::async_trait(?Send)]
+impl JwksProvider for HttpJwksProvider {
+  async fn fetch(&self) -> Result<JwkSet> {
+    let mut stream: TcpStream = TcpStream::connect(self.address).map_err(|_| Error::InvalidContent("JWKS"))?;
+    let request: String = format!(
+      "GET /.well-known/jwks.json HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+      self.address
+    );
+    stream
+      .write_all(request.as_bytes())
+      .map_err(|_| Error::InvalidContent("JWKS"))?;
+
+    let mut response: String = String::new();
+    stream
+      .read_to_string(&mut response)
+      .map_err(|_| Error::InvalidContent("JWKS"))?;
+
+    let body: &str = response
+      .split_once("\r\n\r\n")
+      .map(|(_, body)| body)
+      .ok_or(Error::InvalidContent("JWKS"))?;
+
+    serde_json::from_str(body).map_err(Into::into)
+  }
+}
+
+#[test]
+fn test_key_set_kid_selection() {
+  let (secret1, public1): (Jwk, Jwk) = keypair("key-1");
+  let (secret2, public2): (Jwk, Jwk) = keypair("key-2");
+  let (secret3, _): (Jwk, Jwk) = keypair("key-3");
+
+  let keys: JwkSet = vec![public1, public2].into_iter().collect();
+  let decoder: Decoder<'_, '_> = Decoder::new_with_key_set(&keys);
+
+  for secret in [&secret1, &secret2] {
+    let token: String = encode(secret);
+    let decoded: Token<'_> = decoder.decode(token.as_bytes()).unwrap();
+    assert_eq!(decoded.protected.unwrap().kid(), secret.kid());
+    assert_eq!(decoded.claims, CLAIMS);
+  }
+
+  // Unknown key.
+  assert!(decoder.decode(encode(&secret3).as_bytes()).is_err());
+
+  // Signed by a key of the set but claiming another `kid`.
+  let mut header: JwsHeader = JwsHeader::new(JwsAlgorithm::EdDSA);
+  header.set_kid("key-2");
+  let token: String = Encoder::new().recipient((&secret1, &header)).encode(CLAIMS).unwrap();
+  assert!(decoder.decode(token.as_bytes()).is_err());
+
+  // An explicitly configured `kid` is still enforced.
+  let decoder: Decoder<'_, '_> = Decoder::new_with_key_set(&keys).key_id("key-1");
+  assert!(decoder.decode(encode(&secret1).as_bytes()).is_ok());
+  assert!(decoder.decode(encode(&secret2).as_bytes()).is_err());
+}
+
+#[test]
+fn test_key_set_use_and_alg() {
+  let (secret1, mut public1): (Jwk, Jwk) = keypair("key-1");
+  let (secret2, mut public2): (Jwk, Jwk) = keypair("key-2");
+  let (secret3, mut public3): (Jwk, Jwk) = keypair("key-3");
+
+  public1.set_use(JwkUse::Encryption);
+  public2.set_alg(JwsAlgorithm::ES256.name());
+  public3.set_use(JwkUse::Signature);
+  public3.set_alg(JwsAlgorithm::EdDSA.name());
+
+  let keys: JwkSet = vec![public1, public2, public3].into_iter().collect();
+
+  assert_eq!(keys.verifying_keys(None, JwsAlgorithm::EdDSA.name()).count(), 1);
+  assert_eq!(
+    keys.verifying_keys(Some("key-3"), JwsAlgorithm::EdDSA.name()).count(),
+    1
+  );
+  assert_eq!(
+    keys.verifying_keys(Some("key-3"), JwsAlgorithm::ES256.name()).count(),
+    0
+  );
+
+  let decoder: Decoder<'_, '_> = Decoder::new_with_key_set(&keys);
+  assert!(decoder.decode(encode(&secret1).as_bytes()).is_err());
+  assert!(decoder.decode(encode(&secret2).as_bytes()).is_err());
+  assert!(decoder.decode(encode(&secret3).as_bytes()).is_ok());
+}
+
+#[test]
+fn test_resolver_callback() {
+  let (secret, public): (Jwk, Jwk) = keypair("key-1");
+
+  let resolver = |kid: Option<&str>, algorithm: JwsAlgorithm| -> Result<Vec<Jwk>> {
+    assert_eq!(algorithm, JwsAlgorithm::EdDSA);
+
+    match kid {
+      Some("key-1") => Ok(vec![public.clone()]),
+      _ => Ok(Vec::new()),
+    }
+  };
+
+  let decoder: Decoder<'_, '_> = Decoder::new_with_resolver(&resolver as &dyn JwkResolver);
+  assert!(decoder.decode(encode(&secret).as_bytes()).is_ok());
+
+  let (other, _): (Jwk, Jwk) = keypair("key-2");
+  assert!(decoder.decode(encode(&other).as_bytes()).is_err());
+}
+
+#[test]
+fn test_cached_jwks_rotation() {
+  let (secret1, public1): (Jwk, Jwk) = keypair("key-1");
+  let (secret2, public2): (Jwk, Jwk) = keypair("key-2");
+  let (secret3, _): (Jwk, Jwk) = keypair("key-3");
+
+  let server: JwksServer = JwksServer::spawn(vec![public1.clone()].into_iter().collect());
+  let jwks: CachedJwks<HttpJwksProvider> = CachedJwks::new(HttpJwksProvider {
+    address: server.address,
+  })
+  .refresh_interval(Duration::from_secs(0));
+
+  let decoder: Decoder<'_, '_> = Decoder::new_with_async_resolver(&jwks);
+
+  // The synchronous decoder does not support asynchronous resolvers.
+  assert!(decoder.decode(encode(&secret1).as_bytes()).is_err());
+  assert_eq!(server.requests(), 0);
+
+  // The key set is fetched once and then served from the cache.
+  assert!(block_on(decoder.decode_async(encode(&secret1).as_bytes())).is_ok());
+  assert!(block_on(decoder.decode_async(encode(&secret1).as_bytes())).is_ok());
+  assert_eq!(server.requests(), 1);
+
+  // An unknown `kid` causes a refresh, picking up the rotated key.
+  server.rotate(vec![public1, public2].into_iter().collect());
+  assert!(block_on(decoder.decode_async(encode(&secret2).as_bytes())).is_ok());
+  assert_eq!(server.requests(), 2);
+
+  assert!(block_on(decoder.decode_async(encode(&secret3).as_bytes())).is_err());
+  assert_eq!(server.requests(), 3);
+}
+
+#[test]
+fn test_cached_jwks_refresh_interval() {
+  let (secret1, public1): (Jwk, Jwk) = keypair("key-1");
+  let (secret2, _): (Jwk, Jwk) = keypair("key-2");
+
+  let server: JwksServer = JwksServer::spawn(vec![public1].into_iter().collect());
+  let jwks: CachedJwks<HttpJwksProvider> = CachedJwks::new(HttpJwksProvider {
+    address: server.address,
+  });
+
+  let decoder: Decoder<'_, '_> = Decoder::new_with_async_resolver(&jwks);
+
+  assert!(block_on(decoder.decode_async(encode(&secret1).as_bytes())).is_ok());
+  assert_eq!(server.requests(), 1);
+
+  // Unknown keys do not re-fetch the key set within the refresh interval.
+  assert!(block_on(decoder.decode_async(encode(&secret2).as_bytes())).is_err());
+  assert!(block_on(decoder.decode_async(encode(&secret2).as_bytes())).is_err());
+  assert_eq!(server.requests(), 1);
+
+  jwks.clear();
+  assert_eq!(block_on(jwks.key_set()).unwrap().len(), 1);
+  assert_eq!(server.requests(), 2);
+}
@@ -3,13 +3,18 @@
 
 use libjose::error::Result;
 use libjose::jwk::Jwk;
+use libjose::jws::AsyncJwsSigner;
 use libjose::jws::Decoder;
 use libjose::jws::Encoder;
 use libjose::jws::JwsAlgorithm;
 use libjose::jws::JwsAlgorithm::*;
 use libjose::jws::JwsFormat;
 use libjose::jws::JwsHeader;
+use libjose::jws::JwsSigner;
+use libjose::jws::Recipient;
 use libjose::jws::Token;
+use libjose::utils::Ed25519SecretKey;
+use libjose::utils::Secret;
 
 const __RSA: bool = cfg!(not(feature = "test-rsa-sig"));
 
@@ -66,3 +71,56 @@ fn test_jws_roundtrip() {
     roundtrip(*alg).unwrap();
   }
 }
+
+struct JwkSigner(Jwk);
+
+impl JwsSigner for JwkSigner {
+  fn sign(&self, algorithm: JwsAlgorithm, message: &[u8]) -> Result<Vec<u8>> {
+    assert_eq!(algorithm, EdDSA);
+    let secret: Ed25519SecretKey = Secret::Jwk(&self.0).to_ed25519_secret()?;
+    Ok(secret.sign(message).to_bytes().to_vec())
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl AsyncJwsSigner for JwkSigner {
+  async fn sign(&self, algorithm: JwsAlgorithm, message: &[u8]) -> Result<Vec<u8>> {
+    JwsSigner::sign(self, algorithm, message)
+  }
+}
+
+#[test]
+fn test_jws_signer() {
+  let header: JwsHeader = JwsHeader::new(EdDSA);
+  let signer: JwkSigner = JwkSigner(Jwk::random(EdDSA).unwrap());
+  let public: Jwk = signer.0.to_public();
+
+  let encoder: Encoder<'_> = Encoder::new().recipient(Recipient::new_with_signer(&signer).protected(&header));
+  let mut decoder: Decoder<'_, '_> = Decoder::new(&public);
+
+  let encoded: String = encoder.encode(CLAIMS).unwrap();
+  let decoded: Token<'_> = decoder.decode(encoded.as_bytes()).unwrap();
+
+  assert_eq!(decoded.claims, CLAIMS);
+
+  // The signature matches one created with the in-memory key.
+  let expected: String = Encoder::new().recipient((&signer.0, &header)).encode(CLAIMS).unwrap();
+  assert_eq!(encoded, expected);
+}
+
+#[test]
+fn test_jws_async_signer() {
+  let header: JwsHeader = JwsHeader::new(EdDSA);
+  let signer: JwkSigner = JwkSigner(Jwk::random(EdDSA).unwrap());
+  let public: Jwk = signer.0.to_public();
+
+  let encoder: Encoder<'_> = Encoder::new().recipient(Recipient::new_with_async_signer(&signer).protected(&header));
+  let mut decoder: Decoder<'_, '_> = Decoder::new(&public);
+
+  assert!(encoder.encode(CLAIMS).is_err());
+
+  let encoded: String = futures::executor::block_on(encoder.encode_async(CLAIMS)).unwrap();
+  let decoded: Token<'_> = decoder.decode(encoded.as_bytes()).unwrap();
+
+  assert_eq!(decoded.claims, CLAIMS);
+}
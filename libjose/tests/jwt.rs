@@ -0,0 +1,229 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+use std::time::SystemTime;
+
+use libjose::error::Error;
+use libjose::jwk::Jwk;
+use libjose::jws::Decoder;
+use libjose::jws::Encoder;
+use libjose::jws::JwsAlgorithm;
+use libjose::jws::JwsHeader;
+use libjose::jwt::token_hash;
+use libjose::jwt::AccessTokenProfile;
+use libjose::jwt::ClaimError;
+use libjose::jwt::IdTokenProfile;
+use libjose::jwt::JwtProfile;
+use libjose::jwt::JwtToken;
+use libjose::jwt::JwtValidator;
+use libjose::jwt::TimeCop;
+use libjose::jwt::VcJwtProfile;
+use serde_json::json;
+use serde_json::Value;
+
+const ISSUER: &str = "https://issuer.example.com";
+const AUDIENCE: &str = "https://rs.example.com";
+const CLIENT_ID: &str = "s6BhdRkqt3";
+
+fn now() -> i64 {
+  SystemTime::now()
+    .duration_since(SystemTime::UNIX_EPOCH)
+    .unwrap()
+    .as_secs() as i64
+}
+
+fn encode(secret: &Jwk, typ: Option<&str>, claims: &Value) -> String {
+  let mut header: JwsHeader = JwsHeader::new(JwsAlgorithm::EdDSA);
+
+  if let Some(typ) = typ {
+    header.set_typ(typ);
+  }
+
+  Encoder::new()
+    .recipient((secret, &header))
+    .encode(&serde_json::to_vec(claims).unwrap())
+    .unwrap()
+}
+
+fn validate<P: JwtProfile>(public: &Jwk, profile: P, token: &str) -> Result<JwtToken, Vec<ClaimError>> {
+  match JwtValidator::new(Decoder::new(public), profile).decode(token.as_bytes()) {
+    Ok(token) => Ok(token),
+    Err(Error::InvalidClaims(errors)) => Err(errors),
+    Err(error) => panic!("unexpected error: {}", error),
+  }
+}
+
+fn access_token_claims() -> Value {
+  json!({
+    "iss": ISSUER,
+    "aud": AUDIENCE,
+    "sub": "5ba552d67",
+    "client_id": CLIENT_ID,
+    "exp": now() + 60,
+    "iat": now(),
+    "jti": "dbe39bf3a3ba4238a513f51d6e1691c4",
+    "scope": "openid profile reademail",
+  })
+}
+
+#[test]
+fn test_access_token_profile() {
+  let secret: Jwk = Jwk::random(JwsAlgorithm::EdDSA).unwrap();
+  let public: Jwk = secret.to_public();
+
+  let mut profile: AccessTokenProfile = AccessTokenProfile::new(ISSUER, AUDIENCE);
+  profile.add_scope("reademail");
+
+  let token: String = encode(&secret, Some("at+jwt"), &access_token_claims());
+  let decoded: JwtToken = validate(&public, &profile, &token).unwrap();
+  assert_eq!(decoded.claims.aud().unwrap(), [AUDIENCE.to_owned()]);
+  assert_eq!(decoded.claims.custom().unwrap()["client_id"], CLIENT_ID);
+
+  // The media type prefix is optional.
+  let token: String = encode(&secret, Some("application/at+jwt"), &access_token_claims());
+  assert!(validate(&public, &profile, &token).is_ok());
+
+  // Generic JWTs are rejected.
+  let token: String = encode(&secret, Some("JWT"), &access_token_claims());
+  assert_eq!(
+    validate(&public, &profile, &token).unwrap_err(),
+    [ClaimError::InvalidHeader("typ")]
+  );
+
+  // All failures are reported.
+  let mut claims: Value = access_token_claims();
+  claims["aud"] = json!("https://other.example.com");
+  claims["scope"] = json!("openid");
+  claims.as_object_mut().unwrap().remove("client_id");
+  let token: String = encode(&secret, None, &claims);
+  assert_eq!(
+    validate(&public, &profile, &token).unwrap_err(),
+    [
+      ClaimError::MissingHeader("typ"),
+      ClaimError::InvalidClaim("aud"),
+      ClaimError::MissingClaim("client_id"),
+      ClaimError::InvalidClaim("scope"),
+    ]
+  );
+}
+
+#[test]
+fn test_leeway() {
+  let secret: Jwk = Jwk::random(JwsAlgorithm::EdDSA).unwrap();
+  let public: Jwk = secret.to_public();
+
+  let mut claims: Value = access_token_claims();
+  claims["exp"] = json!(now() - 30);
+  let token: String = encode(&secret, Some("at+jwt"), &claims);
+
+  let mut profile: AccessTokenProfile = AccessTokenProfile::new(ISSUER, AUDIENCE);
+  assert_eq!(validate(&public, &profile, &token).unwrap_err(), [ClaimError::Expired]);
+
+  let mut timecop: TimeCop = TimeCop::new();
+  timecop.set_leeway(Duration::from_secs(60));
+  profile.set_timecop(timecop);
+  assert!(validate(&public, &profile, &token).is_ok());
+}
+
+#[test]
+fn test_id_token_profile() {
+  let secret: Jwk = Jwk::random(JwsAlgorithm::EdDSA).unwrap();
+  let public: Jwk = secret.to_public();
+
+  let access_token: &str = "jHkWEdUXMU1BwAsC4vtUsZwnNvTIxEl0z9K3vx5KF0Y";
+  let claims: Value = json!({
+    "iss": ISSUER,
+    "aud": [CLIENT_ID, "other-client"],
+    "azp": CLIENT_ID,
+    "sub": "24400320",
+    "exp": now() + 60,
+    "iat": now(),
+    "auth_time": now() - 10,
+    "nonce": "n-0S6_WzA2Mj",
+    "at_hash": token_hash(JwsAlgorithm::EdDSA, access_token).unwrap(),
+  });
+
+  let mut profile: IdTokenProfile = IdTokenProfile::new(ISSUER, CLIENT_ID);
+  profile.set_nonce("n-0S6_WzA2Mj");
+  profile.set_access_token(access_token);
+  profile.set_max_age(Duration::from_secs(60));
+
+  let token: String = encode(&secret, None, &claims);
+  assert!(validate(&public, &profile, &token).is_ok());
+
+  let mut invalid: Value = claims.clone();
+  invalid.as_object_mut().unwrap().remove("azp");
+  invalid["nonce"] = json!("replayed");
+  invalid["auth_time"] = json!(now() - 3600);
+  let token: String = encode(&secret, None, &invalid);
+
+  profile.set_access_token("another access token");
+  assert_eq!(
+    validate(&public, &profile, &token).unwrap_err(),
+    [
+      ClaimError::MissingClaim("azp"),
+      ClaimError::InvalidClaim("nonce"),
+      ClaimError::InvalidClaim("at_hash"),
+      ClaimError::AuthenticationExpired,
+    ]
+  );
+}
+
+#[test]
+fn test_token_hash() {
+  // https://openid.net/specs/openid-connect-core-1_0.html#code-id_tokenExample
+  assert_eq!(
+    token_hash(JwsAlgorithm::RS256, "jHkWEdUXMU1BwAsC4vtUsZwnNvTIxEl0z9K3vx5KF0Y").unwrap(),
+    "77QmUPtjPfzWtF2AnpK9RQ"
+  );
+  assert!(token_hash(JwsAlgorithm::NONE, "token").is_none());
+}
+
+#[test]
+fn test_vc_jwt_profile() {
+  let secret: Jwk = Jwk::random(JwsAlgorithm::EdDSA).unwrap();
+  let public: Jwk = secret.to_public();
+
+  let credential: Value = json!({
+    "iss": "did:example:issuer",
+    "sub": "did:example:subject",
+    "nbf": now(),
+    "vc": {
+      "@context": ["https://www.w3.org/2018/credentials/v1"],
+      "type": ["VerifiableCredential"],
+      "credentialSubject": { "degree": "Bachelor" },
+    },
+  });
+
+  let token: String = encode(&secret, Some("JWT"), &credential);
+  assert!(validate(&public, VcJwtProfile::credential(), &token).is_ok());
+  assert_eq!(
+    validate(&public, VcJwtProfile::presentation(), &token).unwrap_err(),
+    [ClaimError::MissingClaim("vp")]
+  );
+
+  let presentation: Value = json!({
+    "iss": "did:example:holder",
+    "aud": "did:example:verifier",
+    "nonce": "343s$FSFDa-",
+    "vp": {
+      "@context": ["https://www.w3.org/2018/credentials/v1"],
+      "type": ["VerifiablePresentation"],
+      "verifiableCredential": [token],
+    },
+  });
+
+  let mut profile: VcJwtProfile = VcJwtProfile::presentation();
+  profile.set_aud("did:example:verifier".to_owned());
+  profile.set_nonce("343s$FSFDa-");
+
+  let token: String = encode(&secret, Some("vp+ld+jwt"), &presentation);
+  assert!(validate(&public, &profile, &token).is_ok());
+
+  let token: String = encode(&secret, Some("vc+ld+jwt"), &presentation);
+  assert_eq!(
+    validate(&public, &profile, &token).unwrap_err(),
+    [ClaimError::InvalidHeader("typ")]
+  );
+}